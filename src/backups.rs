@@ -0,0 +1,251 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Rotating backups of saved pools and projects. Every save also writes a timestamped copy to a
+//! `.backups` folder next to the saved file, of which only the newest ones are kept. Bad saves
+//! and bulk edits that are discovered days later can be restored from them.
+
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::pool_recovery::recover_pool;
+use crate::{ChangeCategory, EditorProject, ObjectInfo};
+
+/// Folder next to the saved file with the backups
+pub const BACKUP_DIR: &str = ".backups";
+
+/// Number of backups that are kept of every saved file
+pub const DEFAULT_BACKUP_COUNT: usize = 20;
+
+/// Length of the timestamps in the file names, e.g. 20240131-154500-123
+const TIMESTAMP_LENGTH: usize = 19;
+
+/// A timestamped copy of a saved pool or project
+#[derive(Debug, Clone, PartialEq)]
+pub struct Backup {
+    pub path: PathBuf,
+
+    /// Name of the saved file without the extension
+    pub stem: String,
+    pub extension: String,
+
+    /// UTC time of the save in the file name, sorts from old to new
+    pub timestamp: String,
+    pub size: u64,
+}
+
+impl Backup {
+    /// Whether the backup is a project, otherwise it is a pool
+    pub fn is_project(&self) -> bool {
+        self.extension == "aitp"
+    }
+
+    /// The time of the save, e.g. 2024-01-31 15:45:00 UTC
+    pub fn saved_at(&self) -> String {
+        let t = &self.timestamp;
+        format!(
+            "{}-{}-{} {}:{}:{} UTC",
+            &t[0..4],
+            &t[4..6],
+            &t[6..8],
+            &t[9..11],
+            &t[11..13],
+            &t[13..15]
+        )
+    }
+
+    pub fn file_name(&self) -> String {
+        format!("{}.{}", self.stem, self.extension)
+    }
+}
+
+/// UTC timestamp for a backup file name, with milliseconds so quick saves get their own backup
+fn timestamp(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+
+    // Days since 1970-01-01 to a date in the proleptic Gregorian calendar
+    let days = seconds / 86_400 + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{:04}{:02}{:02}-{:02}{:02}{:02}-{:03}",
+        year,
+        month,
+        day,
+        seconds / 3_600 % 24,
+        seconds / 60 % 60,
+        seconds % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// The backup folder of a saved file
+pub fn backup_dir(saved: &Path) -> PathBuf {
+    saved
+        .parent()
+        .unwrap_or_else(|| Path::new("."))
+        .join(BACKUP_DIR)
+}
+
+/// Parse a backup file name, `<stem>.<timestamp>.<extension>`
+fn parse_backup(path: &Path) -> Option<Backup> {
+    let name = path.file_name()?.to_str()?;
+    let (rest, extension) = name.rsplit_once('.')?;
+    let (stem, timestamp) = rest.rsplit_once('.')?;
+    let valid = timestamp.len() == TIMESTAMP_LENGTH
+        && timestamp.char_indices().all(|(i, c)| {
+            if i == 8 || i == 15 {
+                c == '-'
+            } else {
+                c.is_ascii_digit()
+            }
+        });
+    if !valid || stem.is_empty() {
+        return None;
+    }
+    Some(Backup {
+        path: path.to_path_buf(),
+        stem: stem.to_string(),
+        extension: extension.to_string(),
+        timestamp: timestamp.to_string(),
+        size: std::fs::metadata(path).map_or(0, |m| m.len()),
+    })
+}
+
+/// The backups in the folder, newest first
+pub fn list_backups(dir: &Path) -> Vec<Backup> {
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    let mut backups: Vec<Backup> = entries
+        .filter_map(|entry| entry.ok())
+        .filter_map(|entry| parse_backup(&entry.path()))
+        .collect();
+    backups.sort_by(|a, b| b.timestamp.cmp(&a.timestamp));
+    backups
+}
+
+/// Write backups of a save, the saved file itself and e.g. the project of an exported pool, each
+/// as contents and extension. Only the newest `keep` backups of each are kept, 0 writes none.
+pub fn back_up_save(saved: &Path, files: &[(&str, &[u8])], keep: usize) -> Result<(), String> {
+    if keep == 0 {
+        return Ok(());
+    }
+    let stem = saved
+        .file_stem()
+        .map(|stem| stem.to_string_lossy().to_string())
+        .ok_or_else(|| format!("{} has no file name", saved.display()))?;
+    let dir = backup_dir(saved);
+    std::fs::create_dir_all(&dir)
+        .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    let time = timestamp(SystemTime::now());
+    for (extension, contents) in files {
+        let path = dir.join(format!("{}.{}.{}", stem, time, extension));
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    }
+
+    // Rotate, the list is sorted from new to old
+    for (extension, _) in files {
+        let outdated = list_backups(&dir)
+            .into_iter()
+            .filter(|backup| backup.stem == stem && backup.extension == *extension)
+            .skip(keep);
+        for backup in outdated {
+            std::fs::remove_file(&backup.path)
+                .map_err(|e| format!("Failed to remove {}: {}", backup.path.display(), e))?;
+        }
+    }
+    Ok(())
+}
+
+/// Replace the pool with the one of the backup, recorded as a single change so it can be undone.
+/// Restoring a project also restores the names and other metadata of its objects, the history
+/// only holds pools so they stay when the change is undone. A damaged pool is not restored.
+pub fn restore_backup(project: &EditorProject, backup: &Backup) -> Result<(), String> {
+    let content = std::fs::read(&backup.path)
+        .map_err(|e| format!("Failed to read {}: {}", backup.path.display(), e))?;
+    let (pool, restored_info) = if backup.is_project() {
+        let restored = EditorProject::load_project(content)?;
+        let info = restored.object_info.borrow().clone();
+        (restored.get_pool().clone(), Some(info))
+    } else {
+        let (pool, report) = recover_pool(&content);
+        if !report.is_clean() {
+            return Err(format!(
+                "The backup {} is damaged: {} bytes in {} regions could not be read",
+                backup.file_name(),
+                report.skipped_bytes(),
+                report.skipped_regions.len()
+            ));
+        }
+        (pool, None)
+    };
+    if pool.objects().is_empty() {
+        return Err(format!("The backup {} has no objects", backup.file_name()));
+    }
+
+    // The metadata is only restored once the pool is accepted
+    let mut object_info = project.object_info.borrow_mut();
+    object_info.extend(restored_info.into_iter().flatten());
+    for object in pool.objects() {
+        object_info
+            .entry(object.id())
+            .or_insert_with(|| ObjectInfo::new(object));
+    }
+    drop(object_info);
+    for object in pool.objects() {
+        project.apply_smart_naming_to_object(object);
+    }
+
+    *project.get_mut_pool().borrow_mut() = pool;
+    project.describe_change(
+        format!(
+            "Restore the backup of {} from {}",
+            backup.file_name(),
+            backup.saved_at()
+        ),
+        ChangeCategory::BulkChange,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    fn backup_names_are_parsed() {
+        let backup = parse_backup(Path::new("pool.v2.20240131-154500-123.aitp")).unwrap();
+        assert_eq!(backup.stem, "pool.v2");
+        assert_eq!(backup.file_name(), "pool.v2.aitp");
+        assert!(backup.is_project());
+        assert_eq!(backup.saved_at(), "2024-01-31 15:45:00 UTC");
+
+        assert!(parse_backup(Path::new("pool.iop")).is_none());
+        assert!(parse_backup(Path::new("pool.20240131_154500_123.iop")).is_none());
+        assert!(parse_backup(Path::new(".20240131-154500-123.iop")).is_none());
+    }
+
+    #[test]
+    fn timestamps_are_utc_dates() {
+        let time = UNIX_EPOCH + Duration::from_millis(1_706_715_900_123);
+        assert_eq!(timestamp(time), "20240131-154500-123");
+        assert_eq!(timestamp(UNIX_EPOCH), "19700101-000000-000");
+    }
+}
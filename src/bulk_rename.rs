@@ -0,0 +1,192 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Renaming many objects at once with a pattern like `btn_{mask}_{n}`. Placeholders are `{n}` for
+//! a counter, `{n:3}` for a counter padded to three digits, `{id}`, `{type}`, `{name}` for the
+//! current name, `{mask}` for the mask the object is shown on and `{parent}` for the object that
+//! references it. Objects without a mask or parent get an empty text for those.
+
+use std::collections::HashMap;
+
+use ag_iso_stack::object_pool::{ObjectId, ObjectType};
+
+use crate::reference_paths::{parent_map, subtree};
+use crate::smart_naming::get_object_type_name;
+use crate::{EditorProject, ObjectInfo};
+
+/// A new name for an object
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rename {
+    pub id: ObjectId,
+    pub old_name: String,
+    pub new_name: String,
+}
+
+/// Name of an object in the C header, e.g. "Speed (km/h)" becomes "SPEED__KM_H_"
+pub fn c_identifier(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            'a'..='z' | 'A'..='Z' | '0'..='9' => c.to_ascii_uppercase(),
+            _ => '_',
+        })
+        .collect()
+}
+
+/// Fill in the placeholders of the pattern, `field` gives the text of a placeholder
+fn expand_pattern(pattern: &str, field: impl Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut name = String::new();
+    let mut rest = pattern;
+    while let Some(start) = rest.find('{') {
+        name.push_str(&rest[..start]);
+        let end = rest[start..]
+            .find('}')
+            .ok_or_else(|| format!("Missing '}}' after '{}'", &rest[start..]))?;
+        let placeholder = &rest[start + 1..start + end];
+        name.push_str(
+            &field(placeholder)
+                .ok_or_else(|| format!("Unknown placeholder {{{}}}", placeholder))?,
+        );
+        rest = &rest[start + end + 1..];
+    }
+    name.push_str(rest);
+    Ok(name)
+}
+
+/// The new names of `targets` in their order, the counter starts at `start`. Locked objects keep
+/// their name.
+pub fn plan_renames(
+    project: &EditorProject,
+    targets: &[ObjectId],
+    pattern: &str,
+    start: u32,
+) -> Result<Vec<Rename>, String> {
+    if pattern.trim().is_empty() {
+        return Err("The pattern is empty".to_string());
+    }
+    let pool = project.get_pool();
+    let name_of = |id: ObjectId| {
+        pool.object_by_id(id)
+            .map(|object| project.get_object_info(object).get_name(object))
+            .unwrap_or_default()
+    };
+    let mut masks: HashMap<ObjectId, ObjectId> = HashMap::new();
+    for mask in pool.objects_by_types(&[ObjectType::DataMask, ObjectType::AlarmMask]) {
+        for id in subtree(pool, mask.id()) {
+            masks.entry(id).or_insert(mask.id());
+        }
+    }
+    let parents = parent_map(pool);
+
+    let mut renames = Vec::new();
+    let mut next_counter = Some(start);
+    for id in targets.iter().filter(|id| !project.is_locked(**id)) {
+        let Some(object) = pool.object_by_id(*id) else {
+            continue;
+        };
+        let counter = next_counter.ok_or("The counter is too large for the number of objects")?;
+        let old_name = name_of(*id);
+        let new_name = expand_pattern(pattern, |placeholder| match placeholder {
+            "n" => Some(counter.to_string()),
+            "id" => Some(id.value().to_string()),
+            "type" => Some(
+                get_object_type_name(object.object_type())
+                    .to_lowercase()
+                    .replace(' ', "_"),
+            ),
+            "name" => Some(old_name.clone()),
+            "mask" => Some(masks.get(id).map(|mask| name_of(*mask)).unwrap_or_default()),
+            "parent" => Some(
+                parents
+                    .get(id)
+                    .and_then(|parents| parents.first())
+                    .map(|parent| name_of(*parent))
+                    .unwrap_or_default(),
+            ),
+            _ => {
+                let width = placeholder.strip_prefix("n:")?.parse().ok()?;
+                Some(format!("{:0width$}", counter, width = width))
+            }
+        })?;
+        next_counter = counter.checked_add(1);
+        renames.push(Rename {
+            id: *id,
+            old_name,
+            new_name,
+        });
+    }
+    Ok(renames)
+}
+
+/// Names in the exported C header that more than one object would have after the renames
+pub fn header_conflicts(project: &EditorProject, renames: &[Rename]) -> Vec<(String, usize)> {
+    let new_names: HashMap<ObjectId, &str> = renames
+        .iter()
+        .map(|rename| (rename.id, rename.new_name.as_str()))
+        .collect();
+    let mut identifiers: HashMap<String, usize> = HashMap::new();
+    for object in project.get_pool().objects() {
+        let name = match new_names.get(&object.id()) {
+            Some(name) => name.to_string(),
+            None => project.get_object_info(object).get_name(object),
+        };
+        *identifiers.entry(c_identifier(&name)).or_default() += 1;
+    }
+    let mut conflicts: Vec<(String, usize)> = identifiers
+        .into_iter()
+        .filter(|(identifier, count)| {
+            *count > 1 && new_names.values().any(|n| c_identifier(n) == *identifier)
+        })
+        .collect();
+    conflicts.sort();
+    conflicts
+}
+
+/// Give the objects their new names. Returns the number of renamed objects
+pub fn apply_renames(project: &EditorProject, renames: &[Rename]) -> usize {
+    let pool = project.get_pool();
+    let mut object_info = project.object_info.borrow_mut();
+    let mut renamed = 0;
+    for rename in renames.iter().filter(|r| r.new_name != r.old_name) {
+        if let Some(object) = pool.object_by_id(rename.id) {
+            object_info
+                .entry(rename.id)
+                .or_insert_with(|| ObjectInfo::new(object))
+                .set_name(rename.new_name.clone());
+            renamed += 1;
+        }
+    }
+    renamed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn field(placeholder: &str) -> Option<String> {
+        match placeholder {
+            "type" => Some("Button".to_string()),
+            "n" => Some("3".to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn placeholders_are_expanded() {
+        assert_eq!(expand_pattern("{type}_{n}", field), Ok("Button_3".into()));
+        assert_eq!(expand_pattern("Plain", field), Ok("Plain".into()));
+        assert_eq!(c_identifier("Speed (km/h)"), "SPEED__KM_H_");
+    }
+
+    #[test]
+    fn invalid_patterns_are_rejected() {
+        assert_eq!(
+            expand_pattern("{size}", field),
+            Err("Unknown placeholder {size}".to_string())
+        );
+        assert_eq!(
+            expand_pattern("Key {n", field),
+            Err("Missing '}' after '{n'".to_string())
+        );
+    }
+}
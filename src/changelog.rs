@@ -0,0 +1,172 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use ag_iso_stack::object_pool::ObjectPool;
+
+use crate::pool_diff::{diff_pools, ObjectChange, ObjectDiff};
+use crate::EditorProject;
+
+/// Longest attribute value that is written to a Markdown changelog
+const MAX_MARKDOWN_VALUE_LENGTH: usize = 60;
+
+/// Output format of a changelog
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangelogFormat {
+    Markdown,
+    Csv,
+}
+
+impl ChangelogFormat {
+    /// File extension, without the dot
+    pub fn extension(&self) -> &'static str {
+        match self {
+            ChangelogFormat::Markdown => "md",
+            ChangelogFormat::Csv => "csv",
+        }
+    }
+}
+
+/// Generate a changelog of the current pool of the project compared to a baseline pool
+pub fn generate_changelog(
+    project: &EditorProject,
+    baseline: &ObjectPool,
+    format: ChangelogFormat,
+) -> String {
+    let diffs = diff_pools(baseline, project.get_pool());
+    match format {
+        ChangelogFormat::Markdown => markdown_changelog(project, &diffs),
+        ChangelogFormat::Csv => csv_changelog(project, &diffs),
+    }
+}
+
+/// Custom name of an object in the project, if it has one
+fn object_name(project: &EditorProject, diff: &ObjectDiff) -> Option<String> {
+    project
+        .object_info
+        .borrow()
+        .get(&diff.id)
+        .and_then(|info| info.name.clone())
+}
+
+/// E.g. "AlarmMask 0x1F00 'Low pressure'"
+pub fn object_label(project: &EditorProject, diff: &ObjectDiff) -> String {
+    let mut label = format!("{:?} 0x{:04X}", diff.object_type, diff.id.value());
+    if let Some(name) = object_name(project, diff) {
+        label.push_str(&format!(" '{}'", name));
+    }
+    label
+}
+
+fn shorten(value: &str) -> String {
+    if value.chars().count() > MAX_MARKDOWN_VALUE_LENGTH {
+        let short: String = value.chars().take(MAX_MARKDOWN_VALUE_LENGTH).collect();
+        format!("{}...", short)
+    } else {
+        value.to_string()
+    }
+}
+
+fn markdown_changelog(project: &EditorProject, diffs: &[ObjectDiff]) -> String {
+    let mut added = Vec::new();
+    let mut modified = Vec::new();
+    let mut removed = Vec::new();
+
+    for diff in diffs {
+        let label = object_label(project, diff);
+        match &diff.change {
+            ObjectChange::Added => added.push(format!("- Added {}", label)),
+            ObjectChange::Removed => removed.push(format!("- Removed {}", label)),
+            ObjectChange::Modified(attributes) => {
+                let names: Vec<&str> = attributes.iter().map(|a| a.attribute.as_str()).collect();
+                modified.push(format!("- Modified {}: {}", label, names.join(", ")));
+                for attribute in attributes {
+                    modified.push(format!(
+                        "  - {}: `{}` \u{2192} `{}`",
+                        attribute.attribute,
+                        shorten(&attribute.old_value),
+                        shorten(&attribute.new_value)
+                    ));
+                }
+            }
+        }
+    }
+
+    let mut text = String::from("# Changelog\n\n");
+    if !project.authors().is_empty() {
+        text.push_str(&format!("Authors: {}\n\n", project.authors().join(", ")));
+    }
+    if diffs.is_empty() {
+        text.push_str("No changes.\n");
+    }
+    let history: Vec<String> = project
+        .undo_history()
+        .iter()
+        .map(|change| {
+            let mut line = format!("- {}: {}", change.category.label(), change.description);
+            if !change.author.is_empty() {
+                line.push_str(&format!(" ({})", change.author));
+            }
+            line
+        })
+        .collect();
+    for (title, lines) in [
+        ("Added", added),
+        ("Modified", modified),
+        ("Removed", removed),
+        ("History", history),
+    ] {
+        if !lines.is_empty() {
+            text.push_str(&format!("## {}\n\n{}\n\n", title, lines.join("\n")));
+        }
+    }
+    text
+}
+
+/// Quote a CSV field if needed
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+fn csv_changelog(project: &EditorProject, diffs: &[ObjectDiff]) -> String {
+    let mut text =
+        String::from("change,object_type,object_id,name,attribute,old_value,new_value\n");
+    for diff in diffs {
+        let object_type = format!("{:?}", diff.object_type);
+        let object_id = format!("0x{:04X}", diff.id.value());
+        let name = object_name(project, diff).unwrap_or_default();
+        let mut row = |change: &str, attribute: &str, old_value: &str, new_value: &str| {
+            let fields = [
+                change,
+                &object_type,
+                &object_id,
+                &name,
+                attribute,
+                old_value,
+                new_value,
+            ];
+            let fields: Vec<String> = fields.iter().map(|f| csv_field(f)).collect();
+            text.push_str(&fields.join(","));
+            text.push('\n');
+        };
+        match &diff.change {
+            ObjectChange::Added => row("Added", "", "", ""),
+            ObjectChange::Removed => row("Removed", "", "", ""),
+            ObjectChange::Modified(attributes) => {
+                for attribute in attributes {
+                    row(
+                        "Modified",
+                        &attribute.attribute,
+                        &attribute.old_value,
+                        &attribute.new_value,
+                    );
+                }
+            }
+        }
+    }
+    text
+}
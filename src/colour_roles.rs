@@ -0,0 +1,135 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::BTreeMap;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+use serde::{Deserialize, Serialize};
+
+/// A theme maps every semantic colour role to a palette index
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ColourTheme {
+    pub name: String,
+
+    /// Palette index for each role, by role name
+    pub colours: BTreeMap<String, u8>,
+}
+
+/// Semantic colour roles of a project, e.g. "Primary background" or "Warning",
+/// together with the themes that give each role an actual colour
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct ColourRoles {
+    pub roles: Vec<String>,
+    pub themes: Vec<ColourTheme>,
+}
+
+impl Default for ColourRoles {
+    fn default() -> Self {
+        let roles = vec![
+            "Primary background".to_string(),
+            "Accent".to_string(),
+            "Warning".to_string(),
+        ];
+        // Standard VT palette: white, blue and red
+        let colours = roles.iter().cloned().zip([1, 9, 12]).collect();
+        ColourRoles {
+            roles,
+            themes: vec![ColourTheme {
+                name: "Default".to_string(),
+                colours,
+            }],
+        }
+    }
+}
+
+impl ColourRoles {
+    /// Add a new role, giving it colour 0 in all themes
+    pub fn add_role(&mut self, name: String) {
+        if name.is_empty() || self.roles.contains(&name) {
+            return;
+        }
+        for theme in &mut self.themes {
+            theme.colours.insert(name.clone(), 0);
+        }
+        self.roles.push(name);
+    }
+
+    /// Remove a role from the project and all themes
+    pub fn remove_role(&mut self, name: &str) {
+        self.roles.retain(|r| r != name);
+        for theme in &mut self.themes {
+            theme.colours.remove(name);
+        }
+    }
+}
+
+/// Get the colour attributes of an object, by attribute name
+pub fn colour_attributes(object: &Object) -> Vec<(&'static str, u8)> {
+    let mut object = object.clone();
+    colour_attributes_mut(&mut object)
+        .into_iter()
+        .map(|(name, value)| (name, *value))
+        .collect()
+}
+
+/// Every palette index used by a colour attribute, with the objects and attributes using it
+pub fn colour_usage(pool: &ObjectPool) -> BTreeMap<u8, Vec<(ObjectId, &'static str)>> {
+    let mut usage: BTreeMap<u8, Vec<(ObjectId, &'static str)>> = BTreeMap::new();
+    for object in pool.objects() {
+        for (attribute, index) in colour_attributes(object) {
+            usage
+                .entry(index)
+                .or_default()
+                .push((object.id(), attribute));
+        }
+    }
+    usage
+}
+
+/// Get mutable references to the colour attributes of an object, by attribute name
+pub fn colour_attributes_mut(object: &mut Object) -> Vec<(&'static str, &mut u8)> {
+    match object {
+        Object::WorkingSet(o) => vec![("Background", &mut o.background_colour)],
+        Object::DataMask(o) => vec![("Background", &mut o.background_colour)],
+        Object::AlarmMask(o) => vec![("Background", &mut o.background_colour)],
+        Object::SoftKeyMask(o) => vec![("Background", &mut o.background_colour)],
+        Object::Key(o) => vec![("Background", &mut o.background_colour)],
+        Object::Button(o) => vec![
+            ("Background", &mut o.background_colour),
+            ("Border", &mut o.border_colour),
+        ],
+        Object::InputBoolean(o) => vec![("Background", &mut o.background_colour)],
+        Object::InputString(o) => vec![("Background", &mut o.background_colour)],
+        Object::InputNumber(o) => vec![("Background", &mut o.background_colour)],
+        Object::OutputString(o) => vec![("Background", &mut o.background_colour)],
+        Object::OutputNumber(o) => vec![("Background", &mut o.background_colour)],
+        Object::OutputMeter(o) => vec![
+            ("Needle", &mut o.needle_colour),
+            ("Border", &mut o.border_colour),
+            ("Arc and ticks", &mut o.arc_and_tick_colour),
+        ],
+        Object::OutputLinearBarGraph(o) => vec![
+            ("Bar", &mut o.colour),
+            ("Target line", &mut o.target_line_colour),
+        ],
+        Object::OutputArchedBarGraph(o) => vec![
+            ("Bar", &mut o.colour),
+            ("Target line", &mut o.target_line_colour),
+        ],
+        Object::FontAttributes(o) => vec![("Font", &mut o.font_colour)],
+        Object::LineAttributes(o) => vec![("Line", &mut o.line_colour)],
+        Object::FillAttributes(o) => vec![("Fill", &mut o.fill_colour)],
+        Object::AuxiliaryFunctionType1(o) => vec![("Background", &mut o.background_colour)],
+        Object::AuxiliaryInputType1(o) => vec![("Background", &mut o.background_colour)],
+        Object::AuxiliaryFunctionType2(o) => vec![("Background", &mut o.background_colour)],
+        Object::AuxiliaryInputType2(o) => vec![("Background", &mut o.background_colour)],
+        Object::WindowMask(o) => vec![("Background", &mut o.background_colour)],
+        Object::GraphicsContext(o) => vec![
+            ("Foreground", &mut o.foreground_colour),
+            ("Background", &mut o.background_colour),
+        ],
+        _ => vec![],
+    }
+}
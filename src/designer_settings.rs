@@ -0,0 +1,372 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Settings of the designer itself, which apply to every project: terminal profiles for new
+//! pools, key bindings, theme, backups and the default export options. They are stored as TOML in
+//! the configuration directory of the user, and a settings file can be exported and imported so a
+//! team can share a common configuration.
+
+use std::collections::BTreeSet;
+#[cfg(not(target_arch = "wasm32"))]
+use std::path::{Path, PathBuf};
+
+use eframe::egui::{self, Key, KeyboardShortcut, Modifiers};
+use serde::{Deserialize, Serialize};
+
+use crate::{Language, MinifyOptions, TerminalProfile, UiTheme};
+
+/// Environment variable with the path of the settings file, instead of the one in the
+/// configuration directory
+pub const SETTINGS_PATH_ENV: &str = "AG_ISO_TERMINAL_DESIGNER_SETTINGS";
+
+/// File name of the settings in the configuration directory
+pub const SETTINGS_FILE: &str = "settings.toml";
+
+/// Settings file format version
+const SETTINGS_FILE_VERSION: u32 = 1;
+
+/// Shortcuts of the designer actions, stored as text like "Ctrl+Alt+Z"
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct KeyBindings {
+    #[serde(with = "shortcut_text")]
+    pub undo: KeyboardShortcut,
+    #[serde(with = "shortcut_text")]
+    pub redo: KeyboardShortcut,
+
+    /// Undo the last change within the active mask
+    #[serde(with = "shortcut_text")]
+    pub scoped_undo: KeyboardShortcut,
+    #[serde(with = "shortcut_text")]
+    pub select_previous: KeyboardShortcut,
+    #[serde(with = "shortcut_text")]
+    pub select_next: KeyboardShortcut,
+
+    /// Focus the object filter
+    #[serde(with = "shortcut_text")]
+    pub find: KeyboardShortcut,
+    #[serde(with = "shortcut_text")]
+    pub run_script: KeyboardShortcut,
+
+    /// Save the workspace while one is open, the project otherwise
+    #[serde(with = "shortcut_text")]
+    pub save: KeyboardShortcut,
+}
+
+impl Default for KeyBindings {
+    fn default() -> Self {
+        KeyBindings {
+            undo: KeyboardShortcut::new(Modifiers::CTRL, Key::Z),
+            redo: KeyboardShortcut::new(Modifiers::CTRL, Key::Y),
+            scoped_undo: KeyboardShortcut::new(Modifiers::CTRL | Modifiers::ALT, Key::Z),
+            select_previous: KeyboardShortcut::new(Modifiers::ALT, Key::ArrowLeft),
+            select_next: KeyboardShortcut::new(Modifiers::ALT, Key::ArrowRight),
+            find: KeyboardShortcut::new(Modifiers::CTRL, Key::F),
+            run_script: KeyboardShortcut::new(Modifiers::CTRL, Key::Enter),
+            save: KeyboardShortcut::new(Modifiers::CTRL, Key::S),
+        }
+    }
+}
+
+impl KeyBindings {
+    /// The shortcuts with a human readable name of their action
+    pub fn actions_mut(&mut self) -> [(&'static str, &mut KeyboardShortcut); 8] {
+        [
+            ("Undo", &mut self.undo),
+            ("Redo", &mut self.redo),
+            ("Undo within the active mask", &mut self.scoped_undo),
+            ("Select previous object", &mut self.select_previous),
+            ("Select next object", &mut self.select_next),
+            ("Find object", &mut self.find),
+            ("Run script", &mut self.run_script),
+            ("Save", &mut self.save),
+        ]
+    }
+
+    /// Pairs of actions that have the same shortcut
+    pub fn conflicts(&self) -> Vec<(&'static str, &'static str)> {
+        let mut copy = self.clone();
+        let actions = copy.actions_mut().map(|(name, shortcut)| (name, *shortcut));
+        let mut conflicts = Vec::new();
+        for (index, (name, shortcut)) in actions.iter().enumerate() {
+            if let Some((earlier, _)) = actions[..index].iter().find(|(_, s)| s == shortcut) {
+                conflicts.push((*earlier, *name));
+            }
+        }
+        conflicts
+    }
+}
+
+/// Text of a shortcut as stored in the settings, the same on every platform
+pub fn shortcut_to_text(shortcut: &KeyboardShortcut) -> String {
+    let modifiers = shortcut.modifiers;
+    let mut parts = Vec::new();
+    for (pressed, name) in [
+        (modifiers.command && !modifiers.ctrl, "Command"),
+        (modifiers.ctrl, "Ctrl"),
+        (modifiers.mac_cmd, "Cmd"),
+        (modifiers.alt, "Alt"),
+        (modifiers.shift, "Shift"),
+    ] {
+        if pressed {
+            parts.push(name);
+        }
+    }
+    parts.push(shortcut.logical_key.name());
+    parts.join("+")
+}
+
+/// Parse a shortcut like "Ctrl+Shift+Z"
+pub fn shortcut_from_text(text: &str) -> Result<KeyboardShortcut, String> {
+    let text = text.trim();
+    // The plus key is written as "+" as well
+    let (modifier_text, key_name) = match text.strip_suffix("++") {
+        Some(modifiers) => (modifiers, "+"),
+        None => text.rsplit_once('+').unwrap_or(("", text)),
+    };
+    let key = Key::from_name(key_name).ok_or_else(|| format!("Unknown key '{}'", key_name))?;
+    let mut modifiers = Modifiers::NONE;
+    for name in modifier_text.split('+').filter(|name| !name.is_empty()) {
+        modifiers |= match name.trim().to_ascii_lowercase().as_str() {
+            "command" => Modifiers::COMMAND,
+            "ctrl" => Modifiers::CTRL,
+            "cmd" => Modifiers::MAC_CMD,
+            "alt" => Modifiers::ALT,
+            "shift" => Modifiers::SHIFT,
+            _ => return Err(format!("Unknown modifier '{}' in '{}'", name, text)),
+        };
+    }
+    Ok(KeyboardShortcut::new(modifiers, key))
+}
+
+mod shortcut_text {
+    use eframe::egui::KeyboardShortcut;
+    use serde::{Deserialize, Deserializer, Serializer};
+
+    pub fn serialize<S: Serializer>(
+        shortcut: &KeyboardShortcut,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&super::shortcut_to_text(shortcut))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<KeyboardShortcut, D::Error> {
+        let text = String::deserialize(deserializer)?;
+        super::shortcut_from_text(&text).map_err(serde::de::Error::custom)
+    }
+}
+
+/// What the minified export does by default
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+#[serde(default)]
+pub struct ExportSettings {
+    pub remove_unused: bool,
+    pub deduplicate_attributes: bool,
+    pub compress_pictures: bool,
+}
+
+impl Default for ExportSettings {
+    fn default() -> Self {
+        ExportSettings::from(&MinifyOptions::default())
+    }
+}
+
+impl From<&MinifyOptions> for ExportSettings {
+    fn from(options: &MinifyOptions) -> Self {
+        ExportSettings {
+            remove_unused: options.remove_unused,
+            deduplicate_attributes: options.deduplicate_attributes,
+            compress_pictures: options.compress_pictures,
+        }
+    }
+}
+
+impl ExportSettings {
+    /// Use the settings in the minify options, the kept objects stay
+    pub fn apply(&self, options: &mut MinifyOptions) {
+        options.remove_unused = self.remove_unused;
+        options.deduplicate_attributes = self.deduplicate_attributes;
+        options.compress_pictures = self.compress_pictures;
+    }
+}
+
+/// Settings of the designer, the same for every project
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+#[serde(default)]
+pub struct DesignerSettings {
+    version: u32,
+
+    /// Name that is recorded on changes and review comments
+    pub author_name: String,
+
+    /// Language code of the user interface, None follows the system
+    pub language: Option<String>,
+    pub theme: UiTheme,
+    pub ui_scale: f32,
+    pub touch_mode: bool,
+
+    /// Allows editing the raw bytes of objects
+    pub expert_mode: bool,
+    pub mask_thumbnails: bool,
+
+    /// Number of backups kept of every saved file, 0 turns them off
+    pub backup_count: usize,
+    pub apply_smart_naming_on_import: bool,
+    pub export: ExportSettings,
+    pub key_bindings: KeyBindings,
+
+    /// Terminal profiles of imported pools, empty for the built-in default profile. Their custom
+    /// fonts are left out, the font data does not belong in the settings file.
+    pub terminal_profiles: Vec<TerminalProfile>,
+
+    /// Words the spell checker accepts in addition to the dictionaries
+    pub ignored_words: BTreeSet<String>,
+}
+
+impl Default for DesignerSettings {
+    fn default() -> Self {
+        DesignerSettings {
+            version: SETTINGS_FILE_VERSION,
+            author_name: String::new(),
+            language: None,
+            theme: UiTheme::default(),
+            ui_scale: 1.0,
+            touch_mode: false,
+            expert_mode: false,
+            mask_thumbnails: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            backup_count: crate::DEFAULT_BACKUP_COUNT,
+            #[cfg(target_arch = "wasm32")]
+            backup_count: 0,
+            apply_smart_naming_on_import: true,
+            export: ExportSettings::default(),
+            key_bindings: KeyBindings::default(),
+            terminal_profiles: Vec::new(),
+            ignored_words: BTreeSet::new(),
+        }
+    }
+}
+
+impl DesignerSettings {
+    pub fn to_toml(&self) -> Result<String, String> {
+        toml::to_string_pretty(self).map_err(|e| format!("Failed to write the settings: {}", e))
+    }
+
+    /// Parse settings, missing values get their default so older and partial files can be
+    /// shared
+    pub fn from_toml(text: &str) -> Result<Self, String> {
+        let settings: DesignerSettings =
+            toml::from_str(text).map_err(|e| format!("Failed to read the settings: {}", e))?;
+        if settings.version > SETTINGS_FILE_VERSION {
+            return Err(format!(
+                "The settings are of a newer designer (version {})",
+                settings.version
+            ));
+        }
+        Ok(DesignerSettings {
+            version: SETTINGS_FILE_VERSION,
+            terminal_profiles: settings
+                .terminal_profiles
+                .iter()
+                .map(TerminalProfile::without_fonts)
+                .collect(),
+            ..settings
+        })
+    }
+
+    /// The language of the user interface
+    pub fn language(&self) -> Language {
+        self.language
+            .as_deref()
+            .and_then(Language::from_locale)
+            .unwrap_or_else(Language::system)
+    }
+
+    /// Load the settings, the defaults if the file does not exist yet
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn load(path: &Path) -> Result<Self, String> {
+        match std::fs::read_to_string(path) {
+            Ok(text) => DesignerSettings::from_toml(&text),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(DesignerSettings::default()),
+            Err(e) => Err(format!("Failed to read {}: {}", path.display(), e)),
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        if let Some(dir) = path.parent() {
+            std::fs::create_dir_all(dir)
+                .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+        }
+        std::fs::write(path, self.to_toml()?)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))
+    }
+
+    /// Apply the settings of the user interface to the context
+    pub fn apply_to_context(&self, ctx: &egui::Context) {
+        crate::set_language(self.language());
+        self.theme.apply(ctx);
+        ctx.set_zoom_factor(self.ui_scale);
+        crate::set_touch_mode(ctx, self.touch_mode);
+    }
+}
+
+/// Path of the settings file, from [`SETTINGS_PATH_ENV`] or in the configuration directory of the
+/// platform
+#[cfg(not(target_arch = "wasm32"))]
+pub fn default_settings_path() -> Option<PathBuf> {
+    if let Ok(path) = std::env::var(SETTINGS_PATH_ENV) {
+        return Some(PathBuf::from(path));
+    }
+    let home = || std::env::var_os("HOME").map(PathBuf::from);
+    let config_dir = if cfg!(target_os = "windows") {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home().map(|home| home.join("Library").join("Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .or_else(|| home().map(|home| home.join(".config")))
+    };
+    config_dir.map(|dir| dir.join("ag-iso-terminal-designer").join(SETTINGS_FILE))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shortcuts_survive_a_round_trip() {
+        let shortcut = shortcut_from_text(" ctrl+Shift+Z ").unwrap();
+        assert_eq!(
+            shortcut,
+            KeyboardShortcut::new(Modifiers::CTRL | Modifiers::SHIFT, Key::Z)
+        );
+        assert_eq!(shortcut_to_text(&shortcut), "Ctrl+Shift+Z");
+
+        let shortcut = shortcut_from_text("Command++").unwrap();
+        assert_eq!(
+            shortcut,
+            KeyboardShortcut::new(Modifiers::COMMAND, Key::Plus)
+        );
+        assert_eq!(
+            shortcut_from_text(&shortcut_to_text(&shortcut)),
+            Ok(shortcut)
+        );
+    }
+
+    #[test]
+    fn invalid_shortcuts_are_rejected() {
+        assert_eq!(
+            shortcut_from_text("Ctrl+Nothing"),
+            Err("Unknown key 'Nothing'".to_string())
+        );
+        assert_eq!(
+            shortcut_from_text("Hyper+Z"),
+            Err("Unknown modifier 'Hyper' in 'Hyper+Z'".to_string())
+        );
+    }
+}
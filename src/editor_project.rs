@@ -2,29 +2,164 @@
 //! SPDX-License-Identifier: GPL-3.0-or-later
 //! Authors: Daan Steenbergen
 
-use std::{cell::RefCell, collections::HashMap};
+use std::{
+    cell::{RefCell, RefMut},
+    collections::{HashMap, HashSet},
+    sync::atomic::{AtomicU64, Ordering},
+};
 
 use ag_iso_stack::object_pool::{
-    object::Object, NullableObjectId, ObjectId, ObjectPool, ObjectType,
+    object::Object,
+    object_attributes::{Event, MacroRef},
+    NullableObjectId, ObjectId, ObjectPool, ObjectType,
 };
 
-use crate::{project_file::ProjectFile, smart_naming, ObjectInfo};
+use crate::{
+    colour_roles::{colour_attributes_mut, ColourRoles},
+    golden_pool::GoldenPool,
+    history::{describe_pool_change, Change, ChangeCategory, HistoryLimits, StoredHistory},
+    macro_references::macro_refs_mut,
+    object_defaults::default_object,
+    pool_diff::diff_pools,
+    pool_version::PoolVersion,
+    project_file::{ProjectFile, ProjectSettings},
+    project_migration::ProjectFileFormat,
+    scoped_undo::{last_change_within, revert_change},
+    smart_naming,
+    state_scenarios::StateScenario,
+    style_presets::{apply_style_preset_to, StylePreset},
+    terminal_profile::TerminalProfile,
+    validation::ValidationIssue,
+    ObjectInfo,
+};
 
-const MAX_UNDO_REDO_POOL: usize = 10;
 const MAX_UNDO_REDO_SELECTED: usize = 20;
 
+/// Source of the pool generations, shared by all projects so a cache also notices when another
+/// project is opened
+static NEXT_POOL_GENERATION: AtomicU64 = AtomicU64::new(0);
+
+fn next_pool_generation() -> u64 {
+    NEXT_POOL_GENERATION.fetch_add(1, Ordering::Relaxed)
+}
+
+/// How recently an object was modified, shown as marker in the object tree
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DirtyMarker {
+    /// Modified since the last save, but not since the last checkpoint
+    SinceSave,
+    /// Modified since the last checkpoint
+    SinceCheckpoint,
+}
+
+/// Objects that differ from the saved pool and the checkpoint pool
+#[derive(Default, Clone)]
+struct DirtyObjects {
+    since_save: HashSet<ObjectId>,
+    since_checkpoint: HashSet<ObjectId>,
+}
+
+/// Results derived from the pool of one generation, computed when they are first needed
+#[derive(Default, Clone)]
+struct PoolCache {
+    generation: u64,
+
+    /// Size of the pool as IOP file
+    iop_size: Option<usize>,
+
+    /// Whether the pool differs from the saved pool
+    unsaved: Option<bool>,
+
+    /// Issues of the validators that only depend on the pool and the terminal profile, with the
+    /// profile they were found for
+    issues: Option<(Option<TerminalProfile>, Vec<ValidationIssue>)>,
+
+    /// Index of the change `undo_within` would undo, for a scope and length of the undo history
+    undo_within: Option<(ObjectId, usize, Option<usize>)>,
+}
+
+/// Smallest soft key designator a VT may report (ISO 11783-6)
+pub const MIN_SOFT_KEY_SIZE: (u16, u16) = (60, 32);
+
 #[derive(Default, Clone)]
 pub struct EditorProject {
     pool: ObjectPool,
     mut_pool: RefCell<ObjectPool>,
-    undo_pool_history: Vec<ObjectPool>,
-    redo_pool_history: Vec<ObjectPool>,
+
+    /// The pool as it was last loaded or saved, to detect unsaved changes
+    saved_pool: ObjectPool,
+
+    /// The pool when the user last set a checkpoint
+    checkpoint_pool: Option<ObjectPool>,
+
+    /// Cached result of comparing the pool with the saved and checkpoint pools,
+    /// cleared whenever one of them changes
+    dirty_objects: RefCell<Option<DirtyObjects>>,
+
+    /// Changes with every change of the current pool, so caches know when to refresh
+    pool_generation: u64,
+
+    /// Results derived from the current pool that are shown every frame
+    pool_cache: RefCell<PoolCache>,
+    undo_pool_history: Vec<Change>,
+    redo_pool_history: Vec<Change>,
+
+    /// When old history entries are coalesced and dropped
+    pub history_limits: HistoryLimits,
+
+    /// Description of the change that is made to the mutating pool in this frame
+    pending_change: RefCell<Option<(String, ChangeCategory)>>,
+
+    /// The pool before the edit that is in progress, e.g. a slider drag, and its description.
+    /// The edit is shown right away but only recorded in the history when it is finished.
+    live_edit: Option<(ObjectPool, Option<(String, ChangeCategory)>)>,
     selected_object: NullableObjectId,
     mut_selected_object: RefCell<NullableObjectId>,
     undo_selected_history: Vec<NullableObjectId>,
     redo_selected_history: Vec<NullableObjectId>,
     pub mask_size: u16,
-    soft_key_size: (u16, u16),
+
+    /// Width and height of a soft key (and working set) designator of the simulated terminal
+    pub soft_key_size: (u16, u16),
+
+    /// Semantic colour roles and the themes that map them to palette indices
+    pub colour_roles: ColourRoles,
+
+    /// Named sets of attributes that are applied together, like classes in a style sheet
+    pub style_presets: Vec<StylePreset>,
+
+    /// Expressions over number variables shown while simulating, e.g. to check scaling
+    pub watch_expressions: Vec<String>,
+
+    /// Named runtime states of containers and input objects, see the state sandbox
+    pub state_scenarios: Vec<StateScenario>,
+
+    /// Released object IDs and names that must not change
+    pub golden_pool: Option<GoldenPool>,
+
+    /// Objects pinned by the user for quick navigation
+    bookmarks: RefCell<Vec<ObjectId>>,
+
+    /// Terminals the preview can simulate
+    pub terminal_profiles: Vec<TerminalProfile>,
+
+    /// Index of the terminal profile that is used by the preview
+    pub active_profile: usize,
+
+    /// Store the undo history in the project file, so it survives closing the project
+    pub save_history: bool,
+
+    /// Version label terminals store the pool under
+    pub pool_version: PoolVersion,
+
+    /// Format version of the loaded project file and the one it is saved in
+    pub file_format: ProjectFileFormat,
+
+    /// Author that is recorded on new changes
+    author: String,
+
+    /// Everyone who made changes to this project
+    authors: Vec<String>,
     pub object_info: RefCell<HashMap<ObjectId, ObjectInfo>>,
 
     /// Used to keep track of the object that is being renamed
@@ -38,6 +173,18 @@ pub struct EditorProject {
 
     /// Request to open image file dialog for PictureGraphic object
     image_load_request: RefCell<Option<ObjectId>>,
+
+    /// Request to open the duplicate wizard for a mask
+    mask_duplication_request: RefCell<Option<ObjectId>>,
+
+    /// Request to create a new macro executed by an event of an object
+    macro_creation_request: RefCell<Option<(ObjectId, Event)>>,
+
+    /// Request to replace objects other than the one being edited
+    object_update_request: RefCell<Option<Vec<Object>>>,
+
+    /// When set, changes to the pool are discarded (e.g. for a pool recovered from damaged data)
+    read_only: bool,
 }
 
 impl From<ObjectPool> for EditorProject {
@@ -54,20 +201,48 @@ impl From<ObjectPool> for EditorProject {
 
         EditorProject {
             mut_pool: RefCell::new(pool.clone()),
+            saved_pool: pool.clone(),
+            checkpoint_pool: None,
+            dirty_objects: RefCell::new(None),
+            pool_generation: next_pool_generation(),
+            pool_cache: RefCell::new(PoolCache::default()),
             pool,
             undo_pool_history: Default::default(),
             redo_pool_history: Default::default(),
+            history_limits: HistoryLimits::default(),
+            pending_change: RefCell::new(None),
+            live_edit: None,
             selected_object: NullableObjectId::default(),
             mut_selected_object: RefCell::new(NullableObjectId::default()),
             undo_selected_history: Default::default(),
             redo_selected_history: Default::default(),
             mask_size,
-            soft_key_size,
+            soft_key_size: (
+                soft_key_size.0.max(MIN_SOFT_KEY_SIZE.0),
+                soft_key_size.1.max(MIN_SOFT_KEY_SIZE.1),
+            ),
+            colour_roles: ColourRoles::default(),
+            style_presets: Vec::new(),
+            watch_expressions: Vec::new(),
+            state_scenarios: Vec::new(),
+            golden_pool: None,
+            bookmarks: RefCell::new(Vec::new()),
+            terminal_profiles: vec![TerminalProfile::default()],
+            active_profile: 0,
+            save_history: false,
+            pool_version: PoolVersion::default(),
+            file_format: ProjectFileFormat::default(),
+            author: String::new(),
+            authors: Vec::new(),
             object_info: RefCell::new(HashMap::new()),
             renaming_object: RefCell::new(None),
             next_available_id: RefCell::new(max_id.saturating_add(1)),
             default_object_names: RefCell::new(HashMap::new()),
             image_load_request: RefCell::new(None),
+            mask_duplication_request: RefCell::new(None),
+            macro_creation_request: RefCell::new(None),
+            object_update_request: RefCell::new(None),
+            read_only: false,
         }
     }
 }
@@ -151,29 +326,331 @@ impl EditorProject {
     /// and update the current pool with the mutated pool.
     /// Returns true if the pool was updated
     pub fn update_pool(&mut self) -> bool {
-        if self.mut_pool.borrow().to_owned() != self.pool {
+        if self.read_only {
+            if self.mut_pool.borrow().to_owned() != self.pool {
+                self.mut_pool.replace(self.pool.clone());
+            }
+            self.pending_change.replace(None);
+            return false;
+        }
+        self.restore_locked_objects();
+        let changed = self.mut_pool.borrow().to_owned() != self.pool;
+        let live_edit = self.live_edit.take();
+        if !changed && live_edit.is_none() {
+            self.pending_change.replace(None);
+            return false;
+        }
+
+        // A finished live edit is recorded as a single change from the pool before it
+        let (before, pending_change) = match live_edit {
+            Some((before, description)) => (before, description.or(self.pending_change.take())),
+            None => (self.pool.clone(), self.pending_change.take()),
+        };
+        if *self.mut_pool.borrow() != before {
+            let (detected, detected_category) =
+                describe_pool_change(&before, &self.mut_pool.borrow());
+            let (description, category) = match pending_change {
+                // The caller describes the action, what it changed gives a more specific category
+                Some((description, ChangeCategory::Edit)) => (description, detected_category),
+                Some(pending_change) => pending_change,
+                None => (detected, detected_category),
+            };
             self.redo_pool_history.clear();
-            self.undo_pool_history.push(self.pool.clone());
-            if self.undo_pool_history.len() > MAX_UNDO_REDO_POOL {
-                self.undo_pool_history
-                    .drain(..self.undo_pool_history.len() - MAX_UNDO_REDO_POOL);
+            self.undo_pool_history.push(Change::new(
+                before,
+                description,
+                category,
+                self.author.clone(),
+            ));
+            if !self.author.is_empty() && !self.authors.contains(&self.author) {
+                self.authors.push(self.author.clone());
             }
-            self.pool = self.mut_pool.borrow().clone();
-            // Clear the default names cache since objects may have changed
-            self.default_object_names.borrow_mut().clear();
-            return true;
+            self.trim_history();
+        }
+        if changed {
+            self.apply_mut_pool();
+        }
+        changed
+    }
+
+    /// Show the changes to the mutating pool right away, but only record them in the history
+    /// when the edit is finished by [`Self::update_pool`]. This way e.g. all steps of a slider
+    /// drag become a single change.
+    pub fn update_pool_live(&mut self) -> bool {
+        if self.read_only {
+            return self.update_pool();
+        }
+        self.restore_locked_objects();
+        if *self.mut_pool.borrow() == self.pool {
+            self.pending_change.replace(None);
+            return false;
+        }
+        let pending_change = self.pending_change.take();
+        match &mut self.live_edit {
+            Some((_, description)) => {
+                if description.is_none() {
+                    *description = pending_change;
+                }
+            }
+            None => self.live_edit = Some((self.pool.clone(), pending_change)),
+        }
+        self.apply_mut_pool();
+        true
+    }
+
+    /// Check if an edit is shown that is not yet recorded in the history
+    pub fn has_live_edit(&self) -> bool {
+        self.live_edit.is_some()
+    }
+
+    fn apply_mut_pool(&mut self) {
+        self.pool = self.mut_pool.borrow().clone();
+        self.dirty_objects.replace(None);
+        self.pool_generation = next_pool_generation();
+        // Clear the default names cache since objects may have changed
+        self.default_object_names.borrow_mut().clear();
+    }
+
+    /// Approximate memory used by the undo and redo history in bytes
+    pub fn history_memory(&self) -> usize {
+        self.undo_pool_history
+            .iter()
+            .chain(&self.redo_pool_history)
+            .map(|change| change.size)
+            .sum()
+    }
+
+    fn history_exceeds_limits(&self) -> bool {
+        self.history_limits.exceeded_by(
+            self.undo_pool_history.len() + self.redo_pool_history.len(),
+            self.history_memory(),
+        )
+    }
+
+    /// Bring the history within its limits. Repeated changes like the steps of a drag are
+    /// coalesced first, starting with the oldest, before the oldest entries are dropped and then
+    /// the undone changes that are furthest away.
+    pub fn trim_history(&mut self) {
+        let mut index = 1;
+        while self.history_exceeds_limits() && index < self.undo_pool_history.len() {
+            // The last change is trimmed before the mutating pool it led to is applied
+            let history = &self.undo_pool_history;
+            let mut_pool = self.mut_pool.borrow();
+            let after = history.get(index + 1).map_or(&*mut_pool, |next| &next.pool);
+            let repeats = history[index].repeats(after, &history[index - 1]);
+            drop(mut_pool);
+            if repeats {
+                // Undoing the earlier entry restores the pool from before both changes
+                self.undo_pool_history.remove(index);
+            } else {
+                index += 1;
+            }
+        }
+        while self.history_exceeds_limits() && !self.undo_pool_history.is_empty() {
+            self.undo_pool_history.remove(0);
+        }
+        // Undone changes furthest from the current pool go last
+        while self.history_exceeds_limits() && !self.redo_pool_history.is_empty() {
+            self.redo_pool_history.remove(0);
+        }
+    }
+
+    /// Revert any modification or deletion of locked objects in the mutating pool
+    fn restore_locked_objects(&self) {
+        let object_info = self.object_info.borrow();
+        let mut mut_pool = self.mut_pool.borrow_mut();
+        for (id, _) in object_info.iter().filter(|(_, info)| info.locked) {
+            let Some(original) = self.pool.object_by_id(*id) else {
+                continue;
+            };
+            match mut_pool.object_mut_by_id(*id) {
+                Some(object) => {
+                    if object != original {
+                        *object = original.clone();
+                    }
+                }
+                None => mut_pool.add(original.clone()),
+            }
+        }
+    }
+
+    /// Check if an object is locked against modification
+    pub fn is_locked(&self, id: ObjectId) -> bool {
+        self.object_info
+            .borrow()
+            .get(&id)
+            .is_some_and(|info| info.locked)
+    }
+
+    /// Lock or unlock an object
+    pub fn set_locked(&self, object: &Object, locked: bool) {
+        self.object_info
+            .borrow_mut()
+            .entry(object.id())
+            .or_insert_with(|| ObjectInfo::new(object))
+            .locked = locked;
+    }
+
+    /// Describe the change that is made to the mutating pool in this frame.
+    /// The description is stored in the undo history once the pool is updated
+    pub fn describe_change(&self, description: impl Into<String>, category: ChangeCategory) {
+        self.pending_change
+            .replace(Some((description.into(), category)));
+    }
+
+    /// Set the author that is recorded on new changes
+    pub fn set_author(&mut self, author: &str) {
+        if self.author != author {
+            self.author = author.to_string();
+        }
+    }
+
+    /// Everyone who made changes to this project
+    pub fn authors(&self) -> &[String] {
+        &self.authors
+    }
+
+    /// Changes that can be undone, oldest first
+    pub fn undo_history(&self) -> &[Change] {
+        &self.undo_pool_history
+    }
+
+    /// Changes that can be redone, the next redo last
+    pub fn redo_history(&self) -> &[Change] {
+        &self.redo_pool_history
+    }
+
+    /// Check if the project is opened read-only
+    pub fn is_read_only(&self) -> bool {
+        self.read_only
+    }
+
+    /// Allow or disallow changes to the pool
+    pub fn set_read_only(&mut self, read_only: bool) {
+        self.read_only = read_only;
+    }
+
+    /// Changes whenever the current pool changes
+    pub fn pool_generation(&self) -> u64 {
+        self.pool_generation
+    }
+
+    /// The cached results of the current pool generation
+    fn pool_cache(&self) -> RefMut<'_, PoolCache> {
+        let mut cache = self.pool_cache.borrow_mut();
+        if cache.generation != self.pool_generation {
+            *cache = PoolCache {
+                generation: self.pool_generation,
+                ..Default::default()
+            };
+        }
+        cache
+    }
+
+    /// Check if the pool changed since it was last loaded or saved
+    pub fn has_unsaved_changes(&self) -> bool {
+        *self
+            .pool_cache()
+            .unsaved
+            .get_or_insert_with(|| self.pool != self.saved_pool)
+    }
+
+    /// Size of the pool as IOP file in bytes
+    pub fn pool_size(&self) -> usize {
+        *self
+            .pool_cache()
+            .iop_size
+            .get_or_insert_with(|| self.pool.as_iop().len())
+    }
+
+    /// Issues of validators that only depend on the pool and the active terminal profile,
+    /// `validate` only runs when one of them changed
+    pub(crate) fn pool_issues(
+        &self,
+        validate: impl FnOnce() -> Vec<ValidationIssue>,
+    ) -> Vec<ValidationIssue> {
+        let profile = self.terminal_profile();
+        if let Some((cached_profile, issues)) = &self.pool_cache().issues {
+            if cached_profile.as_ref() == profile {
+                return issues.clone();
+            }
+        }
+        let issues = validate();
+        self.pool_cache().issues = Some((profile.cloned(), issues.clone()));
+        issues
+    }
+
+    /// Remember the current pool as saved
+    pub fn mark_saved(&mut self) {
+        self.mark_saved_as(self.pool.clone());
+    }
+
+    /// Remember a pool as saved, e.g. the one written by a save dialog that finished later
+    pub fn mark_saved_as(&mut self, pool: ObjectPool) {
+        self.pool_cache.get_mut().unsaved = Some(self.pool != pool);
+        self.saved_pool = pool;
+        self.dirty_objects.replace(None);
+    }
+
+    /// Remember the current pool, objects modified after this are marked separately
+    pub fn set_checkpoint(&mut self) {
+        self.checkpoint_pool = Some(self.pool.clone());
+        self.dirty_objects.replace(None);
+    }
+
+    pub fn has_checkpoint(&self) -> bool {
+        self.checkpoint_pool.is_some()
+    }
+
+    /// Check if and since when an object was modified
+    pub fn dirty_marker(&self, id: ObjectId) -> Option<DirtyMarker> {
+        let mut cache = self.dirty_objects.borrow_mut();
+        let dirty = cache.get_or_insert_with(|| {
+            let changed = |old: &ObjectPool| {
+                diff_pools(old, &self.pool)
+                    .into_iter()
+                    .map(|diff| diff.id)
+                    .collect()
+            };
+            DirtyObjects {
+                since_save: changed(&self.saved_pool),
+                since_checkpoint: self
+                    .checkpoint_pool
+                    .as_ref()
+                    .map(changed)
+                    .unwrap_or_default(),
+            }
+        });
+        if dirty.since_checkpoint.contains(&id) {
+            Some(DirtyMarker::SinceCheckpoint)
+        } else if dirty.since_save.contains(&id) {
+            Some(DirtyMarker::SinceSave)
+        } else {
+            None
         }
-        false
     }
 
     /// Undo the last action
     pub fn undo(&mut self) {
-        if let Some(pool) = self.undo_pool_history.pop() {
-            self.redo_pool_history.push(self.pool.clone());
+        if self.live_edit.is_some() {
+            self.update_pool();
+        }
+        if let Some(change) = self.undo_pool_history.pop() {
+            self.redo_pool_history.push(Change {
+                scoped_undo: change.scoped_undo,
+                ..Change::new(
+                    self.pool.clone(),
+                    change.description,
+                    change.category,
+                    change.author,
+                )
+            });
 
             // Both need to be replaced here because otherwise it will be added to the undo history
-            self.pool = pool.clone();
-            self.mut_pool.replace(pool);
+            self.pool = change.pool.clone();
+            self.mut_pool.replace(change.pool);
+            self.dirty_objects.replace(None);
+            self.pool_generation = next_pool_generation();
 
             // Update next_available_id based on the new pool state
             self.update_next_available_id();
@@ -188,13 +665,82 @@ impl EditorProject {
         !self.undo_pool_history.is_empty()
     }
 
+    /// Get the change that would be undone next
+    pub fn next_undo(&self) -> Option<&Change> {
+        self.undo_pool_history.last()
+    }
+
+    /// Get the change that [`Self::undo_within`] would undo next
+    pub fn next_undo_within(&self, scope: ObjectId) -> Option<&Change> {
+        let history_len = self.undo_pool_history.len();
+        let mut cache = self.pool_cache();
+        let index = match cache.undo_within {
+            Some((cached_scope, cached_len, index))
+                if cached_scope == scope && cached_len == history_len =>
+            {
+                index
+            }
+            _ => {
+                let index = last_change_within(&self.pool, &self.undo_pool_history, scope);
+                cache.undo_within = Some((scope, history_len, index));
+                index
+            }
+        };
+        index.map(|index| &self.undo_pool_history[index])
+    }
+
+    /// Undo the last change that only affected `scope` (e.g. a mask) and the objects it
+    /// references, while later changes elsewhere are kept. The undo is recorded as a new change,
+    /// so it can be undone in turn.
+    pub fn undo_within(&mut self, scope: ObjectId) -> Result<(), String> {
+        if self.live_edit.is_some() {
+            self.update_pool();
+        }
+        let index = last_change_within(&self.pool, &self.undo_pool_history, scope)
+            .ok_or_else(|| format!("No change within object {} to undo", scope.value()))?;
+        let reverted = revert_change(&self.pool, &self.undo_pool_history, index)?;
+        let scope_name = self
+            .pool
+            .object_by_id(scope)
+            .map_or(scope.value().to_string(), |o| {
+                self.get_object_info(o).get_name(o)
+            });
+        let description = format!(
+            "Undo '{}' within {}",
+            self.undo_pool_history[index].description, scope_name
+        );
+        self.mut_pool.replace(reverted);
+        self.describe_change(description, ChangeCategory::Edit);
+        if self.update_pool() {
+            if let Some(change) = self.undo_pool_history.last_mut() {
+                change.scoped_undo = true;
+            }
+        }
+        self.update_next_available_id();
+        self.default_object_names.borrow_mut().clear();
+        Ok(())
+    }
+
     /// Redo the last undone action
     pub fn redo(&mut self) {
-        if let Some(pool) = self.redo_pool_history.pop() {
-            self.undo_pool_history.push(self.pool.clone());
+        if self.live_edit.is_some() {
+            self.update_pool();
+        }
+        if let Some(change) = self.redo_pool_history.pop() {
+            self.undo_pool_history.push(Change {
+                scoped_undo: change.scoped_undo,
+                ..Change::new(
+                    self.pool.clone(),
+                    change.description,
+                    change.category,
+                    change.author,
+                )
+            });
             // Both need to be replaced here because otherwise the redo history will be cleared
-            self.pool = pool.clone();
-            self.mut_pool.replace(pool);
+            self.pool = change.pool.clone();
+            self.mut_pool.replace(change.pool);
+            self.dirty_objects.replace(None);
+            self.pool_generation = next_pool_generation();
 
             // Update next_available_id based on the new pool state
             self.update_next_available_id();
@@ -209,6 +755,11 @@ impl EditorProject {
         !self.redo_pool_history.is_empty()
     }
 
+    /// Get the change that would be redone next
+    pub fn next_redo(&self) -> Option<&Change> {
+        self.redo_pool_history.last()
+    }
+
     /// Update the selected object with the mutating selected object if it is different
     /// Returns true if the selected object was updated
     pub fn update_selected(&mut self) -> bool {
@@ -248,12 +799,52 @@ impl EditorProject {
         }
     }
 
+    /// Check if there is a previously selected object to go back to
+    pub fn previous_selected_available(&self) -> bool {
+        !self.undo_selected_history.is_empty()
+    }
+
+    /// Check if there is a next selected object to go forward to
+    pub fn next_selected_available(&self) -> bool {
+        !self.redo_selected_history.is_empty()
+    }
+
+    /// Get the terminal profile that is used by the preview
+    pub fn terminal_profile(&self) -> Option<&TerminalProfile> {
+        self.terminal_profiles.get(self.active_profile)
+    }
+
+    /// Get the bookmarked objects in the order they were added
+    pub fn get_bookmarks(&self) -> Vec<ObjectId> {
+        self.bookmarks.borrow().clone()
+    }
+
+    /// Check if an object is bookmarked
+    pub fn is_bookmarked(&self, id: ObjectId) -> bool {
+        self.bookmarks.borrow().contains(&id)
+    }
+
+    /// Add or remove a bookmark for an object
+    pub fn toggle_bookmark(&self, id: ObjectId) {
+        let mut bookmarks = self.bookmarks.borrow_mut();
+        if let Some(index) = bookmarks.iter().position(|b| *b == id) {
+            bookmarks.remove(index);
+        } else {
+            bookmarks.push(id);
+        }
+    }
+
     /// Change an object id in the object info hashmap
     pub fn update_object_id_for_info(&self, old_id: ObjectId, new_id: ObjectId) {
         let mut object_info = self.object_info.borrow_mut();
         if let Some(info) = object_info.remove(&old_id) {
             object_info.insert(new_id, info);
         }
+        for bookmark in self.bookmarks.borrow_mut().iter_mut() {
+            if *bookmark == old_id {
+                *bookmark = new_id;
+            }
+        }
     }
 
     /// Get the object info for an object id
@@ -347,10 +938,15 @@ impl EditorProject {
             }
         }
 
-        // Generate names for remaining objects
+        // Generate names for remaining objects, from the texts they show where possible
+        let content_names = smart_naming::content_based_names(&self.pool);
         for object in self.pool.objects() {
-            let new_name =
-                smart_naming::generate_smart_default_name(object.object_type(), &existing_names);
+            let new_name = match content_names.get(&object.id()) {
+                Some(name) => smart_naming::unique_name(name, &existing_names),
+                None => {
+                    smart_naming::generate_smart_default_name(object.object_type(), &existing_names)
+                }
+            };
 
             // Update the count for the new name to ensure uniqueness
             existing_names
@@ -417,8 +1013,33 @@ impl EditorProject {
             self.selected_object.0
         };
 
-        let project = ProjectFile::new(&self.pool, &object_info, self.mask_size, selected);
-        project.to_bytes()
+        let settings = ProjectSettings {
+            mask_size: self.mask_size,
+            last_selected: selected.map(|id| id.value()),
+            soft_key_size: Some(self.soft_key_size),
+            colour_roles: self.colour_roles.clone(),
+            style_presets: self.style_presets.clone(),
+            watch_expressions: self.watch_expressions.clone(),
+            state_scenarios: self.state_scenarios.clone(),
+            authors: self.authors.clone(),
+            golden_pool: self.golden_pool.clone(),
+            bookmarks: self
+                .bookmarks
+                .borrow()
+                .iter()
+                .map(|id| id.value())
+                .collect(),
+            terminal_profiles: self.terminal_profiles.clone(),
+            active_profile: self.active_profile,
+            save_history: self.save_history,
+            history_limits: self.history_limits,
+            pool_version: self.pool_version.clone(),
+        };
+        let history = self.save_history.then(|| {
+            StoredHistory::new(&self.pool, &self.undo_pool_history, &self.redo_pool_history)
+        });
+        let project = ProjectFile::new(&self.pool, &object_info, settings, history);
+        project.to_bytes(self.file_format.save_version)
     }
 
     /// Load a project from file data
@@ -430,6 +1051,40 @@ impl EditorProject {
 
         let mut editor_project = EditorProject::from(pool);
         editor_project.mask_size = settings.mask_size;
+        if let Some(soft_key_size) = settings.soft_key_size {
+            editor_project.soft_key_size = soft_key_size;
+        }
+        editor_project.colour_roles = settings.colour_roles.clone();
+        editor_project.style_presets = settings.style_presets.clone();
+        editor_project.watch_expressions = settings.watch_expressions.clone();
+        editor_project.state_scenarios = settings.state_scenarios.clone();
+        editor_project.authors = settings.authors.clone();
+        editor_project.golden_pool = settings.golden_pool.clone();
+        editor_project.save_history = settings.save_history;
+        editor_project.history_limits = settings.history_limits;
+        editor_project.pool_version = settings.pool_version.clone();
+
+        // Saving a file of a newer designer would drop what this one does not know
+        editor_project.file_format = project.get_format().clone();
+        editor_project.read_only = editor_project.file_format.is_newer();
+        if let Some(history) = project.get_history() {
+            let (undo, redo) = history.restore(&editor_project.pool);
+            editor_project.undo_pool_history = undo;
+            editor_project.redo_pool_history = redo;
+        }
+        if !settings.terminal_profiles.is_empty() {
+            editor_project.terminal_profiles = settings.terminal_profiles.clone();
+            editor_project.active_profile = settings
+                .active_profile
+                .min(settings.terminal_profiles.len() - 1);
+        }
+        editor_project.bookmarks = RefCell::new(
+            settings
+                .bookmarks
+                .iter()
+                .filter_map(|id| ObjectId::new(*id).ok())
+                .collect(),
+        );
 
         // Restore object metadata
         let metadata = project.get_metadata();
@@ -440,9 +1095,7 @@ impl EditorProject {
                 let info = object_info
                     .entry(object.id())
                     .or_insert_with(|| ObjectInfo::new(object));
-                if let Some(name) = &meta.name {
-                    info.set_name(name.clone());
-                }
+                meta.restore(info);
             }
         }
         drop(object_info);
@@ -465,6 +1118,108 @@ impl EditorProject {
         Ok(editor_project)
     }
 
+    /// Set every colour attribute that is tagged with a role to the colour of that role in the
+    /// given theme. All edits are recorded as a single bulk change.
+    /// Returns the number of changed attributes
+    pub fn apply_colour_theme(&self, theme_index: usize) -> usize {
+        let Some(theme) = self.colour_roles.themes.get(theme_index) else {
+            return 0;
+        };
+
+        let object_info = self.object_info.borrow();
+        let mut pool = self.mut_pool.borrow_mut();
+        let mut changed = 0;
+        for (id, info) in object_info.iter().filter(|(_, info)| !info.locked) {
+            let Some(object) = pool.object_mut_by_id(*id) else {
+                continue;
+            };
+            for (attribute, value) in colour_attributes_mut(object) {
+                let colour = info
+                    .colour_roles
+                    .get(attribute)
+                    .and_then(|role| theme.colours.get(role));
+                if let Some(&colour) = colour {
+                    if *value != colour {
+                        *value = colour;
+                        changed += 1;
+                    }
+                }
+            }
+        }
+
+        if changed > 0 {
+            self.describe_change(
+                format!("Apply colour theme '{}'", theme.name),
+                ChangeCategory::BulkChange,
+            );
+        }
+        changed
+    }
+
+    /// Re-apply a style preset to every object it is assigned to, e.g. after the preset changed.
+    /// All edits are recorded as a single bulk change.
+    /// Returns the number of changed attributes
+    pub fn apply_style_preset(&self, name: &str) -> usize {
+        let Some(preset) = self.style_presets.iter().find(|p| p.name == name) else {
+            return 0;
+        };
+
+        let object_info = self.object_info.borrow();
+        let mut pool = self.mut_pool.borrow_mut();
+        let mut changed = 0;
+        for (id, info) in object_info.iter().filter(|(_, info)| !info.locked) {
+            if info.style_preset.as_deref() != Some(name) {
+                continue;
+            }
+            if let Some(object) = pool.object_mut_by_id(*id) {
+                changed += apply_style_preset_to(object, preset);
+            }
+        }
+
+        if changed > 0 {
+            self.describe_change(
+                format!("Apply style preset '{}'", preset.name),
+                ChangeCategory::BulkChange,
+            );
+        }
+        changed
+    }
+
+    /// Objects the style preset is assigned to
+    pub fn style_preset_users(&self, name: &str) -> Vec<ObjectId> {
+        self.object_info
+            .borrow()
+            .iter()
+            .filter(|(_, info)| info.style_preset.as_deref() == Some(name))
+            .map(|(id, _)| *id)
+            .collect()
+    }
+
+    /// Rename a style preset and the assignments of it
+    pub fn rename_style_preset(&mut self, old: &str, new: String) {
+        if new.is_empty() || self.style_presets.iter().any(|p| p.name == new) {
+            return;
+        }
+        for info in self.object_info.borrow_mut().values_mut() {
+            if info.style_preset.as_deref() == Some(old) {
+                info.style_preset = Some(new.clone());
+            }
+        }
+        if let Some(preset) = self.style_presets.iter_mut().find(|p| p.name == old) {
+            preset.name = new;
+        }
+    }
+
+    /// Remove a style preset, the objects keep their attributes
+    pub fn remove_style_preset(&mut self, name: &str) {
+        self.style_presets.retain(|p| p.name != name);
+        for info in self.object_info.borrow_mut().values_mut() {
+            if info.style_preset.as_deref() == Some(name) {
+                info.style_preset = None;
+            }
+        }
+    }
+
     /// Request to open image file dialog for a PictureGraphic object
     pub fn request_image_load(&self, object_id: ObjectId) {
         self.image_load_request.replace(Some(object_id));
@@ -474,4 +1229,89 @@ impl EditorProject {
     pub fn take_image_load_request(&self) -> Option<ObjectId> {
         self.image_load_request.replace(None)
     }
+
+    /// Request to open the duplicate wizard for a mask
+    pub fn request_mask_duplication(&self, mask: ObjectId) {
+        self.mask_duplication_request.replace(Some(mask));
+    }
+
+    /// Take and clear the mask duplication request if any
+    pub fn take_mask_duplication_request(&self) -> Option<ObjectId> {
+        self.mask_duplication_request.replace(None)
+    }
+
+    /// Request to create a new macro executed by an event of an object
+    pub fn request_macro_creation(&self, object: ObjectId, event: Event) {
+        self.macro_creation_request.replace(Some((object, event)));
+    }
+
+    /// Take and clear the macro creation request if any
+    pub fn take_macro_creation_request(&self) -> Option<(ObjectId, Event)> {
+        self.macro_creation_request.replace(None)
+    }
+
+    /// Request to replace an object while another object is being edited. The change is
+    /// described by what it changed, unless [`Self::describe_change`] is called as well.
+    pub fn request_object_update(&self, object: Object) {
+        self.request_objects_update(vec![object]);
+    }
+
+    /// Request to replace several objects as a single change
+    pub fn request_objects_update(&self, objects: Vec<Object>) {
+        self.object_update_request.replace(Some(objects));
+    }
+
+    /// Replace the objects of the update request, if any, and commit the change right away so
+    /// the editor that requested it sees the new object in the next frame. While `live`, e.g.
+    /// during a drag, the change is only recorded in the history when the edit is finished.
+    pub fn apply_object_update_request(&mut self, live: bool) {
+        let Some(objects) = self.object_update_request.replace(None) else {
+            return;
+        };
+        let mut pool = self.mut_pool.borrow_mut();
+        for object in objects {
+            if let Some(target) = pool.object_mut_by_id(object.id()) {
+                *target = object;
+            }
+        }
+        drop(pool);
+        if live {
+            self.update_pool_live();
+        } else {
+            self.update_pool();
+        }
+    }
+
+    /// Create an empty macro that is executed by an event of the object and select it.
+    /// Macro references only hold 8 bit IDs, so None is returned if all of them are in use.
+    pub fn create_macro_for_event(&self, object: ObjectId, event: Event) -> Option<ObjectId> {
+        let mut pool = self.mut_pool.borrow_mut();
+        let id = (0..=u8::MAX)
+            .filter_map(|id| ObjectId::new(id as u16).ok())
+            .find(|id| pool.object_by_id(*id).is_none())?;
+        let macro_refs = pool
+            .object_mut_by_id(object)
+            .and_then(|object| macro_refs_mut(object))?;
+        macro_refs.push(MacroRef {
+            event_id: event,
+            macro_id: id.value() as u8,
+        });
+
+        let mut new_macro = default_object(ObjectType::Macro);
+        new_macro.mut_id().set_value(id.value()).ok();
+        pool.add(new_macro);
+        drop(pool);
+
+        self.mut_selected_object.replace(id.into());
+        self.describe_change(
+            format!(
+                "Create macro {} for {:?} of {}",
+                id.value(),
+                event,
+                object.value()
+            ),
+            ChangeCategory::Edit,
+        );
+        Some(id)
+    }
 }
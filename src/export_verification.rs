@@ -0,0 +1,60 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Checks that an exported pool file reads back into the pool it was written from.
+
+use ag_iso_stack::object_pool::ObjectPool;
+
+use crate::pool_diff::{diff_pools, ObjectDiff};
+use crate::pool_recovery::{recover_pool, SkippedRegion};
+
+/// What was lost when reading an exported file back
+#[derive(Debug, Clone, Default)]
+pub struct ExportVerification {
+    /// Name of the written file
+    pub file_name: String,
+
+    /// Size of the written file in bytes
+    pub bytes: usize,
+
+    /// Number of objects read back from the file
+    pub objects: usize,
+
+    /// Parts of the file that could not be read as objects
+    pub unreadable: Vec<SkippedRegion>,
+
+    /// Objects that were added, lost or changed by the round trip
+    pub differences: Vec<ObjectDiff>,
+
+    /// First offset where writing the read back pool again gives other bytes than the file
+    pub first_byte_difference: Option<usize>,
+}
+
+impl ExportVerification {
+    /// Returns true if the file reads back into exactly the exported pool
+    pub fn is_lossless(&self) -> bool {
+        self.unreadable.is_empty()
+            && self.differences.is_empty()
+            && self.first_byte_difference.is_none()
+    }
+}
+
+/// Read the written file back and compare it object by object with the exported pool
+pub fn verify_export(pool: &ObjectPool, file_name: &str, written: &[u8]) -> ExportVerification {
+    let (read_back, report) = recover_pool(written);
+    let rewritten = read_back.as_iop();
+    let first_byte_difference = rewritten
+        .iter()
+        .zip(written)
+        .position(|(a, b)| a != b)
+        .or_else(|| (rewritten.len() != written.len()).then(|| rewritten.len().min(written.len())));
+    ExportVerification {
+        file_name: file_name.to_string(),
+        bytes: written.len(),
+        objects: read_back.objects().len(),
+        unreadable: report.skipped_regions,
+        differences: diff_pools(pool, &read_back),
+        first_byte_difference,
+    }
+}
@@ -0,0 +1,227 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+/// Values an expression in a numeric field can refer to
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ExpressionContext {
+    /// The value of the field before the edit, `x` in the expression
+    pub current: f64,
+
+    /// Size of the data mask, `%` values are relative to it
+    pub mask_size: u16,
+}
+
+/// Recursive descent parser for the arithmetic in numeric fields and watch expressions
+struct Parser<'a> {
+    chars: std::iter::Peekable<std::str::Chars<'a>>,
+
+    /// Allows `x` and units, for numeric fields
+    context: Option<&'a ExpressionContext>,
+
+    /// Allows `var(id)`, for watch expressions
+    variables: Option<&'a dyn Fn(u16) -> Option<f64>>,
+}
+
+impl Parser<'_> {
+    /// Parse the whole text as one expression
+    fn parse(mut self) -> Option<f64> {
+        let value = self.expression()?;
+        self.skip_whitespace();
+        (self.chars.peek().is_none() && value.is_finite()).then_some(value)
+    }
+
+    fn skip_whitespace(&mut self) {
+        while self.chars.next_if(|c| c.is_whitespace()).is_some() {}
+    }
+
+    fn eat(&mut self, expected: char) -> bool {
+        self.skip_whitespace();
+        self.chars.next_if_eq(&expected).is_some()
+    }
+
+    /// Sum or difference of terms
+    fn expression(&mut self) -> Option<f64> {
+        let mut value = self.term()?;
+        loop {
+            if self.eat('+') {
+                value += self.term()?;
+            } else if self.eat('-') {
+                value -= self.term()?;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// Product or quotient of factors
+    fn term(&mut self) -> Option<f64> {
+        let mut value = self.factor()?;
+        loop {
+            if self.eat('*') {
+                value *= self.factor()?;
+            } else if self.eat('/') {
+                let divisor = self.factor()?;
+                if divisor == 0.0 {
+                    return None;
+                }
+                value /= divisor;
+            } else {
+                return Some(value);
+            }
+        }
+    }
+
+    /// Negated factor, or a number, `x`, `var(id)` or parenthesized expression with an optional
+    /// unit
+    fn factor(&mut self) -> Option<f64> {
+        if self.eat('-') {
+            return Some(-self.factor()?);
+        }
+        if self.eat('+') {
+            return self.factor();
+        }
+        let value = if self.eat('(') {
+            let value = self.expression()?;
+            self.eat(')').then_some(value)?
+        } else if self.context.is_some() && (self.eat('x') || self.eat('X')) {
+            self.context?.current
+        } else if self.variables.is_some() && self.eat('v') {
+            self.variable()?
+        } else {
+            self.number()?
+        };
+        self.unit(value)
+    }
+
+    /// Value of `var(id)` after the `v`, the ID is decimal or hexadecimal with `0x`
+    fn variable(&mut self) -> Option<f64> {
+        self.chars.next_if_eq(&'a')?;
+        self.chars.next_if_eq(&'r')?;
+        if !self.eat('(') {
+            return None;
+        }
+        self.skip_whitespace();
+        let mut text = String::new();
+        while let Some(c) = self.chars.next_if(|c| c.is_ascii_alphanumeric()) {
+            text.push(c);
+        }
+        let id = match text.strip_prefix("0x").or_else(|| text.strip_prefix("0X")) {
+            Some(hex) => u16::from_str_radix(hex, 16).ok()?,
+            None => text.parse().ok()?,
+        };
+        if !self.eat(')') {
+            return None;
+        }
+        (self.variables?)(id)
+    }
+
+    fn number(&mut self) -> Option<f64> {
+        self.skip_whitespace();
+        let mut text = String::new();
+        while let Some(c) = self
+            .chars
+            .next_if(|c| c.is_ascii_digit() || *c == '.' || *c == ',')
+        {
+            // Accept a decimal comma like in the German locale
+            text.push(if c == ',' { '.' } else { c });
+        }
+        text.parse().ok()
+    }
+
+    /// Apply a unit after a value: `px` for pixels or `%` of the data mask size
+    fn unit(&mut self, value: f64) -> Option<f64> {
+        let Some(context) = self.context else {
+            return Some(value);
+        };
+        if self.eat('%') {
+            Some(value * context.mask_size as f64 / 100.0)
+        } else if self.eat('p') {
+            self.chars.next_if_eq(&'x').map(|_| value)
+        } else {
+            Some(value)
+        }
+    }
+}
+
+/// Evaluate arithmetic entered in a numeric field, e.g. `240/2`, `x+8` relative to the current
+/// value or `50%` of the data mask size. Returns None if the text is not a valid expression.
+pub fn evaluate_expression(text: &str, context: &ExpressionContext) -> Option<f64> {
+    Parser {
+        chars: text.chars().peekable(),
+        context: Some(context),
+        variables: None,
+    }
+    .parse()
+}
+
+/// Evaluate a watch expression over number variables, e.g. `var(0x8003)*0.1 - 40` to show a raw
+/// value in engineering units. `variable` gives the value of a variable by ID. Returns None if the
+/// text is not a valid expression or refers to an unknown variable.
+pub fn evaluate_watch_expression(text: &str, variable: &dyn Fn(u16) -> Option<f64>) -> Option<f64> {
+    Parser {
+        chars: text.chars().peekable(),
+        context: None,
+        variables: Some(variable),
+    }
+    .parse()
+}
+
+/// The IDs of the variables a watch expression refers to, in order of appearance
+pub fn watch_variables(text: &str) -> Vec<u16> {
+    let ids = std::cell::RefCell::new(Vec::new());
+    let mut parser = Parser {
+        chars: text.chars().peekable(),
+        context: None,
+        variables: Some(&|id| {
+            ids.borrow_mut().push(id);
+            Some(0.0)
+        }),
+    };
+    // Evaluate as far as possible, also a partly typed expression shows its variables
+    while parser.chars.peek().is_some() {
+        if parser.expression().is_none() {
+            parser.chars.next();
+        }
+    }
+    ids.into_inner()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const CONTEXT: ExpressionContext = ExpressionContext {
+        current: 10.0,
+        mask_size: 480,
+    };
+
+    #[test]
+    fn numeric_fields_accept_arithmetic_and_units() {
+        assert_eq!(evaluate_expression("240/2", &CONTEXT), Some(120.0));
+        assert_eq!(evaluate_expression("x + 8", &CONTEXT), Some(18.0));
+        assert_eq!(evaluate_expression("-(2+3)*2", &CONTEXT), Some(-10.0));
+        assert_eq!(evaluate_expression("50%", &CONTEXT), Some(240.0));
+        assert_eq!(evaluate_expression("12px", &CONTEXT), Some(12.0));
+        assert_eq!(evaluate_expression("2,5", &CONTEXT), Some(2.5));
+        assert_eq!(evaluate_expression("1/0", &CONTEXT), None);
+        assert_eq!(evaluate_expression("2 +", &CONTEXT), None);
+        assert_eq!(evaluate_expression("var(1)", &CONTEXT), None);
+    }
+
+    #[test]
+    fn watch_expressions_read_variables() {
+        let variable = |id| (id == 0x8003).then_some(500.0);
+        assert_eq!(
+            evaluate_watch_expression("var(0x8003)*0.1 - 40", &variable),
+            Some(10.0)
+        );
+        assert_eq!(
+            evaluate_watch_expression("var(32771)", &variable),
+            Some(500.0)
+        );
+        assert_eq!(evaluate_watch_expression("var(1)", &variable), None);
+        assert_eq!(evaluate_watch_expression("x", &variable), None);
+        assert_eq!(watch_variables("var(1) + var(0x10) *"), vec![1, 16]);
+    }
+}
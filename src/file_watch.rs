@@ -0,0 +1,152 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant, SystemTime};
+
+use ag_iso_stack::object_pool::ObjectPool;
+
+use crate::{ChangeCategory, EditorProject, ObjectInfo};
+
+/// How often the opened file is checked for changes
+pub const WATCH_INTERVAL: Duration = Duration::from_secs(1);
+
+/// The pool or project file that is open in the editor, checked for changes made outside of it,
+/// e.g. by a build script or a git pull
+#[derive(Debug, Clone)]
+pub struct WatchedFile {
+    pub path: PathBuf,
+
+    /// Whether the file is a project, otherwise it is a pool
+    pub is_project: bool,
+    modified: Option<SystemTime>,
+    last_check: Instant,
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+impl WatchedFile {
+    pub fn new(path: PathBuf) -> Self {
+        WatchedFile {
+            is_project: path.extension().is_some_and(|e| e == "aitp"),
+            modified: modified_time(&path),
+            last_check: Instant::now(),
+            path,
+        }
+    }
+
+    /// The content of the file if it changed since it was last seen, at most once every
+    /// [`WATCH_INTERVAL`]
+    pub fn poll(&mut self) -> Option<Vec<u8>> {
+        if self.last_check.elapsed() < WATCH_INTERVAL {
+            return None;
+        }
+        self.last_check = Instant::now();
+        let modified = modified_time(&self.path);
+        if modified.is_none() || modified == self.modified {
+            return None;
+        }
+        self.modified = modified;
+        std::fs::read(&self.path).ok()
+    }
+
+    pub fn file_name(&self) -> String {
+        self.path
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().to_string())
+    }
+
+    /// Whether the content has the objects and names the editor has, e.g. because it saved the
+    /// file itself. Settings like the selected object are not compared.
+    pub fn matches_project(&self, content: &[u8], project: &EditorProject) -> bool {
+        if !self.is_project {
+            return project.get_pool().as_iop() == content;
+        }
+        EditorProject::load_project(content.to_vec()).is_ok_and(|disk| {
+            disk.get_pool().as_iop() == project.get_pool().as_iop()
+                && disk.get_all_object_names() == project.get_all_object_names()
+        })
+    }
+}
+
+/// Fill the metadata the local info does not have from the info on disk, local values win
+fn merge_info(local: &mut ObjectInfo, external: &ObjectInfo) {
+    if local.name.is_none() {
+        local.name = external.name.clone();
+    }
+    if local.notes.is_empty() {
+        local.notes = external.notes.clone();
+    }
+    for (attribute, role) in &external.colour_roles {
+        local
+            .colour_roles
+            .entry(attribute.clone())
+            .or_insert_with(|| role.clone());
+    }
+    if local.review == Default::default() {
+        local.review = external.review.clone();
+    }
+    if local.translation == Default::default() {
+        local.translation = external.translation;
+    }
+    if local.style_preset.is_none() {
+        local.style_preset = external.style_preset.clone();
+    }
+    local.locked |= external.locked;
+}
+
+/// Take the objects from the changed file on disk but keep the names, notes and other metadata
+/// of the local objects, filling in what is missing from the file. Recorded as a single change,
+/// so the local edits can be restored with undo. Returns the number of changed objects.
+pub fn merge_from_disk(
+    project: &EditorProject,
+    watched: &WatchedFile,
+    content: Vec<u8>,
+) -> Result<usize, String> {
+    let (pool, external_info) = if watched.is_project {
+        let external = EditorProject::load_project(content)?;
+        let info = external.object_info.borrow().clone();
+        (external.get_pool().clone(), info)
+    } else {
+        (ObjectPool::from_iop(content), Default::default())
+    };
+    if pool.objects().is_empty() {
+        return Err(format!("{} has no objects", watched.file_name()));
+    }
+
+    let current = project.get_pool();
+    let changed = pool
+        .objects()
+        .iter()
+        .filter(|object| current.object_by_id(object.id()) != Some(*object))
+        .count()
+        + current
+            .objects()
+            .iter()
+            .filter(|object| pool.object_by_id(object.id()).is_none())
+            .count();
+
+    let mut object_info = project.object_info.borrow_mut();
+    for object in pool.objects() {
+        let info = object_info
+            .entry(object.id())
+            .or_insert_with(|| ObjectInfo::new(object));
+        if let Some(external) = external_info.get(&object.id()) {
+            merge_info(info, external);
+        }
+    }
+    drop(object_info);
+    for object in pool.objects() {
+        project.apply_smart_naming_to_object(object);
+    }
+
+    *project.get_mut_pool().borrow_mut() = pool;
+    project.describe_change(
+        format!("Merge changes to {} from disk", watched.file_name()),
+        ChangeCategory::BulkChange,
+    );
+    Ok(changed)
+}
@@ -0,0 +1,202 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! The order in which a terminal without touch screen moves the input focus through the input
+//! objects and buttons of a mask. Terminals follow the object references of the mask depth first,
+//! so the order can only be changed by reordering the references inside their parent.
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::Point;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+use eframe::egui;
+
+use crate::layout::{object_refs, object_refs_mut};
+use crate::preview_state::{preview_mode, with_preview_state, PreviewMode};
+use crate::{tr, ChangeCategory, EditorProject};
+
+const BADGE_RADIUS: f32 = 8.0;
+
+/// An object that can get the input focus
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct FocusStop {
+    pub object: ObjectId,
+
+    /// The object with the reference that leads to the object and the index of the reference,
+    /// for objects shown through an object pointer this is the reference to the pointer
+    pub parent: ObjectId,
+    pub index: usize,
+
+    /// Position on the mask
+    pub offset: Point<i16>,
+}
+
+/// Whether the operator can select the object, disabled objects are skipped by terminals
+fn is_focusable(object: &Object) -> bool {
+    match object {
+        Object::InputBoolean(o) => o.enabled,
+        Object::InputString(o) => o.enabled,
+        Object::InputNumber(o) => o.options2.enabled,
+        Object::InputList(o) => o.options.enabled,
+        Object::Button(o) => !o.options.disabled,
+        _ => false,
+    }
+}
+
+fn collect_stops(
+    pool: &ObjectPool,
+    object: &Object,
+    offset: Point<i16>,
+    leading_ref: Option<(ObjectId, usize)>,
+    stops: &mut Vec<FocusStop>,
+) {
+    if let Some((parent, index)) = leading_ref {
+        if is_focusable(object) {
+            stops.push(FocusStop {
+                object: object.id(),
+                parent,
+                index,
+                offset,
+            });
+            return;
+        }
+    }
+    match object {
+        Object::Container(container) if container.hidden => {}
+        Object::DataMask(_) | Object::AlarmMask(_) | Object::Container(_) => {
+            for (index, obj_ref) in object_refs(object).into_iter().flatten().enumerate() {
+                let child_offset = Point {
+                    x: offset.x + obj_ref.offset.x,
+                    y: offset.y + obj_ref.offset.y,
+                };
+                if let Some(child) = pool.object_by_id(obj_ref.id) {
+                    let leading_ref = Some((object.id(), index));
+                    collect_stops(pool, child, child_offset, leading_ref, stops);
+                }
+            }
+        }
+        Object::ObjectPointer(pointer) => {
+            if let Some(target) = pointer.value.0.and_then(|id| pool.object_by_id(id)) {
+                collect_stops(pool, target, offset, leading_ref, stops);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// The objects of a mask in the order the input focus moves through them
+pub fn focus_order(pool: &ObjectPool, mask: &Object) -> Vec<FocusStop> {
+    let mut stops = Vec::new();
+    collect_stops(pool, mask, Point::default(), None, &mut stops);
+    stops
+}
+
+/// Move a focus stop to the place of another one by moving its reference inside their common
+/// parent. This also changes the drawing order, later references are drawn on top.
+pub fn move_focus_stop(
+    project: &EditorProject,
+    stop: &FocusStop,
+    target: &FocusStop,
+) -> Result<(), String> {
+    if stop.parent != target.parent {
+        return Err(format!(
+            "Objects {} and {} are in different parents, only objects in the same parent can be \
+             reordered",
+            stop.object.value(),
+            target.object.value()
+        ));
+    }
+    if project.is_locked(stop.parent) {
+        return Err(format!("Object {} is locked", stop.parent.value()));
+    }
+    let mut pool = project.get_mut_pool().borrow_mut();
+    let refs = pool
+        .object_mut_by_id(stop.parent)
+        .and_then(object_refs_mut)
+        .ok_or_else(|| format!("Object {} does not exist", stop.parent.value()))?;
+    if stop.index >= refs.len() || target.index >= refs.len() {
+        return Err("The focus order is out of date".to_string());
+    }
+    let obj_ref = refs.remove(stop.index);
+    refs.insert(target.index, obj_ref);
+    drop(pool);
+    project.describe_change(
+        format!(
+            "Move object {} to the focus position of object {}",
+            stop.object.value(),
+            target.object.value()
+        ),
+        ChangeCategory::Edit,
+    );
+    Ok(())
+}
+
+/// Show the focus order over the mask drawn in `mask_rect` as numbered badges. Dragging a badge
+/// onto another one moves the object to that focus position.
+pub fn render_focus_order_overlay(
+    ui: &egui::Ui,
+    project: &EditorProject,
+    mask: &Object,
+    mask_rect: egui::Rect,
+) {
+    if !with_preview_state(ui.ctx(), |state| state.show_focus_order)
+        || preview_mode(ui.ctx()) != PreviewMode::Edit
+    {
+        return;
+    }
+    let stops = focus_order(project.get_pool(), mask);
+    let painter = ui.painter_at(mask_rect);
+    let centre_of = |stop: &FocusStop| {
+        mask_rect.min
+            + egui::vec2(stop.offset.x as f32, stop.offset.y as f32)
+            + egui::Vec2::splat(BADGE_RADIUS)
+    };
+
+    let mut dropped = None;
+    for (number, stop) in stops.iter().enumerate() {
+        let centre = centre_of(stop);
+        let rect = egui::Rect::from_center_size(centre, egui::Vec2::splat(BADGE_RADIUS * 2.0));
+        let response = ui
+            .interact(
+                rect,
+                ui.id().with(("focus_stop", stop.object.value(), number)),
+                egui::Sense::drag(),
+            )
+            .on_hover_text(tr(
+                "Drag onto another number to move the object to that focus position",
+            ));
+        let pointer = ui.input(|input| input.pointer.interact_pos());
+        if response.drag_stopped() {
+            dropped = pointer.map(|pointer| (number, pointer));
+        }
+        let centre = match pointer {
+            Some(pointer) if response.dragged() => pointer,
+            _ => centre,
+        };
+        painter.circle(
+            centre,
+            BADGE_RADIUS,
+            egui::Color32::from_rgb(0, 90, 200),
+            egui::Stroke::new(1.0, egui::Color32::WHITE),
+        );
+        painter.text(
+            centre,
+            egui::Align2::CENTER_CENTER,
+            (number + 1).to_string(),
+            egui::FontId::proportional(BADGE_RADIUS * 1.2),
+            egui::Color32::WHITE,
+        );
+    }
+
+    let Some((number, pointer)) = dropped else {
+        return;
+    };
+    let target = stops
+        .iter()
+        .position(|stop| centre_of(stop).distance(pointer) <= BADGE_RADIUS);
+    if let Some(target) = target.filter(|target| *target != number) {
+        if let Err(e) = move_focus_stop(project, &stops[number], &stops[target]) {
+            log::warn!("{}", e);
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::ObjectId;
+
+use crate::{ChangeCategory, EditorProject};
+
+/// The font attributes reference of an object that draws text
+pub fn font_attributes_mut(object: &mut Object) -> Option<&mut ObjectId> {
+    match object {
+        Object::InputString(o) => Some(&mut o.font_attributes),
+        Object::InputNumber(o) => Some(&mut o.font_attributes),
+        Object::OutputString(o) => Some(&mut o.font_attributes),
+        Object::OutputNumber(o) => Some(&mut o.font_attributes),
+        _ => None,
+    }
+}
+
+/// Whether the object references font attributes
+pub fn has_font_attributes(object: &Object) -> bool {
+    font_attributes_mut(&mut object.clone()).is_some()
+}
+
+/// Set the font attributes of every text object in `targets` to `font_attributes`, other and
+/// locked objects are skipped. All edits are recorded as a single bulk change.
+/// Returns the number of changed objects
+pub fn set_font_attributes(
+    project: &EditorProject,
+    targets: &[ObjectId],
+    font_attributes: ObjectId,
+) -> Result<usize, String> {
+    let font_name = match project.get_pool().object_by_id(font_attributes) {
+        Some(font @ Object::FontAttributes(_)) => project.get_object_info(font).get_name(font),
+        _ => {
+            return Err(format!(
+                "Object {} is not a font attributes object",
+                font_attributes.value()
+            ))
+        }
+    };
+
+    let mut pool = project.get_mut_pool().borrow_mut();
+    let mut changed = 0;
+    for id in targets.iter().filter(|id| !project.is_locked(**id)) {
+        let reference = pool.object_mut_by_id(*id).and_then(font_attributes_mut);
+        if let Some(reference) = reference {
+            if *reference != font_attributes {
+                *reference = font_attributes;
+                changed += 1;
+            }
+        }
+    }
+
+    if changed > 0 {
+        project.describe_change(
+            format!("Set the font of {} objects to {}", changed, font_name),
+            ChangeCategory::BulkChange,
+        );
+    }
+    Ok(changed)
+}
@@ -0,0 +1,91 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::BTreeMap;
+
+use ag_iso_stack::object_pool::ObjectId;
+use serde::{Deserialize, Serialize};
+
+use crate::validation::ValidationIssue;
+use crate::EditorProject;
+
+/// An object of a released pool
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct LockedObject {
+    pub object_type: String,
+    pub name: String,
+}
+
+/// Object IDs and names of a released pool.
+/// ECU firmware refers to objects by their ID, so these must not change or disappear afterwards.
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct GoldenPool {
+    /// Locked objects by object ID
+    pub objects: BTreeMap<u16, LockedObject>,
+}
+
+impl GoldenPool {
+    /// Lock all objects currently in the project
+    pub fn from_project(project: &EditorProject) -> Self {
+        let objects = project
+            .get_pool()
+            .objects()
+            .iter()
+            .map(|object| {
+                let locked = LockedObject {
+                    object_type: format!("{:?}", object.object_type()),
+                    name: project.get_object_info(object).get_name(object),
+                };
+                (object.id().value(), locked)
+            })
+            .collect();
+        GoldenPool { objects }
+    }
+
+    /// Check if an object ID is part of the golden pool
+    pub fn is_locked(&self, id: ObjectId) -> bool {
+        self.objects.contains_key(&id.value())
+    }
+
+    /// Report every locked object that was deleted, changed type or was renamed
+    pub fn validate(&self, project: &EditorProject, issues: &mut Vec<ValidationIssue>) {
+        for (&id, locked) in &self.objects {
+            let object = ObjectId::new(id)
+                .ok()
+                .and_then(|id| project.get_pool().object_by_id(id));
+            let Some(object) = object else {
+                issues.push(ValidationIssue::error(
+                    None,
+                    format!(
+                        "Locked {} {} '{}' was deleted or its ID was changed",
+                        locked.object_type, id, locked.name
+                    ),
+                ));
+                continue;
+            };
+
+            let object_type = format!("{:?}", object.object_type());
+            if object_type != locked.object_type {
+                issues.push(ValidationIssue::error(
+                    Some(object.id()),
+                    format!(
+                        "Locked object {} changed from {} to {}",
+                        id, locked.object_type, object_type
+                    ),
+                ));
+            }
+
+            let name = project.get_object_info(object).get_name(object);
+            if name != locked.name {
+                issues.push(ValidationIssue::warning(
+                    Some(object.id()),
+                    format!(
+                        "Locked object {} was renamed from '{}' to '{}'",
+                        id, locked.name, name
+                    ),
+                ));
+            }
+        }
+    }
+}
@@ -0,0 +1,538 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! The serialized bytes of an object, annotated with the attribute each byte belongs to as laid
+//! out in ISO 11783-6.
+
+use ag_iso_stack::object_pool::ObjectId;
+
+use crate::pool_recovery::read_object_at;
+use crate::{ChangeCategory, EditorProject};
+
+/// A range of bytes that encodes one attribute
+#[derive(Debug, Clone, PartialEq)]
+pub struct ByteField {
+    pub offset: usize,
+    pub len: usize,
+    pub name: String,
+
+    /// The number of entries or bytes that follow, rather than an attribute of its own
+    pub count: bool,
+}
+
+/// Walks the bytes of an object and records the fields, stopping at the end of the data
+struct Layout<'a> {
+    bytes: &'a [u8],
+    offset: usize,
+    fields: Vec<ByteField>,
+}
+
+impl Layout<'_> {
+    fn field(&mut self, name: &str, len: usize) {
+        let len = len.min(self.bytes.len().saturating_sub(self.offset));
+        if len > 0 {
+            self.fields.push(ByteField {
+                offset: self.offset,
+                len,
+                name: name.to_string(),
+                count: false,
+            });
+            self.offset += len;
+        }
+    }
+
+    /// A little endian number of entries or bytes that follow
+    fn count(&mut self, name: &str, len: usize) -> usize {
+        let value = self
+            .bytes
+            .iter()
+            .skip(self.offset)
+            .take(len)
+            .enumerate()
+            .map(|(i, byte)| (*byte as usize) << (8 * i))
+            .sum();
+        let start = self.fields.len();
+        self.field(name, len);
+        if let Some(field) = self.fields.get_mut(start) {
+            field.count = true;
+        }
+        value
+    }
+
+    fn list(&mut self, name: &str, count: usize, entry_len: usize) {
+        for index in 1..=count {
+            self.field(&format!("{} {}", name, index), entry_len);
+        }
+    }
+
+    fn fields(&mut self, fields: &[(&str, usize)]) {
+        for (name, len) in fields {
+            self.field(name, *len);
+        }
+    }
+}
+
+/// Object references with their position, as used by masks, containers, keys and buttons
+fn object_list(layout: &mut Layout, objects: usize, macros: usize) {
+    layout.list("Object reference", objects, 6);
+    layout.list("Macro reference", macros, 2);
+}
+
+/// Annotate the serialized bytes of an object. Bytes that cannot be attributed to a field, e.g.
+/// of object types without a known layout, are reported as one trailing field.
+pub fn annotate_object(bytes: &[u8]) -> Vec<ByteField> {
+    let mut layout = Layout {
+        bytes,
+        offset: 0,
+        fields: Vec::new(),
+    };
+    layout.fields(&[("Object ID", 2)]);
+    let object_type = layout.count("Object type", 1);
+    let l = &mut layout;
+    match object_type {
+        0 => {
+            l.fields(&[
+                ("Background colour", 1),
+                ("Selectable", 1),
+                ("Active mask", 2),
+            ]);
+            let objects = l.count("Number of objects", 1);
+            let macros = l.count("Number of macros", 1);
+            let languages = l.count("Number of languages", 1);
+            object_list(l, objects, macros);
+            l.list("Language code", languages, 2);
+        }
+        1 => {
+            l.fields(&[("Background colour", 1), ("Soft key mask", 2)]);
+            let objects = l.count("Number of objects", 1);
+            let macros = l.count("Number of macros", 1);
+            object_list(l, objects, macros);
+        }
+        2 => {
+            l.fields(&[
+                ("Background colour", 1),
+                ("Soft key mask", 2),
+                ("Priority", 1),
+                ("Acoustic signal", 1),
+            ]);
+            let objects = l.count("Number of objects", 1);
+            let macros = l.count("Number of macros", 1);
+            object_list(l, objects, macros);
+        }
+        3 => {
+            l.fields(&[("Width", 2), ("Height", 2), ("Hidden", 1)]);
+            let objects = l.count("Number of objects", 1);
+            let macros = l.count("Number of macros", 1);
+            object_list(l, objects, macros);
+        }
+        4 => {
+            l.field("Background colour", 1);
+            let objects = l.count("Number of objects", 1);
+            let macros = l.count("Number of macros", 1);
+            l.list("Key reference", objects, 2);
+            l.list("Macro reference", macros, 2);
+        }
+        5 => {
+            l.fields(&[("Background colour", 1), ("Key code", 1)]);
+            let objects = l.count("Number of objects", 1);
+            let macros = l.count("Number of macros", 1);
+            object_list(l, objects, macros);
+        }
+        6 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Background colour", 1),
+                ("Border colour", 1),
+                ("Key code", 1),
+                ("Options", 1),
+            ]);
+            let objects = l.count("Number of objects", 1);
+            let macros = l.count("Number of macros", 1);
+            object_list(l, objects, macros);
+        }
+        7 => {
+            l.fields(&[
+                ("Background colour", 1),
+                ("Width", 2),
+                ("Foreground colour", 2),
+                ("Variable reference", 2),
+                ("Value", 1),
+                ("Enabled", 1),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        8 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Background colour", 1),
+                ("Font attributes", 2),
+                ("Input attributes", 2),
+                ("Options", 1),
+                ("Variable reference", 2),
+                ("Justification", 1),
+            ]);
+            let length = l.count("Length", 1);
+            l.field("Value", length);
+            l.field("Enabled", 1);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        9 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Background colour", 1),
+                ("Font attributes", 2),
+                ("Options", 1),
+                ("Variable reference", 2),
+                ("Value", 4),
+                ("Minimum value", 4),
+                ("Maximum value", 4),
+                ("Offset", 4),
+                ("Scale", 4),
+                ("Number of decimals", 1),
+                ("Format", 1),
+                ("Justification", 1),
+                ("Options 2", 1),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        10 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Variable reference", 2),
+                ("Value", 1),
+            ]);
+            let items = l.count("Number of list items", 1);
+            l.field("Options", 1);
+            let macros = l.count("Number of macros", 1);
+            l.list("List item", items, 2);
+            l.list("Macro reference", macros, 2);
+        }
+        11 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Background colour", 1),
+                ("Font attributes", 2),
+                ("Options", 1),
+                ("Variable reference", 2),
+                ("Justification", 1),
+            ]);
+            let length = l.count("Length", 2);
+            l.field("Value", length);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        12 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Background colour", 1),
+                ("Font attributes", 2),
+                ("Options", 1),
+                ("Variable reference", 2),
+                ("Value", 4),
+                ("Offset", 4),
+                ("Scale", 4),
+                ("Number of decimals", 1),
+                ("Format", 1),
+                ("Justification", 1),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        13 => {
+            l.fields(&[
+                ("Line attributes", 2),
+                ("Width", 2),
+                ("Height", 2),
+                ("Line direction", 1),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        14 => {
+            l.fields(&[
+                ("Line attributes", 2),
+                ("Width", 2),
+                ("Height", 2),
+                ("Line suppression", 1),
+                ("Fill attributes", 2),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        15 => {
+            l.fields(&[
+                ("Line attributes", 2),
+                ("Width", 2),
+                ("Height", 2),
+                ("Ellipse type", 1),
+                ("Start angle", 1),
+                ("End angle", 1),
+                ("Fill attributes", 2),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        16 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Line attributes", 2),
+                ("Fill attributes", 2),
+                ("Polygon type", 1),
+            ]);
+            let points = l.count("Number of points", 1);
+            let macros = l.count("Number of macros", 1);
+            l.list("Point", points, 4);
+            l.list("Macro reference", macros, 2);
+        }
+        17 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Needle colour", 1),
+                ("Border colour", 1),
+                ("Arc and tick colour", 1),
+                ("Options", 1),
+                ("Number of ticks", 1),
+                ("Start angle", 1),
+                ("End angle", 1),
+                ("Minimum value", 2),
+                ("Maximum value", 2),
+                ("Variable reference", 2),
+                ("Value", 2),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        18 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Colour", 1),
+                ("Target line colour", 1),
+                ("Options", 1),
+                ("Number of ticks", 1),
+                ("Minimum value", 2),
+                ("Maximum value", 2),
+                ("Variable reference", 2),
+                ("Value", 2),
+                ("Target value variable reference", 2),
+                ("Target value", 2),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        19 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Height", 2),
+                ("Colour", 1),
+                ("Target line colour", 1),
+                ("Options", 1),
+                ("Start angle", 1),
+                ("End angle", 1),
+                ("Bar graph width", 2),
+                ("Minimum value", 2),
+                ("Maximum value", 2),
+                ("Variable reference", 2),
+                ("Value", 2),
+                ("Target value variable reference", 2),
+                ("Target value", 2),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        20 => {
+            l.fields(&[
+                ("Width", 2),
+                ("Actual width", 2),
+                ("Actual height", 2),
+                ("Format", 1),
+                ("Options", 1),
+                ("Transparency colour", 1),
+            ]);
+            let data = l.count("Number of bytes in raw data", 4);
+            let macros = l.count("Number of macros", 1);
+            l.field("Raw data", data);
+            l.list("Macro reference", macros, 2);
+        }
+        21 => l.field("Value", 4),
+        22 => {
+            let length = l.count("Length", 2);
+            l.field("Value", length);
+        }
+        23 => {
+            l.fields(&[
+                ("Font colour", 1),
+                ("Font size", 1),
+                ("Font type", 1),
+                ("Font style", 1),
+            ]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        24 => {
+            l.fields(&[("Line colour", 1), ("Line width", 1), ("Line art", 2)]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        25 => {
+            l.fields(&[("Fill type", 1), ("Fill colour", 1), ("Fill pattern", 2)]);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        26 => {
+            l.field("Validation type", 1);
+            let length = l.count("Length", 1);
+            l.field("Validation string", length);
+            let macros = l.count("Number of macros", 1);
+            l.list("Macro reference", macros, 2);
+        }
+        27 => l.field("Value", 2),
+        28 => {
+            let length = l.count("Number of bytes", 2);
+            l.field("Macro commands", length);
+        }
+        29 => {
+            l.fields(&[("Background colour", 1), ("Function type", 1)]);
+            let objects = l.count("Number of objects", 1);
+            l.list("Object reference", objects, 6);
+        }
+        30 => {
+            l.fields(&[
+                ("Background colour", 1),
+                ("Function type", 1),
+                ("Input ID", 1),
+            ]);
+            let objects = l.count("Number of objects", 1);
+            l.list("Object reference", objects, 6);
+        }
+        _ => {}
+    }
+    let remaining = bytes.len().saturating_sub(layout.offset);
+    layout.field("Attributes", remaining);
+    layout.fields
+}
+
+/// Bytes as space separated hexadecimal pairs
+pub fn format_hex(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Parse hexadecimal pairs, whitespace between them is ignored
+pub fn parse_hex(text: &str) -> Result<Vec<u8>, String> {
+    let digits: Vec<char> = text.chars().filter(|c| !c.is_whitespace()).collect();
+    if !digits.len().is_multiple_of(2) {
+        return Err("The number of hexadecimal digits must be even".to_string());
+    }
+    digits
+        .chunks(2)
+        .map(|pair| {
+            let pair: String = pair.iter().collect();
+            u8::from_str_radix(&pair, 16)
+                .map_err(|_| format!("'{}' is not a hexadecimal byte", pair))
+        })
+        .collect()
+}
+
+/// Replace an object by the object the bytes encode. The bytes must encode exactly one object
+/// with the same ID, changing IDs this way would leave references behind.
+pub fn replace_object_bytes(
+    project: &EditorProject,
+    id: ObjectId,
+    bytes: &[u8],
+) -> Result<(), String> {
+    if project.is_locked(id) {
+        return Err(format!("Object {} is locked", id.value()));
+    }
+    let (object, end) = read_object_at(bytes, 0)?;
+    if end != bytes.len() {
+        return Err(format!(
+            "The object ends after {} bytes, {} bytes are left over",
+            end,
+            bytes.len() - end
+        ));
+    }
+    if object.id() != id {
+        return Err(format!(
+            "The bytes encode object {} instead of {}",
+            object.id().value(),
+            id.value()
+        ));
+    }
+
+    let mut pool = project.get_mut_pool().borrow_mut();
+    let existing = pool
+        .object_mut_by_id(id)
+        .ok_or_else(|| format!("Object {} does not exist", id.value()))?;
+    *existing = object;
+    drop(pool);
+    project.describe_change(
+        format!("Edit the raw bytes of object {}", id.value()),
+        ChangeCategory::Edit,
+    );
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn hex_pairs_are_parsed() {
+        assert_eq!(parse_hex("0a FF\n10"), Ok(vec![0x0A, 0xFF, 0x10]));
+        assert_eq!(parse_hex(""), Ok(Vec::new()));
+        assert!(parse_hex("ABC").is_err());
+        assert!(parse_hex("GG").is_err());
+        assert_eq!(format_hex(&[0x0A, 0xFF]), "0A FF");
+    }
+
+    #[test]
+    fn container_bytes_are_annotated() {
+        #[rustfmt::skip]
+        let bytes = [
+            0xE8, 0x03, 3, // ID and type
+            100, 0, 50, 0, 0, // width, height, hidden
+            1, 0, // objects and macros
+            0xE9, 0x03, 5, 0, 6, 0, // object reference
+        ];
+        let fields = annotate_object(&bytes);
+        let names: Vec<&str> = fields.iter().map(|field| field.name.as_str()).collect();
+        assert_eq!(
+            names,
+            [
+                "Object ID",
+                "Object type",
+                "Width",
+                "Height",
+                "Hidden",
+                "Number of objects",
+                "Number of macros",
+                "Object reference 1"
+            ]
+        );
+        assert_eq!((fields[7].offset, fields[7].len), (10, 6));
+        assert!(fields[5].count && !fields[4].count);
+
+        // Unknown object types keep their bytes in one field, truncated data ends early
+        let fields = annotate_object(&[1, 0, 200, 1, 2, 3]);
+        assert_eq!(
+            fields.last().map(|f| (f.name.as_str(), f.len)),
+            Some(("Attributes", 3))
+        );
+        assert_eq!(annotate_object(&bytes[..9]).len(), 6);
+    }
+}
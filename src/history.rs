@@ -0,0 +1,636 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{BTreeSet, HashMap};
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::Point;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectRef, ObjectType};
+use serde::{Deserialize, Serialize};
+
+use crate::layout::object_refs;
+use crate::pool_diff::{diff_objects, diff_pools, AttributeChange, ObjectChange, ObjectDiff};
+
+/// Attributes that only change where or how large an object is drawn
+const LAYOUT_ATTRIBUTES: &[&str] = &["width", "height", "line_direction", "points"];
+
+/// Longest attribute value that is written to a change description
+const MAX_DESCRIPTION_VALUE_LENGTH: usize = 24;
+
+/// Category of edit a change represents
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeCategory {
+    /// A regular edit made through the editor
+    #[default]
+    Edit,
+    /// A single action that edits many objects at once
+    BulkChange,
+    /// Objects were added to the pool
+    Create,
+    /// Objects were removed from the pool
+    Delete,
+    /// Objects were moved or resized
+    Layout,
+}
+
+impl ChangeCategory {
+    pub const ALL: [ChangeCategory; 5] = [
+        ChangeCategory::Edit,
+        ChangeCategory::BulkChange,
+        ChangeCategory::Create,
+        ChangeCategory::Delete,
+        ChangeCategory::Layout,
+    ];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            ChangeCategory::Edit => "Edit",
+            ChangeCategory::BulkChange => "Bulk change",
+            ChangeCategory::Create => "Create",
+            ChangeCategory::Delete => "Delete",
+            ChangeCategory::Layout => "Layout",
+        }
+    }
+}
+
+/// An entry in the undo/redo history
+#[derive(Debug, Clone)]
+pub struct Change {
+    /// The pool to restore when this change is undone (or redone)
+    pub pool: ObjectPool,
+
+    /// Human readable description of the change
+    pub description: String,
+
+    pub category: ChangeCategory,
+
+    /// Name of the author that made the change, empty if unknown
+    pub author: String,
+
+    /// Approximate memory used by the entry in bytes
+    pub size: usize,
+
+    /// Whether the change reverted an earlier change within a scope, see `undo_within`
+    pub scoped_undo: bool,
+}
+
+impl Change {
+    pub fn new(
+        pool: ObjectPool,
+        description: String,
+        category: ChangeCategory,
+        author: String,
+    ) -> Self {
+        Change {
+            size: estimate_pool_memory(&pool),
+            pool,
+            description,
+            category,
+            author,
+            scoped_undo: false,
+        }
+    }
+
+    /// Check if the change repeats the same action as a directly preceding change, e.g. one
+    /// step of dragging an object: the same author changed the same attributes of the same
+    /// objects. `after` is the pool after this change. The descriptions hold the new values,
+    /// so they differ between the steps.
+    pub fn repeats(&self, after: &ObjectPool, previous: &Change) -> bool {
+        self.category == previous.category
+            && self.author == previous.author
+            && changed_attributes(&self.pool, after)
+                == changed_attributes(&previous.pool, &self.pool)
+    }
+}
+
+/// The objects that differ between two pools with the names of their changed attributes
+fn changed_attributes(before: &ObjectPool, after: &ObjectPool) -> Vec<(ObjectId, Vec<String>)> {
+    diff_pools(before, after)
+        .into_iter()
+        .map(|diff| {
+            let attributes = match diff.change {
+                ObjectChange::Added => vec!["added".to_string()],
+                ObjectChange::Removed => vec!["removed".to_string()],
+                ObjectChange::Modified(attributes) => attributes
+                    .into_iter()
+                    .map(|attribute| attribute.attribute)
+                    .collect(),
+            };
+            (diff.id, attributes)
+        })
+        .collect()
+}
+
+/// Approximate memory of a pool in bytes, cheap enough to run for every change unlike
+/// serializing the pool
+fn estimate_pool_memory(pool: &ObjectPool) -> usize {
+    pool.objects()
+        .iter()
+        .map(|object| {
+            let data = match object {
+                Object::PictureGraphic(o) => o.data.len(),
+                Object::Macro(o) => o.commands.len(),
+                Object::ColourMap(o) => o.colour_map.len(),
+                Object::OutputString(o) => o.value.len(),
+                Object::InputString(o) => o.value.len(),
+                Object::StringVariable(o) => o.value.len(),
+                Object::OutputPolygon(o) => o.points.len() * std::mem::size_of::<Point<u16>>(),
+                _ => 0,
+            };
+            let refs =
+                object_refs(object).map_or(0, |refs| refs.len() * std::mem::size_of::<ObjectRef>());
+            std::mem::size_of::<Object>() + data + refs
+        })
+        .sum()
+}
+
+/// E.g. "OutputString 0x1F00"
+fn object_label(object_type: ObjectType, id: ObjectId) -> String {
+    format!("{:?} 0x{:04X}", object_type, id.value())
+}
+
+fn shorten(value: &str) -> String {
+    if value.chars().count() > MAX_DESCRIPTION_VALUE_LENGTH {
+        let short: String = value.chars().take(MAX_DESCRIPTION_VALUE_LENGTH).collect();
+        format!("{}...", short)
+    } else {
+        value.to_string()
+    }
+}
+
+/// Describe a change of the children of an object. Only moved children are described, as other
+/// changes of the children are described by the objects that were added or removed.
+fn describe_moves(before: &ObjectPool, after: &ObjectPool, parent: &ObjectDiff) -> Option<String> {
+    let old_refs = before.object_by_id(parent.id).and_then(object_refs)?;
+    let new_refs = after.object_by_id(parent.id).and_then(object_refs)?;
+    if old_refs.len() != new_refs.len() || old_refs.iter().zip(new_refs).any(|(o, n)| o.id != n.id)
+    {
+        return None;
+    }
+    let moved: Vec<_> = old_refs
+        .iter()
+        .zip(new_refs)
+        .filter(|(old, new)| old.offset != new.offset)
+        .collect();
+    match moved.as_slice() {
+        [(old, new)] => {
+            let child = after
+                .object_by_id(new.id)
+                .map_or(new.id.value().to_string(), |child| {
+                    object_label(child.object_type(), child.id())
+                });
+            Some(format!(
+                "Move {}: ({}, {}) \u{2192} ({}, {})",
+                child, old.offset.x, old.offset.y, new.offset.x, new.offset.y
+            ))
+        }
+        moved => Some(format!(
+            "Move {} objects in {}",
+            moved.len(),
+            object_label(parent.object_type, parent.id)
+        )),
+    }
+}
+
+fn describe_attributes(
+    diff: &ObjectDiff,
+    attributes: &[AttributeChange],
+) -> (String, ChangeCategory) {
+    let label = object_label(diff.object_type, diff.id);
+    let category = if attributes
+        .iter()
+        .all(|a| LAYOUT_ATTRIBUTES.contains(&a.attribute.as_str()))
+    {
+        ChangeCategory::Layout
+    } else {
+        ChangeCategory::Edit
+    };
+    let description = match attributes {
+        [attribute] => format!(
+            "{}: {} {} \u{2192} {}",
+            label,
+            attribute.attribute,
+            shorten(&attribute.old_value),
+            shorten(&attribute.new_value)
+        ),
+        attributes => {
+            let names: Vec<&str> = attributes.iter().map(|a| a.attribute.as_str()).collect();
+            format!("{}: {}", label, names.join(", "))
+        }
+    };
+    (description, category)
+}
+
+/// Describe the edit that turned one pool into the other from the objects and attributes it
+/// changed, e.g. "OutputNumber 0x1F00: scale 1.0 → 0.1"
+pub fn describe_pool_change(before: &ObjectPool, after: &ObjectPool) -> (String, ChangeCategory) {
+    let diffs = diff_pools(before, after);
+    let with_change = |change: fn(&ObjectChange) -> bool| -> Vec<&ObjectDiff> {
+        diffs.iter().filter(|diff| change(&diff.change)).collect()
+    };
+    let added = with_change(|c| matches!(c, ObjectChange::Added));
+    let removed = with_change(|c| matches!(c, ObjectChange::Removed));
+    let modified: Vec<(&ObjectDiff, &Vec<AttributeChange>)> = diffs
+        .iter()
+        .filter_map(|diff| match &diff.change {
+            ObjectChange::Modified(attributes) => Some((diff, attributes)),
+            _ => None,
+        })
+        .collect();
+
+    // Parents that only changed their references are part of adding or removing the objects
+    match (added.as_slice(), removed.as_slice()) {
+        ([], []) => {}
+        ([diff], []) => {
+            let label = object_label(diff.object_type, diff.id);
+            return (format!("Add {}", label), ChangeCategory::Create);
+        }
+        ([], [diff]) => {
+            let label = object_label(diff.object_type, diff.id);
+            return (format!("Delete {}", label), ChangeCategory::Delete);
+        }
+        (added, []) => {
+            return (
+                format!("Add {} objects", added.len()),
+                ChangeCategory::Create,
+            )
+        }
+        ([], removed) => {
+            return (
+                format!("Delete {} objects", removed.len()),
+                ChangeCategory::Delete,
+            )
+        }
+        (added, removed) => {
+            return (
+                format!("Add {} and delete {} objects", added.len(), removed.len()),
+                ChangeCategory::BulkChange,
+            )
+        }
+    }
+
+    match modified.as_slice() {
+        [] => ("Edit".to_string(), ChangeCategory::Edit),
+        [(diff, attributes)] => {
+            if let [attribute] = attributes.as_slice() {
+                if attribute.attribute == "object_refs" {
+                    if let Some(description) = describe_moves(before, after, diff) {
+                        return (description, ChangeCategory::Layout);
+                    }
+                }
+            }
+            describe_attributes(diff, attributes)
+        }
+        modified => (
+            format!("Edit {} objects", modified.len()),
+            ChangeCategory::BulkChange,
+        ),
+    }
+}
+
+/// Offsets at which the parent places the object
+fn placements(pool: &ObjectPool, parent: ObjectId, id: ObjectId) -> Vec<(i16, i16)> {
+    pool.object_by_id(parent)
+        .and_then(object_refs)
+        .map_or(vec![], |refs| {
+            refs.iter()
+                .filter(|r| r.id == id)
+                .map(|r| (r.offset.x, r.offset.y))
+                .collect()
+        })
+}
+
+fn format_placements(placements: &[(i16, i16)]) -> String {
+    if placements.is_empty() {
+        return "-".to_string();
+    }
+    let placements: Vec<String> = placements
+        .iter()
+        .map(|(x, y)| format!("({}, {})", x, y))
+        .collect();
+    placements.join(", ")
+}
+
+/// How the change between the pools affected the object, including moves by its parents.
+/// None if the object is not affected.
+pub fn object_change(
+    before: &ObjectPool,
+    after: &ObjectPool,
+    id: ObjectId,
+) -> Option<ObjectChange> {
+    let mut attributes = match (before.object_by_id(id), after.object_by_id(id)) {
+        (None, Some(_)) => return Some(ObjectChange::Added),
+        (Some(_), None) => return Some(ObjectChange::Removed),
+        (Some(old), Some(new)) if old != new => diff_objects(old, new),
+        _ => vec![],
+    };
+    let parents: BTreeSet<ObjectId> = after
+        .objects()
+        .iter()
+        .chain(before.objects())
+        .filter(|parent| object_refs(parent).is_some_and(|refs| refs.iter().any(|r| r.id == id)))
+        .map(|parent| parent.id())
+        .collect();
+    for parent in parents {
+        let (old, new) = (
+            placements(before, parent, id),
+            placements(after, parent, id),
+        );
+        if old == new {
+            continue;
+        }
+        let parent_type = after
+            .object_by_id(parent)
+            .or(before.object_by_id(parent))
+            .map(|parent| parent.object_type());
+        attributes.push(AttributeChange {
+            attribute: match parent_type {
+                Some(parent_type) => format!("position in {}", object_label(parent_type, parent)),
+                None => "position".to_string(),
+            },
+            old_value: format_placements(&old),
+            new_value: format_placements(&new),
+        });
+    }
+    (!attributes.is_empty()).then_some(ObjectChange::Modified(attributes))
+}
+
+/// A change in the history that affected an object
+#[derive(Debug, Clone)]
+pub struct ObjectHistoryEntry<'a> {
+    pub change: &'a Change,
+
+    /// Whether the change was undone and can be redone
+    pub undone: bool,
+    pub object_change: ObjectChange,
+}
+
+/// Every change in the undo and redo history that affected the object, oldest first
+pub fn object_history<'a>(
+    current: &ObjectPool,
+    undo: &'a [Change],
+    redo: &'a [Change],
+    id: ObjectId,
+) -> Vec<ObjectHistoryEntry<'a>> {
+    let mut entries = Vec::new();
+    // Undo entries hold the pool before their change, redo entries the pool after it
+    for (index, change) in undo.iter().enumerate() {
+        let after = undo.get(index + 1).map_or(current, |next| &next.pool);
+        if let Some(object_change) = object_change(&change.pool, after, id) {
+            entries.push(ObjectHistoryEntry {
+                change,
+                undone: false,
+                object_change,
+            });
+        }
+    }
+    for (index, change) in redo.iter().enumerate().rev() {
+        let before = redo.get(index + 1).map_or(current, |next| &next.pool);
+        if let Some(object_change) = object_change(before, &change.pool, id) {
+            entries.push(ObjectHistoryEntry {
+                change,
+                undone: true,
+                object_change,
+            });
+        }
+    }
+    entries
+}
+
+/// Which entries the history panel shows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct HistoryFilter {
+    /// Only changes of this category
+    pub category: Option<ChangeCategory>,
+
+    /// Only changes that edit, add, remove or move this object
+    pub object: Option<ObjectId>,
+
+    /// Only changes whose description contains the text, ignoring case
+    pub text: String,
+}
+
+impl HistoryFilter {
+    pub fn is_active(&self) -> bool {
+        self.category.is_some() || self.object.is_some() || !self.text.trim().is_empty()
+    }
+
+    /// Check if the change from `before` to `after` passes the filter
+    pub fn matches(&self, change: &Change, before: &ObjectPool, after: &ObjectPool) -> bool {
+        let text = self.text.trim().to_lowercase();
+        self.category
+            .is_none_or(|category| change.category == category)
+            && (text.is_empty() || change.description.to_lowercase().contains(&text))
+            && self
+                .object
+                .is_none_or(|id| object_change(before, after, id).is_some())
+    }
+}
+
+/// When the history is trimmed
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq)]
+pub struct HistoryLimits {
+    /// Maximum number of undo and redo steps
+    pub max_entries: usize,
+
+    /// Maximum memory of the undo and redo steps in bytes
+    pub max_memory: usize,
+}
+
+impl Default for HistoryLimits {
+    fn default() -> Self {
+        HistoryLimits {
+            max_entries: 50,
+            max_memory: 64 * 1024 * 1024,
+        }
+    }
+}
+
+impl HistoryLimits {
+    pub fn exceeded_by(&self, entries: usize, memory: usize) -> bool {
+        entries > self.max_entries || memory > self.max_memory
+    }
+}
+
+/// The objects that turn one pool into another, so a history entry does not need a full pool
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct PoolPatch {
+    /// IOP data of the added and modified objects
+    objects: Vec<u8>,
+
+    /// IDs of the removed objects
+    removed: Vec<u16>,
+
+    /// IDs of all objects in pool order
+    order: Vec<u16>,
+}
+
+impl PoolPatch {
+    /// Patch that turns `from` into `to`
+    pub fn between(from: &ObjectPool, to: &ObjectPool) -> Self {
+        let mut changed = ObjectPool::new();
+        for object in to.objects() {
+            if from.object_by_id(object.id()) != Some(object) {
+                changed.add(object.clone());
+            }
+        }
+        PoolPatch {
+            objects: changed.as_iop(),
+            removed: from
+                .objects()
+                .iter()
+                .filter(|o| to.object_by_id(o.id()).is_none())
+                .map(|o| o.id().value())
+                .collect(),
+            order: to.objects().iter().map(|o| o.id().value()).collect(),
+        }
+    }
+
+    /// Apply the patch to the pool it was created from
+    pub fn apply(&self, from: &ObjectPool) -> ObjectPool {
+        let mut objects: HashMap<ObjectId, _> = from
+            .objects()
+            .iter()
+            .filter(|o| !self.removed.contains(&o.id().value()))
+            .map(|o| (o.id(), o.clone()))
+            .collect();
+        for object in ObjectPool::from_iop(self.objects.clone()).objects() {
+            objects.insert(object.id(), object.clone());
+        }
+
+        let mut pool = from.clone();
+        *pool.objects_mut() = self
+            .order
+            .iter()
+            .filter_map(|id| ObjectId::new(*id).ok())
+            .filter_map(|id| objects.remove(&id))
+            .collect();
+        pool
+    }
+}
+
+/// A history entry as it is stored in the project file
+#[derive(Serialize, Deserialize, Debug, Clone)]
+pub struct StoredChange {
+    pub description: String,
+    pub category: ChangeCategory,
+    pub author: String,
+    #[serde(default)]
+    pub scoped_undo: bool,
+
+    /// Turns the pool after this entry (or the current pool for the last entry) into the pool of
+    /// this entry
+    pub patch: PoolPatch,
+}
+
+/// Undo and redo history as it is stored in the project file
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct StoredHistory {
+    pub undo: Vec<StoredChange>,
+    pub redo: Vec<StoredChange>,
+}
+
+impl StoredHistory {
+    /// Store both history stacks as patches relative to the current pool
+    pub fn new(current: &ObjectPool, undo: &[Change], redo: &[Change]) -> Self {
+        StoredHistory {
+            undo: store_stack(current, undo),
+            redo: store_stack(current, redo),
+        }
+    }
+
+    /// Rebuild the undo and redo stacks from the current pool
+    pub fn restore(&self, current: &ObjectPool) -> (Vec<Change>, Vec<Change>) {
+        (
+            restore_stack(current, &self.undo),
+            restore_stack(current, &self.redo),
+        )
+    }
+}
+
+/// The top of a stack is the change closest to the current pool, so every entry is stored
+/// relative to the entry above it
+fn store_stack(current: &ObjectPool, stack: &[Change]) -> Vec<StoredChange> {
+    let mut newer = current;
+    let mut stored: Vec<StoredChange> = stack
+        .iter()
+        .rev()
+        .map(|change| {
+            let patch = PoolPatch::between(newer, &change.pool);
+            newer = &change.pool;
+            StoredChange {
+                description: change.description.clone(),
+                category: change.category,
+                author: change.author.clone(),
+                scoped_undo: change.scoped_undo,
+                patch,
+            }
+        })
+        .collect();
+    stored.reverse();
+    stored
+}
+
+fn restore_stack(current: &ObjectPool, stored: &[StoredChange]) -> Vec<Change> {
+    let mut newer = current.clone();
+    let mut stack: Vec<Change> = stored
+        .iter()
+        .rev()
+        .map(|change| {
+            let pool = change.patch.apply(&newer);
+            newer = pool.clone();
+            Change {
+                scoped_undo: change.scoped_undo,
+                ..Change::new(
+                    pool,
+                    change.description.clone(),
+                    change.category,
+                    change.author.clone(),
+                )
+            }
+        })
+        .collect();
+    stack.reverse();
+    stack
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ag_iso_stack::object_pool::object::Macro;
+
+    fn pool(ids: &[u16]) -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        for id in ids {
+            pool.add(Object::Macro(Macro {
+                id: ObjectId::new(*id).unwrap(),
+                commands: vec![],
+            }));
+        }
+        pool
+    }
+
+    #[test]
+    fn added_and_removed_objects_are_described() {
+        assert_eq!(
+            describe_pool_change(&pool(&[1]), &pool(&[1, 2])),
+            ("Add Macro 0x0002".to_string(), ChangeCategory::Create)
+        );
+        assert_eq!(
+            describe_pool_change(&pool(&[1, 2, 3]), &pool(&[1])),
+            ("Delete 2 objects".to_string(), ChangeCategory::Delete)
+        );
+        assert_eq!(
+            describe_pool_change(&pool(&[1]), &pool(&[2])),
+            (
+                "Add 1 and delete 1 objects".to_string(),
+                ChangeCategory::BulkChange
+            )
+        );
+        assert_eq!(
+            describe_pool_change(&pool(&[1]), &pool(&[1])),
+            ("Edit".to_string(), ChangeCategory::Edit)
+        );
+    }
+}
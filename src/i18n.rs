@@ -0,0 +1,1293 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+/// Language of the designer user interface
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Language {
+    #[default]
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 2] = [Language::English, Language::German];
+
+    /// Name of the language in the language itself
+    pub fn native_name(&self) -> &'static str {
+        match self {
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    /// ISO 639-1 code
+    pub fn code(&self) -> &'static str {
+        match self {
+            Language::English => "en",
+            Language::German => "de",
+        }
+    }
+
+    /// Language from a locale like "de_DE.UTF-8", None if it is not supported
+    pub fn from_locale(locale: &str) -> Option<Language> {
+        let code = locale.get(..2)?.to_ascii_lowercase();
+        Language::ALL.into_iter().find(|l| l.code() == code)
+    }
+
+    /// Language of the system, English if it is not supported
+    pub fn system() -> Language {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| std::env::var(var).ok())
+            .find_map(|locale| Language::from_locale(&locale))
+            .unwrap_or_default()
+    }
+}
+
+static LANGUAGE: AtomicU8 = AtomicU8::new(0);
+
+/// Get the language of the user interface
+pub fn language() -> Language {
+    Language::ALL[LANGUAGE.load(Ordering::Relaxed) as usize]
+}
+
+/// Set the language of the user interface
+pub fn set_language(language: Language) {
+    let index = Language::ALL
+        .iter()
+        .position(|l| *l == language)
+        .unwrap_or(0);
+    LANGUAGE.store(index as u8, Ordering::Relaxed);
+}
+
+/// Translate a user interface text. The English text is the key,
+/// texts without a translation are shown in English.
+pub fn tr(text: &'static str) -> &'static str {
+    let catalog = match language() {
+        Language::English => return text,
+        Language::German => german(),
+    };
+    catalog.get(text).copied().unwrap_or(text)
+}
+
+/// Translate a text and replace its `{}` placeholders by the arguments, in order
+pub fn tr_args(text: &'static str, args: &[&dyn Display]) -> String {
+    let mut result = String::new();
+    let mut parts = tr(text).split("{}");
+    if let Some(first) = parts.next() {
+        result.push_str(first);
+    }
+    for (index, part) in parts.enumerate() {
+        if let Some(arg) = args.get(index) {
+            result.push_str(&arg.to_string());
+        }
+        result.push_str(part);
+    }
+    result
+}
+
+fn german() -> &'static HashMap<&'static str, &'static str> {
+    static CATALOG: OnceLock<HashMap<&'static str, &'static str>> = OnceLock::new();
+    CATALOG.get_or_init(|| GERMAN.iter().copied().collect())
+}
+
+/// German translations, keyed by the English text
+const GERMAN: &[(&str, &str)] = &[
+    // Top bar
+    ("Previous selection ({})", "Vorherige Auswahl ({})"),
+    ("Next selection ({})", "Nächste Auswahl ({})"),
+    ("Edit", "Bearbeiten"),
+    (
+        "Click objects in the preview to select them",
+        "Objekte in der Vorschau anklicken, um sie auszuwählen",
+    ),
+    ("Simulate", "Simulieren"),
+    (
+        "Interact with the preview like on a terminal",
+        "Die Vorschau wie ein Terminal bedienen",
+    ),
+    ("Show {} hidden", "{} ausgeblendete anzeigen"),
+    (
+        "Show all objects that are hidden in the preview",
+        "Alle in der Vorschau ausgeblendeten Objekte anzeigen",
+    ),
+    ("Freeze flashing", "Blinken anhalten"),
+    (
+        "Show flashing objects in their normal state",
+        "Blinkende Objekte im Normalzustand anzeigen",
+    ),
+    ("No frame", "Kein Rahmen"),
+    ("6 keys, side column", "6 Tasten, seitliche Spalte"),
+    ("10 keys, dual column", "10 Tasten, zwei Spalten"),
+    ("6 keys, bottom row", "6 Tasten, untere Reihe"),
+    (
+        "Show the preview inside a terminal bezel with soft keys",
+        "Die Vorschau in einem Terminalrahmen mit Softkeys anzeigen",
+    ),
+    ("Virtual Mask size", "Virtuelle Maskengröße"),
+    ("Soft key size:", "Softkey-Größe:"),
+    (
+        "Designator sizes should be a multiple of 8 pixels",
+        "Designator-Größen sollten ein Vielfaches von 8 Pixeln sein",
+    ),
+    // File menu
+    ("File", "Datei"),
+    ("Project Files", "Projektdateien"),
+    ("Open Project (.aitp)", "Projekt öffnen (.aitp)"),
+    ("Save Project (.aitp)", "Projekt speichern (.aitp)"),
+    ("ISOBUS Files", "ISOBUS-Dateien"),
+    ("Import IOP (.iop)", "IOP importieren (.iop)"),
+    (
+        "Apply smart naming on import",
+        "Beim Import automatisch benennen",
+    ),
+    (
+        "Automatically apply smart naming to objects when importing IOP files",
+        "Objekte beim Import von IOP-Dateien automatisch sinnvoll benennen",
+    ),
+    ("Import images...", "Bilder importieren..."),
+    ("Export minified IOP...", "Minimiertes IOP exportieren..."),
+    ("Open Workspace (.aitw)", "Arbeitsbereich öffnen (.aitw)"),
+    ("Save Workspace (.aitw)", "Arbeitsbereich speichern (.aitw)"),
+    ("Workspace...", "Arbeitsbereich..."),
+    ("Image import", "Bildimport"),
+    ("Export IOP (.iop)", "IOP exportieren (.iop)"),
+    ("Export for AgIsoVirtualTerminal...", "Für AgIsoVirtualTerminal exportieren..."),
+    ("Export Header (.h)", "Header exportieren (.h)"),
+    // Add object and tools menus
+    ("Add object", "Objekt hinzufügen"),
+    ("Tools", "Werkzeuge"),
+    ("Colour roles...", "Farbrollen..."),
+    ("Colour usage...", "Farbverwendung..."),
+    ("Style presets...", "Stilvorlagen..."),
+    ("History...", "Verlauf..."),
+    ("Screenshot regression...", "Screenshot-Regression..."),
+    ("Validation...", "Validierung..."),
+    ("Terminal profiles...", "Terminalprofile..."),
+    ("Font metrics...", "Schriftmetriken..."),
+    ("Spell check...", "Rechtschreibprüfung..."),
+    ("Strings...", "Texte..."),
+    ("Macro references...", "Makroverweise..."),
+    ("Navigation map...", "Navigationskarte..."),
+    ("Similar objects...", "Ähnliche Objekte..."),
+    ("Tasks...", "Aufgaben..."),
+    ("Clean up orphan attributes...", "Verwaiste Attribute aufräumen..."),
+    ("Set font attributes...", "Schriftattribute setzen..."),
+    ("Transform selection...", "Auswahl transformieren..."),
+    ("Reference overlay...", "Referenzüberlagerung..."),
+    ("Picture graphic from clipboard", "Grafik aus der Zwischenablage"),
+    ("Copy", "Kopieren"),
+    (
+        "Copy the object and everything it references, to paste it into another pool",
+        "Objekt mit allen referenzierten Objekten kopieren, um es in einen anderen Pool einzufügen",
+    ),
+    ("Pool version...", "Pool-Version..."),
+    ("Terminal storage...", "Terminalspeicher..."),
+    ("Pointing events...", "Zeigeereignisse..."),
+    ("Input messages...", "Eingabenachrichten..."),
+    ("Preview matrix...", "Vorschaumatrix..."),
+    ("Plugins...", "Plugins..."),
+    ("Script console...", "Skriptkonsole..."),
+    ("Remote control...", "Fernsteuerung..."),
+    ("Golden pool", "Freigegebener Pool"),
+    (
+        "Update lock to current pool",
+        "Sperre auf aktuellen Pool aktualisieren",
+    ),
+    (
+        "Lock released IDs and names",
+        "Freigegebene IDs und Namen sperren",
+    ),
+    (
+        "Report an error when a released object is deleted or its ID changes",
+        "Einen Fehler melden, wenn ein freigegebenes Objekt gelöscht oder seine ID geändert wird",
+    ),
+    ("Remove lock", "Sperre entfernen"),
+    (
+        "Changelog against a baseline IOP",
+        "Änderungsprotokoll gegenüber einem Referenz-IOP",
+    ),
+    ("Export Markdown (.md)...", "Markdown exportieren (.md)..."),
+    ("Export CSV (.csv)...", "CSV exportieren (.csv)..."),
+    // Settings menu
+    ("Settings", "Einstellungen"),
+    ("Author name:", "Autorenname:"),
+    (
+        "Recorded on every change and review comment",
+        "Wird bei jeder Änderung und jedem Review-Kommentar gespeichert",
+    ),
+    ("Language:", "Sprache:"),
+    ("Theme:", "Design:"),
+    ("Follow system", "Wie System"),
+    ("Light", "Hell"),
+    ("Dark", "Dunkel"),
+    ("High contrast", "Hoher Kontrast"),
+    ("UI scale:", "Skalierung:"),
+    ("Touch mode", "Touch-Modus"),
+    (
+        "Larger controls, long press for context menus, drag and pinch the preview",
+        "Größere Bedienelemente, langes Drücken für Kontextmenüs, Vorschau ziehen und zoomen",
+    ),
+    (
+        "Size of the designer interface, separate from the virtual mask size",
+        "Größe der Oberfläche, unabhängig von der virtuellen Maskengröße",
+    ),
+    // Object list
+    ("Bookmarks ({})", "Lesezeichen ({})"),
+    ("Missing object {}", "Fehlendes Objekt {}"),
+    (
+        "No working set, please add a new working set...",
+        "Kein Working Set, bitte ein neues Working Set hinzufügen...",
+    ),
+    ("Sort by name", "Nach Name sortieren"),
+    ("Sort by id", "Nach ID sortieren"),
+    ("Sort objects", "Objekte sortieren"),
+    ("Rename", "Umbenennen"),
+    ("Rename object", "Objekt umbenennen"),
+    ("Delete", "Löschen"),
+    ("Delete object", "Objekt löschen"),
+    ("Bookmark", "Lesezeichen setzen"),
+    ("Remove bookmark", "Lesezeichen entfernen"),
+    ("Mirror", "Spiegeln"),
+    ("Duplicate mask...", "Maske duplizieren..."),
+    (
+        "Copy this mask, choosing which children are shared",
+        "Diese Maske kopieren und wählen, welche Kindobjekte geteilt werden",
+    ),
+    ("Lock", "Sperren"),
+    ("Unlock", "Entsperren"),
+    (
+        "Protect the object against modification",
+        "Das Objekt vor Änderungen schützen",
+    ),
+    (
+        "Modified since the last checkpoint",
+        "Seit dem letzten Checkpoint geändert",
+    ),
+    ("Modified since the last save", "Seit dem letzten Speichern geändert"),
+    ("Approximate rendering", "Näherungsweise Darstellung"),
+    ("Not rendered by the preview", "Von der Vorschau nicht dargestellt"),
+    (
+        "The preview of this object differs from how a terminal draws it:",
+        "Die Vorschau dieses Objekts weicht von der Darstellung auf einem Terminal ab:",
+    ),
+    // Object properties
+    ("This object is locked", "Dieses Objekt ist gesperrt"),
+    (
+        "Show spaces and control characters",
+        "Leerzeichen und Steuerzeichen anzeigen",
+    ),
+    ("Name:", "Name:"),
+    ("None", "Keine"),
+    ("Style preset", "Stilvorlage"),
+    ("Re-apply", "Erneut anwenden"),
+    (
+        "Reset the attributes of the preset to its values",
+        "Die Attribute der Vorlage auf ihre Werte zurücksetzen",
+    ),
+    // Repair dangling references
+    ("Repair dangling references", "Hängende Referenzen reparieren"),
+    (
+        "{} references missing object {}",
+        "{} referenziert das fehlende Objekt {}",
+    ),
+    ("Remove reference", "Referenz entfernen"),
+    ("Choose replacement", "Ersatz auswählen"),
+    ("Point to replacement", "Auf Ersatz zeigen"),
+    ("Recreate", "Neu erstellen"),
+    (
+        "Create an object with the missing ID and default attributes",
+        "Ein Objekt mit der fehlenden ID und Standardattributen erstellen",
+    ),
+    // Export verification
+    ("Export verification", "Exportprüfung"),
+    (
+        "{} reads back into the same {} objects ({} bytes)",
+        "{} wird wieder als dieselben {} Objekte gelesen ({} Bytes)",
+    ),
+    (
+        "{} does not read back into the exported pool",
+        "{} wird nicht wieder als der exportierte Pool gelesen",
+    ),
+    ("Unreadable bytes 0x{}..0x{}: {}", "Unlesbare Bytes 0x{}..0x{}: {}"),
+    ("Unexpected object {}", "Unerwartetes Objekt {}"),
+    ("Lost object {}", "Verlorenes Objekt {}"),
+    ("Changed object {}", "Geändertes Objekt {}"),
+    (
+        "Writing the read back pool again differs from byte 0x{}",
+        "Erneutes Schreiben des gelesenen Pools weicht ab Byte 0x{} ab",
+    ),
+    // Duplicate object IDs
+    ("Duplicate object IDs", "Doppelte Objekt-IDs"),
+    (
+        "Several objects in the pool have the same ID. The later ones get a new ID, choose which \
+         object each reference to a shared ID means.",
+        "Mehrere Objekte im Pool haben dieselbe ID. Die späteren erhalten eine neue ID, wählen Sie \
+         für jede Referenz auf eine geteilte ID das gemeinte Objekt.",
+    ),
+    ("New IDs", "Neue IDs"),
+    ("References", "Referenzen"),
+    (
+        "No object references a duplicate ID",
+        "Kein Objekt referenziert eine doppelte ID",
+    ),
+    ("references {}", "referenziert {}"),
+    ("{} with new ID {}", "{} mit neuer ID {}"),
+    ("{} keeping ID {}", "{} mit bisheriger ID {}"),
+    (
+        "The pool is read-only, allow editing to apply the new IDs",
+        "Der Pool ist schreibgeschützt, erlauben Sie die Bearbeitung, um die neuen IDs anzuwenden",
+    ),
+    ("Apply new IDs", "Neue IDs anwenden"),
+    ("Keep duplicates", "Duplikate behalten"),
+    (
+        "Only the first object with an ID can be edited",
+        "Nur das erste Objekt mit einer ID kann bearbeitet werden",
+    ),
+    // Mask thumbnails
+    ("Mask thumbnails", "Maskenvorschauen"),
+    (
+        "Show a strip with all masks below the editor",
+        "Eine Leiste mit allen Masken unter dem Editor anzeigen",
+    ),
+    // Backups
+    ("Backups...", "Sicherungen..."),
+    ("Backups to keep:", "Aufbewahrte Sicherungen:"),
+    (
+        "Timestamped copies of every save in the .backups folder next to the file, 0 turns them off",
+        "Kopien jeder Speicherung mit Zeitstempel im Ordner .backups neben der Datei, 0 schaltet sie aus",
+    ),
+    // Designer settings
+    ("More settings...", "Weitere Einstellungen..."),
+    // Project file format
+    ("Project file format...", "Format der Projektdatei..."),
+    // Designer settings window
+    ("Designer settings", "Designer-Einstellungen"),
+    ("Saved in {}", "Gespeichert in {}"),
+    ("The settings are not saved", "Die Einstellungen werden nicht gespeichert"),
+    ("The browser does not keep the settings, export them to keep them.", "Der Browser behält die Einstellungen nicht, zum Aufbewahren exportieren."),
+    ("Import...", "Importieren..."),
+    ("Export...", "Exportieren..."),
+    ("Reset to defaults", "Auf Standardwerte zurücksetzen"),
+    ("Key bindings", "Tastenkürzel"),
+    ("Undo", "Rückgängig"),
+    ("Redo", "Wiederholen"),
+    ("Undo within the active mask", "In der aktiven Maske rückgängig machen"),
+    ("Select previous object", "Vorheriges Objekt auswählen"),
+    ("Select next object", "Nächstes Objekt auswählen"),
+    ("Find object", "Objekt suchen"),
+    ("Run script", "Skript ausführen"),
+    ("Save", "Speichern"),
+    ("Press a shortcut...", "Tastenkürzel drücken..."),
+    ("Click and press the new shortcut, Escape cancels", "Anklicken und das neue Tastenkürzel drücken, Escape bricht ab"),
+    ("Reset to {}", "Auf {} zurücksetzen"),
+    ("'{}' and '{}' have the same shortcut", "'{}' und '{}' haben dasselbe Tastenkürzel"),
+    ("Export", "Export"),
+    ("Minified exports remove unused objects", "Minimierte Exporte entfernen unbenutzte Objekte"),
+    ("Minified exports merge equal attribute objects", "Minimierte Exporte fassen gleiche Attributobjekte zusammen"),
+    ("Minified exports compress picture graphics", "Minimierte Exporte komprimieren Bildgrafiken"),
+    ("Terminal profiles", "Terminalprofile"),
+    ("Imported pools start with these terminal profiles.", "Importierte Pools beginnen mit diesen Terminalprofilen."),
+    ("The built-in default terminal", "Das eingebaute Standardterminal"),
+    ("Use the profiles of the open project", "Die Profile des geöffneten Projekts verwenden"),
+    ("Custom fonts are not stored in the settings, they stay in the project", "Eigene Schriftarten werden nicht in den Einstellungen gespeichert, sie bleiben im Projekt"),
+    ("Use the built-in default", "Den eingebauten Standard verwenden"),
+    ("Reset the settings to their defaults", "Die Einstellungen auf ihre Standardwerte zurücksetzen"),
+    ("The settings were reset to their defaults", "Die Einstellungen wurden auf ihre Standardwerte zurückgesetzt"),
+    // Project file format window
+    ("Project file format", "Format der Projektdatei"),
+    ("Open a project to see its file format.", "Ein Projekt öffnen, um sein Dateiformat zu sehen."),
+    (", written by designer {}", ", geschrieben von Designer {}"),
+    ("Loaded file format: version {}{}", "Geladenes Dateiformat: Version {}{}"),
+    ("Format of this designer: version {}", "Format dieses Designers: Version {}"),
+    ("The project was saved by a newer designer. Saving it here drops what this designer does not know, so it was opened read-only.", "Das Projekt wurde von einem neueren Designer gespeichert. Beim Speichern hier geht verloren, was dieser Designer nicht kennt, deshalb wurde es schreibgeschützt geöffnet."),
+    ("\u{2022} Unknown annotations: {}", "\u{2022} Unbekannte Anmerkungen: {}"),
+    ("Allow editing anyway", "Trotzdem bearbeiten"),
+    ("Saving writes the format of this designer", "Beim Speichern wird das Format dieses Designers geschrieben"),
+    ("The project was upgraded when it was opened:", "Das Projekt wurde beim Öffnen aktualisiert:"),
+    ("Save as:", "Speichern als:"),
+    ("Version {} (current)", "Version {} (aktuell)"),
+    ("Version {}", "Version {}"),
+    ("Older designers may not be able to open the saved project.", "Ältere Designer können das gespeicherte Projekt möglicherweise nicht öffnen."),
+    ("For older designers, saving in this version leaves out:", "Für ältere Designer lässt das Speichern in dieser Version weg:"),
+    // Backups window
+    ("Backups", "Sicherungen"),
+    ("Save or open a pool or project to see its backups.", "Einen Pool oder ein Projekt speichern oder öffnen, um die Sicherungen zu sehen."),
+    ("Folder: {}", "Ordner: {}"),
+    ("Refresh", "Aktualisieren"),
+    ("Backups are turned off in the settings", "Sicherungen sind in den Einstellungen ausgeschaltet"),
+    ("The newest {} backups of every file are kept. Restoring replaces the pool as a single change, which can be undone. The names and other metadata restored from a project backup stay when it is undone.", "Die neuesten {} Sicherungen jeder Datei werden aufbewahrt. Das Wiederherstellen ersetzt den Pool als eine einzelne Änderung, die rückgängig gemacht werden kann. Die aus einer Projektsicherung wiederhergestellten Namen und anderen Metadaten bleiben dabei erhalten."),
+    ("No backups yet", "Noch keine Sicherungen"),
+    ("Restore", "Wiederherstellen"),
+    ("Open a project to restore into", "Ein Projekt öffnen, in das wiederhergestellt wird"),
+    ("Restored {} from {}", "{} vom {} wiederhergestellt"),
+    // Terminal storage window
+    ("Terminal storage", "Terminalspeicher"),
+    ("Terminals store uploaded pools under their version label. When a working set connects with a label that is already stored, the stored pool is loaded without comparing its content.", "Terminals speichern hochgeladene Pools unter ihrer Versionsbezeichnung. Verbindet sich ein Working Set mit einer bereits gespeicherten Bezeichnung, wird der gespeicherte Pool ohne Vergleich des Inhalts geladen."),
+    ("Version label:", "Versionsbezeichnung:"),
+    ("Bump version", "Version erhöhen"),
+    ("Connect working set", "Working Set verbinden"),
+    ("Clear storage", "Speicher leeren"),
+    ("Stored pools", "Gespeicherte Pools"),
+    ("No pools are stored", "Keine Pools gespeichert"),
+    ("Upload {}", "Upload {}"),
+    ("{} bytes", "{} Bytes"),
+    ("Log", "Protokoll"),
+    // Shared objects
+    ("\u{26A0} Used by {} objects, changes show up in all of them", "\u{26A0} Von {} Objekten verwendet, Änderungen erscheinen in allen"),
+    ("Also used by:", "Auch verwendet von:"),
+    ("Make a unique copy for {}", "Eigene Kopie für {} erstellen"),
+    ("Only this parent uses the copy, the other parents keep the original", "Nur dieses übergeordnete Objekt verwendet die Kopie, die anderen behalten das Original"),
+    // Terminal capabilities
+    ("Import capabilities...", "Fähigkeiten importieren..."),
+    ("Add a profile from the capability file of an installed terminal", "Profil aus der Fähigkeitsdatei eines installierten Terminals hinzufügen"),
+    ("Capture from CAN...", "Vom CAN erfassen..."),
+    ("Add a profile with the capabilities of a terminal on the bus", "Profil mit den Fähigkeiten eines Terminals am Bus hinzufügen"),
+    ("unknown", "unbekannt"),
+    ("Imported capabilities: VT version {}, {} font sizes", "Importierte Fähigkeiten: VT-Version {}, {} Schriftgrößen"),
+    ("The validator checks the pool against these capabilities", "Die Prüfung vergleicht den Pool mit diesen Fähigkeiten"),
+    ("Forget the imported capabilities", "Importierte Fähigkeiten verwerfen"),
+    ("Capture terminal capabilities", "Terminalfähigkeiten erfassen"),
+    ("Asks the terminal on the bus for its hardware, memory, soft keys and fonts and adds a terminal profile with the answers.", "Fragt das Terminal am Bus nach Hardware, Speicher, Softkeys und Schriften und fügt ein Terminalprofil mit den Antworten hinzu."),
+    ("Interface:", "Schnittstelle:"),
+    ("Source address:", "Quelladresse:"),
+    ("Capture", "Erfassen"),
+    // Transform window
+    ("Transform selection", "Auswahl transformieren"),
+    ("Select a mask, container or other object with positioned children", "Maske, Container oder anderes Objekt mit positionierten Kindobjekten auswählen"),
+    ("Children of {}:", "Kindobjekte von {}:"),
+    ("Move:", "Verschieben:"),
+    ("Scale positions:", "Positionen skalieren:"),
+    ("Scale sizes too", "Größen ebenfalls skalieren"),
+    ("Children that are used in other places are resized there too", "Kindobjekte, die an anderen Stellen verwendet werden, ändern dort ebenfalls ihre Größe"),
+    ("Positions are scaled from the top left corner of the parent, then moved", "Positionen werden von der oberen linken Ecke des übergeordneten Objekts skaliert und dann verschoben"),
+    // Simulator window
+    ("The list has no items", "Die Liste hat keine Einträge"),
+    ("Cross-check the rendering against the reference VT server implementation. The simulated terminal uses the hardware of the active terminal profile.", "Darstellung mit der Referenzimplementierung des VT-Servers vergleichen. Das simulierte Terminal verwendet die Hardware des aktiven Terminalprofils."),
+    ("Data mask size", "Datenmaskengröße"),
+    ("Soft key designator", "Softkey-Bezeichner"),
+    ("Physical soft keys", "Physische Softkeys"),
+    ("VT version", "VT-Version"),
+    ("Save {}...", "{} speichern..."),
+    ("AgIsoVirtualTerminal can not be started with a pool or load one from a file. Upload the exported pool from a working set and enter the hardware above in its settings.", "AgIsoVirtualTerminal kann nicht mit einem Pool gestartet werden und keinen Pool aus einer Datei laden. Den exportierten Pool von einem Working Set hochladen und die obige Hardware in seinen Einstellungen eintragen."),
+    // Reference overlay window
+    ("Reference overlay", "Referenzüberlagerung"),
+    ("Show a screenshot of a real terminal or of AgIsoVirtualTerminal over the preview. Take it of the data mask area only, at its native resolution.", "Bildschirmfoto eines echten Terminals oder von AgIsoVirtualTerminal über der Vorschau anzeigen. Nur den Datenmaskenbereich in nativer Auflösung aufnehmen."),
+    ("Load screenshot...", "Bildschirmfoto laden..."),
+    ("Opacity", "Deckkraft"),
+    ("Nudge 1 px", "1 px verschieben"),
+    ("Tolerance", "Toleranz"),
+    ("Allowed difference per colour channel", "Erlaubte Abweichung pro Farbkanal"),
+    ("Compare with the preview", "Mit der Vorschau vergleichen"),
+    ("Mark every pixel where the preview differs in red", "Jedes abweichende Pixel der Vorschau rot markieren"),
+    ("{} of {} pixels differ ({}%)", "{} von {} Pixeln weichen ab ({}%)"),
+    ("Show differences", "Abweichungen anzeigen"),
+    ("Remove overlay", "Überlagerung entfernen"),
+    // History checkpoints
+    ("Set checkpoint", "Prüfpunkt setzen"),
+    ("Objects modified after the checkpoint are marked in the object list", "Nach dem Prüfpunkt geänderte Objekte werden in der Objektliste markiert"),
+    ("Objects modified since the checkpoint are marked", "Seit dem Prüfpunkt geänderte Objekte sind markiert"),
+    // Object properties
+    ("Released in the golden pool, changing this ID breaks compatibility", "Im freigegebenen Pool veröffentlicht, eine Änderung dieser ID bricht die Kompatibilität"),
+    ("Designator size {}x{} is not a multiple of 8 pixels", "Bezeichnergröße {}x{} ist kein Vielfaches von 8 Pixeln"),
+    ("Object {} does not fit in the {}x{} designator and will be clipped", "Objekt {} passt nicht in den {}x{}-Bezeichner und wird abgeschnitten"),
+    ("Fit to text", "An Text anpassen"),
+    ("Change the height to fit the wrapped text", "Höhe an den umbrochenen Text anpassen"),
+    ("Change the size to fit the text", "Größe an den Text anpassen"),
+    ("Live", "Laufend"),
+    ("Keep the size fitted while the text or font changes", "Größe angepasst halten, während sich Text oder Schrift ändern"),
+    ("Grid layout", "Rasteranordnung"),
+    ("Columns:", "Spalten:"),
+    ("Rows: {}", "Zeilen: {}"),
+    ("Spacing:", "Abstand:"),
+    ("Origin:", "Ursprung:"),
+    ("Horizontal alignment:", "Horizontale Ausrichtung:"),
+    ("Vertical alignment:", "Vertikale Ausrichtung:"),
+    ("Arrange the objects row by row in their current order", "Objekte zeilenweise in ihrer aktuellen Reihenfolge anordnen"),
+    ("{}: {} ({} commands)", "{}: {} ({} Befehle)"),
+    ("Missing macro {}", "Fehlendes Makro {}"),
+    ("Missing", "Fehlt"),
+    ("No event executes a macro", "Kein Ereignis führt ein Makro aus"),
+    ("Add event hook:", "Ereignis-Hook hinzufügen:"),
+    ("Select event", "Ereignis auswählen"),
+    ("Select macro", "Makro auswählen"),
+    ("New macro", "Neues Makro"),
+    ("Create an empty macro for this event and open it", "Leeres Makro für dieses Ereignis erstellen und öffnen"),
+    ("Event hooks:", "Ereignis-Hooks:"),
+    ("Preview State:", "Vorschauzustand:"),
+    ("Initial", "Anfangszustand"),
+    ("Pressed", "Gedrückt"),
+    ("Disabled", "Deaktiviert"),
+    ("(blank)", "(leer)"),
+    ("Formatting sandbox", "Formatierungs-Sandbox"),
+    ("Raw value:", "Rohwert:"),
+    ("Current", "Aktuell"),
+    ("Min", "Min"),
+    ("Max", "Max"),
+    ("+ offset ({})", "+ Offset ({})"),
+    ("× scale ({})", "× Skalierung ({})"),
+    ("Truncated", "Abgeschnitten"),
+    ("Rounded", "Gerundet"),
+    ("{} to {} decimals", "{} auf {} Nachkommastellen"),
+    ("Displayed", "Angezeigt"),
+    ("Range: {} to {}", "Bereich: {} bis {}"),
+    ("Scale is 0, every value displays as 0", "Skalierung ist 0, jeder Wert wird als 0 angezeigt"),
+    ("A VT shows at most 7 decimals, the rest is ignored", "Ein VT zeigt höchstens 7 Nachkommastellen, der Rest wird ignoriert"),
+    ("The scale is stored as a 32-bit float: {}", "Die Skalierung wird als 32-Bit-Gleitkommazahl gespeichert: {}"),
+    ("The field is filled with leading zeros up to its width", "Das Feld wird bis zu seiner Breite mit führenden Nullen gefüllt"),
+    ("Drag the endpoints in the preview to move them", "Endpunkte in der Vorschau ziehen, um sie zu verschieben"),
+    ("Start Angle:", "Startwinkel:"),
+    ("End Angle:", "Endwinkel:"),
+    ("Drag the angle handles in the preview to change the sweep", "Winkelgriffe in der Vorschau ziehen, um den Bogen zu ändern"),
+    ("Monochrome", "Monochrom"),
+    ("4-bit colour", "4-Bit-Farbe"),
+    ("8-bit colour", "8-Bit-Farbe"),
+    ("Raw", "Roh"),
+    ("Run-length", "Lauflänge"),
+    ("No image loaded", "Kein Bild geladen"),
+    ("Format:", "Format:"),
+    ("Encoding:", "Kodierung:"),
+    ("Data size: {} → {} bytes", "Datengröße: {} → {} Bytes"),
+    ("Mean colour error: {}", "Mittlerer Farbfehler: {}"),
+    ("Average RGB distance per pixel between the original and the conversion", "Mittlerer RGB-Abstand pro Pixel zwischen Original und Umwandlung"),
+    ("Original", "Original"),
+    ("Converted", "Umgewandelt"),
+    ("Apply conversion", "Umwandlung anwenden"),
+    ("Pick transparency colour", "Transparenzfarbe wählen"),
+    ("Click a pixel of the image to make its colour transparent", "Auf ein Pixel des Bildes klicken, um seine Farbe transparent zu machen"),
+    ("Index {}", "Index {}"),
+    ("Format: {}, {}", "Format: {}, {}"),
+    ("Convert format", "Format umwandeln"),
+    ("(value {})", "(Wert {})"),
+    ("Change to Non-Convex", "In nicht konvex ändern"),
+    ("Point {}: ({}, {})", "Punkt {}: ({}, {})"),
+    ("Drag points to move them, double click to insert, right click to delete", "Punkte zum Verschieben ziehen, Doppelklick zum Einfügen, Rechtsklick zum Löschen"),
+    ("Pattern editor", "Mustereditor"),
+    ("Fill patterns are not scaled, the width should be the actual width", "Füllmuster werden nicht skaliert, die Breite sollte die tatsächliche Breite sein"),
+    ("Pattern widths that are a multiple of 8 tile the same on every terminal", "Musterbreiten, die ein Vielfaches von 8 sind, kacheln auf jedem Terminal gleich"),
+    ("The pattern has {} colours, painting keeps them but only two can be picked", "Das Muster hat {} Farben, Malen behält sie, aber nur zwei können gewählt werden"),
+    ("Size:", "Größe:"),
+    ("Background:", "Hintergrund:"),
+    ("Foreground:", "Vordergrund:"),
+    ("Click or drag to paint, the first pixel decides the colour", "Klicken oder ziehen zum Malen, das erste Pixel bestimmt die Farbe"),
+    ("Preview:", "Vorschau:"),
+    // Bulk rename
+    ("Bulk rename...", "Massenumbenennung..."),
+    ("Bulk rename", "Massenumbenennung"),
+    ("Pattern:", "Muster:"),
+    ("First number:", "Erste Nummer:"),
+    (
+        "Placeholders: {n} counter, {n:3} counter with three digits, {id}, {type}, {name} \
+         current name, {mask} mask the object is on, {parent} referencing object",
+        "Platzhalter: {n} Zähler, {n:3} Zähler mit drei Ziffern, {id}, {type}, {name} aktueller \
+         Name, {mask} Maske des Objekts, {parent} referenzierendes Objekt",
+    ),
+    (
+        "{} objects, {} locked objects keep their name",
+        "{} Objekte, {} gesperrte Objekte behalten ihren Namen",
+    ),
+    (
+        "{} objects would be {} in the exported header",
+        "{} Objekte wären {} im exportierten Header",
+    ),
+    ("Renamed {} objects", "{} Objekte umbenannt"),
+    // Upload simulation
+    ("Simulate upload...", "Upload simulieren..."),
+    ("Upload simulation", "Upload-Simulation"),
+    (
+        "The active terminal profile has no pool size budget, set the memory of the terminal to \
+         simulate the upload",
+        "Das aktive Terminalprofil hat kein Poolgrößen-Budget, legen Sie den Speicher des \
+         Terminals fest, um den Upload zu simulieren",
+    ),
+    (
+        "{} with {} of memory, the pool is {}",
+        "{} mit {} Speicher, der Pool ist {} groß",
+    ),
+    ("Simulate again", "Erneut simulieren"),
+    ("The pool fits, {} are left", "Der Pool passt, {} bleiben frei"),
+    ("The pool is {} too large", "Der Pool ist {} zu groß"),
+    ("The upload fails on object {}", "Der Upload scheitert an Objekt {}"),
+    ("Largest objects", "Größte Objekte"),
+    ("{}, saves {}", "{}, spart {}"),
+    ("Suggested reductions", "Vorgeschlagene Verkleinerungen"),
+    (
+        "Export a minified pool to apply them, the pool then fits",
+        "Exportieren Sie einen minimierten Pool, um sie anzuwenden, dann passt der Pool",
+    ),
+    // Mask printing
+    ("Print mask...", "Maske drucken..."),
+    ("Print mask", "Maske drucken"),
+    ("Mask: {}", "Maske: {}"),
+    ("Scale:", "Maßstab:"),
+    (" mm per pixel", " mm pro Pixel"),
+    ("Fit to page", "An Seite anpassen"),
+    ("Show object IDs", "Objekt-IDs anzeigen"),
+    ("Show grid every", "Raster anzeigen alle"),
+    ("Print", "Drucken"),
+    ("Save as PDF...", "Als PDF speichern..."),
+    // Design review document
+    ("Export design review (.pdf)", "Design-Review exportieren (.pdf)"),
+    // State sandbox
+    ("State sandbox...", "Zustands-Sandbox..."),
+    ("State sandbox", "Zustands-Sandbox"),
+    (
+        "Try the states the working set sets at runtime with Hide/Show and Enable/Disable \
+         commands. The pool is not changed.",
+        "Probieren Sie die Zustände aus, die das Working Set zur Laufzeit mit Hide/Show- und \
+         Enable/Disable-Befehlen setzt. Der Pool wird nicht geändert.",
+    ),
+    ("Pool state", "Zustand im Pool"),
+    ("Scenario:", "Szenario:"),
+    ("Delete scenario", "Szenario löschen"),
+    ("Save as scenario", "Als Szenario speichern"),
+    ("Shown", "Angezeigt"),
+    ("Enabled", "Aktiviert"),
+    ("differs from the pool", "weicht vom Pool ab"),
+    // Watch expressions
+    ("Watch expressions...", "Überwachungsausdrücke..."),
+    ("Watch expressions", "Überwachungsausdrücke"),
+    (
+        "Use var(ID) for the value of a number variable, e.g. var(0x8003)*0.1 - 40",
+        "var(ID) steht für den Wert einer Zahlenvariable, z. B. var(0x8003)*0.1 - 40",
+    ),
+    (
+        "Values entered in the preview are used in simulate mode",
+        "Im Simulationsmodus werden die in der Vorschau eingegebenen Werte verwendet",
+    ),
+    ("Invalid", "Ungültig"),
+    ("Add", "Hinzufügen"),
+    // Focus order
+    (
+        "Drag onto another number to move the object to that focus position",
+        "Auf eine andere Nummer ziehen, um das Objekt an diese Fokusposition zu verschieben",
+    ),
+    ("Focus order...", "Fokusreihenfolge..."),
+    ("Focus order", "Fokusreihenfolge"),
+    (
+        "Number the focus order on the preview",
+        "Fokusreihenfolge in der Vorschau nummerieren",
+    ),
+    (
+        "Terminals move the focus through the references of the mask in order. Reordering also \
+         changes which object is drawn on top.",
+        "Terminals bewegen den Fokus der Reihe nach durch die Referenzen der Maske. Das \
+         Umsortieren ändert auch, welches Objekt oben gezeichnet wird.",
+    ),
+    (
+        "The working set has no active mask",
+        "Das Working Set hat keine aktive Maske",
+    ),
+    (
+        "The active mask has no objects that can get the focus",
+        "Die aktive Maske hat keine Objekte, die den Fokus erhalten können",
+    ),
+    // Hex inspector
+    ("Hex inspector...", "Hex-Inspektor..."),
+    ("Hex inspector", "Hex-Inspektor"),
+    ("Expert mode", "Expertenmodus"),
+    (
+        "Allow editing the raw bytes of objects in the hex inspector",
+        "Bearbeiten der Rohdaten von Objekten im Hex-Inspektor erlauben",
+    ),
+    (
+        "Select an object to inspect its bytes",
+        "Wählen Sie ein Objekt aus, um seine Bytes anzuzeigen",
+    ),
+    ("{} with {} bytes", "{} mit {} Bytes"),
+    ("Offset", "Offset"),
+    ("Bytes", "Bytes"),
+    ("Field", "Feld"),
+    ("Value", "Wert"),
+    (
+        "Turn on expert mode in the settings to edit the bytes",
+        "Aktivieren Sie den Expertenmodus in den Einstellungen, um die Bytes zu bearbeiten",
+    ),
+    ("Edit bytes:", "Bytes bearbeiten:"),
+    ("Apply bytes", "Bytes anwenden"),
+    ("Reset", "Zurücksetzen"),
+    // Orphan attributes
+    ("Clean up orphan attributes", "Verwaiste Attribute aufräumen"),
+    (
+        "Every attribute and variable object is referenced",
+        "Jedes Attribut- und Variablenobjekt wird referenziert",
+    ),
+    ("All", "Alle"),
+    ("Select", "Auswählen"),
+    ("Delete {} objects, saves {}", "{} Objekte löschen, spart {}"),
+    ("Deleted {} objects", "{} Objekte gelöscht"),
+    // Set font attributes
+    ("Set font attributes", "Schriftattribute setzen"),
+    (
+        "Selected object and its children",
+        "Ausgewähltes Objekt und seine Kinder",
+    ),
+    (
+        "Objects shown in the object list",
+        "In der Objektliste angezeigte Objekte",
+    ),
+    (
+        "{} text objects, {} of them locked",
+        "{} Textobjekte, davon {} gesperrt",
+    ),
+    ("Choose font attributes", "Schriftattribute auswählen"),
+    ("Font attributes:", "Schriftattribute:"),
+    ("Apply", "Anwenden"),
+    ("Changed {} objects", "{} Objekte geändert"),
+    // Style presets
+    ("Style presets", "Stilvorlagen"),
+    ("Preset:", "Vorlage:"),
+    ("Add an empty preset", "Eine leere Vorlage hinzufügen"),
+    ("From selected object", "Aus ausgewähltem Objekt"),
+    (
+        "Add a preset with the attributes of the selected object and assign it to the object",
+        "Eine Vorlage mit den Attributen des ausgewählten Objekts hinzufügen und ihm zuweisen",
+    ),
+    (
+        "Presets bundle fonts, colours and options, so objects can be styled consistently and \
+         restyled at once",
+        "Vorlagen bündeln Schriften, Farben und Optionen, damit Objekte einheitlich gestaltet \
+         und auf einmal umgestaltet werden können",
+    ),
+    (
+        "Remove the preset, the objects keep their attributes",
+        "Die Vorlage entfernen, die Objekte behalten ihre Attribute",
+    ),
+    ("Remove attribute", "Attribut entfernen"),
+    (
+        "Add attribute of the selected object",
+        "Attribut des ausgewählten Objekts hinzufügen",
+    ),
+    ("Assigned to {} objects", "{} Objekten zugewiesen"),
+    ("Apply preset", "Vorlage anwenden"),
+    (
+        "Restyle all objects the preset is assigned to",
+        "Alle Objekte umgestalten, denen die Vorlage zugewiesen ist",
+    ),
+    (
+        "Selected object not found: {}",
+        "Ausgewähltes Objekt nicht gefunden: {}",
+    ),
+    // Preview
+    (
+        "Read-only: this pool was recovered from damaged data",
+        "Schreibgeschützt: dieser Pool wurde aus beschädigten Daten wiederhergestellt",
+    ),
+    ("Show report", "Bericht anzeigen"),
+    ("Allow editing", "Bearbeiten erlauben"),
+    (
+        "Edit the salvaged objects, skipped data is lost",
+        "Die geretteten Objekte bearbeiten, übersprungene Daten gehen verloren",
+    ),
+    ("{} validation error(s): {}", "{} Validierungsfehler: {}"),
+    ("Show all", "Alle anzeigen"),
+    (
+        "Missing data masks, please load a pool file or add a new mask...",
+        "Keine Datenmasken, bitte eine Pool-Datei laden oder eine neue Maske hinzufügen...",
+    ),
+    (
+        "No working sets, please add a new working set...",
+        "Keine Working Sets, bitte ein neues Working Set hinzufügen...",
+    ),
+    ("Missing data mask: {}", "Fehlende Datenmaske: {}"),
+    ("Preview", "Vorschau"),
+    ("Detach preview", "Vorschau abdocken"),
+    (
+        "Show the preview in a separate window",
+        "Die Vorschau in einem eigenen Fenster anzeigen",
+    ),
+    (
+        "The preview is shown in a separate window",
+        "Die Vorschau wird in einem eigenen Fenster angezeigt",
+    ),
+    ("Attach preview", "Vorschau andocken"),
+    // Status bar
+    ("{} objects", "{} Objekte"),
+    ("Select the working set", "Das Working Set auswählen"),
+    (
+        "Size of the object pool, the budget is set in the terminal profile",
+        "Größe des Objektpools, das Budget wird im Terminalprofil festgelegt",
+    ),
+    ("{} error(s), {} warning(s)", "{} Fehler, {} Warnung(en)"),
+    ("Unsaved changes", "Ungespeicherte Änderungen"),
+    ("Saved", "Gespeichert"),
+    ("Show the change history", "Den Änderungsverlauf anzeigen"),
+    ("Active terminal profile", "Aktives Terminalprofil"),
+    // Development notice
+    ("Under Active Development", "In aktiver Entwicklung"),
+    (
+        "This application is still under active development. Some features may be missing or broken. We appreciate your patience and feedback!",
+        "Diese Anwendung befindet sich noch in aktiver Entwicklung. Einige Funktionen fehlen möglicherweise oder funktionieren nicht. Wir danken für Ihre Geduld und Ihr Feedback!",
+    ),
+    (
+        "If you encounter issues, please report them at:",
+        "Probleme bitte hier melden:",
+    ),
+    // Object tree
+    ("Path {}/{}", "Pfad {}/{}"),
+    ("Layout horizontally", "Anordnung horizontal spiegeln"),
+    ("Layout horizontally (all levels)", "Anordnung horizontal spiegeln (alle Ebenen)"),
+    ("Position in parent horizontally", "Position im Elternobjekt horizontal spiegeln"),
+    ("Layout vertically", "Anordnung vertikal spiegeln"),
+    ("Layout vertically (all levels)", "Anordnung vertikal spiegeln (alle Ebenen)"),
+    ("Position in parent vertically", "Position im Elternobjekt vertikal spiegeln"),
+    ("Mirror the positions of all direct children", "Die Positionen aller direkten Kindobjekte spiegeln"),
+    ("Mirror the positions inside all child objects too, shared children change everywhere they are used", "Auch die Positionen in allen Kindobjekten spiegeln, gemeinsam genutzte Kindobjekte ändern sich überall, wo sie verwendet werden"),
+    ("Mirror the position of this object inside every parent that uses it", "Die Position dieses Objekts in jedem Elternobjekt spiegeln, das es verwendet"),
+    ("Show in preview", "In der Vorschau anzeigen"),
+    ("Hide in preview (edit mode only)", "In der Vorschau ausblenden (nur im Bearbeitungsmodus)"),
+    ("Missing object: {}", "Fehlendes Objekt: {}"),
+    ("Only show objects that are not reviewed yet", "Nur Objekte anzeigen, die noch nicht geprüft sind"),
+    ("Filter object by name...", "Objekte nach Namen filtern..."),
+    ("Search shortcut ({})", "Suchkürzel ({})"),
+    ("No object pool loaded, please load a pool file...", "Kein Objektpool geladen, bitte eine Pool-Datei laden..."),
+    // Dialogs
+    ("OK", "OK"),
+    ("New {}", "Neues Objekt: {}"),
+    ("Enter a name for the new object:", "Einen Namen für das neue Objekt eingeben:"),
+    ("Create", "Erstellen"),
+    ("Cancel", "Abbrechen"),
+    ("Damaged object pool", "Beschädigter Objektpool"),
+    ("Recovered {} objects, skipped {} of {} bytes.", "{} Objekte wiederhergestellt, {} von {} Bytes übersprungen."),
+    ("The salvaged objects are opened read-only for inspection.", "Die geretteten Objekte werden schreibgeschützt zur Ansicht geöffnet."),
+    ("Size", "Größe"),
+    ("Reason", "Grund"),
+    ("Undo {}({})", "Rückgängig {}({})"),
+    ("Redo {}({})", "Wiederholen {}({})"),
+    ("Undo the last change within the active mask {}({})", "Die letzte Änderung innerhalb der aktiven Maske rückgängig machen {}({})"),
+    ("File changed on disk", "Datei auf der Festplatte geändert"),
+    ("{} was changed outside of the designer, e.g. by a build script or a git pull.", "{} wurde außerhalb des Designers geändert, z. B. durch ein Build-Skript oder einen git pull."),
+    ("Reload", "Neu laden"),
+    ("Discard the local edits and load the file again", "Die lokalen Änderungen verwerfen und die Datei neu laden"),
+    ("Merge", "Zusammenführen"),
+    ("Keep local edits", "Lokale Änderungen behalten"),
+    ("Ignore the change, saving overwrites the file", "Die Änderung ignorieren, beim Speichern wird die Datei überschrieben"),
+    ("Open", "Öffnen"),
+    ("Keep the open project", "Das geöffnete Projekt behalten"),
+    ("Imported the settings from {}", "Die Einstellungen wurden aus {} importiert"),
+    ("Failed to import {}: {}", "{} konnte nicht importiert werden: {}"),
+    // Terminal profiles window
+    ("Profile:", "Profil:"),
+    ("Add profile", "Profil hinzufügen"),
+    ("Remove profile", "Profil entfernen"),
+    ("Pool size budget:", "Budget für die Poolgröße:"),
+    (" bytes", " Bytes"),
+    ("Object pool memory of the terminal, larger pools are reported as error", "Objektpool-Speicher des Terminals, größere Pools werden als Fehler gemeldet"),
+    ("Soft keys per page:", "Softkeys pro Seite:"),
+    ("Soft key masks with more keys are reported, they need paging", "Softkey-Masken mit mehr Tasten werden gemeldet, sie benötigen Seiten"),
+    ("Mask size:", "Maskengröße:"),
+    ("Colours:", "Farben:"),
+    ("256 colours", "256 Farben"),
+    ("16 colours", "16 Farben"),
+    // Fonts window
+    ("Fonts", "Schriftarten"),
+    ("Load TTF/OTF font...", "TTF/OTF-Schriftart laden..."),
+    ("No custom fonts, the built-in fonts are used for all sizes", "Keine eigenen Schriftarten, für alle Größen werden die eingebauten Schriftarten verwendet"),
+    ("Remove font", "Schriftart entfernen"),
+    ("Font mapping", "Schriftartzuordnung"),
+    ("Built-in", "Eingebaut"),
+    // Font metrics window
+    ("Font metrics", "Schriftmetriken"),
+    ("Terminal profile: {}", "Terminalprofil: {}"),
+    ("Sample text:", "Beispieltext:"),
+    ("Proportional height:", "Proportionale Höhe:"),
+    ("Inverted", "Invertiert"),
+    ("Bold, italic, underlined and crossed out styles are not simulated by the preview", "Fett, kursiv, unterstrichen und durchgestrichen werden von der Vorschau nicht simuliert"),
+    ("Font size", "Schriftgröße"),
+    ("Character cell", "Zeichenzelle"),
+    ("Rendered text", "Dargestellter Text"),
+    ("Characters per mask line", "Zeichen pro Maskenzeile"),
+    ("Sample", "Beispiel"),
+    ("Proportional {}", "Proportional {}"),
+    // Spell check window
+    ("Spell check", "Rechtschreibprüfung"),
+    ("Check spelling", "Rechtschreibung prüfen"),
+    ("Dictionaries:", "Wörterbücher:"),
+    ("Load dictionary...", "Wörterbuch laden..."),
+    ("No dictionaries loaded, load a word list or Hunspell .dic file", "Keine Wörterbücher geladen, eine Wortliste oder Hunspell-.dic-Datei laden"),
+    ("{} ({} words)", "{} ({} Wörter)"),
+    ("Remove", "Entfernen"),
+    ("Ignored words ({})", "Ignorierte Wörter ({})"),
+    ("Check again", "Erneut prüfen"),
+    ("Spell checking is off", "Die Rechtschreibprüfung ist ausgeschaltet"),
+    ("No misspelled words found", "Keine falsch geschriebenen Wörter gefunden"),
+    ("Ignore", "Ignorieren"),
+    // Plugins window
+    ("Plugins", "Plugins"),
+    ("Plugin directory:", "Plugin-Verzeichnis:"),
+    ("Set {} to change the default directory", "{} setzen, um das Standardverzeichnis zu ändern"),
+    ("No plugins found", "Keine Plugins gefunden"),
+    ("Running {} ({})", "{} läuft ({})"),
+    // Script console window
+    ("Script console", "Skriptkonsole"),
+    ("Available functions", "Verfügbare Funktionen"),
+    ("Run", "Ausführen"),
+    ("Pool updated", "Pool aktualisiert"),
+    ("No changes", "Keine Änderungen"),
+    // Macro references window
+    ("Macro references", "Makroverweise"),
+    ("No macros are used in this pool", "In diesem Pool werden keine Makros verwendet"),
+    ("Filter:", "Filter:"),
+    ("{} executes", "{} führt aus"),
+    ("macro {}", "Makro {}"),
+    ("{} on", "{} bei"),
+    ("Changed by {} of", "Geändert durch {} von"),
+    // Preview matrix window
+    ("Preview matrix", "Vorschaumatrix"),
+    ("Select a data or alarm mask", "Eine Daten- oder Alarmmaske auswählen"),
+    ("{} for {} terminal profiles", "{} für {} Terminalprofile"),
+    ("{}x{}, {}, {} soft keys of {}x{}", "{}x{}, {}, {} Softkeys mit {}x{}"),
+    // Pointing events window
+    ("Pointing events", "Zeigeereignisse"),
+    ("Clear", "Leeren"),
+    ("Switch the preview to simulate mode and click on the mask", "Die Vorschau in den Simulationsmodus schalten und auf die Maske klicken"),
+    ("Also under the pointer:", "Ebenfalls unter dem Zeiger:"),
+    // Navigation map window
+    ("Navigation map", "Navigationskarte"),
+    ("There are no masks in this pool", "In diesem Pool gibt es keine Masken"),
+    ("{} masks, {} links", "{} Masken, {} Verknüpfungen"),
+    ("{} unreachable", "{} nicht erreichbar"),
+    ("{} dead ends", "{} Sackgassen"),
+    ("Alarm mask", "Alarmmaske"),
+    ("Data mask", "Datenmaske"),
+    ("Links ({})", "Verknüpfungen ({})"),
+    ("{} {}, macro {}", "{} {}, Makro {}"),
+    // Similar objects window
+    ("Similar objects", "Ähnliche Objekte"),
+    ("Scan pool", "Pool durchsuchen"),
+    ("Objects that are the same apart from their IDs, values or strings", "Objekte, die bis auf ihre IDs, Werte oder Texte gleich sind"),
+    ("{} exact duplicate groups, {} near-identical groups", "{} Gruppen exakter Duplikate, {} Gruppen nahezu identischer Objekte"),
+    ("No similar objects found", "Keine ähnlichen Objekte gefunden"),
+    ("Exact duplicates", "Exakte Duplikate"),
+    ("Near-identical", "Nahezu identisch"),
+    ("{}: {} \u{d7} {} objects, {}% similar", "{}: {} \u{d7} {} Objekte, {}% ähnlich"),
+    ("Merge into first", "In das erste zusammenführen"),
+    // Tasks window
+    ("Tasks", "Aufgaben"),
+    ("Filter...", "Filtern..."),
+    ("{} of {} tasks", "{} von {} Aufgaben"),
+    ("No TODO or FIXME items in the object notes and review comments", "Keine TODO- oder FIXME-Einträge in den Objektnotizen und Review-Kommentaren"),
+    ("Notes", "Notizen"),
+    ("Review comment", "Review-Kommentar"),
+    // Workspace window
+    ("Workspace", "Arbeitsbereich"),
+    ("Create workspace from this project", "Einen Arbeitsbereich aus diesem Projekt erstellen"),
+    ("Pools", "Pools"),
+    ("Add pool or project...", "Pool oder Projekt hinzufügen..."),
+    ("Shared objects", "Gemeinsame Objekte"),
+    ("Share the selected object", "Das ausgewählte Objekt freigeben"),
+    ("Check consistency", "Konsistenz prüfen"),
+    ("Copy shared objects to all pools", "Gemeinsame Objekte in alle Pools kopieren"),
+    ("Replace the shared objects in the other pools", "Die gemeinsamen Objekte in den anderen Pools ersetzen"),
+    ("The pools are consistent", "Die Pools sind konsistent"),
+    // Minified export window
+    ("Export minified IOP", "Minimierte IOP exportieren"),
+    ("Remove unused objects", "Unbenutzte Objekte entfernen"),
+    ("Merge duplicate attribute objects", "Doppelte Attributobjekte zusammenführen"),
+    ("Run-length encode pictures where smaller", "Bilder lauflängenkodieren, wo es kleiner ist"),
+    ("Names, notes and other editor data are never part of the exported pool", "Namen, Notizen und andere Editordaten sind nie Teil des exportierten Pools"),
+    ("{} bytes \u{2192} {} bytes, {} bytes saved", "{} Bytes \u{2192} {} Bytes, {} Bytes gespart"),
+    ("Object {}", "Objekt {}"),
+    ("Removed unused objects ({})", "Entfernte unbenutzte Objekte ({})"),
+    ("Merged attribute objects ({})", "Zusammengeführte Attributobjekte ({})"),
+    ("Compressed pictures ({})", "Komprimierte Bilder ({})"),
+    ("{} bytes saved", "{} Bytes gespart"),
+    // Pool version window
+    ("Pool version", "Poolversion"),
+    ("VT version:", "VT-Version:"),
+    ("Bump the version when a changed pool is exported", "Die Version erhöhen, wenn ein geänderter Pool exportiert wird"),
+    ("Messages of the VT client", "Meldungen des VT-Clients"),
+    // Input messages window
+    ("Input messages", "Eingabemeldungen"),
+    ("Switch the preview to simulate mode and click on an input object", "Die Vorschau in den Simulationsmodus schalten und auf ein Eingabeobjekt klicken"),
+    // Strings window
+    ("Strings", "Texte"),
+    ("Over budget only", "Nur über dem Budget"),
+    ("{} strings, {} over budget", "{} Texte, {} über dem Budget"),
+    ("Object", "Objekt"),
+    ("Capacity", "Kapazität"),
+    ("Masks", "Masken"),
+    ("Translation", "Übersetzung"),
+    ("{} chars", "{} Zeichen"),
+    ("Not displayed or displayed with a proportional font", "Nicht angezeigt oder mit einer proportionalen Schriftart angezeigt"),
+    ("Untranslated", "Nicht übersetzt"),
+    ("Needs update", "Aktualisierung nötig"),
+    ("Translated", "Übersetzt"),
+    // Colour usage window
+    ("Colour usage", "Farbverwendung"),
+    ("{} colours used, {} change on 16 colour terminals, {} on monochrome terminals", "{} Farben verwendet, {} ändern sich auf Terminals mit 16 Farben, {} auf monochromen Terminals"),
+    ("Only colours that change on 16 colour or monochrome terminals", "Nur Farben, die sich auf Terminals mit 16 Farben oder monochromen Terminals ändern"),
+    ("Index {}: {}", "Index {}: {}"),
+    ("Colour", "Farbe"),
+    ("Used by", "Verwendet von"),
+    ("{} attributes", "{} Attribute"),
+    // Colour roles window
+    ("Colour roles", "Farbrollen"),
+    ("Add a copy of the selected theme", "Eine Kopie des ausgewählten Themas hinzufügen"),
+    ("Remove the selected theme", "Das ausgewählte Thema entfernen"),
+    ("Remove role", "Rolle entfernen"),
+    ("New role name...", "Name der neuen Rolle..."),
+    ("Add role", "Rolle hinzufügen"),
+    ("Apply theme", "Thema anwenden"),
+    ("Recolour all objects that have a colour role", "Alle Objekte mit einer Farbrolle neu einfärben"),
+    // History window
+    ("History", "Verlauf"),
+    ("Authors: {}", "Autoren: {}"),
+    ("{} entries, about {} of {}", "{} Einträge, etwa {} von {}"),
+    ("Keep at most", "Höchstens"),
+    ("entries and", "Einträge und"),
+    ("Repeated changes like drag steps are merged first, then the oldest changes are dropped", "Wiederholte Änderungen wie Ziehschritte werden zuerst zusammengefasst, dann werden die ältesten Änderungen verworfen"),
+    ("Save the history in the project file", "Den Verlauf in der Projektdatei speichern"),
+    ("Keeps undo and the record of changes after reopening the project", "Rückgängig und die Änderungsaufzeichnung bleiben nach dem erneuten Öffnen des Projekts erhalten"),
+    ("Undo the last change within the active mask", "Die letzte Änderung innerhalb der aktiven Maske rückgängig machen"),
+    ("No change only affected the active mask", "Keine Änderung betraf nur die aktive Maske"),
+    ("No changes yet", "Noch keine Änderungen"),
+    ("Search:", "Suche:"),
+    ("All categories", "Alle Kategorien"),
+    ("Only changes touching the selected object", "Nur Änderungen, die das ausgewählte Objekt betreffen"),
+    ("Any object", "Beliebiges Objekt"),
+    ("Clear filters", "Filter zurücksetzen"),
+    ("Change", "Änderung"),
+    ("Category", "Kategorie"),
+    ("Author", "Autor"),
+    ("No changes match the filters", "Keine Änderungen entsprechen den Filtern"),
+    ("Bulk change", "Massenänderung"),
+    ("Layout", "Layout"),
+    // Screenshot regression window
+    ("Screenshot regression", "Screenshot-Regression"),
+    ("Baseline directory:", "Referenzverzeichnis:"),
+    ("Browse...", "Durchsuchen..."),
+    ("Tolerance per colour channel", "Toleranz pro Farbkanal"),
+    ("Compare", "Vergleichen"),
+    ("Update baseline", "Referenz aktualisieren"),
+    ("Rendering mask {} of {}...", "Maske {} von {} wird gerendert..."),
+    ("{} of {} masks differ from the baseline", "{} von {} Masken weichen von der Referenz ab"),
+    ("All {} masks passed", "Alle {} Masken bestanden"),
+    ("Show diff", "Unterschiede anzeigen"),
+    ("Differences of mask {} (red)", "Unterschiede von Maske {} (rot)"),
+    // Remote control window
+    ("Remote control", "Fernsteuerung"),
+    ("Listening on ws://127.0.0.1:{}", "Wartet auf ws://127.0.0.1:{}"),
+    ("Token:", "Token:"),
+    ("Stop", "Stoppen"),
+    ("Port:", "Port:"),
+    ("Start", "Starten"),
+    ("JSON-RPC 2.0 methods:", "JSON-RPC-2.0-Methoden:"),
+    // Validation window
+    ("Validation", "Validierung"),
+    ("No issues found", "Keine Probleme gefunden"),
+    // Duplicate mask window
+    ("Duplicate mask", "Maske duplizieren"),
+    ("The mask has no children", "Die Maske hat keine Kindobjekte"),
+    ("Share", "Teilen"),
+    ("Reference the existing objects", "Die vorhandenen Objekte referenzieren"),
+    ("Copy the objects with new IDs", "Die Objekte mit neuen IDs kopieren"),
+    ("Also copy variables and attributes inside copied objects", "Auch Variablen und Attribute in kopierten Objekten kopieren"),
+    ("{} objects will be created", "{} Objekte werden erstellt"),
+    ("Duplicate", "Duplizieren"),
+    // Pool comparison
+    ("No object changes", "Keine Objektänderungen"),
+    ("Added {}", "Hinzugefügt: {}"),
+    ("Removed {}", "Entfernt: {}"),
+    ("Modified {}", "Geändert: {}"),
+    // Object history, notes and review
+    ("Change history", "Änderungsverlauf"),
+    ("No changes to this object in the history", "Keine Änderungen an diesem Objekt im Verlauf"),
+    ("Unknown", "Unbekannt"),
+    ("Added", "Hinzugefügt"),
+    ("Removed", "Entfernt"),
+    ("Lines with TODO or FIXME are listed in the tasks window", "Zeilen mit TODO oder FIXME werden im Aufgabenfenster aufgelistet"),
+    ("Review", "Review"),
+    ("Unreviewed", "Nicht geprüft"),
+    ("Approved", "Freigegeben"),
+    ("Needs changes", "Änderungen nötig"),
+    ("Set your author name in the settings to post comments", "Den eigenen Autorennamen in den Einstellungen festlegen, um Kommentare zu schreiben"),
+    ("Write a reply...", "Eine Antwort schreiben..."),
+    ("Write a comment...", "Einen Kommentar schreiben..."),
+    ("Post", "Senden"),
+    ("Cancel reply", "Antwort abbrechen"),
+    ("Reply", "Antworten"),
+    // Object attributes
+    ("Object ID:", "Objekt-ID:"),
+    ("ID already in use!", "ID wird bereits verwendet!"),
+    ("Type:", "Typ:"),
+    ("Move up", "Nach oben"),
+    ("Move down", "Nach unten"),
+    ("Missing object", "Fehlendes Objekt"),
+    ("Missing object in pool", "Fehlendes Objekt im Pool"),
+    ("Add object:", "Objekt hinzufügen:"),
+    ("Select existing object", "Vorhandenes Objekt auswählen"),
+    ("(view)", "(anzeigen)"),
+    ("Objects:", "Objekte:"),
+    ("Background Colour", "Hintergrundfarbe"),
+    ("Border Colour", "Rahmenfarbe"),
+    ("Selectable", "Auswählbar"),
+    ("Active Mask", "Aktive Maske"),
+    ("Soft Key Mask", "Softkey-Maske"),
+    ("Priority:", "Priorität:"),
+    ("Low", "Niedrig"),
+    ("Medium", "Mittel"),
+    ("High", "Hoch"),
+    ("Acoustic signal:", "Akustisches Signal:"),
+    ("Lowest", "Niedrigste"),
+    ("Highest", "Höchste"),
+    ("Hidden", "Ausgeblendet"),
+    ("Width", "Breite"),
+    ("Height", "Höhe"),
+    ("Key code:", "Tastencode:"),
+    ("Latchable", "Einrastend"),
+    ("Initial State:", "Anfangszustand:"),
+    ("Released", "Losgelassen"),
+    ("Latched", "Eingerastet"),
+    ("Suppress Border", "Rahmen unterdrücken"),
+    ("No Border", "Kein Rahmen"),
+    ("Variable reference:", "Variablenverweis:"),
+    ("Variable Reference:", "Variablenverweis:"),
+    ("Initial value:", "Anfangswert:"),
+    ("Initial Value:", "Anfangswert:"),
+    ("False", "Falsch"),
+    ("True", "Wahr"),
+    ("Input attributes:", "Eingabeattribute:"),
+    ("Transparent Background", "Transparenter Hintergrund"),
+    ("Auto Wrap", "Automatischer Umbruch"),
+    ("Wrap on Hyphen", "Umbruch am Bindestrich"),
+    ("Horizontal Justification:", "Horizontale Ausrichtung:"),
+    ("Vertical Justification:", "Vertikale Ausrichtung:"),
+    ("Left", "Links"),
+    ("Middle", "Mitte"),
+    ("Right", "Rechts"),
+    ("Display Leading Zeros", "Führende Nullen anzeigen"),
+    ("Display Zero as Blank", "Null als leer anzeigen"),
+    ("Truncate", "Abschneiden"),
+    ("Min: ", "Min.: "),
+    ("Max: ", "Max.: "),
+    ("Offset: ", "Offset: "),
+    ("Offset:", "Offset:"),
+    ("Scale: ", "Skalierung: "),
+    ("Number of Decimals: ", "Anzahl der Nachkommastellen: "),
+    ("Number of Decimals:", "Anzahl der Nachkommastellen:"),
+    ("Decimal", "Dezimal"),
+    ("Exponential", "Exponentiell"),
+    ("Real Time Editing", "Bearbeitung in Echtzeit"),
+    ("List items:", "Listeneinträge:"),
+    // Output shapes
+    ("Line Attributes:", "Linienattribute:"),
+    ("Fill Attributes:", "Füllattribute:"),
+    ("Line Direction:", "Linienrichtung:"),
+    ("Top-left to bottom-right", "Von oben links nach unten rechts"),
+    ("Bottom-left to top-right", "Von unten links nach oben rechts"),
+    ("Line Suppression:", "Linienunterdrückung:"),
+    ("Ellipse Type:", "Ellipsentyp:"),
+    ("Closed Ellipse", "Geschlossene Ellipse"),
+    ("Open Ellipse", "Offene Ellipse"),
+    ("Closed Ellipse Segment", "Geschlossenes Ellipsensegment"),
+    ("Closed Ellipse Section", "Geschlossener Ellipsenausschnitt"),
+    ("Polygon Type:", "Polygontyp:"),
+    ("Convex", "Konvex"),
+    ("Non-Convex", "Nicht konvex"),
+    ("Complex", "Komplex"),
+    ("Points:", "Punkte:"),
+    ("Point {}", "Punkt {}"),
+    ("Move Up", "Nach oben"),
+    ("Move Down", "Nach unten"),
+    ("Add Point", "Punkt hinzufügen"),
+    // Meters and bar graphs
+    ("Needle Colour", "Zeigerfarbe"),
+    ("Arc & Tick Colour", "Bogen- und Teilstrichfarbe"),
+    ("Draw Arc", "Bogen zeichnen"),
+    ("Draw Border", "Rahmen zeichnen"),
+    ("Draw Ticks", "Teilstriche zeichnen"),
+    ("Deflection Direction:", "Ausschlagrichtung:"),
+    ("Anti-clockwise", "Gegen den Uhrzeigersinn"),
+    ("Clockwise", "Im Uhrzeigersinn"),
+    ("Number of Ticks: ", "Anzahl der Teilstriche: "),
+    ("Min Value: ", "Minimalwert: "),
+    ("Max Value: ", "Maximalwert: "),
+    ("Bar Colour", "Balkenfarbe"),
+    ("Target Line Colour", "Farbe der Ziellinie"),
+    ("Draw Target Line", "Ziellinie zeichnen"),
+    ("Bar Graph Type:", "Balkendiagrammtyp:"),
+    ("Filled", "Gefüllt"),
+    ("Not Filled", "Nicht gefüllt"),
+    ("Axis Orientation:", "Achsenausrichtung:"),
+    ("Vertical", "Vertikal"),
+    ("Horizontal", "Horizontal"),
+    ("Grow Direction:", "Wachstumsrichtung:"),
+    ("Left/Down", "Links/Unten"),
+    ("Right/Up", "Rechts/Oben"),
+    ("Target Value Variable Reference:", "Variablenverweis für den Zielwert:"),
+    ("Target Value:", "Zielwert:"),
+    ("Bar Graph Width: ", "Breite des Balkendiagramms: "),
+    // Picture graphics and attributes
+    ("Actual Image Width: {}", "Tatsächliche Bildbreite: {}"),
+    ("Actual Image Height: {}", "Tatsächliche Bildhöhe: {}"),
+    ("Data Size (bytes): {}", "Datengröße (Bytes): {}"),
+    ("Transparent Pixels", "Transparente Pixel"),
+    ("Transparent Colour", "Transparente Farbe"),
+    ("Flashing", "Blinkend"),
+    ("Image:", "Bild:"),
+    ("Load Image", "Bild laden"),
+    ("Load an image file (PNG, JPG, BMP, etc.)", "Eine Bilddatei laden (PNG, JPG, BMP usw.)"),
+    ("Font Colour", "Schriftfarbe"),
+    ("Proportional Font Height (≥ 8):", "Proportionale Schrifthöhe (≥ 8):"),
+    ("Non-Proportional Font Size", "Nicht proportionale Schriftgröße"),
+    ("Proprietary Font", "Proprietäre Schriftart"),
+    ("Proprietary Font Value:", "Wert der proprietären Schriftart:"),
+    ("Font Type:", "Schrifttyp:"),
+    ("Font Style:", "Schriftstil:"),
+    ("Bold", "Fett"),
+    ("Crossed Out", "Durchgestrichen"),
+    ("Underlined", "Unterstrichen"),
+    ("Italic", "Kursiv"),
+    ("Flashing Inverted", "Blinkend invertiert"),
+    ("Flashing Hidden", "Blinkend ausgeblendet"),
+    ("Proportional", "Proportional"),
+    ("Line Colour", "Linienfarbe"),
+    ("Line Width", "Linienbreite"),
+    ("Line Art Pattern (16 bits):", "Linienmuster (16 Bit):"),
+    ("Each bit in this 16-bit pattern represents a 'paintbrush spot' along the line. ", "Jedes Bit in diesem 16-Bit-Muster steht für einen 'Pinselpunkt' entlang der Linie. "),
+    ("A '1' bit means that spot is drawn in the line color, while a '0' bit means that spot is skipped (shows background).", "Ein '1'-Bit bedeutet, dass der Punkt in der Linienfarbe gezeichnet wird, ein '0'-Bit bedeutet, dass der Punkt übersprungen wird (Hintergrund sichtbar)."),
+    ("Bit {}: {} ({}). Click to toggle.\n1 = Draw line colour\n0 = Skip (background)", "Bit {}: {} ({}). Zum Umschalten klicken.\n1 = Linienfarbe zeichnen\n0 = Überspringen (Hintergrund)"),
+    ("Current Binary Pattern:", "Aktuelles Binärmuster:"),
+    ("This shows the full 16-bit pattern of the line art. '1' bits represent drawn spots; '0' bits represent skipped spots.", "Zeigt das vollständige 16-Bit-Linienmuster. '1'-Bits stehen für gezeichnete Punkte, '0'-Bits für übersprungene Punkte."),
+    ("Fill Type:", "Fülltyp:"),
+    ("No fill", "Keine Füllung"),
+    ("No fill will be drawn, the background will be visible.", "Es wird keine Füllung gezeichnet, der Hintergrund bleibt sichtbar."),
+    ("Fill with line colour", "Mit Linienfarbe füllen"),
+    ("The area will be filled using the currently set line colour of the parent shape.", "Die Fläche wird mit der aktuell eingestellten Linienfarbe der übergeordneten Form gefüllt."),
+    ("Fill with specified colour", "Mit angegebener Farbe füllen"),
+    ("The area will be filled using the 'fill_colour' attribute specified below.", "Die Fläche wird mit dem unten angegebenen Attribut 'fill_colour' gefüllt."),
+    ("Fill with pattern", "Mit Muster füllen"),
+    ("The area will be filled using a pattern defined by a PictureGraphic object referenced below.", "Die Fläche wird mit einem Muster gefüllt, das durch ein unten referenziertes PictureGraphic-Objekt festgelegt ist."),
+    ("Fill Colour:", "Füllfarbe:"),
+    ("Select the colour index (0-255) to use for filling the area.", "Den Farbindex (0-255) für die Füllung der Fläche auswählen."),
+    ("Fill Colour", "Füllfarbe"),
+    ("Fill Pattern (PictureGraphic Object):", "Füllmuster (PictureGraphic-Objekt):"),
+    ("Missing pattern object", "Fehlendes Musterobjekt"),
+    // Macros, input validation and auxiliary objects
+    ("Macros:", "Makros:"),
+    ("Validation Type:", "Validierungstyp:"),
+    ("Valid Characters", "Gültige Zeichen"),
+    ("Invalid Characters", "Ungültige Zeichen"),
+    ("Validation String:", "Validierungstext:"),
+    ("Object reference:", "Objektverweis:"),
+    ("Macro Commands:", "Makrobefehle:"),
+    ("Add command:", "Befehl hinzufügen:"),
+    ("Select command", "Befehl auswählen"),
+    ("Function Type:", "Funktionstyp:"),
+    ("Critical", "Kritisch"),
+    ("Restricted", "Eingeschränkt"),
+    ("Single-assignment", "Einfachzuweisung"),
+    ("Pointer Type:", "Zeigertyp:"),
+    ("0 (Points to Auxiliary Object)", "0 (Zeigt auf Auxiliary-Objekt)"),
+    ("1 (Points to Assigned Aux Objects)", "1 (Zeigt auf zugewiesene Aux-Objekte)"),
+    ("2 (Points to WS Object of this Pool)", "2 (Zeigt auf WS-Objekt dieses Pools)"),
+    ("3 (Points to WS Object of Assigned)", "3 (Zeigt auf WS-Objekt des Zugewiesenen)"),
+    ("Auxiliary Object ID:", "Auxiliary-Objekt-ID:"),
+];
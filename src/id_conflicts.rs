@@ -0,0 +1,162 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Pools where several objects have the same ID: the later objects get new IDs, and the user
+//! decides per referencing object which of them its reference means.
+
+use std::collections::{BTreeSet, HashMap};
+
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+
+use crate::reference_paths::remap_references;
+use crate::reference_repair::expected_types;
+
+/// An object whose ID is already used by an earlier object in the pool
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdConflict {
+    /// Position of the object in the pool
+    pub index: usize,
+    pub id: ObjectId,
+    pub object_type: ObjectType,
+
+    /// The ID the object gets
+    pub new_id: ObjectId,
+}
+
+/// A reference to a conflicting ID, which can mean any of the objects with that ID
+#[derive(Debug, Clone, PartialEq)]
+pub struct ReferenceRewrite {
+    /// Position of the referencing object in the pool
+    pub index: usize,
+    pub object: ObjectId,
+
+    /// The referenced ID
+    pub id: ObjectId,
+
+    /// The conflict whose object is meant, by index in the conflicts. None keeps the reference
+    /// on the first object with the ID.
+    pub target: Option<usize>,
+}
+
+/// New IDs for the objects with a duplicate ID and the references that are rewritten to them
+#[derive(Debug, Clone, PartialEq)]
+pub struct IdConflictResolution {
+    pub conflicts: Vec<IdConflict>,
+    pub rewrites: Vec<ReferenceRewrite>,
+}
+
+/// Find the objects that have the ID of an earlier object, with proposed new IDs and the
+/// references that are ambiguous. References are proposed to point to the object with the type
+/// the reference expects, otherwise they stay on the first object. Returns None if all IDs are
+/// unique.
+pub fn find_id_conflicts(pool: &ObjectPool) -> Option<IdConflictResolution> {
+    let objects = pool.objects();
+    let mut first: HashMap<ObjectId, usize> = HashMap::new();
+    let mut duplicates = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        if first.insert(object.id(), index).is_some() {
+            duplicates.push(index);
+        }
+    }
+    if duplicates.is_empty() {
+        return None;
+    }
+
+    // New IDs are taken from the free IDs above the highest used one, then from the gaps
+    let used: BTreeSet<u16> = objects.iter().map(|object| object.id().value()).collect();
+    let highest = used.iter().next_back().copied().unwrap_or_default();
+    let mut free = (highest.saturating_add(1)..u16::MAX)
+        .chain(0..highest)
+        .filter(|id| !used.contains(id))
+        .filter_map(|id| ObjectId::new(id).ok());
+    let mut conflicts = Vec::new();
+    for index in duplicates {
+        let object = &objects[index];
+        let new_id = free.next()?;
+        conflicts.push(IdConflict {
+            index,
+            id: object.id(),
+            object_type: object.object_type(),
+            new_id,
+        });
+    }
+
+    let mut rewrites = Vec::new();
+    for (index, object) in objects.iter().enumerate() {
+        let referenced: BTreeSet<ObjectId> = object.referenced_objects().into_iter().collect();
+        for id in referenced {
+            let candidates: Vec<usize> = conflicts
+                .iter()
+                .enumerate()
+                .filter(|(_, conflict)| conflict.id == id)
+                .map(|(conflict, _)| conflict)
+                .collect();
+            if candidates.is_empty() {
+                continue;
+            }
+            let expected = expected_types(object, id);
+            let first_matches = objects
+                .iter()
+                .find(|o| o.id() == id)
+                .is_some_and(|o| expected.contains(&o.object_type()));
+            let target = if first_matches {
+                None
+            } else {
+                candidates
+                    .into_iter()
+                    .find(|c| expected.contains(&conflicts[*c].object_type))
+            };
+            rewrites.push(ReferenceRewrite {
+                index,
+                object: object.id(),
+                id,
+                target,
+            });
+        }
+    }
+
+    Some(IdConflictResolution {
+        conflicts,
+        rewrites,
+    })
+}
+
+impl IdConflictResolution {
+    /// Problems with the chosen IDs, the resolution can only be applied without problems
+    pub fn problems(&self, pool: &ObjectPool) -> Vec<String> {
+        let mut problems = Vec::new();
+        let mut new_ids = BTreeSet::new();
+        for conflict in &self.conflicts {
+            if pool.object_by_id(conflict.new_id).is_some() {
+                problems.push(format!("ID {} is already used", conflict.new_id.value()));
+            } else if !new_ids.insert(conflict.new_id) {
+                problems.push(format!(
+                    "ID {} is given to more than one object",
+                    conflict.new_id.value()
+                ));
+            }
+        }
+        problems
+    }
+
+    /// The pool with the new IDs and the rewritten references
+    pub fn apply(&self, pool: &ObjectPool) -> ObjectPool {
+        let mut resolved = pool.clone();
+        let objects = resolved.objects_mut();
+        for rewrite in &self.rewrites {
+            let Some(conflict) = rewrite.target.and_then(|c| self.conflicts.get(c)) else {
+                continue;
+            };
+            if let Some(object) = objects.get_mut(rewrite.index) {
+                remap_references(object, &HashMap::from([(rewrite.id, conflict.new_id)]));
+            }
+        }
+        for conflict in &self.conflicts {
+            if let Some(object) = objects.get_mut(conflict.index) {
+                *object.mut_id() = conflict.new_id;
+            }
+        }
+        resolved
+    }
+}
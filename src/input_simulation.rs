@@ -0,0 +1,267 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use ag_iso_stack::object_pool::object::{InputList, InputNumber, Object};
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+use eframe::egui;
+
+use crate::preview_state::{
+    object_state, preview_mode, with_preview_state, InputMessage, OpenInput, PreviewMode,
+};
+use crate::tr;
+
+/// Value entered in an input object while simulating, stored for its variable if it has one
+pub fn simulated_input_value(
+    ctx: &egui::Context,
+    object: ObjectId,
+    variable: Option<ObjectId>,
+) -> Option<u32> {
+    with_preview_state(ctx, |state| {
+        state.input_values.get(&variable.unwrap_or(object)).copied()
+    })
+}
+
+/// Value of a number variable while simulating, the value entered through an input object or
+/// else the value in the pool. None if there is no number variable with the ID.
+pub fn simulated_variable_value(ctx: &egui::Context, pool: &ObjectPool, id: u16) -> Option<u32> {
+    let id = ObjectId::new(id).ok()?;
+    let Some(Object::NumberVariable(variable)) = pool.object_by_id(id) else {
+        return None;
+    };
+    Some(simulated_input_value(ctx, id, None).unwrap_or(variable.value))
+}
+
+/// Raw value of an input number for a value in displayed units: displayed / scale - offset.
+/// None if no raw value shows that value.
+pub fn raw_from_displayed(displayed: f64, offset: i32, scale: f32) -> Option<u32> {
+    if scale == 0.0 {
+        return None;
+    }
+    let raw = (displayed / scale as f64 - offset as f64).round();
+    (0.0..=u32::MAX as f64).contains(&raw).then_some(raw as u32)
+}
+
+/// Open an enabled input object for input when it is clicked, like a touch VT does.
+/// Another input that is still open is aborted first. Returns whether the object is open.
+fn open_on_click(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    object: ObjectId,
+    enabled: bool,
+    entry: f64,
+) -> bool {
+    let enabled = object_state(ui.ctx(), object, enabled);
+    let response = ui.interact(
+        rect,
+        ui.id().with(("simulated_input", object.value())),
+        if enabled {
+            egui::Sense::click()
+        } else {
+            egui::Sense::hover()
+        },
+    );
+    let open = with_preview_state(ui.ctx(), |state| {
+        let open = state.open_input.is_some_and(|open| open.object == object);
+        if response.clicked() && !open {
+            if let Some(other) = state.open_input.take() {
+                state.log_input_message(InputMessage::Esc {
+                    object: other.object,
+                });
+            }
+            state.open_input = Some(OpenInput { object, entry });
+            state.log_input_message(InputMessage::SelectInputObject {
+                object,
+                open_for_input: true,
+            });
+            return true;
+        }
+        open
+    });
+    if open {
+        ui.painter().rect_stroke(
+            rect,
+            0.0,
+            egui::Stroke::new(2.0, ui.visuals().selection.stroke.color),
+            egui::StrokeKind::Inside,
+        );
+    }
+    open
+}
+
+/// Send a new value of an input object, storing it for its variable if it has one
+fn send_value(ctx: &egui::Context, object: ObjectId, variable: Option<ObjectId>, value: u32) {
+    with_preview_state(ctx, |state| {
+        state.input_values.insert(variable.unwrap_or(object), value);
+        state.log_input_message(InputMessage::ChangeNumericValue { object, value });
+    });
+}
+
+/// Close the open input object, accepting a value or aborting with ESC
+fn close_input(
+    ctx: &egui::Context,
+    object: ObjectId,
+    variable: Option<ObjectId>,
+    value: Option<u32>,
+) {
+    with_preview_state(ctx, |state| state.open_input = None);
+    match value {
+        Some(value) => send_value(ctx, object, variable, value),
+        None => with_preview_state(ctx, |state| {
+            state.log_input_message(InputMessage::Esc { object })
+        }),
+    }
+}
+
+/// Show the input dialog of an open input object below it
+fn input_popup(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    object: ObjectId,
+    add_contents: impl FnOnce(&mut egui::Ui),
+) {
+    // The preview may be in a zoomed layer, the popup is positioned on the screen
+    let rect = match ui.ctx().layer_transform_to_global(ui.layer_id()) {
+        Some(transform) => transform * rect,
+        None => rect,
+    };
+    egui::Area::new(egui::Id::new(("simulated_input_popup", object.value())))
+        .order(egui::Order::Foreground)
+        .fixed_pos(rect.left_bottom() + egui::vec2(0.0, 4.0))
+        .show(ui.ctx(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, add_contents);
+        });
+}
+
+/// Let the operator enter a value in an input number while simulating. The value is entered in
+/// displayed units, in steps of the last displayed decimal, and only accepted within the minimum
+/// and maximum value. With real time editing every valid change is sent right away.
+pub fn simulate_input_number(ui: &egui::Ui, rect: egui::Rect, input: &InputNumber, raw_value: u32) {
+    if preview_mode(ui.ctx()) != PreviewMode::Simulate {
+        return;
+    }
+    let displayed = |raw: u32| (raw as f64 + input.offset as f64) * input.scale as f64;
+    if !open_on_click(
+        ui,
+        rect,
+        input.id,
+        input.options2.enabled,
+        displayed(raw_value),
+    ) {
+        return;
+    }
+
+    let variable = input.variable_reference.0;
+    let decimals = input.nr_of_decimals.min(7) as usize;
+    let step = 10f64.powi(-(decimals as i32));
+    let Some(mut entry) =
+        with_preview_state(ui.ctx(), |state| state.open_input.map(|open| open.entry))
+    else {
+        return;
+    };
+
+    input_popup(ui, rect, input.id, |ui| {
+        let response = ui.add(
+            egui::DragValue::new(&mut entry)
+                .speed(step)
+                .fixed_decimals(decimals),
+        );
+        entry = (entry / step).round() * step;
+
+        let raw = raw_from_displayed(entry, input.offset, input.scale);
+        let accepted = raw.filter(|raw| (input.min_value..=input.max_value).contains(raw));
+        match (raw, accepted) {
+            (_, Some(raw)) => {
+                ui.weak(format!("Raw value: {}", raw));
+            }
+            _ => {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    format!(
+                        "Outside of the range {:.*} to {:.*}",
+                        decimals,
+                        displayed(input.min_value),
+                        decimals,
+                        displayed(input.max_value)
+                    ),
+                );
+            }
+        }
+
+        if input.options2.real_time_editing && response.changed() {
+            if let Some(raw) = accepted {
+                send_value(ui.ctx(), input.id, variable, raw);
+            }
+        }
+
+        let (enter, escape) = ui.input(|i| {
+            (
+                i.key_pressed(egui::Key::Enter),
+                i.key_pressed(egui::Key::Escape),
+            )
+        });
+        ui.horizontal(|ui| {
+            let enter = ui
+                .add_enabled(accepted.is_some(), egui::Button::new("Enter"))
+                .clicked()
+                || (enter && accepted.is_some());
+            if enter {
+                close_input(ui.ctx(), input.id, variable, accepted);
+            } else if ui.button("ESC").clicked() || escape {
+                close_input(ui.ctx(), input.id, variable, None);
+            }
+        });
+    });
+
+    with_preview_state(ui.ctx(), |state| {
+        if let Some(open) = state.open_input.as_mut().filter(|o| o.object == input.id) {
+            open.entry = entry;
+        }
+    });
+}
+
+/// Let the operator pick an item of an input list while simulating
+pub fn simulate_input_list(
+    ui: &egui::Ui,
+    rect: egui::Rect,
+    pool: &ObjectPool,
+    list: &InputList,
+    value: u8,
+) {
+    if preview_mode(ui.ctx()) != PreviewMode::Simulate {
+        return;
+    }
+    if !open_on_click(ui, rect, list.id, list.options.enabled, value as f64) {
+        return;
+    }
+
+    let variable = list.variable_reference.0;
+    input_popup(ui, rect, list.id, |ui| {
+        let mut picked = None;
+        for (index, item) in list.list_items.iter().enumerate() {
+            let label = match item.0.and_then(|id| pool.object_by_id(id)) {
+                Some(object) => format!(
+                    "{}: {:?} {}",
+                    index,
+                    object.object_type(),
+                    object.id().value()
+                ),
+                None => format!("{}: (empty)", index),
+            };
+            if ui
+                .selectable_label(index == value as usize, label)
+                .clicked()
+            {
+                picked = Some(index as u32);
+            }
+        }
+        if list.list_items.is_empty() {
+            ui.weak(tr("The list has no items"));
+        }
+        if let Some(index) = picked {
+            close_input(ui.ctx(), list.id, variable, Some(index));
+        } else if ui.button("ESC").clicked() || ui.input(|i| i.key_pressed(egui::Key::Escape)) {
+            close_input(ui.ctx(), list.id, variable, None);
+        }
+    });
+}
@@ -2,9 +2,17 @@
 //! SPDX-License-Identifier: GPL-3.0-or-later
 //! Authors: Daan Steenbergen
 
-use crate::RenderableObject;
-use ag_iso_stack::object_pool::object_attributes::Point;
-use ag_iso_stack::object_pool::{object::Object, ObjectId, ObjectPool};
+use std::collections::HashSet;
+
+use crate::preview_state::{
+    is_hidden_in_preview, object_state, preview_mode, with_preview_state, PointingEvent,
+    PreviewMode, TouchState,
+};
+use crate::smart_guides::{snap_rect, Guide, Snap};
+use crate::{object_refs, object_refs_mut, EditorProject, RenderableObject};
+use ag_iso_stack::object_pool::object::{Object, OutputLine};
+use ag_iso_stack::object_pool::object_attributes::{LineDirection, Point};
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
 use eframe::egui;
 
 /// Interactive wrapper for rendering masks with clickable objects
@@ -25,20 +33,31 @@ impl<'a> egui::Widget for InteractiveMaskRenderer<'a> {
             // Create a child UI for rendering the objects
             let mut child_ui = ui.new_child(egui::UiBuilder::new().max_rect(rect));
 
+            // While simulating, the objects themselves handle the pointer instead
+            let simulating = preview_mode(ui.ctx()) == PreviewMode::Simulate;
+
             // Render the objects normally
             self.object
                 .render(&mut child_ui, self.pool, Point::default());
 
+            if simulating {
+                self.log_pointing_events(ui, rect);
+            }
+
             // Handle interaction - check if pointer is interacting with this widget
-            if let Some(pointer_pos) = ui.ctx().pointer_hover_pos() {
+            // The response position is in the coordinates of the layer, which may be zoomed
+            if let Some(pointer_pos) = response.hover_pos().filter(|_| !simulating) {
                 // Check if the pointer is within our allocated rect
                 if rect.contains(pointer_pos) {
                     // Convert screen position to widget-relative position
                     let relative_pos =
                         egui::pos2(pointer_pos.x - rect.min.x, pointer_pos.y - rect.min.y);
 
-                    // Find what object is under the hover position
-                    if let Some((object_id, object_rect)) = self.find_object_at(relative_pos) {
+                    // Find what object is under the hover position, ignoring hidden objects
+                    let hidden = with_preview_state(ui.ctx(), |state| state.hidden_objects.clone());
+                    if let Some((object_id, object_rect)) =
+                        self.find_object_at(relative_pos, &hidden)
+                    {
                         // Draw highlight rectangle around the object
                         let screen_rect = egui::Rect::from_min_size(
                             rect.min + object_rect.min.to_vec2(),
@@ -64,7 +83,8 @@ impl<'a> egui::Widget for InteractiveMaskRenderer<'a> {
                             ),
                         );
 
-                        if response.clicked() {
+                        // Also select on right click or long press, for the context menu
+                        if response.clicked() || response.secondary_clicked() {
                             (self.selected_callback)(object_id);
                             ui.ctx().request_repaint(); // Force UI update
                         }
@@ -78,9 +98,114 @@ impl<'a> egui::Widget for InteractiveMaskRenderer<'a> {
 }
 
 impl<'a> InteractiveMaskRenderer<'a> {
+    /// Log presses and releases on the mask with the objects a touch VT would resolve them to
+    fn log_pointing_events(&self, ui: &egui::Ui, rect: egui::Rect) {
+        let (pointer_pos, state) = ui.input(|i| {
+            let state = if i.pointer.primary_pressed() {
+                Some(TouchState::Pressed)
+            } else if i.pointer.primary_released() {
+                Some(TouchState::Released)
+            } else {
+                None
+            };
+            (i.pointer.interact_pos(), state)
+        });
+        let (Some(pointer_pos), Some(state)) = (pointer_pos, state) else {
+            return;
+        };
+        // The pointer is in global coordinates, the mask may be in a zoomed layer
+        let pointer_pos = match ui.ctx().layer_transform_from_global(ui.layer_id()) {
+            Some(transform) => transform * pointer_pos,
+            None => pointer_pos,
+        };
+        // Ignore presses on windows that cover the mask
+        if !ui.rect_contains_pointer(rect) {
+            return;
+        }
+
+        let relative_pos = pointer_pos - rect.min;
+        let mut chains = Vec::new();
+        self.find_chains_at(
+            ui.ctx(),
+            self.object,
+            Point::default(),
+            relative_pos.to_pos2(),
+            &mut Vec::new(),
+            &mut chains,
+        );
+        let mut chains = chains.into_iter();
+        let chain = chains.next().unwrap_or_default();
+        let covered = chains.filter_map(|chain| chain.last().copied()).collect();
+
+        with_preview_state(ui.ctx(), |preview| {
+            preview.log_pointing_event(PointingEvent {
+                mask: self.object.id(),
+                x: relative_pos.x.floor() as i16,
+                y: relative_pos.y.floor() as i16,
+                state,
+                chain,
+                covered,
+            })
+        });
+    }
+
+    /// Collect the paths from the mask to every object under the position, topmost first.
+    /// Children of buttons are not separate touch targets, so the button ends the path. Objects
+    /// that are not drawn, like hidden containers, are skipped.
+    fn find_chains_at(
+        &self,
+        ctx: &egui::Context,
+        object: &Object,
+        offset: Point<i16>,
+        pos: egui::Pos2,
+        path: &mut Vec<ObjectId>,
+        chains: &mut Vec<Vec<ObjectId>>,
+    ) {
+        let shown = match object {
+            Object::Container(container) => object_state(ctx, container.id, !container.hidden),
+            _ => true,
+        };
+        if !shown || is_hidden_in_preview(ctx, object.id()) {
+            return;
+        }
+        let (width, height) = self.pool.content_size(object);
+        let rect = egui::Rect::from_min_size(
+            egui::pos2(offset.x as f32, offset.y as f32),
+            egui::vec2(width as f32, height as f32),
+        );
+        path.push(object.id());
+        let hits_before = chains.len();
+
+        let children = match object {
+            Object::DataMask(mask) => mask.object_refs.as_slice(),
+            Object::AlarmMask(mask) => mask.object_refs.as_slice(),
+            Object::Container(container) => container.object_refs.as_slice(),
+            _ => &[],
+        };
+        for obj_ref in children.iter().rev() {
+            if let Some(child) = self.pool.object_by_id(obj_ref.id) {
+                let child_offset = Point {
+                    x: offset.x + obj_ref.offset.x,
+                    y: offset.y + obj_ref.offset.y,
+                };
+                self.find_chains_at(ctx, child, child_offset, pos, path, chains);
+            }
+        }
+
+        // Containers and masks only count when nothing inside them is hit
+        if rect.contains(pos) && chains.len() == hits_before {
+            chains.push(path.clone());
+        }
+        path.pop();
+    }
+
     /// Find which object is at the given position (relative to widget)
-    fn find_object_at(&self, pos: egui::Pos2) -> Option<(ObjectId, egui::Rect)> {
-        self.find_object_recursive(self.object, Point::default(), pos)
+    fn find_object_at(
+        &self,
+        pos: egui::Pos2,
+        hidden: &HashSet<ObjectId>,
+    ) -> Option<(ObjectId, egui::Rect)> {
+        self.find_object_recursive(self.object, Point::default(), pos, hidden)
     }
 
     fn find_object_recursive(
@@ -88,6 +213,7 @@ impl<'a> InteractiveMaskRenderer<'a> {
         object: &Object,
         offset: Point<i16>,
         pos: egui::Pos2,
+        hidden: &HashSet<ObjectId>,
     ) -> Option<(ObjectId, egui::Rect)> {
         let (width, height) = self.pool.content_size(object);
         let rect = egui::Rect::from_min_size(
@@ -99,12 +225,17 @@ impl<'a> InteractiveMaskRenderer<'a> {
         match object {
             Object::DataMask(mask) => {
                 for obj_ref in mask.object_refs.iter().rev() {
+                    if hidden.contains(&obj_ref.id) {
+                        continue;
+                    }
                     if let Some(child) = self.pool.object_by_id(obj_ref.id) {
                         let child_offset = Point {
                             x: offset.x + obj_ref.offset.x,
                             y: offset.y + obj_ref.offset.y,
                         };
-                        if let Some(result) = self.find_object_recursive(child, child_offset, pos) {
+                        if let Some(result) =
+                            self.find_object_recursive(child, child_offset, pos, hidden)
+                        {
                             return Some(result);
                         }
                     }
@@ -112,12 +243,17 @@ impl<'a> InteractiveMaskRenderer<'a> {
             }
             Object::AlarmMask(mask) => {
                 for obj_ref in mask.object_refs.iter().rev() {
+                    if hidden.contains(&obj_ref.id) {
+                        continue;
+                    }
                     if let Some(child) = self.pool.object_by_id(obj_ref.id) {
                         let child_offset = Point {
                             x: offset.x + obj_ref.offset.x,
                             y: offset.y + obj_ref.offset.y,
                         };
-                        if let Some(result) = self.find_object_recursive(child, child_offset, pos) {
+                        if let Some(result) =
+                            self.find_object_recursive(child, child_offset, pos, hidden)
+                        {
                             return Some(result);
                         }
                     }
@@ -125,12 +261,17 @@ impl<'a> InteractiveMaskRenderer<'a> {
             }
             Object::Container(container) => {
                 for obj_ref in container.object_refs.iter().rev() {
+                    if hidden.contains(&obj_ref.id) {
+                        continue;
+                    }
                     if let Some(child) = self.pool.object_by_id(obj_ref.id) {
                         let child_offset = Point {
                             x: offset.x + obj_ref.offset.x,
                             y: offset.y + obj_ref.offset.y,
                         };
-                        if let Some(result) = self.find_object_recursive(child, child_offset, pos) {
+                        if let Some(result) =
+                            self.find_object_recursive(child, child_offset, pos, hidden)
+                        {
                             return Some(result);
                         }
                     }
@@ -147,3 +288,415 @@ impl<'a> InteractiveMaskRenderer<'a> {
         }
     }
 }
+
+/// Size of the handles that edit the selected object in the preview
+const HANDLE_SIZE: f32 = 10.0;
+
+/// Colour of the handles and guides that edit the selected object in the preview
+const HANDLE_COLOUR: egui::Color32 = egui::Color32::from_rgba_premultiplied(255, 255, 0, 200);
+
+/// Colour of the smart guides shown while moving an object
+const GUIDE_COLOUR: egui::Color32 = egui::Color32::from_rgb(255, 0, 255);
+
+/// Where an object is shown on a mask
+struct Placement {
+    /// The object referencing it and the index of the reference
+    parent: ObjectId,
+    index: usize,
+
+    /// Position of the parent and of the object on the mask
+    parent_offset: Point<i16>,
+    offset: Point<i16>,
+}
+
+fn find_placement(
+    pool: &ObjectPool,
+    object: &Object,
+    target: ObjectId,
+    offset: Point<i16>,
+) -> Option<Placement> {
+    let children = match object {
+        Object::DataMask(mask) => mask.object_refs.as_slice(),
+        Object::AlarmMask(mask) => mask.object_refs.as_slice(),
+        Object::Container(container) => container.object_refs.as_slice(),
+        _ => &[],
+    };
+    for (index, obj_ref) in children.iter().enumerate() {
+        let child_offset = Point {
+            x: offset.x + obj_ref.offset.x,
+            y: offset.y + obj_ref.offset.y,
+        };
+        if obj_ref.id == target {
+            return Some(Placement {
+                parent: object.id(),
+                index,
+                parent_offset: offset,
+                offset: child_offset,
+            });
+        }
+        if let Some(placement) = pool
+            .object_by_id(obj_ref.id)
+            .and_then(|child| find_placement(pool, child, target, child_offset))
+        {
+            return Some(placement);
+        }
+    }
+    None
+}
+
+fn draw_handle(ui: &egui::Ui, centre: egui::Pos2) {
+    ui.painter().circle(
+        centre,
+        HANDLE_SIZE / 2.0 - 1.0,
+        egui::Color32::WHITE,
+        egui::Stroke::new(1.5, HANDLE_COLOUR),
+    );
+}
+
+/// Handles to edit the selected object in the preview, in edit mode
+pub fn render_selection_handles(
+    ui: &egui::Ui,
+    project: &EditorProject,
+    mask: &Object,
+    mask_rect: egui::Rect,
+) {
+    if preview_mode(ui.ctx()) != PreviewMode::Edit {
+        return;
+    }
+    let pool = project.get_pool();
+    let Some(selected) = project.get_mut_selected().borrow().0 else {
+        return;
+    };
+    let Some(object) = pool.object_by_id(selected) else {
+        return;
+    };
+    let Some(placement) = find_placement(pool, mask, selected, Point::default()) else {
+        return;
+    };
+    render_move_drag(ui, project, object, &placement, mask_rect);
+    match object {
+        Object::OutputLine(line) => render_line_handles(ui, project, line, &placement, mask_rect),
+        Object::OutputMeter(_) | Object::OutputArchedBarGraph(_) => {
+            render_arc_handles(ui, project, object, &placement, mask_rect)
+        }
+        _ => {}
+    }
+}
+
+/// Drag the selected object to move it inside its parent, snapping to the edges, centres and
+/// gaps of its siblings with smart guides. Hold Alt to move it freely.
+fn render_move_drag(
+    ui: &egui::Ui,
+    project: &EditorProject,
+    object: &Object,
+    placement: &Placement,
+    mask_rect: egui::Rect,
+) {
+    let pool = project.get_pool();
+    let Some(parent) = pool.object_by_id(placement.parent) else {
+        return;
+    };
+    let Some(refs) = object_refs(parent) else {
+        return;
+    };
+    let rect_at = |offset: Point<i16>, object: &Object| {
+        let (width, height) = pool.content_size(object);
+        egui::Rect::from_min_size(
+            egui::pos2(offset.x as f32, offset.y as f32),
+            egui::vec2(width as f32, height as f32),
+        )
+    };
+    let rect = rect_at(placement.offset, object);
+    let response = ui
+        .interact(
+            rect.translate(mask_rect.min.to_vec2()),
+            ui.id().with(("move_object", object.id().value())),
+            egui::Sense::drag(),
+        )
+        .on_hover_cursor(egui::CursorIcon::Move);
+
+    // The pointer and the position of the object when the drag started
+    let start_id = egui::Id::new(("move_object_start", object.id().value()));
+    if response.drag_started() {
+        if let Some(pointer) = response.interact_pointer_pos() {
+            ui.data_mut(|data| data.insert_temp(start_id, (pointer, rect.min)));
+        }
+    }
+    if !response.dragged() {
+        if response.drag_stopped() {
+            ui.data_mut(|data| data.remove::<(egui::Pos2, egui::Pos2)>(start_id));
+        }
+        return;
+    }
+    let Some((pointer_start, start)) =
+        ui.data(|data| data.get_temp::<(egui::Pos2, egui::Pos2)>(start_id))
+    else {
+        return;
+    };
+    let Some(pointer) = response.interact_pointer_pos() else {
+        return;
+    };
+
+    let moving = egui::Rect::from_min_size(start + (pointer - pointer_start), rect.size());
+    let siblings: Vec<egui::Rect> = refs
+        .iter()
+        .enumerate()
+        .filter(|(index, _)| *index != placement.index)
+        .filter_map(|(_, obj_ref)| {
+            let offset = Point {
+                x: placement.parent_offset.x + obj_ref.offset.x,
+                y: placement.parent_offset.y + obj_ref.offset.y,
+            };
+            pool.object_by_id(obj_ref.id).map(|o| rect_at(offset, o))
+        })
+        .collect();
+    let parent_rect = rect_at(placement.parent_offset, parent);
+    let snap = if ui.input(|i| i.modifiers.alt) {
+        Snap::default()
+    } else {
+        snap_rect(moving, &siblings, Some(parent_rect))
+    };
+
+    let painter = ui.painter();
+    let to_screen = |pos: egui::Pos2| mask_rect.min + pos.to_vec2();
+    for guide in &snap.guides {
+        match *guide {
+            Guide::Alignment { from, to } => {
+                painter.line_segment(
+                    [to_screen(from), to_screen(to)],
+                    egui::Stroke::new(1.0, GUIDE_COLOUR),
+                );
+            }
+            Guide::Spacing { from, to } => {
+                let (from, to) = (to_screen(from), to_screen(to));
+                painter.arrow(from, to - from, egui::Stroke::new(1.0, GUIDE_COLOUR));
+                painter.arrow(to, from - to, egui::Stroke::new(1.0, GUIDE_COLOUR));
+                painter.text(
+                    egui::pos2((from.x + to.x) / 2.0, (from.y + to.y) / 2.0),
+                    egui::Align2::CENTER_BOTTOM,
+                    format!("{}", (to - from).length().round()),
+                    egui::FontId::monospace(10.0),
+                    GUIDE_COLOUR,
+                );
+            }
+        }
+    }
+
+    let target = (moving.min + snap.offset).round();
+    let new_offset = Point {
+        x: (target.x as i32 - placement.parent_offset.x as i32)
+            .clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+        y: (target.y as i32 - placement.parent_offset.y as i32)
+            .clamp(i16::MIN as i32, i16::MAX as i32) as i16,
+    };
+    let mut parent = parent.clone();
+    let Some(obj_ref) = object_refs_mut(&mut parent).and_then(|refs| refs.get_mut(placement.index))
+    else {
+        return;
+    };
+    if obj_ref.offset != new_offset {
+        obj_ref.offset = new_offset;
+        project.request_object_update(parent);
+        ui.ctx().request_repaint();
+    }
+}
+
+/// Endpoint handles for an output line. Dragging an endpoint keeps the other one in place and
+/// updates the position, size and direction of the line to match.
+fn render_line_handles(
+    ui: &egui::Ui,
+    project: &EditorProject,
+    line: &OutputLine,
+    placement: &Placement,
+    mask_rect: egui::Rect,
+) {
+    // Endpoints in mask coordinates
+    let min = egui::pos2(placement.offset.x as f32, placement.offset.y as f32);
+    let size = egui::vec2(line.width as f32, line.height as f32);
+    let endpoints = match line.line_direction {
+        LineDirection::TopLeftToBottomRight => [min, min + size],
+        LineDirection::BottomLeftToTopRight => {
+            [min + egui::vec2(0.0, size.y), min + egui::vec2(size.x, 0.0)]
+        }
+    };
+    let to_screen = |pos: egui::Pos2| mask_rect.min + pos.to_vec2();
+
+    // The endpoint that stays in place while dragging, the handles swap when they cross
+    let anchor_id = egui::Id::new(("line_endpoint_anchor", line.id.value()));
+    let mut anchor = ui.data(|data| data.get_temp::<egui::Pos2>(anchor_id));
+    let mut dragged_to = None;
+
+    ui.painter().line_segment(
+        [to_screen(endpoints[0]), to_screen(endpoints[1])],
+        egui::Stroke::new(1.0, HANDLE_COLOUR),
+    );
+    for (index, endpoint) in endpoints.iter().enumerate() {
+        let centre = to_screen(*endpoint);
+        let handle = ui
+            .interact(
+                egui::Rect::from_center_size(centre, egui::Vec2::splat(HANDLE_SIZE)),
+                ui.id().with(("line_endpoint", line.id.value(), index)),
+                egui::Sense::drag(),
+            )
+            .on_hover_cursor(egui::CursorIcon::Grab)
+            .on_hover_text(format!("({}, {})", endpoint.x, endpoint.y));
+        draw_handle(ui, centre);
+        if handle.drag_started() {
+            anchor = Some(endpoints[1 - index]);
+        }
+        if handle.dragged() {
+            dragged_to = handle.interact_pointer_pos();
+        }
+    }
+
+    // The dragged handle may have moved away from the pointer since the last frame
+    let pointer_down = ui.input(|i| i.pointer.primary_down());
+    if dragged_to.is_none() && pointer_down && anchor.is_some() {
+        dragged_to = ui.input(|i| i.pointer.interact_pos()).map(|pos| {
+            match ui.ctx().layer_transform_from_global(ui.layer_id()) {
+                Some(transform) => transform * pos,
+                None => pos,
+            }
+        });
+    }
+    if !pointer_down {
+        anchor = None;
+    }
+    ui.data_mut(|data| match anchor {
+        Some(anchor) => data.insert_temp(anchor_id, anchor),
+        None => data.remove::<egui::Pos2>(anchor_id),
+    });
+
+    let (Some(anchor), Some(pointer)) = (anchor, dragged_to) else {
+        return;
+    };
+    let moved = (pointer - mask_rect.min).round().to_pos2();
+    let mut new_line = line.clone();
+    new_line.width = (moved.x - anchor.x).abs() as u16;
+    new_line.height = (moved.y - anchor.y).abs() as u16;
+    new_line.line_direction = if (moved.x - anchor.x) * (moved.y - anchor.y) >= 0.0 {
+        LineDirection::TopLeftToBottomRight
+    } else {
+        LineDirection::BottomLeftToTopRight
+    };
+    let new_offset = Point {
+        x: moved.x.min(anchor.x) as i16 - placement.parent_offset.x,
+        y: moved.y.min(anchor.y) as i16 - placement.parent_offset.y,
+    };
+    let old_offset = Point {
+        x: placement.offset.x - placement.parent_offset.x,
+        y: placement.offset.y - placement.parent_offset.y,
+    };
+    if new_line == *line && new_offset == old_offset {
+        return;
+    }
+    let Some(mut parent) = project.get_pool().object_by_id(placement.parent).cloned() else {
+        return;
+    };
+    if let Some(parent_ref) =
+        object_refs_mut(&mut parent).and_then(|refs| refs.get_mut(placement.index))
+    {
+        parent_ref.offset = new_offset;
+    }
+    project.request_objects_update(vec![Object::OutputLine(new_line), parent]);
+    ui.ctx().request_repaint();
+}
+
+/// Angle in degrees of an ISO angle value, which counts in steps of 2 degrees
+pub fn iso_angle_degrees(value: u8) -> u16 {
+    value as u16 * 2
+}
+
+/// ISO angle value of an angle in degrees, rounded to the 2 degree steps
+pub fn iso_angle_value(degrees: f32) -> u8 {
+    ((degrees.rem_euclid(360.0) / 2.0).round() as u16 % 180) as u8
+}
+
+/// Start and end angle handles for a meter or arched bar graph. Angles are counted
+/// anti-clockwise from the 3 o'clock position, like in the object pool.
+fn render_arc_handles(
+    ui: &egui::Ui,
+    project: &EditorProject,
+    object: &Object,
+    placement: &Placement,
+    mask_rect: egui::Rect,
+) {
+    let (start_angle, end_angle) = match object {
+        Object::OutputMeter(meter) => (meter.start_angle, meter.end_angle),
+        Object::OutputArchedBarGraph(graph) => (graph.start_angle, graph.end_angle),
+        _ => return,
+    };
+    let (width, height) = project.get_pool().content_size(object);
+    let rect = egui::Rect::from_min_size(
+        mask_rect.min + egui::vec2(placement.offset.x as f32, placement.offset.y as f32),
+        egui::vec2(width as f32, height as f32),
+    );
+    let radius = rect.size() / 2.0;
+    let at_angle = |degrees: f32| {
+        let radians = degrees.to_radians();
+        rect.center() + egui::vec2(radius.x * radians.cos(), -radius.y * radians.sin())
+    };
+
+    // The sweep from the start to the end angle, anti-clockwise
+    let start = iso_angle_degrees(start_angle) as f32;
+    let mut sweep = iso_angle_degrees(end_angle) as f32 - start;
+    if sweep <= 0.0 {
+        sweep += 360.0;
+    }
+    let steps = (sweep / 5.0).ceil().max(1.0) as usize;
+    let arc: Vec<egui::Pos2> = (0..=steps)
+        .map(|step| at_angle(start + sweep * step as f32 / steps as f32))
+        .collect();
+    ui.painter().add(egui::Shape::line(
+        arc,
+        egui::Stroke::new(2.0, HANDLE_COLOUR),
+    ));
+
+    let mut new_angles = (start_angle, end_angle);
+    for (index, (label, value)) in [("Start", start_angle), ("End", end_angle)]
+        .into_iter()
+        .enumerate()
+    {
+        let centre = at_angle(iso_angle_degrees(value) as f32);
+        ui.painter().line_segment(
+            [rect.center(), centre],
+            egui::Stroke::new(1.0, HANDLE_COLOUR),
+        );
+        let handle = ui
+            .interact(
+                egui::Rect::from_center_size(centre, egui::Vec2::splat(HANDLE_SIZE)),
+                ui.id().with(("arc_angle", object.id().value(), index)),
+                egui::Sense::drag(),
+            )
+            .on_hover_cursor(egui::CursorIcon::Grab)
+            .on_hover_text(format!(
+                "{} angle: {}\u{b0}",
+                label,
+                iso_angle_degrees(value)
+            ));
+        draw_handle(ui, centre);
+        if let Some(pointer) = handle.interact_pointer_pos().filter(|_| handle.dragged()) {
+            let offset = pointer - rect.center();
+            let degrees = (-offset.y / radius.y.max(1.0))
+                .atan2(offset.x / radius.x.max(1.0))
+                .to_degrees();
+            if index == 0 {
+                new_angles.0 = iso_angle_value(degrees);
+            } else {
+                new_angles.1 = iso_angle_value(degrees);
+            }
+        }
+    }
+    if new_angles == (start_angle, end_angle) {
+        return;
+    }
+
+    let mut object = object.clone();
+    match &mut object {
+        Object::OutputMeter(meter) => (meter.start_angle, meter.end_angle) = new_angles,
+        Object::OutputArchedBarGraph(graph) => (graph.start_angle, graph.end_angle) = new_angles,
+        _ => return,
+    }
+    project.request_object_update(object);
+    ui.ctx().request_repaint();
+}
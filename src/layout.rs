@@ -0,0 +1,367 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::HashSet;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectRef};
+
+use crate::reference_paths::parent_map;
+use crate::EditorProject;
+
+/// Where an object is placed inside a grid cell that is larger than the object
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CellAlignment {
+    #[default]
+    Start,
+    Center,
+    End,
+}
+
+impl CellAlignment {
+    pub const ALL: [CellAlignment; 3] = [
+        CellAlignment::Start,
+        CellAlignment::Center,
+        CellAlignment::End,
+    ];
+
+    /// Offset of an object of `size` inside a cell of `cell_size`
+    fn offset(&self, cell_size: u16, size: u16) -> i32 {
+        let free = cell_size.saturating_sub(size) as i32;
+        match self {
+            CellAlignment::Start => 0,
+            CellAlignment::Center => free / 2,
+            CellAlignment::End => free,
+        }
+    }
+}
+
+/// Arranges object references row by row into a grid.
+/// Every column is as wide as its widest object and every row as high as its highest object.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GridLayout {
+    pub columns: u16,
+    pub spacing_x: u16,
+    pub spacing_y: u16,
+    pub align_x: CellAlignment,
+    pub align_y: CellAlignment,
+
+    /// Position of the top left cell
+    pub origin_x: i16,
+    pub origin_y: i16,
+}
+
+impl Default for GridLayout {
+    fn default() -> Self {
+        GridLayout {
+            columns: 2,
+            spacing_x: 4,
+            spacing_y: 4,
+            align_x: CellAlignment::Start,
+            align_y: CellAlignment::Center,
+            origin_x: 0,
+            origin_y: 0,
+        }
+    }
+}
+
+impl GridLayout {
+    /// Number of rows needed for `count` objects
+    pub fn rows(&self, count: usize) -> usize {
+        count.div_ceil(self.columns.max(1) as usize)
+    }
+
+    /// Write the grid offsets into the object references, in their current order
+    pub fn apply(&self, pool: &ObjectPool, object_refs: &mut [ObjectRef]) {
+        let columns = self.columns.max(1) as usize;
+        let sizes: Vec<(u16, u16)> = object_refs
+            .iter()
+            .map(|r| {
+                pool.object_by_id(r.id)
+                    .map_or((0, 0), |o| pool.content_size(o))
+            })
+            .collect();
+
+        let mut column_widths = vec![0u16; columns];
+        let mut row_heights = vec![0u16; self.rows(sizes.len())];
+        for (idx, (width, height)) in sizes.iter().enumerate() {
+            let column = &mut column_widths[idx % columns];
+            *column = (*column).max(*width);
+            let row = &mut row_heights[idx / columns];
+            *row = (*row).max(*height);
+        }
+
+        let start = |cells: &[u16], index: usize, spacing: u16, origin: i16| -> i32 {
+            origin as i32
+                + cells[..index]
+                    .iter()
+                    .map(|size| *size as i32 + spacing as i32)
+                    .sum::<i32>()
+        };
+        for (idx, obj_ref) in object_refs.iter_mut().enumerate() {
+            let (column, row) = (idx % columns, idx / columns);
+            let (width, height) = sizes[idx];
+            let x = start(&column_widths, column, self.spacing_x, self.origin_x)
+                + self.align_x.offset(column_widths[column], width);
+            let y = start(&row_heights, row, self.spacing_y, self.origin_y)
+                + self.align_y.offset(row_heights[row], height);
+            obj_ref.offset.x = x.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+            obj_ref.offset.y = y.clamp(i16::MIN as i32, i16::MAX as i32) as i16;
+        }
+    }
+}
+
+/// Axis to mirror positions over
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MirrorAxis {
+    /// Swap left and right
+    Horizontal,
+    /// Swap top and bottom
+    Vertical,
+}
+
+/// Positioned child references of an object, if it has any
+pub fn object_refs(object: &Object) -> Option<&Vec<ObjectRef>> {
+    match object {
+        Object::WorkingSet(o) => Some(&o.object_refs),
+        Object::DataMask(o) => Some(&o.object_refs),
+        Object::AlarmMask(o) => Some(&o.object_refs),
+        Object::Container(o) => Some(&o.object_refs),
+        Object::Key(o) => Some(&o.object_refs),
+        Object::Button(o) => Some(&o.object_refs),
+        Object::AuxiliaryFunctionType1(o) => Some(&o.object_refs),
+        Object::AuxiliaryInputType1(o) => Some(&o.object_refs),
+        Object::AuxiliaryFunctionType2(o) => Some(&o.object_refs),
+        Object::AuxiliaryInputType2(o) => Some(&o.object_refs),
+        Object::WindowMask(o) => Some(&o.object_refs),
+        Object::Animation(o) => Some(&o.object_refs),
+        _ => None,
+    }
+}
+
+/// Positioned child references of an object, if it has any
+pub fn object_refs_mut(object: &mut Object) -> Option<&mut Vec<ObjectRef>> {
+    match object {
+        Object::WorkingSet(o) => Some(&mut o.object_refs),
+        Object::DataMask(o) => Some(&mut o.object_refs),
+        Object::AlarmMask(o) => Some(&mut o.object_refs),
+        Object::Container(o) => Some(&mut o.object_refs),
+        Object::Key(o) => Some(&mut o.object_refs),
+        Object::Button(o) => Some(&mut o.object_refs),
+        Object::AuxiliaryFunctionType1(o) => Some(&mut o.object_refs),
+        Object::AuxiliaryInputType1(o) => Some(&mut o.object_refs),
+        Object::AuxiliaryFunctionType2(o) => Some(&mut o.object_refs),
+        Object::AuxiliaryInputType2(o) => Some(&mut o.object_refs),
+        Object::WindowMask(o) => Some(&mut o.object_refs),
+        Object::Animation(o) => Some(&mut o.object_refs),
+        _ => None,
+    }
+}
+
+/// Size of the area the children of an object are positioned in
+fn child_area(project: &EditorProject, object: &Object) -> (u16, u16) {
+    match object {
+        Object::DataMask(_) | Object::AlarmMask(_) => (project.mask_size, project.mask_size),
+        Object::WorkingSet(_)
+        | Object::Key(_)
+        | Object::AuxiliaryFunctionType1(_)
+        | Object::AuxiliaryInputType1(_)
+        | Object::AuxiliaryFunctionType2(_)
+        | Object::AuxiliaryInputType2(_) => project.soft_key_size,
+        _ => project.get_pool().content_size(object),
+    }
+}
+
+/// Mirror a child offset so its far edge ends up where its near edge was
+fn mirror_offset(obj_ref: &mut ObjectRef, area: (u16, u16), size: (u16, u16), axis: MirrorAxis) {
+    let mirror = |offset: i16, area: u16, size: u16| {
+        (area as i32 - size as i32 - offset as i32).clamp(i16::MIN as i32, i16::MAX as i32) as i16
+    };
+    match axis {
+        MirrorAxis::Horizontal => obj_ref.offset.x = mirror(obj_ref.offset.x, area.0, size.0),
+        MirrorAxis::Vertical => obj_ref.offset.y = mirror(obj_ref.offset.y, area.1, size.1),
+    }
+}
+
+/// Mirror the positions of all children of an object, optionally also inside its child objects
+/// (e.g. to mirror a whole mask). Returns the number of moved references.
+pub fn mirror_children(
+    project: &EditorProject,
+    id: ObjectId,
+    axis: MirrorAxis,
+    recursive: bool,
+) -> usize {
+    let pool = project.get_pool();
+    let mut mut_pool = project.get_mut_pool().borrow_mut();
+    let mut moved = 0;
+    let mut visited = HashSet::new();
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        let Some(object) = pool.object_by_id(id) else {
+            continue;
+        };
+        let area = child_area(project, object);
+        let Some(object_refs) = mut_pool.object_mut_by_id(id).and_then(object_refs_mut) else {
+            continue;
+        };
+        for obj_ref in object_refs.iter_mut() {
+            let size = pool
+                .object_by_id(obj_ref.id)
+                .map_or((0, 0), |o| pool.content_size(o));
+            mirror_offset(obj_ref, area, size, axis);
+            moved += 1;
+            if recursive {
+                stack.push(obj_ref.id);
+            }
+        }
+    }
+    moved
+}
+
+/// Mirror the position of an object inside every parent that references it.
+/// Returns the number of moved references.
+pub fn mirror_in_parents(project: &EditorProject, id: ObjectId, axis: MirrorAxis) -> usize {
+    let pool = project.get_pool();
+    let Some(object) = pool.object_by_id(id) else {
+        return 0;
+    };
+    let size = pool.content_size(object);
+    let parents = parent_map(pool).remove(&id).unwrap_or_default();
+
+    let mut mut_pool = project.get_mut_pool().borrow_mut();
+    let mut moved = 0;
+    for parent_id in parents {
+        let Some(parent) = pool.object_by_id(parent_id) else {
+            continue;
+        };
+        let area = child_area(project, parent);
+        if let Some(object_refs) = mut_pool
+            .object_mut_by_id(parent_id)
+            .and_then(object_refs_mut)
+        {
+            for obj_ref in object_refs.iter_mut().filter(|r| r.id == id) {
+                mirror_offset(obj_ref, area, size, axis);
+                moved += 1;
+            }
+        }
+    }
+    moved
+}
+
+/// Relative move and scale of child positions, and optionally of the child sizes
+#[derive(Debug, Clone, PartialEq)]
+pub struct Transform {
+    pub dx: i16,
+    pub dy: i16,
+
+    /// Scale in percent, relative to the top left corner of the parent
+    pub scale_x: f32,
+    pub scale_y: f32,
+    pub scale_sizes: bool,
+}
+
+impl Default for Transform {
+    fn default() -> Self {
+        Transform {
+            dx: 0,
+            dy: 0,
+            scale_x: 100.0,
+            scale_y: 100.0,
+            scale_sizes: false,
+        }
+    }
+}
+
+impl Transform {
+    fn apply(&self, offset: i16, scale: f32, delta: i16) -> i16 {
+        let value = (offset as f32 * scale / 100.0).round() as i32 + delta as i32;
+        value.clamp(i16::MIN as i32, i16::MAX as i32) as i16
+    }
+
+    fn scale(value: u16, scale: f32) -> u16 {
+        (value as f32 * scale / 100.0)
+            .round()
+            .clamp(0.0, u16::MAX as f32) as u16
+    }
+}
+
+/// Width and height attributes of an object, the height is None for objects that are sized by
+/// their width only
+fn size_mut(object: &mut Object) -> Option<(&mut u16, Option<&mut u16>)> {
+    match object {
+        Object::Container(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::Button(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::InputBoolean(o) => Some((&mut o.width, None)),
+        Object::InputString(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::InputNumber(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::InputList(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputString(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputNumber(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputList(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputLine(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputRectangle(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputEllipse(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputPolygon(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputMeter(o) => Some((&mut o.width, None)),
+        Object::OutputLinearBarGraph(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::OutputArchedBarGraph(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::PictureGraphic(o) => Some((&mut o.width, None)),
+        Object::Animation(o) => Some((&mut o.width, Some(&mut o.height))),
+        Object::ScaledGraphic(o) => Some((&mut o.width, Some(&mut o.height))),
+        _ => None,
+    }
+}
+
+/// Scale the size of an object, the points of a polygon are scaled along.
+/// Objects sized by their width only are scaled horizontally.
+fn scale_size(object: &mut Object, transform: &Transform) {
+    if let Object::OutputPolygon(polygon) = object {
+        for point in &mut polygon.points {
+            point.x = Transform::scale(point.x, transform.scale_x);
+            point.y = Transform::scale(point.y, transform.scale_y);
+        }
+    }
+    if let Some((width, height)) = size_mut(object) {
+        *width = Transform::scale(*width, transform.scale_x);
+        if let Some(height) = height {
+            *height = Transform::scale(*height, transform.scale_y);
+        }
+    }
+}
+
+/// Move and scale the children at the given reference indices of an object. Objects that are
+/// referenced more than once are resized once. Returns the number of moved references.
+pub fn transform_children(
+    project: &EditorProject,
+    parent: ObjectId,
+    indices: &HashSet<usize>,
+    transform: &Transform,
+) -> usize {
+    let mut mut_pool = project.get_mut_pool().borrow_mut();
+    let Some(object_refs) = mut_pool.object_mut_by_id(parent).and_then(object_refs_mut) else {
+        return 0;
+    };
+    let mut moved = 0;
+    let mut children = HashSet::new();
+    for (index, obj_ref) in object_refs.iter_mut().enumerate() {
+        if !indices.contains(&index) {
+            continue;
+        }
+        obj_ref.offset.x = transform.apply(obj_ref.offset.x, transform.scale_x, transform.dx);
+        obj_ref.offset.y = transform.apply(obj_ref.offset.y, transform.scale_y, transform.dy);
+        children.insert(obj_ref.id);
+        moved += 1;
+    }
+    if transform.scale_sizes {
+        for id in children {
+            if let Some(object) = mut_pool.object_mut_by_id(id) {
+                scale_size(object, transform);
+            }
+        }
+    }
+    moved
+}
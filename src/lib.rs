@@ -3,19 +3,217 @@
 //! Authors: Daan Steenbergen
 
 mod allowed_object_relationships;
+#[cfg(not(target_arch = "wasm32"))]
+mod backups;
+mod bulk_rename;
+mod changelog;
+mod colour_roles;
+mod designer_settings;
 mod editor_project;
+mod export_verification;
+mod expression;
+#[cfg(not(target_arch = "wasm32"))]
+mod file_watch;
+mod focus_order;
+mod font_batch;
+mod golden_pool;
+mod hex_inspector;
+mod history;
+mod i18n;
+mod id_conflicts;
+mod input_simulation;
 mod interactive_rendering_simple;
+mod layout;
+mod macro_references;
+mod mask_capture;
+mod mask_duplication;
+mod mask_print;
+mod mask_thumbnails;
+mod minify;
+mod navigation_map;
+mod object_clipboard;
 mod object_configuring;
 mod object_defaults;
 mod object_info;
 mod object_rendering;
+mod orphan_attributes;
+mod pdf;
+mod picture_graphic;
+mod pixel_font;
+mod plugins;
+mod polygon;
+mod pool_diff;
+mod pool_recovery;
+mod pool_version;
 mod possible_events;
+mod preview_state;
 mod project_file;
+mod project_migration;
+mod reference_overlay;
+mod reference_paths;
+mod reference_repair;
+mod regression;
+#[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+mod remote_control;
+mod render_fidelity;
+mod review;
+mod review_document;
+mod scoped_undo;
+#[cfg(feature = "scripting")]
+mod scripting;
+mod shared_objects;
+mod similarity;
+#[cfg(not(target_arch = "wasm32"))]
+mod single_instance;
+mod smart_guides;
 mod smart_naming;
+mod spell_check;
+mod state_scenarios;
+mod string_inventory;
+mod style_presets;
+mod tasks;
+mod terminal_capabilities;
+mod terminal_capture;
+mod terminal_frame;
+mod terminal_profile;
+mod texture_worker;
+mod ui_theme;
+mod upload_simulation;
+mod validation;
+mod vt_simulator;
+mod vt_storage;
+mod workspace;
 
-pub use editor_project::EditorProject;
-pub use interactive_rendering_simple::InteractiveMaskRenderer;
+#[cfg(not(target_arch = "wasm32"))]
+pub use backups::{
+    back_up_save, backup_dir, list_backups, restore_backup, Backup, BACKUP_DIR,
+    DEFAULT_BACKUP_COUNT,
+};
+pub use bulk_rename::{apply_renames, c_identifier, header_conflicts, plan_renames, Rename};
+pub use changelog::{generate_changelog, object_label, ChangelogFormat};
+pub use colour_roles::{colour_attributes, colour_usage, ColourRoles, ColourTheme};
+#[cfg(not(target_arch = "wasm32"))]
+pub use designer_settings::default_settings_path;
+pub use designer_settings::{
+    shortcut_from_text, shortcut_to_text, DesignerSettings, ExportSettings, KeyBindings,
+    SETTINGS_FILE, SETTINGS_PATH_ENV,
+};
+pub use editor_project::{DirtyMarker, EditorProject, MIN_SOFT_KEY_SIZE};
+pub use export_verification::{verify_export, ExportVerification};
+pub use expression::{
+    evaluate_expression, evaluate_watch_expression, watch_variables, ExpressionContext,
+};
+#[cfg(not(target_arch = "wasm32"))]
+pub use file_watch::{merge_from_disk, WatchedFile, WATCH_INTERVAL};
+pub use focus_order::{focus_order, move_focus_stop, render_focus_order_overlay, FocusStop};
+pub use font_batch::{font_attributes_mut, has_font_attributes, set_font_attributes};
+pub use golden_pool::{GoldenPool, LockedObject};
+pub use hex_inspector::{annotate_object, format_hex, parse_hex, replace_object_bytes, ByteField};
+pub use history::{object_history, Change, ChangeCategory, HistoryFilter};
+pub use i18n::{language, set_language, tr, tr_args, Language};
+pub use id_conflicts::{find_id_conflicts, IdConflict, IdConflictResolution, ReferenceRewrite};
+pub use input_simulation::{raw_from_displayed, simulated_input_value, simulated_variable_value};
+pub use interactive_rendering_simple::{
+    iso_angle_degrees, iso_angle_value, render_selection_handles, InteractiveMaskRenderer,
+};
+pub use layout::{
+    mirror_children, mirror_in_parents, object_refs, object_refs_mut, transform_children,
+    CellAlignment, GridLayout, MirrorAxis, Transform,
+};
+pub use macro_references::{
+    macro_command_name, macro_refs, macro_refs_mut, macro_targets, macro_triggers,
+    remap_macro_references, split_macro_commands, validate_macros, MacroTarget, MacroTrigger,
+};
+pub use mask_duplication::{shared_by_default, MaskDuplication, ShareMode};
+#[cfg(not(target_arch = "wasm32"))]
+pub use mask_print::open_in_viewer;
+pub use mask_print::{fit_to_page_scale, print_document, MaskPrintRun, PrintOptions};
+pub use mask_thumbnails::MaskThumbnails;
+pub use minify::{minify_pool, MinifyOptions, MinifyReport};
+pub use navigation_map::{navigation_map, NavigationLink, NavigationMap};
+pub use object_clipboard::{copy_objects, is_clipboard_objects, paste_objects};
 pub use object_configuring::ConfigurableObject;
 pub use object_defaults::default_object;
 pub use object_info::ObjectInfo;
-pub use object_rendering::RenderableObject;
+pub use object_rendering::{
+    format_vt_number, measure_vt_text, vt_display_text, vt_font_id, RenderableObject,
+};
+pub use orphan_attributes::{delete_orphans, orphan_attributes, OrphanObject};
+pub use picture_graphic::{
+    compress_picture, create_picture_graphic, import_images, run_length_encode, set_picture_image,
+    DEFAULT_TRANSPARENCY_COLOUR,
+};
+pub use pixel_font::{pixel_font_data, PIXEL_FONT_NAME};
+pub use plugins::{
+    default_plugin_dir, load_plugins, Plugin, PluginHook, PluginRun, DEFAULT_PLUGIN_TIMEOUT,
+    PLUGIN_DIR_ENV,
+};
+pub use polygon::{is_convex, polygon_problems, POLYGON_TYPE_CONVEX, POLYGON_TYPE_OPEN};
+pub use pool_diff::{diff_objects, diff_pools, AttributeChange, ObjectChange, ObjectDiff};
+pub use pool_recovery::{recover_pool, RecoveryReport};
+pub use pool_version::{
+    PoolVersion, VersionMessage, EXTENDED_VERSION_LABEL_LENGTH, VERSION_LABEL_LENGTH,
+};
+pub use preview_state::{
+    designator_size_info, object_state, preview_mode, previewed_mask, vt_font_family,
+    with_preview_state, InputMessage, PointingEvent, PreviewMode, TouchState,
+};
+pub use project_migration::{
+    ProjectFileFormat, KNOWN_FEATURES, OLDEST_PROJECT_FILE_VERSION, PROJECT_FILE_VERSION,
+};
+pub use reference_overlay::{
+    load_reference_overlay, render_reference_overlay, request_reference_comparison,
+    set_reference_settings, update_reference_comparison, ReferenceOverlay,
+};
+pub use reference_paths::{
+    parent_map, reference_paths, remap_references, remove_references, subtree,
+};
+pub use reference_repair::{
+    dangling_references, expected_types, recreate_missing_object, redirect_dangling_reference,
+    remove_dangling_reference, DanglingReference,
+};
+pub use regression::{
+    baseline_path, ComparisonResult, MaskResult, RegressionRun, DEFAULT_TOLERANCE,
+};
+#[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+pub use remote_control::{
+    RemoteError, RemoteRequest, RemoteResult, RemoteServer, DEFAULT_REMOTE_PORT, REMOTE_TOKEN_ENV,
+};
+pub use render_fidelity::{render_fidelity, Fidelity, FidelityIssue};
+pub use review::{ObjectReview, ReviewComment, ReviewStatus};
+pub use review_document::ReviewDocumentRun;
+#[cfg(feature = "scripting")]
+pub use scripting::{run_script, ScriptResult, SCRIPT_API};
+pub use shared_objects::{make_unique_copy, object_parents};
+pub use similarity::{find_similar_objects, merge_duplicates, SimilarGroup, SubtreeDifference};
+#[cfg(not(target_arch = "wasm32"))]
+pub use single_instance::{
+    file_arguments, forward_to_running_instance, listen_for_open_requests,
+    register_file_associations, OPENABLE_EXTENSIONS, SINGLE_INSTANCE_PORT,
+};
+pub use smart_guides::{snap_rect, Guide, Snap, SNAP_DISTANCE};
+pub use spell_check::{
+    check_spelling, spell_checked_text_edit, spell_checker, visible_text, with_spell_checker,
+    Dictionary, SpellChecker, SpellingIssue,
+};
+pub use state_scenarios::{pool_state, StateScenario};
+pub use string_inventory::{set_string_value, string_inventory, StringEntry, TranslationStatus};
+pub use style_presets::{apply_style_preset_to, preset_values, PresetValue, StylePreset};
+pub use tasks::{project_tasks, ProjectTask, TaskSource, TaskTag};
+pub use terminal_capabilities::TerminalCapabilities;
+#[cfg(all(feature = "can-capture", target_os = "linux"))]
+pub use terminal_capture::SocketCanInterface;
+pub use terminal_capture::{capture_capabilities, CanInterface, DEFAULT_CAPTURE_ADDRESS};
+pub use terminal_frame::{render_terminal_frame, TerminalFrame};
+pub use terminal_profile::{
+    font_size_key, font_size_keys, validate_font, ColourDepth, CustomFont, TerminalProfile,
+    DEFAULT_POOL_SIZE_BUDGET, DEFAULT_SOFT_KEY_COUNT, NON_PROPORTIONAL_FONT_SIZES,
+    PROPORTIONAL_FONT_KEY,
+};
+pub use texture_worker::{lazy_texture, ready_texture};
+pub use ui_theme::{set_touch_mode, touch_mode, UiTheme, UI_SCALE_RANGE};
+pub use upload_simulation::{simulate_upload, UploadOffender, UploadSimulation, UploadStep};
+pub use validation::{validate_project, Severity, ValidationIssue};
+pub use vt_simulator::{simulator_export, SimulatorSettings};
+pub use vt_storage::{ConnectOutcome, SimulatedVtStorage, StoredVersion};
+pub use workspace::{Workspace, WorkspaceMember};
@@ -0,0 +1,644 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::{Event, MacroRef};
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+
+use crate::object_configuring::ALLOWED_MACRO_COMMANDS;
+use crate::ValidationIssue;
+
+/// Length of a macro command, except for Change String Value which has a variable length
+const MACRO_COMMAND_LENGTH: usize = 8;
+
+/// Change String Value command, followed by the object ID, string length and string
+const CHANGE_STRING_VALUE: u8 = 0xB3;
+
+/// Change Numeric Value and Change Attribute commands, their 32 bit value is an object ID when
+/// an object pointer or an attribute that refers to an object is changed
+const CHANGE_NUMERIC_VALUE: u8 = 0xA8;
+const CHANGE_ATTRIBUTE: u8 = 0xAF;
+
+/// Execute Macro command, followed by the macro ID
+const EXECUTE_MACRO: u8 = 0xBE;
+
+/// The number of bytes of a macro is stored as 16 bit value
+const MAX_MACRO_SIZE: usize = u16::MAX as usize;
+
+/// An event of an object that executes a macro
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroTrigger {
+    pub object: ObjectId,
+    pub event: Event,
+    pub macro_id: u8,
+}
+
+/// An object that is changed by a command of a macro
+#[derive(Debug, Clone, PartialEq)]
+pub struct MacroTarget {
+    pub macro_object: ObjectId,
+    pub command: u8,
+    pub object: ObjectId,
+}
+
+/// Macro references of an object, empty for objects that can not execute macros
+pub fn macro_refs(object: &Object) -> &[MacroRef] {
+    match object {
+        Object::WorkingSet(o) => &o.macro_refs,
+        Object::DataMask(o) => &o.macro_refs,
+        Object::AlarmMask(o) => &o.macro_refs,
+        Object::Container(o) => &o.macro_refs,
+        Object::WindowMask(o) => &o.macro_refs,
+        Object::SoftKeyMask(o) => &o.macro_refs,
+        Object::Key(o) => &o.macro_refs,
+        Object::Button(o) => &o.macro_refs,
+        Object::KeyGroup(o) => &o.macro_refs,
+        Object::InputBoolean(o) => &o.macro_refs,
+        Object::InputString(o) => &o.macro_refs,
+        Object::InputNumber(o) => &o.macro_refs,
+        Object::InputList(o) => &o.macro_refs,
+        Object::OutputString(o) => &o.macro_refs,
+        Object::OutputNumber(o) => &o.macro_refs,
+        Object::OutputList(o) => &o.macro_refs,
+        Object::OutputLine(o) => &o.macro_refs,
+        Object::OutputRectangle(o) => &o.macro_refs,
+        Object::OutputEllipse(o) => &o.macro_refs,
+        Object::OutputPolygon(o) => &o.macro_refs,
+        Object::OutputMeter(o) => &o.macro_refs,
+        Object::OutputLinearBarGraph(o) => &o.macro_refs,
+        Object::OutputArchedBarGraph(o) => &o.macro_refs,
+        Object::PictureGraphic(o) => &o.macro_refs,
+        Object::ScaledGraphic(o) => &o.macro_refs,
+        Object::Animation(o) => &o.macro_refs,
+        Object::FontAttributes(o) => &o.macro_refs,
+        Object::LineAttributes(o) => &o.macro_refs,
+        Object::FillAttributes(o) => &o.macro_refs,
+        Object::InputAttributes(o) => &o.macro_refs,
+        _ => &[],
+    }
+}
+
+/// Mutable macro references of an object, None for objects that can not execute macros
+pub fn macro_refs_mut(object: &mut Object) -> Option<&mut Vec<MacroRef>> {
+    match object {
+        Object::WorkingSet(o) => Some(&mut o.macro_refs),
+        Object::DataMask(o) => Some(&mut o.macro_refs),
+        Object::AlarmMask(o) => Some(&mut o.macro_refs),
+        Object::Container(o) => Some(&mut o.macro_refs),
+        Object::WindowMask(o) => Some(&mut o.macro_refs),
+        Object::SoftKeyMask(o) => Some(&mut o.macro_refs),
+        Object::Key(o) => Some(&mut o.macro_refs),
+        Object::Button(o) => Some(&mut o.macro_refs),
+        Object::KeyGroup(o) => Some(&mut o.macro_refs),
+        Object::InputBoolean(o) => Some(&mut o.macro_refs),
+        Object::InputString(o) => Some(&mut o.macro_refs),
+        Object::InputNumber(o) => Some(&mut o.macro_refs),
+        Object::InputList(o) => Some(&mut o.macro_refs),
+        Object::OutputString(o) => Some(&mut o.macro_refs),
+        Object::OutputNumber(o) => Some(&mut o.macro_refs),
+        Object::OutputList(o) => Some(&mut o.macro_refs),
+        Object::OutputLine(o) => Some(&mut o.macro_refs),
+        Object::OutputRectangle(o) => Some(&mut o.macro_refs),
+        Object::OutputEllipse(o) => Some(&mut o.macro_refs),
+        Object::OutputPolygon(o) => Some(&mut o.macro_refs),
+        Object::OutputMeter(o) => Some(&mut o.macro_refs),
+        Object::OutputLinearBarGraph(o) => Some(&mut o.macro_refs),
+        Object::OutputArchedBarGraph(o) => Some(&mut o.macro_refs),
+        Object::PictureGraphic(o) => Some(&mut o.macro_refs),
+        Object::ScaledGraphic(o) => Some(&mut o.macro_refs),
+        Object::Animation(o) => Some(&mut o.macro_refs),
+        Object::FontAttributes(o) => Some(&mut o.macro_refs),
+        Object::LineAttributes(o) => Some(&mut o.macro_refs),
+        Object::FillAttributes(o) => Some(&mut o.macro_refs),
+        Object::InputAttributes(o) => Some(&mut o.macro_refs),
+        _ => None,
+    }
+}
+
+/// Human readable name of a macro command
+pub fn macro_command_name(code: u8) -> &'static str {
+    ALLOWED_MACRO_COMMANDS
+        .iter()
+        .find(|(c, _, _)| *c == code)
+        .map_or("Unknown command", |(_, name, _)| name)
+}
+
+/// Every event in the pool that executes a macro
+pub fn macro_triggers(pool: &ObjectPool) -> Vec<MacroTrigger> {
+    pool.objects()
+        .iter()
+        .flat_map(|object| {
+            macro_refs(object).iter().map(|macro_ref| MacroTrigger {
+                object: object.id(),
+                event: macro_ref.event_id,
+                macro_id: macro_ref.macro_id,
+            })
+        })
+        .collect()
+}
+
+/// Macros an object executes: the macros of its macro references and, for a macro, the macros of
+/// its Execute Macro commands
+pub fn executed_macros(object: &Object) -> Vec<ObjectId> {
+    let mut macro_ids: Vec<u8> = macro_refs(object).iter().map(|r| r.macro_id).collect();
+    if let Object::Macro(macro_object) = object {
+        macro_ids.extend(
+            split_macro_commands(&macro_object.commands)
+                .0
+                .iter()
+                .filter(|command| command[0] == EXECUTE_MACRO)
+                .map(|command| command[1]),
+        );
+    }
+    macro_ids
+        .into_iter()
+        .filter_map(|id| ObjectId::new(id as u16).ok())
+        .collect()
+}
+
+/// Split the command data of a macro into commands.
+/// Also returns the bytes of an incomplete command at the end, empty if there is none.
+pub fn split_macro_commands(mut data: &[u8]) -> (Vec<&[u8]>, &[u8]) {
+    let mut commands = Vec::new();
+    while let Some(&code) = data.first() {
+        let length = if code == CHANGE_STRING_VALUE {
+            match data.get(3..5) {
+                Some(len) => 5 + u16::from_le_bytes([len[0], len[1]]) as usize,
+                None => break,
+            }
+        } else {
+            MACRO_COMMAND_LENGTH
+        };
+        if data.len() < length {
+            break;
+        }
+        let (command, rest) = data.split_at(length);
+        commands.push(command);
+        data = rest;
+    }
+    (commands, data)
+}
+
+/// Every object changed by a macro command.
+/// Incomplete commands at the end of a macro are ignored.
+pub fn macro_targets(pool: &ObjectPool) -> Vec<MacroTarget> {
+    let mut targets = Vec::new();
+    for object in pool.objects_by_type(ObjectType::Macro) {
+        let Object::Macro(macro_object) = object else {
+            continue;
+        };
+        for command in split_macro_commands(&macro_object.commands).0 {
+            for id in command_object_ids(command) {
+                if let Ok(id) = ObjectId::new(id) {
+                    targets.push(MacroTarget {
+                        macro_object: object.id(),
+                        command: command[0],
+                        object: id,
+                    });
+                }
+            }
+        }
+    }
+    targets
+}
+
+/// Objects a macro command may set as a value, e.g. the object an object pointer is changed to.
+/// Whether a value is an object ID depends on the changed attribute, so every value that is the
+/// ID of an object in the pool is returned.
+pub fn macro_value_objects(pool: &ObjectPool) -> Vec<ObjectId> {
+    let mut objects = Vec::new();
+    for object in pool.objects_by_type(ObjectType::Macro) {
+        let Object::Macro(macro_object) = object else {
+            continue;
+        };
+        for command in split_macro_commands(&macro_object.commands).0 {
+            if ![CHANGE_NUMERIC_VALUE, CHANGE_ATTRIBUTE].contains(&command[0]) {
+                continue;
+            }
+            let value = u32::from_le_bytes([command[4], command[5], command[6], command[7]]);
+            let id = u16::try_from(value)
+                .ok()
+                .and_then(|id| ObjectId::new(id).ok());
+            if let Some(id) = id.filter(|id| pool.object_by_id(*id).is_some()) {
+                objects.push(id);
+            }
+        }
+    }
+    objects
+}
+
+/// Positions of the object IDs in a command, see ISO 11783-6 annex F. The ID of Execute Macro
+/// is a single byte, all others are 16 bit.
+fn command_id_positions(code: u8) -> &'static [usize] {
+    match code {
+        // Hide/show, enable/disable, select input object, change size, background colour,
+        // numeric value, end point, font/line/fill attributes, attribute, priority, string value,
+        // polygon point/scale, graphics context, colour map
+        0xA0..=0xA2 | 0xA6..=0xAC | 0xAF | 0xB0 | 0xB3 | 0xB6..=0xB8 | 0xBA => &[1],
+        // Change list item: list and new item
+        0xB1 => &[1, 4],
+        // Change object label: object, string variable and graphic representation
+        0xB5 => &[1, 3, 6],
+        // Change child location/position: parent and child
+        0xA5 | 0xB4 => &[1, 3],
+        // Change active mask: working set and mask
+        0xAD => &[1, 3],
+        // Change soft key mask: data or alarm mask and soft key mask
+        0xAE => &[2, 4],
+        // Lock/unlock mask
+        0xBD => &[2],
+        // Execute macro
+        EXECUTE_MACRO => &[1],
+        _ => &[],
+    }
+}
+
+/// Object IDs a command refers to
+fn command_object_ids(command: &[u8]) -> Vec<u16> {
+    command_id_positions(command[0])
+        .iter()
+        .map(|&index| match command[0] {
+            EXECUTE_MACRO => command[index] as u16,
+            _ => u16::from_le_bytes([command[index], command[index + 1]]),
+        })
+        .collect()
+}
+
+/// Replace the object IDs in `map` in the commands of a macro object and the macro IDs of the
+/// macro references of other objects, the macro counterpart of `remap_references`. Macro IDs are
+/// a single byte, so they are only changed when the new ID fits. Values of Change Numeric Value
+/// and Change Attribute in `map` are changed too, like `macro_value_objects` they may be IDs.
+pub fn remap_macro_references(object: &mut Object, map: &HashMap<ObjectId, ObjectId>) {
+    let new_macro_id = |macro_id: u8| {
+        ObjectId::new(macro_id as u16)
+            .ok()
+            .and_then(|id| map.get(&id))
+            .and_then(|id| u8::try_from(id.value()).ok())
+    };
+    if let Object::Macro(macro_object) = object {
+        let mut start = 0;
+        let lengths: Vec<usize> = split_macro_commands(&macro_object.commands)
+            .0
+            .iter()
+            .map(|command| command.len())
+            .collect();
+        for length in lengths {
+            let command = &mut macro_object.commands[start..start + length];
+            start += length;
+            for &index in command_id_positions(command[0]) {
+                if command[0] == EXECUTE_MACRO {
+                    if let Some(id) = new_macro_id(command[index]) {
+                        command[index] = id;
+                    }
+                    continue;
+                }
+                let id = u16::from_le_bytes([command[index], command[index + 1]]);
+                if let Some(new_id) = ObjectId::new(id).ok().and_then(|id| map.get(&id)) {
+                    command[index..index + 2].copy_from_slice(&new_id.value().to_le_bytes());
+                }
+            }
+            if [CHANGE_NUMERIC_VALUE, CHANGE_ATTRIBUTE].contains(&command[0]) {
+                let value = u32::from_le_bytes([command[4], command[5], command[6], command[7]]);
+                let new_id = u16::try_from(value)
+                    .ok()
+                    .and_then(|id| ObjectId::new(id).ok())
+                    .and_then(|id| map.get(&id));
+                if let Some(new_id) = new_id {
+                    command[4..8].copy_from_slice(&u32::from(new_id.value()).to_le_bytes());
+                }
+            }
+        }
+    }
+    for macro_ref in macro_refs_mut(object).into_iter().flatten() {
+        if let Some(id) = new_macro_id(macro_ref.macro_id) {
+            macro_ref.macro_id = id;
+        }
+    }
+}
+
+/// Object types a command may change, None if any object is allowed
+fn allowed_target_types(code: u8, position: usize) -> Option<&'static [ObjectType]> {
+    let types: &'static [ObjectType] = match (code, position) {
+        (0xA0, _) => &[ObjectType::Container],
+        (0xA1, _) => &[
+            ObjectType::InputBoolean,
+            ObjectType::InputString,
+            ObjectType::InputNumber,
+            ObjectType::InputList,
+            ObjectType::Button,
+            ObjectType::Animation,
+        ],
+        (0xA2, _) => &[
+            ObjectType::InputBoolean,
+            ObjectType::InputString,
+            ObjectType::InputNumber,
+            ObjectType::InputList,
+            ObjectType::Button,
+            ObjectType::Key,
+        ],
+        (0xA8, _) => &[
+            ObjectType::NumberVariable,
+            ObjectType::InputBoolean,
+            ObjectType::InputNumber,
+            ObjectType::InputList,
+            ObjectType::OutputNumber,
+            ObjectType::OutputList,
+            ObjectType::OutputMeter,
+            ObjectType::OutputLinearBarGraph,
+            ObjectType::OutputArchedBarGraph,
+            ObjectType::ObjectPointer,
+            ObjectType::ExternalObjectPointer,
+            ObjectType::Animation,
+        ],
+        (0xA9, _) => &[ObjectType::OutputLine],
+        (0xAA, _) => &[ObjectType::FontAttributes],
+        (0xAB, _) => &[ObjectType::LineAttributes],
+        (0xAC, _) => &[ObjectType::FillAttributes],
+        (0xAD, 0) => &[ObjectType::WorkingSet],
+        (0xAD, _) => &[ObjectType::DataMask, ObjectType::AlarmMask],
+        (0xAE, 0) => &[ObjectType::DataMask, ObjectType::AlarmMask],
+        (0xAE, _) => &[ObjectType::SoftKeyMask],
+        (0xB0, _) => &[ObjectType::AlarmMask],
+        (0xB1, 0) => &[
+            ObjectType::InputList,
+            ObjectType::OutputList,
+            ObjectType::Animation,
+        ],
+        (0xB5, 1) => &[ObjectType::StringVariable],
+        (0xB5, 2) => &[ObjectType::PictureGraphic, ObjectType::ScaledGraphic],
+        (0xB3, _) => &[
+            ObjectType::StringVariable,
+            ObjectType::InputString,
+            ObjectType::OutputString,
+        ],
+        (0xB6 | 0xB7, _) => &[ObjectType::OutputPolygon],
+        (0xB8, _) => &[ObjectType::GraphicsContext],
+        (0xBA, _) => &[ObjectType::ColourMap, ObjectType::ColourPalette],
+        (0xBD, _) => &[ObjectType::DataMask],
+        (EXECUTE_MACRO, _) => &[ObjectType::Macro],
+        _ => return None,
+    };
+    Some(types)
+}
+
+/// Check the parameters of a command that have a limited range, returns a message per problem
+fn check_command_values(command: &[u8]) -> Option<String> {
+    let (name, value, allowed): (&str, u8, &[u8]) = match command[0] {
+        0xA0 => ("show", command[3], &[0, 1]),
+        0xA1 => ("enable", command[3], &[0, 1]),
+        0xAE => ("mask type", command[1], &[1, 2]),
+        0xB0 => ("priority", command[3], &[0, 1, 2]),
+        0xBD => ("lock", command[1], &[0, 1]),
+        _ => return None,
+    };
+    (!allowed.contains(&value)).then(|| {
+        format!(
+            "{} has {} {}, allowed are {:?}",
+            macro_command_name(command[0]),
+            name,
+            value,
+            allowed
+        )
+    })
+}
+
+/// Check the commands of all macros: known commands, existing objects of a legal type,
+/// parameter ranges, the size limit and macros that execute themselves
+pub fn validate_macros(pool: &ObjectPool, issues: &mut Vec<ValidationIssue>) {
+    let mut executes: BTreeMap<ObjectId, BTreeSet<ObjectId>> = BTreeMap::new();
+    for object in pool.objects_by_type(ObjectType::Macro) {
+        let Object::Macro(macro_object) = object else {
+            continue;
+        };
+        let id = object.id();
+        let error = |message: String| {
+            ValidationIssue::error(Some(id), format!("Macro {}: {}", id.value(), message))
+        };
+
+        if macro_object.commands.len() > MAX_MACRO_SIZE {
+            issues.push(error(format!(
+                "{} bytes of commands, at most {} are allowed",
+                macro_object.commands.len(),
+                MAX_MACRO_SIZE
+            )));
+        }
+        let (commands, incomplete) = split_macro_commands(&macro_object.commands);
+        if !incomplete.is_empty() {
+            issues.push(error(format!(
+                "incomplete {} at the end",
+                macro_command_name(incomplete[0])
+            )));
+        }
+
+        for command in commands {
+            let code = command[0];
+            if !ALLOWED_MACRO_COMMANDS.iter().any(|(c, _, _)| *c == code) {
+                issues.push(error(format!(
+                    "command 0x{:02X} is not allowed in macros",
+                    code
+                )));
+                continue;
+            }
+            if let Some(message) = check_command_values(command) {
+                issues.push(error(message));
+            }
+            for (position, target) in command_object_ids(command).into_iter().enumerate() {
+                // The null object ID selects the default, e.g. the default colour map
+                let Ok(target) = ObjectId::new(target) else {
+                    continue;
+                };
+                let Some(target_object) = pool.object_by_id(target) else {
+                    issues.push(error(format!(
+                        "{} refers to object {} which does not exist",
+                        macro_command_name(code),
+                        target.value()
+                    )));
+                    continue;
+                };
+                if let Some(types) = allowed_target_types(code, position) {
+                    if !types.contains(&target_object.object_type()) {
+                        issues.push(error(format!(
+                            "{} can not be applied to {:?} {}",
+                            macro_command_name(code),
+                            target_object.object_type(),
+                            target.value()
+                        )));
+                    }
+                }
+                if code == EXECUTE_MACRO {
+                    executes.entry(id).or_default().insert(target);
+                }
+            }
+        }
+    }
+
+    for trigger in macro_triggers(pool) {
+        let exists = ObjectId::new(trigger.macro_id as u16)
+            .ok()
+            .and_then(|id| pool.object_by_id(id))
+            .is_some_and(|o| o.object_type() == ObjectType::Macro);
+        if !exists {
+            issues.push(ValidationIssue::error(
+                Some(trigger.object),
+                format!(
+                    "Object {} executes macro {} on {:?}, which does not exist",
+                    trigger.object.value(),
+                    trigger.macro_id,
+                    trigger.event
+                ),
+            ));
+        }
+    }
+
+    // A macro must not execute itself, directly or through other macros
+    for (&start, executed) in &executes {
+        let mut visited = BTreeSet::new();
+        let mut stack: Vec<ObjectId> = executed.iter().copied().collect();
+        while let Some(id) = stack.pop() {
+            if id == start {
+                issues.push(ValidationIssue::error(
+                    Some(start),
+                    format!("Macro {} executes itself recursively", start.value()),
+                ));
+                break;
+            }
+            if visited.insert(id) {
+                stack.extend(executes.get(&id).into_iter().flatten().copied());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::default_object;
+    use ag_iso_stack::object_pool::object::Macro;
+
+    fn id(value: u16) -> ObjectId {
+        ObjectId::new(value).unwrap()
+    }
+
+    #[test]
+    fn remapping_changes_command_ids_and_macro_refs() {
+        let mut macro_object = Object::Macro(Macro {
+            id: id(5),
+            commands: vec![
+                // Hide/show container 1000, then change the soft key mask of mask 1000
+                0xA0, 0xE8, 0x03, 0x01, 0xFF, 0xFF, 0xFF, 0xFF, //
+                0xAE, 0x01, 0xE8, 0x03, 0xE9, 0x03, 0xFF, 0xFF, //
+                // Execute macro 5
+                0xBE, 0x05, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            ],
+        });
+        let map = HashMap::from([(id(1000), id(2000)), (id(5), id(6))]);
+        remap_macro_references(&mut macro_object, &map);
+        let Object::Macro(macro_object) = &macro_object else {
+            unreachable!();
+        };
+        let ids: Vec<Vec<u16>> = split_macro_commands(&macro_object.commands)
+            .0
+            .into_iter()
+            .map(command_object_ids)
+            .collect();
+        assert_eq!(ids, vec![vec![2000], vec![2000, 1001], vec![6]]);
+
+        let mut button = default_object(ObjectType::Button);
+        macro_refs_mut(&mut button).unwrap().push(MacroRef {
+            macro_id: 5,
+            event_id: Event::OnKeyPress,
+        });
+        let map = HashMap::from([(id(5), id(300))]);
+        remap_macro_references(&mut button, &map);
+        assert_eq!(
+            macro_refs(&button)[0].macro_id,
+            5,
+            "300 does not fit in a macro ID"
+        );
+        remap_macro_references(&mut button, &HashMap::from([(id(5), id(7))]));
+        assert_eq!(macro_refs(&button)[0].macro_id, 7);
+    }
+
+    #[test]
+    fn remapping_changes_list_items_labels_and_object_values() {
+        let mut macro_object = Object::Macro(Macro {
+            id: id(5),
+            commands: vec![
+                // Set item 2 of list 1 to object 1000
+                0xB1, 0x01, 0x00, 0x02, 0xE8, 0x03, 0xFF, 0xFF, //
+                // Label object 1 with string variable 1000 and graphic 1000
+                0xB5, 0x01, 0x00, 0xE8, 0x03, 0xFF, 0xE8, 0x03, //
+                // Point object pointer 1 to object 1000
+                0xA8, 0x01, 0x00, 0xFF, 0xE8, 0x03, 0x00, 0x00,
+            ],
+        });
+        remap_macro_references(&mut macro_object, &HashMap::from([(id(1000), id(2000))]));
+        let Object::Macro(macro_object) = &macro_object else {
+            unreachable!();
+        };
+        assert_eq!(
+            macro_object.commands,
+            vec![
+                0xB1, 0x01, 0x00, 0x02, 0xD0, 0x07, 0xFF, 0xFF, //
+                0xB5, 0x01, 0x00, 0xD0, 0x07, 0xFF, 0xD0, 0x07, //
+                0xA8, 0x01, 0x00, 0xFF, 0xD0, 0x07, 0x00, 0x00,
+            ]
+        );
+    }
+
+    fn pool_with_macro(commands: Vec<u8>) -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        for (value, object_type) in [
+            (10, ObjectType::InputList),
+            (11, ObjectType::OutputString),
+            (20, ObjectType::StringVariable),
+        ] {
+            let mut object = default_object(object_type);
+            *object.mut_id() = id(value);
+            pool.add(object);
+        }
+        pool.add(Object::Macro(Macro {
+            id: id(1),
+            commands,
+        }));
+        pool
+    }
+
+    fn messages(pool: &ObjectPool) -> Vec<String> {
+        let mut issues = Vec::new();
+        validate_macros(pool, &mut issues);
+        issues.into_iter().map(|issue| issue.message).collect()
+    }
+
+    #[test]
+    fn missing_list_items_are_reported() {
+        // Set item 0 of list 10 to object 99, then clear item 1
+        let pool = pool_with_macro(vec![
+            0xB1, 0x0A, 0x00, 0x00, 0x63, 0x00, 0xFF, 0xFF, //
+            0xB1, 0x0A, 0x00, 0x01, 0xFF, 0xFF, 0xFF, 0xFF,
+        ]);
+        assert_eq!(
+            messages(&pool),
+            vec!["Macro 1: Change List item command refers to object 99 which does not exist"]
+        );
+    }
+
+    #[test]
+    fn object_label_references_are_checked() {
+        // Label object 11 with string variable 98 and graphic 20, then with variable 20 only
+        let pool = pool_with_macro(vec![
+            0xB5, 0x0B, 0x00, 0x62, 0x00, 0xFF, 0x14, 0x00, //
+            0xB5, 0x0B, 0x00, 0x14, 0x00, 0xFF, 0xFF, 0xFF,
+        ]);
+        assert_eq!(
+            messages(&pool),
+            vec![
+                "Macro 1: Change Object Label command refers to object 98 which does not exist",
+                "Macro 1: Change Object Label command can not be applied to StringVariable 20",
+            ]
+        );
+    }
+}
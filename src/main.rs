@@ -4,39 +4,371 @@
 
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 use ag_iso_stack::object_pool::object::*;
-use ag_iso_stack::object_pool::object_attributes::{DataCodeType, PictureGraphicFormat, Point};
+use ag_iso_stack::object_pool::object_attributes::{FontSize, PictureGraphicFormat, Point};
 use ag_iso_stack::object_pool::NullableObjectId;
 use ag_iso_stack::object_pool::ObjectId;
 use ag_iso_stack::object_pool::ObjectPool;
 use ag_iso_stack::object_pool::ObjectType;
+#[cfg(not(target_arch = "wasm32"))]
+use ag_iso_terminal_designer::create_picture_graphic;
+#[cfg(not(target_arch = "wasm32"))]
+use ag_iso_terminal_designer::default_settings_path;
+use ag_iso_terminal_designer::navigation_map;
+#[cfg(not(target_arch = "wasm32"))]
+use ag_iso_terminal_designer::open_in_viewer;
+use ag_iso_terminal_designer::reference_paths;
+use ag_iso_terminal_designer::Change;
+use ag_iso_terminal_designer::ChangeCategory;
+use ag_iso_terminal_designer::ChangelogFormat;
 use ag_iso_terminal_designer::ConfigurableObject;
-use ag_iso_terminal_designer::EditorProject;
-use ag_iso_terminal_designer::InteractiveMaskRenderer;
+use ag_iso_terminal_designer::HistoryFilter;
+use ag_iso_terminal_designer::RecoveryReport;
 use ag_iso_terminal_designer::RenderableObject;
+use ag_iso_terminal_designer::Workspace;
+use ag_iso_terminal_designer::{annotate_object, format_hex, parse_hex, replace_object_bytes};
+use ag_iso_terminal_designer::{apply_renames, c_identifier, header_conflicts, plan_renames};
+use ag_iso_terminal_designer::{apply_style_preset_to, preset_values, PresetValue, StylePreset};
+#[cfg(not(target_arch = "wasm32"))]
+use ag_iso_terminal_designer::{back_up_save, backup_dir, list_backups, restore_backup, Backup};
+#[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+use ag_iso_terminal_designer::{
+    baseline_path, RemoteError, RemoteRequest, RemoteResult, RemoteServer, DEFAULT_REMOTE_PORT,
+};
+#[cfg(all(feature = "can-capture", target_os = "linux"))]
+use ag_iso_terminal_designer::{capture_capabilities, SocketCanInterface, DEFAULT_CAPTURE_ADDRESS};
+use ag_iso_terminal_designer::{check_spelling, spell_checker, with_spell_checker, Dictionary};
+use ag_iso_terminal_designer::{colour_attributes, colour_usage};
+use ag_iso_terminal_designer::{copy_objects, is_clipboard_objects, paste_objects};
+use ag_iso_terminal_designer::{
+    dangling_references, expected_types, recreate_missing_object, redirect_dangling_reference,
+    remove_dangling_reference, DanglingReference,
+};
+use ag_iso_terminal_designer::{delete_orphans, orphan_attributes};
+use ag_iso_terminal_designer::{
+    designator_size_info, object_state, preview_mode, previewed_mask, with_preview_state,
+    InputMessage, PreviewMode,
+};
+use ag_iso_terminal_designer::{diff_pools, object_history, object_label};
+use ag_iso_terminal_designer::{
+    evaluate_watch_expression, simulated_variable_value, watch_variables,
+};
+#[cfg(not(target_arch = "wasm32"))]
+use ag_iso_terminal_designer::{
+    file_arguments, forward_to_running_instance, listen_for_open_requests, merge_from_disk,
+    register_file_associations, WatchedFile, WATCH_INTERVAL,
+};
+use ag_iso_terminal_designer::{find_id_conflicts, IdConflictResolution};
+use ag_iso_terminal_designer::{find_similar_objects, merge_duplicates, SimilarGroup};
+use ag_iso_terminal_designer::{fit_to_page_scale, MaskPrintRun, PrintOptions};
+use ag_iso_terminal_designer::{focus_order, move_focus_stop, render_focus_order_overlay};
+use ag_iso_terminal_designer::{
+    font_size_key, font_size_keys, ColourDepth, TerminalCapabilities, TerminalProfile,
+};
+use ag_iso_terminal_designer::{has_font_attributes, set_font_attributes, subtree};
+use ag_iso_terminal_designer::{import_images, set_picture_image, DEFAULT_TRANSPARENCY_COLOUR};
+use ag_iso_terminal_designer::{language, set_language, tr, tr_args, Language};
+use ag_iso_terminal_designer::{
+    load_reference_overlay, render_reference_overlay, request_reference_comparison,
+    set_reference_settings, update_reference_comparison,
+};
+use ag_iso_terminal_designer::{macro_command_name, macro_targets, macro_triggers};
+use ag_iso_terminal_designer::{make_unique_copy, object_parents};
+use ag_iso_terminal_designer::{minify_pool, MinifyOptions, MinifyReport};
+use ag_iso_terminal_designer::{
+    mirror_children, mirror_in_parents, object_refs, transform_children, MirrorAxis, Transform,
+};
+use ag_iso_terminal_designer::{
+    pixel_font_data, validate_font, validate_project, GoldenPool, Severity, PIXEL_FONT_NAME,
+};
+use ag_iso_terminal_designer::{pool_state, ReviewDocumentRun, StateScenario};
+use ag_iso_terminal_designer::{project_tasks, TaskTag};
+use ag_iso_terminal_designer::{render_fidelity, Fidelity};
+use ag_iso_terminal_designer::{render_selection_handles, InteractiveMaskRenderer};
+use ag_iso_terminal_designer::{render_terminal_frame, TerminalFrame};
+use ag_iso_terminal_designer::{set_string_value, string_inventory, TranslationStatus};
+use ag_iso_terminal_designer::{set_touch_mode, touch_mode, UiTheme, UI_SCALE_RANGE};
+use ag_iso_terminal_designer::{simulate_upload, UploadSimulation};
+use ag_iso_terminal_designer::{simulator_export, SimulatorSettings};
+use ag_iso_terminal_designer::{verify_export, ExportVerification};
+use ag_iso_terminal_designer::{vt_font_id, NON_PROPORTIONAL_FONT_SIZES};
+use ag_iso_terminal_designer::{AttributeChange, ObjectChange, ObjectDiff};
+use ag_iso_terminal_designer::{ComparisonResult, RegressionRun, DEFAULT_TOLERANCE};
+use ag_iso_terminal_designer::{ConnectOutcome, SimulatedVtStorage};
+use ag_iso_terminal_designer::{DesignerSettings, ExportSettings, SETTINGS_FILE};
+use ag_iso_terminal_designer::{DirtyMarker, EditorProject, MIN_SOFT_KEY_SIZE};
+use ag_iso_terminal_designer::{MaskDuplication, MaskThumbnails, ShareMode};
+use ag_iso_terminal_designer::{Plugin, PluginHook, PluginRun, ValidationIssue};
+use ag_iso_terminal_designer::{
+    ProjectFileFormat, OLDEST_PROJECT_FILE_VERSION, PROJECT_FILE_VERSION,
+};
+use ag_iso_terminal_designer::{ReviewComment, ReviewStatus};
+use ag_iso_terminal_designer::{DEFAULT_POOL_SIZE_BUDGET, DEFAULT_SOFT_KEY_COUNT};
+use ag_iso_terminal_designer::{EXTENDED_VERSION_LABEL_LENGTH, VERSION_LABEL_LENGTH};
 use eframe::egui;
+use std::collections::{HashMap, HashSet};
 use std::future::Future;
+use std::path::PathBuf;
+use std::sync::atomic::{AtomicI32, Ordering};
 use std::sync::mpsc::Receiver;
 use std::sync::mpsc::Sender;
+use std::sync::Arc;
 
 const OBJECT_HIERARCHY_ID: &str = "object_hierarchy_ui";
 
+/// Length of the longer side of the preview mini-map in points
+const MINI_MAP_SIZE: f32 = 160.0;
+
+/// Seconds the settings stay unchanged before they are written, so typing e.g. the author name
+/// does not write the file for every key
+#[cfg(not(target_arch = "wasm32"))]
+const SETTINGS_SAVE_DELAY: f64 = 1.0;
+#[cfg(feature = "scripting")]
+const EXAMPLE_SCRIPT: &str = r#"// Move every OutputNumber 2 px right on masks with "metric" in their name
+for mask in objects_of_type("DataMask") {
+    if !name(mask).contains("metric") { continue; }
+    for parent in [mask] + descendants(mask) {
+        for child in children(parent) {
+            if object_type(child) == "OutputNumber" {
+                move_child(parent, child, 2, 0);
+            }
+        }
+    }
+}
+describe("Move metric numbers 2 px right");
+"#;
+
+#[derive(Clone, Copy)]
 enum FileDialogReason {
     LoadPool,
     LoadProject,
     OpenImagePictureGraphics(ObjectId),
+    ChangelogBaseline(ChangelogFormat),
+    TerminalFont,
+    TerminalCapabilities,
+    SpellingDictionary,
+    LoadWorkspace,
+    AddWorkspacePool,
+    ReferenceScreenshot,
+    ImportSettings,
 }
 
+/// File name and content of a file picked in the file dialog
+type LoadedFile = (String, Vec<u8>);
+
+/// A file picked in the file dialog with its path, which is only known on native platforms
+type PickedFile = (LoadedFile, Option<PathBuf>);
+
 pub struct DesignerApp {
     project: Option<EditorProject>,
     file_dialog_reason: Option<FileDialogReason>,
-    file_channel: (Sender<Vec<u8>>, Receiver<Vec<u8>>),
+    file_channel: (Sender<PickedFile>, Receiver<PickedFile>),
+    image_import_channel: (Sender<Vec<LoadedFile>>, Receiver<Vec<LoadedFile>>),
+    image_import_log: Option<Vec<String>>,
+    export_verification_channel: (Sender<ExportVerification>, Receiver<ExportVerification>),
+
+    /// Result of reading back the last exported pool file
+    export_verification: Option<ExportVerification>,
     show_development_popup: bool,
     new_object_dialog: Option<(ObjectType, String)>,
-    apply_smart_naming_on_import: bool,
+    recovery_report: Option<RecoveryReport>,
+    show_recovery_report: bool,
+
+    /// New IDs for objects of the loaded pool that have the ID of another object
+    id_conflicts: Option<IdConflictResolution>,
+    show_colour_roles: bool,
+    show_colour_usage: bool,
+    colour_usage_lossy_only: bool,
+    new_colour_role: String,
+    selected_colour_theme: usize,
+    show_style_presets: bool,
+    selected_style_preset: usize,
+    show_history: bool,
+    scoped_undo_error: Option<String>,
+    history_filter: HistoryFilter,
+    history_selected_only: bool,
+    show_validation: bool,
+    reference_repair_error: Option<String>,
+    mask_duplication: Option<MaskDuplication>,
+    show_terminal_profiles: bool,
+    show_font_metrics: bool,
+    show_spell_check: bool,
+    show_strings: bool,
+    show_macro_references: bool,
+    macro_references_filter: String,
+    show_pointing_events: bool,
+    show_input_messages: bool,
+    show_watch_expressions: bool,
+    new_watch_expression: String,
+    show_navigation_map: bool,
+    show_similar_objects: bool,
+    show_tasks: bool,
+    show_transform: bool,
+    transform: Transform,
+    show_font_batch: bool,
+    show_orphan_cleanup: bool,
+
+    /// Orphans the user chose to keep, all others are deleted
+    orphans_kept: HashSet<ObjectId>,
+    orphan_cleanup_status: Option<String>,
+    show_hex_inspector: bool,
+    show_focus_order: bool,
+    show_state_sandbox: bool,
+    show_mask_print: bool,
+    print_options: PrintOptions,
+
+    /// Renders the mask to print, with whether the document is opened for printing or saved
+    mask_print_run: Option<(MaskPrintRun, bool)>,
+    mask_print_error: Option<String>,
+    show_upload_simulation: bool,
+    upload_simulation: Option<UploadSimulation>,
+
+    /// The state scenario shown in the preview, None for the state of the pool
+    active_state_scenario: Option<usize>,
+    new_state_scenario: String,
+    focus_order_error: Option<String>,
+    mask_thumbnails: MaskThumbnails,
+
+    /// Object whose bytes are being edited and the edited hex text
+    hex_edit: Option<(ObjectId, String)>,
+    hex_edit_error: Option<String>,
+    font_batch_scope: BatchScope,
+    show_bulk_rename: bool,
+    rename_scope: BatchScope,
+    rename_pattern: String,
+    rename_start: u32,
+    rename_status: Option<String>,
+    font_batch_font: Option<ObjectId>,
+    font_batch_status: Option<String>,
+    show_vt_simulator: bool,
+    show_reference_overlay: bool,
+
+    /// The object whose children are transformed and the indices of the chosen references
+    transform_selection: (Option<ObjectId>, HashSet<usize>),
+    show_minify: bool,
+    show_pool_version: bool,
+    show_vt_storage: bool,
+    vt_storage: SimulatedVtStorage,
+    /// The pool as IOP data for the terminal storage window, for a pool generation
+    vt_storage_iop: Option<(u64, Vec<u8>)>,
+    minify_options: MinifyOptions,
+    minify_report: Option<MinifyReport>,
+    workspace: Option<Workspace>,
+    show_workspace: bool,
+    workspace_name: String,
+    workspace_problems: Option<Vec<String>>,
+    task_filter: String,
+    task_tag_filter: Option<TaskTag>,
+    similar_objects: Option<(u64, Vec<SimilarGroup>)>,
+    show_preview_matrix: bool,
+    /// Pools of the preview matrix with the colours of each colour depth, for a pool generation
+    preview_matrix_pools: Option<(u64, Vec<(ColourDepth, ObjectPool)>)>,
+    show_plugins: bool,
+    #[cfg(feature = "scripting")]
+    show_script_console: bool,
+    #[cfg(feature = "scripting")]
+    script: String,
+    #[cfg(feature = "scripting")]
+    script_output: Vec<String>,
+    #[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+    remote: RemoteControl,
+    #[cfg(all(feature = "can-capture", target_os = "linux"))]
+    can_capture: CanCapture,
+    /// Files other instances hand over to open in this window
+    #[cfg(not(target_arch = "wasm32"))]
+    open_requests: Option<Receiver<PathBuf>>,
+    /// Handed over files that would replace a project with unsaved changes, until the user
+    /// decides to open them
+    #[cfg(not(target_arch = "wasm32"))]
+    pending_open_requests: Vec<PathBuf>,
+    /// The opened pool or project file, checked for changes made outside of the designer
+    #[cfg(not(target_arch = "wasm32"))]
+    watched_file: Option<WatchedFile>,
+    /// Content of the opened file after it changed on disk, until the user decides what to do
+    #[cfg(not(target_arch = "wasm32"))]
+    external_change: Option<Vec<u8>>,
+    /// The last saved or opened file, whose backups are shown
+    #[cfg(not(target_arch = "wasm32"))]
+    backup_source: Option<PathBuf>,
+    #[cfg(not(target_arch = "wasm32"))]
+    saved_file_channel: (Sender<PathBuf>, Receiver<PathBuf>),
+    /// Pools of projects that were written to a file, they are marked as saved
+    saved_project_channel: (Sender<ObjectPool>, Receiver<ObjectPool>),
+    /// Contents of pool files that were written, they are registered as exports of the pool version
+    exported_pool_channel: (Sender<Vec<u8>>, Receiver<Vec<u8>>),
+    #[cfg(not(target_arch = "wasm32"))]
+    show_backups: bool,
+    show_file_format: bool,
+    #[cfg(not(target_arch = "wasm32"))]
+    backups: Vec<Backup>,
+    #[cfg(not(target_arch = "wasm32"))]
+    backup_status: Option<String>,
+    plugins: Vec<Plugin>,
+    plugin_dir: String,
+
+    /// Messages of the last plugin runs and manifests that failed to load
+    plugin_log: Vec<String>,
+
+    /// Issues reported by the validate hooks of the plugins, shown in the validation window
+    plugin_issues: Vec<ValidationIssue>,
+
+    /// Plugin program that is running, only one runs at a time
+    plugin_run: Option<PluginRun>,
+    strings_filter: String,
+    strings_over_budget_only: bool,
+    font_metrics_sample: String,
+    font_metrics_proportional_height: u8,
+    font_metrics_inverted: bool,
+
+    /// Name and data hash of the custom fonts that are registered with egui, None before the
+    /// first registration
+    registered_fonts: Option<Vec<(String, u64)>>,
+
+    /// Why the last font file could not be added to the terminal profile
+    font_error: Option<String>,
+
+    /// Settings of the designer, the same for every project
+    settings: DesignerSettings,
+    /// The settings as they are stored in the settings file
+    #[cfg(not(target_arch = "wasm32"))]
+    saved_settings: DesignerSettings,
+    /// Time of the last change of the settings that is not written yet
+    #[cfg(not(target_arch = "wasm32"))]
+    settings_changed_at: Option<(DesignerSettings, f64)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    settings_path: Option<PathBuf>,
+    show_settings: bool,
+    settings_status: Option<String>,
+
+    /// Action whose shortcut is changed by pressing the new shortcut
+    recording_shortcut: Option<&'static str>,
+
+    /// Show the preview in a separate window instead of the central panel
+    preview_detached: bool,
+    show_regression: bool,
+    regression_dir: String,
+    regression_tolerance: u8,
+    regression_run: Option<RegressionRun>,
+
+    /// Renders the masks for the PDF design review document
+    review_document_run: Option<ReviewDocumentRun>,
+    regression_diff: Option<(ObjectId, egui::TextureHandle)>,
+
+    /// Set when started from the command line, receives the exit code once the run is finished
+    regression_exit_code: Option<Arc<AtomicI32>>,
+}
+
+/// Comment that is being written in the review section
+#[derive(Clone, Default)]
+struct ReviewDraft {
+    text: String,
+
+    /// Path of the comment that is replied to, or None for a new thread
+    reply_to: Option<Vec<usize>>,
 }
 
 impl DesignerApp {
     pub fn new(cc: &eframe::CreationContext<'_>) -> Self {
+        set_language(Language::system());
         let mut fonts = egui::FontDefinitions::default();
 
         // TODO: Create font files and load them here
@@ -110,17 +442,270 @@ impl DesignerApp {
             project: None,
             file_dialog_reason: None,
             file_channel: std::sync::mpsc::channel(),
+            image_import_channel: std::sync::mpsc::channel(),
+            image_import_log: None,
+            export_verification_channel: std::sync::mpsc::channel(),
+            export_verification: None,
             show_development_popup: true,
             new_object_dialog: None,
-            apply_smart_naming_on_import: true, // Default to true for better UX
+            recovery_report: None,
+            show_recovery_report: false,
+            id_conflicts: None,
+            show_colour_roles: false,
+            show_colour_usage: false,
+            colour_usage_lossy_only: false,
+            new_colour_role: String::new(),
+            selected_colour_theme: 0,
+            show_style_presets: false,
+            selected_style_preset: 0,
+            show_history: false,
+            scoped_undo_error: None,
+            history_filter: HistoryFilter::default(),
+            history_selected_only: false,
+            show_validation: false,
+            reference_repair_error: None,
+            mask_duplication: None,
+            show_terminal_profiles: false,
+            show_font_metrics: false,
+            show_spell_check: false,
+            show_strings: false,
+            show_macro_references: false,
+            macro_references_filter: String::new(),
+            show_pointing_events: false,
+            show_input_messages: false,
+            show_watch_expressions: false,
+            new_watch_expression: String::new(),
+            show_navigation_map: false,
+            show_similar_objects: false,
+            show_tasks: false,
+            show_transform: false,
+            font_batch_scope: BatchScope::Selection,
+            show_bulk_rename: false,
+            rename_scope: BatchScope::Selection,
+            rename_pattern: "{type}_{n}".to_string(),
+            rename_start: 1,
+            rename_status: None,
+            show_font_batch: false,
+            show_orphan_cleanup: false,
+            show_hex_inspector: false,
+            show_focus_order: false,
+            show_state_sandbox: false,
+            show_mask_print: false,
+            print_options: PrintOptions::default(),
+            mask_print_run: None,
+            mask_print_error: None,
+            show_upload_simulation: false,
+            upload_simulation: None,
+            active_state_scenario: None,
+            new_state_scenario: String::new(),
+            focus_order_error: None,
+            mask_thumbnails: MaskThumbnails::default(),
+            hex_edit: None,
+            hex_edit_error: None,
+            orphans_kept: HashSet::new(),
+            orphan_cleanup_status: None,
+            font_batch_font: None,
+            font_batch_status: None,
+            transform: Transform::default(),
+            show_vt_simulator: false,
+            show_reference_overlay: false,
+            transform_selection: (None, HashSet::new()),
+            show_minify: false,
+            show_pool_version: false,
+            show_vt_storage: false,
+            vt_storage: SimulatedVtStorage::default(),
+            vt_storage_iop: None,
+            minify_options: MinifyOptions::default(),
+            minify_report: None,
+            workspace: None,
+            show_workspace: false,
+            workspace_name: "Product".to_string(),
+            workspace_problems: None,
+            task_filter: String::new(),
+            task_tag_filter: None,
+            similar_objects: None,
+            show_preview_matrix: false,
+            preview_matrix_pools: None,
+            show_plugins: false,
+            #[cfg(feature = "scripting")]
+            show_script_console: false,
+            #[cfg(feature = "scripting")]
+            script: EXAMPLE_SCRIPT.to_string(),
+            #[cfg(feature = "scripting")]
+            script_output: Vec::new(),
+            #[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+            remote: RemoteControl::default(),
+            #[cfg(all(feature = "can-capture", target_os = "linux"))]
+            can_capture: CanCapture::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            open_requests: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            pending_open_requests: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            watched_file: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            external_change: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            backup_source: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            saved_file_channel: std::sync::mpsc::channel(),
+            saved_project_channel: std::sync::mpsc::channel(),
+            exported_pool_channel: std::sync::mpsc::channel(),
+            #[cfg(not(target_arch = "wasm32"))]
+            show_backups: false,
+            show_file_format: false,
+            #[cfg(not(target_arch = "wasm32"))]
+            backups: Vec::new(),
+            #[cfg(not(target_arch = "wasm32"))]
+            backup_status: None,
+            plugins: Vec::new(),
+            plugin_dir: ag_iso_terminal_designer::default_plugin_dir()
+                .map(|dir| dir.display().to_string())
+                .unwrap_or_default(),
+            plugin_log: Vec::new(),
+            plugin_issues: Vec::new(),
+            plugin_run: None,
+            strings_filter: String::new(),
+            strings_over_budget_only: false,
+            font_metrics_sample: "Speed 12.5 km/h".to_string(),
+            font_metrics_proportional_height: 16,
+            font_metrics_inverted: false,
+            registered_fonts: None,
+            font_error: None,
+            settings: DesignerSettings::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            saved_settings: DesignerSettings::default(),
+            #[cfg(not(target_arch = "wasm32"))]
+            settings_changed_at: None,
+            #[cfg(not(target_arch = "wasm32"))]
+            settings_path: None,
+            show_settings: false,
+            settings_status: None,
+            recording_shortcut: None,
+            preview_detached: false,
+            show_regression: false,
+            regression_dir: String::new(),
+            regression_tolerance: DEFAULT_TOLERANCE,
+            regression_run: None,
+            review_document_run: None,
+            regression_diff: None,
+            regression_exit_code: None,
         }
     }
 }
 
 impl DesignerApp {
+    /// Load the designer settings from the settings file and apply them. A settings file that can
+    /// not be read is left alone, the changed settings are not saved then.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn load_settings(&mut self, ctx: &egui::Context) {
+        let Some(path) = default_settings_path() else {
+            return;
+        };
+        match DesignerSettings::load(&path) {
+            Ok(settings) => {
+                self.saved_settings = settings.clone();
+                self.settings_path = Some(path);
+                self.apply_settings(settings, ctx);
+            }
+            Err(e) => {
+                log::error!("{}", e);
+                self.settings_status = Some(format!("{}, changed settings are not saved", e));
+            }
+        }
+    }
+
+    fn apply_settings(&mut self, settings: DesignerSettings, ctx: &egui::Context) {
+        settings.apply_to_context(ctx);
+        settings.export.apply(&mut self.minify_options);
+        with_spell_checker(ctx, |checker| {
+            checker.ignored = settings.ignored_words.clone();
+        });
+        self.settings = settings;
+    }
+
+    /// Take the settings that are kept by egui and other parts of the designer into the settings
+    fn collect_settings(&mut self, ctx: &egui::Context) {
+        self.settings.ui_scale = ctx.zoom_factor();
+        self.settings.touch_mode = touch_mode(ctx);
+        if language() != self.settings.language() {
+            self.settings.language = Some(language().code().to_string());
+        }
+        self.settings.export = ExportSettings::from(&self.minify_options);
+        let checker = spell_checker(ctx);
+        if checker.ignored != self.settings.ignored_words {
+            self.settings.ignored_words = checker.ignored.clone();
+        }
+    }
+
+    /// Write the settings to the settings file when they changed, once nothing is dragged and
+    /// they stayed the same for [`SETTINGS_SAVE_DELAY`] or the window closes
+    #[cfg(not(target_arch = "wasm32"))]
+    fn save_changed_settings(&mut self, ctx: &egui::Context) {
+        let Some(path) = &self.settings_path else {
+            return;
+        };
+        if self.settings == self.saved_settings {
+            self.settings_changed_at = None;
+            return;
+        }
+        let now = ctx.input(|i| i.time);
+        let changed_at = match &self.settings_changed_at {
+            Some((settings, changed_at)) if *settings == self.settings => *changed_at,
+            _ => {
+                self.settings_changed_at = Some((self.settings.clone(), now));
+                now
+            }
+        };
+        let closing = ctx.input(|i| i.viewport().close_requested());
+        let waiting = SETTINGS_SAVE_DELAY - (now - changed_at);
+        if !closing && (waiting > 0.0 || ctx.is_using_pointer()) {
+            ctx.request_repaint_after(std::time::Duration::from_secs_f64(waiting.max(0.1)));
+            return;
+        }
+        self.settings_changed_at = None;
+        match self.settings.save(path) {
+            Ok(()) => self.saved_settings = self.settings.clone(),
+            Err(e) => {
+                log::error!("{}", e);
+                self.settings_status = Some(format!("{}, changed settings are not saved", e));
+                self.settings_path = None;
+            }
+        }
+    }
+
+    /// Open a file dialog to save the settings, to share them with the team
+    fn export_settings(&mut self) {
+        let contents = match self.settings.to_toml() {
+            Ok(contents) => contents,
+            Err(e) => {
+                self.settings_status = Some(e);
+                return;
+            }
+        };
+        let task = rfd::AsyncFileDialog::new()
+            .set_file_name(SETTINGS_FILE)
+            .add_filter("Designer settings", &["toml"])
+            .save_file();
+        execute(async move {
+            if let Some(file) = task.await {
+                _ = file.write(contents.as_bytes()).await;
+            }
+        });
+    }
+
     /// Open a file dialog
     fn open_file_dialog(&mut self, reason: FileDialogReason, ctx: &egui::Context) {
-        let is_image_loading = matches!(reason, FileDialogReason::OpenImagePictureGraphics(_));
+        let is_image_loading = matches!(
+            reason,
+            FileDialogReason::OpenImagePictureGraphics(_) | FileDialogReason::ReferenceScreenshot
+        );
+        let is_font_loading = matches!(reason, FileDialogReason::TerminalFont);
+        let is_capabilities_loading = matches!(reason, FileDialogReason::TerminalCapabilities);
+        let is_dictionary_loading = matches!(reason, FileDialogReason::SpellingDictionary);
+        let is_workspace_loading = matches!(reason, FileDialogReason::LoadWorkspace);
+        let is_workspace_pool_loading = matches!(reason, FileDialogReason::AddWorkspacePool);
+        let is_settings_loading = matches!(reason, FileDialogReason::ImportSettings);
         self.file_dialog_reason = Some(reason);
 
         let sender = self.file_channel.0.clone();
@@ -135,6 +720,24 @@ impl DesignerApp {
                 ],
             );
         }
+        if is_font_loading {
+            dialog = dialog.add_filter("Font Files", &["ttf", "otf"]);
+        }
+        if is_capabilities_loading {
+            dialog = dialog.add_filter("Terminal capabilities", &["txt", "cap"]);
+        }
+        if is_dictionary_loading {
+            dialog = dialog.add_filter("Dictionaries", &["dic", "txt"]);
+        }
+        if is_workspace_loading {
+            dialog = dialog.add_filter("AgIsoTerminal Workspace", &["aitw"]);
+        }
+        if is_workspace_pool_loading {
+            dialog = dialog.add_filter("Projects and pools", &["aitp", "iop"]);
+        }
+        if is_settings_loading {
+            dialog = dialog.add_filter("Designer settings", &["toml"]);
+        }
 
         let task = dialog.pick_file();
         let ctx = ctx.clone();
@@ -142,180 +745,556 @@ impl DesignerApp {
             let file = task.await;
             if let Some(file) = file {
                 let content = file.read().await;
-                let _ = sender.send(content);
+                #[cfg(not(target_arch = "wasm32"))]
+                let path = Some(file.path().to_path_buf());
+                #[cfg(target_arch = "wasm32")]
+                let path = None;
+                let _ = sender.send(((file.file_name(), content), path));
             }
             ctx.request_repaint();
         });
     }
 
-    /// Handle a file loaded in the file dialog
-    fn handle_file_loaded(&mut self) {
-        if let Ok(content) = self.file_channel.1.try_recv() {
-            match self.file_dialog_reason {
-                Some(FileDialogReason::LoadPool) => {
-                    let project = EditorProject::from(ObjectPool::from_iop(content));
-                    // Apply smart naming to all objects that don't have custom names (if enabled)
-                    if self.apply_smart_naming_on_import {
-                        project.apply_smart_naming_to_all_objects();
-                    }
-                    self.project = Some(project);
+    /// Open a file dialog to pick the images, and optionally a manifest, of a batch import
+    fn open_image_import_dialog(&mut self, ctx: &egui::Context) {
+        let sender = self.image_import_channel.0.clone();
+        let task = rfd::AsyncFileDialog::new()
+            .add_filter(
+                "Images and manifest",
+                &[
+                    "png", "jpg", "jpeg", "bmp", "gif", "ico", "tiff", "tif", "webp", "csv",
+                ],
+            )
+            .pick_files();
+        let ctx = ctx.clone();
+        execute(async move {
+            if let Some(files) = task.await {
+                let mut loaded = Vec::new();
+                for file in files {
+                    loaded.push((file.file_name(), file.read().await));
                 }
-                Some(FileDialogReason::LoadProject) => {
-                    match EditorProject::load_project(content) {
-                        Ok(project) => {
-                            self.project = Some(project);
-                        }
-                        Err(e) => {
-                            log::error!("Failed to load project: {}", e);
-                            // TODO: Show error dialog
+                let _ = sender.send(loaded);
+            }
+            ctx.request_repaint();
+        });
+    }
+
+    /// Handle the files picked for a batch image import
+    fn handle_image_import(&mut self) {
+        if let Ok(files) = self.image_import_channel.1.try_recv() {
+            let Some(project) = &self.project else {
+                return;
+            };
+            self.image_import_log = Some(match import_images(project, &files) {
+                Ok(log) => log,
+                Err(e) => vec![e],
+            });
+        }
+    }
+
+    /// Copy the selected object with everything it references on Ctrl+C, and paste copied
+    /// objects on Ctrl+V, also when they come from another designer instance
+    fn handle_object_clipboard(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            return;
+        };
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let events = ctx.input(|i| i.events.clone());
+        for event in events {
+            match event {
+                egui::Event::Copy => {
+                    if let Some(selected) = project.get_selected().0 {
+                        match copy_objects(project, selected) {
+                            Ok(text) => ctx.copy_text(text),
+                            Err(e) => log::error!("{}", e),
                         }
                     }
                 }
-                Some(FileDialogReason::OpenImagePictureGraphics(id)) => {
-                    if let Some(pool) = &mut self.project {
-                        if let Some(obj) = pool.get_mut_pool().borrow_mut().object_mut_by_id(id) {
-                            match obj {
-                                Object::PictureGraphic(o) => {
-                                    if let Ok(img) = image::load_from_memory(&content) {
-                                        // Update dimensions based on the new picture
-                                        let w = img.width();
-                                        let h = img.height();
-
-                                        if w > u16::MAX as u32 || h > u16::MAX as u32 {
-                                            log::error!(
-                                                "Image dimensions exceed maximum size of {}x{}",
-                                                u16::MAX,
-                                                u16::MAX
-                                            );
-                                            return;
-                                        }
-
-                                        o.actual_width = w as u16;
-                                        o.actual_height = h as u16;
-                                        if o.width == 0 {
-                                            o.width = o.actual_width;
-                                        }
-
-                                        // Set format by default to 8-bit color, user can change it in UI
-                                        o.format = PictureGraphicFormat::EightBit;
-
-                                        // We set transparent color to 1 (arbitrary choice) as we
-                                        // only use index 15..255 for actual colors
-                                        o.transparency_colour = 1;
-                                        o.options.transparent = true;
+                egui::Event::Paste(text) if is_clipboard_objects(&text) => {
+                    if let Err(e) = paste_objects(project, &text) {
+                        log::error!("{}", e);
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
 
-                                        let rgba = if let Some(view) = img.as_rgba8() {
-                                            // Borrowed view (no allocation)
-                                            std::borrow::Cow::Borrowed(view)
-                                        } else {
-                                            // Allocates once if the image isn't already RGBA8
-                                            std::borrow::Cow::Owned(img.to_rgba8())
-                                        };
+    /// Create a picture graphic from the image on the system clipboard and select it. Errors are
+    /// only reported when asked for, the paste shortcut is also used for text.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn paste_clipboard_image(&mut self, report_errors: bool) {
+        let Some(project) = &self.project else {
+            return;
+        };
+        let name = project.generate_smart_name_for_new_object(ObjectType::PictureGraphic);
+        let result =
+            clipboard_image().and_then(|image| create_picture_graphic(project, &image, &name));
+        match result {
+            Ok(id) => {
+                project
+                    .get_mut_selected()
+                    .replace(NullableObjectId(Some(id)));
+                self.image_import_log = Some(vec![format!(
+                    "Clipboard: created picture graphic {} '{}'",
+                    id.value(),
+                    name
+                )]);
+            }
+            Err(e) if report_errors => {
+                self.image_import_log = Some(vec![format!("Clipboard: {}", e)])
+            }
+            Err(e) => log::debug!("Not pasting an image: {}", e),
+        }
+    }
 
-                                        // Build raw and run-length encoded data
-                                        let pixel_count = (w as usize) * (h as usize);
+    /// Paste an image on Ctrl+V. egui only turns the shortcut into a paste event when the
+    /// clipboard holds text, but the release of the V key still comes through.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_image_paste(&mut self, ctx: &egui::Context) {
+        if ctx.wants_keyboard_input() {
+            return;
+        }
+        let pasted = ctx.input(|i| {
+            i.events.iter().any(|event| {
+                matches!(
+                    event,
+                    egui::Event::Key { key: egui::Key::V, pressed: false, modifiers, .. }
+                        if modifiers.command
+                )
+            })
+        });
+        if pasted {
+            self.paste_clipboard_image(false);
+        }
+    }
 
-                                        // Worst case: raw = N, rle = 2*N
-                                        let mut raw = Vec::with_capacity(pixel_count);
-                                        let mut rle = Vec::with_capacity(pixel_count * 2);
+    /// Open a pool, project or workspace given on the command line or handed over by another
+    /// instance. With a workspace open, pools and projects are added to it as another pool.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn open_path(&mut self, path: &std::path::Path, ctx: &egui::Context) {
+        let content = match std::fs::read(path) {
+            Ok(content) => content,
+            Err(e) => {
+                log::error!("Failed to open {}: {}", path.display(), e);
+                return;
+            }
+        };
+        let extension = path
+            .extension()
+            .map(|e| e.to_string_lossy().to_ascii_lowercase())
+            .unwrap_or_default();
+        let reason = match extension.as_str() {
+            "aitw" => FileDialogReason::LoadWorkspace,
+            _ if self.workspace.is_some() => {
+                self.show_workspace = true;
+                FileDialogReason::AddWorkspacePool
+            }
+            "aitp" => FileDialogReason::LoadProject,
+            _ => FileDialogReason::LoadPool,
+        };
+        let file_name = path
+            .file_name()
+            .map_or(String::new(), |name| name.to_string_lossy().to_string());
+        log::info!("Opening {}", path.display());
+        self.load_file(reason, file_name, content, ctx);
+        self.watch_opened_file(reason, Some(path.to_path_buf()));
+    }
 
-                                        let mut have_run = false;
-                                        let mut run_value: u8 = 0;
-                                        let mut run_count: u8 = 0;
+    /// Watch an opened pool or project for changes made outside of the designer
+    fn watch_opened_file(&mut self, reason: FileDialogReason, path: Option<PathBuf>) {
+        #[cfg(not(target_arch = "wasm32"))]
+        match reason {
+            FileDialogReason::LoadPool | FileDialogReason::LoadProject => {
+                self.backup_source = path.clone();
+                self.refresh_backups();
+                self.watched_file = path.map(WatchedFile::new);
+                self.external_change = None;
+            }
+            FileDialogReason::LoadWorkspace => {
+                self.watched_file = None;
+                self.external_change = None;
+            }
+            _ => (),
+        }
+        #[cfg(target_arch = "wasm32")]
+        let _ = (reason, path);
+    }
 
-                                        for p in rgba.pixels() {
-                                            let idx = if p[3] == 0 {
-                                                o.transparency_colour
-                                            } else {
-                                                find_closest_color_index(p[0], p[1], p[2])
-                                            };
+    /// List the backups of the last saved or opened file
+    #[cfg(not(target_arch = "wasm32"))]
+    fn refresh_backups(&mut self) {
+        self.backups = self
+            .backup_source
+            .as_deref()
+            .map(|path| list_backups(&backup_dir(path)))
+            .unwrap_or_default();
+    }
 
-                                            raw.push(idx);
+    /// Check the opened file for changes made outside of the designer
+    #[cfg(not(target_arch = "wasm32"))]
+    fn check_watched_file(&mut self, ctx: &egui::Context) {
+        let (Some(watched), Some(project)) = (&mut self.watched_file, &self.project) else {
+            return;
+        };
+        ctx.request_repaint_after(WATCH_INTERVAL);
+        if let Some(content) = watched.poll() {
+            if !watched.matches_project(&content, project) {
+                self.external_change = Some(content);
+            }
+        }
+    }
 
-                                            if !have_run {
-                                                have_run = true;
-                                                run_value = idx;
-                                                run_count = 1;
-                                                continue;
-                                            }
+    /// Open the files other instances handed over and bring the window to the front. Files that
+    /// would replace unsaved changes are only opened when the user confirms.
+    #[cfg(not(target_arch = "wasm32"))]
+    fn handle_open_requests(&mut self, ctx: &egui::Context) {
+        let Some(receiver) = &self.open_requests else {
+            return;
+        };
+        let paths: Vec<PathBuf> = receiver.try_iter().collect();
+        if paths.is_empty() {
+            return;
+        }
+        for path in paths {
+            // Pools and projects are added to an open workspace, nothing is replaced then
+            let replaces_project = self.workspace.is_none()
+                || path
+                    .extension()
+                    .is_some_and(|ext| ext.eq_ignore_ascii_case("aitw"));
+            let unsaved = self
+                .project
+                .as_ref()
+                .is_some_and(|project| project.has_unsaved_changes());
+            if replaces_project && unsaved {
+                self.pending_open_requests.push(path);
+            } else {
+                self.open_path(&path, ctx);
+            }
+        }
+        ctx.send_viewport_cmd(egui::ViewportCommand::Minimized(false));
+        ctx.send_viewport_cmd(egui::ViewportCommand::Focus);
+    }
 
-                                            if idx == run_value && run_count < u8::MAX {
-                                                run_count += 1;
-                                            } else {
-                                                rle.push(run_count);
-                                                rle.push(run_value);
-                                                run_value = idx;
-                                                run_count = 1;
-                                            }
-                                        }
+    /// Handle a file loaded in the file dialog
+    fn handle_file_loaded(&mut self, ctx: &egui::Context) {
+        if let Ok(((file_name, content), path)) = self.file_channel.1.try_recv() {
+            if let Some(reason) = self.file_dialog_reason {
+                self.load_file(reason, file_name, content, ctx);
+                self.watch_opened_file(reason, path);
+            }
+        }
+    }
 
-                                        // flush final run
-                                        if have_run {
-                                            rle.push(run_count);
-                                            rle.push(run_value);
-                                        }
+    /// Load a file picked in the file dialog or opened from outside the designer
+    fn load_file(
+        &mut self,
+        reason: FileDialogReason,
+        file_name: String,
+        content: Vec<u8>,
+        ctx: &egui::Context,
+    ) {
+        match reason {
+            FileDialogReason::LoadPool => {
+                let (pool, report) = ag_iso_terminal_designer::recover_pool(&content);
+                let mut project = EditorProject::from(pool);
+                if !self.settings.terminal_profiles.is_empty() {
+                    project.terminal_profiles = self.settings.terminal_profiles.clone();
+                }
+                // Apply smart naming to all objects that don't have custom names (if enabled)
+                if self.settings.apply_smart_naming_on_import {
+                    project.apply_smart_naming_to_all_objects();
+                }
+                if report.is_clean() {
+                    self.recovery_report = None;
+                } else {
+                    log::warn!(
+                        "Pool is damaged: recovered {} objects, skipped {} bytes in {} regions",
+                        report.recovered_objects,
+                        report.skipped_bytes(),
+                        report.skipped_regions.len()
+                    );
+                    // Only allow inspecting the salvaged objects, editing could silently drop data
+                    project.set_read_only(true);
+                    self.recovery_report = Some(report);
+                    self.show_recovery_report = true;
+                }
+                self.id_conflicts = find_id_conflicts(project.get_pool());
+                self.project = Some(project);
+                self.workspace = None;
+            }
+            FileDialogReason::LoadProject => {
+                match EditorProject::load_project(content) {
+                    Ok(project) => {
+                        self.id_conflicts = find_id_conflicts(project.get_pool());
+                        self.show_file_format = project.file_format.needs_prompt();
+                        self.project = Some(project);
+                        self.workspace = None;
+                        self.recovery_report = None;
+                    }
+                    Err(e) => {
+                        log::error!("Failed to load project: {}", e);
+                        // TODO: Show error dialog
+                    }
+                }
+            }
+            FileDialogReason::LoadWorkspace => {
+                match Workspace::from_bytes(&content)
+                    .and_then(|workspace| Ok((workspace.load(workspace.active)?, workspace)))
+                {
+                    Ok((project, workspace)) => {
+                        self.id_conflicts = find_id_conflicts(project.get_pool());
+                        self.project = Some(project);
+                        self.workspace = Some(workspace);
+                        self.recovery_report = None;
+                        self.workspace_problems = None;
+                    }
+                    Err(e) => log::error!("Failed to load workspace: {}", e),
+                }
+            }
+            FileDialogReason::AddWorkspacePool => {
+                if let Some(workspace) = &mut self.workspace {
+                    let name = std::path::Path::new(&file_name)
+                        .file_stem()
+                        .map_or(file_name.clone(), |s| s.to_string_lossy().to_string());
+                    let project = if file_name.ends_with(".aitp") {
+                        EditorProject::load_project(content)
+                    } else {
+                        // A damaged pool can only be inspected read-only, it can not join a workspace
+                        let (pool, report) = ag_iso_terminal_designer::recover_pool(&content);
+                        if report.is_clean() {
+                            Ok(EditorProject::from(pool))
+                        } else {
+                            Err(format!(
+                                "The pool is damaged: recovered {} objects, skipped {} bytes",
+                                report.recovered_objects,
+                                report.skipped_bytes()
+                            ))
+                        }
+                    };
+                    if let Err(e) = project.and_then(|p| workspace.add_member(name, &p)) {
+                        log::error!("Failed to add pool to the workspace: {}", e);
+                    }
+                }
+            }
+            FileDialogReason::ChangelogBaseline(format) => {
+                if let Some(project) = &self.project {
+                    let (baseline, report) = ag_iso_terminal_designer::recover_pool(&content);
+                    if !report.is_clean() {
+                        log::warn!(
+                            "Baseline pool is damaged: recovered {} objects, skipped {} bytes",
+                            report.recovered_objects,
+                            report.skipped_bytes()
+                        );
+                    }
+                    let changelog =
+                        ag_iso_terminal_designer::generate_changelog(project, &baseline, format);
+                    self.save_changelog(changelog, format);
+                }
+            }
+            FileDialogReason::TerminalFont => {
+                let profile = self
+                    .project
+                    .as_mut()
+                    .and_then(|p| p.terminal_profiles.get_mut(p.active_profile));
+                if let Some(profile) = profile {
+                    let name = std::path::Path::new(&file_name)
+                        .file_stem()
+                        .map_or(file_name.clone(), |s| s.to_string_lossy().to_string());
+                    self.font_error = profile.add_font(name, content).err();
+                }
+            }
+            FileDialogReason::TerminalCapabilities => {
+                match TerminalCapabilities::parse(&String::from_utf8_lossy(&content)) {
+                    Ok(capabilities) => {
+                        let Some(project) = &mut self.project else {
+                            return;
+                        };
+                        let mut profile = TerminalProfile {
+                            name: std::path::Path::new(&file_name)
+                                .file_stem()
+                                .map_or(file_name.clone(), |s| s.to_string_lossy().to_string()),
+                            ..Default::default()
+                        };
+                        profile.apply_capabilities(capabilities);
+                        project.terminal_profiles.push(profile);
+                        project.active_profile = project.terminal_profiles.len() - 1;
+                    }
+                    Err(e) => log::error!("Failed to import {}: {}", file_name, e),
+                }
+            }
+            FileDialogReason::SpellingDictionary => {
+                let language = std::path::Path::new(&file_name)
+                    .file_stem()
+                    .map_or(file_name.clone(), |s| s.to_string_lossy().to_string());
+                let text = String::from_utf8_lossy(&content);
+                let dictionary = Dictionary::from_word_list(language, &text);
+                with_spell_checker(ctx, |checker| {
+                    checker
+                        .dictionaries
+                        .retain(|d| d.language != dictionary.language);
+                    checker.dictionaries.push(dictionary);
+                    checker.enabled = true;
+                });
+            }
+            FileDialogReason::ReferenceScreenshot => {
+                if let Err(e) = load_reference_overlay(ctx, file_name, &content) {
+                    log::error!("{}", e);
+                }
+            }
+            FileDialogReason::ImportSettings => {
+                let settings = std::str::from_utf8(&content)
+                    .map_err(|e| e.to_string())
+                    .and_then(DesignerSettings::from_toml);
+                self.settings_status = Some(match settings {
+                    Ok(settings) => {
+                        self.apply_settings(settings, ctx);
+                        tr_args("Imported the settings from {}", &[&file_name])
+                    }
+                    Err(e) => tr_args("Failed to import {}: {}", &[&file_name, &e]),
+                });
+            }
+            FileDialogReason::OpenImagePictureGraphics(id) => {
+                if let Some(pool) = &mut self.project {
+                    if let Some(Object::PictureGraphic(o)) =
+                        pool.get_mut_pool().borrow_mut().object_mut_by_id(id)
+                    {
+                        match image::load_from_memory(&content) {
+                            Ok(img) => {
+                                // Index 1 is not used by the colour cube of 8-bit images
+                                o.transparency_colour = DEFAULT_TRANSPARENCY_COLOUR;
+                                o.options.transparent = true;
 
-                                        // Choose the best encoding
-                                        if rle.len() < raw.len() {
-                                            o.data = rle;
-                                            o.options.data_code_type = DataCodeType::RunLength;
-                                            log::info!(
-                                            "Selected run-length encoding ({} bytes) over raw ({} bytes)",
-                                            o.data.len(),
-                                            raw.len()
-                                        );
-                                        } else {
-                                            o.data = raw;
-                                            o.options.data_code_type = DataCodeType::Raw;
-                                            log::info!(
-                                            "Selected raw encoding ({} bytes) over run-length ({} bytes)",
-                                            o.data.len(),
-                                            rle.len()
-                                        );
-                                        }
-                                    } else {
-                                        log::error!("Failed to decode image");
-                                    }
+                                // Default to 8-bit color, the user can change it in the UI
+                                if let Err(e) =
+                                    set_picture_image(o, &img, PictureGraphicFormat::EightBit)
+                                {
+                                    log::error!("{}", e);
                                 }
-                                _ => (),
                             }
+                            Err(_) => log::error!("Failed to decode image"),
                         }
                     }
                 }
-                _ => (),
             }
         }
     }
 
     /// Open a file dialog to save a pool file
-    fn save_pool(&mut self) {
+    fn save_pool(&mut self, ctx: &egui::Context) {
         if let Some(pool) = &self.project {
-            let task = rfd::AsyncFileDialog::new()
-                .set_file_name("object_pool.iop")
-                .save_file();
             let contents = pool.get_pool().as_iop();
-            execute(async move {
-                let file = task.await;
-                if let Some(file) = file {
-                    _ = file.write(&contents).await;
+            let exported = pool.get_pool().clone();
+            self.write_verified_pool(exported, contents, ctx);
+        }
+    }
+
+    /// Open a file dialog to save the pool file, then read the file back and compare it with the
+    /// exported pool. The export is registered with the pool version once the file is written.
+    fn write_verified_pool(&self, exported: ObjectPool, contents: Vec<u8>, ctx: &egui::Context) {
+        let task = rfd::AsyncFileDialog::new()
+            .set_file_name("object_pool.iop")
+            .save_file();
+        let sender = self.export_verification_channel.0.clone();
+        let exported_sender = self.exported_pool_channel.0.clone();
+        let ctx = ctx.clone();
+        // The project is backed up with the pool, so the object names can be restored too
+        #[cfg(not(target_arch = "wasm32"))]
+        let project_backup = self
+            .project
+            .as_ref()
+            .filter(|_| self.settings.backup_count > 0)
+            .and_then(|project| project.save_project().ok());
+        #[cfg(not(target_arch = "wasm32"))]
+        let (backup_count, saved_sender) = (
+            self.settings.backup_count,
+            self.saved_file_channel.0.clone(),
+        );
+        execute(async move {
+            let Some(file) = task.await else {
+                return;
+            };
+            if let Err(e) = file.write(&contents).await {
+                log::error!("Failed to write the pool file: {}", e);
+                return;
+            }
+            let _ = exported_sender.send(contents.clone());
+            #[cfg(not(target_arch = "wasm32"))]
+            {
+                let mut files: Vec<(&str, &[u8])> = vec![("iop", &contents)];
+                if let Some(project) = &project_backup {
+                    files.push(("aitp", project));
                 }
-            });
+                back_up_saved_file(file.path(), &files, backup_count, &saved_sender);
+            }
+            // Browsers only offer the file as a download, so the written contents are checked
+            #[cfg(not(target_arch = "wasm32"))]
+            let written = file.read().await;
+            #[cfg(target_arch = "wasm32")]
+            let written = contents;
+            let _ = sender.send(verify_export(&exported, &file.file_name(), &written));
+            ctx.request_repaint();
+        });
+    }
+
+    /// Open a file dialog to save a minified copy of the pool
+    fn save_minified_pool(&mut self, ctx: &egui::Context) {
+        if let Some(project) = &self.project {
+            let mut options = self.minify_options.clone();
+            options.keep.extend(project.get_bookmarks());
+            let (pool, report) = minify_pool(project.get_pool(), &options);
+            let contents = pool.as_iop();
+            self.minify_report = Some(report);
+            self.write_verified_pool(pool, contents, ctx);
         }
     }
 
+    /// Open a file dialog to save a changelog
+    fn save_changelog(&self, changelog: String, format: ChangelogFormat) {
+        let task = rfd::AsyncFileDialog::new()
+            .set_file_name(format!("changelog.{}", format.extension()))
+            .save_file();
+        execute(async move {
+            let file = task.await;
+            if let Some(file) = file {
+                _ = file.write(changelog.as_bytes()).await;
+            }
+        });
+    }
+
     /// Open a file dialog to save a project file
     fn save_project(&mut self) {
-        if let Some(project) = &self.project {
+        if let Some(project) = &mut self.project {
             match project.save_project() {
                 Ok(contents) => {
+                    let saved_pool = project.get_pool().clone();
+                    let project_sender = self.saved_project_channel.0.clone();
                     let task = rfd::AsyncFileDialog::new()
                         .set_file_name("project.aitp")
                         .add_filter("AgIsoTerminal Project", &["aitp"])
                         .save_file();
+                    #[cfg(not(target_arch = "wasm32"))]
+                    let (backup_count, saved_sender) = (
+                        self.settings.backup_count,
+                        self.saved_file_channel.0.clone(),
+                    );
                     execute(async move {
                         let file = task.await;
                         if let Some(file) = file {
-                            _ = file.write(&contents).await;
+                            if let Err(e) = file.write(&contents).await {
+                                log::error!("Failed to write the project file: {}", e);
+                                return;
+                            }
+                            _ = project_sender.send(saved_pool);
+                            #[cfg(not(target_arch = "wasm32"))]
+                            back_up_saved_file(
+                                file.path(),
+                                &[("aitp", &contents)],
+                                backup_count,
+                                &saved_sender,
+                            );
                         }
                     });
                 }
@@ -327,14 +1306,31 @@ impl DesignerApp {
         }
     }
 
-    /// Convert a string to a valid C identifier
-    fn to_c_identifier(name: &str) -> String {
-        name.chars()
-            .map(|c| match c {
-                'a'..='z' | 'A'..='Z' | '0'..='9' => c.to_ascii_uppercase(),
-                _ => '_',
-            })
-            .collect()
+    /// Open a file dialog to save the workspace, with the open project stored in it
+    fn save_workspace(&mut self) {
+        let (Some(workspace), Some(project)) = (&mut self.workspace, &self.project) else {
+            return;
+        };
+        let contents = workspace.store(project).and_then(|_| {
+            workspace
+                .to_bytes()
+                .map_err(|e| format!("Failed to save workspace: {}", e))
+        });
+        match contents {
+            Ok(contents) => {
+                let task = rfd::AsyncFileDialog::new()
+                    .set_file_name(format!("{}.aitw", workspace.name))
+                    .add_filter("AgIsoTerminal Workspace", &["aitw"])
+                    .save_file();
+                execute(async move {
+                    let file = task.await;
+                    if let Some(file) = file {
+                        _ = file.write(&contents).await;
+                    }
+                });
+            }
+            Err(e) => log::error!("{}", e),
+        }
     }
 
     /// Open a file dialog to save a C header file with object IDs
@@ -346,6 +1342,10 @@ impl DesignerApp {
             let mut header = String::from("// Object IDs for the objects in the object pool.\n\n");
             header.push_str("#pragma once\n");
             header.push_str("#define UNDEFINED 65535\n");
+            header.push_str(&format!(
+                "#define POOL_VERSION_LABEL \"{}\"\n",
+                project.pool_version.label.replace(['\\', '"'], "")
+            ));
 
             // Collect all objects with their names and IDs
             let mut objects: Vec<(String, u16)> = pool
@@ -353,7 +1353,7 @@ impl DesignerApp {
                 .iter()
                 .map(|obj| {
                     let name = project.get_object_info(obj).get_name(obj);
-                    let c_name = Self::to_c_identifier(&name);
+                    let c_name = c_identifier(&name);
                     let id = u16::from(obj.id());
                     (c_name, id)
                 })
@@ -382,52 +1382,869 @@ impl DesignerApp {
     }
 }
 
-fn render_selectable_object(ui: &mut egui::Ui, object: &Object, project: &EditorProject) {
-    let this_ui_id = ui.id();
-    let object_info = project.get_object_info(object);
+/// Objects a batch edit is applied to
+#[derive(Clone, Copy, PartialEq)]
+enum BatchScope {
+    /// The selected object and everything it references
+    Selection,
 
-    let renaming_object = project.get_renaming_object();
-    if renaming_object
-        .clone()
-        .is_some_and(|(ui_id, id, _)| id == object.id() && ui_id == this_ui_id)
-    {
-        let mut name = renaming_object.unwrap().2;
-        let response = ui.text_edit_singleline(&mut name);
-        project.set_renaming_object(this_ui_id, object.id(), name); // Update the name in the project
-        let cancelled = ui.input(|i| i.key_pressed(egui::Key::Escape));
-        if response.lost_focus() {
-            project.finish_renaming_object(!cancelled);
-        } else if !response.has_focus() {
-            // We need to focus the text edit when we start renaming
-            response.request_focus();
-        }
-    } else {
-        let is_selected = project.get_selected() == object.id().into();
-        let label_text = format!(
-            "{}: {}",
-            u16::from(object.id()),
-            object_info.get_name(object)
-        );
-        let response = ui.selectable_label(is_selected, label_text);
+    /// The objects the object list shows with its filter
+    ObjectList,
+}
 
-        if response.clicked() {
-            project
-                .get_mut_selected()
-                .replace(NullableObjectId(Some(object.id())));
+impl BatchScope {
+    /// The objects in the scope
+    fn objects(&self, ctx: &egui::Context, project: &EditorProject) -> Vec<ObjectId> {
+        let pool = project.get_pool();
+        match self {
+            BatchScope::Selection => project
+                .get_selected()
+                .0
+                .map(|id| subtree(pool, id))
+                .unwrap_or_default(),
+            BatchScope::ObjectList => pool
+                .objects()
+                .iter()
+                .filter(|object| matches_object_list_filter(ctx, project, object))
+                .map(|object| object.id())
+                .collect(),
         }
-        if response.double_clicked() {
+    }
+}
+
+/// Filter text of the object list
+const OBJECT_LIST_FILTER_ID: &str = "object_list_filter";
+
+/// Whether the object list only shows objects that are not reviewed yet
+const OBJECT_LIST_UNREVIEWED_ID: &str = "object_list_unreviewed_only";
+
+/// Whether the object list shows the object with its current filter
+fn matches_object_list_filter(
+    ctx: &egui::Context,
+    project: &EditorProject,
+    object: &Object,
+) -> bool {
+    let (filter_text, unreviewed_only) = ctx.data(|data| {
+        (
+            data.get_temp::<String>(egui::Id::new(OBJECT_LIST_FILTER_ID))
+                .unwrap_or_default(),
+            data.get_temp::<bool>(egui::Id::new(OBJECT_LIST_UNREVIEWED_ID))
+                .unwrap_or_default(),
+        )
+    });
+    let object_info = project.get_object_info(object);
+    if unreviewed_only && !object_info.review.is_unreviewed() {
+        return false;
+    }
+    filter_text.is_empty()
+        || object_info
+            .get_name(object)
+            .to_lowercase()
+            .contains(&filter_text.to_lowercase())
+}
+
+/// Panel a status bar item opens
+enum StatusBarTarget {
+    Validation,
+    History,
+    TerminalProfiles,
+}
+
+/// Format a number of bytes for humans, e.g. "12.5 kB"
+fn format_bytes(bytes: usize) -> String {
+    if bytes < 1024 {
+        format!("{} B", bytes)
+    } else if bytes < 1024 * 1024 {
+        format!("{:.1} kB", bytes as f64 / 1024.0)
+    } else {
+        format!("{:.1} MB", bytes as f64 / (1024.0 * 1024.0))
+    }
+}
+
+/// Summary of the pool health along the bottom of the window.
+/// Returns the panel to open when an item was clicked.
+fn render_status_bar(
+    ui: &mut egui::Ui,
+    project: &EditorProject,
+    issues: &[ValidationIssue],
+) -> Option<StatusBarTarget> {
+    let mut target = None;
+    ui.horizontal(|ui| {
+        let pool = project.get_pool();
+        if ui
+            .link(tr_args("{} objects", &[&pool.objects().len()]))
+            .on_hover_text(tr("Select the working set"))
+            .clicked()
+        {
+            if let Some(working_set) = pool.objects_by_type(ObjectType::WorkingSet).first() {
+                project
+                    .get_mut_selected()
+                    .replace(NullableObjectId(Some(working_set.id())));
+            }
+        }
+        ui.separator();
+
+        let size = project.pool_size();
+        let budget = project
+            .terminal_profile()
+            .and_then(|profile| profile.pool_size_budget);
+        let (text, colour) = match budget {
+            Some(budget) => {
+                let colour = if size > budget {
+                    Some(egui::Color32::RED)
+                } else if size * 10 > budget * 9 {
+                    Some(egui::Color32::ORANGE)
+                } else {
+                    None
+                };
+                (
+                    format!("{} / {}", format_bytes(size), format_bytes(budget)),
+                    colour,
+                )
+            }
+            None => (format_bytes(size), None),
+        };
+        let mut text = egui::RichText::new(text);
+        if let Some(colour) = colour {
+            text = text.color(colour);
+        }
+        if ui
+            .link(text)
+            .on_hover_text(tr(
+                "Size of the object pool, the budget is set in the terminal profile",
+            ))
+            .clicked()
+        {
+            target = Some(StatusBarTarget::TerminalProfiles);
+        }
+        ui.separator();
+
+        let errors = issues
+            .iter()
+            .filter(|issue| issue.severity == Severity::Error)
+            .count();
+        let warnings = issues.len() - errors;
+        let mut text = egui::RichText::new(format!("\u{2716} {}  \u{26A0} {}", errors, warnings));
+        if errors > 0 {
+            text = text.color(egui::Color32::RED);
+        } else if warnings > 0 {
+            text = text.color(egui::Color32::ORANGE);
+        }
+        if ui
+            .link(text)
+            .on_hover_text(tr_args("{} error(s), {} warning(s)", &[&errors, &warnings]))
+            .clicked()
+        {
+            target = Some(StatusBarTarget::Validation);
+        }
+        ui.separator();
+
+        let text = if project.has_unsaved_changes() {
+            format!("\u{25CF} {}", tr("Unsaved changes"))
+        } else {
+            tr("Saved").to_string()
+        };
+        if ui
+            .link(text)
+            .on_hover_text(tr("Show the change history"))
+            .clicked()
+        {
+            target = Some(StatusBarTarget::History);
+        }
+
+        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+            let profile = project
+                .terminal_profile()
+                .map_or(String::new(), |profile| profile.name.clone());
+            if ui
+                .link(format!("\u{1F5B5} {}", profile))
+                .on_hover_text(tr("Active terminal profile"))
+                .clicked()
+            {
+                target = Some(StatusBarTarget::TerminalProfiles);
+            }
+        });
+    });
+    target
+}
+
+/// The image on the system clipboard
+#[cfg(not(target_arch = "wasm32"))]
+fn clipboard_image() -> Result<image::DynamicImage, String> {
+    let image = arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_image())
+        .map_err(|e| format!("No image on the clipboard: {}", e))?;
+    image::RgbaImage::from_raw(
+        image.width as u32,
+        image.height as u32,
+        image.bytes.into_owned(),
+    )
+    .map(image::DynamicImage::ImageRgba8)
+    .ok_or_else(|| "The clipboard image has an unexpected size".to_string())
+}
+
+/// Write the backups of a saved file and hand its path over, so its backups are listed
+#[cfg(not(target_arch = "wasm32"))]
+fn back_up_saved_file(
+    path: &std::path::Path,
+    files: &[(&str, &[u8])],
+    keep: usize,
+    sender: &Sender<PathBuf>,
+) {
+    if let Err(e) = back_up_save(path, files, keep) {
+        log::error!("Failed to back up {}: {}", path.display(), e);
+    }
+    let _ = sender.send(path.to_path_buf());
+}
+
+/// Open a file dialog to save the PDF design review document
+fn save_review_document(document: Vec<u8>) {
+    let task = rfd::AsyncFileDialog::new()
+        .set_file_name("design_review.pdf")
+        .save_file();
+    execute(async move {
+        if let Some(file) = task.await {
+            _ = file.write(&document).await;
+        }
+    });
+}
+
+/// Open a file dialog to save the printable PDF of a mask
+fn save_mask_print(mask: ObjectId, document: Vec<u8>) {
+    let task = rfd::AsyncFileDialog::new()
+        .set_file_name(format!("mask_{}.pdf", mask.value()))
+        .save_file();
+    execute(async move {
+        if let Some(file) = task.await {
+            _ = file.write(&document).await;
+        }
+    });
+}
+
+/// Write the printable PDF of a mask to a temporary file and open it in the system viewer, which
+/// prints it
+#[cfg(not(target_arch = "wasm32"))]
+fn print_mask_document(mask: ObjectId, document: Vec<u8>) -> Result<(), String> {
+    let path = std::env::temp_dir().join(format!("mask_{}.pdf", mask.value()));
+    std::fs::write(&path, document)
+        .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+    open_in_viewer(&path)
+}
+
+/// The browser has no access to the printer, the document is saved instead
+#[cfg(target_arch = "wasm32")]
+fn print_mask_document(mask: ObjectId, document: Vec<u8>) -> Result<(), String> {
+    save_mask_print(mask, document);
+    Ok(())
+}
+
+/// Whether the user is in the middle of an edit, dragging a slider or handle or typing in a text
+/// field. The changes are shown live and recorded as a single change when the edit ends.
+fn edit_in_progress(ctx: &egui::Context) -> bool {
+    ctx.dragged_id().is_some() || ctx.wants_keyboard_input()
+}
+
+/// Render the active data mask of the working set, or why it can not be shown
+fn render_mask_preview(ui: &mut egui::Ui, pool: &EditorProject) {
+    if pool
+        .get_pool()
+        .objects_by_type(ObjectType::DataMask)
+        .is_empty()
+    {
+        ui.colored_label(
+            egui::Color32::RED,
+            tr("Missing data masks, please load a pool file or add a new mask..."),
+        );
+    } else {
+        match pool.get_pool().working_set_object() {
+            Some(working_set) => {
+                let shown =
+                    previewed_mask(ui.ctx(), pool.get_pool()).unwrap_or(working_set.active_mask);
+                match pool.get_pool().object_by_id(shown) {
+                    Some(obj) => {
+                        let selected_ref = pool.get_mut_selected();
+
+                        let add_mask = |ui: &mut egui::Ui| {
+                            let mask = InteractiveMaskRenderer {
+                                object: obj,
+                                pool: pool.get_pool(),
+                                selected_callback: Box::new(move |object_id| {
+                                    *selected_ref.borrow_mut() = NullableObjectId(Some(object_id));
+                                }),
+                            };
+                            let mask_size = pool.mask_size as f32;
+                            let response = match with_preview_state(ui.ctx(), |state| state.frame) {
+                                TerminalFrame::None => ui.add_sized([mask_size, mask_size], mask),
+                                frame => render_terminal_frame(
+                                    ui,
+                                    pool.get_pool(),
+                                    obj,
+                                    frame,
+                                    frame.key_count(),
+                                    mask_size,
+                                    mask,
+                                ),
+                            };
+                            render_reference_overlay(ui, response.rect);
+                            render_selection_handles(ui, pool, obj, response.rect);
+                            render_focus_order_overlay(ui, pool, obj, response.rect);
+                            response.context_menu(|ui| {
+                                let selected = pool.get_mut_selected().borrow().0;
+                                match selected.and_then(|id| pool.get_pool().object_by_id(id)) {
+                                    Some(object) => {
+                                        render_object_context_menu(ui, object, pool, None)
+                                    }
+                                    None => ui.close(),
+                                }
+                            });
+                            (ui.min_rect(), response.rect)
+                        };
+
+                        if touch_mode(ui.ctx()) {
+                            // Drag to pan and pinch to zoom
+                            let scene_id = ui.id().with("preview_scene");
+                            let mut scene_rect = ui
+                                .data(|data| data.get_temp(scene_id))
+                                .unwrap_or(egui::Rect::ZERO);
+                            let scene = egui::Scene::new().zoom_range(0.25..=8.0).show(
+                                ui,
+                                &mut scene_rect,
+                                add_mask,
+                            );
+                            let (content, mask_rect) = scene.inner;
+                            if let Some(centre) = render_mini_map(
+                                ui,
+                                scene.response.rect,
+                                pool.get_pool(),
+                                obj,
+                                content,
+                                mask_rect,
+                                scene_rect,
+                            ) {
+                                scene_rect =
+                                    egui::Rect::from_center_size(centre, scene_rect.size());
+                            }
+                            ui.data_mut(|data| data.insert_temp(scene_id, scene_rect));
+                        } else {
+                            let mut output = egui::ScrollArea::both().show(ui, add_mask);
+                            // Everything in the coordinates of the scrolled content
+                            let (_, mask_rect) = output.inner;
+                            let content =
+                                egui::Rect::from_min_size(egui::Pos2::ZERO, output.content_size);
+                            let viewport = egui::Rect::from_min_size(
+                                output.state.offset.to_pos2(),
+                                output.inner_rect.size(),
+                            );
+                            let mask_rect = mask_rect
+                                .translate(output.state.offset - output.inner_rect.min.to_vec2());
+                            if let Some(centre) = render_mini_map(
+                                ui,
+                                output.inner_rect,
+                                pool.get_pool(),
+                                obj,
+                                content,
+                                mask_rect,
+                                viewport,
+                            ) {
+                                let max_offset =
+                                    (content.size() - viewport.size()).max(egui::Vec2::ZERO);
+                                output.state.offset = (centre - viewport.size() / 2.0)
+                                    .to_vec2()
+                                    .clamp(egui::Vec2::ZERO, max_offset);
+                                output.state.store(ui.ctx(), output.id);
+                                ui.ctx().request_repaint();
+                            }
+                        }
+                    }
+                    None => {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            tr_args("Missing data mask: {}", &[&format!("{:?}", shown)]),
+                        );
+                    }
+                }
+            }
+            None => {
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr("No working sets, please add a new working set..."),
+                );
+            }
+        }
+    }
+}
+
+/// Overview of the whole preview in the corner of the preview area, with the visible part marked.
+/// Only shown when part of the preview is out of view. Returns the new centre of the visible
+/// part when the mini-map is clicked or dragged.
+fn render_mini_map(
+    ui: &mut egui::Ui,
+    area: egui::Rect,
+    pool: &ObjectPool,
+    mask: &Object,
+    content: egui::Rect,
+    mask_rect: egui::Rect,
+    viewport: egui::Rect,
+) -> Option<egui::Pos2> {
+    if !content.is_positive() || viewport.contains_rect(content.shrink(1.0)) {
+        return None;
+    }
+    let scale = MINI_MAP_SIZE / content.width().max(content.height());
+    let size = content.size() * scale;
+    let to_map = |origin: egui::Pos2, rect: egui::Rect| {
+        egui::Rect::from_min_max(
+            origin + (rect.min - content.min) * scale,
+            origin + (rect.max - content.min) * scale,
+        )
+    };
+
+    let (background, object_refs) = match mask {
+        Object::DataMask(m) => (m.background_colour, m.object_refs.as_slice()),
+        Object::AlarmMask(m) => (m.background_colour, m.object_refs.as_slice()),
+        _ => (0, [].as_slice()),
+    };
+    let background = pool.color_by_index(background);
+
+    egui::Area::new(ui.id().with("mini_map"))
+        .fixed_pos(area.right_bottom() - size - egui::vec2(8.0, 8.0))
+        .show(ui.ctx(), |ui| {
+            let (map, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+            let painter = ui.painter();
+            painter.rect_filled(map.expand(2.0), 2.0, ui.visuals().extreme_bg_color);
+            painter.rect_filled(
+                to_map(map.min, mask_rect),
+                0.0,
+                egui::Color32::from_rgb(background.r, background.g, background.b),
+            );
+            let outline = egui::Stroke::new(1.0, egui::Color32::from_gray(128));
+            for object_ref in object_refs {
+                if let Some(object) = pool.object_by_id(object_ref.id) {
+                    let (width, height) = pool.content_size(object);
+                    let rect = egui::Rect::from_min_size(
+                        mask_rect.min
+                            + egui::vec2(object_ref.offset.x as f32, object_ref.offset.y as f32),
+                        egui::vec2(width as f32, height as f32),
+                    );
+                    painter.rect_stroke(
+                        to_map(map.min, rect),
+                        0.0,
+                        outline,
+                        egui::StrokeKind::Inside,
+                    );
+                }
+            }
+            painter.rect_stroke(
+                to_map(map.min, viewport).intersect(map),
+                0.0,
+                egui::Stroke::new(1.5, ui.visuals().selection.stroke.color),
+                egui::StrokeKind::Inside,
+            );
+
+            response
+                .interact_pointer_pos()
+                .filter(|_| response.clicked() || response.dragged())
+                .map(|pos| content.min + (pos - map.min) / scale)
+        })
+        .inner
+}
+
+fn render_selectable_object(ui: &mut egui::Ui, object: &Object, project: &EditorProject) {
+    let this_ui_id = ui.id();
+    let object_info = project.get_object_info(object);
+
+    let renaming_object = project.get_renaming_object();
+    if renaming_object
+        .clone()
+        .is_some_and(|(ui_id, id, _)| id == object.id() && ui_id == this_ui_id)
+    {
+        let mut name = renaming_object.unwrap().2;
+        let response = ui.text_edit_singleline(&mut name);
+        project.set_renaming_object(this_ui_id, object.id(), name); // Update the name in the project
+        let cancelled = ui.input(|i| i.key_pressed(egui::Key::Escape));
+        if response.lost_focus() {
+            project.finish_renaming_object(!cancelled);
+        } else if !response.has_focus() {
+            // We need to focus the text edit when we start renaming
+            response.request_focus();
+        }
+    } else {
+        let is_selected = project.get_selected() == object.id().into();
+        let mut label_text = format!(
+            "{}: {}",
+            u16::from(object.id()),
+            object_info.get_name(object)
+        );
+        if let Some(icon) = object_info.review.status.icon() {
+            label_text = format!("{} {}", icon, label_text);
+        }
+        if object_info.locked {
+            label_text = format!("\u{1F512} {}", label_text);
+        }
+        let response = ui
+            .horizontal(|ui| {
+                render_visibility_toggle(ui, object.id());
+                render_dirty_marker(ui, project, object.id());
+                render_fidelity_badge(ui, project, object);
+                ui.selectable_label(is_selected, label_text)
+            })
+            .inner;
+
+        if response.clicked() {
+            project
+                .get_mut_selected()
+                .replace(NullableObjectId(Some(object.id())));
+        }
+        if response.double_clicked() && !object_info.locked {
             project.set_renaming_object(this_ui_id, object.id(), object_info.get_name(object));
         }
 
-        response.context_menu(|ui| {
-            if ui.button("Rename").on_hover_text("Rename object").clicked() {
-                project.set_renaming_object(this_ui_id, object.id(), object_info.get_name(object));
-                ui.close();
+        response
+            .context_menu(|ui| render_object_context_menu(ui, object, project, Some(this_ui_id)));
+    }
+}
+
+/// Badge for objects the preview draws differently than a terminal, with the details on hover
+fn render_fidelity_badge(ui: &mut egui::Ui, project: &EditorProject, object: &Object) {
+    let issues = render_fidelity(project.get_pool(), object, project.terminal_profile());
+    let Some(worst) = issues.iter().map(|issue| issue.fidelity).max() else {
+        return;
+    };
+    let (symbol, colour, title) = match worst {
+        Fidelity::Approximate => (
+            "\u{2248}",
+            ui.visuals().weak_text_color(),
+            tr("Approximate rendering"),
+        ),
+        Fidelity::NotRendered => (
+            "\u{26A0}",
+            ui.visuals().warn_fg_color,
+            tr("Not rendered by the preview"),
+        ),
+    };
+    let details: Vec<String> = issues
+        .iter()
+        .map(|issue| format!("\u{2022} {}", issue.message))
+        .collect();
+    ui.label(egui::RichText::new(symbol).small().color(colour))
+        .on_hover_text(format!("{}\n{}", title, details.join("\n")));
+}
+
+/// The differences between the preview and a terminal for the selected object
+fn render_fidelity_details(ui: &mut egui::Ui, project: &EditorProject, object: &Object) {
+    let issues = render_fidelity(project.get_pool(), object, project.terminal_profile());
+    if issues.is_empty() {
+        return;
+    }
+    egui::CollapsingHeader::new(tr("Approximate rendering"))
+        .id_salt("render_fidelity_details")
+        .show(ui, |ui| {
+            ui.label(tr(
+                "The preview of this object differs from how a terminal draws it:",
+            ));
+            for issue in &issues {
+                ui.label(format!("\u{2022} {}", issue.message));
             }
-            if ui.button("Delete").on_hover_text("Delete object").clicked() {
-                project.get_mut_pool().borrow_mut().remove(object.id());
+        });
+    ui.separator();
+}
+
+/// Subtle dot for objects that were modified since the last save or checkpoint
+fn render_dirty_marker(ui: &mut egui::Ui, project: &EditorProject, id: ObjectId) {
+    let (colour, hint) = match project.dirty_marker(id) {
+        Some(DirtyMarker::SinceCheckpoint) => (
+            ui.visuals().warn_fg_color,
+            tr("Modified since the last checkpoint"),
+        ),
+        Some(DirtyMarker::SinceSave) => (
+            ui.visuals().weak_text_color(),
+            tr("Modified since the last save"),
+        ),
+        None => return,
+    };
+    ui.label(egui::RichText::new("\u{25CF}").small().color(colour))
+        .on_hover_text(hint);
+}
+
+/// Actions on an object, shown on right click or long press.
+/// Renaming is only offered where the name can be edited in place, in the `rename_ui_id` ui.
+fn render_object_context_menu(
+    ui: &mut egui::Ui,
+    object: &Object,
+    project: &EditorProject,
+    rename_ui_id: Option<egui::Id>,
+) {
+    let object_info = project.get_object_info(object);
+    ui.add_enabled_ui(!object_info.locked, |ui| {
+        if let Some(rename_ui_id) = rename_ui_id {
+            if ui
+                .button(tr("Rename"))
+                .on_hover_text(tr("Rename object"))
+                .clicked()
+            {
+                project.set_renaming_object(
+                    rename_ui_id,
+                    object.id(),
+                    object_info.get_name(object),
+                );
                 ui.close();
             }
+        }
+        if ui
+            .button(tr("Delete"))
+            .on_hover_text(tr("Delete object"))
+            .clicked()
+        {
+            project.get_mut_pool().borrow_mut().remove(object.id());
+            ui.close();
+        }
+    });
+    let bookmarked = project.is_bookmarked(object.id());
+    if ui
+        .button(if bookmarked {
+            tr("Remove bookmark")
+        } else {
+            tr("Bookmark")
+        })
+        .clicked()
+    {
+        project.toggle_bookmark(object.id());
+        ui.close();
+    }
+    if ui
+        .button(tr("Copy"))
+        .on_hover_text(tr(
+            "Copy the object and everything it references, to paste it into another pool",
+        ))
+        .clicked()
+    {
+        match copy_objects(project, object.id()) {
+            Ok(text) => ui.ctx().copy_text(text),
+            Err(e) => log::error!("{}", e),
+        }
+        ui.close();
+    }
+    ui.add_enabled_ui(!object_info.locked, |ui| {
+        ui.menu_button(tr("Mirror"), |ui| render_mirror_menu(ui, object, project));
+    });
+    if matches!(object, Object::DataMask(_) | Object::AlarmMask(_))
+        && ui
+            .button(tr("Duplicate mask..."))
+            .on_hover_text(tr("Copy this mask, choosing which children are shared"))
+            .clicked()
+    {
+        project.request_mask_duplication(object.id());
+        ui.close();
+    }
+    ui.separator();
+    if object_info.locked {
+        if ui.button(tr("Unlock")).clicked() {
+            project.set_locked(object, false);
+            ui.close();
+        }
+    } else if ui
+        .button(tr("Lock"))
+        .on_hover_text(tr("Protect the object against modification"))
+        .clicked()
+    {
+        project.set_locked(object, true);
+        ui.close();
+    }
+}
+
+/// Clickable reference path from the root down to the selected object.
+/// If the object is referenced from multiple places a dropdown selects the path that is shown.
+fn render_breadcrumbs(ui: &mut egui::Ui, project: &EditorProject, id: ObjectId) {
+    let paths = reference_paths(project.get_pool(), id);
+    if paths.is_empty() {
+        return;
+    }
+
+    // Prefer the path that was shown last, so navigating up or down keeps the same path
+    let path_id = egui::Id::new("breadcrumb_path");
+    let remembered = ui
+        .data(|data| data.get_temp::<Vec<ObjectId>>(path_id))
+        .unwrap_or_default();
+    let mut index = paths
+        .iter()
+        .position(|p| *p == remembered)
+        .or_else(|| {
+            paths
+                .iter()
+                .position(|p| remembered.starts_with(p) || p.starts_with(&remembered))
+        })
+        .unwrap_or(0);
+
+    let crumb_text = |id: ObjectId| match project.get_pool().object_by_id(id) {
+        Some(object) => format!("{:?} 0x{:04X}", object.object_type(), id.value()),
+        None => format!("0x{:04X}", id.value()),
+    };
+
+    ui.horizontal_wrapped(|ui| {
+        if paths.len() > 1 {
+            egui::ComboBox::from_id_salt("breadcrumb_path_select")
+                .selected_text(tr_args("Path {}/{}", &[&(index + 1), &paths.len()]))
+                .show_ui(ui, |ui| {
+                    for (i, path) in paths.iter().enumerate() {
+                        let text: Vec<String> = path.iter().map(|id| crumb_text(*id)).collect();
+                        ui.selectable_value(&mut index, i, text.join(" \u{25B8} "));
+                    }
+                });
+        }
+        for (i, crumb) in paths[index].iter().enumerate() {
+            if i > 0 {
+                ui.label("\u{25B8}");
+            }
+            let mut response = ui.selectable_label(*crumb == id, crumb_text(*crumb));
+            if let Some(object) = project.get_pool().object_by_id(*crumb) {
+                response = response.on_hover_text(project.get_object_info(object).get_name(object));
+            }
+            if response.clicked() && *crumb != id {
+                project
+                    .get_mut_selected()
+                    .replace(NullableObjectId(Some(*crumb)));
+            }
+        }
+    });
+    ui.data_mut(|data| data.insert_temp(path_id, paths[index].clone()));
+}
+
+/// Notice for objects with several parents, as edits show up in all of them. Lists the other
+/// parents and offers a copy for the parent of the shown breadcrumb path.
+fn render_shared_object_notice(ui: &mut egui::Ui, project: &EditorProject, id: ObjectId) {
+    let parents = object_parents(project.get_pool(), id);
+    if parents.len() < 2 {
+        return;
+    }
+    let path = ui
+        .data(|data| data.get_temp::<Vec<ObjectId>>(egui::Id::new("breadcrumb_path")))
+        .unwrap_or_default();
+    let parent = match path.as_slice() {
+        [.., parent, last] if *last == id && parents.contains(parent) => *parent,
+        _ => parents[0],
+    };
+    let label = |id: ObjectId| match project.get_pool().object_by_id(id) {
+        Some(object) => project.get_object_info(object).get_name(object),
+        None => id.value().to_string(),
+    };
+
+    ui.colored_label(
+        egui::Color32::ORANGE,
+        tr_args(
+            "\u{26A0} Used by {} objects, changes show up in all of them",
+            &[&parents.len()],
+        ),
+    );
+    ui.horizontal_wrapped(|ui| {
+        ui.label(tr("Also used by:"));
+        for other in parents.iter().filter(|other| **other != parent) {
+            if ui.link(label(*other)).clicked() {
+                project
+                    .get_mut_selected()
+                    .replace(NullableObjectId(Some(*other)));
+            }
+        }
+    });
+    if ui
+        .button(tr_args("Make a unique copy for {}", &[&label(parent)]))
+        .on_hover_text(tr(
+            "Only this parent uses the copy, the other parents keep the original",
+        ))
+        .clicked()
+    {
+        match make_unique_copy(project, id, parent) {
+            Ok(copy) => {
+                project
+                    .get_mut_selected()
+                    .replace(NullableObjectId(Some(copy)));
+            }
+            Err(error) => log::error!("{}", error),
+        }
+    }
+    ui.separator();
+}
+
+/// Mirror the children of an object, or the object inside its parents
+fn render_mirror_menu(ui: &mut egui::Ui, object: &Object, project: &EditorProject) {
+    let has_children = !object.referenced_objects().is_empty();
+    for (axis, label, buttons) in [
+        (
+            MirrorAxis::Horizontal,
+            "horizontally",
+            [
+                "Layout horizontally",
+                "Layout horizontally (all levels)",
+                "Position in parent horizontally",
+            ],
+        ),
+        (
+            MirrorAxis::Vertical,
+            "vertically",
+            [
+                "Layout vertically",
+                "Layout vertically (all levels)",
+                "Position in parent vertically",
+            ],
+        ),
+    ] {
+        let mut moved = 0;
+        if has_children {
+            if ui
+                .button(tr(buttons[0]))
+                .on_hover_text(tr("Mirror the positions of all direct children"))
+                .clicked()
+            {
+                moved = mirror_children(project, object.id(), axis, false);
+            }
+            if ui
+                .button(tr(buttons[1]))
+                .on_hover_text(
+                    tr("Mirror the positions inside all child objects too, shared children change everywhere they are used"),
+                )
+                .clicked()
+            {
+                moved = mirror_children(project, object.id(), axis, true);
+            }
+        }
+        if ui
+            .button(tr(buttons[2]))
+            .on_hover_text(tr(
+                "Mirror the position of this object inside every parent that uses it",
+            ))
+            .clicked()
+        {
+            moved = mirror_in_parents(project, object.id(), axis);
+        }
+        if moved > 0 {
+            project.describe_change(
+                format!("Mirror {} positions {}", moved, label),
+                ChangeCategory::Edit,
+            );
+            ui.close();
+        }
+        if axis == MirrorAxis::Horizontal {
+            ui.separator();
+        }
+    }
+}
+
+/// Eye toggle to hide an object from the preview in edit mode
+fn render_visibility_toggle(ui: &mut egui::Ui, id: ObjectId) {
+    let hidden = with_preview_state(ui.ctx(), |state| state.hidden_objects.contains(&id));
+    let icon = if hidden { "\u{25CC}" } else { "\u{1F441}" };
+    let response = ui
+        .add(egui::Button::new(icon).frame(false).small())
+        .on_hover_text(if hidden {
+            tr("Show in preview")
+        } else {
+            tr("Hide in preview (edit mode only)")
+        });
+    if response.clicked() {
+        with_preview_state(ui.ctx(), |state| {
+            if hidden {
+                state.hidden_objects.remove(&id);
+            } else {
+                state.hidden_objects.insert(id);
+            }
         });
     }
 }
@@ -459,7 +2276,7 @@ fn render_object_hierarchy(
                         None => {
                             ui.colored_label(
                                 egui::Color32::RED,
-                                format!("Missing object: {:?}", id),
+                                tr_args("Missing object: {}", &[&id.value()]),
                             );
                         }
                     }
@@ -507,9 +2324,47 @@ impl eframe::App for DesignerApp {
         ctx.style_mut(|style| {
             style.interaction.selectable_labels = false;
         });
+        self.collect_settings(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.save_changed_settings(ctx);
 
         // Handle file dialog
-        self.handle_file_loaded();
+        self.handle_file_loaded(ctx);
+        self.handle_image_import();
+        if let Ok(verification) = self.export_verification_channel.1.try_recv() {
+            self.export_verification = Some(verification);
+        }
+        self.handle_object_clipboard(ctx);
+        self.handle_plugin_run(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle_open_requests(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.check_watched_file(ctx);
+        if let Some(pool) = self.saved_project_channel.1.try_iter().last() {
+            if let Some(project) = &mut self.project {
+                project.mark_saved_as(pool);
+            }
+        }
+        while let Ok(contents) = self.exported_pool_channel.1.try_recv() {
+            if let Some(project) = &mut self.project {
+                if project.pool_version.register_export(&contents) {
+                    log::info!("Bumped the pool version to {}", project.pool_version.label);
+                }
+            }
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if let Some(path) = self.saved_file_channel.1.try_iter().last() {
+            self.backup_source = Some(path);
+            self.refresh_backups();
+        }
+        update_reference_comparison(ctx);
+        #[cfg(not(target_arch = "wasm32"))]
+        self.handle_image_paste(ctx);
+        #[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+        self.handle_remote_requests(ctx);
+        #[cfg(all(feature = "can-capture", target_os = "linux"))]
+        self.handle_can_capture();
+        self.update_terminal_fonts(ctx);
 
         // Check for image load requests
         if let Some(pool) = &self.project {
@@ -518,25 +2373,46 @@ impl eframe::App for DesignerApp {
             }
         }
 
+        // Check for mask duplication requests
+        if let Some(pool) = &self.project {
+            if let Some(mask) = pool.take_mask_duplication_request() {
+                self.mask_duplication = Some(MaskDuplication::new(pool, mask));
+            }
+        }
+
+        // Check for macro creation requests of the event hook editor
+        if let Some(pool) = &self.project {
+            if let Some((object, event)) = pool.take_macro_creation_request() {
+                if pool.create_macro_for_event(object, event).is_none() {
+                    log::error!("Failed to create a macro, all 256 macro IDs are in use");
+                }
+            }
+        }
+
+        // Apply changes an editor made to another object, e.g. the fill pattern of fill attributes
+        if let Some(pool) = &mut self.project {
+            pool.apply_object_update_request(edit_in_progress(ctx));
+        }
+
         if self.show_development_popup {
-            egui::Window::new("🚧 Under Active Development")
+            egui::Window::new(format!("🚧 {}", tr("Under Active Development")))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
                     ui.add_space(10.0);
-                    ui.label("This application is still under active development. Some features may be missing or broken. We appreciate your patience and feedback!");
+                    ui.label(tr("This application is still under active development. Some features may be missing or broken. We appreciate your patience and feedback!"));
 
                     ui.add_space(10.0);
                     ui.horizontal_wrapped(|ui| {
-                        ui.label("If you encounter issues, please report them at:");
+                        ui.label(tr("If you encounter issues, please report them at:"));
                         ui.hyperlink("https://github.com/Open-Agriculture/AgIsoTerminalDesigner/issues");
                     });
 
                     ui.add_space(20.0);
                     ui.horizontal(|ui| {
                         ui.add_space(ui.available_width() - 60.0);
-                        if ui.button("OK").clicked() {
+                        if ui.button(tr("OK")).clicked() {
                             self.show_development_popup = false;
                         }
                     });
@@ -549,12 +2425,12 @@ impl eframe::App for DesignerApp {
             let mut should_create = false;
             let mut should_cancel = false;
 
-            egui::Window::new(format!("New {:?}", object_type))
+            egui::Window::new(tr_args("New {}", &[&format!("{:?}", object_type)]))
                 .collapsible(false)
                 .resizable(false)
                 .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
                 .show(ctx, |ui| {
-                    ui.label("Enter a name for the new object:");
+                    ui.label(tr("Enter a name for the new object:"));
                     ui.add_space(10.0);
 
                     let response = ui.text_edit_singleline(&mut name);
@@ -576,10 +2452,10 @@ impl eframe::App for DesignerApp {
 
                     ui.add_space(20.0);
                     ui.horizontal(|ui| {
-                        if ui.button("Create").clicked() || should_create {
+                        if ui.button(tr("Create")).clicked() || should_create {
                             should_create = true;
                         }
-                        if ui.button("Cancel").clicked() || should_cancel {
+                        if ui.button(tr("Cancel")).clicked() || should_cancel {
                             should_cancel = true;
                         }
                     });
@@ -618,25 +2494,262 @@ impl eframe::App for DesignerApp {
             }
         }
 
-        egui::TopBottomPanel::top("topbar").show(ctx, |ui| {
-            ui.horizontal_wrapped(|ui| {
-                egui::widgets::global_theme_preference_buttons(ui);
-                ui.separator();
+        // Show report of a pool that was recovered from damaged data
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.external_change.is_some() {
+            self.render_external_change_window(ctx);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if !self.pending_open_requests.is_empty() {
+            self.render_open_request_window(ctx);
+        }
+        if self.show_recovery_report {
+            if let Some(report) = &self.recovery_report {
+                egui::Window::new(tr("Damaged object pool"))
+                    .collapsible(false)
+                    .resizable(true)
+                    .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+                    .show(ctx, |ui| {
+                        ui.label(tr_args(
+                            "Recovered {} objects, skipped {} of {} bytes.",
+                            &[
+                                &report.recovered_objects,
+                                &report.skipped_bytes(),
+                                &report.total_bytes,
+                            ],
+                        ));
+                        ui.label(tr(
+                            "The salvaged objects are opened read-only for inspection.",
+                        ));
+                        ui.add_space(10.0);
 
-                // Undo/redo buttons
-                if let Some(pool) = &mut self.project {
-                    let undo_shortcut =
-                        egui::KeyboardShortcut::new(egui::Modifiers::CTRL, egui::Key::Z);
-                    let redo_shortcut =
-                        egui::KeyboardShortcut::new(egui::Modifiers::CTRL, egui::Key::Y);
+                        egui::ScrollArea::vertical()
+                            .max_height(300.0)
+                            .show(ui, |ui| {
+                                egui::Grid::new("recovery_report_grid").striped(true).show(
+                                    ui,
+                                    |ui| {
+                                        ui.strong(tr("Offset"));
+                                        ui.strong(tr("Size"));
+                                        ui.strong(tr("Reason"));
+                                        ui.end_row();
+                                        for region in &report.skipped_regions {
+                                            ui.monospace(format!(
+                                                "0x{:06X}..0x{:06X}",
+                                                region.start, region.end
+                                            ));
+                                            ui.label(tr_args("{} bytes", &[&region.size()]));
+                                            ui.label(&region.reason);
+                                            ui.end_row();
+                                        }
+                                    },
+                                );
+                            });
 
-                    if ui
-                        .add_enabled(
-                            pool.undo_available(),
-                            egui::widgets::Button::new("\u{2BAA}"),
-                        )
-                        .on_hover_text(format!("Undo ({})", ctx.format_shortcut(&undo_shortcut)))
-                        .clicked()
+                        ui.add_space(20.0);
+                        ui.horizontal(|ui| {
+                            ui.add_space(ui.available_width() - 60.0);
+                            if ui.button(tr("OK")).clicked() {
+                                self.show_recovery_report = false;
+                            }
+                        });
+                    });
+            }
+        }
+
+        if self.export_verification.is_some() {
+            self.render_export_verification_window(ctx);
+        }
+
+        // Show the result of a batch image import
+        if let Some(log) = &self.image_import_log {
+            let mut open = true;
+            egui::Window::new(tr("Image import"))
+                .open(&mut open)
+                .resizable(true)
+                .show(ctx, |ui| {
+                    egui::ScrollArea::vertical()
+                        .max_height(300.0)
+                        .show(ui, |ui| {
+                            for line in log {
+                                ui.label(line);
+                            }
+                        });
+                });
+            if !open {
+                self.image_import_log = None;
+            }
+        }
+
+        if self.show_colour_roles {
+            self.render_colour_roles_window(ctx);
+        }
+        if self.show_colour_usage {
+            self.render_colour_usage_window(ctx);
+        }
+        if self.show_style_presets {
+            self.render_style_presets_window(ctx);
+        }
+        if self.show_history {
+            self.render_history_window(ctx);
+        }
+        if self.show_regression {
+            self.render_regression_window(ctx);
+        }
+        if self.show_validation {
+            self.render_validation_window(ctx);
+        }
+        if self.mask_duplication.is_some() {
+            self.render_mask_duplication_window(ctx);
+        }
+        if self.show_terminal_profiles {
+            self.render_terminal_profiles_window(ctx);
+        }
+        if self.show_font_metrics {
+            self.render_font_metrics_window(ctx);
+        }
+        if self.show_spell_check {
+            self.render_spell_check_window(ctx);
+        }
+        if self.show_strings {
+            self.render_strings_window(ctx);
+        }
+        if self.show_macro_references {
+            self.render_macro_references_window(ctx);
+        }
+        if self.show_pointing_events {
+            self.render_pointing_events_window(ctx);
+        }
+        if self.show_watch_expressions {
+            self.render_watch_expressions_window(ctx);
+        }
+        if self.show_input_messages {
+            self.render_input_messages_window(ctx);
+        }
+        if self.show_navigation_map {
+            self.render_navigation_map_window(ctx);
+        }
+        if self.show_similar_objects {
+            self.render_similar_objects_window(ctx);
+        }
+        if self.show_tasks {
+            self.render_tasks_window(ctx);
+        }
+        if self.show_transform {
+            self.render_transform_window(ctx);
+        }
+        if self.show_font_batch {
+            self.render_font_batch_window(ctx);
+        }
+        if self.show_bulk_rename {
+            self.render_bulk_rename_window(ctx);
+        }
+        if self.show_orphan_cleanup {
+            self.render_orphan_cleanup_window(ctx);
+        }
+        if self.show_hex_inspector {
+            self.render_hex_inspector_window(ctx);
+        }
+        if self.show_focus_order {
+            self.render_focus_order_window(ctx);
+        }
+        if self.show_state_sandbox {
+            self.render_state_sandbox_window(ctx);
+        }
+        if self.show_mask_print {
+            self.render_mask_print_window(ctx);
+        }
+        if self.show_upload_simulation {
+            self.render_upload_simulation_window(ctx);
+        }
+        if self.id_conflicts.is_some() {
+            self.render_id_conflicts_window(ctx);
+        }
+        if self.show_vt_simulator {
+            self.render_vt_simulator_window(ctx);
+        }
+        if self.show_reference_overlay {
+            self.render_reference_overlay_window(ctx);
+        }
+        if self.show_workspace {
+            self.render_workspace_window(ctx);
+        }
+        if self.show_minify {
+            self.render_minify_window(ctx);
+        }
+        if self.show_pool_version {
+            self.render_pool_version_window(ctx);
+        }
+        if self.show_vt_storage {
+            self.render_vt_storage_window(ctx);
+        }
+        #[cfg(not(target_arch = "wasm32"))]
+        if self.show_backups {
+            self.render_backups_window(ctx);
+        }
+        if self.show_file_format {
+            self.render_file_format_window(ctx);
+        }
+        if self.show_settings {
+            self.render_settings_window(ctx);
+        }
+        if self.show_preview_matrix {
+            self.render_preview_matrix_window(ctx);
+        }
+        if self.show_plugins {
+            self.render_plugins_window(ctx);
+        }
+        if self.preview_detached {
+            self.render_detached_preview(ctx);
+        }
+        #[cfg(feature = "scripting")]
+        if self.show_script_console {
+            self.render_script_console(ctx);
+        }
+        #[cfg(all(feature = "can-capture", target_os = "linux"))]
+        if self.can_capture.show_window {
+            self.render_can_capture_window(ctx);
+        }
+        #[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+        if self.remote.show_window {
+            self.render_remote_control_window(ctx);
+        }
+
+        let key_bindings = self.settings.key_bindings.clone();
+        egui::TopBottomPanel::top("topbar").show(ctx, |ui| {
+            ui.horizontal_wrapped(|ui| {
+                // Quick light/dark switch, the other themes are in the settings
+                let (icon, theme) = match ctx.theme() {
+                    egui::Theme::Dark => ("\u{2600}", UiTheme::Light),
+                    egui::Theme::Light => ("\u{1F319}", UiTheme::Dark),
+                };
+                if ui
+                    .button(icon)
+                    .on_hover_text(tr(theme.label()))
+                    .clicked()
+                {
+                    self.settings.theme = theme;
+                    self.settings.theme.apply(ctx);
+                }
+                ui.separator();
+
+                // Undo/redo buttons
+                if let Some(pool) = &mut self.project {
+                    let undo_shortcut = key_bindings.undo;
+                    let redo_shortcut = key_bindings.redo;
+                    let scoped_undo_shortcut = key_bindings.scoped_undo;
+                    // Consumed first, as the undo shortcut would match it as well
+                    let scoped_undo_pressed =
+                        ctx.input_mut(|i| i.consume_shortcut(&scoped_undo_shortcut));
+
+                    if ui
+                        .add_enabled(
+                            pool.undo_available(),
+                            egui::widgets::Button::new("\u{2BAA}"),
+                        )
+                        .on_hover_text(tr_args("Undo {}({})", &[&(pool.next_undo()
+                                .map_or(String::new(), |c| format!("{} ", c.description))), &ctx.format_shortcut(&undo_shortcut)]))
+                        .clicked()
                         || ctx.input_mut(|i| i.consume_shortcut(&undo_shortcut))
                     {
                         pool.undo();
@@ -646,54 +2759,210 @@ impl eframe::App for DesignerApp {
                             pool.redo_available(),
                             egui::widgets::Button::new("\u{2BAB}"),
                         )
-                        .on_hover_text(format!("Redo ({})", ctx.format_shortcut(&redo_shortcut)))
+                        .on_hover_text(tr_args("Redo {}({})", &[&(pool.next_redo()
+                                .map_or(String::new(), |c| format!("{} ", c.description))), &ctx.format_shortcut(&redo_shortcut)]))
                         .clicked()
                         || ctx.input_mut(|i| i.consume_shortcut(&redo_shortcut))
                     {
                         pool.redo();
                     }
+                    if ui
+                        .add_enabled(
+                            pool.undo_available(),
+                            egui::widgets::Button::new("\u{2BAA}\u{25A3}"),
+                        )
+                        .on_hover_ui(|ui| {
+                            let next = active_mask(pool.get_pool())
+                                .and_then(|mask| pool.next_undo_within(mask));
+                            ui.label(tr_args("Undo the last change within the active mask {}({})", &[&(next.map_or(String::new(), |c| format!("'{}' ", c.description))), &ctx.format_shortcut(&scoped_undo_shortcut)]));
+                        })
+                        .clicked()
+                        || scoped_undo_pressed
+                    {
+                        let result = match active_mask(pool.get_pool()) {
+                            Some(mask) => pool.undo_within(mask),
+                            None => Err("The working set has no active mask".to_string()),
+                        };
+                        self.scoped_undo_error = result.err();
+                        if self.scoped_undo_error.is_some() {
+                            self.show_history = true;
+                        }
+                    }
+                    ui.separator();
+
+                    // Selection history buttons
+                    if ui
+                        .add_enabled(
+                            pool.previous_selected_available(),
+                            egui::widgets::Button::new("\u{23F4}"),
+                        )
+                        .on_hover_text(tr_args(
+                            "Previous selection ({})",
+                            &[&ctx.format_shortcut(&key_bindings.select_previous)]
+                        ))
+                        .clicked()
+                    {
+                        pool.set_previous_selected();
+                    }
+                    if ui
+                        .add_enabled(
+                            pool.next_selected_available(),
+                            egui::widgets::Button::new("\u{23F5}"),
+                        )
+                        .on_hover_text(tr_args(
+                            "Next selection ({})",
+                            &[&ctx.format_shortcut(&key_bindings.select_next)]
+                        ))
+                        .clicked()
+                    {
+                        pool.set_next_selected();
+                    }
                     ui.separator();
                 }
 
-                ui.menu_button("File", |ui| {
-                    ui.label("Project Files");
-                    if ui.button("Open Project (.aitp)").clicked() {
+                // Switch between editing and simulating the pool
+                if self.project.is_some() {
+                    let mut mode = preview_mode(ctx);
+                    ui.selectable_value(&mut mode, PreviewMode::Edit, format!("\u{270F} {}", tr("Edit")))
+                        .on_hover_text(tr("Click objects in the preview to select them"));
+                    ui.selectable_value(&mut mode, PreviewMode::Simulate, format!("\u{25B6} {}", tr("Simulate")))
+                        .on_hover_text(tr("Interact with the preview like on a terminal"));
+                    with_preview_state(ctx, |state| state.set_mode(mode));
+
+                    let hidden_count = with_preview_state(ctx, |state| state.hidden_objects.len());
+                    if hidden_count > 0
+                        && ui
+                            .button(format!("\u{1F441} {}", tr_args("Show {} hidden", &[&hidden_count])))
+                            .on_hover_text(tr("Show all objects that are hidden in the preview"))
+                            .clicked()
+                    {
+                        with_preview_state(ctx, |state| state.hidden_objects.clear());
+                    }
+
+                    let mut frozen = with_preview_state(ctx, |state| state.flashing_frozen);
+                    if ui
+                        .toggle_value(&mut frozen, format!("\u{2744} {}", tr("Freeze flashing")))
+                        .on_hover_text(tr("Show flashing objects in their normal state"))
+                        .changed()
+                    {
+                        with_preview_state(ctx, |state| state.flashing_frozen = frozen);
+                    }
+
+                    let mut frame = with_preview_state(ctx, |state| state.frame);
+                    egui::ComboBox::from_id_salt("terminal_frame")
+                        .selected_text(tr(frame.label()))
+                        .show_ui(ui, |ui| {
+                            for option in TerminalFrame::ALL {
+                                ui.selectable_value(&mut frame, option, tr(option.label()));
+                            }
+                        })
+                        .response
+                        .on_hover_text(tr("Show the preview inside a terminal bezel with soft keys"));
+                    with_preview_state(ctx, |state| state.frame = frame);
+                    ui.separator();
+                }
+
+                if self.project.is_some() && ctx.input_mut(|i| i.consume_shortcut(&key_bindings.save))
+                {
+                    if self.workspace.is_some() {
+                        self.save_workspace();
+                    } else {
+                        self.save_project();
+                    }
+                }
+                ui.menu_button(tr("File"), |ui| {
+                    ui.label(tr("Project Files"));
+                    if ui.button(tr("Open Project (.aitp)")).clicked() {
                         self.open_file_dialog(FileDialogReason::LoadProject, ctx);
                         ui.close();
                     }
-                    if self.project.is_some() && ui.button("Save Project (.aitp)").clicked() {
+                    if self.project.is_some() && ui.button(tr("Save Project (.aitp)")).clicked() {
                         self.save_project();
                         ui.close();
                     }
+                    if ui.button(tr("Open Workspace (.aitw)")).clicked() {
+                        self.open_file_dialog(FileDialogReason::LoadWorkspace, ctx);
+                        ui.close();
+                    }
+                    if self.workspace.is_some()
+                        && ui.button(tr("Save Workspace (.aitw)")).clicked()
+                    {
+                        self.save_workspace();
+                        ui.close();
+                    }
+                    if self.project.is_some() && ui.button(tr("Workspace...")).clicked() {
+                        self.show_workspace = true;
+                        ui.close();
+                    }
+                    if self.project.is_some() && ui.button(tr("Project file format...")).clicked()
+                    {
+                        self.show_file_format = true;
+                        ui.close();
+                    }
+                    #[cfg(not(target_arch = "wasm32"))]
+                    if ui.button(tr("Backups...")).clicked() {
+                        self.show_backups = true;
+                        self.backup_status = None;
+                        self.refresh_backups();
+                        ui.close();
+                    }
 
                     ui.separator();
-                    ui.label("ISOBUS Files");
+                    ui.label(tr("ISOBUS Files"));
 
-                    if ui.button("Import IOP (.iop)").clicked() {
+                    if ui.button(tr("Import IOP (.iop)")).clicked() {
                         self.open_file_dialog(FileDialogReason::LoadPool, ctx);
                         ui.close();
                     }
 
                     ui.checkbox(
-                        &mut self.apply_smart_naming_on_import,
-                        "Apply smart naming on import",
+                        &mut self.settings.apply_smart_naming_on_import,
+                        tr("Apply smart naming on import"),
                     )
                     .on_hover_text(
-                        "Automatically apply smart naming to objects when importing IOP files",
+                        tr("Automatically apply smart naming to objects when importing IOP files"),
                     );
-                    if self.project.is_some() && ui.button("Export IOP (.iop)").clicked() {
-                        self.save_pool();
+                    if self.project.is_some() && ui.button(tr("Import images...")).clicked() {
+                        self.open_image_import_dialog(ctx);
+                        ui.close();
+                    }
+                    if self.project.is_some() && ui.button(tr("Export IOP (.iop)")).clicked() {
+                        self.save_pool(ctx);
                         ui.close();
                     }
-                    if self.project.is_some() && ui.button("Export Header (.h)").clicked() {
+                    if self.project.is_some() && ui.button(tr("Export minified IOP...")).clicked() {
+                        self.show_minify = true;
+                        self.minify_report = None;
+                        ui.close();
+                    }
+                    if self.project.is_some() && ui.button(tr("Export Header (.h)")).clicked() {
                         self.save_header();
                         ui.close();
                     }
+                    if let Some(project) = &self.project {
+                        if ui.button(tr("Export design review (.pdf)")).clicked() {
+                            self.review_document_run = Some(ReviewDocumentRun::new(project));
+                            ui.close();
+                        }
+                    }
+                    if self.project.is_some()
+                        && ui
+                            .button(tr("Export for AgIsoVirtualTerminal..."))
+                            .clicked()
+                    {
+                        self.show_vt_simulator = true;
+                        ui.close();
+                    }
                 });
 
                 if self.project.is_some() {
                     // Add a new object
-                    ui.menu_button("Add object", |ui| {
+                    ui.menu_button(tr("Add object"), |ui| {
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button(tr("Picture graphic from clipboard")).clicked() {
+                            self.paste_clipboard_image(true);
+                            ui.close();
+                        }
                         egui::ScrollArea::vertical().show(ui, |ui| {
                             for object_type in ObjectType::values() {
                                 if ui.button(format!("{:?}", object_type)).clicked() {
@@ -707,31 +2976,379 @@ impl eframe::App for DesignerApp {
                             }
                         });
                     });
+
+                    ui.menu_button(tr("Tools"), |ui| {
+                        if ui.button(tr("Colour roles...")).clicked() {
+                            self.show_colour_roles = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Colour usage...")).clicked() {
+                            self.show_colour_usage = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Style presets...")).clicked() {
+                            self.show_style_presets = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("History...")).clicked() {
+                            self.show_history = true;
+                            ui.close();
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button(tr("Screenshot regression...")).clicked() {
+                            self.show_regression = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Validation...")).clicked() {
+                            self.show_validation = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Terminal profiles...")).clicked() {
+                            self.show_terminal_profiles = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Font metrics...")).clicked() {
+                            self.show_font_metrics = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Spell check...")).clicked() {
+                            self.show_spell_check = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Strings...")).clicked() {
+                            self.show_strings = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Macro references...")).clicked() {
+                            self.show_macro_references = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Navigation map...")).clicked() {
+                            self.show_navigation_map = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Similar objects...")).clicked() {
+                            self.show_similar_objects = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Tasks...")).clicked() {
+                            self.show_tasks = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Reference overlay...")).clicked() {
+                            self.show_reference_overlay = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Clean up orphan attributes...")).clicked() {
+                            self.show_orphan_cleanup = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Hex inspector...")).clicked() {
+                            self.show_hex_inspector = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Focus order...")).clicked() {
+                            self.show_focus_order = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("State sandbox...")).clicked() {
+                            self.show_state_sandbox = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Print mask...")).clicked() {
+                            self.show_mask_print = true;
+                            self.mask_print_error = None;
+                            ui.close();
+                        }
+                        if ui.button(tr("Simulate upload...")).clicked() {
+                            self.show_upload_simulation = true;
+                            self.upload_simulation =
+                                self.project.as_ref().and_then(simulate_upload);
+                            ui.close();
+                        }
+                        if ui.button(tr("Set font attributes...")).clicked() {
+                            self.show_font_batch = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Bulk rename...")).clicked() {
+                            self.show_bulk_rename = true;
+                            self.rename_status = None;
+                            ui.close();
+                        }
+                        if ui.button(tr("Transform selection...")).clicked() {
+                            self.show_transform = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Pool version...")).clicked() {
+                            self.show_pool_version = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Terminal storage...")).clicked() {
+                            self.show_vt_storage = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Pointing events...")).clicked() {
+                            self.show_pointing_events = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Input messages...")).clicked() {
+                            self.show_input_messages = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Watch expressions...")).clicked() {
+                            self.show_watch_expressions = true;
+                            ui.close();
+                        }
+                        if ui.button(tr("Preview matrix...")).clicked() {
+                            self.show_preview_matrix = true;
+                            ui.close();
+                        }
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button(tr("Plugins...")).clicked() {
+                            self.reload_plugins();
+                            self.show_plugins = true;
+                            ui.close();
+                        }
+                        #[cfg(feature = "scripting")]
+                        if ui.button(tr("Script console...")).clicked() {
+                            self.show_script_console = true;
+                            ui.close();
+                        }
+                        #[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+                        if ui.button(tr("Remote control...")).clicked() {
+                            self.remote.show_window = true;
+                            ui.close();
+                        }
+                        ui.separator();
+                        ui.label(tr("Golden pool"));
+                        if let Some(project) = &mut self.project {
+                            let lock_text = if project.golden_pool.is_some() {
+                                tr("Update lock to current pool")
+                            } else {
+                                tr("Lock released IDs and names")
+                            };
+                            if ui
+                                .button(lock_text)
+                                .on_hover_text(
+                                    tr("Report an error when a released object is deleted or its ID changes"),
+                                )
+                                .clicked()
+                            {
+                                project.golden_pool = Some(GoldenPool::from_project(project));
+                                ui.close();
+                            }
+                            if project.golden_pool.is_some() && ui.button(tr("Remove lock")).clicked()
+                            {
+                                project.golden_pool = None;
+                                ui.close();
+                            }
+                        }
+                        ui.separator();
+                        ui.label(tr("Changelog against a baseline IOP"));
+                        for (format, label) in [
+                            (ChangelogFormat::Markdown, tr("Export Markdown (.md)...")),
+                            (ChangelogFormat::Csv, tr("Export CSV (.csv)...")),
+                        ] {
+                            if ui.button(label).clicked() {
+                                self.open_file_dialog(
+                                    FileDialogReason::ChangelogBaseline(format),
+                                    ctx,
+                                );
+                                ui.close();
+                            }
+                        }
+                    });
                 }
 
+                ui.menu_button(tr("Settings"), |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Author name:"));
+                        ui.text_edit_singleline(&mut self.settings.author_name);
+                    })
+                    .response
+                    .on_hover_text(tr("Recorded on every change and review comment"));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Language:"));
+                        let mut current = language();
+                        egui::ComboBox::from_id_salt("ui_language")
+                            .selected_text(current.native_name())
+                            .show_ui(ui, |ui| {
+                                for language in Language::ALL {
+                                    ui.selectable_value(
+                                        &mut current,
+                                        language,
+                                        language.native_name(),
+                                    );
+                                }
+                            });
+                        set_language(current);
+                    });
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Theme:"));
+                        let previous = self.settings.theme;
+                        egui::ComboBox::from_id_salt("ui_theme")
+                            .selected_text(tr(self.settings.theme.label()))
+                            .show_ui(ui, |ui| {
+                                for theme in UiTheme::ALL {
+                                    ui.selectable_value(
+                                        &mut self.settings.theme,
+                                        theme,
+                                        tr(theme.label()),
+                                    );
+                                }
+                            });
+                        if self.settings.theme != previous {
+                            self.settings.theme.apply(ctx);
+                        }
+                    });
+                    let mut touch = touch_mode(ctx);
+                    if ui
+                        .checkbox(&mut touch, tr("Touch mode"))
+                        .on_hover_text(tr(
+                            "Larger controls, long press for context menus, drag and pinch the preview",
+                        ))
+                        .changed()
+                    {
+                        set_touch_mode(ctx, touch);
+                    }
+                    ui.checkbox(&mut self.settings.expert_mode, tr("Expert mode"))
+                        .on_hover_text(tr(
+                            "Allow editing the raw bytes of objects in the hex inspector",
+                        ));
+                    ui.checkbox(&mut self.settings.mask_thumbnails, tr("Mask thumbnails"))
+                        .on_hover_text(tr("Show a strip with all masks below the editor"));
+                    #[cfg(not(target_arch = "wasm32"))]
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Backups to keep:"));
+                        let count = &mut self.settings.backup_count;
+                        ui.add(egui::DragValue::new(count).range(0..=1000));
+                    })
+                    .response
+                    .on_hover_text(tr(
+                        "Timestamped copies of every save in the .backups folder next to the file, 0 turns them off",
+                    ));
+                    ui.horizontal(|ui| {
+                        ui.label(tr("UI scale:"));
+                        let mut scale = ctx.zoom_factor();
+                        let slider = egui::Slider::new(&mut scale, UI_SCALE_RANGE)
+                            .step_by(0.25)
+                            .suffix("x");
+                        if ui.add(slider).changed() {
+                            ctx.set_zoom_factor(scale);
+                        }
+                    })
+                    .response
+                    .on_hover_text(tr(
+                        "Size of the designer interface, separate from the virtual mask size",
+                    ));
+                    ui.separator();
+                    if ui.button(tr("More settings...")).clicked() {
+                        self.show_settings = true;
+                        ui.close();
+                    }
+                });
+
                 if let Some(pool) = &mut self.project {
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         ui.add(
                             egui::Slider::new(&mut pool.mask_size, 100..=2000)
-                                .text("Virtual Mask size"),
+                                .text(tr("Virtual Mask size")),
+                        );
+                        ui.separator();
+                        ui.add(
+                            egui::DragValue::new(&mut pool.soft_key_size.1)
+                                .range(MIN_SOFT_KEY_SIZE.1..=pool.mask_size)
+                                .speed(1.0),
+                        );
+                        ui.label("x");
+                        ui.add(
+                            egui::DragValue::new(&mut pool.soft_key_size.0)
+                                .range(MIN_SOFT_KEY_SIZE.0..=pool.mask_size)
+                                .speed(1.0),
                         );
+                        ui.label(tr("Soft key size:"));
+                        if pool.soft_key_size.0 % 8 != 0 || pool.soft_key_size.1 % 8 != 0 {
+                            ui.colored_label(egui::Color32::YELLOW, "\u{26A0}")
+                                .on_hover_text(tr("Designator sizes should be a multiple of 8 pixels"));
+                        }
                     });
                 }
             });
         });
 
         if let Some(pool) = &mut self.project {
-            // Set forward and backward navigation shortcuts to mouse buttons
-            if ctx.input(|i| i.pointer.button_released(egui::PointerButton::Extra1)) {
+            // Make the simulated terminal properties available while rendering
+            with_preview_state(ctx, |state| state.soft_key_size = pool.soft_key_size);
+            pool.set_author(self.settings.author_name.trim());
+
+            // Set forward and backward navigation shortcuts to mouse buttons and the keyboard
+            if ctx.input(|i| i.pointer.button_released(egui::PointerButton::Extra1))
+                || ctx.input_mut(|i| i.consume_shortcut(&key_bindings.select_previous))
+            {
                 pool.set_previous_selected();
-            } else if ctx.input(|i| i.pointer.button_released(egui::PointerButton::Extra2)) {
+            } else if ctx.input(|i| i.pointer.button_released(egui::PointerButton::Extra2))
+                || ctx.input_mut(|i| i.consume_shortcut(&key_bindings.select_next))
+            {
                 pool.set_next_selected();
             }
 
+            let issues: Vec<ValidationIssue> = validate_project(pool)
+                .into_iter()
+                .chain(self.plugin_issues.iter().cloned())
+                .collect();
+            egui::TopBottomPanel::bottom("status_bar").show(ctx, |ui| {
+                match render_status_bar(ui, pool, &issues) {
+                    Some(StatusBarTarget::Validation) => self.show_validation = true,
+                    Some(StatusBarTarget::History) => self.show_history = true,
+                    Some(StatusBarTarget::TerminalProfiles) => self.show_terminal_profiles = true,
+                    None => {}
+                }
+            });
+            if self.settings.mask_thumbnails {
+                // Thumbnails are captured with screenshots, which the other captures would take
+                let capture_allowed = self.mask_print_run.is_none()
+                    && self.regression_run.is_none()
+                    && self.review_document_run.is_none();
+                egui::TopBottomPanel::bottom("mask_thumbnails").show(ctx, |ui| {
+                    if let Some(mask) = self.mask_thumbnails.show(ui, pool, capture_allowed) {
+                        show_mask(ctx, pool, mask);
+                    }
+                });
+            }
+
             // Object selector panel
             egui::SidePanel::left("left_panel").show(ctx, |ui| {
                 egui::ScrollArea::vertical().show(ui, |ui| {
                     ui.style_mut().wrap_mode = Some(egui::TextWrapMode::Truncate);
+                    let bookmarks = pool.get_bookmarks();
+                    if !bookmarks.is_empty() {
+                        ui.push_id("bookmarks", |ui| {
+                            egui::CollapsingHeader::new(format!(
+                                "\u{2605} {}",
+                                tr_args("Bookmarks ({})", &[&bookmarks.len()])
+                            ))
+                            .default_open(true)
+                            .show(ui, |ui| {
+                                for id in bookmarks {
+                                    match pool.get_pool().object_by_id(id) {
+                                        Some(object) => render_selectable_object(ui, object, pool),
+                                        None => {
+                                            ui.horizontal(|ui| {
+                                                ui.colored_label(
+                                                    egui::Color32::RED,
+                                                    tr_args("Missing object {}", &[&id.value()]),
+                                                );
+                                                if ui.small_button("\u{2716}").clicked() {
+                                                    pool.toggle_bookmark(id);
+                                                }
+                                            });
+                                        }
+                                    }
+                                }
+                            });
+                        });
+                        ui.separator();
+                    }
                     if let Some(working_set) = pool.get_pool().working_set_object() {
                         render_object_hierarchy(
                             ui,
@@ -742,7 +3359,7 @@ impl eframe::App for DesignerApp {
                     } else {
                         ui.colored_label(
                             egui::Color32::RED,
-                            "No working set, please add a new working set...",
+                            tr("No working set, please add a new working set..."),
                         );
                     }
                     let auxiliary_objects = pool.get_pool().objects_by_types(&[
@@ -760,16 +3377,20 @@ impl eframe::App for DesignerApp {
                     ui.separator();
 
                     // Filter objects in the pool by name
-                    let filter_id = ui.id().with("filter_text");
+                    let filter_id = egui::Id::new(OBJECT_LIST_FILTER_ID);
                     let mut filter_text = ui
                         .data(|data| data.get_temp::<String>(filter_id))
                         .unwrap_or_default();
+                    let unreviewed_id = egui::Id::new(OBJECT_LIST_UNREVIEWED_ID);
+                    let mut unreviewed_only = ui
+                        .data(|data| data.get_temp::<bool>(unreviewed_id))
+                        .unwrap_or_default();
 
                     ui.horizontal(|ui| {
                         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                             ui.add_space(ui.spacing().scroll.bar_width);
                             ui.menu_button("\u{2195}", |ui| {
-                                if ui.button("Sort by name").clicked() {
+                                if ui.button(tr("Sort by name")).clicked() {
                                     let pool_copy = pool.clone();
                                     pool.sort_objects_by(|a, b| {
                                         pool_copy
@@ -779,7 +3400,7 @@ impl eframe::App for DesignerApp {
                                     });
                                     ui.close();
                                 }
-                                if ui.button("Sort by id").clicked() {
+                                if ui.button(tr("Sort by id")).clicked() {
                                     pool.sort_objects_by(|a, b| {
                                         u16::from(a.id()).cmp(&u16::from(b.id()))
                                     });
@@ -787,20 +3408,29 @@ impl eframe::App for DesignerApp {
                                 }
                             })
                             .response
-                            .on_hover_text("Sort objects");
+                            .on_hover_text(tr("Sort objects"));
+
+                            if ui
+                                .toggle_value(&mut unreviewed_only, "\u{2610}")
+                                .on_hover_text(tr("Only show objects that are not reviewed yet"))
+                                .changed()
+                            {
+                                ui.data_mut(|data| {
+                                    data.insert_temp(unreviewed_id, unreviewed_only)
+                                });
+                            }
 
-                            let filter_shortcut =
-                                egui::KeyboardShortcut::new(egui::Modifiers::CTRL, egui::Key::F);
+                            let filter_shortcut = key_bindings.find;
 
                             let response = ui
                                 .add(
                                     egui::TextEdit::singleline(&mut filter_text)
-                                        .hint_text("Filter object by name...")
+                                        .hint_text(tr("Filter object by name..."))
                                         .desired_width(ui.available_width()),
                                 )
-                                .on_hover_text(format!(
+                                .on_hover_text(tr_args(
                                     "Search shortcut ({})",
-                                    ctx.format_shortcut(&filter_shortcut)
+                                    &[&ctx.format_shortcut(&filter_shortcut)],
                                 ));
                             if response.changed() {
                                 ui.data_mut(|data| {
@@ -812,15 +3442,8 @@ impl eframe::App for DesignerApp {
                         });
                     });
 
-                    let filter_text = filter_text.to_lowercase();
                     for object in pool.get_pool().objects() {
-                        if filter_text.is_empty()
-                            || pool
-                                .get_object_info(object)
-                                .get_name(object)
-                                .to_lowercase()
-                                .contains(&filter_text)
-                        {
+                        if matches_object_list_filter(ctx, pool, object) {
                             render_selectable_object(ui, object, pool);
                         }
                     }
@@ -831,121 +3454,6305 @@ impl eframe::App for DesignerApp {
 
             // Main panel
             egui::CentralPanel::default().show(ctx, |ui| {
-                if pool
-                    .get_pool()
-                    .objects_by_type(ObjectType::DataMask)
-                    .is_empty()
-                {
-                    ui.colored_label(
-                        egui::Color32::RED,
-                        "Missing data masks, please load a pool file or add a new mask...",
-                    );
-                } else {
-                    match pool.get_pool().working_set_object() {
-                        Some(mask) => match pool.get_pool().object_by_id(mask.active_mask) {
-                            Some(obj) => {
-                                let selected_ref = pool.get_mut_selected();
-
-                                egui::ScrollArea::both().show(ui, |ui| {
-                                    ui.add_sized(
-                                        [pool.mask_size as f32, pool.mask_size as f32],
-                                        InteractiveMaskRenderer {
-                                            object: obj,
-                                            pool: pool.get_pool(),
-                                            selected_callback: Box::new(move |object_id| {
-                                                *selected_ref.borrow_mut() =
-                                                    NullableObjectId(Some(object_id));
-                                            }),
-                                        },
-                                    );
-                                });
-                            }
-                            None => {
-                                ui.colored_label(
-                                    egui::Color32::RED,
-                                    format!("Missing data mask: {:?}", mask),
-                                );
-                            }
-                        },
-                        None => {
-                            ui.colored_label(
-                                egui::Color32::RED,
-                                "No working sets, please add a new working set...",
-                            );
+                if pool.is_read_only() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            egui::Color32::ORANGE,
+                            tr("Read-only: this pool was recovered from damaged data"),
+                        );
+                        if self.recovery_report.is_some() && ui.button(tr("Show report")).clicked()
+                        {
+                            self.show_recovery_report = true;
                         }
-                    }
+                        if ui
+                            .button(tr("Allow editing"))
+                            .on_hover_text(tr("Edit the salvaged objects, skipped data is lost"))
+                            .clicked()
+                        {
+                            pool.set_read_only(false);
+                        }
+                    });
+                    ui.separator();
                 }
-            });
+                let errors: Vec<_> = issues
+                    .iter()
+                    .filter(|issue| issue.severity == Severity::Error)
+                    .collect();
+                if let Some(first) = errors.first() {
+                    ui.horizontal(|ui| {
+                        ui.label(
+                            egui::RichText::new(format!(
+                                "\u{26A0} {}",
+                                tr_args(
+                                    "{} validation error(s): {}",
+                                    &[&errors.len(), &first.message]
+                                )
+                            ))
+                            .color(egui::Color32::RED)
+                            .strong(),
+                        );
+                        if ui.button(tr("Show all")).clicked() {
+                            self.show_validation = true;
+                        }
+                    });
+                    ui.separator();
+                }
+                if self.preview_detached {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("The preview is shown in a separate window"));
+                        if ui.button(tr("Attach preview")).clicked() {
+                            self.preview_detached = false;
+                        }
+                    });
+                } else {
+                    if ui
+                        .small_button(format!("\u{29C9} {}", tr("Detach preview")))
+                        .on_hover_text(tr("Show the preview in a separate window"))
+                        .clicked()
+                    {
+                        self.preview_detached = true;
+                    }
+                    render_mask_preview(ui, pool);
+                }
+            });
+
+            // Parameters panel
+            egui::SidePanel::right("right_panel").show(ctx, |ui: &mut egui::Ui| {
+                if let Some(id) = pool.get_selected().into() {
+                    if let Some(obj) = pool.get_mut_pool().borrow_mut().object_mut_by_id(id) {
+                        egui::ScrollArea::vertical().show(ui, |ui| {
+                            render_breadcrumbs(ui, pool, obj.id());
+                            render_shared_object_notice(ui, pool, obj.id());
+                            ui.separator();
+                            render_fidelity_details(ui, pool, obj);
+
+                            let locked = pool.is_locked(obj.id());
+                            if locked {
+                                ui.horizontal(|ui| {
+                                    ui.colored_label(
+                                        egui::Color32::ORANGE,
+                                        format!("\u{1F512} {}", tr("This object is locked")),
+                                    );
+                                    if ui.button(tr("Unlock")).clicked() {
+                                        pool.set_locked(obj, false);
+                                    }
+                                });
+                                ui.separator();
+                            }
+
+                            // Display editable object name as header
+                            ui.add_enabled_ui(!locked, |ui| {
+                                ui.horizontal(|ui| {
+                                    let bookmarked = pool.is_bookmarked(obj.id());
+                                    if ui
+                                        .add(
+                                            egui::Button::new(if bookmarked {
+                                                "\u{2605}"
+                                            } else {
+                                                "\u{2606}"
+                                            })
+                                            .frame(false),
+                                        )
+                                        .on_hover_text(if bookmarked {
+                                            tr("Remove bookmark")
+                                        } else {
+                                            tr("Bookmark")
+                                        })
+                                        .clicked()
+                                    {
+                                        pool.toggle_bookmark(obj.id());
+                                    }
+                                    ui.label(tr("Name:"));
+
+                                    let object_info = pool.get_object_info(obj);
+                                    let mut name = object_info.get_name(obj);
+                                    let response = ui.text_edit_singleline(&mut name);
+
+                                    if response.changed() {
+                                        let mut object_info_map = pool.object_info.borrow_mut();
+                                        if let Some(info) = object_info_map.get_mut(&obj.id()) {
+                                            info.set_name(name);
+                                        }
+                                    }
+                                })
+                            });
+                            ui.separator();
+
+                            ui.add_enabled_ui(!locked, |ui| {
+                                obj.render_parameters(ui, pool);
+                                render_colour_role_tags(ui, obj, pool);
+                                render_style_preset_assignment(ui, obj, pool);
+                            });
+                            render_notes(ui, obj, pool);
+                            render_review(ui, obj, pool, self.settings.author_name.trim());
+                            render_object_history(ui, obj, pool);
+                            let (width, height) = pool.get_pool().content_size(obj);
+                            ui.separator();
+                            let desired_size = egui::Vec2::new(width as f32, height as f32);
+                            ui.allocate_ui(desired_size, |ui| {
+                                obj.render(ui, pool.get_pool(), Point::default());
+                            });
+                        });
+                    } else {
+                        ui.colored_label(
+                            egui::Color32::RED,
+                            tr_args("Selected object not found: {}", &[&u16::from(id)]),
+                        );
+                    }
+                }
+                ui.allocate_space(ui.available_size());
+            });
+
+            if let Some(run) = &mut self.review_document_run {
+                run.update(ctx, pool);
+                if run.is_finished() {
+                    match run.document(pool) {
+                        Ok(document) => save_review_document(document),
+                        Err(e) => log::error!("Failed to create the design review: {}", e),
+                    }
+                    self.review_document_run = None;
+                }
+            }
+
+            if let Some((run, print)) = &mut self.mask_print_run {
+                run.update(ctx, pool);
+                if run.is_finished() {
+                    let result = run.document(pool).and_then(|document| {
+                        if *print {
+                            print_mask_document(run.mask, document)
+                        } else {
+                            save_mask_print(run.mask, document);
+                            Ok(())
+                        }
+                    });
+                    self.mask_print_error = result.err();
+                    self.mask_print_run = None;
+                }
+            }
+
+            if let Some(run) = &mut self.regression_run {
+                run.update(ctx, pool);
+                if run.is_finished() {
+                    if let Some(exit_code) = self.regression_exit_code.take() {
+                        print_regression_report(run);
+                        exit_code.store((run.failures() > 0) as i32, Ordering::SeqCst);
+                        ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+                    }
+                }
+            }
+
+            let changed = if edit_in_progress(ctx) {
+                pool.update_pool_live()
+            } else {
+                pool.update_pool()
+            };
+            if changed {
+                ctx.request_repaint();
+            }
+            if pool.update_selected() {
+                // Make sure all collapsing headers for the selected object are open
+                if let Some(working_set) = pool.get_pool().working_set_object() {
+                    update_object_hierarchy_headers(
+                        ctx,
+                        egui::Id::new(OBJECT_HIERARCHY_ID),
+                        &Object::WorkingSet(working_set.clone()),
+                        pool.get_pool(),
+                        pool.get_selected(),
+                    );
+                }
+                ctx.request_repaint();
+            }
+        } else {
+            egui::CentralPanel::default().show(ctx, |ui| {
+                ui.label(tr("No object pool loaded, please load a pool file..."));
+            });
+        }
+    }
+}
+
+impl DesignerApp {
+    /// Register the custom fonts of the active terminal profile with egui and make the font
+    /// mapping available to the preview.
+    /// New fonts only become usable in the next frame, so the mapping is applied one frame later.
+    fn update_terminal_fonts(&mut self, ctx: &egui::Context) {
+        let profile = self.project.as_ref().and_then(|p| p.terminal_profile());
+        let fonts: Vec<(String, u64)> = profile.map_or(Vec::new(), |profile| {
+            profile
+                .fonts
+                .iter()
+                .map(|f| (f.name.clone(), f.data_hash()))
+                .collect()
+        });
+
+        if self.registered_fonts.as_ref() != Some(&fonts) {
+            let mut definitions = egui::FontDefinitions::default();
+            let fallback = definitions
+                .families
+                .get(&egui::FontFamily::Proportional)
+                .cloned()
+                .unwrap_or_default();
+            let custom_fonts = profile
+                .map_or(&[][..], |p| &p.fonts[..])
+                .iter()
+                .filter(|font| match validate_font(&font.data) {
+                    Ok(()) => true,
+                    Err(error) => {
+                        log::warn!("Skipping font '{}': {}", font.name, error);
+                        false
+                    }
+                })
+                .map(|font| (font.name.as_str(), font.data.clone()));
+            let pixel_font = std::iter::once((PIXEL_FONT_NAME, pixel_font_data()));
+            for (name, data) in pixel_font.chain(custom_fonts) {
+                let family_name = TerminalProfile::font_family_name(name);
+                definitions.font_data.insert(
+                    family_name.clone(),
+                    Arc::new(egui::FontData::from_owned(data)),
+                );
+                let mut family = vec![family_name.clone()];
+                family.extend(fallback.iter().cloned());
+                definitions
+                    .families
+                    .insert(egui::FontFamily::Name(family_name.into()), family);
+            }
+            ctx.set_fonts(definitions);
+            self.registered_fonts = Some(fonts);
+            with_preview_state(ctx, |state| state.font_families.clear());
+            ctx.request_repaint();
+            return;
+        }
+
+        let font_families = profile.map_or(HashMap::new(), |profile| {
+            font_size_keys()
+                .into_iter()
+                .filter_map(|key| {
+                    let font = profile.font_mapping.get(&key)?;
+                    if !profile.has_font(font) {
+                        return None;
+                    }
+                    let family_name = TerminalProfile::font_family_name(font);
+                    Some((key, egui::FontFamily::Name(family_name.into())))
+                })
+                .collect()
+        });
+        with_preview_state(ctx, |state| state.font_families = font_families);
+    }
+
+    /// Window to manage the terminal profiles and their font mapping
+    fn render_terminal_profiles_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &mut self.project else {
+            self.show_terminal_profiles = false;
+            return;
+        };
+
+        let mut open = true;
+        let mut load_font = false;
+        let mut import_capabilities = false;
+        egui::Window::new(tr("Terminal profiles"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Profile:"));
+                    let selected_name = project
+                        .terminal_profile()
+                        .map_or(String::new(), |p| p.name.clone());
+                    egui::ComboBox::from_id_salt("terminal_profile_select")
+                        .selected_text(selected_name)
+                        .show_ui(ui, |ui| {
+                            for (idx, profile) in project.terminal_profiles.iter().enumerate() {
+                                ui.selectable_value(
+                                    &mut project.active_profile,
+                                    idx,
+                                    &profile.name,
+                                );
+                            }
+                        });
+                    if ui
+                        .button("\u{2795}")
+                        .on_hover_text(tr("Add profile"))
+                        .clicked()
+                    {
+                        project.terminal_profiles.push(TerminalProfile {
+                            name: format!("Terminal {}", project.terminal_profiles.len() + 1),
+                            ..Default::default()
+                        });
+                        project.active_profile = project.terminal_profiles.len() - 1;
+                    }
+                    if ui
+                        .add_enabled(
+                            project.terminal_profiles.len() > 1,
+                            egui::Button::new("\u{1F5D1}"),
+                        )
+                        .on_hover_text(tr("Remove profile"))
+                        .clicked()
+                    {
+                        project.terminal_profiles.remove(project.active_profile);
+                        project.active_profile = 0;
+                    }
+                    if ui
+                        .button(tr("Import capabilities..."))
+                        .on_hover_text(tr(
+                            "Add a profile from the capability file of an installed terminal",
+                        ))
+                        .clicked()
+                    {
+                        import_capabilities = true;
+                    }
+                    #[cfg(all(feature = "can-capture", target_os = "linux"))]
+                    if ui
+                        .button(tr("Capture from CAN..."))
+                        .on_hover_text(tr(
+                            "Add a profile with the capabilities of a terminal on the bus",
+                        ))
+                        .clicked()
+                    {
+                        self.can_capture.show_window = true;
+                    }
+                });
+
+                let active_profile = project.active_profile;
+                let Some(profile) = project.terminal_profiles.get_mut(active_profile) else {
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    ui.label(tr("Name:"));
+                    ui.text_edit_singleline(&mut profile.name);
+                });
+                ui.horizontal(|ui| {
+                    let mut limited = profile.pool_size_budget.is_some();
+                    if ui.checkbox(&mut limited, tr("Pool size budget:")).changed() {
+                        profile.pool_size_budget = limited.then_some(DEFAULT_POOL_SIZE_BUDGET);
+                    }
+                    if let Some(budget) = &mut profile.pool_size_budget {
+                        ui.add(
+                            egui::DragValue::new(budget)
+                                .range(1024..=usize::MAX)
+                                .speed(1024.0)
+                                .suffix(tr(" bytes")),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text(tr(
+                    "Object pool memory of the terminal, larger pools are reported as error",
+                ));
+                ui.horizontal(|ui| {
+                    let mut limited = profile.soft_key_count.is_some();
+                    if ui
+                        .checkbox(&mut limited, tr("Soft keys per page:"))
+                        .changed()
+                    {
+                        profile.soft_key_count = limited.then_some(DEFAULT_SOFT_KEY_COUNT);
+                    }
+                    if let Some(count) = &mut profile.soft_key_count {
+                        ui.add(egui::DragValue::new(count).range(1..=64));
+                    }
+                })
+                .response
+                .on_hover_text(tr(
+                    "Soft key masks with more keys are reported, they need paging",
+                ));
+                ui.horizontal(|ui| {
+                    let mut known = profile.mask_size.is_some();
+                    if ui.checkbox(&mut known, tr("Mask size:")).changed() {
+                        profile.mask_size = known.then_some(project.mask_size);
+                    }
+                    if let Some(size) = &mut profile.mask_size {
+                        ui.add(egui::DragValue::new(size).range(100..=2000).suffix(" px"));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    let mut known = profile.soft_key_size.is_some();
+                    if ui.checkbox(&mut known, tr("Soft key size:")).changed() {
+                        profile.soft_key_size = known.then_some(project.soft_key_size);
+                    }
+                    if let Some((width, height)) = &mut profile.soft_key_size {
+                        ui.add(egui::DragValue::new(width).range(MIN_SOFT_KEY_SIZE.0..=2000));
+                        ui.label("x");
+                        ui.add(egui::DragValue::new(height).range(MIN_SOFT_KEY_SIZE.1..=2000));
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("Colours:"));
+                    egui::ComboBox::from_id_salt("terminal_colour_depth")
+                        .selected_text(tr(profile.colour_depth.label()))
+                        .show_ui(ui, |ui| {
+                            for depth in ColourDepth::ALL {
+                                ui.selectable_value(
+                                    &mut profile.colour_depth,
+                                    depth,
+                                    tr(depth.label()),
+                                );
+                            }
+                        });
+                });
+                let mut forget_capabilities = false;
+                if let Some(capabilities) = &profile.capabilities {
+                    ui.horizontal(|ui| {
+                        let version = capabilities
+                            .vt_version
+                            .map_or(tr("unknown").to_string(), |v| v.to_string());
+                        let fonts = capabilities
+                            .font_sizes
+                            .as_ref()
+                            .map_or(tr("unknown").to_string(), |f| f.len().to_string());
+                        ui.label(tr_args(
+                            "Imported capabilities: VT version {}, {} font sizes",
+                            &[&version, &fonts],
+                        ))
+                        .on_hover_text(tr(
+                            "The validator checks the pool against these capabilities",
+                        ));
+                        if ui
+                            .small_button("\u{1F5D1}")
+                            .on_hover_text(tr("Forget the imported capabilities"))
+                            .clicked()
+                        {
+                            forget_capabilities = true;
+                        }
+                    });
+                }
+                if forget_capabilities {
+                    profile.capabilities = None;
+                }
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.heading(tr("Fonts"));
+                    if ui.button(tr("Load TTF/OTF font...")).clicked() {
+                        load_font = true;
+                    }
+                });
+                if let Some(error) = &self.font_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                if profile.fonts.is_empty() {
+                    ui.label(tr(
+                        "No custom fonts, the built-in fonts are used for all sizes",
+                    ));
+                }
+                let mut remove = None;
+                for font in &profile.fonts {
+                    ui.horizontal(|ui| {
+                        ui.label(format!("{} ({} kB)", font.name, font.data.len() / 1024));
+                        if ui
+                            .small_button("\u{1F5D1}")
+                            .on_hover_text(tr("Remove font"))
+                            .clicked()
+                        {
+                            remove = Some(font.name.clone());
+                        }
+                    });
+                }
+                if let Some(name) = remove {
+                    profile.remove_font(&name);
+                }
+                ui.separator();
+
+                ui.heading(tr("Font mapping"));
+                egui::Grid::new("font_mapping_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for key in font_size_keys() {
+                            ui.label(&key);
+                            let mut selected = profile.font_mapping.get(&key).cloned();
+                            egui::ComboBox::from_id_salt(format!("font_mapping_{}", key))
+                                .selected_text(selected.clone().unwrap_or("Built-in".to_string()))
+                                .show_ui(ui, |ui| {
+                                    ui.selectable_value(&mut selected, None, tr("Built-in"));
+                                    ui.selectable_value(
+                                        &mut selected,
+                                        Some(PIXEL_FONT_NAME.to_string()),
+                                        PIXEL_FONT_NAME,
+                                    );
+                                    for font in &profile.fonts {
+                                        ui.selectable_value(
+                                            &mut selected,
+                                            Some(font.name.clone()),
+                                            &font.name,
+                                        );
+                                    }
+                                });
+                            match selected {
+                                Some(font) => profile.font_mapping.insert(key, font),
+                                None => profile.font_mapping.remove(&key),
+                            };
+                            ui.end_row();
+                        }
+                    });
+            });
+
+        if load_font {
+            self.open_file_dialog(FileDialogReason::TerminalFont, ctx);
+        }
+        if import_capabilities {
+            self.open_file_dialog(FileDialogReason::TerminalCapabilities, ctx);
+        }
+        if !open {
+            self.show_terminal_profiles = false;
+        }
+    }
+
+    /// Show the preview in its own OS window, so it can be moved to another monitor
+    fn render_detached_preview(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.preview_detached = false;
+            return;
+        };
+
+        let mut open = true;
+        ctx.show_viewport_immediate(
+            egui::ViewportId::from_hash_of("detached_preview"),
+            egui::ViewportBuilder::default()
+                .with_title(tr("Preview"))
+                .with_inner_size([
+                    project.mask_size as f32 + 32.0,
+                    project.mask_size as f32 + 32.0,
+                ]),
+            |ctx, class| {
+                if class == egui::ViewportClass::Embedded {
+                    // The platform has no multiple windows, show the preview as a window instead
+                    egui::Window::new(tr("Preview"))
+                        .open(&mut open)
+                        .resizable(true)
+                        .show(ctx, |ui| render_mask_preview(ui, project));
+                    return;
+                }
+                egui::CentralPanel::default().show(ctx, |ui| render_mask_preview(ui, project));
+                if ctx.input(|i| i.viewport().close_requested()) {
+                    open = false;
+                }
+            },
+        );
+        self.preview_detached = open;
+    }
+
+    /// Window that renders a sample text in every VT font size of the active terminal profile,
+    /// annotated with its pixel dimensions
+    fn render_font_metrics_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_font_metrics = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Font metrics"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                if let Some(profile) = project.terminal_profile() {
+                    ui.label(tr_args("Terminal profile: {}", &[&profile.name]));
+                }
+                ui.horizontal(|ui| {
+                    ui.label(tr("Sample text:"));
+                    ui.text_edit_singleline(&mut self.font_metrics_sample);
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("Proportional height:"));
+                    ui.add(
+                        egui::DragValue::new(&mut self.font_metrics_proportional_height)
+                            .range(1..=255),
+                    );
+                    ui.checkbox(&mut self.font_metrics_inverted, tr("Inverted"));
+                });
+                ui.weak(tr("Bold, italic, underlined and crossed out styles are not simulated by the preview"));
+                ui.separator();
+
+                let (foreground, background) = if self.font_metrics_inverted {
+                    (egui::Color32::WHITE, egui::Color32::BLACK)
+                } else {
+                    (egui::Color32::BLACK, egui::Color32::WHITE)
+                };
+                let sample_chars = self.font_metrics_sample.chars().count();
+                let font_sizes = NON_PROPORTIONAL_FONT_SIZES
+                    .iter()
+                    .map(|size| FontSize::NonProportional(*size))
+                    .chain([FontSize::Proportional(self.font_metrics_proportional_height)]);
+
+                egui::ScrollArea::both().show(ui, |ui| {
+                    egui::Grid::new("font_metrics_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            ui.strong(tr("Font size"));
+                            ui.strong(tr("Character cell"));
+                            ui.strong(tr("Rendered text"));
+                            ui.strong(tr("Characters per mask line"));
+                            ui.strong(tr("Sample"));
+                            ui.end_row();
+
+                            for font_size in font_sizes {
+                                let font_id = vt_font_id(ui, &font_size);
+                                let galley = ui.fonts(|fonts| {
+                                    fonts.layout_no_wrap(
+                                        self.font_metrics_sample.clone(),
+                                        font_id,
+                                        foreground,
+                                    )
+                                });
+                                let text_size = galley.size();
+
+                                match font_size {
+                                    FontSize::NonProportional(size) => {
+                                        ui.label(font_size_key(&font_size));
+                                        ui.label(format!("{} x {} px", size.width(), size.height()));
+                                    }
+                                    FontSize::Proportional(height) => {
+                                        ui.label(tr_args("Proportional {}", &[&height]));
+                                        ui.label(format!("? x {} px", height));
+                                    }
+                                }
+                                ui.label(format!(
+                                    "{:.0} x {:.0} px",
+                                    text_size.x, text_size.y
+                                ));
+                                if sample_chars > 0 && text_size.x > 0.0 {
+                                    let char_width = text_size.x / sample_chars as f32;
+                                    ui.label(format!(
+                                        "{:.0}",
+                                        (project.mask_size as f32 / char_width).floor()
+                                    ));
+                                } else {
+                                    ui.label("-");
+                                }
+
+                                let (rect, _) = ui.allocate_exact_size(
+                                    text_size.max(egui::vec2(1.0, 1.0)),
+                                    egui::Sense::hover(),
+                                );
+                                ui.painter().rect_filled(rect, 0.0, background);
+                                ui.painter().galley(rect.min, galley, foreground);
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if !open {
+            self.show_font_metrics = false;
+        }
+    }
+
+    /// Window to manage the spelling dictionaries and review the misspelled words of the project
+    fn render_spell_check_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_spell_check = false;
+            return;
+        };
+
+        let mut open = true;
+        let mut load_dictionary = false;
+        egui::Window::new(tr("Spell check"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let checker = spell_checker(ctx);
+                let mut enabled = checker.enabled;
+                if ui.checkbox(&mut enabled, tr("Check spelling")).changed() {
+                    with_spell_checker(ctx, |checker| checker.enabled = enabled);
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("Dictionaries:"));
+                    if ui.button(tr("Load dictionary...")).clicked() {
+                        load_dictionary = true;
+                    }
+                });
+                if checker.dictionaries.is_empty() {
+                    ui.label(tr(
+                        "No dictionaries loaded, load a word list or Hunspell .dic file",
+                    ));
+                }
+                let mut removed = None;
+                for (idx, dictionary) in checker.dictionaries.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.label(tr_args(
+                            "{} ({} words)",
+                            &[&dictionary.language, &dictionary.len()],
+                        ));
+                        if ui.small_button(tr("Remove")).clicked() {
+                            removed = Some(idx);
+                        }
+                    });
+                }
+                if let Some(idx) = removed {
+                    with_spell_checker(ctx, |checker| checker.dictionaries.remove(idx));
+                }
+
+                if !checker.ignored.is_empty() {
+                    ui.collapsing(
+                        tr_args("Ignored words ({})", &[&checker.ignored.len()]),
+                        |ui| {
+                            let mut restored = None;
+                            for word in &checker.ignored {
+                                ui.horizontal(|ui| {
+                                    ui.label(word);
+                                    if ui.small_button(tr("Check again")).clicked() {
+                                        restored = Some(word.clone());
+                                    }
+                                });
+                            }
+                            if let Some(word) = restored {
+                                with_spell_checker(ctx, |checker| checker.ignored.remove(&word));
+                            }
+                        },
+                    );
+                }
+                ui.separator();
+
+                if !checker.is_active() {
+                    ui.label(tr("Spell checking is off"));
+                } else {
+                    let issues = check_spelling(project, &checker);
+                    if issues.is_empty() {
+                        ui.label(tr("No misspelled words found"));
+                    }
+                    egui::ScrollArea::vertical()
+                        .max_height(400.0)
+                        .show(ui, |ui| {
+                            for issue in issues {
+                                ui.horizontal(|ui| {
+                                    ui.colored_label(egui::Color32::RED, &issue.word);
+                                    if ui
+                                        .link(format!(
+                                            "{}: \"{}\"",
+                                            issue.object.value(),
+                                            issue.text
+                                        ))
+                                        .clicked()
+                                    {
+                                        project
+                                            .get_mut_selected()
+                                            .replace(NullableObjectId(Some(issue.object)));
+                                    }
+                                    if ui.small_button(tr("Ignore")).clicked() {
+                                        let word = issue.word.to_lowercase();
+                                        with_spell_checker(ctx, |checker| {
+                                            checker.ignored.insert(word)
+                                        });
+                                    }
+                                });
+                            }
+                        });
+                }
+            });
+
+        if load_dictionary {
+            self.open_file_dialog(FileDialogReason::SpellingDictionary, ctx);
+        }
+        if !open {
+            self.show_spell_check = false;
+        }
+    }
+
+    /// Load the plugin manifests from the plugin directory
+    fn reload_plugins(&mut self) {
+        let (plugins, errors) =
+            ag_iso_terminal_designer::load_plugins(&PathBuf::from(self.plugin_dir.trim()));
+        self.plugins = plugins;
+        self.plugin_log = errors;
+    }
+
+    /// Open a file dialog to save the output of an export plugin
+    fn save_plugin_export(&self, plugin: &Plugin, contents: Vec<u8>) {
+        let extension = plugin.export_extension.as_deref().unwrap_or("bin");
+        let task = rfd::AsyncFileDialog::new()
+            .set_file_name(format!("export.{}", extension))
+            .add_filter(&plugin.name, &[extension])
+            .save_file();
+        execute(async move {
+            let file = task.await;
+            if let Some(file) = file {
+                _ = file.write(&contents).await;
+            }
+        });
+    }
+
+    /// Handle the output of the running plugin once it finished
+    fn handle_plugin_run(&mut self, ctx: &egui::Context) {
+        let Some(running) = &self.plugin_run else {
+            return;
+        };
+        let Some(output) = running.try_finish() else {
+            ctx.request_repaint_after(std::time::Duration::from_millis(100));
+            return;
+        };
+        let Some(running) = self.plugin_run.take() else {
+            return;
+        };
+        let plugin = &running.plugin;
+        let result = output.and_then(|output| match running.hook {
+            PluginHook::Export => {
+                self.save_plugin_export(plugin, output);
+                Ok(())
+            }
+            PluginHook::Validate => plugin.issues(&output).map(|issues| {
+                self.plugin_issues
+                    .retain(|issue| !issue.message.starts_with(&format!("[{}]", plugin.name)));
+                self.plugin_issues.extend(issues);
+                self.show_validation = true;
+            }),
+            PluginHook::Transform => match &self.project {
+                Some(project) => plugin.transform(project, &output).map(|report| {
+                    if !report.is_clean() {
+                        self.plugin_log.push(format!(
+                            "{} returned a damaged pool: recovered {} objects, skipped {} bytes",
+                            plugin.name,
+                            report.recovered_objects,
+                            report.skipped_bytes()
+                        ));
+                    }
+                }),
+                None => Ok(()),
+            },
+        });
+        if let Err(e) = result {
+            log::error!("{}", e);
+            self.plugin_log.push(e);
+        }
+    }
+
+    /// Window to run the export, validate and transform hooks of the installed plugins
+    fn render_plugins_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut run: Option<(usize, PluginHook)> = None;
+        let mut reload = false;
+        egui::Window::new(tr("Plugins"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Plugin directory:"));
+                    ui.text_edit_singleline(&mut self.plugin_dir);
+                    if ui.button(tr("Reload")).clicked() {
+                        reload = true;
+                    }
+                });
+                ui.label(tr_args(
+                    "Set {} to change the default directory",
+                    &[&(ag_iso_terminal_designer::PLUGIN_DIR_ENV)],
+                ));
+                ui.separator();
+
+                if self.plugins.is_empty() {
+                    ui.label(tr("No plugins found"));
+                }
+                let can_run = self.project.is_some() && self.plugin_run.is_none();
+                if let Some(running) = &self.plugin_run {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr_args(
+                            "Running {} ({})",
+                            &[&running.plugin.name, &running.hook.argument()],
+                        ));
+                        if ui.button(tr("Cancel")).clicked() {
+                            running.cancel();
+                        }
+                    });
+                }
+                for (idx, plugin) in self.plugins.iter().enumerate() {
+                    ui.horizontal(|ui| {
+                        ui.strong(&plugin.name);
+                        if !plugin.description.is_empty() {
+                            ui.label(&plugin.description);
+                        }
+                    });
+                    ui.horizontal(|ui| {
+                        for (hook, label) in [
+                            (PluginHook::Export, "Export..."),
+                            (PluginHook::Validate, "Validate"),
+                            (PluginHook::Transform, "Transform"),
+                        ] {
+                            if plugin.supports(hook)
+                                && ui.add_enabled(can_run, egui::Button::new(label)).clicked()
+                            {
+                                run = Some((idx, hook));
+                            }
+                        }
+                    });
+                }
+
+                if !self.plugin_log.is_empty() {
+                    ui.separator();
+                    for message in &self.plugin_log {
+                        ui.colored_label(egui::Color32::RED, message);
+                    }
+                }
+            });
+
+        if reload {
+            self.reload_plugins();
+        }
+        if let (Some((idx, hook)), Some(project)) = (run, &self.project) {
+            self.plugin_log.clear();
+            self.plugin_run = Some(self.plugins[idx].start(hook, project.get_pool()));
+        }
+        if !open {
+            self.show_plugins = false;
+        }
+    }
+
+    /// Console to run a script over the pool, the whole script is a single undoable change
+    #[cfg(feature = "scripting")]
+    fn render_script_console(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        egui::Window::new(tr("Script console"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                ui.collapsing(tr("Available functions"), |ui| {
+                    for function in ag_iso_terminal_designer::SCRIPT_API {
+                        ui.monospace(*function);
+                    }
+                });
+                egui::ScrollArea::vertical()
+                    .id_salt("script_editor")
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(&mut self.script)
+                                .code_editor()
+                                .desired_rows(12)
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+
+                let run_clicked = ui
+                    .add_enabled(
+                        self.project.is_some(),
+                        egui::Button::new(tr("Run")).shortcut_text(
+                            ctx.format_shortcut(&self.settings.key_bindings.run_script),
+                        ),
+                    )
+                    .clicked();
+                let run_shortcut =
+                    ui.input_mut(|i| i.consume_shortcut(&self.settings.key_bindings.run_script));
+                if let (true, Some(project)) = (run_clicked || run_shortcut, &self.project) {
+                    let result = ag_iso_terminal_designer::run_script(project, &self.script);
+                    self.script_output = result.output;
+                    match result.error {
+                        Some(error) => self.script_output.push(format!("Error: {}", error)),
+                        None if result.changed => {
+                            self.script_output.push(tr("Pool updated").to_string())
+                        }
+                        None => self.script_output.push(tr("No changes").to_string()),
+                    }
+                }
+
+                ui.separator();
+                egui::ScrollArea::vertical()
+                    .id_salt("script_output")
+                    .max_height(200.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for line in &self.script_output {
+                            if line.starts_with("Error:") {
+                                ui.colored_label(egui::Color32::RED, line);
+                            } else {
+                                ui.monospace(line);
+                            }
+                        }
+                    });
+            });
+
+        if !open {
+            self.show_script_console = false;
+        }
+    }
+
+    /// Which events trigger which macros and which objects the macros change, per object
+    fn render_macro_references_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_macro_references = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Macro references"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                let pool = project.get_pool();
+                let triggers = macro_triggers(pool);
+                let targets = macro_targets(pool);
+                if triggers.is_empty() && targets.is_empty() {
+                    ui.label(tr("No macros are used in this pool"));
+                    return;
+                }
+                ui.horizontal(|ui| {
+                    ui.label(tr("Filter:"));
+                    ui.text_edit_singleline(&mut self.macro_references_filter);
+                });
+                ui.separator();
+
+                let object_link = |ui: &mut egui::Ui, id: ObjectId| {
+                    let name = pool
+                        .object_by_id(id)
+                        .map(|o| project.get_object_info(o).get_name(o))
+                        .unwrap_or_else(|| tr_args("Missing object {}", &[&id.value()]));
+                    if ui.link(name).clicked() {
+                        project
+                            .get_mut_selected()
+                            .replace(NullableObjectId(Some(id)));
+                    }
+                };
+                let macro_id = |id: u8| ObjectId::new(id as u16).ok();
+
+                let filter = self.macro_references_filter.to_lowercase();
+                egui::ScrollArea::vertical()
+                    .max_height(500.0)
+                    .show(ui, |ui| {
+                        for object in pool.objects() {
+                            let id = object.id();
+                            let object_triggers: Vec<_> =
+                                triggers.iter().filter(|t| t.object == id).collect();
+                            let targeted_by: Vec<_> =
+                                targets.iter().filter(|t| t.object == id).collect();
+                            let macro_commands: Vec<_> =
+                                targets.iter().filter(|t| t.macro_object == id).collect();
+                            if object_triggers.is_empty()
+                                && targeted_by.is_empty()
+                                && macro_commands.is_empty()
+                            {
+                                continue;
+                            }
+                            let name = project.get_object_info(object).get_name(object);
+                            if !filter.is_empty() && !name.to_lowercase().contains(&filter) {
+                                continue;
+                            }
+
+                            ui.horizontal(|ui| {
+                                ui.label(format!("{:?}", object.object_type()));
+                                object_link(ui, id);
+                            });
+                            ui.indent(id, |ui| {
+                                for trigger in object_triggers {
+                                    ui.horizontal(|ui| {
+                                        ui.label(tr_args(
+                                            "{} executes",
+                                            &[&format!("{:?}", trigger.event)],
+                                        ));
+                                        match macro_id(trigger.macro_id) {
+                                            Some(id) => object_link(ui, id),
+                                            None => {
+                                                ui.label(tr_args("macro {}", &[&trigger.macro_id]));
+                                            }
+                                        }
+                                    });
+                                }
+                                for target in macro_commands {
+                                    ui.horizontal(|ui| {
+                                        ui.label(tr_args(
+                                            "{} on",
+                                            &[&macro_command_name(target.command)],
+                                        ));
+                                        object_link(ui, target.object);
+                                    });
+                                }
+                                for target in targeted_by {
+                                    ui.horizontal(|ui| {
+                                        ui.label(tr_args(
+                                            "Changed by {} of",
+                                            &[&macro_command_name(target.command)],
+                                        ));
+                                        object_link(ui, target.macro_object);
+                                    });
+                                }
+                            });
+                        }
+                    });
+            });
+
+        if !open {
+            self.show_macro_references = false;
+        }
+    }
+
+    /// The selected mask rendered for every terminal profile side by side
+    fn render_preview_matrix_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_preview_matrix = false;
+            self.preview_matrix_pools = None;
+            return;
+        };
+
+        // Reducing the colours copies the pool, so it is only done when the pool changed
+        let generation = project.pool_generation();
+        if !matches!(&self.preview_matrix_pools, Some((cached, _)) if *cached == generation) {
+            self.preview_matrix_pools = Some((generation, Vec::new()));
+        }
+        let Some((_, reduced_pools)) = &mut self.preview_matrix_pools else {
+            return;
+        };
+        for profile in &project.terminal_profiles {
+            let depth = profile.colour_depth;
+            if depth != ColourDepth::Colours256 && !reduced_pools.iter().any(|(d, _)| *d == depth) {
+                reduced_pools.push((depth, depth.reduce_colours(project.get_pool())));
+            }
+        }
+        let reduced_pools = &*reduced_pools;
+
+        let mut open = true;
+        egui::Window::new(tr("Preview matrix"))
+            .open(&mut open)
+            .resizable(true)
+            .default_size([900.0, 500.0])
+            .show(ctx, |ui| {
+                let pool = project.get_pool();
+                // The selected mask, or the active mask of the working set
+                let selected = project
+                    .get_selected()
+                    .0
+                    .and_then(|id| pool.object_by_id(id));
+                let mask = selected
+                    .filter(|o| {
+                        matches!(
+                            o.object_type(),
+                            ObjectType::DataMask | ObjectType::AlarmMask
+                        )
+                    })
+                    .or_else(|| {
+                        pool.working_set_object()
+                            .and_then(|ws| pool.object_by_id(ws.active_mask))
+                    });
+                let Some(mask) = mask else {
+                    ui.label(tr("Select a data or alarm mask"));
+                    return;
+                };
+                ui.label(tr_args(
+                    "{} for {} terminal profiles",
+                    &[
+                        &(project.get_object_info(mask).get_name(mask)),
+                        &project.terminal_profiles.len(),
+                    ],
+                ));
+                ui.separator();
+
+                let project_soft_key_size = project.soft_key_size;
+                egui::ScrollArea::both().show(ui, |ui| {
+                    ui.horizontal_top(|ui| {
+                        for profile in &project.terminal_profiles {
+                            let mask_size = profile.mask_size.unwrap_or(project.mask_size) as f32;
+                            let soft_key_size =
+                                profile.soft_key_size.unwrap_or(project_soft_key_size);
+                            let key_count =
+                                profile.soft_key_count.unwrap_or(DEFAULT_SOFT_KEY_COUNT) as usize;
+                            let reduced = reduced_pools
+                                .iter()
+                                .find(|(depth, _)| *depth == profile.colour_depth)
+                                .map_or(pool, |(_, reduced)| reduced);
+                            ui.vertical(|ui| {
+                                ui.strong(&profile.name);
+                                ui.label(tr_args(
+                                    "{}x{}, {}, {} soft keys of {}x{}",
+                                    &[
+                                        &mask_size,
+                                        &mask_size,
+                                        &tr(profile.colour_depth.label()),
+                                        &key_count,
+                                        &soft_key_size.0,
+                                        &soft_key_size.1,
+                                    ],
+                                ));
+                                let Some(reduced_mask) = reduced.object_by_id(mask.id()) else {
+                                    return;
+                                };
+                                let renderer = InteractiveMaskRenderer {
+                                    object: reduced_mask,
+                                    pool: reduced,
+                                    selected_callback: Box::new(|object_id| {
+                                        project
+                                            .get_mut_selected()
+                                            .replace(NullableObjectId(Some(object_id)));
+                                    }),
+                                };
+                                // The soft keys of this terminal, without changing the preview
+                                ui.scope_builder(
+                                    egui::UiBuilder::new()
+                                        .ui_stack_info(designator_size_info(soft_key_size)),
+                                    |ui| {
+                                        render_terminal_frame(
+                                            ui,
+                                            reduced,
+                                            reduced_mask,
+                                            TerminalFrame::SideColumn,
+                                            key_count,
+                                            mask_size,
+                                            renderer,
+                                        );
+                                    },
+                                );
+                            });
+                        }
+                    });
+                });
+            });
+
+        if !open {
+            self.show_preview_matrix = false;
+            self.preview_matrix_pools = None;
+        }
+    }
+
+    fn render_pointing_events_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_pointing_events = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Pointing events"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let events = with_preview_state(ctx, |state| state.pointing_events.clone());
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Clear")).clicked() {
+                        with_preview_state(ctx, |state| state.pointing_events.clear());
+                    }
+                    if preview_mode(ctx) != PreviewMode::Simulate {
+                        ui.weak(tr(
+                            "Switch the preview to simulate mode and click on the mask",
+                        ));
+                    }
+                });
+                ui.separator();
+
+                let pool = project.get_pool();
+                let object_link = |ui: &mut egui::Ui, id: ObjectId| {
+                    let label = match pool.object_by_id(id) {
+                        Some(o) => format!(
+                            "{:?} {} ({})",
+                            o.object_type(),
+                            id.value(),
+                            project.get_object_info(o).get_name(o)
+                        ),
+                        None => tr_args("Missing object {}", &[&id.value()]),
+                    };
+                    if ui.link(label).clicked() {
+                        project
+                            .get_mut_selected()
+                            .replace(NullableObjectId(Some(id)));
+                    }
+                };
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        for (index, event) in events.iter().enumerate() {
+                            ui.horizontal_wrapped(|ui| {
+                                ui.monospace(format!(
+                                    "{:?} x={} y={}",
+                                    event.state, event.x, event.y
+                                ));
+                                for (index, id) in event.chain.iter().enumerate() {
+                                    if index > 0 {
+                                        ui.label("\u{203A}");
+                                    }
+                                    object_link(ui, *id);
+                                }
+                            });
+                            if !event.covered.is_empty() {
+                                ui.indent(index, |ui| {
+                                    ui.horizontal_wrapped(|ui| {
+                                        ui.weak(tr("Also under the pointer:"));
+                                        for id in &event.covered {
+                                            object_link(ui, *id);
+                                        }
+                                    });
+                                });
+                            }
+                        }
+                    });
+            });
+
+        if !open {
+            self.show_pointing_events = false;
+        }
+    }
+
+    fn render_navigation_map_window(&mut self, ctx: &egui::Context) {
+        const NODE_SIZE: egui::Vec2 = egui::vec2(150.0, 36.0);
+        const NODE_SPACING: egui::Vec2 = egui::vec2(70.0, 16.0);
+
+        let Some(project) = &self.project else {
+            self.show_navigation_map = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Navigation map"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(800.0)
+            .default_height(500.0)
+            .show(ctx, |ui| {
+                let pool = project.get_pool();
+                let map = navigation_map(pool);
+                if map.masks.is_empty() {
+                    ui.label(tr("There are no masks in this pool"));
+                    return;
+                }
+                let depths = map.depths();
+                let unreachable = map.unreachable();
+                let dead_ends = map.dead_ends();
+                let mask_name = |id: ObjectId| {
+                    pool.object_by_id(id)
+                        .map(|o| project.get_object_info(o).get_name(o))
+                        .unwrap_or_else(|| tr_args("Missing object {}", &[&id.value()]))
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(tr_args(
+                        "{} masks, {} links",
+                        &[&map.masks.len(), &map.links.len()],
+                    ));
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        tr_args("{} unreachable", &[&unreachable.len()]),
+                    );
+                    ui.colored_label(
+                        egui::Color32::ORANGE,
+                        tr_args("{} dead ends", &[&dead_ends.len()]),
+                    );
+                });
+                ui.weak(
+                    "Columns are the number of mask changes from the active mask of the working \
+                     set, unreachable masks are in the last column",
+                );
+                ui.separator();
+
+                // Reachable masks by their distance from the start, unreachable ones last
+                let mut columns: Vec<Vec<ObjectId>> = Vec::new();
+                for (mask, depth) in &depths {
+                    if columns.len() <= *depth {
+                        columns.resize(depth + 1, Vec::new());
+                    }
+                    columns[*depth].push(*mask);
+                }
+                if !unreachable.is_empty() {
+                    columns.push(unreachable.clone());
+                }
+                let rows = columns.iter().map(Vec::len).max().unwrap_or(0);
+                let size = egui::vec2(
+                    columns.len() as f32 * (NODE_SIZE.x + NODE_SPACING.x),
+                    rows as f32 * (NODE_SIZE.y + NODE_SPACING.y),
+                );
+
+                egui::ScrollArea::both().max_height(350.0).show(ui, |ui| {
+                    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+                    let mut nodes = HashMap::new();
+                    for (column, masks) in columns.iter().enumerate() {
+                        for (row, mask) in masks.iter().enumerate() {
+                            let min = rect.min
+                                + egui::vec2(
+                                    column as f32 * (NODE_SIZE.x + NODE_SPACING.x),
+                                    row as f32 * (NODE_SIZE.y + NODE_SPACING.y),
+                                );
+                            nodes.insert(*mask, egui::Rect::from_min_size(min, NODE_SIZE));
+                        }
+                    }
+
+                    let painter = ui.painter();
+                    let edge = egui::Stroke::new(1.5, ui.visuals().weak_text_color());
+                    let mut drawn = HashSet::new();
+                    for link in &map.links {
+                        let (Some(from), Some(to)) = (nodes.get(&link.from), nodes.get(&link.to))
+                        else {
+                            continue;
+                        };
+                        if link.from == link.to || !drawn.insert((link.from, link.to)) {
+                            continue;
+                        }
+                        let (start, end) = if to.center().x > from.center().x {
+                            (from.right_center(), to.left_center())
+                        } else {
+                            (from.center_bottom(), to.center_bottom())
+                        };
+                        painter.arrow(start, end - start, edge);
+                    }
+
+                    for (mask, node) in &nodes {
+                        let response = ui.interact(
+                            *node,
+                            ui.id().with(("navigation_node", mask.value())),
+                            egui::Sense::click(),
+                        );
+                        let border = if unreachable.contains(mask) {
+                            egui::Color32::RED
+                        } else if dead_ends.contains(mask) {
+                            egui::Color32::ORANGE
+                        } else {
+                            ui.visuals().widgets.noninteractive.fg_stroke.color
+                        };
+                        let fill = if Some(*mask) == map.start {
+                            ui.visuals().selection.bg_fill
+                        } else if response.hovered() {
+                            ui.visuals().widgets.hovered.bg_fill
+                        } else {
+                            ui.visuals().extreme_bg_color
+                        };
+                        let painter = ui.painter();
+                        painter.rect_filled(*node, 4.0, fill);
+                        painter.rect_stroke(
+                            *node,
+                            4.0,
+                            egui::Stroke::new(1.5, border),
+                            egui::StrokeKind::Inside,
+                        );
+                        let kind = match pool.object_by_id(*mask).map(|o| o.object_type()) {
+                            Some(ObjectType::AlarmMask) => tr("Alarm mask"),
+                            _ => tr("Data mask"),
+                        };
+                        painter.text(
+                            node.center(),
+                            egui::Align2::CENTER_CENTER,
+                            format!("{}\n{} {}", mask_name(*mask), kind, mask.value()),
+                            egui::FontId::proportional(11.0),
+                            ui.visuals().text_color(),
+                        );
+                        if response.clicked() {
+                            project
+                                .get_mut_selected()
+                                .replace(NullableObjectId(Some(*mask)));
+                        }
+                    }
+                });
+
+                ui.separator();
+                egui::CollapsingHeader::new(tr_args("Links ({})", &[&map.links.len()]))
+                    .id_salt("navigation_links")
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .id_salt("navigation_links_scroll")
+                            .max_height(200.0)
+                            .show(ui, |ui| {
+                                for link in &map.links {
+                                    ui.horizontal(|ui| {
+                                        ui.label(format!(
+                                            "{} \u{2192} {}",
+                                            mask_name(link.from),
+                                            mask_name(link.to)
+                                        ));
+                                        if ui
+                                            .link(tr_args(
+                                                "{} {}, macro {}",
+                                                &[
+                                                    &mask_name(link.trigger),
+                                                    &format!("{:?}", link.event),
+                                                    &link.macro_id,
+                                                ],
+                                            ))
+                                            .clicked()
+                                        {
+                                            project
+                                                .get_mut_selected()
+                                                .replace(NullableObjectId(Some(link.trigger)));
+                                        }
+                                    });
+                                }
+                            });
+                    });
+            });
+
+        if !open {
+            self.show_navigation_map = false;
+        }
+    }
+
+    fn render_similar_objects_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_similar_objects = false;
+            return;
+        };
+
+        let mut open = true;
+        let mut merge = None;
+        egui::Window::new(tr("Similar objects"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(600.0)
+            .show(ctx, |ui| {
+                let pool = project.get_pool();
+                let name = |id: ObjectId| {
+                    pool.object_by_id(id)
+                        .map(|o| {
+                            format!(
+                                "{} ({})",
+                                project.get_object_info(o).get_name(o),
+                                id.value()
+                            )
+                        })
+                        .unwrap_or_else(|| tr_args("Missing object {}", &[&id.value()]))
+                };
+
+                // Scan again when the pool changed, so merged groups are still duplicates
+                let generation = project.pool_generation();
+                ui.horizontal(|ui| {
+                    let scanned = matches!(
+                        &self.similar_objects,
+                        Some((cached, _)) if *cached == generation
+                    );
+                    if ui.button(tr("Scan pool")).clicked() || !scanned {
+                        self.similar_objects = Some((generation, find_similar_objects(pool)));
+                    }
+                    ui.weak(tr(
+                        "Objects that are the same apart from their IDs, values or strings",
+                    ));
+                });
+                let Some((_, groups)) = &self.similar_objects else {
+                    return;
+                };
+                let exact = groups.iter().filter(|g| g.is_exact()).count();
+                ui.label(tr_args(
+                    "{} exact duplicate groups, {} near-identical groups",
+                    &[&exact, &(groups.len() - exact)],
+                ));
+                ui.separator();
+                if groups.is_empty() {
+                    ui.label(tr("No similar objects found"));
+                    return;
+                }
+
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    for (index, group) in groups.iter().enumerate() {
+                        let kind = if group.is_exact() {
+                            tr("Exact duplicates")
+                        } else {
+                            tr("Near-identical")
+                        };
+                        egui::CollapsingHeader::new(tr_args(
+                            "{}: {} \u{d7} {} objects, {}% similar",
+                            &[
+                                &kind,
+                                &group.objects.len(),
+                                &group.subtree_size,
+                                &format!("{:.0}", group.similarity * 100.0),
+                            ],
+                        ))
+                        .id_salt(("similar_group", index))
+                        .show(ui, |ui| {
+                            ui.horizontal_wrapped(|ui| {
+                                for id in &group.objects {
+                                    if ui.link(name(*id)).clicked() {
+                                        project
+                                            .get_mut_selected()
+                                            .replace(NullableObjectId(Some(*id)));
+                                    }
+                                }
+                            });
+                            if group.is_exact() {
+                                ui.weak(
+                                    "These subtrees are identical. Referencing the first one \
+                                     everywhere removes the copies from the pool.",
+                                );
+                                if ui.button(tr("Merge into first")).clicked() {
+                                    merge = Some(group.objects.clone());
+                                    ui.close();
+                                }
+                                return;
+                            }
+                            ui.weak(
+                                "These subtrees only differ in values and strings. Consider \
+                                 keeping one copy and showing it through an object pointer, or \
+                                 sharing the children that are equal and only keeping the \
+                                 differing objects separate.",
+                            );
+                            for difference in &group.differences {
+                                ui.horizontal_wrapped(|ui| {
+                                    for id in [difference.objects.0, difference.objects.1] {
+                                        if ui.link(name(id)).clicked() {
+                                            project
+                                                .get_mut_selected()
+                                                .replace(NullableObjectId(Some(id)));
+                                        }
+                                    }
+                                    ui.label(format!(
+                                        "{}: {} \u{2192} {}",
+                                        difference.attribute,
+                                        difference.values.0,
+                                        difference.values.1
+                                    ));
+                                });
+                            }
+                        });
+                    }
+                });
+            });
+
+        if let Some(objects) = merge {
+            merge_duplicates(project, objects[0], &objects[1..]);
+            self.similar_objects = None;
+        }
+        if !open {
+            self.show_similar_objects = false;
+        }
+    }
+
+    /// TODO and FIXME items from the notes and review comments of all objects
+    fn render_tasks_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_tasks = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Tasks"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let tasks = project_tasks(project);
+                ui.horizontal(|ui| {
+                    ui.selectable_value(&mut self.task_tag_filter, None, tr("All"));
+                    for tag in TaskTag::ALL {
+                        ui.selectable_value(&mut self.task_tag_filter, Some(tag), tag.label());
+                    }
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.task_filter)
+                            .hint_text(tr("Filter...")),
+                    );
+                });
+                let filter = self.task_filter.to_lowercase();
+                let pool = project.get_pool();
+                let shown: Vec<_> = tasks
+                    .iter()
+                    .filter(|task| self.task_tag_filter.is_none_or(|tag| tag == task.tag))
+                    .map(|task| {
+                        let name = pool
+                            .object_by_id(task.object)
+                            .map(|o| project.get_object_info(o).get_name(o))
+                            .unwrap_or_default();
+                        (task, name)
+                    })
+                    .filter(|(task, name)| {
+                        filter.is_empty()
+                            || task.text.to_lowercase().contains(&filter)
+                            || name.to_lowercase().contains(&filter)
+                    })
+                    .collect();
+                ui.label(tr_args("{} of {} tasks", &[&shown.len(), &tasks.len()]));
+                ui.separator();
+                if tasks.is_empty() {
+                    ui.label(tr(
+                        "No TODO or FIXME items in the object notes and review comments",
+                    ));
+                    return;
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("tasks_grid").striped(true).show(ui, |ui| {
+                            for (task, name) in shown {
+                                let colour = match task.tag {
+                                    TaskTag::Todo => egui::Color32::YELLOW,
+                                    TaskTag::Fixme => egui::Color32::RED,
+                                };
+                                ui.colored_label(colour, task.tag.label());
+                                if ui.link(name).clicked() {
+                                    project
+                                        .get_mut_selected()
+                                        .replace(NullableObjectId(Some(task.object)));
+                                }
+                                ui.weak(tr(task.source.label()));
+                                ui.label(&task.text);
+                                ui.end_row();
+                            }
+                        });
+                    });
+            });
+
+        if !open {
+            self.show_tasks = false;
+        }
+    }
+
+    /// Pools of the workspace, the objects they share and the consistency between them
+    fn render_workspace_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_workspace = false;
+            return;
+        };
+
+        let mut open = true;
+        let mut switch_to = None;
+        let mut add_pool = false;
+        egui::Window::new(tr("Workspace"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let Some(workspace) = &mut self.workspace else {
+                    ui.label(
+                        "A workspace groups the related pools of a product, e.g. the main \
+                         implement pool, an auxiliary control pool and a low resolution variant",
+                    );
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Name:"));
+                        ui.text_edit_singleline(&mut self.workspace_name);
+                    });
+                    if ui
+                        .button(tr("Create workspace from this project"))
+                        .clicked()
+                    {
+                        match Workspace::new(self.workspace_name.clone(), project) {
+                            Ok(workspace) => self.workspace = Some(workspace),
+                            Err(e) => log::error!("{}", e),
+                        }
+                    }
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("Name:"));
+                    ui.text_edit_singleline(&mut workspace.name);
+                });
+                ui.separator();
+
+                ui.strong(tr("Pools"));
+                let mut remove = None;
+                egui::Grid::new("workspace_members").show(ui, |ui| {
+                    for (index, member) in workspace.members.iter_mut().enumerate() {
+                        ui.text_edit_singleline(&mut member.name);
+                        if index == workspace.active {
+                            ui.label(tr("Open"));
+                        } else {
+                            if ui.button(tr("Open")).clicked() {
+                                switch_to = Some(index);
+                            }
+                            if ui.button(tr("Remove")).clicked() {
+                                remove = Some(index);
+                            }
+                        }
+                        ui.end_row();
+                    }
+                });
+                if let Some(index) = remove {
+                    workspace.remove_member(index);
+                }
+                add_pool = ui.button(tr("Add pool or project...")).clicked();
+                ui.separator();
+
+                ui.strong(tr("Shared objects"));
+                ui.weak(
+                    "Templates and other shared concepts that must have the same ID and \
+                     definition in every pool. Colour roles are always shared.",
+                );
+                let pool = project.get_pool();
+                let mut unshare = None;
+                for id in workspace.shared_ids() {
+                    ui.horizontal(|ui| {
+                        let label = pool
+                            .object_by_id(id)
+                            .map(|o| project.get_object_info(o).get_name(o))
+                            .unwrap_or_else(|| tr_args("Missing object {}", &[&id.value()]));
+                        if ui.link(label).clicked() {
+                            project
+                                .get_mut_selected()
+                                .replace(NullableObjectId(Some(id)));
+                        }
+                        if ui.small_button("\u{2716}").clicked() {
+                            unshare = Some(id.value());
+                        }
+                    });
+                }
+                if let Some(id) = unshare {
+                    workspace.shared_objects.retain(|shared| *shared != id);
+                }
+                if let Some(selected) = project.get_selected().0 {
+                    if !workspace.shared_objects.contains(&selected.value())
+                        && ui.button(tr("Share the selected object")).clicked()
+                    {
+                        workspace.shared_objects.push(selected.value());
+                    }
+                }
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Check consistency")).clicked() {
+                        self.workspace_problems = Some(workspace.check_consistency(project));
+                    }
+                    if ui
+                        .button(tr("Copy shared objects to all pools"))
+                        .on_hover_text(tr("Replace the shared objects in the other pools"))
+                        .clicked()
+                    {
+                        match workspace.sync_shared_objects(project) {
+                            Ok(changed) => log::info!("Updated {} pools", changed),
+                            Err(e) => log::error!("{}", e),
+                        }
+                        self.workspace_problems = Some(workspace.check_consistency(project));
+                    }
+                });
+                match &self.workspace_problems {
+                    Some(problems) if problems.is_empty() => {
+                        ui.label(tr("The pools are consistent"));
+                    }
+                    Some(problems) => {
+                        for problem in problems {
+                            ui.colored_label(egui::Color32::YELLOW, problem);
+                        }
+                    }
+                    None => {}
+                }
+            });
+
+        if add_pool {
+            self.open_file_dialog(FileDialogReason::AddWorkspacePool, ctx);
+        }
+        if let (Some(index), Some(workspace), Some(project)) =
+            (switch_to, &mut self.workspace, &self.project)
+        {
+            match workspace.switch_to(project, index) {
+                Ok(project) => {
+                    self.project = Some(project);
+                    self.workspace_problems = None;
+                }
+                Err(e) => log::error!("Failed to open pool: {}", e),
+            }
+        }
+        if !open {
+            self.show_workspace = false;
+        }
+    }
+
+    /// Options and report of the minified pool export
+    fn render_minify_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_minify = false;
+            return;
+        };
+
+        let mut open = true;
+        let mut export = false;
+        egui::Window::new(tr("Export minified IOP"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let options = &mut self.minify_options;
+                let mut changed = false;
+                changed |= ui
+                    .checkbox(&mut options.remove_unused, tr("Remove unused objects"))
+                    .on_hover_text(
+                        "Objects that are not referenced by a mask, macro or other used object. \
+                         Bookmarked objects are always kept.",
+                    )
+                    .changed();
+                changed |= ui
+                    .checkbox(
+                        &mut options.deduplicate_attributes,
+                        tr("Merge duplicate attribute objects"),
+                    )
+                    .changed();
+                changed |= ui
+                    .checkbox(
+                        &mut options.compress_pictures,
+                        tr("Run-length encode pictures where smaller"),
+                    )
+                    .changed();
+                ui.weak(tr(
+                    "Names, notes and other editor data are never part of the exported pool",
+                ));
+                if changed {
+                    self.minify_report = None;
+                }
+
+                let report = self.minify_report.get_or_insert_with(|| {
+                    let mut options = options.clone();
+                    options.keep.extend(project.get_bookmarks());
+                    minify_pool(project.get_pool(), &options).1
+                });
+                ui.separator();
+                ui.strong(tr_args(
+                    "{} bytes \u{2192} {} bytes, {} bytes saved",
+                    &[
+                        &report.original_size,
+                        &report.minified_size,
+                        &report.bytes_saved(),
+                    ],
+                ));
+
+                let pool = project.get_pool();
+                let name = |id: ObjectId| {
+                    pool.object_by_id(id)
+                        .map(|o| project.get_object_info(o).get_name(o))
+                        .unwrap_or_else(|| tr_args("Object {}", &[&id.value()]))
+                };
+                let select = |id: ObjectId| {
+                    project
+                        .get_mut_selected()
+                        .replace(NullableObjectId(Some(id)));
+                };
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::CollapsingHeader::new(tr_args(
+                            "Removed unused objects ({})",
+                            &[&report.removed.len()],
+                        ))
+                        .show(ui, |ui| {
+                            for id in &report.removed {
+                                if ui.link(name(*id)).clicked() {
+                                    select(*id);
+                                }
+                            }
+                        });
+                        egui::CollapsingHeader::new(tr_args(
+                            "Merged attribute objects ({})",
+                            &[&report.merged.len()],
+                        ))
+                        .show(ui, |ui| {
+                            for (duplicate, kept) in &report.merged {
+                                ui.horizontal(|ui| {
+                                    if ui.link(name(*duplicate)).clicked() {
+                                        select(*duplicate);
+                                    }
+                                    ui.label("\u{2192}");
+                                    if ui.link(name(*kept)).clicked() {
+                                        select(*kept);
+                                    }
+                                });
+                            }
+                        });
+                        egui::CollapsingHeader::new(tr_args(
+                            "Compressed pictures ({})",
+                            &[&report.compressed.len()],
+                        ))
+                        .show(ui, |ui| {
+                            for (id, saved) in &report.compressed {
+                                ui.horizontal(|ui| {
+                                    if ui.link(name(*id)).clicked() {
+                                        select(*id);
+                                    }
+                                    ui.label(tr_args("{} bytes saved", &[&saved]));
+                                });
+                            }
+                        });
+                    });
+                ui.separator();
+                export = ui.button(tr("Export...")).clicked();
+            });
+
+        if export {
+            self.save_minified_pool(ctx);
+        }
+        if !open {
+            self.show_minify = false;
+        }
+    }
+
+    /// Version label of the pool and the version messages the VT client sends with it
+    fn render_pool_version_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &mut self.project else {
+            self.show_pool_version = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Pool version"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let version = &mut project.pool_version;
+                ui.horizontal(|ui| {
+                    ui.label(tr("Version label:"));
+                    ui.text_edit_singleline(&mut version.label);
+                    let limit = if version.is_extended() {
+                        EXTENDED_VERSION_LABEL_LENGTH
+                    } else {
+                        VERSION_LABEL_LENGTH
+                    };
+                    ui.weak(format!("{}/{}", version.label.chars().count(), limit));
+                    if ui.button(tr("Bump version")).clicked() {
+                        version.bump();
+                    }
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("VT version:"));
+                    ui.add(egui::DragValue::new(&mut version.vt_version).range(2..=6));
+                });
+                ui.checkbox(
+                    &mut version.bump_on_export,
+                    tr("Bump the version when a changed pool is exported"),
+                );
+                for problem in version.problems() {
+                    ui.colored_label(egui::Color32::YELLOW, problem);
+                }
+                ui.separator();
+
+                ui.strong(tr("Messages of the VT client"));
+                ui.weak(
+                    "The terminal loads the pool from its cache when it has a pool stored \
+                     under the same label, otherwise the pool is uploaded and stored",
+                );
+                egui::Grid::new("pool_version_messages")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for message in version.messages() {
+                            ui.label(message.name);
+                            ui.monospace(
+                                message
+                                    .data
+                                    .iter()
+                                    .map(|byte| format!("{:02X}", byte))
+                                    .collect::<Vec<_>>()
+                                    .join(" "),
+                            );
+                            ui.end_row();
+                        }
+                    });
+                ui.separator();
+                ui.label("AgIsoStack++:");
+                ui.monospace(format!(
+                    "virtualTerminalClient->set_object_pool(0, pool.data(), pool.size(), \"{}\");",
+                    version.label
+                ));
+            });
+
+        if !open {
+            self.show_pool_version = false;
+        }
+    }
+
+    /// Change the shortcut of the action that is being recorded to the pressed keys, Escape
+    /// cancels
+    fn record_shortcut(&mut self, ctx: &egui::Context) {
+        let Some(action) = self.recording_shortcut else {
+            return;
+        };
+        let pressed = ctx.input_mut(|i| {
+            let pressed = i.events.iter().find_map(|event| match event {
+                egui::Event::Key {
+                    key,
+                    pressed: true,
+                    modifiers,
+                    ..
+                } => Some((*key, *modifiers)),
+                _ => None,
+            });
+            if pressed.is_some() {
+                i.events.retain(|event| {
+                    !matches!(event, egui::Event::Key { .. } | egui::Event::Text(_))
+                });
+            }
+            pressed
+        });
+        match pressed {
+            Some((egui::Key::Escape, _)) => self.recording_shortcut = None,
+            Some((key, modifiers)) => {
+                // Command follows Ctrl or Cmd depending on the platform, the physical key is kept
+                let modifiers = egui::Modifiers {
+                    command: false,
+                    ..modifiers
+                };
+                for (name, shortcut) in self.settings.key_bindings.actions_mut() {
+                    if name == action {
+                        *shortcut = egui::KeyboardShortcut::new(modifiers, key);
+                    }
+                }
+                self.recording_shortcut = None;
+            }
+            None => (),
+        }
+    }
+
+    /// Settings of the designer that are not in the settings menu, and the settings file
+    fn render_settings_window(&mut self, ctx: &egui::Context) {
+        self.record_shortcut(ctx);
+
+        let mut open = true;
+        let mut import = false;
+        let mut export = false;
+        let mut reset = false;
+        egui::Window::new(tr("Designer settings"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                #[cfg(not(target_arch = "wasm32"))]
+                match &self.settings_path {
+                    Some(path) => ui.weak(tr_args("Saved in {}", &[&path.display()])),
+                    None => ui.weak(tr("The settings are not saved")),
+                };
+                #[cfg(target_arch = "wasm32")]
+                ui.weak(tr(
+                    "The browser does not keep the settings, export them to keep them.",
+                ));
+                ui.horizontal(|ui| {
+                    import = ui.button(tr("Import...")).clicked();
+                    export = ui.button(tr("Export...")).clicked();
+                    reset = ui
+                        .button(tr("Reset to defaults"))
+                        .on_hover_text(tr("Reset the settings to their defaults"))
+                        .clicked();
+                });
+                if let Some(status) = &self.settings_status {
+                    ui.label(status);
+                }
+
+                ui.separator();
+                ui.heading(tr("Key bindings"));
+                let mut defaults = DesignerSettings::default().key_bindings;
+                egui::Grid::new("key_bindings")
+                    .num_columns(3)
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for ((name, shortcut), (_, default)) in self
+                            .settings
+                            .key_bindings
+                            .actions_mut()
+                            .into_iter()
+                            .zip(defaults.actions_mut())
+                        {
+                            ui.label(tr(name));
+                            let text = if self.recording_shortcut == Some(name) {
+                                tr("Press a shortcut...").to_string()
+                            } else {
+                                ctx.format_shortcut(shortcut)
+                            };
+                            if ui
+                                .button(text)
+                                .on_hover_text(tr(
+                                    "Click and press the new shortcut, Escape cancels",
+                                ))
+                                .clicked()
+                            {
+                                self.recording_shortcut = Some(name);
+                            }
+                            if ui
+                                .add_enabled(shortcut != default, egui::Button::new("\u{21BA}"))
+                                .on_hover_text(tr_args(
+                                    "Reset to {}",
+                                    &[&ctx.format_shortcut(default)],
+                                ))
+                                .clicked()
+                            {
+                                *shortcut = *default;
+                            }
+                            ui.end_row();
+                        }
+                    });
+                for (first, second) in self.settings.key_bindings.conflicts() {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        tr_args(
+                            "'{}' and '{}' have the same shortcut",
+                            &[&tr(first), &tr(second)],
+                        ),
+                    );
+                }
+
+                ui.separator();
+                ui.heading(tr("Export"));
+                ui.checkbox(
+                    &mut self.minify_options.remove_unused,
+                    tr("Minified exports remove unused objects"),
+                );
+                ui.checkbox(
+                    &mut self.minify_options.deduplicate_attributes,
+                    tr("Minified exports merge equal attribute objects"),
+                );
+                ui.checkbox(
+                    &mut self.minify_options.compress_pictures,
+                    tr("Minified exports compress picture graphics"),
+                );
+                ui.checkbox(
+                    &mut self.settings.apply_smart_naming_on_import,
+                    tr("Apply smart naming on import"),
+                );
+
+                ui.separator();
+                ui.heading(tr("Terminal profiles"));
+                ui.weak(tr("Imported pools start with these terminal profiles."));
+                if self.settings.terminal_profiles.is_empty() {
+                    ui.label(tr("The built-in default terminal"));
+                }
+                for profile in &self.settings.terminal_profiles {
+                    ui.label(format!("\u{2022} {}", profile.name));
+                }
+                ui.horizontal(|ui| {
+                    if let Some(project) = &self.project {
+                        if ui
+                            .button(tr("Use the profiles of the open project"))
+                            .on_hover_text(tr(
+                                "Custom fonts are not stored in the settings, they stay in the \
+                                 project",
+                            ))
+                            .clicked()
+                        {
+                            self.settings.terminal_profiles = project
+                                .terminal_profiles
+                                .iter()
+                                .map(TerminalProfile::without_fonts)
+                                .collect();
+                        }
+                    }
+                    if !self.settings.terminal_profiles.is_empty()
+                        && ui.button(tr("Use the built-in default")).clicked()
+                    {
+                        self.settings.terminal_profiles.clear();
+                    }
+                });
+            });
+        if !open {
+            self.show_settings = false;
+            self.recording_shortcut = None;
+        }
+        if import {
+            self.open_file_dialog(FileDialogReason::ImportSettings, ctx);
+        }
+        if export {
+            self.export_settings();
+        }
+        if reset {
+            self.apply_settings(DesignerSettings::default(), ctx);
+            self.settings_status =
+                Some(tr("The settings were reset to their defaults").to_string());
+        }
+    }
+
+    /// How the project file is migrated and which format version it is saved in
+    fn render_file_format_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        egui::Window::new(tr("Project file format"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let Some(project) = &mut self.project else {
+                    ui.weak(tr("Open a project to see its file format."));
+                    return;
+                };
+                let format = &mut project.file_format;
+                let written_by = format
+                    .designer_version
+                    .as_ref()
+                    .map_or(String::new(), |version| {
+                        tr_args(", written by designer {}", &[version])
+                    });
+                ui.label(tr_args(
+                    "Loaded file format: version {}{}",
+                    &[&format.loaded_version, &written_by],
+                ));
+                ui.label(tr_args(
+                    "Format of this designer: version {}",
+                    &[&PROJECT_FILE_VERSION],
+                ));
+
+                if format.is_newer() {
+                    ui.separator();
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        tr(
+                            "The project was saved by a newer designer. Saving it here drops \
+                            what this designer does not know, so it was opened read-only.",
+                        ),
+                    );
+                    for feature in &format.unknown_features {
+                        ui.label(tr_args("\u{2022} Unknown annotations: {}", &[feature]));
+                    }
+                    let allow = ui
+                        .add_enabled(
+                            project.is_read_only(),
+                            egui::Button::new(tr("Allow editing anyway")),
+                        )
+                        .on_hover_text(tr("Saving writes the format of this designer"));
+                    if allow.clicked() {
+                        project.set_read_only(false);
+                    }
+                    return;
+                }
+
+                if !format.migrations.is_empty() {
+                    ui.separator();
+                    ui.label(tr("The project was upgraded when it was opened:"));
+                    for migration in &format.migrations {
+                        ui.label(format!("\u{2022} {}", migration));
+                    }
+                }
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(tr("Save as:"));
+                    for version in OLDEST_PROJECT_FILE_VERSION..=PROJECT_FILE_VERSION {
+                        let text = if version == PROJECT_FILE_VERSION {
+                            tr_args("Version {} (current)", &[&version])
+                        } else {
+                            tr_args("Version {}", &[&version])
+                        };
+                        ui.radio_value(&mut format.save_version, version, text);
+                    }
+                });
+                let losses = ProjectFileFormat::downgrade_losses(format.save_version);
+                if losses.is_empty() {
+                    ui.weak(tr(
+                        "Older designers may not be able to open the saved project.",
+                    ));
+                } else {
+                    ui.weak(tr(
+                        "For older designers, saving in this version leaves out:",
+                    ));
+                    for loss in losses {
+                        ui.weak(format!("\u{2022} {}", loss));
+                    }
+                }
+            });
+        if !open {
+            self.show_file_format = false;
+        }
+    }
+
+    /// Browse the backups of the last saved or opened file and restore one of them
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_backups_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut refresh = false;
+        let mut restore = None;
+        egui::Window::new(tr("Backups"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let Some(source) = &self.backup_source else {
+                    ui.weak(tr("Save or open a pool or project to see its backups."));
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    ui.label(tr_args("Folder: {}", &[&backup_dir(source).display()]));
+                    refresh = ui.button(tr("Refresh")).clicked();
+                });
+                if self.settings.backup_count == 0 {
+                    ui.colored_label(
+                        egui::Color32::YELLOW,
+                        tr("Backups are turned off in the settings"),
+                    );
+                } else {
+                    ui.weak(tr_args(
+                        "The newest {} backups of every file are kept. Restoring replaces the \
+                         pool as a single change, which can be undone. The names and other \
+                         metadata restored from a project backup stay when it is undone.",
+                        &[&self.settings.backup_count],
+                    ));
+                }
+                ui.separator();
+
+                if self.backups.is_empty() {
+                    ui.label(tr("No backups yet"));
+                }
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("backups")
+                        .num_columns(4)
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for backup in &self.backups {
+                                ui.label(backup.saved_at());
+                                ui.label(backup.file_name());
+                                ui.label(format_bytes(backup.size as usize));
+                                let button = ui
+                                    .add_enabled(
+                                        self.project.is_some(),
+                                        egui::Button::new(tr("Restore")),
+                                    )
+                                    .on_disabled_hover_text(tr("Open a project to restore into"));
+                                if button.clicked() {
+                                    restore = Some(backup.clone());
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+                if let Some(status) = &self.backup_status {
+                    ui.separator();
+                    ui.label(status);
+                }
+            });
+        if !open {
+            self.show_backups = false;
+        }
+        if refresh {
+            self.refresh_backups();
+        }
+        if let (Some(backup), Some(project)) = (restore, &self.project) {
+            self.backup_status = Some(match restore_backup(project, &backup) {
+                Ok(()) => tr_args(
+                    "Restored {} from {}",
+                    &[&backup.file_name(), &backup.saved_at()],
+                ),
+                Err(e) => e,
+            });
+        }
+    }
+
+    /// Pools stored by a simulated terminal, to show when it would load a cached pool
+    fn render_vt_storage_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &mut self.project else {
+            self.show_vt_storage = false;
+            return;
+        };
+        let generation = project.pool_generation();
+        if !matches!(&self.vt_storage_iop, Some((cached, _)) if *cached == generation) {
+            self.vt_storage_iop = Some((generation, project.get_pool().as_iop()));
+        }
+        let Some((_, iop)) = &self.vt_storage_iop else {
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Terminal storage"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let storage = &mut self.vt_storage;
+                ui.weak(tr(
+                    "Terminals store uploaded pools under their version label. When a working \
+                     set connects with a label that is already stored, the stored pool is \
+                     loaded without comparing its content.",
+                ));
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("Version label:"));
+                    ui.text_edit_singleline(&mut project.pool_version.label);
+                    if ui.button(tr("Bump version")).clicked() {
+                        project.pool_version.bump();
+                    }
+                });
+                let outcome = storage.preview(&project.pool_version, iop);
+                let colour = match outcome {
+                    ConnectOutcome::LoadCached { stale: true, .. } => egui::Color32::RED,
+                    ConnectOutcome::LoadCached { stale: false, .. } => egui::Color32::GREEN,
+                    ConnectOutcome::Upload => ui.visuals().text_color(),
+                };
+                ui.colored_label(colour, outcome.describe());
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Connect working set")).clicked() {
+                        storage.connect(&project.pool_version, iop);
+                    }
+                    if ui.button(tr("Clear storage")).clicked() {
+                        storage.clear();
+                    }
+                });
+                ui.separator();
+
+                ui.strong(tr("Stored pools"));
+                if storage.versions.is_empty() {
+                    ui.weak(tr("No pools are stored"));
+                }
+                let mut delete = None;
+                egui::Grid::new("vt_storage_versions")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for stored in &storage.versions {
+                            ui.monospace(format!("\"{}\"", stored.label_text()));
+                            ui.label(tr_args("Upload {}", &[&stored.upload]));
+                            ui.label(tr_args("{} bytes", &[&stored.size]));
+                            if ui.button(tr("Delete")).clicked() {
+                                delete = Some(stored.label.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(label) = delete {
+                    storage.delete(&label);
+                }
+                ui.separator();
+
+                egui::CollapsingHeader::new(tr("Log"))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .max_height(200.0)
+                            .stick_to_bottom(true)
+                            .show(ui, |ui| {
+                                for line in &storage.log {
+                                    ui.label(line);
+                                }
+                            });
+                    });
+            });
+
+        if !open {
+            self.show_vt_storage = false;
+            self.vt_storage_iop = None;
+        }
+    }
+
+    /// Rename the objects of the selection or the object list with a pattern
+    fn render_bulk_rename_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_bulk_rename = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Bulk rename"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut self.rename_scope,
+                        BatchScope::Selection,
+                        tr("Selected object and its children"),
+                    );
+                    ui.radio_value(
+                        &mut self.rename_scope,
+                        BatchScope::ObjectList,
+                        tr("Objects shown in the object list"),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("Pattern:"));
+                    ui.text_edit_singleline(&mut self.rename_pattern);
+                    ui.label(tr("First number:"));
+                    ui.add(egui::DragValue::new(&mut self.rename_start));
+                })
+                .response
+                .on_hover_text(tr(
+                    "Placeholders: {n} counter, {n:3} counter with three digits, {id}, {type}, \
+                     {name} current name, {mask} mask the object is on, {parent} referencing \
+                     object",
+                ));
+                ui.separator();
+
+                let targets = self.rename_scope.objects(ctx, project);
+                let renames = match plan_renames(
+                    project,
+                    &targets,
+                    &self.rename_pattern,
+                    self.rename_start,
+                ) {
+                    Ok(renames) => renames,
+                    Err(e) => {
+                        ui.colored_label(egui::Color32::RED, e);
+                        return;
+                    }
+                };
+                let locked = targets.len() - renames.len();
+                ui.label(tr_args(
+                    "{} objects, {} locked objects keep their name",
+                    &[&renames.len(), &locked],
+                ));
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("bulk_rename_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for rename in &renames {
+                                    ui.label(rename.id.value().to_string());
+                                    ui.label(&rename.old_name);
+                                    ui.label("\u{2192}");
+                                    ui.label(&rename.new_name);
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                let conflicts = header_conflicts(project, &renames);
+                for (identifier, count) in &conflicts {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        tr_args(
+                            "{} objects would be {} in the exported header",
+                            &[count, identifier],
+                        ),
+                    );
+                }
+                ui.separator();
+                if ui
+                    .add_enabled(
+                        !renames.is_empty() && conflicts.is_empty(),
+                        egui::Button::new(tr("Rename")),
+                    )
+                    .clicked()
+                {
+                    let renamed = apply_renames(project, &renames);
+                    self.rename_status = Some(tr_args("Renamed {} objects", &[&renamed]));
+                }
+                if let Some(status) = &self.rename_status {
+                    ui.label(status);
+                }
+            });
+
+        if !open {
+            self.show_bulk_rename = false;
+        }
+    }
+
+    /// Set the font attributes of all text objects in the selection or the object list at once
+    fn render_font_batch_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_font_batch = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Set font attributes"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let pool = project.get_pool();
+                ui.horizontal(|ui| {
+                    ui.radio_value(
+                        &mut self.font_batch_scope,
+                        BatchScope::Selection,
+                        tr("Selected object and its children"),
+                    );
+                    ui.radio_value(
+                        &mut self.font_batch_scope,
+                        BatchScope::ObjectList,
+                        tr("Objects shown in the object list"),
+                    );
+                });
+                let targets: Vec<&Object> = self
+                    .font_batch_scope
+                    .objects(ctx, project)
+                    .into_iter()
+                    .filter_map(|id| pool.object_by_id(id))
+                    .filter(|object| has_font_attributes(object))
+                    .collect();
+                let locked = targets
+                    .iter()
+                    .filter(|object| project.is_locked(object.id()))
+                    .count();
+
+                let name = |object: &Object| {
+                    format!(
+                        "{}: {}",
+                        object.id().value(),
+                        project.get_object_info(object).get_name(object)
+                    )
+                };
+                egui::CollapsingHeader::new(tr_args(
+                    "{} text objects, {} of them locked",
+                    &[&targets.len(), &locked],
+                ))
+                .id_salt("font_batch_targets")
+                .show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .max_height(200.0)
+                        .show(ui, |ui| {
+                            for object in &targets {
+                                ui.label(name(object));
+                            }
+                        });
+                });
+                ui.separator();
+
+                let fonts = pool.objects_by_type(ObjectType::FontAttributes);
+                if self
+                    .font_batch_font
+                    .is_some_and(|id| !fonts.iter().any(|font| font.id() == id))
+                {
+                    self.font_batch_font = None;
+                }
+                let selected_font = self
+                    .font_batch_font
+                    .and_then(|id| pool.object_by_id(id))
+                    .map_or(tr("Choose font attributes").to_string(), name);
+                ui.horizontal(|ui| {
+                    ui.label(tr("Font attributes:"));
+                    egui::ComboBox::from_id_salt("font_batch_font")
+                        .selected_text(selected_font)
+                        .show_ui(ui, |ui| {
+                            for font in &fonts {
+                                ui.selectable_value(
+                                    &mut self.font_batch_font,
+                                    Some(font.id()),
+                                    name(font),
+                                );
+                            }
+                        });
+                });
+
+                let enabled = !project.is_read_only()
+                    && self.font_batch_font.is_some()
+                    && targets.len() > locked;
+                ui.add_enabled_ui(enabled, |ui| {
+                    if ui.button(tr("Apply")).clicked() {
+                        if let Some(font) = self.font_batch_font {
+                            let ids: Vec<ObjectId> =
+                                targets.iter().map(|object| object.id()).collect();
+                            self.font_batch_status =
+                                Some(match set_font_attributes(project, &ids, font) {
+                                    Ok(changed) => tr_args("Changed {} objects", &[&changed]),
+                                    Err(e) => e,
+                                });
+                        }
+                    }
+                });
+                if let Some(status) = &self.font_batch_status {
+                    ui.label(status);
+                }
+            });
+
+        if !open {
+            self.show_font_batch = false;
+            self.font_batch_status = None;
+        }
+    }
+
+    /// Whether the last exported pool file reads back into the exported pool
+    fn render_export_verification_window(&mut self, ctx: &egui::Context) {
+        let (Some(project), Some(verification)) = (&self.project, &self.export_verification) else {
+            self.export_verification = None;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Export verification"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if verification.is_lossless() {
+                    ui.colored_label(
+                        egui::Color32::GREEN,
+                        tr_args(
+                            "{} reads back into the same {} objects ({} bytes)",
+                            &[
+                                &verification.file_name,
+                                &verification.objects,
+                                &verification.bytes,
+                            ],
+                        ),
+                    );
+                    return;
+                }
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args(
+                        "{} does not read back into the exported pool",
+                        &[&verification.file_name],
+                    ),
+                );
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for region in &verification.unreadable {
+                            ui.label(tr_args(
+                                "Unreadable bytes 0x{}..0x{}: {}",
+                                &[
+                                    &format!("{:06X}", region.start),
+                                    &format!("{:06X}", region.end),
+                                    &region.reason,
+                                ],
+                            ));
+                        }
+                        for diff in &verification.differences {
+                            let label = object_label(project, diff);
+                            match &diff.change {
+                                ObjectChange::Added => {
+                                    ui.label(tr_args("Unexpected object {}", &[&label]));
+                                }
+                                ObjectChange::Removed => {
+                                    ui.label(tr_args("Lost object {}", &[&label]));
+                                }
+                                ObjectChange::Modified(attributes) => {
+                                    ui.label(tr_args("Changed object {}", &[&label]));
+                                    for attribute in attributes {
+                                        ui.horizontal_wrapped(|ui| {
+                                            ui.add_space(ui.spacing().indent);
+                                            ui.label(format!(
+                                                "{}: {} \u{2192} {}",
+                                                attribute.attribute,
+                                                attribute.old_value,
+                                                attribute.new_value
+                                            ));
+                                        });
+                                    }
+                                }
+                            }
+                        }
+                        if let Some(offset) = verification.first_byte_difference {
+                            ui.label(tr_args(
+                                "Writing the read back pool again differs from byte 0x{}",
+                                &[&format!("{:06X}", offset)],
+                            ));
+                        }
+                    });
+            });
+
+        if !open {
+            self.export_verification = None;
+        }
+    }
+
+    /// New IDs for the objects of a loaded pool that share their ID with another object
+    fn render_id_conflicts_window(&mut self, ctx: &egui::Context) {
+        let (Some(project), Some(resolution)) = (&self.project, &mut self.id_conflicts) else {
+            self.id_conflicts = None;
+            return;
+        };
+
+        let mut open = true;
+        let mut done = false;
+        egui::Window::new(tr("Duplicate object IDs"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let pool = project.get_pool();
+                let objects = pool.objects();
+                ui.label(tr(
+                    "Several objects in the pool have the same ID. The later ones get a new ID, \
+                     choose which object each reference to a shared ID means.",
+                ));
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        ui.strong(tr("New IDs"));
+                        egui::Grid::new("id_conflicts_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for conflict in resolution.conflicts.iter_mut() {
+                                    ui.label(format!(
+                                        "{:?} {}",
+                                        conflict.object_type,
+                                        conflict.id.value()
+                                    ));
+                                    ui.label("\u{2192}");
+                                    let mut new_id = conflict.new_id.value();
+                                    if ui
+                                        .add(egui::DragValue::new(&mut new_id).range(0..=65534))
+                                        .changed()
+                                    {
+                                        if let Ok(id) = ObjectId::new(new_id) {
+                                            conflict.new_id = id;
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                        ui.separator();
+
+                        ui.strong(tr("References"));
+                        if resolution.rewrites.is_empty() {
+                            ui.label(tr("No object references a duplicate ID"));
+                        }
+                        let first_type = |id: ObjectId| {
+                            pool.object_by_id(id)
+                                .map(|object| format!("{:?}", object.object_type()))
+                                .unwrap_or_default()
+                        };
+                        egui::Grid::new("id_conflict_rewrites_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (index, rewrite) in resolution.rewrites.iter_mut().enumerate() {
+                                    let referencing = objects
+                                        .get(rewrite.index)
+                                        .map_or(String::new(), |object| {
+                                            format!("{:?}", object.object_type())
+                                        });
+                                    ui.label(format!("{} {}", referencing, rewrite.object.value()));
+                                    ui.label(tr_args("references {}", &[&rewrite.id.value()]));
+                                    let text = |target: Option<usize>| match target
+                                        .and_then(|c| resolution.conflicts.get(c))
+                                    {
+                                        Some(conflict) => tr_args(
+                                            "{} with new ID {}",
+                                            &[
+                                                &format!("{:?}", conflict.object_type),
+                                                &conflict.new_id.value(),
+                                            ],
+                                        ),
+                                        None => tr_args(
+                                            "{} keeping ID {}",
+                                            &[&first_type(rewrite.id), &rewrite.id.value()],
+                                        ),
+                                    };
+                                    let candidates: Vec<usize> = resolution
+                                        .conflicts
+                                        .iter()
+                                        .enumerate()
+                                        .filter(|(_, conflict)| conflict.id == rewrite.id)
+                                        .map(|(c, _)| c)
+                                        .collect();
+                                    let mut target = rewrite.target;
+                                    egui::ComboBox::from_id_salt(("id_conflict_rewrite", index))
+                                        .selected_text(text(target))
+                                        .show_ui(ui, |ui| {
+                                            ui.selectable_value(&mut target, None, text(None));
+                                            for candidate in candidates {
+                                                ui.selectable_value(
+                                                    &mut target,
+                                                    Some(candidate),
+                                                    text(Some(candidate)),
+                                                );
+                                            }
+                                        });
+                                    rewrite.target = target;
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.separator();
+
+                let problems = resolution.problems(pool);
+                for problem in &problems {
+                    ui.colored_label(egui::Color32::RED, problem);
+                }
+                if project.is_read_only() {
+                    ui.colored_label(
+                        egui::Color32::ORANGE,
+                        tr("The pool is read-only, allow editing to apply the new IDs"),
+                    );
+                }
+                ui.horizontal(|ui| {
+                    let enabled = problems.is_empty() && !project.is_read_only();
+                    if ui
+                        .add_enabled(enabled, egui::Button::new(tr("Apply new IDs")))
+                        .clicked()
+                    {
+                        let resolved = resolution.apply(pool);
+                        *project.get_mut_pool().borrow_mut() = resolved;
+                        project.describe_change(
+                            format!(
+                                "Give {} objects with a duplicate ID a new ID",
+                                resolution.conflicts.len()
+                            ),
+                            ChangeCategory::BulkChange,
+                        );
+                        done = true;
+                    }
+                    if ui
+                        .button(tr("Keep duplicates"))
+                        .on_hover_text(tr("Only the first object with an ID can be edited"))
+                        .clicked()
+                    {
+                        done = true;
+                    }
+                });
+            });
+
+        if !open || done {
+            self.id_conflicts = None;
+        }
+    }
+
+    /// Simulate the upload to the terminal of the active profile and show what makes the pool
+    /// too large
+    fn render_upload_simulation_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_upload_simulation = false;
+            return;
+        };
+
+        let mut open = true;
+        let mut simulate = false;
+        let mut open_profiles = false;
+        egui::Window::new(tr("Upload simulation"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let Some(simulation) = &self.upload_simulation else {
+                    ui.label(tr(
+                        "The active terminal profile has no pool size budget, set the memory of \
+                         the terminal to simulate the upload",
+                    ));
+                    open_profiles = ui.button(tr("Terminal profiles...")).clicked();
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    ui.label(tr_args(
+                        "{} with {} of memory, the pool is {}",
+                        &[
+                            &simulation.profile,
+                            &format_bytes(simulation.memory),
+                            &format_bytes(simulation.pool_size),
+                        ],
+                    ));
+                    simulate = ui.button(tr("Simulate again")).clicked();
+                });
+                ui.separator();
+
+                egui::Grid::new("upload_steps_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for step in &simulation.steps {
+                            ui.label(step.message);
+                            ui.monospace(format_hex(&step.data));
+                            ui.label(&step.outcome);
+                            ui.end_row();
+                        }
+                    });
+                ui.separator();
+
+                if simulation.fits() {
+                    ui.colored_label(
+                        egui::Color32::GREEN,
+                        tr_args(
+                            "The pool fits, {} are left",
+                            &[&format_bytes(simulation.memory - simulation.pool_size)],
+                        ),
+                    );
+                    return;
+                }
+                ui.colored_label(
+                    egui::Color32::RED,
+                    tr_args(
+                        "The pool is {} too large",
+                        &[&format_bytes(simulation.overflow())],
+                    ),
+                );
+                if let Some((id, _)) = simulation.failing_object {
+                    ui.horizontal(|ui| {
+                        ui.label(tr_args("The upload fails on object {}", &[&id.value()]));
+                        if ui.small_button(tr("Select")).clicked() {
+                            project
+                                .get_mut_selected()
+                                .replace(NullableObjectId(Some(id)));
+                        }
+                    });
+                }
+
+                ui.heading(tr("Largest objects"));
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("upload_offenders_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for offender in &simulation.offenders {
+                                    let name = project
+                                        .get_pool()
+                                        .object_by_id(offender.id)
+                                        .map(|o| project.get_object_info(o).get_name(o))
+                                        .unwrap_or_default();
+                                    ui.label(format!("{}: {}", offender.id.value(), name));
+                                    ui.label(format!("{:?}", offender.object_type));
+                                    ui.label(format_bytes(offender.size));
+                                    match &offender.suggestion {
+                                        Some((suggestion, saved)) => ui.label(tr_args(
+                                            "{}, saves {}",
+                                            &[suggestion, &format_bytes(*saved)],
+                                        )),
+                                        None => ui.label(""),
+                                    };
+                                    if ui.small_button(tr("Select")).clicked() {
+                                        project
+                                            .get_mut_selected()
+                                            .replace(NullableObjectId(Some(offender.id)));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                if !simulation.reductions.is_empty() {
+                    ui.heading(tr("Suggested reductions"));
+                    for (reduction, saved) in &simulation.reductions {
+                        ui.label(tr_args("{}, saves {}", &[reduction, &format_bytes(*saved)]));
+                    }
+                    let saved: usize = simulation.reductions.iter().map(|(_, saved)| saved).sum();
+                    if saved >= simulation.overflow() {
+                        ui.label(tr(
+                            "Export a minified pool to apply them, the pool then fits",
+                        ));
+                    }
+                }
+            });
+
+        if simulate {
+            self.upload_simulation = simulate_upload(project);
+        }
+        if open_profiles {
+            self.show_terminal_profiles = true;
+        }
+        if !open {
+            self.show_upload_simulation = false;
+        }
+    }
+
+    /// Print the active mask at a scale, or save it as PDF to print later
+    fn render_mask_print_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_mask_print = false;
+            return;
+        };
+        let pool = project.get_pool();
+        let mask = pool
+            .working_set_object()
+            .and_then(|working_set| pool.object_by_id(working_set.active_mask));
+
+        let mut open = true;
+        egui::Window::new(tr("Print mask"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                let Some(mask) = mask else {
+                    ui.label(tr("The working set has no active mask"));
+                    return;
+                };
+                ui.label(tr_args(
+                    "Mask: {}",
+                    &[&project.get_object_info(mask).get_name(mask)],
+                ));
+                let options = &mut self.print_options;
+                ui.horizontal(|ui| {
+                    ui.label(tr("Scale:"));
+                    ui.add(
+                        egui::DragValue::new(&mut options.scale)
+                            .range(0.05..=5.0)
+                            .speed(0.01)
+                            .suffix(tr(" mm per pixel")),
+                    );
+                    if ui.button(tr("Fit to page")).clicked() {
+                        options.scale = fit_to_page_scale(project.get_pool().content_size(mask));
+                    }
+                });
+                ui.checkbox(&mut options.show_ids, tr("Show object IDs"));
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut options.show_grid, tr("Show grid every"));
+                    ui.add_enabled(
+                        options.show_grid,
+                        egui::DragValue::new(&mut options.grid_spacing)
+                            .range(1..=200)
+                            .suffix(" px"),
+                    );
+                });
+                ui.separator();
+
+                let running = self.mask_print_run.is_some();
+                ui.horizontal(|ui| {
+                    let mut start = |print: bool| {
+                        let run = MaskPrintRun::new(project, mask.id(), self.print_options);
+                        self.mask_print_run = Some((run, print));
+                        self.mask_print_error = None;
+                    };
+                    if ui
+                        .add_enabled(!running, egui::Button::new(tr("Print")))
+                        .clicked()
+                    {
+                        start(true);
+                    }
+                    if ui
+                        .add_enabled(!running, egui::Button::new(tr("Save as PDF...")))
+                        .clicked()
+                    {
+                        start(false);
+                    }
+                    if running {
+                        ui.spinner();
+                    }
+                });
+                if let Some(error) = &self.mask_print_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+
+        if !open {
+            self.show_mask_print = false;
+        }
+    }
+
+    /// Show and hide containers and enable and disable input objects of the active mask in the
+    /// preview only, and save the states as named scenarios
+    fn render_state_sandbox_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &mut self.project else {
+            self.show_state_sandbox = false;
+            return;
+        };
+        if self
+            .active_state_scenario
+            .is_some_and(|index| index >= project.state_scenarios.len())
+        {
+            self.active_state_scenario = None;
+        }
+
+        let mut open = true;
+        egui::Window::new(tr("State sandbox"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(tr(
+                    "Try the states the working set sets at runtime with Hide/Show and \
+                     Enable/Disable commands. The pool is not changed.",
+                ));
+                ui.separator();
+
+                let scenario_name = |index: Option<usize>| {
+                    index
+                        .and_then(|index| project.state_scenarios.get(index))
+                        .map_or(tr("Pool state").to_string(), |s| s.name.clone())
+                };
+                let mut selected = self.active_state_scenario;
+                ui.horizontal(|ui| {
+                    ui.label(tr("Scenario:"));
+                    egui::ComboBox::from_id_salt("state_scenario")
+                        .selected_text(scenario_name(selected))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut selected, None, scenario_name(None));
+                            for index in 0..project.state_scenarios.len() {
+                                ui.selectable_value(
+                                    &mut selected,
+                                    Some(index),
+                                    scenario_name(Some(index)),
+                                );
+                            }
+                        });
+                });
+                let mut deleted = false;
+                if let Some(index) = self.active_state_scenario {
+                    if ui.button(tr("Delete scenario")).clicked() {
+                        project.state_scenarios.remove(index);
+                        selected = None;
+                        deleted = true;
+                    }
+                }
+                if selected != self.active_state_scenario || deleted {
+                    self.active_state_scenario = selected;
+                    let states = selected
+                        .and_then(|index| project.state_scenarios.get(index))
+                        .map(|scenario| scenario.object_states().collect())
+                        .unwrap_or_default();
+                    with_preview_state(ctx, |state| state.object_states = states);
+                }
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(&mut self.new_state_scenario);
+                    let name = self.new_state_scenario.trim();
+                    let valid =
+                        !name.is_empty() && project.state_scenarios.iter().all(|s| s.name != name);
+                    if ui
+                        .add_enabled(valid, egui::Button::new(tr("Save as scenario")))
+                        .clicked()
+                    {
+                        let states = with_preview_state(ctx, |state| {
+                            state
+                                .object_states
+                                .iter()
+                                .map(|(id, shown)| (id.value(), *shown))
+                                .collect()
+                        });
+                        project.state_scenarios.push(StateScenario {
+                            name: name.to_string(),
+                            states,
+                        });
+                        self.active_state_scenario = Some(project.state_scenarios.len() - 1);
+                        self.new_state_scenario.clear();
+                    }
+                });
+                ui.separator();
+
+                let pool = project.get_pool();
+                let Some(mask) = pool
+                    .working_set_object()
+                    .map(|working_set| working_set.active_mask)
+                else {
+                    ui.label(tr("The working set has no active mask"));
+                    return;
+                };
+                let mut changed = Vec::new();
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("state_sandbox_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for id in subtree(pool, mask) {
+                                    let Some(object) = pool.object_by_id(id) else {
+                                        continue;
+                                    };
+                                    let Some(in_pool) = pool_state(object) else {
+                                        continue;
+                                    };
+                                    let name = project.get_object_info(object).get_name(object);
+                                    if ui.link(name).clicked() {
+                                        project
+                                            .get_mut_selected()
+                                            .replace(NullableObjectId(Some(id)));
+                                    }
+                                    let label = match object {
+                                        Object::Container(_) => tr("Shown"),
+                                        _ => tr("Enabled"),
+                                    };
+                                    let mut state = object_state(ctx, id, in_pool);
+                                    if ui.checkbox(&mut state, label).changed() {
+                                        changed.push((id, state, in_pool));
+                                    }
+                                    if state != in_pool {
+                                        ui.weak(tr("differs from the pool"));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                let mut scenario = self
+                    .active_state_scenario
+                    .and_then(|index| project.state_scenarios.get_mut(index));
+                for (id, state, in_pool) in changed {
+                    with_preview_state(ctx, |preview| {
+                        if state == in_pool {
+                            preview.object_states.remove(&id);
+                        } else {
+                            preview.object_states.insert(id, state);
+                        }
+                    });
+                    if let Some(scenario) = &mut scenario {
+                        if state == in_pool {
+                            scenario.states.remove(&id.value());
+                        } else {
+                            scenario.states.insert(id.value(), state);
+                        }
+                    }
+                }
+            });
+
+        if !open {
+            self.show_state_sandbox = false;
+        }
+    }
+
+    /// The order in which the input focus moves through the active mask on terminals without
+    /// touch screen
+    fn render_focus_order_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_focus_order = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Focus order"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let mut show = with_preview_state(ctx, |state| state.show_focus_order);
+                if ui
+                    .checkbox(&mut show, tr("Number the focus order on the preview"))
+                    .changed()
+                {
+                    with_preview_state(ctx, |state| state.show_focus_order = show);
+                }
+                ui.label(tr(
+                    "Terminals move the focus through the references of the mask in order. \
+                     Reordering also changes which object is drawn on top.",
+                ));
+                ui.separator();
+
+                let pool = project.get_pool();
+                let Some(mask) = pool
+                    .working_set_object()
+                    .and_then(|working_set| pool.object_by_id(working_set.active_mask))
+                else {
+                    ui.label(tr("The working set has no active mask"));
+                    return;
+                };
+                let stops = focus_order(pool, mask);
+                if stops.is_empty() {
+                    ui.label(tr("The active mask has no objects that can get the focus"));
+                    return;
+                }
+                let mut moved = None;
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("focus_order_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (number, stop) in stops.iter().enumerate() {
+                                    ui.label(format!("{}.", number + 1));
+                                    match pool.object_by_id(stop.object) {
+                                        Some(object) => {
+                                            let name =
+                                                project.get_object_info(object).get_name(object);
+                                            if ui.link(name).clicked() {
+                                                project
+                                                    .get_mut_selected()
+                                                    .replace(NullableObjectId(Some(stop.object)));
+                                            }
+                                        }
+                                        None => {
+                                            ui.label(stop.object.value().to_string());
+                                        }
+                                    }
+                                    let same_parent = |other: usize| {
+                                        stops
+                                            .get(other)
+                                            .is_some_and(|other| other.parent == stop.parent)
+                                    };
+                                    let up = number > 0 && same_parent(number - 1);
+                                    if ui
+                                        .add_enabled(up, egui::Button::new("\u{2191}").small())
+                                        .clicked()
+                                    {
+                                        moved = Some((number, number - 1));
+                                    }
+                                    let down = same_parent(number + 1);
+                                    if ui
+                                        .add_enabled(down, egui::Button::new("\u{2193}").small())
+                                        .clicked()
+                                    {
+                                        moved = Some((number, number + 1));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                if let Some((from, to)) = moved {
+                    self.focus_order_error =
+                        move_focus_stop(project, &stops[from], &stops[to]).err();
+                }
+                if let Some(error) = &self.focus_order_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+
+        if !open {
+            self.show_focus_order = false;
+            self.focus_order_error = None;
+        }
+    }
+
+    /// The serialized bytes of the selected object with the attribute of every byte
+    fn render_hex_inspector_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_hex_inspector = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Hex inspector"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let selected = project.get_selected().0;
+                let Some(object) = selected.and_then(|id| project.get_pool().object_by_id(id))
+                else {
+                    ui.label(tr("Select an object to inspect its bytes"));
+                    return;
+                };
+                let bytes = object.write();
+                ui.label(tr_args(
+                    "{} with {} bytes",
+                    &[
+                        &project.get_object_info(object).get_name(object),
+                        &bytes.len(),
+                    ],
+                ));
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .id_salt("hex_inspector_fields")
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("hex_inspector_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong(tr("Offset"));
+                                ui.strong(tr("Bytes"));
+                                ui.strong(tr("Field"));
+                                ui.strong(tr("Value"));
+                                ui.end_row();
+                                for field in annotate_object(&bytes) {
+                                    let data = &bytes[field.offset..field.offset + field.len];
+                                    ui.monospace(format!("{:04X}", field.offset));
+                                    let shown = &data[..data.len().min(16)];
+                                    let mut hex = format_hex(shown);
+                                    if shown.len() < data.len() {
+                                        hex.push_str(" \u{2026}");
+                                    }
+                                    ui.monospace(hex);
+                                    ui.label(field.name);
+                                    if data.len() <= 4 {
+                                        let value = data
+                                            .iter()
+                                            .enumerate()
+                                            .map(|(i, byte)| (*byte as u32) << (8 * i))
+                                            .sum::<u32>();
+                                        ui.label(value.to_string());
+                                    } else {
+                                        ui.label("");
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.separator();
+
+                if !self.settings.expert_mode {
+                    ui.label(tr("Turn on expert mode in the settings to edit the bytes"));
+                    return;
+                }
+                let id = object.id();
+                if self
+                    .hex_edit
+                    .as_ref()
+                    .is_none_or(|(edited, _)| *edited != id)
+                {
+                    self.hex_edit = Some((id, format_hex(&bytes)));
+                    self.hex_edit_error = None;
+                }
+                let Some((_, text)) = &mut self.hex_edit else {
+                    return;
+                };
+                ui.label(tr("Edit bytes:"));
+                egui::ScrollArea::vertical()
+                    .id_salt("hex_inspector_edit")
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        ui.add(
+                            egui::TextEdit::multiline(text)
+                                .code_editor()
+                                .desired_width(f32::INFINITY),
+                        );
+                    });
+                ui.horizontal(|ui| {
+                    let editable = !project.is_read_only() && !project.is_locked(id);
+                    if ui
+                        .add_enabled(editable, egui::Button::new(tr("Apply bytes")))
+                        .clicked()
+                    {
+                        self.hex_edit_error = parse_hex(text)
+                            .and_then(|bytes| replace_object_bytes(project, id, &bytes))
+                            .err();
+                    }
+                    if ui.button(tr("Reset")).clicked() {
+                        *text = format_hex(&bytes);
+                        self.hex_edit_error = None;
+                    }
+                });
+                if let Some(error) = &self.hex_edit_error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+
+        if !open {
+            self.show_hex_inspector = false;
+            self.hex_edit = None;
+        }
+    }
+
+    /// Attribute and variable objects that nothing references, to delete them in bulk
+    fn render_orphan_cleanup_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_orphan_cleanup = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Clean up orphan attributes"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let orphans = orphan_attributes(project.get_pool());
+                if orphans.is_empty() {
+                    ui.label(tr("Every attribute and variable object is referenced"));
+                } else {
+                    ui.horizontal(|ui| {
+                        if ui.small_button(tr("All")).clicked() {
+                            self.orphans_kept.clear();
+                        }
+                        if ui.small_button(tr("None")).clicked() {
+                            self.orphans_kept = orphans.iter().map(|orphan| orphan.id).collect();
+                        }
+                    });
+                    egui::ScrollArea::vertical()
+                        .max_height(400.0)
+                        .show(ui, |ui| {
+                            egui::Grid::new("orphan_cleanup_grid")
+                                .striped(true)
+                                .show(ui, |ui| {
+                                    for orphan in &orphans {
+                                        let locked = project.is_locked(orphan.id);
+                                        let mut delete =
+                                            !locked && !self.orphans_kept.contains(&orphan.id);
+                                        let name = project
+                                            .get_pool()
+                                            .object_by_id(orphan.id)
+                                            .map(|o| project.get_object_info(o).get_name(o))
+                                            .unwrap_or_default();
+                                        let checkbox = ui.add_enabled(
+                                            !locked,
+                                            egui::Checkbox::new(
+                                                &mut delete,
+                                                format!("{}: {}", orphan.id.value(), name),
+                                            ),
+                                        );
+                                        if checkbox.changed() {
+                                            if delete {
+                                                self.orphans_kept.remove(&orphan.id);
+                                            } else {
+                                                self.orphans_kept.insert(orphan.id);
+                                            }
+                                        }
+                                        ui.label(format!("{:?}", orphan.object_type));
+                                        ui.label(format_bytes(orphan.size));
+                                        if ui.small_button(tr("Select")).clicked() {
+                                            project
+                                                .get_mut_selected()
+                                                .replace(NullableObjectId(Some(orphan.id)));
+                                        }
+                                        ui.end_row();
+                                    }
+                                });
+                        });
+                    ui.separator();
+
+                    let chosen: Vec<_> = orphans
+                        .iter()
+                        .filter(|o| !self.orphans_kept.contains(&o.id) && !project.is_locked(o.id))
+                        .collect();
+                    let saved: usize = chosen.iter().map(|orphan| orphan.size).sum();
+                    ui.add_enabled_ui(!project.is_read_only() && !chosen.is_empty(), |ui| {
+                        if ui
+                            .button(tr_args(
+                                "Delete {} objects, saves {}",
+                                &[&chosen.len(), &format_bytes(saved)],
+                            ))
+                            .clicked()
+                        {
+                            let ids: Vec<ObjectId> =
+                                chosen.iter().map(|orphan| orphan.id).collect();
+                            let deleted = delete_orphans(project, &ids);
+                            self.orphan_cleanup_status =
+                                Some(tr_args("Deleted {} objects", &[&deleted]));
+                        }
+                    });
+                }
+                if let Some(status) = &self.orphan_cleanup_status {
+                    ui.label(status);
+                }
+            });
+
+        if !open {
+            self.show_orphan_cleanup = false;
+            self.orphan_cleanup_status = None;
+        }
+    }
+
+    /// Move and scale a selection of the children of the selected object in one change
+    fn render_transform_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_transform = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Transform selection"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let pool = project.get_pool();
+                let parent = project
+                    .get_selected()
+                    .0
+                    .and_then(|id| pool.object_by_id(id))
+                    .and_then(|o| object_refs(o).map(|refs| (o, refs)));
+                let Some((parent, refs)) = parent else {
+                    ui.label(tr(
+                        "Select a mask, container or other object with positioned children",
+                    ));
+                    return;
+                };
+
+                // Start with all children chosen when another object is selected
+                let (selected_parent, indices) = &mut self.transform_selection;
+                if *selected_parent != Some(parent.id()) {
+                    *selected_parent = Some(parent.id());
+                    *indices = (0..refs.len()).collect();
+                }
+                indices.retain(|index| *index < refs.len());
+
+                ui.horizontal(|ui| {
+                    ui.label(tr_args(
+                        "Children of {}:",
+                        &[&project.get_object_info(parent).get_name(parent)],
+                    ));
+                    if ui.small_button(tr("All")).clicked() {
+                        *indices = (0..refs.len()).collect();
+                    }
+                    if ui.small_button(tr("None")).clicked() {
+                        indices.clear();
+                    }
+                });
+                egui::ScrollArea::vertical()
+                    .max_height(250.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("transform_children_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (index, obj_ref) in refs.iter().enumerate() {
+                                    let mut chosen = indices.contains(&index);
+                                    let name = pool.object_by_id(obj_ref.id).map_or_else(
+                                        || tr_args("Missing object {}", &[&obj_ref.id.value()]),
+                                        |o| project.get_object_info(o).get_name(o),
+                                    );
+                                    if ui.checkbox(&mut chosen, name).changed() {
+                                        if chosen {
+                                            indices.insert(index);
+                                        } else {
+                                            indices.remove(&index);
+                                        }
+                                    }
+                                    ui.weak(format!(
+                                        "({}, {})",
+                                        obj_ref.offset.x, obj_ref.offset.y
+                                    ));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.separator();
+
+                let transform = &mut self.transform;
+                egui::Grid::new("transform_grid").show(ui, |ui| {
+                    ui.label(tr("Move:"));
+                    ui.add(egui::DragValue::new(&mut transform.dx).prefix("dx: "));
+                    ui.add(egui::DragValue::new(&mut transform.dy).prefix("dy: "));
+                    ui.end_row();
+                    ui.label(tr("Scale positions:"));
+                    for scale in [&mut transform.scale_x, &mut transform.scale_y] {
+                        ui.add(
+                            egui::DragValue::new(scale)
+                                .range(1.0..=1000.0)
+                                .speed(1.0)
+                                .suffix(" %"),
+                        );
+                    }
+                    ui.end_row();
+                });
+                ui.checkbox(&mut transform.scale_sizes, tr("Scale sizes too"))
+                    .on_hover_text(tr(
+                        "Children that are used in other places are resized there too",
+                    ));
+                ui.weak(tr(
+                    "Positions are scaled from the top left corner of the parent, then moved",
+                ));
+
+                ui.horizontal(|ui| {
+                    if ui
+                        .add_enabled(!indices.is_empty(), egui::Button::new(tr("Apply")))
+                        .clicked()
+                    {
+                        let moved = transform_children(project, parent.id(), indices, transform);
+                        project.describe_change(
+                            format!("Transform {} objects", moved),
+                            ChangeCategory::BulkChange,
+                        );
+                    }
+                    if ui.button(tr("Reset")).clicked() {
+                        *transform = Transform::default();
+                    }
+                });
+            });
+
+        if !open {
+            self.show_transform = false;
+        }
+    }
+
+    /// Ask what to do with the opened file after it changed outside of the designer
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_external_change_window(&mut self, ctx: &egui::Context) {
+        let (Some(watched), Some(project)) = (&self.watched_file, &self.project) else {
+            self.external_change = None;
+            return;
+        };
+        let mut choice = None;
+        egui::Window::new(tr("File changed on disk"))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr_args(
+                    "{} was changed outside of the designer, e.g. by a build script or a git pull.",
+                    &[&watched.path.display()],
+                ));
+                if project.has_unsaved_changes() {
+                    ui.colored_label(
+                        ui.visuals().warn_fg_color,
+                        "Reloading discards your unsaved changes.",
+                    );
+                }
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr("Reload"))
+                        .on_hover_text(tr("Discard the local edits and load the file again"))
+                        .clicked()
+                    {
+                        choice = Some(true);
+                    }
+                    if ui
+                        .button(tr("Merge"))
+                        .on_hover_text(
+                            "Take the objects from the file but keep the local names, notes and \
+                             other metadata. Undo restores the local edits.",
+                        )
+                        .clicked()
+                    {
+                        choice = Some(false);
+                    }
+                    if ui
+                        .button(tr("Keep local edits"))
+                        .on_hover_text(tr("Ignore the change, saving overwrites the file"))
+                        .clicked()
+                    {
+                        self.external_change = None;
+                    }
+                });
+            });
+
+        let Some(reload) = choice else {
+            return;
+        };
+        let Some(content) = self.external_change.take() else {
+            return;
+        };
+        let watched = watched.clone();
+        if reload {
+            let reason = if watched.is_project {
+                FileDialogReason::LoadProject
+            } else {
+                FileDialogReason::LoadPool
+            };
+            self.load_file(reason, watched.file_name(), content, ctx);
+        } else if let Some(project) = &self.project {
+            match merge_from_disk(project, &watched, content) {
+                Ok(changed) => log::info!(
+                    "Merged {} changed objects from {}",
+                    changed,
+                    watched.file_name()
+                ),
+                Err(e) => log::error!("Failed to merge {}: {}", watched.file_name(), e),
+            }
+        }
+    }
+
+    /// Ask before a handed over file replaces a project with unsaved changes
+    #[cfg(not(target_arch = "wasm32"))]
+    fn render_open_request_window(&mut self, ctx: &egui::Context) {
+        let mut choice = None;
+        egui::Window::new(tr("Unsaved changes"))
+            .collapsible(false)
+            .resizable(false)
+            .show(ctx, |ui| {
+                for path in &self.pending_open_requests {
+                    ui.monospace(path.display().to_string());
+                }
+                ui.colored_label(
+                    ui.visuals().warn_fg_color,
+                    "Opening discards your unsaved changes.",
+                );
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Open")).clicked() {
+                        choice = Some(true);
+                    }
+                    if ui.button(tr("Keep the open project")).clicked() {
+                        choice = Some(false);
+                    }
+                });
+            });
+
+        let Some(open) = choice else {
+            return;
+        };
+        for path in std::mem::take(&mut self.pending_open_requests) {
+            if open {
+                self.open_path(&path, ctx);
+            }
+        }
+    }
+
+    /// Export the pool with the terminal settings for the AgIsoVirtualTerminal simulator, or
+    /// start the simulator with it
+    fn render_vt_simulator_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_vt_simulator = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new("AgIsoVirtualTerminal")
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr(
+                    "Cross-check the rendering against the reference VT server implementation. \
+                     The simulated terminal uses the hardware of the active terminal profile.",
+                ));
+                let settings = SimulatorSettings::from_project(project);
+                egui::Grid::new("vt_simulator_settings").show(ui, |ui| {
+                    ui.label(tr("Data mask size"));
+                    ui.label(format!("{} px", settings.data_mask_size));
+                    ui.end_row();
+                    ui.label(tr("Soft key designator"));
+                    ui.label(format!(
+                        "{}\u{d7}{} px",
+                        settings.soft_key_size.0, settings.soft_key_size.1
+                    ));
+                    ui.end_row();
+                    ui.label(tr("Physical soft keys"));
+                    ui.label(settings.physical_soft_keys.to_string());
+                    ui.end_row();
+                    ui.label(tr("VT version"));
+                    ui.label(settings.vt_version.to_string());
+                    ui.end_row();
+                });
+                ui.separator();
+
+                ui.horizontal(|ui| {
+                    for (name, contents) in simulator_export(project) {
+                        if ui.button(tr_args("Save {}...", &[&name])).clicked() {
+                            let task = rfd::AsyncFileDialog::new().set_file_name(name).save_file();
+                            execute(async move {
+                                if let Some(file) = task.await {
+                                    _ = file.write(&contents).await;
+                                }
+                            });
+                        }
+                    }
+                });
+                ui.weak(tr(
+                    "AgIsoVirtualTerminal can not be started with a pool or load one from a \
+                     file. Upload the exported pool from a working set and enter the hardware \
+                     above in its settings.",
+                ));
+            });
+        if !open {
+            self.show_vt_simulator = false;
+        }
+    }
+
+    /// Screenshot of a real terminal over the preview, to see where the rendering differs
+    fn render_reference_overlay_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut load = false;
+        let mut compare = false;
+        let mut remove = false;
+        let mut overlay = with_preview_state(ctx, |state| state.reference.clone());
+        egui::Window::new(tr("Reference overlay"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr(
+                    "Show a screenshot of a real terminal or of AgIsoVirtualTerminal over the \
+                     preview. Take it of the data mask area only, at its native resolution.",
+                ));
+                if ui.button(tr("Load screenshot...")).clicked() {
+                    load = true;
+                }
+                let Some(overlay) = &mut overlay else {
+                    return;
+                };
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut overlay.visible, overlay.name.as_str());
+                    ui.label(format!(
+                        "{}\u{d7}{} px",
+                        overlay.image.size[0], overlay.image.size[1]
+                    ));
+                });
+                ui.add(egui::Slider::new(&mut overlay.opacity, 0.0..=1.0).text(tr("Opacity")));
+                ui.horizontal(|ui| {
+                    ui.label(tr("Offset"));
+                    ui.add(egui::DragValue::new(&mut overlay.offset.0).prefix("x: "));
+                    ui.add(egui::DragValue::new(&mut overlay.offset.1).prefix("y: "));
+                    for (label, dx, dy) in [
+                        ("\u{2190}", -1, 0),
+                        ("\u{2191}", 0, -1),
+                        ("\u{2193}", 0, 1),
+                        ("\u{2192}", 1, 0),
+                    ] {
+                        if ui
+                            .small_button(label)
+                            .on_hover_text(tr("Nudge 1 px"))
+                            .clicked()
+                        {
+                            overlay.offset.0 = overlay.offset.0.saturating_add(dx);
+                            overlay.offset.1 = overlay.offset.1.saturating_add(dy);
+                        }
+                    }
+                    if ui.small_button(tr("Reset")).clicked() {
+                        overlay.offset = (0, 0);
+                    }
+                });
+                ui.separator();
+                ui.horizontal(|ui| {
+                    ui.label(tr("Tolerance"));
+                    ui.add(egui::DragValue::new(&mut overlay.tolerance))
+                        .on_hover_text(tr("Allowed difference per colour channel"));
+                    if ui
+                        .button(tr("Compare with the preview"))
+                        .on_hover_text(tr("Mark every pixel where the preview differs in red"))
+                        .clicked()
+                    {
+                        compare = true;
+                    }
+                });
+                if let Some((differing, total)) = overlay.differences {
+                    ui.label(tr_args(
+                        "{} of {} pixels differ ({}%)",
+                        &[
+                            &differing,
+                            &total,
+                            &format!("{:.1}", differing as f32 * 100.0 / total.max(1) as f32),
+                        ],
+                    ));
+                    if differing > 0 {
+                        ui.checkbox(&mut overlay.show_differences, tr("Show differences"));
+                    }
+                }
+                if ui.button(tr("Remove overlay")).clicked() {
+                    remove = true;
+                }
+            });
+        if let Some(overlay) = &overlay {
+            set_reference_settings(ctx, overlay);
+        }
+        if compare {
+            request_reference_comparison(ctx);
+        }
+        if remove {
+            with_preview_state(ctx, |state| state.reference = None);
+        }
+        if load {
+            self.open_file_dialog(FileDialogReason::ReferenceScreenshot, ctx);
+        }
+        if !open {
+            self.show_reference_overlay = false;
+        }
+    }
+
+    /// Expressions over number variables with their raw values, evaluated live while simulating
+    fn render_watch_expressions_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &mut self.project else {
+            self.show_watch_expressions = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Watch expressions"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.label(tr(
+                    "Use var(ID) for the value of a number variable, e.g. var(0x8003)*0.1 - 40",
+                ));
+                if preview_mode(ctx) != PreviewMode::Simulate {
+                    ui.weak(tr(
+                        "Values entered in the preview are used in simulate mode",
+                    ));
+                }
+                ui.separator();
+
+                // Taken out while editing, the pool of the project is borrowed for the values
+                let mut expressions = std::mem::take(&mut project.watch_expressions);
+                let pool = project.get_pool();
+                let value = |id: u16| simulated_variable_value(ctx, pool, id);
+                let mut removed = None;
+                egui::Grid::new("watch_expressions_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (index, expression) in expressions.iter_mut().enumerate() {
+                            ui.add(egui::TextEdit::singleline(expression).code_editor());
+                            let raw: Vec<String> = watch_variables(expression)
+                                .into_iter()
+                                .map(|id| match value(id) {
+                                    Some(value) => format!("0x{:04X} = {}", id, value),
+                                    None => format!("0x{:04X} = ?", id),
+                                })
+                                .collect();
+                            ui.monospace(raw.join(", "));
+                            match evaluate_watch_expression(expression, &|id| {
+                                value(id).map(f64::from)
+                            }) {
+                                Some(result) => ui.strong(format!("{}", result)),
+                                None => ui.colored_label(egui::Color32::RED, tr("Invalid")),
+                            };
+                            if ui.small_button("\u{1F5D1}").clicked() {
+                                removed = Some(index);
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(index) = removed {
+                    expressions.remove(index);
+                }
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_watch_expression)
+                            .code_editor()
+                            .hint_text("var(0x8003)*0.1 - 40"),
+                    );
+                    let text = self.new_watch_expression.trim();
+                    if ui
+                        .add_enabled(!text.is_empty(), egui::Button::new(tr("Add")))
+                        .clicked()
+                    {
+                        expressions.push(text.to_string());
+                        self.new_watch_expression.clear();
+                    }
+                });
+                project.watch_expressions = expressions;
+            });
+
+        if !open {
+            self.show_watch_expressions = false;
+        }
+    }
+
+    fn render_input_messages_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_input_messages = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Input messages"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(450.0)
+            .show(ctx, |ui| {
+                let messages = with_preview_state(ctx, |state| state.input_messages.clone());
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Clear")).clicked() {
+                        with_preview_state(ctx, |state| state.input_messages.clear());
+                    }
+                    if preview_mode(ctx) != PreviewMode::Simulate {
+                        ui.weak(tr(
+                            "Switch the preview to simulate mode and click on an input object",
+                        ));
+                    }
+                });
+                ui.separator();
+
+                let pool = project.get_pool();
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .stick_to_bottom(true)
+                    .show(ui, |ui| {
+                        egui::Grid::new("input_messages_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for message in &messages {
+                                    ui.monospace(format!("0x{:02X}", message.function_code()));
+                                    ui.label(match message {
+                                        InputMessage::SelectInputObject {
+                                            open_for_input, ..
+                                        } => format!(
+                                            "VT Select Input Object (open for input: {})",
+                                            open_for_input
+                                        ),
+                                        InputMessage::Esc { .. } => "VT ESC".to_string(),
+                                        InputMessage::ChangeNumericValue { value, .. } => {
+                                            format!("VT Change Numeric Value = {}", value)
+                                        }
+                                    });
+                                    let id = message.object();
+                                    let label = match pool.object_by_id(id) {
+                                        Some(o) => format!(
+                                            "{:?} {} ({})",
+                                            o.object_type(),
+                                            id.value(),
+                                            project.get_object_info(o).get_name(o)
+                                        ),
+                                        None => tr_args("Missing object {}", &[&id.value()]),
+                                    };
+                                    if ui.link(label).clicked() {
+                                        project
+                                            .get_mut_selected()
+                                            .replace(NullableObjectId(Some(id)));
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if !open {
+            self.show_input_messages = false;
+        }
+    }
+
+    /// Window listing every user visible string with its length budget, the masks that show it
+    /// and its translation status
+    fn render_strings_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_strings = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Strings"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(800.0)
+            .show(ctx, |ui| {
+                let entries = string_inventory(project);
+                let over_budget = entries.iter().filter(|e| e.exceeds_capacity()).count();
+                ui.horizontal(|ui| {
+                    ui.label(tr("Filter:"));
+                    ui.text_edit_singleline(&mut self.strings_filter);
+                    ui.checkbox(&mut self.strings_over_budget_only, tr("Over budget only"));
+                });
+                ui.label(tr_args(
+                    "{} strings, {} over budget",
+                    &[&entries.len(), &over_budget],
+                ));
+                ui.separator();
+
+                let filter = self.strings_filter.to_lowercase();
+                egui::ScrollArea::both().max_height(500.0).show(ui, |ui| {
+                    egui::Grid::new("string_inventory")
+                        .striped(true)
+                        .num_columns(6)
+                        .show(ui, |ui| {
+                            ui.strong(tr("Object"));
+                            ui.strong(tr("Value"));
+                            ui.strong(tr("Bytes"));
+                            ui.strong(tr("Capacity"));
+                            ui.strong(tr("Masks"));
+                            ui.strong(tr("Translation"));
+                            ui.end_row();
+
+                            for entry in entries {
+                                if self.strings_over_budget_only && !entry.exceeds_capacity() {
+                                    continue;
+                                }
+                                if !filter.is_empty()
+                                    && !entry.value.to_lowercase().contains(&filter)
+                                {
+                                    continue;
+                                }
+
+                                let name = project
+                                    .get_pool()
+                                    .object_by_id(entry.object)
+                                    .map(|o| project.get_object_info(o).get_name(o))
+                                    .unwrap_or_default();
+                                if ui.link(name).clicked() {
+                                    project
+                                        .get_mut_selected()
+                                        .replace(NullableObjectId(Some(entry.object)));
+                                }
+
+                                let mut value = entry.value.clone();
+                                if ui.text_edit_singleline(&mut value).changed() {
+                                    set_string_value(project, entry.object, value);
+                                    project.describe_change(
+                                        format!("Edit string {}", entry.object.value()),
+                                        ChangeCategory::Edit,
+                                    );
+                                }
+
+                                ui.label(entry.length.to_string());
+                                match entry.capacity {
+                                    Some(capacity) if entry.exceeds_capacity() => {
+                                        ui.colored_label(
+                                            egui::Color32::RED,
+                                            tr_args("{} chars", &[&capacity]),
+                                        );
+                                    }
+                                    Some(capacity) => {
+                                        ui.label(tr_args("{} chars", &[&capacity]));
+                                    }
+                                    None => {
+                                        ui.label("-").on_hover_text(tr(
+                                            "Not displayed or displayed with a proportional font",
+                                        ));
+                                    }
+                                }
+
+                                ui.horizontal(|ui| {
+                                    if entry.masks.is_empty() {
+                                        ui.label("-");
+                                    }
+                                    for mask in &entry.masks {
+                                        if ui.link(mask.value().to_string()).clicked() {
+                                            project
+                                                .get_mut_selected()
+                                                .replace(NullableObjectId(Some(*mask)));
+                                        }
+                                    }
+                                });
+
+                                let mut translation = entry.translation;
+                                egui::ComboBox::from_id_salt(("string_translation", entry.object))
+                                    .selected_text(tr(translation.label()))
+                                    .show_ui(ui, |ui| {
+                                        for status in TranslationStatus::ALL {
+                                            ui.selectable_value(
+                                                &mut translation,
+                                                status,
+                                                tr(status.label()),
+                                            );
+                                        }
+                                    });
+                                if translation != entry.translation {
+                                    if let Some(info) =
+                                        project.object_info.borrow_mut().get_mut(&entry.object)
+                                    {
+                                        info.translation = translation;
+                                    }
+                                }
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+
+        if !open {
+            self.show_strings = false;
+        }
+    }
+
+    /// Every palette index used in the pool and whether it survives a colour reduction
+    fn render_colour_usage_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_colour_usage = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Colour usage"))
+            .open(&mut open)
+            .resizable(true)
+            .default_width(500.0)
+            .show(ctx, |ui| {
+                let pool = project.get_pool();
+                let usage = colour_usage(pool);
+                let reduced = |depth: ColourDepth, index: u8| depth.closest_colour(pool, index);
+                let lost = |depth: ColourDepth| {
+                    usage
+                        .keys()
+                        .filter(|index| reduced(depth, **index) != **index)
+                        .count()
+                };
+                ui.label(tr_args(
+                    "{} colours used, {} change on 16 colour terminals, {} on monochrome terminals",
+                    &[
+                        &usage.len(),
+                        &lost(ColourDepth::Colours16),
+                        &lost(ColourDepth::Monochrome),
+                    ],
+                ));
+                ui.checkbox(
+                    &mut self.colour_usage_lossy_only,
+                    tr("Only colours that change on 16 colour or monochrome terminals"),
+                );
+                ui.separator();
+
+                let swatch = |ui: &mut egui::Ui, index: u8| {
+                    let colour = pool.color_by_index(index);
+                    let (rect, response) =
+                        ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+                    ui.painter().rect_filled(
+                        rect,
+                        2.0,
+                        egui::Color32::from_rgb(colour.r, colour.g, colour.b),
+                    );
+                    response.on_hover_text(tr_args(
+                        "Index {}: {}",
+                        &[
+                            &index,
+                            &format!("#{:02X}{:02X}{:02X}", colour.r, colour.g, colour.b),
+                        ],
+                    ));
+                };
+                let reduction = |ui: &mut egui::Ui, depth: ColourDepth, index: u8| {
+                    let target = reduced(depth, index);
+                    if target == index {
+                        ui.label("\u{2714}");
+                    } else {
+                        ui.horizontal(|ui| {
+                            ui.label("\u{2192}");
+                            swatch(ui, target);
+                            ui.label(target.to_string());
+                        });
+                    }
+                };
+
+                egui::ScrollArea::vertical()
+                    .max_height(500.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("colour_usage_grid")
+                            .striped(true)
+                            .num_columns(4)
+                            .show(ui, |ui| {
+                                ui.strong(tr("Colour"));
+                                ui.strong(tr("16 colours"));
+                                ui.strong(tr("Monochrome"));
+                                ui.strong(tr("Used by"));
+                                ui.end_row();
+
+                                for (index, users) in &usage {
+                                    let index = *index;
+                                    let lossy = reduced(ColourDepth::Colours16, index) != index
+                                        || reduced(ColourDepth::Monochrome, index) != index;
+                                    if self.colour_usage_lossy_only && !lossy {
+                                        continue;
+                                    }
+                                    ui.horizontal(|ui| {
+                                        swatch(ui, index);
+                                        ui.monospace(format!("{:>3}", index));
+                                    });
+                                    reduction(ui, ColourDepth::Colours16, index);
+                                    reduction(ui, ColourDepth::Monochrome, index);
+                                    egui::CollapsingHeader::new(tr_args(
+                                        "{} attributes",
+                                        &[&users.len()],
+                                    ))
+                                    .id_salt(("colour_users", index))
+                                    .show(ui, |ui| {
+                                        for (id, attribute) in users {
+                                            let label = pool
+                                                .object_by_id(*id)
+                                                .map(|o| {
+                                                    format!(
+                                                        "{:?} {}: {}",
+                                                        o.object_type(),
+                                                        id.value(),
+                                                        project.get_object_info(o).get_name(o)
+                                                    )
+                                                })
+                                                .unwrap_or_else(|| id.value().to_string());
+                                            if ui
+                                                .link(format!("{} ({})", label, attribute))
+                                                .clicked()
+                                            {
+                                                project
+                                                    .get_mut_selected()
+                                                    .replace(NullableObjectId(Some(*id)));
+                                            }
+                                        }
+                                    });
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if !open {
+            self.show_colour_usage = false;
+        }
+    }
+
+    /// Window to manage the colour roles and themes of the project
+    fn render_colour_roles_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &mut self.project else {
+            self.show_colour_roles = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Colour roles"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let mut colour_roles = project.colour_roles.clone();
+                if self.selected_colour_theme >= colour_roles.themes.len() {
+                    self.selected_colour_theme = 0;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("Theme:"));
+                    for (index, theme) in colour_roles.themes.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected_colour_theme, index, &theme.name);
+                    }
+                    if ui
+                        .button("+")
+                        .on_hover_text(tr("Add a copy of the selected theme"))
+                        .clicked()
+                    {
+                        let mut theme = colour_roles.themes[self.selected_colour_theme].clone();
+                        theme.name = format!("Theme {}", colour_roles.themes.len() + 1);
+                        colour_roles.themes.push(theme);
+                        self.selected_colour_theme = colour_roles.themes.len() - 1;
+                    }
+                    if colour_roles.themes.len() > 1
+                        && ui
+                            .button("\u{1F5D1}")
+                            .on_hover_text(tr("Remove the selected theme"))
+                            .clicked()
+                    {
+                        colour_roles.themes.remove(self.selected_colour_theme);
+                        self.selected_colour_theme = 0;
+                    }
+                });
+
+                let theme = &mut colour_roles.themes[self.selected_colour_theme];
+                ui.horizontal(|ui| {
+                    ui.label(tr("Name:"));
+                    ui.text_edit_singleline(&mut theme.name);
+                });
+                ui.separator();
+
+                let mut removed_role = None;
+                egui::Grid::new("colour_roles_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for role in &colour_roles.roles {
+                            let colour = theme.colours.entry(role.clone()).or_insert(0);
+                            ui.label(role);
+                            ui.add(egui::Slider::new(colour, 0..=255).drag_value_speed(1.0));
+                            let swatch = project.get_pool().color_by_index(*colour);
+                            let (rect, _) = ui
+                                .allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+                            ui.painter().rect_filled(
+                                rect,
+                                2.0,
+                                egui::Color32::from_rgb(swatch.r, swatch.g, swatch.b),
+                            );
+                            if ui
+                                .button("\u{1F5D1}")
+                                .on_hover_text(tr("Remove role"))
+                                .clicked()
+                            {
+                                removed_role = Some(role.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+
+                if let Some(role) = removed_role {
+                    colour_roles.remove_role(&role);
+                    for info in project.object_info.borrow_mut().values_mut() {
+                        info.colour_roles.retain(|_, r| *r != role);
+                    }
+                }
+
+                ui.horizontal(|ui| {
+                    ui.add(
+                        egui::TextEdit::singleline(&mut self.new_colour_role)
+                            .hint_text(tr("New role name...")),
+                    );
+                    if ui.button(tr("Add role")).clicked() {
+                        colour_roles.add_role(std::mem::take(&mut self.new_colour_role));
+                    }
+                });
+                ui.separator();
+
+                project.colour_roles = colour_roles;
+                ui.add_enabled_ui(!project.is_read_only(), |ui| {
+                    if ui
+                        .button(tr("Apply theme"))
+                        .on_hover_text(tr("Recolour all objects that have a colour role"))
+                        .clicked()
+                    {
+                        project.apply_colour_theme(self.selected_colour_theme);
+                    }
+                });
+            });
+
+        if !open {
+            self.show_colour_roles = false;
+        }
+    }
+
+    fn render_style_presets_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &mut self.project else {
+            self.show_style_presets = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Style presets"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let selected_object = project
+                    .get_selected()
+                    .0
+                    .and_then(|id| project.get_pool().object_by_id(id).cloned());
+
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr("Preset:"));
+                    for (index, preset) in project.style_presets.iter().enumerate() {
+                        ui.selectable_value(&mut self.selected_style_preset, index, &preset.name);
+                    }
+                    let name = (1..)
+                        .map(|n| format!("Preset {}", n))
+                        .find(|name| project.style_presets.iter().all(|p| &p.name != name))
+                        .unwrap_or_default();
+                    if ui
+                        .button("+")
+                        .on_hover_text(tr("Add an empty preset"))
+                        .clicked()
+                    {
+                        project.style_presets.push(StylePreset {
+                            name,
+                            values: Default::default(),
+                        });
+                        self.selected_style_preset = project.style_presets.len() - 1;
+                    } else if let Some(object) = &selected_object {
+                        if ui
+                            .button(tr("From selected object"))
+                            .on_hover_text(tr(
+                                "Add a preset with the attributes of the selected object and \
+                                 assign it to the object",
+                            ))
+                            .clicked()
+                        {
+                            if let Some(info) =
+                                project.object_info.borrow_mut().get_mut(&object.id())
+                            {
+                                info.style_preset = Some(name.clone());
+                            }
+                            project.style_presets.push(StylePreset {
+                                name,
+                                values: preset_values(object),
+                            });
+                            self.selected_style_preset = project.style_presets.len() - 1;
+                        }
+                    }
+                });
+
+                let Some(preset) = project
+                    .style_presets
+                    .get(self.selected_style_preset)
+                    .cloned()
+                else {
+                    ui.weak(tr(
+                        "Presets bundle fonts, colours and options, so objects can be styled \
+                         consistently and restyled at once",
+                    ));
+                    return;
+                };
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("Name:"));
+                    let mut name = preset.name.clone();
+                    if ui.text_edit_singleline(&mut name).changed() {
+                        project.rename_style_preset(&preset.name, name);
+                    }
+                    if ui
+                        .button("\u{1F5D1}")
+                        .on_hover_text(tr("Remove the preset, the objects keep their attributes"))
+                        .clicked()
+                    {
+                        project.remove_style_preset(&preset.name);
+                        self.selected_style_preset = 0;
+                    }
+                });
+                let Some(preset) = project
+                    .style_presets
+                    .get(self.selected_style_preset)
+                    .cloned()
+                else {
+                    return;
+                };
+                ui.separator();
+
+                let mut edited = preset.clone();
+                let mut removed = None;
+                egui::Grid::new("style_preset_grid")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        for (attribute, value) in edited.values.iter_mut() {
+                            ui.label(attribute.as_str());
+                            render_preset_value(ui, project, attribute, value);
+                            if ui
+                                .button("\u{1F5D1}")
+                                .on_hover_text(tr("Remove attribute"))
+                                .clicked()
+                            {
+                                removed = Some(attribute.clone());
+                            }
+                            ui.end_row();
+                        }
+                    });
+                if let Some(attribute) = removed {
+                    edited.values.remove(&attribute);
+                }
+
+                // New attributes are taken from the selected object, with its values
+                if let Some(object) = &selected_object {
+                    let missing: Vec<(String, PresetValue)> = preset_values(object)
+                        .into_iter()
+                        .filter(|(attribute, _)| !edited.values.contains_key(attribute))
+                        .collect();
+                    if !missing.is_empty() {
+                        ui.menu_button(tr("Add attribute of the selected object"), |ui| {
+                            for (attribute, value) in missing {
+                                if ui.button(&attribute).clicked() {
+                                    edited.values.insert(attribute, value);
+                                    ui.close();
+                                }
+                            }
+                        });
+                    }
+                }
+                if edited != preset {
+                    project.style_presets[self.selected_style_preset] = edited;
+                }
+                ui.separator();
+
+                let users = project.style_preset_users(&preset.name).len();
+                ui.horizontal(|ui| {
+                    ui.label(tr_args("Assigned to {} objects", &[&users]));
+                    ui.add_enabled_ui(!project.is_read_only() && users > 0, |ui| {
+                        if ui
+                            .button(tr("Apply preset"))
+                            .on_hover_text(tr("Restyle all objects the preset is assigned to"))
+                            .clicked()
+                        {
+                            project.apply_style_preset(&preset.name);
+                        }
+                    });
+                });
+            });
+
+        if !open {
+            self.show_style_presets = false;
+        }
+    }
+}
+
+impl DesignerApp {
+    /// Window that lists the undo and redo history with the author of every change
+    fn render_history_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &mut self.project else {
+            self.show_history = false;
+            return;
+        };
+
+        let filter = &mut self.history_filter;
+        let selected_only = &mut self.history_selected_only;
+        let scoped_undo_error = &mut self.scoped_undo_error;
+        let mut open = true;
+        egui::Window::new(tr("History"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                if !project.authors().is_empty() {
+                    ui.label(tr_args("Authors: {}", &[&(project.authors().join(", "))]));
+                    ui.separator();
+                }
+                let entries = project.undo_history().len() + project.redo_history().len();
+                ui.label(tr_args("{} entries, about {} of {}", &[&entries, &(format_bytes(project.history_memory())), &format_bytes(project.history_limits.max_memory)]));
+                let mut limits = project.history_limits;
+                ui.horizontal(|ui| {
+                    ui.label(tr("Keep at most"));
+                    ui.add(egui::DragValue::new(&mut limits.max_entries).range(1..=1000));
+                    ui.label(tr("entries and"));
+                    let mut megabytes = limits.max_memory / (1024 * 1024);
+                    if ui
+                        .add(
+                            egui::DragValue::new(&mut megabytes)
+                                .range(1..=4096)
+                                .suffix(" MB"),
+                        )
+                        .changed()
+                    {
+                        limits.max_memory = megabytes * 1024 * 1024;
+                    }
+                })
+                .response
+                .on_hover_text(tr("Repeated changes like drag steps are merged first, then the oldest changes are dropped"));
+                if limits != project.history_limits {
+                    project.history_limits = limits;
+                    project.trim_history();
+                }
+                ui.checkbox(
+                    &mut project.save_history,
+                    tr("Save the history in the project file"),
+                )
+                .on_hover_text(tr("Keeps undo and the record of changes after reopening the project"));
+                ui.horizontal(|ui| {
+                    if ui
+                        .button(tr("Set checkpoint"))
+                        .on_hover_text(tr(
+                            "Objects modified after the checkpoint are marked in the object list",
+                        ))
+                        .clicked()
+                    {
+                        project.set_checkpoint();
+                    }
+                    if project.has_checkpoint() {
+                        ui.weak(tr("Objects modified since the checkpoint are marked"));
+                    }
+                });
+                if let Some(mask) = active_mask(project.get_pool()) {
+                    let next = project.next_undo_within(mask).map(|c| c.description.clone());
+                    let button = egui::Button::new(tr("Undo the last change within the active mask"));
+                    if ui
+                        .add_enabled(next.is_some(), button)
+                        .on_hover_text(next.unwrap_or_default())
+                        .on_disabled_hover_text(tr("No change only affected the active mask"))
+                        .clicked()
+                    {
+                        *scoped_undo_error = project.undo_within(mask).err();
+                    }
+                }
+                if let Some(error) = scoped_undo_error {
+                    ui.colored_label(egui::Color32::RED, error.as_str());
+                }
+                ui.separator();
+
+                let undo_history = project.undo_history();
+                let redo_history = project.redo_history();
+                if undo_history.is_empty() && redo_history.is_empty() {
+                    ui.label(tr("No changes yet"));
+                    return;
+                }
+
+                ui.horizontal(|ui| {
+                    ui.label(tr("Search:"));
+                    ui.text_edit_singleline(&mut filter.text);
+                    egui::ComboBox::from_id_salt("history_category_filter")
+                        .selected_text(filter.category.map_or(tr("All categories"), |c| tr(c.label())))
+                        .show_ui(ui, |ui| {
+                            ui.selectable_value(&mut filter.category, None, tr("All categories"));
+                            for category in ChangeCategory::ALL {
+                                ui.selectable_value(
+                                    &mut filter.category,
+                                    Some(category),
+                                    tr(category.label()),
+                                );
+                            }
+                        });
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(selected_only, tr("Only changes touching the selected object"));
+                    if *selected_only {
+                        filter.object = project.get_selected().into();
+                    } else {
+                        let pool = project.get_pool();
+                        let name = |id: ObjectId| {
+                            pool.object_by_id(id).map_or(id.value().to_string(), |o| {
+                                project.get_object_info(o).get_name(o)
+                            })
+                        };
+                        egui::ComboBox::from_id_salt("history_object_filter")
+                            .selected_text(filter.object.map_or(tr("Any object").to_string(), name))
+                            .show_ui(ui, |ui| {
+                                ui.selectable_value(&mut filter.object, None, tr("Any object"));
+                                for object in pool.objects() {
+                                    ui.selectable_value(
+                                        &mut filter.object,
+                                        Some(object.id()),
+                                        name(object.id()),
+                                    );
+                                }
+                            });
+                    }
+                    if filter.is_active() && ui.button(tr("Clear filters")).clicked() {
+                        *filter = HistoryFilter::default();
+                        *selected_only = false;
+                    }
+                });
+                ui.separator();
+
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("history_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.strong(tr("Change"));
+                                ui.strong(tr("Category"));
+                                ui.strong(tr("Author"));
+                                ui.end_row();
+                                // Each entry holds the pool before its change, the pool after
+                                // it is held by the next entry or is the current pool
+                                let current = project.get_pool();
+                                let mut shown = 0;
+                                for (index, change) in undo_history.iter().enumerate() {
+                                    let after = undo_history
+                                        .get(index + 1)
+                                        .map_or(current, |next| &next.pool);
+                                    if !filter.matches(change, &change.pool, after) {
+                                        continue;
+                                    }
+                                    shown += 1;
+                                    render_history_entry(
+                                        ui,
+                                        project,
+                                        change,
+                                        (&change.pool, after),
+                                        false,
+                                        ("undo", index),
+                                    );
+                                }
+                                // Redo entries hold the pool after their change instead
+                                for (index, change) in redo_history.iter().enumerate().rev() {
+                                    let before = redo_history
+                                        .get(index + 1)
+                                        .map_or(current, |next| &next.pool);
+                                    if !filter.matches(change, before, &change.pool) {
+                                        continue;
+                                    }
+                                    shown += 1;
+                                    render_history_entry(
+                                        ui,
+                                        project,
+                                        change,
+                                        (before, &change.pool),
+                                        true,
+                                        ("redo", index),
+                                    );
+                                }
+                                if shown == 0 {
+                                    ui.weak(tr("No changes match the filters"));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+            });
+
+        if !open {
+            self.show_history = false;
+        }
+    }
+}
+
+impl DesignerApp {
+    /// Window to compare screenshots of all masks against a baseline directory
+    fn render_regression_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_regression = false;
+            return;
+        };
+
+        let mut open = true;
+        egui::Window::new(tr("Screenshot regression"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let running = self
+                    .regression_run
+                    .as_ref()
+                    .is_some_and(|run| !run.is_finished());
+
+                ui.add_enabled_ui(!running, |ui| {
+                    ui.horizontal(|ui| {
+                        ui.label(tr("Baseline directory:"));
+                        ui.text_edit_singleline(&mut self.regression_dir);
+                        #[cfg(not(target_arch = "wasm32"))]
+                        if ui.button(tr("Browse...")).clicked() {
+                            if let Some(dir) = rfd::FileDialog::new().pick_folder() {
+                                self.regression_dir = dir.display().to_string();
+                            }
+                        }
+                    });
+                    ui.add(
+                        egui::Slider::new(&mut self.regression_tolerance, 0..=255)
+                            .text(tr("Tolerance per colour channel")),
+                    );
+                    ui.horizontal(|ui| {
+                        let has_dir = !self.regression_dir.trim().is_empty();
+                        for (update_baseline, label) in
+                            [(false, "Compare"), (true, "Update baseline")]
+                        {
+                            if ui
+                                .add_enabled(has_dir, egui::Button::new(tr(label)))
+                                .clicked()
+                            {
+                                self.regression_diff = None;
+                                self.regression_run = Some(RegressionRun::new(
+                                    project,
+                                    PathBuf::from(self.regression_dir.trim()),
+                                    self.regression_tolerance,
+                                    update_baseline,
+                                ));
+                            }
+                        }
+                    });
+                });
+
+                let Some(run) = &self.regression_run else {
+                    return;
+                };
+                ui.separator();
+                let (done, total) = run.progress();
+                if running {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr_args(
+                            "Rendering mask {} of {}...",
+                            &[&(done + 1), &total],
+                        ));
+                    });
+                } else if run.failures() > 0 {
+                    ui.colored_label(
+                        egui::Color32::RED,
+                        tr_args(
+                            "{} of {} masks differ from the baseline",
+                            &[&run.failures(), &total],
+                        ),
+                    );
+                } else {
+                    ui.label(tr_args("All {} masks passed", &[&total]));
+                }
+
+                egui::ScrollArea::vertical()
+                    .max_height(200.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("regression_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for mask in &run.results {
+                                    ui.label(format!("{}: {}", mask.mask.value(), mask.name));
+                                    if mask.result.is_failure() {
+                                        ui.colored_label(egui::Color32::RED, mask.result.summary());
+                                    } else {
+                                        ui.label(mask.result.summary());
+                                    }
+                                    if let ComparisonResult::Different { diff, .. } = &mask.result {
+                                        if ui.button(tr("Show diff")).clicked() {
+                                            let texture = ctx.load_texture(
+                                                "regression_diff",
+                                                diff.clone(),
+                                                egui::TextureOptions::NEAREST,
+                                            );
+                                            self.regression_diff = Some((mask.mask, texture));
+                                        }
+                                    }
+                                    ui.end_row();
+                                }
+                            });
+                    });
+
+                if let Some((mask, texture)) = &self.regression_diff {
+                    ui.separator();
+                    ui.label(tr_args("Differences of mask {} (red)", &[&mask.value()]));
+                    egui::ScrollArea::both().show(ui, |ui| {
+                        ui.image((texture.id(), texture.size_vec2()));
+                    });
+                }
+            });
+
+        if !open {
+            self.show_regression = false;
+        }
+    }
+
+    /// Load the given pool or project and start a regression run that closes the application
+    /// with an exit code when finished
+    #[cfg(not(target_arch = "wasm32"))]
+    fn start_command_line_regression(&mut self, args: RegressionArgs, exit_code: Arc<AtomicI32>) {
+        match load_project_file(&args.input) {
+            Ok(project) => {
+                self.regression_run = Some(RegressionRun::new(
+                    &project,
+                    args.baseline_dir,
+                    args.tolerance,
+                    args.update_baseline,
+                ));
+                self.project = Some(project);
+                self.show_development_popup = false;
+                self.regression_exit_code = Some(exit_code);
+            }
+            Err(e) => {
+                eprintln!("Failed to load {}: {}", args.input.display(), e);
+                std::process::exit(2);
+            }
+        }
+    }
+}
+
+/// State of the capture of terminal capabilities from the bus
+#[cfg(all(feature = "can-capture", target_os = "linux"))]
+struct CanCapture {
+    interface: String,
+    address: u8,
+    show_window: bool,
+
+    /// Receives the capabilities once the capture thread is finished
+    running: Option<Receiver<Result<TerminalCapabilities, String>>>,
+    error: Option<String>,
+}
+
+#[cfg(all(feature = "can-capture", target_os = "linux"))]
+impl Default for CanCapture {
+    fn default() -> Self {
+        CanCapture {
+            interface: "can0".to_string(),
+            address: DEFAULT_CAPTURE_ADDRESS,
+            show_window: false,
+            running: None,
+            error: None,
+        }
+    }
+}
+
+#[cfg(all(feature = "can-capture", target_os = "linux"))]
+impl DesignerApp {
+    /// Capture the capabilities on a separate thread, the bus exchange takes a few seconds
+    fn start_can_capture(&mut self, ctx: &egui::Context) {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let interface = self.can_capture.interface.clone();
+        let address = self.can_capture.address;
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            let result = SocketCanInterface::open(&interface)
+                .and_then(|mut bus| capture_capabilities(&mut bus, address));
+            let _ = sender.send(result);
+            ctx.request_repaint();
+        });
+        self.can_capture.running = Some(receiver);
+        self.can_capture.error = None;
+    }
+
+    /// Add a profile for the captured terminal once the capture is finished
+    fn handle_can_capture(&mut self) {
+        let Some(result) = self
+            .can_capture
+            .running
+            .as_ref()
+            .and_then(|receiver| receiver.try_recv().ok())
+        else {
+            return;
+        };
+        self.can_capture.running = None;
+        match (result, &mut self.project) {
+            (Ok(capabilities), Some(project)) => {
+                let mut profile = TerminalProfile {
+                    name: format!("Terminal on {}", self.can_capture.interface),
+                    ..Default::default()
+                };
+                profile.apply_capabilities(capabilities);
+                project.terminal_profiles.push(profile);
+                project.active_profile = project.terminal_profiles.len() - 1;
+                self.can_capture.show_window = false;
+                self.show_terminal_profiles = true;
+            }
+            (Ok(_), None) => {}
+            (Err(e), _) => self.can_capture.error = Some(e),
+        }
+    }
+
+    fn render_can_capture_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut start = false;
+        let running = self.can_capture.running.is_some();
+        egui::Window::new(tr("Capture terminal capabilities"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                ui.label(tr(
+                    "Asks the terminal on the bus for its hardware, memory, soft keys and fonts \
+                     and adds a terminal profile with the answers.",
+                ));
+                ui.horizontal(|ui| {
+                    ui.label(tr("Interface:"));
+                    ui.add_enabled(
+                        !running,
+                        egui::TextEdit::singleline(&mut self.can_capture.interface)
+                            .desired_width(80.0),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("Source address:"));
+                    ui.add_enabled(
+                        !running,
+                        egui::DragValue::new(&mut self.can_capture.address)
+                            .range(0..=253)
+                            .hexadecimal(2, false, true),
+                    );
+                });
+                ui.horizontal(|ui| {
+                    start = ui
+                        .add_enabled(
+                            !running && self.project.is_some(),
+                            egui::Button::new(tr("Capture")),
+                        )
+                        .clicked();
+                    if running {
+                        ui.spinner();
+                    }
+                });
+                if let Some(error) = &self.can_capture.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+            });
+
+        if start {
+            self.start_can_capture(ctx);
+        }
+        if !open {
+            self.can_capture.show_window = false;
+        }
+    }
+}
+
+/// State of the remote control server
+#[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+struct RemoteControl {
+    server: Option<RemoteServer>,
+    port: u16,
+    show_window: bool,
+
+    /// Error of the last attempt to start the server
+    error: Option<String>,
+
+    /// Screenshot request that is answered when its regression run is finished
+    pending_screenshot: Option<RemoteRequest>,
+}
+
+#[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+impl Default for RemoteControl {
+    fn default() -> Self {
+        RemoteControl {
+            server: None,
+            port: DEFAULT_REMOTE_PORT,
+            show_window: false,
+            error: None,
+            pending_screenshot: None,
+        }
+    }
+}
+
+#[cfg(all(feature = "remote-control", not(target_arch = "wasm32")))]
+impl DesignerApp {
+    /// Start the remote control server, replacing a running one
+    fn start_remote_server(&mut self, ctx: &egui::Context) {
+        self.remote.server = None;
+        match RemoteServer::start(self.remote.port, ctx.clone()) {
+            Ok(server) => {
+                log::info!("Remote control listening on ws://127.0.0.1:{}", server.port);
+                self.remote.server = Some(server);
+                self.remote.error = None;
+            }
+            Err(e) => {
+                log::error!("Failed to start remote control: {}", e);
+                self.remote.error = Some(e.to_string());
+            }
+        }
+    }
+
+    /// Execute the requests of remote clients
+    fn handle_remote_requests(&mut self, ctx: &egui::Context) {
+        let run_active = self
+            .regression_run
+            .as_ref()
+            .is_some_and(|run| !run.is_finished());
+        if !run_active {
+            if let Some(request) = self.remote.pending_screenshot.take() {
+                let results: Vec<_> = self
+                    .regression_run
+                    .iter()
+                    .flat_map(|run| {
+                        run.results.iter().map(|mask| {
+                            serde_json::json!({
+                                "mask": mask.mask.value(),
+                                "path": baseline_path(&run.baseline_dir, mask.mask),
+                                "result": mask.result.summary(),
+                            })
+                        })
+                    })
+                    .collect();
+                request.respond(Ok(serde_json::json!(results)));
+            }
+        }
+
+        let mut requests = Vec::new();
+        if let Some(server) = &self.remote.server {
+            while let Some(request) = server.try_recv() {
+                requests.push(request);
+            }
+        }
+        for request in requests {
+            if request.method == "screenshot" {
+                self.start_remote_screenshot(request);
+                continue;
+            }
+            let result = self.execute_remote_request(&request, ctx);
+            request.respond(result);
+        }
+    }
+
+    /// Render masks into a directory like a regression run that updates its baseline
+    fn start_remote_screenshot(&mut self, request: RemoteRequest) {
+        let Some(project) = &self.project else {
+            request.respond(Err(RemoteError::new("No project loaded")));
+            return;
+        };
+        if self
+            .regression_run
+            .as_ref()
+            .is_some_and(|run| !run.is_finished())
+        {
+            request.respond(Err(RemoteError::new("A screenshot run is already active")));
+            return;
+        }
+        let dir = match request.param_str("dir") {
+            Ok(dir) => PathBuf::from(dir),
+            Err(e) => {
+                request.respond(Err(e));
+                return;
+            }
+        };
+
+        let mut run = RegressionRun::new(project, dir, 0, true);
+        if request.params.get("masks").is_some() {
+            let masks = request.params["masks"]
+                .as_array()
+                .into_iter()
+                .flatten()
+                .filter_map(|id| id.as_u64())
+                .filter_map(|id| u16::try_from(id).ok())
+                .filter_map(|id| ObjectId::new(id).ok())
+                .collect();
+            run = run.with_masks(masks);
+        }
+        self.regression_run = Some(run);
+        self.remote.pending_screenshot = Some(request);
+    }
+
+    /// Execute a request that can be answered immediately
+    fn execute_remote_request(
+        &mut self,
+        request: &RemoteRequest,
+        ctx: &egui::Context,
+    ) -> RemoteResult {
+        if request.method == "open" {
+            let path = PathBuf::from(request.param_str("path")?);
+            let project = load_project_file(&path).map_err(RemoteError::new)?;
+            let objects = project.get_pool().objects().len();
+            self.project = Some(project);
+            self.recovery_report = None;
+            ctx.request_repaint();
+            return Ok(serde_json::json!({ "objects": objects }));
+        }
+
+        let project = self
+            .project
+            .as_mut()
+            .ok_or_else(|| RemoteError::new("No project loaded"))?;
+        match request.method.as_str() {
+            "save" => {
+                let path = PathBuf::from(request.param_str("path")?);
+                let data = if path.extension().is_some_and(|ext| ext == "iop") {
+                    project.get_pool().as_iop()
+                } else {
+                    project
+                        .save_project()
+                        .map_err(|e| RemoteError::new(e.to_string()))?
+                };
+                std::fs::write(&path, data).map_err(|e| RemoteError::new(e.to_string()))?;
+                project.mark_saved();
+                Ok(serde_json::Value::Null)
+            }
+            "set_variable" => {
+                let id = request.param_object_id("id")?;
+                let value = request.param("value")?;
+                let mut pool = project.get_mut_pool().borrow_mut();
+                match pool.object_mut_by_id(id) {
+                    Some(Object::NumberVariable(o)) => {
+                        o.value = value
+                            .as_u64()
+                            .and_then(|v| u32::try_from(v).ok())
+                            .ok_or_else(|| {
+                                RemoteError::invalid_params(
+                                    "Value must be a 32 bit unsigned number",
+                                )
+                            })?;
+                    }
+                    Some(Object::StringVariable(o)) => {
+                        o.value = value
+                            .as_str()
+                            .ok_or_else(|| RemoteError::invalid_params("Value must be a string"))?
+                            .to_string();
+                    }
+                    _ => {
+                        return Err(RemoteError::new(
+                            "Object is not a number or string variable",
+                        ))
+                    }
+                }
+                project.describe_change(
+                    format!("Remote: set variable {}", id.value()),
+                    ChangeCategory::Edit,
+                );
+                Ok(serde_json::Value::Null)
+            }
+            "switch_mask" => {
+                let id = request.param_object_id("id")?;
+                let is_mask = project.get_pool().object_by_id(id).is_some_and(|o| {
+                    matches!(
+                        o.object_type(),
+                        ObjectType::DataMask | ObjectType::AlarmMask
+                    )
+                });
+                if !is_mask {
+                    return Err(RemoteError::new("Object is not a data or alarm mask"));
+                }
+                let working_set = project
+                    .get_pool()
+                    .working_set_object()
+                    .map(|ws| ws.id)
+                    .ok_or_else(|| RemoteError::new("The pool has no working set"))?;
+                if let Some(Object::WorkingSet(o)) = project
+                    .get_mut_pool()
+                    .borrow_mut()
+                    .object_mut_by_id(working_set)
+                {
+                    o.active_mask = id;
+                }
+                project.describe_change(
+                    format!("Remote: switch to mask {}", id.value()),
+                    ChangeCategory::Edit,
+                );
+                Ok(serde_json::Value::Null)
+            }
+            "validate" => {
+                let issues: Vec<_> = validate_project(project)
+                    .iter()
+                    .chain(self.plugin_issues.iter())
+                    .map(|issue| {
+                        serde_json::json!({
+                            "severity": match issue.severity {
+                                Severity::Error => "error",
+                                Severity::Warning => "warning",
+                            },
+                            "object": issue.object.map(|id| id.value()),
+                            "message": issue.message,
+                        })
+                    })
+                    .collect();
+                Ok(serde_json::json!(issues))
+            }
+            method => Err(RemoteError::method_not_found(method)),
+        }
+    }
+
+    /// Window to start and stop the remote control server
+    fn render_remote_control_window(&mut self, ctx: &egui::Context) {
+        let mut open = true;
+        let mut start = false;
+        egui::Window::new(tr("Remote control"))
+            .open(&mut open)
+            .resizable(false)
+            .show(ctx, |ui| {
+                match &self.remote.server {
+                    Some(server) => {
+                        ui.label(tr_args("Listening on ws://127.0.0.1:{}", &[&server.port]));
+                        ui.horizontal(|ui| {
+                            ui.label(tr("Token:"));
+                            ui.monospace(&server.token);
+                            if ui.small_button(tr("Copy")).clicked() {
+                                ui.ctx().copy_text(server.token.clone());
+                            }
+                        });
+                        ui.label(
+                            "Clients send the token as \"Authorization: Bearer <token>\" header \
+                             or ?token=<token> in the URL",
+                        );
+                        if ui.button(tr("Stop")).clicked() {
+                            self.remote.server = None;
+                        }
+                    }
+                    None => {
+                        ui.horizontal(|ui| {
+                            ui.label(tr("Port:"));
+                            ui.add(egui::DragValue::new(&mut self.remote.port).range(1024..=65535));
+                            start = ui.button(tr("Start")).clicked();
+                        });
+                    }
+                }
+                if let Some(error) = &self.remote.error {
+                    ui.colored_label(egui::Color32::RED, error);
+                }
+                ui.separator();
+                ui.label(tr("JSON-RPC 2.0 methods:"));
+                for method in [
+                    "open {path}",
+                    "save {path}",
+                    "set_variable {id, value}",
+                    "switch_mask {id}",
+                    "screenshot {dir, masks?}",
+                    "validate",
+                ] {
+                    ui.monospace(method);
+                }
+            });
+
+        if start {
+            self.start_remote_server(ctx);
+        }
+        if !open {
+            self.remote.show_window = false;
+        }
+    }
+}
+
+/// Load a project (.aitp) or object pool file from disk
+#[cfg(not(target_arch = "wasm32"))]
+fn load_project_file(path: &std::path::Path) -> Result<EditorProject, String> {
+    std::fs::read(path)
+        .map_err(|e| e.to_string())
+        .and_then(|data| {
+            if path.extension().is_some_and(|ext| ext == "aitp") {
+                EditorProject::load_project(data)
+            } else {
+                Ok(EditorProject::from(ObjectPool::from_iop(data)))
+            }
+        })
+}
 
-            // Parameters panel
-            egui::SidePanel::right("right_panel").show(ctx, |ui: &mut egui::Ui| {
-                if let Some(id) = pool.get_selected().into() {
-                    if let Some(obj) = pool.get_mut_pool().borrow_mut().object_mut_by_id(id) {
-                        egui::ScrollArea::vertical().show(ui, |ui| {
-                            // Display editable object name as header
-                            ui.horizontal(|ui| {
-                                ui.label("Name:");
+/// Print the results of a regression run to the terminal
+fn print_regression_report(run: &RegressionRun) {
+    for mask in &run.results {
+        println!(
+            "{} {:>5} {}: {}",
+            if mask.result.is_failure() {
+                "FAIL"
+            } else {
+                "ok  "
+            },
+            mask.mask.value(),
+            mask.name,
+            mask.result.summary()
+        );
+    }
+    println!("{} of {} masks failed", run.failures(), run.progress().1);
+}
+
+/// Command line arguments of a screenshot regression run
+#[cfg(not(target_arch = "wasm32"))]
+struct RegressionArgs {
+    input: PathBuf,
+    baseline_dir: PathBuf,
+    tolerance: u8,
+    update_baseline: bool,
+}
+
+/// Parse `--regression <pool.iop|project.aitp> <baseline dir> [--tolerance <0-255>] [--update-baseline]`
+#[cfg(not(target_arch = "wasm32"))]
+fn parse_regression_args(args: &[String]) -> Result<Option<RegressionArgs>, String> {
+    let Some(position) = args.iter().position(|a| a == "--regression") else {
+        return Ok(None);
+    };
+    let usage = "Usage: --regression <pool.iop|project.aitp> <baseline dir> [--tolerance <0-255>] [--update-baseline]";
+
+    let mut args = args[position + 1..].iter();
+    let input = args.next().ok_or(usage)?;
+    let baseline_dir = args.next().ok_or(usage)?;
+    let mut regression = RegressionArgs {
+        input: PathBuf::from(input),
+        baseline_dir: PathBuf::from(baseline_dir),
+        tolerance: DEFAULT_TOLERANCE,
+        update_baseline: false,
+    };
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--tolerance" => {
+                regression.tolerance = args.next().and_then(|t| t.parse().ok()).ok_or(usage)?;
+            }
+            "--update-baseline" => regression.update_baseline = true,
+            _ => return Err(usage.to_string()),
+        }
+    }
+    Ok(Some(regression))
+}
+
+impl DesignerApp {
+    /// Window that lists all validation issues of the project
+    fn render_validation_window(&mut self, ctx: &egui::Context) {
+        let Some(project) = &self.project else {
+            self.show_validation = false;
+            return;
+        };
 
-                                let object_info = pool.get_object_info(obj);
-                                let mut name = object_info.get_name(obj);
-                                let response = ui.text_edit_singleline(&mut name);
+        let mut open = true;
+        egui::Window::new(tr("Validation"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                let mut issues = validate_project(project);
+                issues.extend(self.plugin_issues.iter().cloned());
+                issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+                if issues.is_empty() {
+                    ui.label(tr("No issues found"));
+                    return;
+                }
 
-                                if response.changed() {
-                                    let mut object_info_map = pool.object_info.borrow_mut();
-                                    if let Some(info) = object_info_map.get_mut(&obj.id()) {
-                                        info.set_name(name);
+                egui::ScrollArea::vertical()
+                    .max_height(400.0)
+                    .show(ui, |ui| {
+                        for issue in issues {
+                            let (icon, colour) = match issue.severity {
+                                Severity::Error => ("\u{26D4}", egui::Color32::RED),
+                                Severity::Warning => ("\u{26A0}", egui::Color32::YELLOW),
+                            };
+                            ui.horizontal(|ui| {
+                                ui.colored_label(colour, icon);
+                                match issue.object {
+                                    Some(id) => {
+                                        if ui.link(&issue.message).clicked() {
+                                            project
+                                                .get_mut_selected()
+                                                .replace(NullableObjectId(Some(id)));
+                                        }
+                                    }
+                                    None => {
+                                        ui.label(&issue.message);
                                     }
                                 }
                             });
-                            ui.separator();
+                        }
+                    });
 
-                            obj.render_parameters(ui, pool);
-                            let (width, height) = pool.get_pool().content_size(obj);
-                            ui.separator();
-                            let desired_size = egui::Vec2::new(width as f32, height as f32);
-                            ui.allocate_ui(desired_size, |ui| {
-                                obj.render(ui, pool.get_pool(), Point::default());
+                let dangling = dangling_references(project.get_pool());
+                if dangling.is_empty() {
+                    self.reference_repair_error = None;
+                    return;
+                }
+                ui.separator();
+                egui::CollapsingHeader::new(tr("Repair dangling references"))
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        egui::ScrollArea::vertical()
+                            .id_salt("dangling_references")
+                            .max_height(300.0)
+                            .show(ui, |ui| {
+                                for reference in dangling {
+                                    match render_dangling_reference_repair(ui, project, reference) {
+                                        Some(Ok(())) => self.reference_repair_error = None,
+                                        Some(Err(e)) => self.reference_repair_error = Some(e),
+                                        None => {}
+                                    }
+                                }
                             });
-                        });
-                    } else {
-                        ui.colored_label(
-                            egui::Color32::RED,
-                            format!("Selected object not found: {}", u16::from(id)),
-                        );
-                    }
+                        if let Some(error) = &self.reference_repair_error {
+                            ui.colored_label(egui::Color32::RED, error);
+                        }
+                    });
+            });
+
+        if !open {
+            self.show_validation = false;
+            self.reference_repair_error = None;
+        }
+    }
+
+    /// Wizard to duplicate a mask, choosing per child subtree whether it is shared or copied
+    fn render_mask_duplication_window(&mut self, ctx: &egui::Context) {
+        let (Some(project), Some(duplication)) = (&self.project, &mut self.mask_duplication) else {
+            self.mask_duplication = None;
+            return;
+        };
+
+        let mut open = true;
+        let mut done = false;
+        egui::Window::new(tr("Duplicate mask"))
+            .open(&mut open)
+            .resizable(true)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("Name:"));
+                    ui.text_edit_singleline(&mut duplication.name);
+                });
+                ui.separator();
+
+                if duplication.choices.is_empty() {
+                    ui.label(tr("The mask has no children"));
                 }
-                ui.allocate_space(ui.available_size());
+                egui::ScrollArea::vertical()
+                    .max_height(300.0)
+                    .show(ui, |ui| {
+                        egui::Grid::new("mask_duplication_grid")
+                            .striped(true)
+                            .show(ui, |ui| {
+                                for (id, mode) in duplication.choices.iter_mut() {
+                                    match project.get_pool().object_by_id(*id) {
+                                        Some(object) => ui.label(format!(
+                                            "{}: {:?} - {}",
+                                            id.value(),
+                                            object.object_type(),
+                                            project.get_object_info(object).get_name(object)
+                                        )),
+                                        None => ui.colored_label(
+                                            egui::Color32::RED,
+                                            tr_args("Missing object {}", &[&id.value()]),
+                                        ),
+                                    };
+                                    ui.selectable_value(mode, ShareMode::Share, tr("Share"))
+                                        .on_hover_text(tr("Reference the existing objects"));
+                                    ui.selectable_value(mode, ShareMode::Copy, tr("Copy"))
+                                        .on_hover_text(tr("Copy the objects with new IDs"));
+                                    ui.end_row();
+                                }
+                            });
+                    });
+                ui.checkbox(
+                    &mut duplication.copy_shared_types,
+                    tr("Also copy variables and attributes inside copied objects"),
+                );
+                ui.separator();
+
+                let count = duplication.objects_to_copy(project).len();
+                ui.label(tr_args("{} objects will be created", &[&count]));
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Duplicate")).clicked() {
+                        if let Err(e) = duplication.apply(project) {
+                            log::error!("Failed to duplicate mask: {}", e);
+                        }
+                        done = true;
+                    }
+                    if ui.button(tr("Cancel")).clicked() {
+                        done = true;
+                    }
+                });
             });
 
-            if pool.update_pool() {
-                ctx.request_repaint();
+        if !open || done {
+            self.mask_duplication = None;
+        }
+    }
+}
+
+/// Row of the history grid, expanding it shows the changed objects and attributes
+fn render_history_entry(
+    ui: &mut egui::Ui,
+    project: &EditorProject,
+    change: &Change,
+    (before, after): (&ObjectPool, &ObjectPool),
+    undone: bool,
+    id_salt: impl std::hash::Hash,
+) {
+    let mut description = egui::RichText::new(&change.description);
+    let mut author = egui::RichText::new(if change.author.is_empty() {
+        "Unknown"
+    } else {
+        &change.author
+    });
+    if undone {
+        description = description.weak().strikethrough();
+        author = author.weak();
+    }
+    egui::CollapsingHeader::new(description)
+        .id_salt(id_salt)
+        .show(ui, |ui| {
+            // The pools of an entry only change with the history, so the diff is kept until then
+            let cache_id = ui.id().with("history_entry_diff");
+            let generation = (
+                project.pool_generation(),
+                project.undo_history().len(),
+                project.redo_history().len(),
+            );
+            let cached = ui.data(|data| {
+                data.get_temp::<((u64, usize, usize), Arc<Vec<ObjectDiff>>)>(cache_id)
+                    .filter(|(cached_generation, _)| *cached_generation == generation)
+            });
+            let diffs = match cached {
+                Some((_, diffs)) => diffs,
+                None => {
+                    let diffs = Arc::new(diff_pools(before, after));
+                    ui.data_mut(|data| data.insert_temp(cache_id, (generation, diffs.clone())));
+                    diffs
+                }
+            };
+            if diffs.is_empty() {
+                ui.weak(tr("No object changes"));
             }
-            if pool.update_selected() {
-                // Make sure all collapsing headers for the selected object are open
-                if let Some(working_set) = pool.get_pool().working_set_object() {
-                    update_object_hierarchy_headers(
-                        ctx,
-                        egui::Id::new(OBJECT_HIERARCHY_ID),
-                        &Object::WorkingSet(working_set.clone()),
-                        pool.get_pool(),
-                        pool.get_selected(),
-                    );
+            for diff in diffs.iter() {
+                let label = object_label(project, diff);
+                match &diff.change {
+                    ObjectChange::Added => {
+                        ui.label(tr_args("Added {}", &[&label]));
+                    }
+                    ObjectChange::Removed => {
+                        ui.label(tr_args("Removed {}", &[&label]));
+                    }
+                    ObjectChange::Modified(attributes) => {
+                        ui.label(tr_args("Modified {}", &[&label]));
+                        for attribute in attributes {
+                            render_attribute_change(ui, attribute);
+                        }
+                    }
                 }
-                ctx.request_repaint();
             }
-        } else {
-            egui::CentralPanel::default().show(ctx, |ui| {
-                ui.label("No object pool loaded, please load a pool file...");
+        });
+    ui.weak(tr(change.category.label()));
+    ui.label(author);
+    ui.end_row();
+}
+
+/// Let the user tag the colour attributes of an object with a colour role
+fn render_colour_role_tags(ui: &mut egui::Ui, obj: &Object, project: &EditorProject) {
+    let attributes = colour_attributes(obj);
+    if attributes.is_empty() {
+        return;
+    }
+
+    ui.separator();
+    egui::CollapsingHeader::new(tr("Colour roles")).show(ui, |ui| {
+        let mut object_info = project.object_info.borrow_mut();
+        let Some(info) = object_info.get_mut(&obj.id()) else {
+            return;
+        };
+        for (attribute, _) in attributes {
+            let mut role = info.colour_roles.get(attribute).cloned();
+            egui::ComboBox::from_label(attribute)
+                .selected_text(role.as_deref().unwrap_or("None"))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut role, None, tr("None"));
+                    for name in &project.colour_roles.roles {
+                        ui.selectable_value(&mut role, Some(name.clone()), name);
+                    }
+                });
+            match role {
+                Some(role) => info.colour_roles.insert(attribute.to_string(), role),
+                None => info.colour_roles.remove(attribute),
+            };
+        }
+    });
+}
+
+/// The object type a preset attribute references, e.g. font attributes
+fn preset_attribute_object_type(attribute: &str) -> Option<ObjectType> {
+    match attribute {
+        "Font attributes" => Some(ObjectType::FontAttributes),
+        "Line attributes" => Some(ObjectType::LineAttributes),
+        "Fill attributes" => Some(ObjectType::FillAttributes),
+        _ => None,
+    }
+}
+
+/// Editor for the value of an attribute in a style preset
+fn render_preset_value(
+    ui: &mut egui::Ui,
+    project: &EditorProject,
+    attribute: &str,
+    value: &mut PresetValue,
+) {
+    match value {
+        PresetValue::Colour(colour) => {
+            ui.horizontal(|ui| {
+                ui.add(egui::DragValue::new(colour).range(0..=255));
+                let swatch = project.get_pool().color_by_index(*colour);
+                let (rect, _) =
+                    ui.allocate_exact_size(egui::vec2(16.0, 16.0), egui::Sense::hover());
+                ui.painter().rect_filled(
+                    rect,
+                    2.0,
+                    egui::Color32::from_rgb(swatch.r, swatch.g, swatch.b),
+                );
+            });
+        }
+        PresetValue::Object(id) => {
+            let pool = project.get_pool();
+            let candidates = preset_attribute_object_type(attribute)
+                .map(|object_type| pool.objects_by_type(object_type))
+                .unwrap_or_default();
+            let label = |id: Option<u16>| {
+                id.and_then(|id| ObjectId::new(id).ok())
+                    .and_then(|id| pool.object_by_id(id))
+                    .map_or(tr("None").to_string(), |object| {
+                        format!(
+                            "{}: {}",
+                            object.id().value(),
+                            project.get_object_info(object).get_name(object)
+                        )
+                    })
+            };
+            egui::ComboBox::from_id_salt(("style_preset_object", attribute))
+                .selected_text(label(*id))
+                .show_ui(ui, |ui| {
+                    // Only the fill attributes are optional
+                    if attribute == "Fill attributes" {
+                        ui.selectable_value(id, None, tr("None"));
+                    }
+                    for object in candidates {
+                        let value = Some(object.id().value());
+                        ui.selectable_value(id, value, label(value));
+                    }
+                });
+        }
+        PresetValue::Flag(flag) => {
+            ui.checkbox(flag, "");
+        }
+        PresetValue::Justification(justification) => {
+            ui.horizontal(|ui| {
+                let mut horizontal = *justification & 0x03;
+                let mut vertical = (*justification >> 2) & 0x03;
+                egui::ComboBox::from_id_salt(("style_preset_horizontal", attribute))
+                    .selected_text(["Left", "Middle", "Right", "Reserved"][horizontal as usize])
+                    .show_ui(ui, |ui| {
+                        for (index, name) in ["Left", "Middle", "Right"].into_iter().enumerate() {
+                            ui.selectable_value(&mut horizontal, index as u8, name);
+                        }
+                    });
+                egui::ComboBox::from_id_salt(("style_preset_vertical", attribute))
+                    .selected_text(["Top", "Middle", "Bottom", "Reserved"][vertical as usize])
+                    .show_ui(ui, |ui| {
+                        for (index, name) in ["Top", "Middle", "Bottom"].into_iter().enumerate() {
+                            ui.selectable_value(&mut vertical, index as u8, name);
+                        }
+                    });
+                *justification = horizontal | (vertical << 2);
             });
         }
     }
 }
 
+/// The style preset of an object, assigning a preset applies it
+fn render_style_preset_assignment(ui: &mut egui::Ui, obj: &mut Object, project: &EditorProject) {
+    if project.style_presets.is_empty() || preset_values(obj).is_empty() {
+        return;
+    }
+
+    ui.separator();
+    egui::CollapsingHeader::new(tr("Style preset")).show(ui, |ui| {
+        let mut object_info = project.object_info.borrow_mut();
+        let Some(info) = object_info.get_mut(&obj.id()) else {
+            return;
+        };
+        let mut assigned = info.style_preset.clone();
+        ui.horizontal(|ui| {
+            egui::ComboBox::from_id_salt("style_preset_assignment")
+                .selected_text(assigned.clone().unwrap_or_else(|| tr("None").to_string()))
+                .show_ui(ui, |ui| {
+                    ui.selectable_value(&mut assigned, None, tr("None"));
+                    for preset in &project.style_presets {
+                        ui.selectable_value(&mut assigned, Some(preset.name.clone()), &preset.name);
+                    }
+                });
+            let preset = assigned
+                .as_ref()
+                .and_then(|name| project.style_presets.iter().find(|p| &p.name == name));
+            if let Some(preset) = preset {
+                if ui
+                    .button(tr("Re-apply"))
+                    .on_hover_text(tr("Reset the attributes of the preset to its values"))
+                    .clicked()
+                    && apply_style_preset_to(obj, preset) > 0
+                {
+                    project.describe_change(
+                        format!("Apply style preset '{}'", preset.name),
+                        ChangeCategory::Edit,
+                    );
+                }
+            }
+        });
+        if assigned != info.style_preset {
+            let preset = assigned
+                .as_ref()
+                .and_then(|name| project.style_presets.iter().find(|p| &p.name == name));
+            if let Some(preset) = preset {
+                apply_style_preset_to(obj, preset);
+                project.describe_change(
+                    format!("Apply style preset '{}'", preset.name),
+                    ChangeCategory::Edit,
+                );
+            }
+            info.style_preset = assigned;
+        }
+    });
+}
+
+/// Fixes for a dangling reference: remove it, point it to a chosen object or recreate the missing
+/// object. Returns the result of the fix that was clicked, if any.
+fn render_dangling_reference_repair(
+    ui: &mut egui::Ui,
+    project: &EditorProject,
+    dangling: DanglingReference,
+) -> Option<Result<(), String>> {
+    let pool = project.get_pool();
+    let object = pool.object_by_id(dangling.object)?;
+    let types = expected_types(object, dangling.missing);
+    let label = |object: &Object| {
+        format!(
+            "{}: {}",
+            object.id().value(),
+            project.get_object_info(object).get_name(object)
+        )
+    };
+
+    let mut result = None;
+    ui.horizontal_wrapped(|ui| {
+        let text = tr_args(
+            "{} references missing object {}",
+            &[&label(object), &dangling.missing.value()],
+        );
+        if ui.link(text).clicked() {
+            project
+                .get_mut_selected()
+                .replace(NullableObjectId(Some(dangling.object)));
+        }
+        if ui.button(tr("Remove reference")).clicked() {
+            result = Some(remove_dangling_reference(project, dangling));
+        }
+
+        // Replacements of the expected types, or any object if the type is not known
+        let replacement_id = egui::Id::new(("dangling_replacement", dangling));
+        let mut replacement = ui
+            .data(|data| data.get_temp::<Option<ObjectId>>(replacement_id))
+            .flatten();
+        egui::ComboBox::from_id_salt(replacement_id)
+            .selected_text(
+                replacement
+                    .and_then(|id| pool.object_by_id(id))
+                    .map_or(tr("Choose replacement").to_string(), label),
+            )
+            .show_ui(ui, |ui| {
+                for candidate in pool
+                    .objects()
+                    .iter()
+                    .filter(|o| types.is_empty() || types.contains(&o.object_type()))
+                {
+                    ui.selectable_value(&mut replacement, Some(candidate.id()), label(candidate));
+                }
+            });
+        ui.data_mut(|data| data.insert_temp(replacement_id, replacement));
+        if let Some(replacement) = replacement {
+            if ui.button(tr("Point to replacement")).clicked() {
+                result = Some(redirect_dangling_reference(project, dangling, replacement));
+            }
+        }
+
+        if let Some(first) = types.first() {
+            let type_id = egui::Id::new(("dangling_type", dangling));
+            let mut object_type = ui
+                .data(|data| data.get_temp::<ObjectType>(type_id))
+                .filter(|object_type| types.contains(object_type))
+                .unwrap_or(*first);
+            egui::ComboBox::from_id_salt(type_id)
+                .selected_text(format!("{:?}", object_type))
+                .show_ui(ui, |ui| {
+                    for candidate in &types {
+                        ui.selectable_value(
+                            &mut object_type,
+                            *candidate,
+                            format!("{:?}", candidate),
+                        );
+                    }
+                });
+            ui.data_mut(|data| data.insert_temp(type_id, object_type));
+            if ui
+                .button(tr("Recreate"))
+                .on_hover_text(tr(
+                    "Create an object with the missing ID and default attributes",
+                ))
+                .clicked()
+            {
+                result = Some(recreate_missing_object(project, dangling, object_type));
+            }
+        }
+    });
+    ui.separator();
+    result
+}
+
+/// Show the mask in the preview and select it, the active mask of the working set is kept
+fn show_mask(ctx: &egui::Context, project: &EditorProject, mask: ObjectId) {
+    project
+        .get_mut_selected()
+        .replace(NullableObjectId(Some(mask)));
+    let mask_override = (active_mask(project.get_pool()) != Some(mask)).then_some(mask);
+    with_preview_state(ctx, |state| state.mask_override = mask_override);
+}
+
+/// The mask the working set shows
+fn active_mask(pool: &ObjectPool) -> Option<ObjectId> {
+    pool.working_set_object()
+        .map(|working_set| working_set.active_mask)
+}
+
+/// Old and new value of a changed attribute, indented below the changed object
+fn render_attribute_change(ui: &mut egui::Ui, attribute: &AttributeChange) {
+    ui.horizontal_wrapped(|ui| {
+        ui.add_space(ui.spacing().indent);
+        ui.label(format!("{}:", attribute.attribute));
+        ui.label(egui::RichText::new(&attribute.old_value).color(egui::Color32::RED));
+        ui.label("\u{2192}");
+        ui.label(egui::RichText::new(&attribute.new_value).color(egui::Color32::GREEN));
+    });
+}
+
+/// Every change in the history that affected the object, newest first, with the author and the
+/// changed attributes
+fn render_object_history(ui: &mut egui::Ui, obj: &Object, project: &EditorProject) {
+    ui.separator();
+    egui::CollapsingHeader::new(tr("Change history")).show(ui, |ui| {
+        let entries = object_history(
+            project.get_pool(),
+            project.undo_history(),
+            project.redo_history(),
+            obj.id(),
+        );
+        if entries.is_empty() {
+            ui.weak(tr("No changes to this object in the history"));
+            return;
+        }
+        for entry in entries.iter().rev() {
+            let change = entry.change;
+            let mut description = egui::RichText::new(&change.description).strong();
+            if entry.undone {
+                description = description.weak().strikethrough();
+            }
+            ui.horizontal_wrapped(|ui| {
+                ui.label(description);
+                ui.weak(format!(
+                    "{}, {}",
+                    tr(change.category.label()),
+                    if change.author.is_empty() {
+                        tr("Unknown")
+                    } else {
+                        &change.author
+                    }
+                ));
+            });
+            match &entry.object_change {
+                ObjectChange::Added => {
+                    ui.label(tr("Added"));
+                }
+                ObjectChange::Removed => {
+                    ui.label(tr("Removed"));
+                }
+                ObjectChange::Modified(attributes) => {
+                    for attribute in attributes {
+                        render_attribute_change(ui, attribute);
+                    }
+                }
+            }
+        }
+    });
+}
+
+/// Free text notes of an object
+fn render_notes(ui: &mut egui::Ui, obj: &Object, project: &EditorProject) {
+    ui.separator();
+    egui::CollapsingHeader::new(tr("Notes")).show(ui, |ui| {
+        let mut object_info = project.object_info.borrow_mut();
+        let Some(info) = object_info.get_mut(&obj.id()) else {
+            return;
+        };
+        ui.add(egui::TextEdit::multiline(&mut info.notes).hint_text(tr(
+            "Lines with TODO or FIXME are listed in the tasks window",
+        )));
+    });
+}
+
+/// Review status and comment threads of an object
+fn render_review(ui: &mut egui::Ui, obj: &Object, project: &EditorProject, reviewer: &str) {
+    ui.separator();
+    egui::CollapsingHeader::new(tr("Review")).show(ui, |ui| {
+        let mut object_info = project.object_info.borrow_mut();
+        let Some(info) = object_info.get_mut(&obj.id()) else {
+            return;
+        };
+
+        ui.horizontal(|ui| {
+            for status in ReviewStatus::ALL {
+                ui.selectable_value(&mut info.review.status, status, tr(status.label()));
+            }
+        });
+        if reviewer.is_empty() {
+            ui.colored_label(
+                egui::Color32::YELLOW,
+                tr("Set your author name in the settings to post comments"),
+            );
+        }
+
+        let draft_id = ui.id().with(("review_draft", obj.id()));
+        let mut draft = ui
+            .data(|data| data.get_temp::<ReviewDraft>(draft_id))
+            .unwrap_or_default();
+
+        for (index, comment) in info.review.comments.iter().enumerate() {
+            render_review_comment(ui, comment, vec![index], &mut draft.reply_to);
+        }
+
+        let hint = if draft.reply_to.is_some() {
+            tr("Write a reply...")
+        } else {
+            tr("Write a comment...")
+        };
+        ui.add(egui::TextEdit::multiline(&mut draft.text).hint_text(hint));
+        ui.horizontal(|ui| {
+            let can_post = !draft.text.trim().is_empty() && !reviewer.is_empty();
+            if ui
+                .add_enabled(can_post, egui::Button::new(tr("Post")))
+                .clicked()
+            {
+                let comment = ReviewComment::new(reviewer.to_string(), draft.text.clone());
+                let path = draft.reply_to.clone().unwrap_or_default();
+                if info.review.add_comment(&path, comment) {
+                    draft = ReviewDraft::default();
+                }
+            }
+            if draft.reply_to.is_some() && ui.button(tr("Cancel reply")).clicked() {
+                draft.reply_to = None;
+            }
+        });
+
+        ui.data_mut(|data| data.insert_temp(draft_id, draft));
+    });
+}
+
+/// Render a review comment and its replies
+fn render_review_comment(
+    ui: &mut egui::Ui,
+    comment: &ReviewComment,
+    path: Vec<usize>,
+    reply_to: &mut Option<Vec<usize>>,
+) {
+    ui.group(|ui| {
+        ui.horizontal(|ui| {
+            ui.strong(&comment.author);
+            let replying = reply_to.as_ref() == Some(&path);
+            if ui.selectable_label(replying, tr("Reply")).clicked() {
+                *reply_to = if replying { None } else { Some(path.clone()) };
+            }
+        });
+        ui.label(&comment.text);
+        ui.indent(("review_replies", &path), |ui| {
+            for (index, reply) in comment.replies.iter().enumerate() {
+                let mut reply_path = path.clone();
+                reply_path.push(index);
+                render_review_comment(ui, reply, reply_path, reply_to);
+            }
+        });
+    });
+}
+
 // When compiling natively:
 #[cfg(not(target_arch = "wasm32"))]
 fn main() {
     env_logger::init(); // Log to stderr (if you run with `RUST_LOG=debug`).
 
+    let args: Vec<String> = std::env::args().collect();
+    let regression = match parse_regression_args(&args) {
+        Ok(regression) => regression,
+        Err(usage) => {
+            eprintln!("{}", usage);
+            std::process::exit(2);
+        }
+    };
+    let regression_mode = regression.is_some();
+
+    // `--register-file-types` makes the designer the application for pools, projects and
+    // workspaces of the current user
+    if args.iter().any(|a| a == "--register-file-types") {
+        let executable = std::env::current_exe().unwrap_or_else(|_| PathBuf::from(&args[0]));
+        match register_file_associations(&executable) {
+            Ok(log) => log.iter().for_each(|line| println!("{}", line)),
+            Err(e) => {
+                eprintln!("{}", e);
+                std::process::exit(1);
+            }
+        }
+        return;
+    }
+
+    // Files passed on the command line, e.g. by double-clicking them, open in the running
+    // instance if there is one. `--new-instance` always opens a new window.
+    let files = if regression_mode {
+        Vec::new()
+    } else {
+        file_arguments(&args)
+    };
+    if !files.is_empty()
+        && !args.iter().any(|a| a == "--new-instance")
+        && forward_to_running_instance(&files)
+    {
+        return;
+    }
+    // `--remote-control [port]` starts the remote control server on launch
+    #[cfg(feature = "remote-control")]
+    let remote_port = args
+        .iter()
+        .position(|a| a == "--remote-control")
+        .map(|position| {
+            args.get(position + 1)
+                .and_then(|port| port.parse().ok())
+                .unwrap_or(DEFAULT_REMOTE_PORT)
+        });
+    let exit_code = Arc::new(AtomicI32::new(0));
+    let app_exit_code = exit_code.clone();
+
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder::default()
             .with_inner_size([800.0, 600.0])
@@ -960,9 +9767,32 @@ fn main() {
     eframe::run_native(
         "AgIsoTerminalDesigner",
         native_options,
-        Box::new(|cc| Ok(Box::new(DesignerApp::new(cc)))),
+        Box::new(move |cc| {
+            let mut app = DesignerApp::new(cc);
+            if !regression_mode {
+                app.load_settings(&cc.egui_ctx);
+                let ctx = cc.egui_ctx.clone();
+                app.open_requests = listen_for_open_requests(move || ctx.request_repaint());
+            }
+            for file in &files {
+                app.open_path(file, &cc.egui_ctx);
+            }
+            if let Some(regression) = regression {
+                app.start_command_line_regression(regression, app_exit_code);
+            }
+            #[cfg(feature = "remote-control")]
+            if let Some(port) = remote_port {
+                app.remote.port = port;
+                app.start_remote_server(&cc.egui_ctx);
+            }
+            Ok(Box::new(app))
+        }),
     )
     .ok();
+
+    if regression_mode {
+        std::process::exit(exit_code.load(Ordering::SeqCst));
+    }
 }
 
 // When compiling to web using trunk:
@@ -1012,20 +9842,6 @@ fn main() {
     });
 }
 
-/// Find the closest color index in the palette for a given RGB value
-fn find_closest_color_index(r: u8, g: u8, b: u8) -> u8 {
-    fn quantize_channel(c: u8) -> u8 {
-        // ((c + 25) / 51) in integer math, capped to 0..5
-        let v = (c as u16 + 25) / 51;
-        v.min(5) as u8
-    }
-    let rq = quantize_channel(r);
-    let gq = quantize_channel(g);
-    let bq = quantize_channel(b);
-
-    16 + 36 * rq + 6 * gq + bq
-}
-
 #[cfg(not(target_arch = "wasm32"))]
 fn execute<F: Future<Output = ()> + Send + 'static>(f: F) {
     // this is stupid... use any executor of your choice instead
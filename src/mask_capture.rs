@@ -0,0 +1,157 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use ag_iso_stack::object_pool::{ObjectId, ObjectType};
+use eframe::egui;
+
+use crate::preview_state::{with_preview_state, PreviewMode};
+use crate::{EditorProject, InteractiveMaskRenderer};
+
+/// Frames to wait after switching masks, so images are loaded and the layout is settled
+const SETTLE_FRAMES: u8 = 3;
+
+/// Renders masks one after the other on top of the user interface and takes a screenshot of each,
+/// like the terminal would show them
+pub struct MaskCapture {
+    masks: Vec<ObjectId>,
+    current: usize,
+    settle_frames: u8,
+    screenshot_requested: bool,
+    mask_rect: Option<egui::Rect>,
+
+    /// Preview mode and flashing state to restore when all masks are captured
+    previous_preview: Option<(PreviewMode, bool)>,
+}
+
+impl MaskCapture {
+    /// Capture every data and alarm mask of the project
+    pub fn new(project: &EditorProject) -> Self {
+        let masks = project
+            .get_pool()
+            .objects_by_types(&[ObjectType::DataMask, ObjectType::AlarmMask])
+            .iter()
+            .map(|o| o.id())
+            .collect();
+        MaskCapture {
+            masks,
+            current: 0,
+            settle_frames: SETTLE_FRAMES,
+            screenshot_requested: false,
+            mask_rect: None,
+            previous_preview: None,
+        }
+    }
+
+    /// Only capture the given masks
+    pub fn with_masks(mut self, masks: Vec<ObjectId>) -> Self {
+        self.masks.retain(|mask| masks.contains(mask));
+        self
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.current >= self.masks.len()
+    }
+
+    /// Number of masks done and the total number of masks
+    pub fn progress(&self) -> (usize, usize) {
+        (self.current.min(self.masks.len()), self.masks.len())
+    }
+
+    /// Drive the capture, call this once every frame until it is finished. The current mask is
+    /// rendered on top of the user interface. Returns a mask with its image once it is captured.
+    pub fn update(
+        &mut self,
+        ctx: &egui::Context,
+        project: &EditorProject,
+    ) -> Option<(ObjectId, Result<egui::ColorImage, String>)> {
+        if self.is_finished() {
+            return None;
+        }
+
+        if self.previous_preview.is_none() {
+            // Render masks like the terminal would, without hover highlights or flashing
+            self.previous_preview = Some(with_preview_state(ctx, |state| {
+                let previous = (state.mode, state.flashing_frozen);
+                state.set_mode(PreviewMode::Simulate);
+                state.flashing_frozen = true;
+                previous
+            }));
+
+            // Make sure the largest mask fits in the window
+            let largest = self
+                .masks
+                .iter()
+                .filter_map(|id| project.get_pool().object_by_id(*id))
+                .map(|mask| project.get_pool().content_size(mask))
+                .fold(egui::Vec2::ZERO, |size, (w, h)| {
+                    size.max(egui::vec2(w as f32, h as f32))
+                });
+            let window_size = ctx.screen_rect().size().max(largest);
+            ctx.send_viewport_cmd(egui::ViewportCommand::InnerSize(window_size));
+        }
+
+        let screenshot = ctx.input(|i| {
+            i.raw.events.iter().find_map(|e| match e {
+                egui::Event::Screenshot { image, .. } => Some(image.clone()),
+                _ => None,
+            })
+        });
+        if let (Some(image), Some(rect), true) =
+            (screenshot, self.mask_rect, self.screenshot_requested)
+        {
+            let actual = image.region(&rect, Some(ctx.pixels_per_point()));
+            return Some(self.next_mask(ctx, Ok(actual)));
+        }
+
+        let Some(mask) = project.get_pool().object_by_id(self.masks[self.current]) else {
+            return Some(self.next_mask(ctx, Err("Mask no longer exists".to_string())));
+        };
+        let response = egui::Area::new(egui::Id::new("captured_mask"))
+            .fixed_pos(egui::Pos2::ZERO)
+            .order(egui::Order::Foreground)
+            .show(ctx, |ui| {
+                ui.add(InteractiveMaskRenderer {
+                    object: mask,
+                    pool: project.get_pool(),
+                    selected_callback: Box::new(|_| {}),
+                })
+            })
+            .inner;
+        self.mask_rect = Some(response.rect);
+
+        if self.settle_frames > 0 {
+            self.settle_frames -= 1;
+        } else if !self.screenshot_requested {
+            ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(Default::default()));
+            self.screenshot_requested = true;
+        }
+        ctx.request_repaint();
+        None
+    }
+
+    /// Continue with the next mask, restoring the preview state when this was the last mask.
+    /// Returns the finished mask with its image.
+    fn next_mask(
+        &mut self,
+        ctx: &egui::Context,
+        image: Result<egui::ColorImage, String>,
+    ) -> (ObjectId, Result<egui::ColorImage, String>) {
+        let mask = self.masks[self.current];
+        self.current += 1;
+        self.settle_frames = SETTLE_FRAMES;
+        self.screenshot_requested = false;
+        self.mask_rect = None;
+
+        if self.is_finished() {
+            if let Some((mode, frozen)) = self.previous_preview {
+                with_preview_state(ctx, |state| {
+                    state.set_mode(mode);
+                    state.flashing_frozen = frozen;
+                });
+            }
+        }
+        ctx.request_repaint();
+        (mask, image)
+    }
+}
@@ -0,0 +1,163 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{HashMap, HashSet};
+
+use ag_iso_stack::object_pool::{NullableObjectId, ObjectId, ObjectType};
+
+use crate::reference_paths::remap_references;
+use crate::{ChangeCategory, EditorProject, ObjectInfo};
+
+/// What to do with a child subtree when duplicating a mask
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ShareMode {
+    /// Reference the existing objects from the new mask
+    Share,
+    /// Deep copy the objects with new IDs
+    Copy,
+}
+
+/// Objects that are usually reused between masks, so they are shared unless asked otherwise
+pub fn shared_by_default(object_type: ObjectType) -> bool {
+    matches!(
+        object_type,
+        ObjectType::NumberVariable
+            | ObjectType::StringVariable
+            | ObjectType::FontAttributes
+            | ObjectType::LineAttributes
+            | ObjectType::FillAttributes
+            | ObjectType::InputAttributes
+            | ObjectType::ExtendedInputAttributes
+            | ObjectType::Macro
+            | ObjectType::PictureGraphic
+            | ObjectType::ScaledGraphic
+            | ObjectType::GraphicData
+            | ObjectType::ColourMap
+            | ObjectType::ColourPalette
+            | ObjectType::SoftKeyMask
+    )
+}
+
+/// Settings of the "Duplicate mask" wizard
+#[derive(Debug, Clone)]
+pub struct MaskDuplication {
+    pub mask: ObjectId,
+
+    /// Name of the new mask
+    pub name: String,
+
+    /// Share or copy choice for every direct child of the mask
+    pub choices: Vec<(ObjectId, ShareMode)>,
+
+    /// Also copy variables and attributes that are found inside copied subtrees
+    pub copy_shared_types: bool,
+}
+
+impl MaskDuplication {
+    pub fn new(project: &EditorProject, mask: ObjectId) -> Self {
+        let pool = project.get_pool();
+        let mut choices = Vec::new();
+        let mut name = format!("{}", mask.value());
+        if let Some(object) = pool.object_by_id(mask) {
+            name = format!("{} copy", project.get_object_info(object).get_name(object));
+            for child in object.referenced_objects() {
+                if choices.iter().any(|(id, _)| *id == child) {
+                    continue;
+                }
+                let mode = match pool.object_by_id(child) {
+                    Some(c) if !shared_by_default(c.object_type()) => ShareMode::Copy,
+                    _ => ShareMode::Share,
+                };
+                choices.push((child, mode));
+            }
+        }
+        MaskDuplication {
+            mask,
+            name,
+            choices,
+            copy_shared_types: false,
+        }
+    }
+
+    /// All objects that get a copy with a new ID, including the mask itself
+    pub fn objects_to_copy(&self, project: &EditorProject) -> Vec<ObjectId> {
+        let pool = project.get_pool();
+        let mut copied = vec![self.mask];
+        let mut visited = HashSet::from([self.mask]);
+        let mut stack: Vec<ObjectId> = self
+            .choices
+            .iter()
+            .rev()
+            .filter(|(_, mode)| *mode == ShareMode::Copy)
+            .map(|(id, _)| *id)
+            .collect();
+
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            let Some(object) = pool.object_by_id(id) else {
+                continue;
+            };
+            let explicitly_chosen = self.choices.iter().any(|(c, _)| *c == id);
+            if !explicitly_chosen
+                && !self.copy_shared_types
+                && shared_by_default(object.object_type())
+            {
+                continue;
+            }
+            copied.push(id);
+            stack.extend(object.referenced_objects().into_iter().rev());
+        }
+        copied
+    }
+
+    /// Add the duplicate to the mutating pool and select it. Returns the ID of the new mask.
+    pub fn apply(&self, project: &EditorProject) -> Result<ObjectId, String> {
+        let pool = project.get_pool();
+        if pool.object_by_id(self.mask).is_none() {
+            return Err(format!("Mask {} no longer exists", self.mask.value()));
+        }
+
+        let copied = self.objects_to_copy(project);
+        let id_map: HashMap<ObjectId, ObjectId> = copied
+            .iter()
+            .map(|id| (*id, project.allocate_object_id()))
+            .collect();
+
+        let mut mut_pool = project.get_mut_pool().borrow_mut();
+        let mut object_info = project.object_info.borrow_mut();
+        for old_id in &copied {
+            let Some(original) = pool.object_by_id(*old_id) else {
+                continue;
+            };
+            let mut object = original.clone();
+            *object.mut_id() = id_map[old_id];
+            remap_references(&mut object, &id_map);
+
+            let mut info = ObjectInfo::new(&object);
+            if *old_id == self.mask {
+                info.set_name(self.name.clone());
+            } else if let Some(name) = object_info.get(old_id).and_then(|i| i.name.clone()) {
+                info.set_name(format!("{} copy", name));
+            }
+            object_info.insert(object.id(), info);
+            mut_pool.add(object);
+        }
+
+        let new_mask = id_map[&self.mask];
+        project.describe_change(
+            format!(
+                "Duplicate mask {} ({} objects copied)",
+                self.mask.value(),
+                copied.len()
+            ),
+            ChangeCategory::Edit,
+        );
+        project
+            .get_mut_selected()
+            .replace(NullableObjectId(Some(new_mask)));
+        Ok(new_mask)
+    }
+}
@@ -0,0 +1,216 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Printing of a single mask at a true scale, with object IDs and a grid for marking up paper
+//! copies during reviews. Masks are printed through a PDF document.
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::Point;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+use eframe::egui;
+
+use crate::mask_capture::MaskCapture;
+use crate::pdf::{PdfDocument, A4};
+use crate::EditorProject;
+
+/// Points per millimetre
+const POINTS_PER_MM: f32 = 72.0 / 25.4;
+const MARGIN: f32 = 40.0;
+
+/// Where the mask image starts below the title
+const IMAGE_TOP: f32 = MARGIN + 40.0;
+
+/// How a mask is printed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct PrintOptions {
+    /// Millimetres on paper per pixel of the mask
+    pub scale: f32,
+
+    /// Label every object with its ID
+    pub show_ids: bool,
+
+    /// Draw a grid with lines every `grid_spacing` pixels
+    pub show_grid: bool,
+    pub grid_spacing: u16,
+}
+
+impl Default for PrintOptions {
+    fn default() -> Self {
+        PrintOptions {
+            scale: 0.3,
+            show_ids: true,
+            show_grid: false,
+            grid_spacing: 20,
+        }
+    }
+}
+
+/// The scale that fits a mask of `size` pixels on the page
+pub fn fit_to_page_scale(size: (u16, u16)) -> f32 {
+    let width = (A4.0 - 2.0 * MARGIN) / POINTS_PER_MM;
+    let height = (A4.1 - IMAGE_TOP - MARGIN) / POINTS_PER_MM;
+    (width / size.0.max(1) as f32).min(height / size.1.max(1) as f32)
+}
+
+/// Positions of the objects shown on a mask, visible containers are followed
+fn object_positions(
+    pool: &ObjectPool,
+    object: &Object,
+    offset: Point<i16>,
+    positions: &mut Vec<(ObjectId, Point<i16>)>,
+) {
+    let refs = match object {
+        Object::DataMask(o) => &o.object_refs,
+        Object::AlarmMask(o) => &o.object_refs,
+        Object::Container(o) if !o.hidden => &o.object_refs,
+        _ => return,
+    };
+    for obj_ref in refs {
+        let child_offset = Point {
+            x: offset.x + obj_ref.offset.x,
+            y: offset.y + obj_ref.offset.y,
+        };
+        positions.push((obj_ref.id, child_offset));
+        if let Some(child) = pool.object_by_id(obj_ref.id) {
+            object_positions(pool, child, child_offset, positions);
+        }
+    }
+}
+
+/// A PDF page with the mask at the scale of the options. The image is the capture of the mask in
+/// physical pixels, the grid and labels use the pixels of the mask. A scale too large for the
+/// page is reduced so the mask fits.
+pub fn print_document(
+    project: &EditorProject,
+    mask: &Object,
+    image: &egui::ColorImage,
+    options: &PrintOptions,
+) -> Result<Vec<u8>, String> {
+    let size = project.get_pool().content_size(mask);
+    let mm_per_pixel = options.scale.min(fit_to_page_scale(size));
+
+    let mut pdf = PdfDocument::default();
+    pdf.add_page();
+    let name = project.get_object_info(mask).get_name(mask);
+    pdf.text(MARGIN, MARGIN + 14.0, 14.0, true, &name);
+    pdf.text(
+        MARGIN,
+        MARGIN + 28.0,
+        9.0,
+        false,
+        &format!(
+            "{:?} {}, {} x {} px at {:.3} mm per px{}",
+            mask.object_type(),
+            mask.id().value(),
+            size.0,
+            size.1,
+            mm_per_pixel,
+            if mm_per_pixel < options.scale {
+                " (reduced to fit the page)"
+            } else {
+                ""
+            }
+        ),
+    );
+
+    // Points per pixel of the mask
+    let scale = mm_per_pixel * POINTS_PER_MM;
+    let to_page = |x: f32, y: f32| (MARGIN + x * scale, IMAGE_TOP + y * scale);
+    let (width, height) = (size.0 as f32, size.1 as f32);
+    let rgb: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|pixel| [pixel.r(), pixel.g(), pixel.b()])
+        .collect();
+    pdf.image(
+        &rgb,
+        (image.size[0] as u32, image.size[1] as u32),
+        to_page(0.0, 0.0),
+        (width * scale, height * scale),
+    )?;
+
+    if options.show_grid && options.grid_spacing > 0 {
+        pdf.colour([150, 150, 150]);
+        let spacing = options.grid_spacing as usize;
+        for x in (0..=size.0 as usize).step_by(spacing) {
+            pdf.line(to_page(x as f32, 0.0), to_page(x as f32, height), 0.3);
+        }
+        for y in (0..=size.1 as usize).step_by(spacing) {
+            pdf.line(to_page(0.0, y as f32), to_page(width, y as f32), 0.3);
+        }
+    }
+
+    if options.show_ids {
+        let mut positions = Vec::new();
+        object_positions(project.get_pool(), mask, Point::default(), &mut positions);
+        pdf.colour([200, 0, 0]);
+        for (id, position) in positions {
+            let (x, y) = to_page(position.x as f32, position.y as f32);
+            pdf.text(x + 1.0, y + 7.0, 6.0, true, &id.value().to_string());
+        }
+    }
+    Ok(pdf.to_bytes())
+}
+
+/// Renders a mask and prints it once it is rendered
+pub struct MaskPrintRun {
+    pub mask: ObjectId,
+    pub options: PrintOptions,
+    capture: MaskCapture,
+    image: Option<Result<egui::ColorImage, String>>,
+}
+
+impl MaskPrintRun {
+    pub fn new(project: &EditorProject, mask: ObjectId, options: PrintOptions) -> Self {
+        MaskPrintRun {
+            mask,
+            options,
+            capture: MaskCapture::new(project).with_masks(vec![mask]),
+            image: None,
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.capture.is_finished()
+    }
+
+    /// Drive the run, call this once every frame until the run is finished
+    pub fn update(&mut self, ctx: &egui::Context, project: &EditorProject) {
+        if let Some((_, image)) = self.capture.update(ctx, project) {
+            self.image = Some(image);
+        }
+    }
+
+    /// The PDF document once the mask is rendered
+    pub fn document(&self, project: &EditorProject) -> Result<Vec<u8>, String> {
+        let mask = project
+            .get_pool()
+            .object_by_id(self.mask)
+            .ok_or_else(|| format!("Mask {} does not exist", self.mask.value()))?;
+        match &self.image {
+            Some(Ok(image)) => print_document(project, mask, image, &self.options),
+            Some(Err(e)) => Err(e.clone()),
+            None => Err("The mask is not rendered yet".to_string()),
+        }
+    }
+}
+
+/// Open a document in the default viewer of the system, to print it from there
+#[cfg(not(target_arch = "wasm32"))]
+pub fn open_in_viewer(path: &std::path::Path) -> Result<(), String> {
+    let mut command = if cfg!(target_os = "windows") {
+        let mut command = std::process::Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else if cfg!(target_os = "macos") {
+        std::process::Command::new("open")
+    } else {
+        std::process::Command::new("xdg-open")
+    };
+    command
+        .arg(path)
+        .spawn()
+        .map(|_| ())
+        .map_err(|e| format!("Failed to open {}: {}", path.display(), e))
+}
@@ -0,0 +1,278 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::HashMap;
+use std::hash::{DefaultHasher, Hash, Hasher};
+
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+use eframe::egui;
+
+use crate::preview_state::previewed_mask;
+use crate::reference_paths::subtree;
+use crate::texture_worker::{lazy_texture, ready_texture};
+use crate::{EditorProject, InteractiveMaskRenderer};
+
+/// Height of a thumbnail in the strip
+pub const THUMBNAIL_HEIGHT: f32 = 90.0;
+
+/// Frames to wait before capturing a live rendered thumbnail, so images are loaded
+const SETTLE_FRAMES: u8 = 2;
+
+/// Marks the screenshots taken for thumbnails
+struct ThumbnailScreenshot;
+
+/// A thumbnail that is rendered live until its screenshot arrives
+struct PendingCapture {
+    mask: ObjectId,
+    signature: u64,
+    rect: egui::Rect,
+    settle_frames: u8,
+    requested: bool,
+}
+
+/// Strip of thumbnails of all data and alarm masks. Thumbnails are rendered when they are
+/// scrolled into view, captured and turned into textures on the texture worker, which are cached
+/// until the mask or an object on it changes.
+#[derive(Default)]
+pub struct MaskThumbnails {
+    /// Signatures of the masks whose screenshot is turned into a texture on the worker
+    captured: HashMap<ObjectId, u64>,
+
+    /// Signatures of the masks and everything they reference, for the pool generation
+    signatures: HashMap<ObjectId, u64>,
+    generation: Option<u64>,
+    pending: Option<PendingCapture>,
+
+    /// Whether the mask of the pending capture was rendered in this frame
+    pending_rendered: bool,
+}
+
+fn texture_key(mask: ObjectId) -> String {
+    format!("mask_thumbnail_{}", mask.value())
+}
+
+/// Hash of the mask and the objects it references, changes when any of them changes
+fn mask_signature(pool: &ObjectPool, mask: ObjectId, mask_size: u16) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    mask_size.hash(&mut hasher);
+    for id in subtree(pool, mask) {
+        if let Some(object) = pool.object_by_id(id) {
+            object.write().hash(&mut hasher);
+        }
+    }
+    hasher.finish()
+}
+
+impl MaskThumbnails {
+    fn update_signatures(&mut self, project: &EditorProject) {
+        if self.generation == Some(project.pool_generation()) {
+            return;
+        }
+        let pool = project.get_pool();
+        self.signatures = pool
+            .objects_by_types(&[ObjectType::DataMask, ObjectType::AlarmMask])
+            .iter()
+            .map(|mask| {
+                (
+                    mask.id(),
+                    mask_signature(pool, mask.id(), project.mask_size),
+                )
+            })
+            .collect();
+        self.captured
+            .retain(|mask, signature| self.signatures.get(mask) == Some(signature));
+        self.generation = Some(project.pool_generation());
+    }
+
+    /// Take the screenshot of the pending capture once it arrived
+    fn receive_screenshot(&mut self, ctx: &egui::Context) {
+        let screenshot = ctx.input(|i| {
+            i.raw.events.iter().find_map(|e| match e {
+                egui::Event::Screenshot {
+                    image, user_data, ..
+                } if user_data
+                    .data
+                    .as_ref()
+                    .is_some_and(|data| data.is::<ThumbnailScreenshot>()) =>
+                {
+                    Some(image.clone())
+                }
+                _ => None,
+            })
+        });
+        let Some(image) = screenshot else {
+            return;
+        };
+        let Some(pending) = self.pending.take() else {
+            return;
+        };
+        // The mask changed while the screenshot was taken
+        if self.signatures.get(&pending.mask) != Some(&pending.signature) {
+            return;
+        }
+        let pixels_per_point = ctx.pixels_per_point();
+        lazy_texture(
+            ctx,
+            &texture_key(pending.mask),
+            pending.signature,
+            move || image.region(&pending.rect, Some(pixels_per_point)),
+        );
+        self.captured.insert(pending.mask, pending.signature);
+    }
+
+    /// Render the mask into the rect and capture it when it is settled
+    fn render_live(
+        &mut self,
+        ui: &mut egui::Ui,
+        project: &EditorProject,
+        mask: ObjectId,
+        rect: egui::Rect,
+        capture_allowed: bool,
+    ) {
+        let Some(object) = project.get_pool().object_by_id(mask) else {
+            return;
+        };
+        let (width, height) = project.get_pool().content_size(object);
+        let mut scene_rect = egui::Rect::from_min_size(
+            egui::Pos2::ZERO,
+            egui::vec2(width.max(1) as f32, height.max(1) as f32),
+        );
+        ui.push_id(("mask_thumbnail", mask.value()), |ui| {
+            let mut child = ui.new_child(egui::UiBuilder::new().max_rect(rect));
+            egui::Scene::new()
+                .sense(egui::Sense::hover())
+                .zoom_range(0.0..=1.0)
+                .show(&mut child, &mut scene_rect, |ui| {
+                    ui.add(InteractiveMaskRenderer {
+                        object,
+                        pool: project.get_pool(),
+                        selected_callback: Box::new(|_| {}),
+                    })
+                });
+        });
+
+        // One thumbnail is captured at a time
+        if self.pending.as_ref().is_some_and(|p| p.mask != mask) {
+            return;
+        }
+        self.pending_rendered = true;
+        let signature = self.signatures.get(&mask).copied().unwrap_or_default();
+        let pending = self.pending.get_or_insert(PendingCapture {
+            mask,
+            signature,
+            rect,
+            settle_frames: SETTLE_FRAMES,
+            requested: false,
+        });
+        if pending.requested {
+            return;
+        }
+        // Start over when the thumbnail moved, or it would capture the wrong place
+        if pending.rect != rect || pending.signature != signature {
+            *pending = PendingCapture {
+                mask,
+                signature,
+                rect,
+                settle_frames: SETTLE_FRAMES,
+                requested: false,
+            };
+        }
+        // Hover highlights should not end up in the thumbnail
+        if !capture_allowed || ui.rect_contains_pointer(rect) {
+            return;
+        }
+        if pending.settle_frames > 0 {
+            pending.settle_frames -= 1;
+        } else {
+            ui.ctx()
+                .send_viewport_cmd(egui::ViewportCommand::Screenshot(egui::UserData::new(
+                    ThumbnailScreenshot,
+                )));
+            pending.requested = true;
+        }
+        ui.ctx().request_repaint();
+    }
+
+    /// Show the strip. `capture_allowed` should be false while other screenshots are taken.
+    /// Returns the mask that was clicked
+    pub fn show(
+        &mut self,
+        ui: &mut egui::Ui,
+        project: &EditorProject,
+        capture_allowed: bool,
+    ) -> Option<ObjectId> {
+        self.update_signatures(project);
+        self.receive_screenshot(ui.ctx());
+        if self
+            .pending
+            .as_ref()
+            .is_some_and(|pending| !self.signatures.contains_key(&pending.mask))
+        {
+            self.pending = None;
+        }
+
+        let active = previewed_mask(ui.ctx(), project.get_pool());
+        let mut masks: Vec<ObjectId> = self.signatures.keys().copied().collect();
+        masks.sort();
+        self.pending_rendered = false;
+
+        let mut clicked = None;
+        egui::ScrollArea::horizontal().show(ui, |ui| {
+            ui.horizontal(|ui| {
+                for mask in masks {
+                    let Some(object) = project.get_pool().object_by_id(mask) else {
+                        continue;
+                    };
+                    let (width, height) = project.get_pool().content_size(object);
+                    let scale = THUMBNAIL_HEIGHT / height.max(1) as f32;
+                    let size = egui::vec2(width.max(1) as f32 * scale, THUMBNAIL_HEIGHT);
+
+                    ui.vertical(|ui| {
+                        let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click());
+                        if ui.is_rect_visible(rect) {
+                            let signature = self.signatures.get(&mask).copied();
+                            let texture = signature
+                                .and_then(|s| ready_texture(ui.ctx(), &texture_key(mask), s));
+                            if let Some(texture) = texture {
+                                egui::Image::new((texture.id(), size)).paint_at(ui, rect);
+                            } else if !self.captured.contains_key(&mask)
+                                && ui.clip_rect().contains_rect(rect)
+                            {
+                                self.render_live(ui, project, mask, rect, capture_allowed);
+                            } else {
+                                // Rendered once it is scrolled into view completely, or the
+                                // captured thumbnail is still being made on the worker
+                                ui.painter()
+                                    .rect_filled(rect, 0.0, ui.visuals().extreme_bg_color);
+                            }
+                            let stroke = if active == Some(mask) {
+                                ui.visuals().selection.stroke
+                            } else {
+                                ui.visuals().widgets.noninteractive.bg_stroke
+                            };
+                            ui.painter()
+                                .rect_stroke(rect, 0.0, stroke, egui::StrokeKind::Outside);
+                        }
+                        let name = project.get_object_info(object).get_name(object);
+                        // The live rendered masks are on top, so clicks are taken from the pointer
+                        let pointer_clicked = ui.rect_contains_pointer(rect)
+                            && ui.input(|i| i.pointer.primary_clicked());
+                        if response.on_hover_text(&name).clicked() || pointer_clicked {
+                            clicked = Some(mask);
+                        }
+                        ui.add_sized(
+                            [size.x, ui.spacing().interact_size.y],
+                            egui::Label::new(egui::RichText::new(name).small()).truncate(),
+                        );
+                    });
+                }
+            });
+        });
+        // A capture that was not requested yet starts over for a mask that is in view
+        if !self.pending_rendered && self.pending.as_ref().is_some_and(|p| !p.requested) {
+            self.pending = None;
+        }
+        clicked
+    }
+}
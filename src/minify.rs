@@ -0,0 +1,195 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{BTreeSet, HashMap};
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+
+use crate::macro_references::{macro_targets, macro_value_objects, remap_macro_references};
+use crate::picture_graphic::compress_picture;
+use crate::pool_diff::object_attributes;
+use crate::reference_paths::remap_references;
+
+/// Objects that are used without being referenced, e.g. masks the application changes to
+const ROOT_TYPES: &[ObjectType] = &[
+    ObjectType::WorkingSet,
+    ObjectType::DataMask,
+    ObjectType::AlarmMask,
+    ObjectType::SoftKeyMask,
+    ObjectType::WindowMask,
+    ObjectType::KeyGroup,
+    ObjectType::Macro,
+    ObjectType::AuxiliaryFunctionType1,
+    ObjectType::AuxiliaryInputType1,
+    ObjectType::AuxiliaryFunctionType2,
+    ObjectType::AuxiliaryInputType2,
+    ObjectType::AuxiliaryControlDesignatorType2,
+    ObjectType::ColourMap,
+    ObjectType::ColourPalette,
+    ObjectType::ObjectLabelReferenceList,
+    ObjectType::ExternalObjectDefinition,
+    ObjectType::ExternalReferenceName,
+    ObjectType::WorkingSetSpecialControls,
+];
+
+/// Attribute objects that are merged when they are the same
+const ATTRIBUTE_TYPES: &[ObjectType] = &[
+    ObjectType::FontAttributes,
+    ObjectType::LineAttributes,
+    ObjectType::FillAttributes,
+    ObjectType::InputAttributes,
+    ObjectType::ExtendedInputAttributes,
+];
+
+/// What the minification pass does
+#[derive(Debug, Clone, PartialEq)]
+pub struct MinifyOptions {
+    pub remove_unused: bool,
+    pub deduplicate_attributes: bool,
+    pub compress_pictures: bool,
+
+    /// Objects that are never removed, e.g. objects the application only shows through an
+    /// object pointer it changes at runtime
+    pub keep: BTreeSet<ObjectId>,
+}
+
+impl Default for MinifyOptions {
+    fn default() -> Self {
+        MinifyOptions {
+            remove_unused: false,
+            deduplicate_attributes: false,
+            compress_pictures: true,
+            keep: BTreeSet::new(),
+        }
+    }
+}
+
+/// What the minification pass changed
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct MinifyReport {
+    /// Size of the pool before and after, in bytes
+    pub original_size: usize,
+    pub minified_size: usize,
+
+    /// Objects that are not referenced by any used object
+    pub removed: Vec<ObjectId>,
+    /// Attribute objects that were replaced by an equal one, and the one replacing them
+    pub merged: Vec<(ObjectId, ObjectId)>,
+    /// Picture graphics that were stored smaller, with the bytes saved
+    pub compressed: Vec<(ObjectId, usize)>,
+}
+
+impl MinifyReport {
+    pub fn bytes_saved(&self) -> usize {
+        self.original_size.saturating_sub(self.minified_size)
+    }
+}
+
+/// Replace attribute objects that are the same as an earlier one by that one. Objects that macros
+/// change or set are kept, a command would otherwise change every object using the merged one.
+fn deduplicate_attributes(pool: &mut ObjectPool, report: &mut MinifyReport) {
+    let changed_by_macros: BTreeSet<ObjectId> = macro_targets(pool)
+        .into_iter()
+        .map(|target| target.object)
+        .chain(macro_value_objects(pool))
+        .collect();
+    let mut first: HashMap<String, ObjectId> = HashMap::new();
+    let mut map = HashMap::new();
+    for object in pool.objects_by_types(ATTRIBUTE_TYPES) {
+        if changed_by_macros.contains(&object.id()) {
+            continue;
+        }
+        let attributes: Vec<_> = object_attributes(object)
+            .into_iter()
+            .filter(|(name, _)| name != "id")
+            .collect();
+        let key = format!("{:?}{:?}", object.object_type(), attributes);
+        match first.get(&key) {
+            Some(kept) => {
+                map.insert(object.id(), *kept);
+            }
+            None => {
+                first.insert(key, object.id());
+            }
+        }
+    }
+    if map.is_empty() {
+        return;
+    }
+    for object in pool.objects_mut() {
+        remap_references(object, &map);
+        remap_macro_references(object, &map);
+    }
+    for (duplicate, kept) in map {
+        pool.remove(duplicate);
+        report.merged.push((duplicate, kept));
+    }
+    report
+        .merged
+        .sort_by_key(|(duplicate, _)| duplicate.value());
+}
+
+/// Remove the objects that can not be reached from a root object, a macro command or a value a
+/// macro command sets
+fn remove_unused(pool: &mut ObjectPool, keep: &BTreeSet<ObjectId>, report: &mut MinifyReport) {
+    let mut used = BTreeSet::new();
+    let mut stack: Vec<ObjectId> = pool
+        .objects_by_types(ROOT_TYPES)
+        .into_iter()
+        .map(Object::id)
+        .chain(macro_targets(pool).into_iter().map(|target| target.object))
+        .chain(macro_value_objects(pool))
+        .chain(keep.iter().copied())
+        .collect();
+    while let Some(id) = stack.pop() {
+        if !used.insert(id) {
+            continue;
+        }
+        if let Some(object) = pool.object_by_id(id) {
+            stack.extend(object.referenced_objects());
+        }
+    }
+
+    let unused: Vec<ObjectId> = pool
+        .objects()
+        .iter()
+        .map(Object::id)
+        .filter(|id| !used.contains(id))
+        .collect();
+    for id in &unused {
+        pool.remove(*id);
+    }
+    report.removed = unused;
+}
+
+/// Make a copy of a pool that is as small as possible for exporting it.
+/// Only the pool is exported, so names, notes and other editor data are left out anyway.
+pub fn minify_pool(pool: &ObjectPool, options: &MinifyOptions) -> (ObjectPool, MinifyReport) {
+    let mut minified = pool.clone();
+    let mut report = MinifyReport {
+        original_size: pool.as_iop().len(),
+        ..Default::default()
+    };
+
+    if options.deduplicate_attributes {
+        deduplicate_attributes(&mut minified, &mut report);
+    }
+    if options.remove_unused {
+        remove_unused(&mut minified, &options.keep, &mut report);
+    }
+    if options.compress_pictures {
+        for object in minified.objects_mut() {
+            if let Object::PictureGraphic(picture) = object {
+                let saved = compress_picture(picture);
+                if saved > 0 {
+                    report.compressed.push((picture.id, saved));
+                }
+            }
+        }
+    }
+
+    report.minified_size = minified.as_iop().len();
+    (minified, report)
+}
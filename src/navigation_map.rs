@@ -0,0 +1,195 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::btree_map::Entry;
+use std::collections::{BTreeMap, BTreeSet, HashMap, VecDeque};
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::Event;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+
+use crate::macro_references::{macro_refs, split_macro_commands};
+use crate::reference_paths::parent_map;
+
+/// Change Active Mask command, followed by the working set ID and the mask ID
+const CHANGE_ACTIVE_MASK: u8 = 0xAD;
+
+/// Execute Macro command, followed by the macro ID
+const EXECUTE_MACRO: u8 = 0xBE;
+
+/// A way to get from one mask to another
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigationLink {
+    pub from: ObjectId,
+    pub to: ObjectId,
+    /// The object whose event runs the macro, e.g. a button or key on the mask
+    pub trigger: ObjectId,
+    pub event: Event,
+    pub macro_id: u8,
+}
+
+/// Which masks can be reached from which others through Change Active Mask commands
+#[derive(Debug, Clone, Default)]
+pub struct NavigationMap {
+    /// All data and alarm masks
+    pub masks: Vec<ObjectId>,
+    /// The active mask of the working set
+    pub start: Option<ObjectId>,
+    pub links: Vec<NavigationLink>,
+}
+
+impl NavigationMap {
+    /// Number of links from the start mask to every mask that can be reached
+    pub fn depths(&self) -> BTreeMap<ObjectId, usize> {
+        let mut depths = BTreeMap::new();
+        let mut queue = VecDeque::new();
+        if let Some(start) = self.start {
+            depths.insert(start, 0);
+            queue.push_back(start);
+        }
+        while let Some(mask) = queue.pop_front() {
+            let depth = depths[&mask];
+            for link in self.links.iter().filter(|link| link.from == mask) {
+                if let Entry::Vacant(entry) = depths.entry(link.to) {
+                    entry.insert(depth + 1);
+                    queue.push_back(link.to);
+                }
+            }
+        }
+        depths
+    }
+
+    /// Masks that can not be reached from the start mask
+    pub fn unreachable(&self) -> Vec<ObjectId> {
+        let depths = self.depths();
+        self.masks
+            .iter()
+            .filter(|mask| !depths.contains_key(mask))
+            .copied()
+            .collect()
+    }
+
+    /// Masks without a way to another mask
+    pub fn dead_ends(&self) -> Vec<ObjectId> {
+        self.masks
+            .iter()
+            .filter(|mask| {
+                !self
+                    .links
+                    .iter()
+                    .any(|link| link.from == **mask && link.to != **mask)
+            })
+            .copied()
+            .collect()
+    }
+}
+
+/// Masks changed to by a macro, following Execute Macro commands
+fn change_active_mask_targets(
+    macros: &HashMap<u8, &[u8]>,
+    macro_id: u8,
+    visited: &mut BTreeSet<u8>,
+    targets: &mut BTreeSet<ObjectId>,
+) {
+    if !visited.insert(macro_id) {
+        return;
+    }
+    let Some(commands) = macros.get(&macro_id) else {
+        return;
+    };
+    for command in split_macro_commands(commands).0 {
+        match command[0] {
+            CHANGE_ACTIVE_MASK => {
+                let mask = u16::from_le_bytes([command[3], command[4]]);
+                if let Ok(mask) = ObjectId::new(mask) {
+                    targets.insert(mask);
+                }
+            }
+            EXECUTE_MACRO => change_active_mask_targets(macros, command[1], visited, targets),
+            _ => {}
+        }
+    }
+}
+
+/// Data and alarm masks that show an object, directly or through its parents
+fn masks_showing(
+    pool: &ObjectPool,
+    parents: &HashMap<ObjectId, Vec<ObjectId>>,
+    id: ObjectId,
+) -> BTreeSet<ObjectId> {
+    let mut masks = BTreeSet::new();
+    let mut visited = BTreeSet::new();
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        match pool.object_by_id(id).map(|o| o.object_type()) {
+            Some(ObjectType::DataMask | ObjectType::AlarmMask) => {
+                masks.insert(id);
+            }
+            _ => stack.extend(parents.get(&id).into_iter().flatten().copied()),
+        }
+    }
+    masks
+}
+
+/// Build the navigation map of a pool: every event of an object on a mask that runs a macro
+/// with a Change Active Mask command links that mask to the new one
+pub fn navigation_map(pool: &ObjectPool) -> NavigationMap {
+    let macros: HashMap<u8, &[u8]> = pool
+        .objects_by_type(ObjectType::Macro)
+        .into_iter()
+        .filter_map(|object| match object {
+            Object::Macro(m) => Some((m.id.value() as u8, m.commands.as_slice()))
+                .filter(|_| m.id.value() <= u8::MAX as u16),
+            _ => None,
+        })
+        .collect();
+    let parents = parent_map(pool);
+
+    let mut links = Vec::new();
+    for object in pool.objects() {
+        for macro_ref in macro_refs(object) {
+            let mut targets = BTreeSet::new();
+            change_active_mask_targets(
+                &macros,
+                macro_ref.macro_id,
+                &mut BTreeSet::new(),
+                &mut targets,
+            );
+            if targets.is_empty() {
+                continue;
+            }
+            for from in masks_showing(pool, &parents, object.id()) {
+                for to in &targets {
+                    links.push(NavigationLink {
+                        from,
+                        to: *to,
+                        trigger: object.id(),
+                        event: macro_ref.event_id,
+                        macro_id: macro_ref.macro_id,
+                    });
+                }
+            }
+        }
+    }
+
+    let start = pool
+        .objects_by_type(ObjectType::WorkingSet)
+        .into_iter()
+        .find_map(|object| match object {
+            Object::WorkingSet(ws) => Some(ws.active_mask),
+            _ => None,
+        });
+    NavigationMap {
+        masks: pool
+            .objects_by_types(&[ObjectType::DataMask, ObjectType::AlarmMask])
+            .into_iter()
+            .map(|o| o.id())
+            .collect(),
+        start,
+        links,
+    }
+}
@@ -0,0 +1,166 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::HashMap;
+
+use ag_iso_stack::object_pool::{NullableObjectId, ObjectId, ObjectPool, ObjectType};
+use serde::{Deserialize, Serialize};
+
+use crate::macro_references::{executed_macros, remap_macro_references};
+use crate::project_file::ObjectMetadata;
+use crate::reference_paths::{remap_references, subtree};
+use crate::{ChangeCategory, EditorProject, ObjectInfo};
+
+/// Marks clipboard text as objects copied from the designer
+const CLIPBOARD_FORMAT: &str = "ag-iso-terminal-designer/objects";
+
+/// Version of the clipboard format
+const CLIPBOARD_VERSION: u32 = 1;
+
+/// Objects on the clipboard, so they can be pasted into another pool or designer instance
+#[derive(Serialize, Deserialize)]
+struct ClipboardObjects {
+    format: String,
+    version: u32,
+
+    /// ID of the copied object, the other objects are referenced by it
+    root: u16,
+
+    /// The copied objects as IOP bytes, with their IDs in the source pool
+    objects: Vec<u8>,
+
+    /// Names and other metadata of the copied objects, by their ID in the source pool
+    metadata: HashMap<u16, ObjectMetadata>,
+}
+
+/// An object with everything it references, including the macros the copied objects execute
+fn copied_objects(pool: &ObjectPool, root: ObjectId) -> Vec<ObjectId> {
+    let mut objects = subtree(pool, root);
+    let mut index = 0;
+    while index < objects.len() {
+        let macros = pool
+            .object_by_id(objects[index])
+            .map(executed_macros)
+            .unwrap_or_default();
+        for macro_id in macros {
+            if !objects.contains(&macro_id) && pool.object_by_id(macro_id).is_some() {
+                objects.extend(subtree(pool, macro_id));
+            }
+        }
+        index += 1;
+    }
+    objects
+}
+
+/// Serialize an object with everything it references to clipboard text
+pub fn copy_objects(project: &EditorProject, root: ObjectId) -> Result<String, String> {
+    let pool = project.get_pool();
+    if pool.object_by_id(root).is_none() {
+        return Err(format!("Object {} does not exist", root.value()));
+    }
+
+    let mut copied = ObjectPool::new();
+    let mut metadata = HashMap::new();
+    let object_info = project.object_info.borrow();
+    for id in copied_objects(pool, root) {
+        if let Some(object) = pool.object_by_id(id) {
+            copied.add(object.clone());
+        }
+        if let Some(info) = object_info.get(&id) {
+            metadata.insert(id.value(), ObjectMetadata::from_info(info));
+        }
+    }
+
+    let clipboard = ClipboardObjects {
+        format: CLIPBOARD_FORMAT.to_string(),
+        version: CLIPBOARD_VERSION,
+        root: root.value(),
+        objects: copied.as_iop(),
+        metadata,
+    };
+    serde_json::to_string(&clipboard).map_err(|e| format!("Failed to copy the objects: {}", e))
+}
+
+/// Whether clipboard text holds objects copied from the designer
+pub fn is_clipboard_objects(text: &str) -> bool {
+    text.trim_start().starts_with('{') && text.contains(CLIPBOARD_FORMAT)
+}
+
+/// Add the objects from clipboard text to the mutating pool with new IDs and select the pasted
+/// root object. Returns the ID of the pasted root object.
+pub fn paste_objects(project: &EditorProject, text: &str) -> Result<ObjectId, String> {
+    let clipboard: ClipboardObjects = serde_json::from_str(text)
+        .map_err(|e| format!("The clipboard does not hold objects: {}", e))?;
+    if clipboard.format != CLIPBOARD_FORMAT {
+        return Err("The clipboard does not hold objects".to_string());
+    }
+    if clipboard.version > CLIPBOARD_VERSION {
+        return Err(format!(
+            "The objects were copied from a newer designer (clipboard version {})",
+            clipboard.version
+        ));
+    }
+    let objects = ObjectPool::from_iop(clipboard.objects);
+    let root = ObjectId::new(clipboard.root).map_err(|_| "Invalid root object".to_string())?;
+    if objects.object_by_id(root).is_none() {
+        return Err("The copied object is missing from the clipboard".to_string());
+    }
+
+    let (macros, others): (Vec<_>, Vec<_>) = objects
+        .objects()
+        .iter()
+        .partition(|object| object.object_type() == ObjectType::Macro);
+    let mut id_map: HashMap<ObjectId, ObjectId> = others
+        .iter()
+        .map(|object| (object.id(), project.allocate_object_id()))
+        .collect();
+    // Macros are referenced by 8 bit IDs, so they need one of the free IDs below 256
+    let mut free_macro_ids = (0..=u8::MAX)
+        .filter_map(|id| ObjectId::new(id as u16).ok())
+        .filter(|id| project.get_pool().object_by_id(*id).is_none())
+        .filter(|id| !id_map.values().any(|used| used == id))
+        .collect::<Vec<_>>()
+        .into_iter();
+    for macro_object in macros {
+        let id = free_macro_ids
+            .next()
+            .ok_or("All macro IDs are in use, the copied macros can not be pasted")?;
+        id_map.insert(macro_object.id(), id);
+    }
+    let existing_names = project.get_all_object_names();
+
+    let mut mut_pool = project.get_mut_pool().borrow_mut();
+    let mut object_info = project.object_info.borrow_mut();
+    for original in objects.objects() {
+        let mut object = original.clone();
+        *object.mut_id() = id_map[&original.id()];
+        remap_references(&mut object, &id_map);
+        remap_macro_references(&mut object, &id_map);
+
+        let mut info = ObjectInfo::new(&object);
+        if let Some(meta) = clipboard.metadata.get(&original.id().value()) {
+            meta.restore(&mut info);
+        }
+        if let Some(name) = info.name.clone() {
+            if existing_names.contains_key(&name) {
+                info.set_name(format!("{} copy", name));
+            }
+        }
+        object_info.insert(object.id(), info);
+        mut_pool.add(object);
+    }
+
+    let new_root = id_map[&root];
+    project.describe_change(
+        format!(
+            "Paste {} objects from the clipboard",
+            objects.objects().len()
+        ),
+        ChangeCategory::Edit,
+    );
+    project
+        .get_mut_selected()
+        .replace(NullableObjectId(Some(new_root)));
+    Ok(new_root)
+}
@@ -4,8 +4,22 @@
 
 use crate::allowed_object_relationships::get_allowed_child_refs;
 use crate::allowed_object_relationships::AllowedChildRefs;
+use crate::layout::{CellAlignment, GridLayout};
+use crate::macro_references::split_macro_commands;
+use crate::object_rendering::Colorable;
+use crate::object_rendering::{format_vt_number, measure_vt_text, round_vt_number};
+use crate::picture_graphic::{
+    conversion_error, convert_picture, picture_image, picture_pixels, set_picture_pixels,
+};
+use crate::polygon::nearest_edge;
 use crate::possible_events::PossibleEvents;
-use crate::EditorProject;
+use crate::preview_state::{with_preview_state, ButtonPreviewState};
+use crate::spell_check::spell_checked_text_edit;
+use crate::{
+    evaluate_expression, is_convex, iso_angle_degrees, iso_angle_value, polygon_problems, tr,
+    tr_args, ChangeCategory, EditorProject, ExpressionContext, POLYGON_TYPE_CONVEX,
+    POLYGON_TYPE_OPEN,
+};
 
 use ag_iso_stack::object_pool::object::*;
 use ag_iso_stack::object_pool::object_attributes::*;
@@ -16,8 +30,47 @@ use ag_iso_stack::object_pool::ObjectPool;
 use ag_iso_stack::object_pool::ObjectRef;
 use ag_iso_stack::object_pool::ObjectType;
 use eframe::egui;
+use eframe::egui::emath::Numeric;
 use eframe::egui::TextWrapMode;
+use std::collections::hash_map::DefaultHasher;
 use std::collections::HashSet;
+use std::hash::{Hash, Hasher};
+
+/// Parser for numeric fields that accepts arithmetic, see [`evaluate_expression`]
+fn expression_parser<Num: Numeric>(
+    value: &Num,
+    design: &EditorProject,
+) -> impl Fn(&str) -> Option<f64> + 'static {
+    let context = ExpressionContext {
+        current: value.to_f64(),
+        mask_size: design.mask_size,
+    };
+    let integral = Num::INTEGRAL;
+    move |text| {
+        let result = evaluate_expression(text, &context)?;
+        Some(if integral { result.round() } else { result })
+    }
+}
+
+/// Drag value that also accepts expressions like `240/2`, `x+8` relative to the current value
+/// or `50%` of the data mask size
+fn number_field<'a, Num: Numeric>(
+    value: &'a mut Num,
+    design: &EditorProject,
+) -> egui::DragValue<'a> {
+    let parser = expression_parser(value, design);
+    egui::DragValue::new(value).custom_parser(parser)
+}
+
+/// Slider that also accepts expressions in its value field, like [`number_field`]
+fn number_slider<'a, Num: Numeric>(
+    value: &'a mut Num,
+    range: std::ops::RangeInclusive<Num>,
+    design: &EditorProject,
+) -> egui::Slider<'a> {
+    let parser = expression_parser(value, design);
+    egui::Slider::new(value, range).custom_parser(parser)
+}
 
 /// Check if adding a reference from `parent_id` to `child_id` would create a circular reference
 /// Returns true if it would create a cycle (and should be blocked)
@@ -128,9 +181,9 @@ fn render_object_id(ui: &mut egui::Ui, id: &mut ObjectId, design: &EditorProject
     let mut current_id = u16::from(*id);
 
     ui.horizontal(|ui| {
-        ui.label("Object ID:");
+        ui.label(tr("Object ID:"));
 
-        let widget = egui::DragValue::new(&mut current_id)
+        let widget = number_field(&mut current_id, design)
             .speed(1.0)
             .range(0..=65534);
         let resp = ui.add(widget);
@@ -144,7 +197,7 @@ fn render_object_id(ui: &mut egui::Ui, id: &mut ObjectId, design: &EditorProject
         let was_conflict = ui.data(|data| data.get_temp::<u16>(conflict_storage));
 
         if conflict || was_conflict.is_some_and(|id| id == current_id) {
-            ui.colored_label(egui::Color32::RED, "ID already in use!");
+            ui.colored_label(egui::Color32::RED, tr("ID already in use!"));
 
             // Save the conflict in storage so it is still displayed next frame
             ui.data_mut(|data| {
@@ -167,9 +220,19 @@ fn render_object_id(ui: &mut egui::Ui, id: &mut ObjectId, design: &EditorProject
         // Add the object type display
         if let Some(obj) = design.get_pool().object_by_id(*id) {
             ui.separator();
-            ui.label("Type:");
+            ui.label(tr("Type:"));
             ui.label(format!("{:?}", obj.object_type()));
         }
+
+        if design
+            .golden_pool
+            .as_ref()
+            .is_some_and(|golden| golden.is_locked(*id))
+        {
+            ui.label("\u{1F512}").on_hover_text(tr(
+                "Released in the golden pool, changing this ID breaks compatibility",
+            ));
+        }
     });
 }
 
@@ -233,7 +296,7 @@ fn render_nullable_object_id_selector(
                 .map_or("None".to_string(), |id| format!("{:?}", id.value())),
         )
         .show_ui(ui, |ui| {
-            ui.selectable_value(object_id, NullableObjectId::NULL, "None");
+            ui.selectable_value(object_id, NullableObjectId::NULL, tr("None"));
             for potential_child in pool.objects_by_types(allowed_child_objects) {
                 let child_id = potential_child.id();
 
@@ -269,23 +332,179 @@ fn render_nullable_object_id_selector(
 fn render_index_modifiers<T>(ui: &mut egui::Ui, idx: usize, list: &mut Vec<T>) {
     if ui
         .add_enabled(idx > 0, egui::Button::new("\u{23F6}"))
-        .on_hover_text("Move up")
+        .on_hover_text(tr("Move up"))
         .clicked()
     {
         list.swap(idx, idx - 1);
     }
     if ui
         .add_enabled(idx < list.len() - 1, egui::Button::new("\u{23F7}"))
-        .on_hover_text("Move down")
+        .on_hover_text(tr("Move down"))
         .clicked()
     {
         list.swap(idx, idx + 1);
     }
-    if ui.button("\u{1F5D9}").on_hover_text("Remove").clicked() {
+    if ui.button("\u{1F5D9}").on_hover_text(tr("Remove")).clicked() {
         list.remove(idx);
     }
 }
 
+/// Warn about objects that do not fit inside a (soft key or working set) designator.
+/// The VT clips them, and ISO 11783-6 designators are sized in multiples of 8 pixels.
+fn render_designator_warnings(
+    ui: &mut egui::Ui,
+    design: &EditorProject,
+    object_refs: &[ObjectRef],
+) {
+    let (width, height) = design.soft_key_size;
+    if width % 8 != 0 || height % 8 != 0 {
+        ui.colored_label(
+            egui::Color32::YELLOW,
+            tr_args(
+                "Designator size {}x{} is not a multiple of 8 pixels",
+                &[&width, &height],
+            ),
+        );
+    }
+
+    for obj_ref in object_refs {
+        if let Some(obj) = design.get_pool().object_by_id(obj_ref.id) {
+            let (obj_width, obj_height) = design.get_pool().content_size(obj);
+            let fits = obj_ref.offset.x >= 0
+                && obj_ref.offset.y >= 0
+                && obj_ref.offset.x as i32 + obj_width as i32 <= width as i32
+                && obj_ref.offset.y as i32 + obj_height as i32 <= height as i32;
+            if !fits {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    tr_args(
+                        "Object {} does not fit in the {}x{} designator and will be clipped",
+                        &[&obj_ref.id.value(), &width, &height],
+                    ),
+                );
+            }
+        }
+    }
+}
+
+/// Text a string object shows, the value of its string variable if it references one
+fn displayed_text(
+    design: &EditorProject,
+    variable_reference: NullableObjectId,
+    value: &str,
+) -> String {
+    match variable_reference
+        .0
+        .and_then(|id| design.get_pool().object_by_id(id))
+    {
+        Some(Object::StringVariable(s)) => s.value.clone(),
+        _ => value.to_string(),
+    }
+}
+
+/// "Fit to text" button and live toggle that resize a string object to its text.
+/// With auto wrap only the height is changed, the text is wrapped at the current width.
+fn render_fit_to_text(
+    ui: &mut egui::Ui,
+    design: &EditorProject,
+    id: ObjectId,
+    font_attributes: ObjectId,
+    text: &str,
+    auto_wrap: bool,
+    size: (&mut u16, &mut u16),
+) {
+    let live_id = egui::Id::new("fit_to_text_live").with(id);
+    let mut live = ui
+        .data(|data| data.get_temp::<bool>(live_id))
+        .unwrap_or_default();
+
+    let wrap_width = auto_wrap.then_some(*size.0 as f32);
+    let measured = measure_vt_text(ui, design.get_pool(), font_attributes, text, wrap_width);
+
+    ui.horizontal(|ui| {
+        let clicked = ui
+            .add_enabled(measured.is_some(), egui::Button::new(tr("Fit to text")))
+            .on_hover_text(if auto_wrap {
+                tr("Change the height to fit the wrapped text")
+            } else {
+                tr("Change the size to fit the text")
+            })
+            .clicked();
+        ui.checkbox(&mut live, tr("Live"))
+            .on_hover_text(tr("Keep the size fitted while the text or font changes"));
+
+        if let Some(measured) = measured {
+            let width = (measured.x.ceil() as u16).min(design.mask_size);
+            let height = (measured.y.ceil() as u16).min(design.mask_size);
+            let fitted = *size.1 == height && (auto_wrap || *size.0 == width);
+            if (clicked || live) && !fitted {
+                if !auto_wrap {
+                    *size.0 = width;
+                }
+                *size.1 = height;
+                if clicked {
+                    design.describe_change(
+                        format!("Fit object {} to its text", id.value()),
+                        ChangeCategory::Edit,
+                    );
+                }
+            }
+        }
+    });
+
+    ui.data_mut(|data| data.insert_temp(live_id, live));
+}
+
+/// Settings and apply button to arrange object references into a grid
+fn render_grid_layout(ui: &mut egui::Ui, design: &EditorProject, object_refs: &mut [ObjectRef]) {
+    let layout_id = egui::Id::new("grid_layout");
+    let mut layout = ui
+        .data(|data| data.get_temp::<GridLayout>(layout_id))
+        .unwrap_or_default();
+
+    egui::CollapsingHeader::new(tr("Grid layout")).show(ui, |ui| {
+        ui.horizontal(|ui| {
+            ui.label(tr("Columns:"));
+            ui.add(number_field(&mut layout.columns, design).range(1..=64));
+            ui.label(tr_args("Rows: {}", &[&layout.rows(object_refs.len())]));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Spacing:"));
+            ui.add(number_field(&mut layout.spacing_x, design).prefix("X: "));
+            ui.add(number_field(&mut layout.spacing_y, design).prefix("Y: "));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Origin:"));
+            ui.add(number_field(&mut layout.origin_x, design).prefix("X: "));
+            ui.add(number_field(&mut layout.origin_y, design).prefix("Y: "));
+        });
+        for (label, alignment) in [
+            (tr("Horizontal alignment:"), &mut layout.align_x),
+            (tr("Vertical alignment:"), &mut layout.align_y),
+        ] {
+            ui.horizontal(|ui| {
+                ui.label(label);
+                for option in CellAlignment::ALL {
+                    ui.selectable_value(alignment, option, format!("{:?}", option));
+                }
+            });
+        }
+        if ui
+            .add_enabled(!object_refs.is_empty(), egui::Button::new(tr("Apply")))
+            .on_hover_text(tr("Arrange the objects row by row in their current order"))
+            .clicked()
+        {
+            layout.apply(design.get_pool(), object_refs);
+            design.describe_change(
+                format!("Grid layout of {} objects", object_refs.len()),
+                ChangeCategory::Edit,
+            );
+        }
+    });
+
+    ui.data_mut(|data| data.insert_temp(layout_id, layout));
+}
+
 fn render_object_references_list(
     ui: &mut egui::Ui,
     design: &EditorProject,
@@ -330,17 +549,17 @@ fn render_object_references_list(
                     ui.label(object_info.get_name(obj));
 
                     ui.add(
-                        egui::Slider::new(&mut obj_ref.offset.x, 0..=max_x)
+                        number_slider(&mut obj_ref.offset.x, 0..=max_x, design)
                             .text("X")
                             .drag_value_speed(1.0),
                     );
                     ui.add(
-                        egui::Slider::new(&mut obj_ref.offset.y, 0..=max_y)
+                        number_slider(&mut obj_ref.offset.y, 0..=max_y, design)
                             .text("Y")
                             .drag_value_speed(1.0),
                     );
                 } else {
-                    ui.colored_label(egui::Color32::RED, "Missing object");
+                    ui.colored_label(egui::Color32::RED, tr("Missing object"));
                 }
 
                 render_index_modifiers(ui, idx, object_refs);
@@ -398,7 +617,7 @@ fn render_object_id_list(
                     let object_info = design.get_object_info(obj);
                     ui.label(object_info.get_name(obj));
                 } else {
-                    ui.colored_label(egui::Color32::RED, "Missing object");
+                    ui.colored_label(egui::Color32::RED, tr("Missing object"));
                     ui.label(""); // Empty cell for name column
                 }
 
@@ -453,7 +672,7 @@ fn render_nullable_object_id_list(
                         let object_info = design.get_object_info(obj);
                         ui.label(object_info.get_name(obj));
                     } else {
-                        ui.colored_label(egui::Color32::RED, "Missing object");
+                        ui.colored_label(egui::Color32::RED, tr("Missing object"));
                         ui.label(""); // Empty cell for name column
                     }
                 } else {
@@ -488,12 +707,12 @@ fn render_add_object_id(
     let pool = design.get_pool();
     let mut result = (None, false);
     ui.horizontal(|ui| {
-        ui.label("Add object:");
+        ui.label(tr("Add object:"));
         egui::ComboBox::from_id_salt("New Object Type")
-            .selected_text("Select existing object")
+            .selected_text(tr("Select existing object"))
             .show_ui(ui, |ui| {
                 if allow_none {
-                    if ui.selectable_label(false, "None").clicked() {
+                    if ui.selectable_label(false, tr("None")).clicked() {
                         result = (None, true);
                     }
                 }
@@ -533,12 +752,32 @@ fn render_add_object_id(
     result
 }
 
+/// Label of a macro in the event hook editor: its ID, name and number of commands
+fn macro_label(design: &EditorProject, macro_object: &Object) -> String {
+    let commands = match macro_object {
+        Object::Macro(m) => split_macro_commands(&m.commands).0.len(),
+        _ => 0,
+    };
+    tr_args(
+        "{}: {} ({} commands)",
+        &[
+            &u16::from(macro_object.id()),
+            &design.get_object_info(macro_object).get_name(macro_object),
+            &commands,
+        ],
+    )
+}
+
+/// Event hooks of an object: which macro is executed on which event
 fn render_macro_references(
     ui: &mut egui::Ui,
     design: &EditorProject,
+    object_id: ObjectId,
     macro_refs: &mut Vec<MacroRef>,
     possible_events: &[Event],
 ) {
+    let pool = design.get_pool();
+    let macros = pool.objects_by_type(ObjectType::Macro);
     egui::Grid::new("macro_grid")
         .striped(true)
         .min_col_width(0.0)
@@ -546,50 +785,50 @@ fn render_macro_references(
             let mut idx = 0;
             while idx < macro_refs.len() {
                 let macro_ref = &mut macro_refs[idx];
-
-                if let Some(macro_obj) = design
-                    .get_pool()
-                    .objects_by_type(ObjectType::Macro)
+                let macro_obj = macros
                     .iter()
-                    .find(|o| u16::from(o.id()) == macro_ref.macro_id as u16)
-                {
-                    ui.label(" - ");
-                    ui.push_id(idx, |ui| {
-                        egui::ComboBox::from_id_salt("event_id")
-                            .selected_text(format!("{:?}", macro_ref.event_id))
-                            .show_ui(ui, |ui| {
-                                for event in possible_events {
-                                    ui.selectable_value(
-                                        &mut macro_ref.event_id,
-                                        *event,
-                                        format!("{:?}", event),
-                                    );
-                                }
-                            });
+                    .find(|o| u16::from(o.id()) == macro_ref.macro_id as u16);
+
+                ui.push_id(idx, |ui| {
+                    egui::ComboBox::from_id_salt("event_id")
+                        .selected_text(format!("{:?}", macro_ref.event_id))
+                        .show_ui(ui, |ui| {
+                            for event in possible_events {
+                                ui.selectable_value(
+                                    &mut macro_ref.event_id,
+                                    *event,
+                                    format!("{:?}", event),
+                                );
+                            }
+                        });
+                    ui.label("\u{2192}");
+
+                    egui::ComboBox::from_id_salt("macro_id")
+                        .width(200.0)
+                        .selected_text(match macro_obj {
+                            Some(macro_obj) => macro_label(design, macro_obj),
+                            None => tr_args("Missing macro {}", &[&macro_ref.macro_id]),
+                        })
+                        .show_ui(ui, |ui| {
+                            for potential_macro in &macros {
+                                ui.selectable_value(
+                                    &mut macro_ref.macro_id,
+                                    u16::from(potential_macro.id()) as u8,
+                                    macro_label(design, potential_macro),
+                                );
+                            }
+                        });
+                });
 
-                        if ui.link(" Macro ").clicked() {
+                match macro_obj {
+                    Some(macro_obj) => {
+                        if ui.link(tr("Edit")).clicked() {
                             *design.get_mut_selected().borrow_mut() = macro_obj.id().into();
                         }
-
-                        egui::ComboBox::from_id_salt("macro_id")
-                            .selected_text(format!("{:?}", macro_ref.macro_id))
-                            .show_ui(ui, |ui| {
-                                for potential_macro in
-                                    design.get_pool().objects_by_type(ObjectType::Macro)
-                                {
-                                    ui.selectable_value(
-                                        &mut macro_ref.macro_id,
-                                        u16::from(potential_macro.id()) as u8,
-                                        format!("{:?}", u16::from(potential_macro.id())),
-                                    );
-                                }
-                            });
-                    });
-                } else {
-                    ui.label(format!(
-                        "- {:?}: Missing macro object {:?}",
-                        macro_ref.event_id, macro_ref.macro_id
-                    ));
+                    }
+                    None => {
+                        ui.colored_label(egui::Color32::RED, tr("Missing"));
+                    }
                 }
 
                 render_index_modifiers(ui, idx, macro_refs);
@@ -597,63 +836,69 @@ fn render_macro_references(
                 ui.end_row();
             }
         });
+    if macro_refs.is_empty() {
+        ui.weak(tr("No event executes a macro"));
+    }
 
-    render_add_macro_reference(ui, design.get_pool(), macro_refs, possible_events);
+    render_add_macro_reference(ui, design, object_id, macro_refs, possible_events);
 }
 
 fn render_add_macro_reference(
     ui: &mut egui::Ui,
-    pool: &ObjectPool,
+    design: &EditorProject,
+    object_id: ObjectId,
     macro_refs: &mut Vec<MacroRef>,
     possible_events: &[Event],
 ) {
     ui.horizontal(|ui| {
-        ui.label("Add macro:");
-        ui.horizontal(|ui| {
-            let mut selected_event = ui.data_mut(|data| {
-                data.get_temp(egui::Id::new("selected_event"))
-                    .unwrap_or(Event::Reserved)
+        ui.label(tr("Add event hook:"));
+        let mut selected_event = ui.data_mut(|data| {
+            data.get_temp(egui::Id::new("selected_event"))
+                .unwrap_or(Event::Reserved)
+        });
+        egui::ComboBox::from_id_salt("New Event Type")
+            .selected_text(if selected_event == Event::Reserved {
+                tr("Select event").to_string()
+            } else {
+                format!("{:?}", selected_event)
+            })
+            .show_ui(ui, |ui| {
+                for event in possible_events {
+                    if ui
+                        .selectable_value(&mut selected_event, *event, format!("{:?}", event))
+                        .changed()
+                    {
+                        ui.data_mut(|data| {
+                            data.insert_temp(egui::Id::new("selected_event"), selected_event);
+                        });
+                    }
+                }
             });
-            egui::ComboBox::from_id_salt("New Event Type")
-                .selected_text(if selected_event == Event::Reserved {
-                    "Select event".to_string()
-                } else {
-                    format!("{:?}", selected_event)
-                })
+
+        if selected_event != Event::Reserved {
+            egui::ComboBox::from_id_salt("New Macro")
+                .selected_text(tr("Select macro"))
                 .show_ui(ui, |ui| {
-                    for event in possible_events {
+                    for potential_macro in design.get_pool().objects_by_type(ObjectType::Macro) {
                         if ui
-                            .selectable_value(&mut selected_event, *event, format!("{:?}", event))
-                            .changed()
+                            .selectable_label(false, macro_label(design, potential_macro))
+                            .clicked()
                         {
-                            ui.data_mut(|data| {
-                                data.insert_temp(egui::Id::new("selected_event"), selected_event);
+                            macro_refs.push(MacroRef {
+                                event_id: selected_event,
+                                macro_id: u16::from(potential_macro.id()) as u8,
                             });
                         }
                     }
                 });
-
-            if selected_event != Event::Reserved {
-                egui::ComboBox::from_id_salt("New Macro")
-                    .selected_text("Select macro")
-                    .show_ui(ui, |ui| {
-                        for potential_macro in pool.objects_by_type(ObjectType::Macro) {
-                            if ui
-                                .selectable_label(
-                                    false,
-                                    format!("{:?}", u16::from(potential_macro.id())),
-                                )
-                                .clicked()
-                            {
-                                macro_refs.push(MacroRef {
-                                    event_id: selected_event,
-                                    macro_id: u16::from(potential_macro.id()) as u8,
-                                });
-                            }
-                        }
-                    });
+            if ui
+                .button(tr("New macro"))
+                .on_hover_text(tr("Create an empty macro for this event and open it"))
+                .clicked()
+            {
+                design.request_macro_creation(object_id, selected_event);
             }
-        });
+        }
     });
 }
 
@@ -661,16 +906,16 @@ impl ConfigurableObject for WorkingSet {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
-        ui.checkbox(&mut self.selectable, "Selectable");
+        ui.checkbox(&mut self.selectable, tr("Selectable"));
         ui.horizontal(|ui| {
             let masks = design
                 .get_pool()
                 .objects_by_types(&[ObjectType::DataMask, ObjectType::AlarmMask]);
-            egui::ComboBox::from_label("Active Mask")
+            egui::ComboBox::from_label(tr("Active Mask"))
                 .selected_text(format!("{:?}", u16::from(self.active_mask)))
                 .show_ui(ui, |ui| {
                     for object in masks {
@@ -681,27 +926,29 @@ impl ConfigurableObject for WorkingSet {
                         );
                     }
                 });
-            if ui.link("(view)").clicked() {
+            if ui.link(tr("(view)")).clicked() {
                 *design.get_mut_selected().borrow_mut() = self.active_mask.into();
             }
         });
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_references_list(
             ui,
             design,
-            design.mask_size,
-            design.mask_size,
+            design.soft_key_size.0,
+            design.soft_key_size.1,
             &mut self.object_refs,
             &Self::get_allowed_child_refs(VtVersion::Version3),
             self.id,
         );
+        render_designator_warnings(ui, design, &self.object_refs);
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -712,12 +959,12 @@ impl ConfigurableObject for DataMask {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.horizontal(|ui| {
-            egui::ComboBox::from_label("Soft Key Mask")
+            egui::ComboBox::from_label(tr("Soft Key Mask"))
                 .selected_text(
                     self.soft_key_mask
                         .0
@@ -738,13 +985,13 @@ impl ConfigurableObject for DataMask {
                     }
                 });
             if let Some(mask) = self.soft_key_mask.0 {
-                if ui.link("(view)").clicked() {
+                if ui.link(tr("(view)")).clicked() {
                     *design.get_mut_selected().borrow_mut() = mask.into();
                 }
             }
         });
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_references_list(
             ui,
             design,
@@ -756,10 +1003,11 @@ impl ConfigurableObject for DataMask {
         );
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -770,12 +1018,12 @@ impl ConfigurableObject for AlarmMask {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.horizontal(|ui| {
-            egui::ComboBox::from_label("Soft Key Mask")
+            egui::ComboBox::from_label(tr("Soft Key Mask"))
                 .selected_text(
                     self.soft_key_mask
                         .0
@@ -796,26 +1044,26 @@ impl ConfigurableObject for AlarmMask {
                     }
                 });
             if let Some(mask) = self.soft_key_mask.0 {
-                if ui.link("(view)").clicked() {
+                if ui.link(tr("(view)")).clicked() {
                     *design.get_mut_selected().borrow_mut() = mask.into();
                 }
             }
         });
         ui.horizontal(|ui| {
-            ui.label("Priority:");
-            ui.radio_value(&mut self.priority, 2, "Low");
-            ui.radio_value(&mut self.priority, 1, "Medium");
-            ui.radio_value(&mut self.priority, 0, "High");
+            ui.label(tr("Priority:"));
+            ui.radio_value(&mut self.priority, 2, tr("Low"));
+            ui.radio_value(&mut self.priority, 1, tr("Medium"));
+            ui.radio_value(&mut self.priority, 0, tr("High"));
         });
         ui.horizontal(|ui| {
-            ui.label("Acoustic signal:");
-            ui.radio_value(&mut self.acoustic_signal, 3, "None");
-            ui.radio_value(&mut self.acoustic_signal, 2, "Lowest");
-            ui.radio_value(&mut self.acoustic_signal, 1, "Medium");
-            ui.radio_value(&mut self.acoustic_signal, 0, "Highest");
+            ui.label(tr("Acoustic signal:"));
+            ui.radio_value(&mut self.acoustic_signal, 3, tr("None"));
+            ui.radio_value(&mut self.acoustic_signal, 2, tr("Lowest"));
+            ui.radio_value(&mut self.acoustic_signal, 1, tr("Medium"));
+            ui.radio_value(&mut self.acoustic_signal, 0, tr("Highest"));
         });
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_references_list(
             ui,
             design,
@@ -827,10 +1075,11 @@ impl ConfigurableObject for AlarmMask {
         );
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -840,19 +1089,19 @@ impl ConfigurableObject for AlarmMask {
 impl ConfigurableObject for Container {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
-        ui.checkbox(&mut self.hidden, "Hidden");
+        ui.checkbox(&mut self.hidden, tr("Hidden"));
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_references_list(
             ui,
             design,
@@ -862,12 +1111,14 @@ impl ConfigurableObject for Container {
             &Self::get_allowed_child_refs(VtVersion::Version3),
             self.id,
         );
+        render_grid_layout(ui, design, &mut self.object_refs);
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -878,12 +1129,12 @@ impl ConfigurableObject for SoftKeyMask {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_id_list(
             ui,
             design,
@@ -893,10 +1144,11 @@ impl ConfigurableObject for SoftKeyMask {
         );
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -907,32 +1159,34 @@ impl ConfigurableObject for Key {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.horizontal(|ui| {
-            ui.label("Key code:");
+            ui.label(tr("Key code:"));
             ui.radio_value(&mut self.key_code, 0, "ACK");
-            ui.add(egui::DragValue::new(&mut self.key_code).speed(1));
+            ui.add(number_field(&mut self.key_code, design).speed(1));
         });
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_references_list(
             ui,
             design,
-            design.mask_size,
-            design.mask_size,
+            design.soft_key_size.0,
+            design.soft_key_size.1,
             &mut self.object_refs,
             &Self::get_allowed_child_refs(VtVersion::Version3),
             self.id,
         );
+        render_designator_warnings(ui, design, &self.object_refs);
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -944,53 +1198,75 @@ impl ConfigurableObject for Button {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
 
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.border_colour, 0..=255)
-                .text("Border Colour")
+            number_slider(&mut self.border_colour, 0..=255, design)
+                .text(tr("Border Colour"))
                 .drag_value_speed(1.0),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Key code:");
-            ui.add(egui::DragValue::new(&mut self.key_code).speed(1.0));
+            ui.label(tr("Key code:"));
+            ui.add(number_field(&mut self.key_code, design).speed(1.0));
         });
 
         ui.separator();
-        ui.checkbox(&mut self.options.latchable, "Latchable");
+        ui.checkbox(&mut self.options.latchable, tr("Latchable"));
         if self.options.latchable {
             ui.horizontal(|ui| {
-                ui.label("Initial State:");
-                ui.radio_value(&mut self.options.state, ButtonState::Released, "Released");
-                ui.radio_value(&mut self.options.state, ButtonState::Latched, "Latched");
+                ui.label(tr("Initial State:"));
+                ui.radio_value(
+                    &mut self.options.state,
+                    ButtonState::Released,
+                    tr("Released"),
+                );
+                ui.radio_value(&mut self.options.state, ButtonState::Latched, tr("Latched"));
             });
         }
 
+        // Only affects the preview in edit mode, the pool itself is not changed
+        let mut preview = with_preview_state(ui.ctx(), |state| {
+            state
+                .button_preview
+                .get(&self.id)
+                .copied()
+                .unwrap_or_default()
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Preview State:"));
+            ui.radio_value(&mut preview, ButtonPreviewState::Initial, tr("Initial"));
+            ui.radio_value(&mut preview, ButtonPreviewState::Pressed, tr("Pressed"));
+            ui.radio_value(&mut preview, ButtonPreviewState::Disabled, tr("Disabled"));
+        });
+        with_preview_state(ui.ctx(), |state| {
+            state.button_preview.insert(self.id, preview);
+        });
+
         // TODO: check if we have VT version 4 or later
-        // ui.checkbox(&mut self.options.suppress_border, "Suppress Border");
+        // ui.checkbox(&mut self.options.suppress_border, tr("Suppress Border"));
         // ui.checkbox(
         //     &mut self.options.transparent_background,
         //     "Transparent Background",
         // );
-        // ui.checkbox(&mut self.options.disabled, "Disabled");
-        // ui.checkbox(&mut self.options.no_border, "No Border");
+        // ui.checkbox(&mut self.options.disabled, tr("Disabled"));
+        // ui.checkbox(&mut self.options.no_border, tr("No Border"));
 
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_references_list(
             ui,
             design,
@@ -1002,10 +1278,11 @@ impl ConfigurableObject for Button {
         );
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1016,13 +1293,13 @@ impl ConfigurableObject for InputBoolean {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         egui::ComboBox::from_id_salt("foreground_colour")
@@ -1044,14 +1321,14 @@ impl ConfigurableObject for InputBoolean {
                 }
             });
         ui.horizontal(|ui| {
-            ui.label("Variable reference:");
+            ui.label(tr("Variable reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(format!("{:?}", u16::from(self.variable_reference)))
                 .show_ui(ui, |ui| {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -1070,20 +1347,21 @@ impl ConfigurableObject for InputBoolean {
                 });
         });
         if self.variable_reference.0.is_none() {
-            ui.label("Initial value:");
+            ui.label(tr("Initial value:"));
             egui::ComboBox::from_id_salt("initial_value")
                 .selected_text(format!("{:?}", self.value))
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.value, false, "False");
-                    ui.selectable_value(&mut self.value, true, "True");
+                    ui.selectable_value(&mut self.value, false, tr("False"));
+                    ui.selectable_value(&mut self.value, true, tr("True"));
                 });
         }
-        ui.checkbox(&mut self.enabled, "Enabled");
+        ui.checkbox(&mut self.enabled, tr("Enabled"));
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1094,22 +1372,31 @@ impl ConfigurableObject for InputString {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
+        render_fit_to_text(
+            ui,
+            design,
+            self.id,
+            self.font_attributes,
+            &displayed_text(design, self.variable_reference, &self.value),
+            self.options.auto_wrap,
+            (&mut self.width, &mut self.height),
+        );
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.horizontal(|ui| {
-            ui.label("Font attributes:");
+            ui.label(tr("Font attributes:"));
             egui::ComboBox::from_id_salt("font_attributes")
                 .selected_text(format!("{:?}", u16::from(self.font_attributes)))
                 .show_ui(ui, |ui| {
@@ -1126,11 +1413,15 @@ impl ConfigurableObject for InputString {
                 });
         });
         ui.horizontal(|ui| {
-            ui.label("Input attributes:");
+            ui.label(tr("Input attributes:"));
             egui::ComboBox::from_id_salt("input_attributes")
                 .selected_text(format!("{:?}", u16::from(self.input_attributes)))
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.input_attributes, NullableObjectId::NULL, "None");
+                    ui.selectable_value(
+                        &mut self.input_attributes,
+                        NullableObjectId::NULL,
+                        tr("None"),
+                    );
                     for potential_child in design
                         .get_pool()
                         .objects_by_type(ObjectType::InputAttributes)
@@ -1143,21 +1434,21 @@ impl ConfigurableObject for InputString {
                     }
                 });
         });
-        ui.checkbox(&mut self.options.transparent, "Transparent Background");
-        ui.checkbox(&mut self.options.auto_wrap, "Auto Wrap");
+        ui.checkbox(&mut self.options.transparent, tr("Transparent Background"));
+        ui.checkbox(&mut self.options.auto_wrap, tr("Auto Wrap"));
         // TODO: check if we have VT version 4 or later
         // if self.options.auto_wrap {
-        //     ui.checkbox(&mut self.options.wrap_on_hyphen, "Wrap on Hyphen");
+        //     ui.checkbox(&mut self.options.wrap_on_hyphen, tr("Wrap on Hyphen"));
         // }
         ui.horizontal(|ui| {
-            ui.label("Variable reference:");
+            ui.label(tr("Variable reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(format!("{:?}", u16::from(self.variable_reference)))
                 .show_ui(ui, |ui| {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -1176,26 +1467,26 @@ impl ConfigurableObject for InputString {
                 });
         });
         ui.horizontal(|ui| {
-            ui.label("Horizontal Justification:");
+            ui.label(tr("Horizontal Justification:"));
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Left,
-                "Left",
+                tr("Left"),
             );
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Middle,
-                "Middle",
+                tr("Middle"),
             );
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Right,
-                "Right",
+                tr("Right"),
             );
         });
         // TODO: check if we have VT version 4 or later
         // ui.horizontal(|ui| {
-        //     ui.label("Vertical Justification:");
+        //     ui.label(tr("Vertical Justification:"));
         //     ui.radio_value(
         //         &mut self.justification.vertical,
         //         VerticalAlignment::Top,
@@ -1213,41 +1504,137 @@ impl ConfigurableObject for InputString {
         //     );
         // });
         if self.variable_reference.0.is_none() {
-            ui.label("Initial value:");
-            ui.text_edit_singleline(&mut self.value);
+            ui.label(tr("Initial value:"));
+            spell_checked_text_edit(ui, &mut self.value);
         }
-        ui.checkbox(&mut self.enabled, "Enabled");
+        ui.checkbox(&mut self.enabled, tr("Enabled"));
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
     }
 }
 
+/// Calculator that shows step by step what a number object displays for a raw value, since
+/// misconfigured offset and scale are easy to miss in the preview
+#[allow(clippy::too_many_arguments)]
+fn render_number_sandbox(
+    ui: &mut egui::Ui,
+    id: ObjectId,
+    value: u32,
+    range: (u32, u32),
+    offset: i32,
+    scale: f32,
+    nr_of_decimals: u8,
+    format: &FormatType,
+    options: &NumberOptions,
+) {
+    let display = |raw: u32| {
+        format_vt_number(raw, offset, scale, nr_of_decimals, format, options)
+            .map_or(tr("(blank)").to_string(), |text| format!("\"{}\"", text))
+    };
+    egui::CollapsingHeader::new(tr("Formatting sandbox"))
+        .id_salt(("number_sandbox", id.value()))
+        .show(ui, |ui| {
+            let raw_id = egui::Id::new(("number_sandbox_raw", id.value()));
+            let mut raw = ui.data(|data| data.get_temp(raw_id).unwrap_or(value));
+            ui.horizontal(|ui| {
+                ui.label(tr("Raw value:"));
+                ui.add(egui::DragValue::new(&mut raw).speed(1.0));
+                if ui.small_button(tr("Current")).clicked() {
+                    raw = value;
+                }
+                if ui.small_button(tr("Min")).clicked() {
+                    raw = range.0;
+                }
+                if ui.small_button(tr("Max")).clicked() {
+                    raw = range.1;
+                }
+            });
+            ui.data_mut(|data| data.insert_temp(raw_id, raw));
+
+            let with_offset = raw as f64 + offset as f64;
+            let scaled = with_offset * scale as f64;
+            let decimals = nr_of_decimals.min(7);
+            egui::Grid::new(("number_sandbox_steps", id.value()))
+                .num_columns(2)
+                .show(ui, |ui| {
+                    ui.label(tr_args("+ offset ({})", &[&offset]));
+                    ui.monospace(with_offset.to_string());
+                    ui.end_row();
+                    ui.label(tr_args("× scale ({})", &[&scale]));
+                    ui.monospace(scaled.to_string());
+                    ui.end_row();
+                    let rounding = if options.truncate {
+                        tr("Truncated")
+                    } else {
+                        tr("Rounded")
+                    };
+                    ui.label(tr_args("{} to {} decimals", &[&rounding, &decimals]));
+                    let rounded = round_vt_number(scaled, decimals, options.truncate);
+                    ui.monospace(format!("{:.*}", decimals as usize, rounded));
+                    ui.end_row();
+                    ui.strong(tr("Displayed"));
+                    ui.monospace(display(raw));
+                    ui.end_row();
+                });
+            ui.label(tr_args(
+                "Range: {} to {}",
+                &[&display(range.0), &display(range.1)],
+            ));
+
+            if scale == 0.0 {
+                ui.colored_label(
+                    egui::Color32::ORANGE,
+                    tr("Scale is 0, every value displays as 0"),
+                );
+            }
+            if nr_of_decimals > 7 {
+                ui.colored_label(
+                    egui::Color32::ORANGE,
+                    tr("A VT shows at most 7 decimals, the rest is ignored"),
+                );
+            }
+            if scale as f64 != (scale.to_string().parse::<f64>().unwrap_or(0.0)) {
+                ui.colored_label(
+                    egui::Color32::ORANGE,
+                    tr_args(
+                        "The scale is stored as a 32-bit float: {}",
+                        &[&(scale as f64)],
+                    ),
+                );
+            }
+            if options.display_leading_zeros {
+                ui.label(tr("The field is filled with leading zeros up to its width"));
+            }
+        });
+}
+
 impl ConfigurableObject for InputNumber {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.horizontal(|ui| {
-            ui.label("Font attributes:");
+            ui.label(tr("Font attributes:"));
             egui::ComboBox::from_id_salt("font_attributes")
                 .selected_text(format!("{:?}", u16::from(self.font_attributes)))
                 .show_ui(ui, |ui| {
@@ -1263,26 +1650,26 @@ impl ConfigurableObject for InputNumber {
                     }
                 });
         });
-        ui.checkbox(&mut self.options.transparent, "Transparent Background");
+        ui.checkbox(&mut self.options.transparent, tr("Transparent Background"));
         ui.checkbox(
             &mut self.options.display_leading_zeros,
-            "Display Leading Zeros",
+            tr("Display Leading Zeros"),
         );
         ui.checkbox(
             &mut self.options.display_zero_as_blank,
-            "Display Zero as Blank",
+            tr("Display Zero as Blank"),
         );
         // TODO: check if we have VT version 4 or later
-        // ui.checkbox(&mut self.options.truncate, "Truncate");
+        // ui.checkbox(&mut self.options.truncate, tr("Truncate"));
         ui.horizontal(|ui| {
-            ui.label("Variable reference:");
+            ui.label(tr("Variable reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(format!("{:?}", u16::from(self.variable_reference)))
                 .show_ui(ui, |ui| {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -1301,57 +1688,68 @@ impl ConfigurableObject for InputNumber {
                 });
         });
         if self.variable_reference.0.is_none() {
-            ui.label("Initial value:");
-            ui.add(egui::DragValue::new(&mut self.value).speed(1.0));
+            ui.label(tr("Initial value:"));
+            ui.add(number_field(&mut self.value, design).speed(1.0));
         }
         ui.add(
-            egui::DragValue::new(&mut self.min_value)
+            number_field(&mut self.min_value, design)
                 .speed(1.0)
-                .prefix("Min: "),
+                .prefix(tr("Min: ")),
         );
         ui.add(
-            egui::DragValue::new(&mut self.max_value)
+            number_field(&mut self.max_value, design)
                 .speed(1.0)
-                .prefix("Max: "),
+                .prefix(tr("Max: ")),
         );
         ui.add(
-            egui::DragValue::new(&mut self.offset)
+            number_field(&mut self.offset, design)
                 .speed(1.0)
-                .prefix("Offset: "),
+                .prefix(tr("Offset: ")),
         );
-        ui.add(egui::DragValue::new(&mut self.scale).prefix("Scale: "));
+        ui.add(number_field(&mut self.scale, design).prefix(tr("Scale: ")));
         ui.add(
-            egui::DragValue::new(&mut self.nr_of_decimals)
+            number_field(&mut self.nr_of_decimals, design)
                 .speed(1.0)
-                .prefix("Number of Decimals: "),
+                .prefix(tr("Number of Decimals: ")),
         );
         ui.horizontal(|ui| {
-            ui.label("Format:");
-            ui.radio_value(&mut self.format, FormatType::Decimal, "Decimal");
-            ui.radio_value(&mut self.format, FormatType::Exponential, "Exponential");
+            ui.label(tr("Format:"));
+            ui.radio_value(&mut self.format, FormatType::Decimal, tr("Decimal"));
+            ui.radio_value(&mut self.format, FormatType::Exponential, tr("Exponential"));
         });
+        render_number_sandbox(
+            ui,
+            self.id,
+            self.value,
+            (self.min_value, self.max_value),
+            self.offset,
+            self.scale,
+            self.nr_of_decimals,
+            &self.format,
+            &self.options,
+        );
 
         ui.horizontal(|ui| {
-            ui.label("Horizontal Justification:");
+            ui.label(tr("Horizontal Justification:"));
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Left,
-                "Left",
+                tr("Left"),
             );
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Middle,
-                "Middle",
+                tr("Middle"),
             );
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Right,
-                "Right",
+                tr("Right"),
             );
         });
         // TODO: check if we have VT version 4 or later
         // ui.horizontal(|ui| {
-        //     ui.label("Vertical Justification:");
+        //     ui.label(tr("Vertical Justification:"));
         //     ui.radio_value(
         //         &mut self.justification.vertical,
         //         VerticalAlignment::Top,
@@ -1369,15 +1767,16 @@ impl ConfigurableObject for InputNumber {
         //     );
         // });
 
-        ui.checkbox(&mut self.options2.enabled, "Enabled");
+        ui.checkbox(&mut self.options2.enabled, tr("Enabled"));
         // TODO: check if we have VT version 4 or later
-        // ui.checkbox(&mut self.options2.real_time_editing, "Real Time Editing");
+        // ui.checkbox(&mut self.options2.real_time_editing, tr("Real Time Editing"));
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1388,24 +1787,24 @@ impl ConfigurableObject for InputList {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
         ui.horizontal(|ui| {
-            ui.label("Variable reference:");
+            ui.label(tr("Variable reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(format!("{:?}", u16::from(self.variable_reference)))
                 .show_ui(ui, |ui| {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -1424,16 +1823,16 @@ impl ConfigurableObject for InputList {
                 });
         });
         if self.variable_reference.0.is_none() {
-            ui.label("Initial value:");
-            ui.add(egui::DragValue::new(&mut self.value).speed(1.0));
+            ui.label(tr("Initial value:"));
+            ui.add(number_field(&mut self.value, design).speed(1.0));
         }
 
-        ui.checkbox(&mut self.options.enabled, "Enabled");
+        ui.checkbox(&mut self.options.enabled, tr("Enabled"));
         // TODO: check if we have VT version 4 or later
-        // ui.checkbox(&mut self.options.real_time_editing, "Real Time Editing");
+        // ui.checkbox(&mut self.options.real_time_editing, tr("Real Time Editing"));
 
         ui.separator();
-        ui.label("List items:");
+        ui.label(tr("List items:"));
         render_nullable_object_id_list(
             ui,
             design,
@@ -1443,10 +1842,11 @@ impl ConfigurableObject for InputList {
         );
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1457,22 +1857,31 @@ impl ConfigurableObject for OutputString {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
+        render_fit_to_text(
+            ui,
+            design,
+            self.id,
+            self.font_attributes,
+            &displayed_text(design, self.variable_reference, &self.value),
+            self.options.auto_wrap,
+            (&mut self.width, &mut self.height),
+        );
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.horizontal(|ui| {
-            ui.label("Font attributes:");
+            ui.label(tr("Font attributes:"));
             egui::ComboBox::from_id_salt("font_attributes")
                 .selected_text(format!("{:?}", u16::from(self.font_attributes)))
                 .show_ui(ui, |ui| {
@@ -1488,21 +1897,21 @@ impl ConfigurableObject for OutputString {
                     }
                 });
         });
-        ui.checkbox(&mut self.options.transparent, "Transparent Background");
-        ui.checkbox(&mut self.options.auto_wrap, "Auto Wrap");
+        ui.checkbox(&mut self.options.transparent, tr("Transparent Background"));
+        ui.checkbox(&mut self.options.auto_wrap, tr("Auto Wrap"));
         // TODO: check if we have VT version 4 or later
         // if self.options.auto_wrap {
-        //     ui.checkbox(&mut self.options.wrap_on_hyphen, "Wrap on Hyphen");
+        //     ui.checkbox(&mut self.options.wrap_on_hyphen, tr("Wrap on Hyphen"));
         // }
         ui.horizontal(|ui| {
-            ui.label("Variable reference:");
+            ui.label(tr("Variable reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(format!("{:?}", u16::from(self.variable_reference)))
                 .show_ui(ui, |ui| {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -1521,26 +1930,26 @@ impl ConfigurableObject for OutputString {
                 });
         });
         ui.horizontal(|ui| {
-            ui.label("Horizontal Justification:");
+            ui.label(tr("Horizontal Justification:"));
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Left,
-                "Left",
+                tr("Left"),
             );
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Middle,
-                "Middle",
+                tr("Middle"),
             );
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Right,
-                "Right",
+                tr("Right"),
             );
         });
         // TODO: check if we have VT version 4 or later
         // ui.horizontal(|ui| {
-        //     ui.label("Vertical Justification:");
+        //     ui.label(tr("Vertical Justification:"));
         //     ui.radio_value(
         //         &mut self.justification.vertical,
         //         VerticalAlignment::Top,
@@ -1558,14 +1967,15 @@ impl ConfigurableObject for OutputString {
         //     );
         // });
         if self.variable_reference.0.is_none() {
-            ui.label("Initial value:");
-            ui.text_edit_singleline(&mut self.value);
+            ui.label(tr("Initial value:"));
+            spell_checked_text_edit(ui, &mut self.value);
         }
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1576,22 +1986,22 @@ impl ConfigurableObject for OutputNumber {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
         ui.horizontal(|ui| {
-            ui.label("Font attributes:");
+            ui.label(tr("Font attributes:"));
             egui::ComboBox::from_id_salt("font_attributes")
                 .selected_text(format!("{:?}", u16::from(self.font_attributes)))
                 .show_ui(ui, |ui| {
@@ -1608,26 +2018,26 @@ impl ConfigurableObject for OutputNumber {
                 });
         });
 
-        ui.checkbox(&mut self.options.transparent, "Transparent Background");
+        ui.checkbox(&mut self.options.transparent, tr("Transparent Background"));
         ui.checkbox(
             &mut self.options.display_leading_zeros,
-            "Display Leading Zeros",
+            tr("Display Leading Zeros"),
         );
         ui.checkbox(
             &mut self.options.display_zero_as_blank,
-            "Display Zero as Blank",
+            tr("Display Zero as Blank"),
         );
         // TODO: check if we have VT version 4 or later
-        // ui.checkbox(&mut self.options.truncate, "Truncate");
+        // ui.checkbox(&mut self.options.truncate, tr("Truncate"));
         ui.horizontal(|ui| {
-            ui.label("Variable reference:");
+            ui.label(tr("Variable reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(format!("{:?}", u16::from(self.variable_reference)))
                 .show_ui(ui, |ui| {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -1646,48 +2056,59 @@ impl ConfigurableObject for OutputNumber {
                 });
         });
         if self.variable_reference.0.is_none() {
-            ui.label("Initial value:");
-            ui.add(egui::DragValue::new(&mut self.value).speed(1.0));
+            ui.label(tr("Initial value:"));
+            ui.add(number_field(&mut self.value, design).speed(1.0));
         }
         ui.horizontal(|ui| {
-            ui.label("Offset:");
-            ui.add(egui::DragValue::new(&mut self.offset).speed(1.0));
+            ui.label(tr("Offset:"));
+            ui.add(number_field(&mut self.offset, design).speed(1.0));
         });
         ui.horizontal(|ui| {
-            ui.label("Scale:");
-            ui.add(egui::DragValue::new(&mut self.scale).speed(1.0));
+            ui.label(tr("Scale:"));
+            ui.add(number_field(&mut self.scale, design).speed(1.0));
         });
         ui.horizontal(|ui| {
-            ui.label("Number of Decimals:");
-            ui.add(egui::DragValue::new(&mut self.nr_of_decimals).speed(1.0));
+            ui.label(tr("Number of Decimals:"));
+            ui.add(number_field(&mut self.nr_of_decimals, design).speed(1.0));
         });
         ui.horizontal(|ui| {
-            ui.label("Format:");
-            ui.radio_value(&mut self.format, FormatType::Decimal, "Decimal");
-            ui.radio_value(&mut self.format, FormatType::Exponential, "Exponential");
+            ui.label(tr("Format:"));
+            ui.radio_value(&mut self.format, FormatType::Decimal, tr("Decimal"));
+            ui.radio_value(&mut self.format, FormatType::Exponential, tr("Exponential"));
         });
+        render_number_sandbox(
+            ui,
+            self.id,
+            self.value,
+            (0, u32::MAX),
+            self.offset,
+            self.scale,
+            self.nr_of_decimals,
+            &self.format,
+            &self.options,
+        );
 
         ui.horizontal(|ui| {
-            ui.label("Horizontal Justification:");
+            ui.label(tr("Horizontal Justification:"));
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Left,
-                "Left",
+                tr("Left"),
             );
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Middle,
-                "Middle",
+                tr("Middle"),
             );
             ui.radio_value(
                 &mut self.justification.horizontal,
                 HorizontalAlignment::Right,
-                "Right",
+                tr("Right"),
             );
         });
         // TODO: check if we have VT version 4 or later
         // ui.horizontal(|ui| {
-        //     ui.label("Vertical Justification:");
+        //     ui.label(tr("Vertical Justification:"));
         //     ui.radio_value(
         //         &mut self.justification.vertical,
         //         VerticalAlignment::Top,
@@ -1706,10 +2127,11 @@ impl ConfigurableObject for OutputNumber {
         // });
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1721,25 +2143,25 @@ impl ConfigurableObject for OutputList {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Variable reference:");
+            ui.label(tr("Variable reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(format!("{:?}", u16::from(self.variable_reference)))
                 .show_ui(ui, |ui| {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -1759,12 +2181,12 @@ impl ConfigurableObject for OutputList {
         });
 
         if self.variable_reference.0.is_none() {
-            ui.label("Initial value:");
-            ui.add(egui::DragValue::new(&mut self.value).speed(1.0));
+            ui.label(tr("Initial value:"));
+            ui.add(number_field(&mut self.value, design).speed(1.0));
         }
 
         ui.separator();
-        ui.label("List items:");
+        ui.label(tr("List items:"));
         render_nullable_object_id_list(
             ui,
             design,
@@ -1774,10 +2196,11 @@ impl ConfigurableObject for OutputList {
         );
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1789,7 +2212,7 @@ impl ConfigurableObject for OutputLine {
         render_object_id(ui, &mut self.id, design);
 
         ui.horizontal(|ui| {
-            ui.label("Line Attributes:");
+            ui.label(tr("Line Attributes:"));
             egui::ComboBox::from_id_salt("line_attributes")
                 .selected_text(format!("{:?}", u16::from(self.line_attributes)))
                 .show_ui(ui, |ui| {
@@ -1811,44 +2234,46 @@ impl ConfigurableObject for OutputLine {
 
             // If a valid line_attributes object is selected, provide a link to navigate there
             if let Some(obj) = design.get_pool().object_by_id(self.line_attributes) {
-                if ui.link("(view)").clicked() {
+                if ui.link(tr("(view)")).clicked() {
                     *design.get_mut_selected().borrow_mut() = self.line_attributes.into();
                 }
             } else {
-                ui.colored_label(egui::Color32::RED, "Missing object");
+                ui.colored_label(egui::Color32::RED, tr("Missing object"));
             }
         });
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Line Direction:");
+            ui.label(tr("Line Direction:"));
             ui.radio_value(
                 &mut self.line_direction,
                 LineDirection::TopLeftToBottomRight,
-                "Top-left to bottom-right",
+                tr("Top-left to bottom-right"),
             );
             ui.radio_value(
                 &mut self.line_direction,
                 LineDirection::BottomLeftToTopRight,
-                "Bottom-left to top-right",
+                tr("Bottom-left to top-right"),
             );
         });
+        ui.weak(tr("Drag the endpoints in the preview to move them"));
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1860,7 +2285,7 @@ impl ConfigurableObject for OutputRectangle {
         render_object_id(ui, &mut self.id, design);
 
         ui.horizontal(|ui| {
-            ui.label("Line Attributes:");
+            ui.label(tr("Line Attributes:"));
             egui::ComboBox::from_id_salt("line_attributes_selector")
                 .selected_text(format!("{:?}", u16::from(self.line_attributes)))
                 .show_ui(ui, |ui| {
@@ -1882,33 +2307,33 @@ impl ConfigurableObject for OutputRectangle {
 
             // Link to view the selected line attributes object
             if let Some(obj) = design.get_pool().object_by_id(self.line_attributes) {
-                if ui.link("(view)").clicked() {
+                if ui.link(tr("(view)")).clicked() {
                     *design.get_mut_selected().borrow_mut() = self.line_attributes.into();
                 }
             } else {
-                ui.colored_label(egui::Color32::RED, "Missing object");
+                ui.colored_label(egui::Color32::RED, tr("Missing object"));
             }
         });
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Line Suppression:");
-            ui.add(egui::DragValue::new(&mut self.line_suppression).speed(1.0));
+            ui.label(tr("Line Suppression:"));
+            ui.add(number_field(&mut self.line_suppression, design).speed(1.0));
         });
 
         // Fill Attributes Selection
         ui.horizontal(|ui| {
-            ui.label("Fill Attributes:");
+            ui.label(tr("Fill Attributes:"));
             egui::ComboBox::from_id_salt("fill_attributes_selector")
                 .selected_text(
                     self.fill_attributes
@@ -1916,7 +2341,11 @@ impl ConfigurableObject for OutputRectangle {
                         .map_or("None".to_string(), |id| format!("{:?}", u16::from(id))),
                 )
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.fill_attributes, NullableObjectId::NULL, "None");
+                    ui.selectable_value(
+                        &mut self.fill_attributes,
+                        NullableObjectId::NULL,
+                        tr("None"),
+                    );
                     for potential_child in design
                         .get_pool()
                         .objects_by_type(ObjectType::FillAttributes)
@@ -1936,20 +2365,21 @@ impl ConfigurableObject for OutputRectangle {
             // Link to view the selected fill attributes object if present
             if let Some(id) = self.fill_attributes.into() {
                 if let Some(obj) = design.get_pool().object_by_id(id) {
-                    if ui.link("(view)").clicked() {
+                    if ui.link(tr("(view)")).clicked() {
                         *design.get_mut_selected().borrow_mut() = id.into();
                     }
                 } else {
-                    ui.colored_label(egui::Color32::RED, "Missing object");
+                    ui.colored_label(egui::Color32::RED, tr("Missing object"));
                 }
             }
         });
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -1961,7 +2391,7 @@ impl ConfigurableObject for OutputEllipse {
         render_object_id(ui, &mut self.id, design);
 
         ui.horizontal(|ui| {
-            ui.label("Line Attributes:");
+            ui.label(tr("Line Attributes:"));
             egui::ComboBox::from_id_salt("line_attributes_selector")
                 .selected_text(format!("{:?}", u16::from(self.line_attributes)))
                 .show_ui(ui, |ui| {
@@ -1983,48 +2413,48 @@ impl ConfigurableObject for OutputEllipse {
 
             // Link to navigate to the chosen line attributes object
             if let Some(obj) = design.get_pool().object_by_id(self.line_attributes) {
-                if ui.link("(view)").clicked() {
+                if ui.link(tr("(view)")).clicked() {
                     *design.get_mut_selected().borrow_mut() = self.line_attributes.into();
                 }
             } else {
-                ui.colored_label(egui::Color32::RED, "Missing object");
+                ui.colored_label(egui::Color32::RED, tr("Missing object"));
             }
         });
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
 
-        ui.label("Ellipse Type:");
-        ui.radio_value(&mut self.ellipse_type, 0, "Closed Ellipse");
-        ui.radio_value(&mut self.ellipse_type, 1, "Open Ellipse");
-        ui.radio_value(&mut self.ellipse_type, 2, "Closed Ellipse Segment");
-        ui.radio_value(&mut self.ellipse_type, 3, "Closed Ellipse Section");
+        ui.label(tr("Ellipse Type:"));
+        ui.radio_value(&mut self.ellipse_type, 0, tr("Closed Ellipse"));
+        ui.radio_value(&mut self.ellipse_type, 1, tr("Open Ellipse"));
+        ui.radio_value(&mut self.ellipse_type, 2, tr("Closed Ellipse Segment"));
+        ui.radio_value(&mut self.ellipse_type, 3, tr("Closed Ellipse Section"));
 
         ui.horizontal(|ui| {
-            ui.label("Start Angle:");
+            ui.label(tr("Start Angle:"));
             ui.add(
-                egui::DragValue::new(&mut self.start_angle)
+                number_field(&mut self.start_angle, design)
                     .speed(1.0)
                     .range(0..=180),
             );
-            ui.label("End Angle:");
+            ui.label(tr("End Angle:"));
             ui.add(
-                egui::DragValue::new(&mut self.end_angle)
+                number_field(&mut self.end_angle, design)
                     .speed(1.0)
                     .range(0..=180),
             );
         });
 
         ui.horizontal(|ui| {
-            ui.label("Fill Attributes:");
+            ui.label(tr("Fill Attributes:"));
             egui::ComboBox::from_id_salt("fill_attributes_selector")
                 .selected_text(
                     self.fill_attributes
@@ -2032,7 +2462,11 @@ impl ConfigurableObject for OutputEllipse {
                         .map_or("None".to_string(), |id| format!("{:?}", u16::from(id))),
                 )
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.fill_attributes, NullableObjectId::NULL, "None");
+                    ui.selectable_value(
+                        &mut self.fill_attributes,
+                        NullableObjectId::NULL,
+                        tr("None"),
+                    );
                     for potential_child in design
                         .get_pool()
                         .objects_by_type(ObjectType::FillAttributes)
@@ -2052,20 +2486,21 @@ impl ConfigurableObject for OutputEllipse {
             // Link to view the chosen fill attributes object, if any
             if let Some(id) = self.fill_attributes.into() {
                 if let Some(obj) = design.get_pool().object_by_id(id) {
-                    if ui.link("(view)").clicked() {
+                    if ui.link(tr("(view)")).clicked() {
                         *design.get_mut_selected().borrow_mut() = id.into();
                     }
                 } else {
-                    ui.colored_label(egui::Color32::RED, "Missing object");
+                    ui.colored_label(egui::Color32::RED, tr("Missing object"));
                 }
             }
         });
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -2077,18 +2512,18 @@ impl ConfigurableObject for OutputPolygon {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Line Attributes:");
+            ui.label(tr("Line Attributes:"));
             egui::ComboBox::from_id_salt("line_attributes_selector")
                 .selected_text(format!("{:?}", u16::from(self.line_attributes)))
                 .show_ui(ui, |ui| {
@@ -2110,16 +2545,16 @@ impl ConfigurableObject for OutputPolygon {
 
             // Link to navigate to the chosen line attributes object
             if let Some(obj) = design.get_pool().object_by_id(self.line_attributes) {
-                if ui.link("(view)").clicked() {
+                if ui.link(tr("(view)")).clicked() {
                     *design.get_mut_selected().borrow_mut() = self.line_attributes.into();
                 }
             } else {
-                ui.colored_label(egui::Color32::RED, "Missing object");
+                ui.colored_label(egui::Color32::RED, tr("Missing object"));
             }
         });
 
         ui.horizontal(|ui| {
-            ui.label("Fill Attributes:");
+            ui.label(tr("Fill Attributes:"));
             egui::ComboBox::from_id_salt("fill_attributes_selector")
                 .selected_text(
                     self.fill_attributes
@@ -2127,7 +2562,11 @@ impl ConfigurableObject for OutputPolygon {
                         .map_or("None".to_string(), |id| format!("{:?}", u16::from(id))),
                 )
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.fill_attributes, NullableObjectId::NULL, "None");
+                    ui.selectable_value(
+                        &mut self.fill_attributes,
+                        NullableObjectId::NULL,
+                        tr("None"),
+                    );
                     for potential_child in design
                         .get_pool()
                         .objects_by_type(ObjectType::FillAttributes)
@@ -2147,36 +2586,37 @@ impl ConfigurableObject for OutputPolygon {
             // Link to view the chosen fill attributes object
             if let Some(id) = self.fill_attributes.into() {
                 if let Some(obj) = design.get_pool().object_by_id(id) {
-                    if ui.link("(view)").clicked() {
+                    if ui.link(tr("(view)")).clicked() {
                         *design.get_mut_selected().borrow_mut() = id.into();
                     }
                 } else {
-                    ui.colored_label(egui::Color32::RED, "Missing object");
+                    ui.colored_label(egui::Color32::RED, tr("Missing object"));
                 }
             }
         });
 
-        ui.label("Polygon Type:");
-        ui.radio_value(&mut self.polygon_type, 0, "Convex");
-        ui.radio_value(&mut self.polygon_type, 1, "Non-Convex");
-        ui.radio_value(&mut self.polygon_type, 2, "Complex");
-        ui.radio_value(&mut self.polygon_type, 3, "Open");
+        ui.label(tr("Polygon Type:"));
+        ui.radio_value(&mut self.polygon_type, 0, tr("Convex"));
+        ui.radio_value(&mut self.polygon_type, 1, tr("Non-Convex"));
+        ui.radio_value(&mut self.polygon_type, 2, tr("Complex"));
+        ui.radio_value(&mut self.polygon_type, 3, tr("Open"));
 
         ui.separator();
-        ui.label("Points:");
+        render_polygon_point_editor(ui, self);
+        ui.label(tr("Points:"));
         egui::Grid::new("points_grid")
             .striped(true)
             .min_col_width(0.0)
             .show(ui, |ui| {
                 let mut idx = 0;
                 while idx < self.points.len() {
-                    ui.label(format!("Point {}", idx));
-                    ui.add(egui::DragValue::new(&mut self.points[idx].x).speed(1.0));
-                    ui.add(egui::DragValue::new(&mut self.points[idx].y).speed(1.0));
+                    ui.label(tr_args("Point {}", &[&idx]));
+                    ui.add(number_field(&mut self.points[idx].x, design).speed(1.0));
+                    ui.add(number_field(&mut self.points[idx].y, design).speed(1.0));
 
                     if ui
                         .add_enabled(idx > 0, egui::Button::new("\u{23F6}"))
-                        .on_hover_text("Move Up")
+                        .on_hover_text(tr("Move Up"))
                         .clicked()
                     {
                         self.points.swap(idx, idx - 1);
@@ -2184,7 +2624,7 @@ impl ConfigurableObject for OutputPolygon {
 
                     if ui
                         .add_enabled(idx < self.points.len() - 1, egui::Button::new("\u{23F7}"))
-                        .on_hover_text("Move Down")
+                        .on_hover_text(tr("Move Down"))
                         .clicked()
                     {
                         self.points.swap(idx, idx + 1);
@@ -2192,7 +2632,7 @@ impl ConfigurableObject for OutputPolygon {
                     if self.points.len() > 3 {
                         if ui
                             .add(egui::Button::new("\u{1F5D9}"))
-                            .on_hover_text("Remove")
+                            .on_hover_text(tr("Remove"))
                             .clicked()
                         {
                             self.points.remove(idx);
@@ -2205,15 +2645,16 @@ impl ConfigurableObject for OutputPolygon {
                 }
             });
 
-        if ui.button("Add Point").clicked() {
+        if ui.button(tr("Add Point")).clicked() {
             self.points.push(Point { x: 0, y: 0 });
         }
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -2225,77 +2666,70 @@ impl ConfigurableObject for OutputMeter {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
 
         ui.add(
-            egui::Slider::new(&mut self.needle_colour, 0..=255)
-                .text("Needle Colour")
+            number_slider(&mut self.needle_colour, 0..=255, design)
+                .text(tr("Needle Colour"))
                 .drag_value_speed(1.0),
         );
 
         ui.add(
-            egui::Slider::new(&mut self.border_colour, 0..=255)
-                .text("Border Colour")
+            number_slider(&mut self.border_colour, 0..=255, design)
+                .text(tr("Border Colour"))
                 .drag_value_speed(1.0),
         );
 
         ui.add(
-            egui::Slider::new(&mut self.arc_and_tick_colour, 0..=255)
-                .text("Arc & Tick Colour")
+            number_slider(&mut self.arc_and_tick_colour, 0..=255, design)
+                .text(tr("Arc & Tick Colour"))
                 .drag_value_speed(1.0),
         );
 
-        ui.checkbox(&mut self.options.draw_arc, "Draw Arc");
-        ui.checkbox(&mut self.options.draw_border, "Draw Border");
-        ui.checkbox(&mut self.options.draw_ticks, "Draw Ticks");
+        ui.checkbox(&mut self.options.draw_arc, tr("Draw Arc"));
+        ui.checkbox(&mut self.options.draw_border, tr("Draw Border"));
+        ui.checkbox(&mut self.options.draw_ticks, tr("Draw Ticks"));
 
         ui.horizontal(|ui| {
-            ui.label("Deflection Direction:");
+            ui.label(tr("Deflection Direction:"));
             ui.radio_value(
                 &mut self.options.deflection_direction,
                 DeflectionDirection::AntiClockwise,
-                "Anti-clockwise",
+                tr("Anti-clockwise"),
             );
             ui.radio_value(
                 &mut self.options.deflection_direction,
                 DeflectionDirection::Clockwise,
-                "Clockwise",
+                tr("Clockwise"),
             );
         });
 
         ui.add(
-            egui::DragValue::new(&mut self.nr_of_ticks)
-                .speed(1.0)
-                .prefix("Number of Ticks: "),
-        );
-        ui.add(
-            egui::DragValue::new(&mut self.start_angle)
+            number_field(&mut self.nr_of_ticks, design)
                 .speed(1.0)
-                .prefix("Start Angle: ")
-                .range(0..=180),
+                .prefix(tr("Number of Ticks: ")),
         );
+        render_iso_angle(ui, tr("Start Angle:"), &mut self.start_angle);
+        render_iso_angle(ui, tr("End Angle:"), &mut self.end_angle);
+        ui.weak(tr(
+            "Drag the angle handles in the preview to change the sweep",
+        ));
         ui.add(
-            egui::DragValue::new(&mut self.end_angle)
+            number_field(&mut self.min_value, design)
                 .speed(1.0)
-                .prefix("End Angle: ")
-                .range(0..=180),
+                .prefix(tr("Min Value: ")),
         );
         ui.add(
-            egui::DragValue::new(&mut self.min_value)
+            number_field(&mut self.max_value, design)
                 .speed(1.0)
-                .prefix("Min Value: "),
-        );
-        ui.add(
-            egui::DragValue::new(&mut self.max_value)
-                .speed(1.0)
-                .prefix("Max Value: "),
+                .prefix(tr("Max Value: ")),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Variable reference:");
+            ui.label(tr("Variable reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(
                     self.variable_reference
@@ -2306,7 +2740,7 @@ impl ConfigurableObject for OutputMeter {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -2327,15 +2761,16 @@ impl ConfigurableObject for OutputMeter {
 
         // If there's no variable reference, allow editing the initial value
         if self.variable_reference.0.is_none() {
-            ui.label("Initial value:");
-            ui.add(egui::DragValue::new(&mut self.value).speed(1.0));
+            ui.label(tr("Initial value:"));
+            ui.add(number_field(&mut self.value, design).speed(1.0));
         }
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -2347,94 +2782,94 @@ impl ConfigurableObject for OutputLinearBarGraph {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
 
         ui.add(
-            egui::Slider::new(&mut self.colour, 0..=255)
-                .text("Bar Colour")
+            number_slider(&mut self.colour, 0..=255, design)
+                .text(tr("Bar Colour"))
                 .drag_value_speed(1.0),
         );
         if self.options.draw_target_line {
             ui.add(
-                egui::Slider::new(&mut self.target_line_colour, 0..=255)
-                    .text("Target Line Colour")
+                number_slider(&mut self.target_line_colour, 0..=255, design)
+                    .text(tr("Target Line Colour"))
                     .drag_value_speed(1.0),
             );
         }
 
-        ui.checkbox(&mut self.options.draw_border, "Draw Border");
-        ui.checkbox(&mut self.options.draw_target_line, "Draw Target Line");
-        ui.checkbox(&mut self.options.draw_ticks, "Draw Ticks");
+        ui.checkbox(&mut self.options.draw_border, tr("Draw Border"));
+        ui.checkbox(&mut self.options.draw_target_line, tr("Draw Target Line"));
+        ui.checkbox(&mut self.options.draw_ticks, tr("Draw Ticks"));
         ui.horizontal(|ui| {
-            ui.label("Bar Graph Type:");
+            ui.label(tr("Bar Graph Type:"));
             ui.radio_value(
                 &mut self.options.bar_graph_type,
                 BarGraphType::Filled,
-                "Filled",
+                tr("Filled"),
             );
             ui.radio_value(
                 &mut self.options.bar_graph_type,
                 BarGraphType::NotFilled,
-                "Not Filled",
+                tr("Not Filled"),
             );
         });
 
         ui.horizontal(|ui| {
-            ui.label("Axis Orientation:");
+            ui.label(tr("Axis Orientation:"));
             ui.radio_value(
                 &mut self.options.axis_orientation,
                 AxisOrientation::Vertical,
-                "Vertical",
+                tr("Vertical"),
             );
             ui.radio_value(
                 &mut self.options.axis_orientation,
                 AxisOrientation::Horizontal,
-                "Horizontal",
+                tr("Horizontal"),
             );
         });
 
         ui.horizontal(|ui| {
-            ui.label("Grow Direction:");
+            ui.label(tr("Grow Direction:"));
             ui.radio_value(
                 &mut self.options.grow_direction,
                 GrowDirection::GrowLeftDown,
-                "Left/Down",
+                tr("Left/Down"),
             );
             ui.radio_value(
                 &mut self.options.grow_direction,
                 GrowDirection::GrowRightUp,
-                "Right/Up",
+                tr("Right/Up"),
             );
         });
 
         if self.options.draw_ticks {
             ui.add(
-                egui::DragValue::new(&mut self.nr_of_ticks)
+                number_field(&mut self.nr_of_ticks, design)
                     .speed(1.0)
-                    .prefix("Number of Ticks: "),
+                    .prefix(tr("Number of Ticks: ")),
             );
         }
         ui.add(
-            egui::DragValue::new(&mut self.min_value)
+            number_field(&mut self.min_value, design)
                 .speed(1.0)
-                .prefix("Min Value: "),
+                .prefix(tr("Min Value: ")),
         );
         ui.add(
-            egui::DragValue::new(&mut self.max_value)
+            number_field(&mut self.max_value, design)
                 .speed(1.0)
-                .prefix("Max Value: "),
+                .prefix(tr("Max Value: ")),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Variable Reference:");
+            ui.label(tr("Variable Reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(
                     self.variable_reference
@@ -2445,7 +2880,7 @@ impl ConfigurableObject for OutputLinearBarGraph {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -2466,12 +2901,12 @@ impl ConfigurableObject for OutputLinearBarGraph {
 
         // If no variable reference, allow setting initial value manually
         if self.variable_reference.0.is_none() {
-            ui.label("Initial Value:");
-            ui.add(egui::DragValue::new(&mut self.value).speed(1.0));
+            ui.label(tr("Initial Value:"));
+            ui.add(number_field(&mut self.value, design).speed(1.0));
         }
 
         ui.horizontal(|ui| {
-            ui.label("Target Value Variable Reference:");
+            ui.label(tr("Target Value Variable Reference:"));
             egui::ComboBox::from_id_salt("target_value_variable_reference")
                 .selected_text(
                     self.target_value_variable_reference
@@ -2482,7 +2917,7 @@ impl ConfigurableObject for OutputLinearBarGraph {
                     ui.selectable_value(
                         &mut self.target_value_variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -2503,15 +2938,16 @@ impl ConfigurableObject for OutputLinearBarGraph {
 
         // If no target value variable reference, allow setting target value manually
         if self.target_value_variable_reference.0.is_none() {
-            ui.label("Target Value:");
-            ui.add(egui::DragValue::new(&mut self.target_value).speed(1.0));
+            ui.label(tr("Target Value:"));
+            ui.add(number_field(&mut self.target_value, design).speed(1.0));
         }
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -2523,116 +2959,111 @@ impl ConfigurableObject for OutputArchedBarGraph {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
         ui.add(
-            egui::Slider::new(&mut self.height, 0..=design.mask_size)
-                .text("Height")
+            number_slider(&mut self.height, 0..=design.mask_size, design)
+                .text(tr("Height"))
                 .drag_value_speed(1.0),
         );
 
         ui.add(
-            egui::Slider::new(&mut self.colour, 0..=255)
-                .text("Bar Colour")
+            number_slider(&mut self.colour, 0..=255, design)
+                .text(tr("Bar Colour"))
                 .drag_value_speed(1.0),
         );
         if self.options.draw_target_line {
             ui.add(
-                egui::Slider::new(&mut self.target_line_colour, 0..=255)
-                    .text("Target Line Colour")
+                number_slider(&mut self.target_line_colour, 0..=255, design)
+                    .text(tr("Target Line Colour"))
                     .drag_value_speed(1.0),
             );
         }
 
-        ui.checkbox(&mut self.options.draw_border, "Draw Border");
-        ui.checkbox(&mut self.options.draw_target_line, "Draw Target Line");
+        ui.checkbox(&mut self.options.draw_border, tr("Draw Border"));
+        ui.checkbox(&mut self.options.draw_target_line, tr("Draw Target Line"));
 
         ui.horizontal(|ui| {
-            ui.label("Bar Graph Type:");
+            ui.label(tr("Bar Graph Type:"));
             ui.radio_value(
                 &mut self.options.bar_graph_type,
                 BarGraphType::Filled,
-                "Filled",
+                tr("Filled"),
             );
             ui.radio_value(
                 &mut self.options.bar_graph_type,
                 BarGraphType::NotFilled,
-                "Not Filled",
+                tr("Not Filled"),
             );
         });
 
         ui.horizontal(|ui| {
-            ui.label("Axis Orientation:");
+            ui.label(tr("Axis Orientation:"));
             ui.radio_value(
                 &mut self.options.axis_orientation,
                 AxisOrientation::Vertical,
-                "Vertical",
+                tr("Vertical"),
             );
             ui.radio_value(
                 &mut self.options.axis_orientation,
                 AxisOrientation::Horizontal,
-                "Horizontal",
+                tr("Horizontal"),
             );
         });
 
         ui.horizontal(|ui| {
-            ui.label("Grow Direction:");
+            ui.label(tr("Grow Direction:"));
             ui.radio_value(
                 &mut self.options.grow_direction,
                 GrowDirection::GrowLeftDown,
-                "Left/Down",
+                tr("Left/Down"),
             );
             ui.radio_value(
                 &mut self.options.grow_direction,
                 GrowDirection::GrowRightUp,
-                "Right/Up",
+                tr("Right/Up"),
             );
         });
 
         ui.horizontal(|ui| {
-            ui.label("Deflection Direction:");
+            ui.label(tr("Deflection Direction:"));
             ui.radio_value(
                 &mut self.options.deflection_direction,
                 DeflectionDirection::AntiClockwise,
-                "Anti-clockwise",
+                tr("Anti-clockwise"),
             );
             ui.radio_value(
                 &mut self.options.deflection_direction,
                 DeflectionDirection::Clockwise,
-                "Clockwise",
+                tr("Clockwise"),
             );
         });
 
+        render_iso_angle(ui, tr("Start Angle:"), &mut self.start_angle);
+        render_iso_angle(ui, tr("End Angle:"), &mut self.end_angle);
+        ui.weak(tr(
+            "Drag the angle handles in the preview to change the sweep",
+        ));
         ui.add(
-            egui::DragValue::new(&mut self.start_angle)
+            number_field(&mut self.bar_graph_width, design)
                 .speed(1.0)
-                .prefix("Start Angle: "),
+                .prefix(tr("Bar Graph Width: ")),
         );
         ui.add(
-            egui::DragValue::new(&mut self.end_angle)
+            number_field(&mut self.min_value, design)
                 .speed(1.0)
-                .prefix("End Angle: "),
+                .prefix(tr("Min Value: ")),
         );
         ui.add(
-            egui::DragValue::new(&mut self.bar_graph_width)
+            number_field(&mut self.max_value, design)
                 .speed(1.0)
-                .prefix("Bar Graph Width: "),
-        );
-        ui.add(
-            egui::DragValue::new(&mut self.min_value)
-                .speed(1.0)
-                .prefix("Min Value: "),
-        );
-        ui.add(
-            egui::DragValue::new(&mut self.max_value)
-                .speed(1.0)
-                .prefix("Max Value: "),
+                .prefix(tr("Max Value: ")),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Variable Reference:");
+            ui.label(tr("Variable Reference:"));
             egui::ComboBox::from_id_salt("variable_reference")
                 .selected_text(
                     self.variable_reference
@@ -2643,7 +3074,7 @@ impl ConfigurableObject for OutputArchedBarGraph {
                     ui.selectable_value(
                         &mut self.variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -2664,12 +3095,12 @@ impl ConfigurableObject for OutputArchedBarGraph {
 
         // If no variable reference, set initial value
         if self.variable_reference.0.is_none() {
-            ui.label("Initial Value:");
-            ui.add(egui::DragValue::new(&mut self.value).speed(1.0));
+            ui.label(tr("Initial Value:"));
+            ui.add(number_field(&mut self.value, design).speed(1.0));
         }
 
         ui.horizontal(|ui| {
-            ui.label("Target Value Variable Reference:");
+            ui.label(tr("Target Value Variable Reference:"));
             egui::ComboBox::from_id_salt("target_value_variable_reference")
                 .selected_text(
                     self.target_value_variable_reference
@@ -2680,7 +3111,7 @@ impl ConfigurableObject for OutputArchedBarGraph {
                     ui.selectable_value(
                         &mut self.target_value_variable_reference,
                         NullableObjectId::NULL,
-                        "None",
+                        tr("None"),
                     );
                     for potential_child in design
                         .get_pool()
@@ -2701,186 +3132,331 @@ impl ConfigurableObject for OutputArchedBarGraph {
 
         // If no target value variable reference, set target value
         if self.target_value_variable_reference.0.is_none() {
-            ui.label("Target Value:");
-            ui.add(egui::DragValue::new(&mut self.target_value).speed(1.0));
+            ui.label(tr("Target Value:"));
+            ui.add(number_field(&mut self.target_value, design).speed(1.0));
         }
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
     }
 }
 
+fn picture_format_label(format: PictureGraphicFormat) -> &'static str {
+    match format {
+        PictureGraphicFormat::Monochrome => tr("Monochrome"),
+        PictureGraphicFormat::FourBit => tr("4-bit colour"),
+        PictureGraphicFormat::EightBit => tr("8-bit colour"),
+    }
+}
+
+fn encoding_label(encoding: DataCodeType) -> &'static str {
+    match encoding {
+        DataCodeType::Raw => tr("Raw"),
+        DataCodeType::RunLength => tr("Run-length"),
+    }
+}
+
+/// Converted picture and the textures comparing it with the original
+#[derive(Clone)]
+struct ConversionPreview {
+    key: u64,
+    converted: PictureGraphic,
+    error: f32,
+    before: egui::TextureHandle,
+    after: egui::TextureHandle,
+}
+
+/// Pick a target format and encoding for a picture graphic, compare the result and apply it
+fn render_picture_conversion(
+    ui: &mut egui::Ui,
+    picture: &mut PictureGraphic,
+    design: &EditorProject,
+) {
+    if picture.actual_width == 0 || picture.actual_height == 0 {
+        ui.label(tr("No image loaded"));
+        return;
+    }
+    let target_id = egui::Id::new(("picture_conversion_target", picture.id.value()));
+    let (mut format, mut encoding) = ui.data(|data| {
+        data.get_temp(target_id)
+            .unwrap_or((picture.format, picture.options.data_code_type))
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr("Format:"));
+        for option in [
+            PictureGraphicFormat::Monochrome,
+            PictureGraphicFormat::FourBit,
+            PictureGraphicFormat::EightBit,
+        ] {
+            ui.radio_value(&mut format, option, picture_format_label(option));
+        }
+    });
+    ui.horizontal(|ui| {
+        ui.label(tr("Encoding:"));
+        for option in [DataCodeType::Raw, DataCodeType::RunLength] {
+            ui.radio_value(&mut encoding, option, encoding_label(option));
+        }
+    });
+    ui.data_mut(|data| data.insert_temp(target_id, (format, encoding)));
+
+    let mut hasher = DefaultHasher::new();
+    (&picture.data, picture.actual_width, picture.actual_height).hash(&mut hasher);
+    (picture.transparency_colour, picture.options.transparent).hash(&mut hasher);
+    for label in [
+        picture_format_label(format),
+        encoding_label(encoding),
+        picture_format_label(picture.format),
+        encoding_label(picture.options.data_code_type),
+    ] {
+        label.hash(&mut hasher);
+    }
+    let key = hasher.finish();
+
+    let preview_id = egui::Id::new(("picture_conversion_preview", picture.id.value()));
+    let preview = match ui.data(|data| data.get_temp::<ConversionPreview>(preview_id)) {
+        Some(preview) if preview.key == key => preview,
+        _ => {
+            let pool = design.get_pool();
+            let converted = convert_picture(picture, pool, format, encoding);
+            let texture = |name: &str, image| {
+                ui.ctx().load_texture(
+                    format!("picture_conversion_{}_{}", picture.id.value(), name),
+                    image,
+                    egui::TextureOptions::NEAREST,
+                )
+            };
+            let preview = ConversionPreview {
+                key,
+                error: conversion_error(pool, picture, &converted),
+                before: texture("before", picture_image(picture, pool)),
+                after: texture("after", picture_image(&converted, pool)),
+                converted,
+            };
+            ui.data_mut(|data| data.insert_temp(preview_id, preview.clone()));
+            preview
+        }
+    };
+
+    ui.label(tr_args(
+        "Data size: {} → {} bytes",
+        &[&picture.data.len(), &preview.converted.data.len()],
+    ));
+    ui.label(tr_args(
+        "Mean colour error: {}",
+        &[&format!("{:.1}", preview.error)],
+    ))
+    .on_hover_text(tr(
+        "Average RGB distance per pixel between the original and the conversion",
+    ));
+    let scale = 128.0 / picture.actual_width.max(picture.actual_height).max(1) as f32;
+    let size = egui::vec2(picture.actual_width as f32, picture.actual_height as f32) * scale;
+    ui.horizontal(|ui| {
+        ui.vertical(|ui| {
+            ui.label(tr("Original"));
+            ui.image((preview.before.id(), size));
+        });
+        ui.vertical(|ui| {
+            ui.label(tr("Converted"));
+            ui.image((preview.after.id(), size));
+        });
+    });
+
+    let unchanged = format == picture.format && encoding == picture.options.data_code_type;
+    if ui
+        .add_enabled(!unchanged, egui::Button::new(tr("Apply conversion")))
+        .clicked()
+    {
+        *picture = preview.converted;
+        design.describe_change(
+            format!(
+                "Convert picture graphic {} to {}, {}",
+                picture.id.value(),
+                picture_format_label(format),
+                encoding_label(encoding)
+            ),
+            ChangeCategory::Edit,
+        );
+    }
+}
+
+/// Size of the squares of the checkerboard shown behind transparent pixels
+const CHECKERBOARD_SQUARE: f32 = 8.0;
+
+fn paint_checkerboard(painter: &egui::Painter, rect: egui::Rect) {
+    painter.rect_filled(rect, 0.0, egui::Color32::from_gray(204));
+    let columns = (rect.width() / CHECKERBOARD_SQUARE).ceil() as usize;
+    let rows = (rect.height() / CHECKERBOARD_SQUARE).ceil() as usize;
+    for row in 0..rows {
+        for column in (row % 2..columns).step_by(2) {
+            let min = rect.min + egui::vec2(column as f32, row as f32) * CHECKERBOARD_SQUARE;
+            let square = egui::Rect::from_min_size(min, egui::Vec2::splat(CHECKERBOARD_SQUARE));
+            painter.rect_filled(square.intersect(rect), 0.0, egui::Color32::from_gray(153));
+        }
+    }
+}
+
+/// Show a picture graphic over a checkerboard, with an eyedropper to pick its transparency
+/// colour from the image
+fn render_transparency_assistant(
+    ui: &mut egui::Ui,
+    picture: &mut PictureGraphic,
+    design: &EditorProject,
+) {
+    if picture.actual_width == 0 || picture.actual_height == 0 {
+        return;
+    }
+    let pool = design.get_pool();
+
+    let mut hasher = DefaultHasher::new();
+    (&picture.data, picture.actual_width, picture.actual_height).hash(&mut hasher);
+    (picture.transparency_colour, picture.options.transparent).hash(&mut hasher);
+    picture_format_label(picture.format).hash(&mut hasher);
+    let key = hasher.finish();
+    let texture_id = egui::Id::new(("picture_transparency_texture", picture.id.value()));
+    let texture = match ui.data(|data| data.get_temp::<(u64, egui::TextureHandle)>(texture_id)) {
+        Some((cached, texture)) if cached == key => texture,
+        _ => {
+            let texture = ui.ctx().load_texture(
+                format!("picture_transparency_{}", picture.id.value()),
+                picture_image(picture, pool),
+                egui::TextureOptions::NEAREST,
+            );
+            ui.data_mut(|data| data.insert_temp(texture_id, (key, texture.clone())));
+            texture
+        }
+    };
+
+    let eyedropper_id = egui::Id::new(("picture_eyedropper", picture.id.value()));
+    let mut eyedropper = ui.data(|data| data.get_temp(eyedropper_id).unwrap_or(false));
+    ui.horizontal(|ui| {
+        ui.toggle_value(&mut eyedropper, tr("Pick transparency colour"))
+            .on_hover_text(tr(
+                "Click a pixel of the image to make its colour transparent",
+            ));
+        if picture.options.transparent {
+            let colour = pool.color_by_index(picture.transparency_colour);
+            let (rect, _) = ui.allocate_exact_size(egui::Vec2::splat(16.0), egui::Sense::hover());
+            ui.painter().rect_filled(
+                rect,
+                2.0,
+                egui::Color32::from_rgb(colour.r, colour.g, colour.b),
+            );
+            ui.label(tr_args("Index {}", &[&picture.transparency_colour]));
+        }
+    });
+
+    let scale = (256.0 / picture.actual_width.max(picture.actual_height) as f32).min(4.0);
+    let size = egui::vec2(picture.actual_width as f32, picture.actual_height as f32) * scale;
+    let sense = if eyedropper {
+        egui::Sense::click()
+    } else {
+        egui::Sense::hover()
+    };
+    let (rect, response) = ui.allocate_exact_size(size, sense);
+    let painter = ui.painter_at(rect);
+    paint_checkerboard(&painter, rect);
+    painter.image(
+        texture.id(),
+        rect,
+        egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0)),
+        egui::Color32::WHITE,
+    );
+
+    if !eyedropper {
+        ui.data_mut(|data| data.insert_temp(eyedropper_id, eyedropper));
+        return;
+    }
+    let pixel_index = |pos: egui::Pos2| {
+        let pixel = ((pos - rect.min) / scale).floor();
+        let x = (pixel.x as usize).min(picture.actual_width as usize - 1);
+        let y = (pixel.y as usize).min(picture.actual_height as usize - 1);
+        picture_pixels(picture)
+            .get(y * picture.actual_width as usize + x)
+            .copied()
+    };
+    let response = response.on_hover_cursor(egui::CursorIcon::Crosshair);
+    if let Some(index) = response.hover_pos().and_then(pixel_index) {
+        response
+            .clone()
+            .on_hover_text(tr_args("Index {}", &[&index]));
+    }
+    if let Some(index) = response
+        .interact_pointer_pos()
+        .filter(|_| response.clicked())
+        .and_then(pixel_index)
+    {
+        picture.transparency_colour = index;
+        picture.options.transparent = true;
+        eyedropper = false;
+        design.describe_change(
+            format!(
+                "Set transparency colour of picture graphic {} to {}",
+                picture.id.value(),
+                index
+            ),
+            ChangeCategory::Edit,
+        );
+    }
+    ui.data_mut(|data| data.insert_temp(eyedropper_id, eyedropper));
+}
+
 impl ConfigurableObject for PictureGraphic {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.add(
-            egui::Slider::new(&mut self.width, 0..=design.mask_size)
-                .text("Width")
+            number_slider(&mut self.width, 0..=design.mask_size, design)
+                .text(tr("Width"))
                 .drag_value_speed(1.0),
         );
-        ui.label(format!("Actual Image Width: {}", self.actual_width));
-        ui.label(format!("Actual Image Height: {}", self.actual_height));
-        ui.label(format!("Data Size (bytes): {}", self.data.len()));
-        ui.horizontal(|ui| {
-            ui.label("Format:");
-            if ui
-                .radio(
-                    self.format == PictureGraphicFormat::Monochrome,
-                    "Monochrome",
-                )
-                .clicked()
-            {
-                match self.format {
-                    PictureGraphicFormat::FourBit => {
-                        self.data = self
-                            .data_as_raw_encoded()
-                            .windows(4)
-                            .step_by(4)
-                            .flat_map(|chunk| {
-                                let mut byte = 0;
-                                for (i, bit) in chunk.iter().enumerate() {
-                                    for j in 0..2 {
-                                        if *bit & (1 << j) != 0 {
-                                            byte |= 1 << (i * 2 + j);
-                                        }
-                                    }
-                                }
-                                vec![byte]
-                            })
-                            .collect();
-                    }
-                    PictureGraphicFormat::EightBit => {
-                        self.data = self
-                            .data_as_raw_encoded()
-                            .windows(8)
-                            .step_by(8)
-                            .flat_map(|chunk| {
-                                let mut byte = 0;
-                                for (i, bit) in chunk.iter().enumerate() {
-                                    if *bit != 0 {
-                                        byte |= 1 << i;
-                                    }
-                                }
-                                vec![byte]
-                            })
-                            .collect();
-                    }
-                    _ => {}
-                }
-                self.format = PictureGraphicFormat::Monochrome;
-                self.options.data_code_type = DataCodeType::Raw;
-            }
-            if ui
-                .radio(self.format == PictureGraphicFormat::FourBit, "4-bit colour")
-                .clicked()
-            {
-                match self.format {
-                    PictureGraphicFormat::Monochrome => {
-                        self.data = self
-                            .data_as_raw_encoded()
-                            .iter()
-                            .flat_map(|value| {
-                                let mut result = vec![];
-                                for idx in 0..8 {
-                                    let bit_color = value << idx & 0x01;
-                                    if idx % 2 == 0 {
-                                        result.push(bit_color);
-                                    } else if let Some(last) = result.last_mut() {
-                                        *last |= bit_color >> 4;
-                                    }
-                                }
-                                result
-                            })
-                            .collect();
-                    }
-                    PictureGraphicFormat::EightBit => {
-                        self.data = self
-                            .data_as_raw_encoded()
-                            .windows(2)
-                            .step_by(2)
-                            .flat_map(|values| {
-                                let high = (values[0] & 0x0F) << 4;
-                                let low = values[1] & 0x0F;
-                                vec![high | low]
-                            })
-                            .collect();
-                    }
-                    _ => {}
-                }
-                self.format = PictureGraphicFormat::FourBit;
-                self.options.data_code_type = DataCodeType::Raw;
-            }
-            if ui
-                .radio(
-                    self.format == PictureGraphicFormat::EightBit,
-                    "8-bit colour",
-                )
-                .clicked()
-            {
-                match self.format {
-                    PictureGraphicFormat::Monochrome => {
-                        self.data = self
-                            .data_as_raw_encoded()
-                            .iter()
-                            .flat_map(|value| {
-                                let mut result = vec![];
-                                for bit in 0..8 {
-                                    result.push(value >> bit & 0x01);
-                                }
-                                result
-                            })
-                            .collect();
-                    }
-                    PictureGraphicFormat::FourBit => {
-                        self.data = self
-                            .data_as_raw_encoded()
-                            .iter()
-                            .flat_map(|value| {
-                                let high = (value >> 4) & 0x0F;
-                                let low = value & 0x0F;
-                                vec![high, low]
-                            })
-                            .collect();
-                    }
-                    _ => {}
-                }
-                self.format = PictureGraphicFormat::EightBit;
-                self.options.data_code_type = DataCodeType::Raw;
-            }
-        });
+        ui.label(tr_args("Actual Image Width: {}", &[&self.actual_width]));
+        ui.label(tr_args("Actual Image Height: {}", &[&self.actual_height]));
+        ui.label(tr_args("Data Size (bytes): {}", &[&self.data.len()]));
+        ui.label(tr_args(
+            "Format: {}, {}",
+            &[
+                &picture_format_label(self.format),
+                &encoding_label(self.options.data_code_type),
+            ],
+        ));
+        egui::CollapsingHeader::new(tr("Convert format"))
+            .id_salt(("picture_conversion", self.id.value()))
+            .show(ui, |ui| render_picture_conversion(ui, self, design));
         ui.horizontal(|ui| {
-            ui.checkbox(&mut self.options.transparent, "Transparent Pixels");
+            ui.checkbox(&mut self.options.transparent, tr("Transparent Pixels"));
             if self.options.transparent {
                 ui.add(
-                    egui::Slider::new(&mut self.transparency_colour, 0..=255)
-                        .text("Transparent Colour")
+                    number_slider(&mut self.transparency_colour, 0..=255, design)
+                        .text(tr("Transparent Colour"))
                         .drag_value_speed(1.0),
                 );
             }
         });
-        ui.checkbox(&mut self.options.flashing, "Flashing");
+        ui.checkbox(&mut self.options.flashing, tr("Flashing"));
 
         ui.separator();
-        ui.label("Image:");
+        ui.label(tr("Image:"));
         if ui
-            .button("Load Image")
-            .on_hover_text("Load an image file (PNG, JPG, BMP, etc.)")
+            .button(tr("Load Image"))
+            .on_hover_text(tr("Load an image file (PNG, JPG, BMP, etc.)"))
             .clicked()
         {
             design.request_image_load(self.id);
         }
+        render_transparency_assistant(ui, self, design);
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -2892,8 +3468,8 @@ impl ConfigurableObject for NumberVariable {
         render_object_id(ui, &mut self.id, design);
 
         ui.horizontal(|ui| {
-            ui.label("Initial Value:");
-            ui.add(egui::DragValue::new(&mut self.value).speed(1.0));
+            ui.label(tr("Initial Value:"));
+            ui.add(number_field(&mut self.value, design).speed(1.0));
         });
     }
 }
@@ -2903,8 +3479,8 @@ impl ConfigurableObject for StringVariable {
         render_object_id(ui, &mut self.id, design);
 
         ui.horizontal(|ui| {
-            ui.label("Initial Value:");
-            ui.text_edit_singleline(&mut self.value);
+            ui.label(tr("Initial Value:"));
+            spell_checked_text_edit(ui, &mut self.value);
         });
     }
 }
@@ -2914,8 +3490,8 @@ impl ConfigurableObject for FontAttributes {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.font_colour, 0..=255)
-                .text("Font Colour")
+            number_slider(&mut self.font_colour, 0..=255, design)
+                .text(tr("Font Colour"))
                 .drag_value_speed(1.0),
         );
 
@@ -2930,8 +3506,8 @@ impl ConfigurableObject for FontAttributes {
                 FontSize::NonProportional(_) => 8, // default to minimal proportional height if needed
             };
             ui.horizontal(|ui| {
-                ui.label("Proportional Font Height (≥ 8):");
-                if ui.add(egui::DragValue::new(&mut height)).changed() {
+                ui.label(tr("Proportional Font Height (≥ 8):"));
+                if ui.add(number_field(&mut height, design)).changed() {
                     self.font_size = FontSize::Proportional(height);
                 }
             });
@@ -2942,7 +3518,7 @@ impl ConfigurableObject for FontAttributes {
                 FontSize::Proportional(_) => NonProportionalFontSize::Px6x8,
             };
 
-            egui::ComboBox::from_label("Non-Proportional Font Size")
+            egui::ComboBox::from_label(tr("Non-Proportional Font Size"))
                 .selected_text(format!("{:?}", current_size))
                 .show_ui(ui, |ui| {
                     for value in [
@@ -2977,7 +3553,7 @@ impl ConfigurableObject for FontAttributes {
         } else {
             false
         };
-        ui.checkbox(&mut is_proprietary, "Proprietary Font");
+        ui.checkbox(&mut is_proprietary, tr("Proprietary Font"));
 
         if is_proprietary {
             const PROPRIETARY_RANGE_V3_AND_PRIOR: std::ops::RangeInclusive<u8> = 255..=255;
@@ -2990,8 +3566,8 @@ impl ConfigurableObject for FontAttributes {
                 _ => range.clone().last().unwrap(),
             };
             ui.horizontal(|ui| {
-                ui.label("Proprietary Font Value:");
-                ui.add(egui::DragValue::new(&mut raw_value).range(range).speed(1.0));
+                ui.label(tr("Proprietary Font Value:"));
+                ui.add(number_field(&mut raw_value, design).range(range).speed(1.0));
             });
             self.font_type = FontType::Proprietary(raw_value);
         } else {
@@ -3004,7 +3580,7 @@ impl ConfigurableObject for FontAttributes {
             }
 
             ui.horizontal(|ui| {
-                ui.label("Font Type:");
+                ui.label(tr("Font Type:"));
                 egui::ComboBox::from_id_salt("font_type")
                     .selected_text(format!("{:?}", self.font_type))
                     .show_ui(ui, |ui| {
@@ -3030,21 +3606,25 @@ impl ConfigurableObject for FontAttributes {
         }
 
         ui.separator();
-        ui.label("Font Style:");
-        ui.checkbox(&mut self.font_style.bold, "Bold");
-        ui.checkbox(&mut self.font_style.crossed_out, "Crossed Out");
-        ui.checkbox(&mut self.font_style.underlined, "Underlined");
-        ui.checkbox(&mut self.font_style.italic, "Italic");
-        ui.checkbox(&mut self.font_style.inverted, "Inverted");
-        ui.checkbox(&mut self.font_style.flashing_inverted, "Flashing Inverted");
-        ui.checkbox(&mut self.font_style.flashing_hidden, "Flashing Hidden");
-        // ui.checkbox(&mut self.font_style.proportional, "Proportional"); // TODO: check if we have VT version 4 or later
+        ui.label(tr("Font Style:"));
+        ui.checkbox(&mut self.font_style.bold, tr("Bold"));
+        ui.checkbox(&mut self.font_style.crossed_out, tr("Crossed Out"));
+        ui.checkbox(&mut self.font_style.underlined, tr("Underlined"));
+        ui.checkbox(&mut self.font_style.italic, tr("Italic"));
+        ui.checkbox(&mut self.font_style.inverted, tr("Inverted"));
+        ui.checkbox(
+            &mut self.font_style.flashing_inverted,
+            tr("Flashing Inverted"),
+        );
+        ui.checkbox(&mut self.font_style.flashing_hidden, tr("Flashing Hidden"));
+        // ui.checkbox(&mut self.font_style.proportional, tr("Proportional")); // TODO: check if we have VT version 4 or later
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -3056,20 +3636,20 @@ impl ConfigurableObject for LineAttributes {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.line_colour, 0..=255)
-                .text("Line Colour")
+            number_slider(&mut self.line_colour, 0..=255, design)
+                .text(tr("Line Colour"))
                 .drag_value_speed(1.0),
         );
 
         ui.add(
-            egui::Slider::new(&mut self.line_width, 0..=255)
-                .text("Line Width")
+            number_slider(&mut self.line_width, 0..=255, design)
+                .text(tr("Line Width"))
                 .drag_value_speed(1.0),
         );
 
-        ui.label("Line Art Pattern (16 bits):")
-            .on_hover_text("Each bit in this 16-bit pattern represents a 'paintbrush spot' along the line. ")
-            .on_hover_text("A '1' bit means that spot is drawn in the line color, while a '0' bit means that spot is skipped (shows background).");
+        ui.label(tr("Line Art Pattern (16 bits):"))
+            .on_hover_text(tr("Each bit in this 16-bit pattern represents a 'paintbrush spot' along the line. "))
+            .on_hover_text(tr("A '1' bit means that spot is drawn in the line color, while a '0' bit means that spot is skipped (shows background)."));
 
         ui.horizontal(|ui| {
             for i in (0..16).rev() {
@@ -3083,36 +3663,416 @@ impl ConfigurableObject for LineAttributes {
                         self.line_art &= !bit_mask;
                     }
                 }
-                check.on_hover_text(format!(
-                    "Bit {}: {} ({}). Click to toggle.\n1 = Draw line colour\n0 = Skip (background)",
-                    i,
-                    if bit_is_set { "Currently: Draw" } else { "Currently: Skip" },
-                    if bit_is_set { "One (1)" } else { "Zero (0)" }
-                ));
+                check.on_hover_text(tr_args("Bit {}: {} ({}). Click to toggle.\n1 = Draw line colour\n0 = Skip (background)", &[&i, &(if bit_is_set { "Currently: Draw" } else { "Currently: Skip" }), &(if bit_is_set { "One (1)" } else { "Zero (0)" })]));
             }
         });
 
         ui.horizontal(|ui| {
-            ui.label("Current Binary Pattern:");
+            ui.label(tr("Current Binary Pattern:"));
             ui.label(format!("{:016b}", self.line_art))
-                .on_hover_text("This shows the full 16-bit pattern of the line art. '1' bits represent drawn spots; '0' bits represent skipped spots.");
+                .on_hover_text(tr("This shows the full 16-bit pattern of the line art. '1' bits represent drawn spots; '0' bits represent skipped spots."));
         });
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
     }
 }
 
+/// Angle in degrees, anti-clockwise from the 3 o'clock position, with the encoded value
+fn render_iso_angle(ui: &mut egui::Ui, label: &str, value: &mut u8) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let mut degrees = iso_angle_degrees(*value);
+        if ui
+            .add(
+                egui::DragValue::new(&mut degrees)
+                    .speed(2.0)
+                    .range(0..=358)
+                    .suffix("\u{b0}"),
+            )
+            .changed()
+        {
+            *value = iso_angle_value(degrees as f32);
+        }
+        ui.weak(tr_args("(value {})", &[&value]));
+    });
+}
+
+/// Largest size of the polygon point editor
+const POLYGON_EDITOR_SIZE: f32 = 300.0;
+
+/// Radius of the vertex handles in the polygon point editor
+const POLYGON_HANDLE_RADIUS: f32 = 5.0;
+
+/// Polygon area with draggable vertices. Double click to insert a point on the nearest edge,
+/// right click a point to delete it.
+fn render_polygon_point_editor(ui: &mut egui::Ui, polygon: &mut OutputPolygon) {
+    for problem in polygon_problems(polygon) {
+        ui.colored_label(egui::Color32::YELLOW, problem);
+    }
+    if polygon.polygon_type == POLYGON_TYPE_CONVEX
+        && !is_convex(&polygon.points)
+        && ui.button(tr("Change to Non-Convex")).clicked()
+    {
+        polygon.polygon_type = 1;
+    }
+
+    let (width, height) = (polygon.width.max(1) as f32, polygon.height.max(1) as f32);
+    let scale = (ui.available_width().min(POLYGON_EDITOR_SIZE) / width)
+        .min(POLYGON_EDITOR_SIZE / height)
+        .min(8.0);
+    let margin = POLYGON_HANDLE_RADIUS * 2.0;
+    let (canvas, response) = ui.allocate_exact_size(
+        egui::vec2(width, height) * scale + egui::Vec2::splat(margin * 2.0),
+        egui::Sense::click(),
+    );
+    let area = egui::Rect::from_min_size(
+        canvas.min + egui::Vec2::splat(margin),
+        egui::vec2(width, height) * scale,
+    );
+    let to_screen = |p: &Point<u16>| area.min + egui::vec2(p.x as f32, p.y as f32) * scale;
+    let to_point = |pos: egui::Pos2| {
+        let offset = (pos - area.min) / scale;
+        Point {
+            x: offset.x.round().clamp(0.0, polygon.width as f32) as u16,
+            y: offset.y.round().clamp(0.0, polygon.height as f32) as u16,
+        }
+    };
+
+    let painter = ui.painter_at(canvas);
+    painter.rect_filled(canvas, 2.0, ui.visuals().extreme_bg_color);
+    painter.rect_stroke(
+        area,
+        0.0,
+        egui::Stroke::new(1.0, egui::Color32::from_gray(128)),
+        egui::StrokeKind::Outside,
+    );
+    let closed = polygon.polygon_type != POLYGON_TYPE_OPEN;
+    let line_colour = if polygon.polygon_type == POLYGON_TYPE_CONVEX && !is_convex(&polygon.points)
+    {
+        egui::Color32::YELLOW
+    } else {
+        ui.visuals().strong_text_color()
+    };
+    let screen_points: Vec<egui::Pos2> = polygon.points.iter().map(to_screen).collect();
+    let line = egui::Stroke::new(1.5, line_colour);
+    if closed && screen_points.len() > 2 {
+        painter.add(egui::Shape::closed_line(screen_points.clone(), line));
+    } else {
+        painter.add(egui::Shape::line(screen_points.clone(), line));
+    }
+
+    let mut remove = None;
+    let mut active = None;
+    for (index, centre) in screen_points.iter().enumerate() {
+        let handle = ui
+            .interact(
+                egui::Rect::from_center_size(
+                    *centre,
+                    egui::Vec2::splat(POLYGON_HANDLE_RADIUS * 2.0),
+                ),
+                ui.id().with(("polygon_vertex", index)),
+                egui::Sense::click_and_drag(),
+            )
+            .on_hover_cursor(egui::CursorIcon::Grab);
+        if handle.dragged() {
+            if let Some(pos) = handle.interact_pointer_pos() {
+                polygon.points[index] = to_point(pos);
+            }
+        }
+        if handle.secondary_clicked() && polygon.points.len() > 3 {
+            remove = Some(index);
+        }
+        if handle.dragged() || handle.hovered() {
+            active = Some(index);
+        }
+        let fill = if active == Some(index) {
+            ui.visuals().selection.bg_fill
+        } else {
+            ui.visuals().widgets.inactive.bg_fill
+        };
+        painter.circle(
+            *centre,
+            POLYGON_HANDLE_RADIUS,
+            fill,
+            egui::Stroke::new(1.0, line_colour),
+        );
+    }
+    if let Some(index) = remove {
+        polygon.points.remove(index);
+    } else if let Some(index) = active {
+        let point = polygon.points[index];
+        painter.text(
+            area.left_top(),
+            egui::Align2::LEFT_TOP,
+            tr_args("Point {}: ({}, {})", &[&index, &point.x, &point.y]),
+            egui::FontId::monospace(11.0),
+            ui.visuals().text_color(),
+        );
+    } else if response.double_clicked() {
+        if let Some(pos) = response.interact_pointer_pos() {
+            let offset = (pos - area.min) / scale;
+            let point = to_point(pos);
+            match nearest_edge(&polygon.points, closed, offset.x, offset.y) {
+                Some(edge) => polygon.points.insert(edge + 1, point),
+                None => polygon.points.push(point),
+            }
+        }
+    }
+    ui.weak(tr(
+        "Drag points to move them, double click to insert, right click to delete",
+    ));
+}
+
+/// Sizes of the patterns the pattern editor creates. Pattern rows fill whole bytes in every
+/// picture format, so terminals can tile them without shifting bits.
+const PATTERN_SIZES: [u16; 3] = [8, 16, 32];
+
+/// Size of a pattern pixel in the pattern editor
+const PATTERN_CELL: f32 = 10.0;
+
+/// Pixels of a pattern while the user is painting them with a drag
+#[derive(Clone)]
+struct PatternStroke {
+    pixels: Vec<u8>,
+    colour: u8,
+}
+
+/// The two colours of a pattern: the most used one as background and the next one as foreground
+fn pattern_colours(picture: &PictureGraphic, pixels: &[u8]) -> (u8, u8) {
+    let mut counts = [0usize; 256];
+    for index in pixels {
+        counts[*index as usize] += 1;
+    }
+    let mut used: Vec<u8> = (0..=255).filter(|i| counts[*i as usize] > 0).collect();
+    used.sort_by_key(|i| std::cmp::Reverse(counts[*i as usize]));
+    let background = used.first().copied().unwrap_or(1);
+    let foreground = used.get(1).copied().unwrap_or(match picture.format {
+        PictureGraphicFormat::Monochrome => 1 - background.min(1),
+        _ if background == 0 => 1,
+        _ => 0,
+    });
+    (background, foreground)
+}
+
+/// Two-colour editor for the picture graphic used as fill pattern, with the pattern tiled in a
+/// sample rectangle. The picture is not the object being edited, so changes are requested and
+/// applied at the start of the next frame.
+fn render_fill_pattern_editor(ui: &mut egui::Ui, design: &EditorProject, pattern_id: ObjectId) {
+    let pool = design.get_pool();
+    let Some(Object::PictureGraphic(picture)) = pool.object_by_id(pattern_id) else {
+        return;
+    };
+    let width = picture.actual_width as usize;
+    let height = picture.actual_height as usize;
+    let stroke_id = egui::Id::new(("fill_pattern_stroke", pattern_id.value()));
+    let stroke = ui.data(|data| data.get_temp::<PatternStroke>(stroke_id));
+    let pixels = stroke
+        .as_ref()
+        .map_or_else(|| picture_pixels(picture), |s| s.pixels.clone());
+    let (background, foreground) = pattern_colours(picture, &pixels);
+
+    let ctx = ui.ctx().clone();
+    let update = |pixels: &[u8], width: u16, height: u16, description: &str| {
+        let mut picture = picture.clone();
+        set_picture_pixels(&mut picture, width, height, pixels);
+        design.request_object_update(Object::PictureGraphic(picture));
+        design.describe_change(description, ChangeCategory::Edit);
+        ctx.request_repaint();
+    };
+
+    egui::CollapsingHeader::new(tr("Pattern editor"))
+        .default_open(true)
+        .show(ui, |ui| {
+            if picture.width != picture.actual_width {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    tr("Fill patterns are not scaled, the width should be the actual width"),
+                );
+            }
+            if !width.is_multiple_of(8) || !PATTERN_SIZES.contains(&picture.actual_width) {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    tr("Pattern widths that are a multiple of 8 tile the same on every terminal"),
+                );
+            }
+            let distinct: HashSet<u8> = pixels.iter().copied().collect();
+            if distinct.len() > 2 {
+                ui.colored_label(
+                    egui::Color32::YELLOW,
+                    tr_args(
+                        "The pattern has {} colours, painting keeps them but only two can be \
+                         picked",
+                        &[&distinct.len()],
+                    ),
+                );
+            }
+
+            ui.horizontal(|ui| {
+                ui.label(tr("Size:"));
+                for size in PATTERN_SIZES {
+                    let current = width == size as usize && height == size as usize;
+                    if ui
+                        .selectable_label(current, format!("{0}\u{d7}{0}", size))
+                        .clicked()
+                        && !current
+                    {
+                        let resized: Vec<u8> = (0..size as usize * size as usize)
+                            .map(|i| {
+                                let (x, y) = (i % size as usize, i / size as usize);
+                                if x < width && y < height {
+                                    pixels[y * width + x]
+                                } else {
+                                    background
+                                }
+                            })
+                            .collect();
+                        update(&resized, size, size, "Resize fill pattern");
+                    }
+                }
+            });
+
+            // Monochrome patterns are black and white, other formats can recolour the pattern
+            if picture.format != PictureGraphicFormat::Monochrome {
+                ui.horizontal(|ui| {
+                    for (label, colour) in [
+                        (tr("Background:"), background),
+                        (tr("Foreground:"), foreground),
+                    ] {
+                        ui.label(label);
+                        let mut new = colour;
+                        ui.add(number_field(&mut new, design).range(
+                            0..=match picture.format {
+                                PictureGraphicFormat::FourBit => 15,
+                                _ => 255,
+                            },
+                        ));
+                        let (rect, _) =
+                            ui.allocate_exact_size(egui::Vec2::splat(14.0), egui::Sense::hover());
+                        ui.painter()
+                            .rect_filled(rect, 2.0, pool.color_by_index(new).convert());
+                        if new != colour {
+                            let recoloured: Vec<u8> = pixels
+                                .iter()
+                                .map(|p| if *p == colour { new } else { *p })
+                                .collect();
+                            update(
+                                &recoloured,
+                                width as u16,
+                                height as u16,
+                                "Recolour fill pattern",
+                            );
+                        }
+                    }
+                });
+            }
+
+            if width == 0 || height == 0 {
+                return;
+            }
+            ui.weak(tr(
+                "Click or drag to paint, the first pixel decides the colour",
+            ));
+            let size = egui::vec2(width as f32, height as f32) * PATTERN_CELL;
+            let (rect, response) = ui.allocate_exact_size(size, egui::Sense::click_and_drag());
+            let painter = ui.painter_at(rect);
+            for (i, index) in pixels.iter().enumerate() {
+                let min =
+                    rect.min + egui::vec2((i % width) as f32, (i / width) as f32) * PATTERN_CELL;
+                let cell = egui::Rect::from_min_size(min, egui::Vec2::splat(PATTERN_CELL));
+                painter.rect_filled(cell, 0.0, pool.color_by_index(*index).convert());
+                painter.rect_stroke(
+                    cell,
+                    0.0,
+                    egui::Stroke::new(0.5, egui::Color32::from_gray(128)),
+                    egui::StrokeKind::Inside,
+                );
+            }
+
+            let cell_at = |pos: egui::Pos2| {
+                let offset = (pos - rect.min) / PATTERN_CELL;
+                let (x, y) = (offset.x.floor(), offset.y.floor());
+                (x >= 0.0 && y >= 0.0 && (x as usize) < width && (y as usize) < height)
+                    .then(|| y as usize * width + x as usize)
+            };
+            let pointer = response.interact_pointer_pos().and_then(cell_at);
+            if response.drag_started() || response.clicked() {
+                if let Some(cell) = pointer {
+                    let colour = if pixels[cell] == foreground {
+                        background
+                    } else {
+                        foreground
+                    };
+                    let mut stroke = PatternStroke {
+                        pixels: pixels.clone(),
+                        colour,
+                    };
+                    stroke.pixels[cell] = colour;
+                    if response.clicked() {
+                        update(
+                            &stroke.pixels,
+                            width as u16,
+                            height as u16,
+                            "Paint fill pattern",
+                        );
+                    } else {
+                        ui.data_mut(|data| data.insert_temp(stroke_id, stroke));
+                    }
+                }
+            } else if let Some(mut stroke) = stroke {
+                if let Some(cell) = pointer {
+                    stroke.pixels[cell] = stroke.colour;
+                }
+                if response.drag_stopped() {
+                    ui.data_mut(|data| data.remove::<PatternStroke>(stroke_id));
+                    update(
+                        &stroke.pixels,
+                        width as u16,
+                        height as u16,
+                        "Paint fill pattern",
+                    );
+                } else {
+                    ui.data_mut(|data| data.insert_temp(stroke_id, stroke));
+                }
+            }
+
+            ui.label(tr("Preview:"));
+            let (sample, _) = ui.allocate_exact_size(egui::vec2(192.0, 96.0), egui::Sense::hover());
+            let painter = ui.painter_at(sample);
+            painter.rect_filled(sample, 0.0, pool.color_by_index(background).convert());
+            for y in 0..sample.height() as usize {
+                for x in 0..sample.width() as usize {
+                    let index = pixels[(y % height) * width + x % width];
+                    if index == background {
+                        continue;
+                    }
+                    let pixel = egui::Rect::from_min_size(
+                        sample.min + egui::vec2(x as f32, y as f32),
+                        egui::Vec2::splat(1.0),
+                    );
+                    painter.rect_filled(pixel, 0.0, pool.color_by_index(index).convert());
+                }
+            }
+            painter.rect_stroke(
+                sample,
+                0.0,
+                egui::Stroke::new(1.0, ui.visuals().text_color()),
+                egui::StrokeKind::Inside,
+            );
+        });
+}
+
 impl ConfigurableObject for FillAttributes {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
-        ui.label("Fill Type:").on_hover_text(
+        ui.label(tr("Fill Type:")).on_hover_text(
             "Select how this area should be filled:\n\
                             0 = No fill\n\
                             1 = Fill with line colour\n\
@@ -3121,26 +4081,27 @@ impl ConfigurableObject for FillAttributes {
         );
 
         ui.horizontal(|ui| {
-            ui.radio_value(&mut self.fill_type, 0, "No fill")
-                .on_hover_text("No fill will be drawn, the background will be visible.");
-            ui.radio_value(&mut self.fill_type, 1, "Fill with line colour")
-                .on_hover_text("The area will be filled using the currently set line colour of the parent shape.");
-            ui.radio_value(&mut self.fill_type, 2, "Fill with specified colour")
-                .on_hover_text("The area will be filled using the 'fill_colour' attribute specified below.");
-            ui.radio_value(&mut self.fill_type, 3, "Fill with pattern")
-                .on_hover_text("The area will be filled using a pattern defined by a PictureGraphic object referenced below.");
+            ui.radio_value(&mut self.fill_type, 0, tr("No fill"))
+                .on_hover_text(tr("No fill will be drawn, the background will be visible."));
+            ui.radio_value(&mut self.fill_type, 1, tr("Fill with line colour"))
+                .on_hover_text(tr("The area will be filled using the currently set line colour of the parent shape."));
+            ui.radio_value(&mut self.fill_type, 2, tr("Fill with specified colour"))
+                .on_hover_text(tr("The area will be filled using the 'fill_colour' attribute specified below."));
+            ui.radio_value(&mut self.fill_type, 3, tr("Fill with pattern"))
+                .on_hover_text(tr("The area will be filled using a pattern defined by a PictureGraphic object referenced below."));
         });
 
         if self.fill_type == 2 {
-            ui.label("Fill Colour:")
-                .on_hover_text("Select the colour index (0-255) to use for filling the area.");
+            ui.label(tr("Fill Colour:")).on_hover_text(tr(
+                "Select the colour index (0-255) to use for filling the area.",
+            ));
             ui.add(
-                egui::Slider::new(&mut self.fill_colour, 0..=255)
-                    .text("Fill Colour")
+                number_slider(&mut self.fill_colour, 0..=255, design)
+                    .text(tr("Fill Colour"))
                     .drag_value_speed(1.0),
             );
         } else if self.fill_type == 3 {
-            ui.label("Fill Pattern (PictureGraphic Object):")
+            ui.label(tr("Fill Pattern (PictureGraphic Object):"))
                 .on_hover_text("Select a PictureGraphic object to use as a pattern.\n\
                                 Make sure the PictureGraphic width and format match the restrictions.");
             // Render a nullable object selector restricted to PictureGraphic objects
@@ -3156,25 +4117,29 @@ impl ConfigurableObject for FillAttributes {
 
                 if let Some(pattern_id) = self.fill_pattern.0 {
                     if let Some(obj) = design.get_pool().object_by_id(pattern_id) {
-                        if ui.link("(view)").clicked() {
+                        if ui.link(tr("(view)")).clicked() {
                             *design.get_mut_selected().borrow_mut() = pattern_id.into();
                         }
                     } else {
-                        ui.colored_label(egui::Color32::RED, "Missing pattern object");
+                        ui.colored_label(egui::Color32::RED, tr("Missing pattern object"));
                     }
                 } else {
-                    ui.label("None");
+                    ui.label(tr("None"));
                 }
             });
+            if let Some(pattern_id) = self.fill_pattern.0 {
+                render_fill_pattern_editor(ui, design, pattern_id);
+            }
         }
 
         ui.separator();
-        ui.label("Macros:")
+        ui.label(tr("Macros:"))
             .on_hover_text("Define macros that could be triggered by events associated with this object.\n\
                             Currently, FillAttributes does not trigger events, but this is included for consistency.");
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -3186,27 +4151,28 @@ impl ConfigurableObject for InputAttributes {
         render_object_id(ui, &mut self.id, design);
 
         ui.horizontal(|ui| {
-            ui.label("Validation Type:");
+            ui.label(tr("Validation Type:"));
             ui.radio_value(
                 &mut self.validation_type,
                 ValidationType::ValidCharacters,
-                "Valid Characters",
+                tr("Valid Characters"),
             );
             ui.radio_value(
                 &mut self.validation_type,
                 ValidationType::InvalidCharacters,
-                "Invalid Characters",
+                tr("Invalid Characters"),
             );
         });
 
-        ui.label("Validation String:");
+        ui.label(tr("Validation String:"));
         ui.text_edit_singleline(&mut self.validation_string);
 
         ui.separator();
-        ui.label("Macros:");
+        ui.label(tr("Event hooks:"));
         render_macro_references(
             ui,
             design,
+            self.id,
             &mut self.macro_refs,
             &Self::get_possible_events(),
         );
@@ -3217,11 +4183,11 @@ impl ConfigurableObject for ObjectPointer {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
         ui.horizontal(|ui| {
-            ui.label("Object reference:");
+            ui.label(tr("Object reference:"));
             egui::ComboBox::from_id_salt("object_reference")
                 .selected_text(format!("{:?}", u16::from(self.value)))
                 .show_ui(ui, |ui| {
-                    ui.selectable_value(&mut self.value, NullableObjectId::NULL, "None");
+                    ui.selectable_value(&mut self.value, NullableObjectId::NULL, tr("None"));
                     let object_types: Vec<ObjectType> = design
                         .get_pool()
                         .parent_objects(self.id)
@@ -3249,14 +4215,14 @@ impl ConfigurableObject for ObjectPointer {
                         *design.get_mut_selected().borrow_mut() = id.into();
                     }
                 } else {
-                    ui.colored_label(egui::Color32::RED, "Missing object in pool");
+                    ui.colored_label(egui::Color32::RED, tr("Missing object in pool"));
                 }
             }
         });
     }
 }
 
-const ALLOWED_MACRO_COMMANDS: &[(u8, &str, VtVersion)] = &[
+pub(crate) const ALLOWED_MACRO_COMMANDS: &[(u8, &str, VtVersion)] = &[
     (0xA0, "Hide/Show Object command", VtVersion::Version2),
     (0xA1, "Enable/Disable Object command", VtVersion::Version2),
     (0xA2, "Select Input Object command", VtVersion::Version2),
@@ -3305,7 +4271,7 @@ impl ConfigurableObject for Macro {
     fn render_parameters(&mut self, ui: &mut egui::Ui, design: &EditorProject) {
         render_object_id(ui, &mut self.id, design);
 
-        ui.label("Macro Commands:");
+        ui.label(tr("Macro Commands:"));
         egui::Grid::new("macro_commands_grid")
             .striped(true)
             .min_col_width(0.0)
@@ -3329,9 +4295,9 @@ impl ConfigurableObject for Macro {
             });
 
         ui.horizontal(|ui| {
-            ui.label("Add command:");
+            ui.label(tr("Add command:"));
             egui::ComboBox::from_id_salt("add_macro_command")
-                .selected_text("Select command")
+                .selected_text(tr("Select command"))
                 .show_ui(ui, |ui| {
                     for &(code, name, version) in ALLOWED_MACRO_COMMANDS {
                         if version > VtVersion::Version3 {
@@ -3355,13 +4321,13 @@ impl ConfigurableObject for AuxiliaryFunctionType2 {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Function Type:");
+            ui.label(tr("Function Type:"));
             egui::ComboBox::from_id_salt("function_type")
                 .selected_text(format!("{:?}", self.function_attributes.function_type))
                 .show_ui(ui, |ui| {
@@ -3393,20 +4359,20 @@ impl ConfigurableObject for AuxiliaryFunctionType2 {
                 });
         });
 
-        ui.checkbox(&mut self.function_attributes.critical, "Critical");
-        ui.checkbox(&mut self.function_attributes.restricted, "Restricted");
+        ui.checkbox(&mut self.function_attributes.critical, tr("Critical"));
+        ui.checkbox(&mut self.function_attributes.restricted, tr("Restricted"));
         ui.checkbox(
             &mut self.function_attributes.single_assignment,
-            "Single-assignment",
+            tr("Single-assignment"),
         );
 
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_references_list(
             ui,
             design,
-            design.mask_size,
-            design.mask_size,
+            design.soft_key_size.0,
+            design.soft_key_size.1,
             &mut self.object_refs,
             &Self::get_allowed_child_refs(VtVersion::Version3),
             self.id,
@@ -3419,13 +4385,13 @@ impl ConfigurableObject for AuxiliaryInputType2 {
         render_object_id(ui, &mut self.id, design);
 
         ui.add(
-            egui::Slider::new(&mut self.background_colour, 0..=255)
-                .text("Background Colour")
+            number_slider(&mut self.background_colour, 0..=255, design)
+                .text(tr("Background Colour"))
                 .drag_value_speed(1.0),
         );
 
         ui.horizontal(|ui| {
-            ui.label("Function Type:");
+            ui.label(tr("Function Type:"));
             egui::ComboBox::from_id_salt("input_function_type")
                 .selected_text(format!("{:?}", self.function_attributes.function_type))
                 .show_ui(ui, |ui| {
@@ -3457,19 +4423,19 @@ impl ConfigurableObject for AuxiliaryInputType2 {
                 });
         });
 
-        ui.checkbox(&mut self.function_attributes.critical, "Critical");
+        ui.checkbox(&mut self.function_attributes.critical, tr("Critical"));
         ui.checkbox(
             &mut self.function_attributes.single_assignment,
-            "Single-assignment",
+            tr("Single-assignment"),
         );
 
         ui.separator();
-        ui.label("Objects:");
+        ui.label(tr("Objects:"));
         render_object_references_list(
             ui,
             design,
-            design.mask_size,
-            design.mask_size,
+            design.soft_key_size.0,
+            design.soft_key_size.1,
             &mut self.object_refs,
             &Self::get_allowed_child_refs(VtVersion::Version3),
             self.id,
@@ -3482,29 +4448,29 @@ impl ConfigurableObject for AuxiliaryControlDesignatorType2 {
         render_object_id(ui, &mut self.id, design);
 
         ui.horizontal(|ui| {
-            ui.label("Pointer Type:");
+            ui.label(tr("Pointer Type:"));
             egui::ComboBox::from_id_salt("aux_control_pointer_type")
                 .selected_text(format!("{}", self.pointer_type))
                 .show_ui(ui, |ui| {
                     ui.selectable_value(
                         &mut self.pointer_type,
                         0,
-                        "0 (Points to Auxiliary Object)",
+                        tr("0 (Points to Auxiliary Object)"),
                     );
                     ui.selectable_value(
                         &mut self.pointer_type,
                         1,
-                        "1 (Points to Assigned Aux Objects)",
+                        tr("1 (Points to Assigned Aux Objects)"),
                     );
                     ui.selectable_value(
                         &mut self.pointer_type,
                         2,
-                        "2 (Points to WS Object of this Pool)",
+                        tr("2 (Points to WS Object of this Pool)"),
                     );
                     ui.selectable_value(
                         &mut self.pointer_type,
                         3,
-                        "3 (Points to WS Object of Assigned)",
+                        tr("3 (Points to WS Object of Assigned)"),
                     );
                 });
         });
@@ -3516,7 +4482,7 @@ impl ConfigurableObject for AuxiliaryControlDesignatorType2 {
         } else {
             // Allow user to select an Auxiliary Input or Auxiliary Function object.
             ui.horizontal(|ui| {
-                ui.label("Auxiliary Object ID:");
+                ui.label(tr("Auxiliary Object ID:"));
                 egui::ComboBox::from_id_salt("aux_object_id_selector")
                     .selected_text(format!("{:?}", u16::from(self.auxiliary_object_id)))
                     .show_ui(ui, |ui| {
@@ -3551,7 +4517,7 @@ impl ConfigurableObject for AuxiliaryControlDesignatorType2 {
                             *design.get_mut_selected().borrow_mut() = ref_id.into();
                         }
                     } else {
-                        ui.colored_label(egui::Color32::RED, "Missing object in pool");
+                        ui.colored_label(egui::Color32::RED, tr("Missing object in pool"));
                     }
                 }
             });
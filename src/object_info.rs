@@ -2,6 +2,10 @@
 //! SPDX-License-Identifier: GPL-3.0-or-later
 //! Authors: Daan Steenbergen
 
+use std::collections::BTreeMap;
+
+use crate::review::ObjectReview;
+use crate::string_inventory::TranslationStatus;
 use ag_iso_stack::object_pool::object::Object;
 use uuid::Uuid;
 
@@ -14,6 +18,25 @@ pub struct ObjectInfo {
     /// Optional name for the object.
     /// This is used to give the object a name throughout the editor that is more human-readable
     pub name: Option<String>,
+
+    /// Semantic colour role assigned to each colour attribute, by attribute name
+    pub colour_roles: BTreeMap<String, String>,
+
+    /// Review status and comments
+    pub review: ObjectReview,
+
+    /// Locked objects can not be modified or deleted until they are unlocked
+    pub locked: bool,
+
+    /// Translation status of the text of string objects
+    pub translation: TranslationStatus,
+
+    /// Free text notes, lines tagged TODO or FIXME are listed as tasks and words like `#metric`
+    /// tag the object for scripts
+    pub notes: String,
+
+    /// Name of the style preset that is applied to the object
+    pub style_preset: Option<String>,
 }
 
 impl ObjectInfo {
@@ -21,6 +44,12 @@ impl ObjectInfo {
         ObjectInfo {
             unique_id: Uuid::new_v4(),
             name: None,
+            colour_roles: BTreeMap::new(),
+            review: ObjectReview::default(),
+            locked: false,
+            translation: TranslationStatus::default(),
+            notes: String::new(),
+            style_preset: None,
         }
     }
 
@@ -10,25 +10,32 @@ use std::ops::Sub;
 use ag_iso_stack::object_pool::object::*;
 use ag_iso_stack::object_pool::object_attributes::ButtonState;
 use ag_iso_stack::object_pool::object_attributes::FontSize;
+use ag_iso_stack::object_pool::object_attributes::FontStyle;
 use ag_iso_stack::object_pool::object_attributes::FormatType;
 use ag_iso_stack::object_pool::object_attributes::HorizontalAlignment;
 use ag_iso_stack::object_pool::object_attributes::LineDirection;
-use ag_iso_stack::object_pool::object_attributes::PictureGraphicFormat;
+use ag_iso_stack::object_pool::object_attributes::NumberOptions;
 use ag_iso_stack::object_pool::object_attributes::Point;
 use ag_iso_stack::object_pool::object_attributes::VerticalAlignment;
 use ag_iso_stack::object_pool::vt_version::VtVersion;
 use ag_iso_stack::object_pool::Colour;
+use ag_iso_stack::object_pool::ObjectId;
 use ag_iso_stack::object_pool::ObjectPool;
 use ag_iso_stack::object_pool::ObjectRef;
 use eframe::egui;
 use eframe::egui::Color32;
-use eframe::egui::ColorImage;
 use eframe::egui::FontId;
 use eframe::egui::TextWrapMode;
-use eframe::egui::TextureHandle;
-use eframe::egui::TextureId;
 use eframe::egui::UiBuilder;
 
+use crate::input_simulation::{simulate_input_list, simulate_input_number, simulated_input_value};
+use crate::picture_graphic::{palette_image, picture_palette};
+use crate::preview_state::{
+    designator_size, flash_phase_on, is_hidden_in_preview, object_state, vt_font_family,
+    with_preview_state, ButtonPreviewState, PreviewMode,
+};
+use crate::texture_worker::lazy_texture;
+
 pub trait RenderableObject {
     fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>);
 }
@@ -92,7 +99,7 @@ impl RenderableObject for Object {
     }
 }
 
-trait Colorable {
+pub(crate) trait Colorable {
     fn convert(&self) -> egui::Color32;
 }
 
@@ -128,8 +135,101 @@ fn create_relative_rect(ui: &mut egui::Ui, position: Point<i16>, size: egui::Vec
     )
 }
 
+/// Apply the inverted and flashing font styles to the text colours.
+/// Returns the font colour, the background colour and whether the text is visible.
+fn apply_font_style(
+    ctx: &egui::Context,
+    style: &FontStyle,
+    font_colour: egui::Color32,
+    background_colour: egui::Color32,
+) -> (egui::Color32, egui::Color32, bool) {
+    let mut inverted = style.inverted;
+    let mut visible = true;
+    if (style.flashing_inverted || style.flashing_hidden) && !flash_phase_on(ctx) {
+        inverted ^= style.flashing_inverted;
+        visible = !style.flashing_hidden;
+    }
+
+    if inverted {
+        (background_colour, font_colour, visible)
+    } else {
+        (font_colour, background_colour, visible)
+    }
+}
+
+/// Font to lay out text of a VT font size with, as used by output strings
+pub fn vt_font_id(ui: &egui::Ui, font_size: &FontSize) -> FontId {
+    let font_family = vt_font_family(ui.ctx(), font_size);
+    let font_height = match font_size {
+        FontSize::NonProportional(size) => {
+            // We need to calculate the font height based on the width of a letter in the monospace font.
+            let letter_size = ui.fonts(|fonts| {
+                fonts
+                    .layout_no_wrap(
+                        "a".into(),
+                        FontId::new(size.height() as f32, font_family.clone()),
+                        Color32::WHITE,
+                    )
+                    .size()
+            });
+            size.height() as f32 * (letter_size.x / size.width() as f32)
+        }
+        FontSize::Proportional(height) => *height as f32,
+    };
+    FontId::new(font_height, font_family)
+}
+
+/// Size of a text laid out in the font of a font attributes object, wrapped at `wrap_width` if set.
+/// Returns None if the font attributes object is missing.
+pub fn measure_vt_text(
+    ui: &egui::Ui,
+    pool: &ObjectPool,
+    font_attributes: ObjectId,
+    text: &str,
+    wrap_width: Option<f32>,
+) -> Option<egui::Vec2> {
+    let Some(Object::FontAttributes(font_attributes)) = pool.object_by_id(font_attributes) else {
+        return None;
+    };
+    let text = vt_display_text(text, &HorizontalAlignment::Left, false);
+    let font_id = vt_font_id(ui, &font_attributes.font_size);
+    let galley = ui.fonts(|fonts| {
+        fonts.layout(
+            text,
+            font_id,
+            Color32::WHITE,
+            wrap_width.unwrap_or(f32::INFINITY),
+        )
+    });
+    Some(galley.size())
+}
+
+/// Render the object references inside a designator (soft key or working set) of the simulated
+/// terminal. Children are clipped to the designator, like a VT would do.
+fn render_designator(
+    ui: &mut egui::Ui,
+    pool: &ObjectPool,
+    position: Point<i16>,
+    background_colour: u8,
+    object_refs: &Vec<ObjectRef>,
+) {
+    let (width, height) = designator_size(ui);
+    let rect = create_relative_rect(ui, position, egui::Vec2::new(width as f32, height as f32));
+
+    ui.painter_at(rect)
+        .rect_filled(rect, 0.0, pool.color_by_index(background_colour).convert());
+
+    ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
+        ui.set_clip_rect(rect.intersect(ui.clip_rect()));
+        render_object_refs(ui, pool, object_refs);
+    });
+}
+
 fn render_object_refs(ui: &mut egui::Ui, pool: &ObjectPool, object_refs: &Vec<ObjectRef>) {
     for object in object_refs.iter() {
+        if is_hidden_in_preview(ui.ctx(), object.id) {
+            continue;
+        }
         match pool.object_by_id(object.id) {
             Some(obj) => {
                 obj.render(ui, pool, object.offset);
@@ -142,19 +242,20 @@ fn render_object_refs(ui: &mut egui::Ui, pool: &ObjectPool, object_refs: &Vec<Ob
 }
 
 impl RenderableObject for WorkingSet {
-    fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, _: Point<i16>) {
+    fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>) {
         if !self.selectable {
             // The working set is not visible
             return;
         }
 
-        ui.painter().rect_filled(
-            ui.available_rect_before_wrap(),
-            0.0,
-            pool.color_by_index(self.background_colour).convert(),
+        // The working set is shown in a designator with the same size as a soft key designator
+        render_designator(
+            ui,
+            pool,
+            position,
+            self.background_colour,
+            &self.object_refs,
         );
-
-        render_object_refs(ui, pool, &self.object_refs);
     }
 }
 
@@ -184,7 +285,7 @@ impl RenderableObject for AlarmMask {
 
 impl RenderableObject for Container {
     fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>) {
-        if self.hidden {
+        if !object_state(ui.ctx(), self.id, !self.hidden) {
             return;
         }
 
@@ -222,14 +323,22 @@ impl RenderableObject for Button {
             transparent_background = self.options.transparent_background;
             disabled = self.options.disabled;
         }
-
-        // Determine if button is latchable and currently latched (pressed).
-        let latchable = self.options.latchable;
-        let latched = if latchable {
-            self.options.state == ButtonState::Latched
-        } else {
-            false
-        };
+        disabled = !object_state(ui.ctx(), self.id, !disabled);
+
+        let (mode, preview, simulated_latched) = with_preview_state(ui.ctx(), |state| {
+            (
+                state.mode,
+                state
+                    .button_preview
+                    .get(&self.id)
+                    .copied()
+                    .unwrap_or_default(),
+                state.button_latched.get(&self.id).copied(),
+            )
+        });
+        if mode == PreviewMode::Edit && preview == ButtonPreviewState::Disabled {
+            disabled = true;
+        }
 
         // Compute the face rectangle based on border settings
         // According to the standard:
@@ -247,17 +356,38 @@ impl RenderableObject for Button {
             rect.shrink(BORDER_WIDTH)
         };
 
+        // Only react to the pointer when simulating, otherwise clicks are used for selecting
+        let simulating = mode == PreviewMode::Simulate && !disabled;
         let response = ui.interact(
             face_rect,
             ui.id().with(self.id.value()),
-            egui::Sense::click(),
+            if simulating {
+                egui::Sense::click()
+            } else {
+                egui::Sense::hover()
+            },
         );
 
+        // Determine if button is latchable and currently latched.
+        let latchable = self.options.latchable;
+        let mut latched =
+            latchable && simulated_latched.unwrap_or(self.options.state == ButtonState::Latched);
+        if simulating && latchable && response.clicked() {
+            latched = !latched;
+            with_preview_state(ui.ctx(), |state| {
+                state.button_latched.insert(self.id, latched);
+            });
+        }
+
         // Determine the current visual state
         // Priority: latched > pressed > hovered > normal
-        let is_pressed_state = latched || (response.is_pointer_button_down_on() && !latchable);
-        let is_hovered_state = response.hovered();
-        // TODO: better visuals for latched states
+        let is_pressed_state = match mode {
+            PreviewMode::Edit => latched || preview == ButtonPreviewState::Pressed,
+            PreviewMode::Simulate => {
+                latched || (simulating && !latchable && response.is_pointer_button_down_on())
+            }
+        };
+        let is_hovered_state = simulating && response.hovered();
 
         let background_color = if transparent_background {
             egui::Color32::TRANSPARENT
@@ -296,6 +426,16 @@ impl RenderableObject for Button {
 
         ui.painter().rect_filled(face_rect, 0.0, background_color);
 
+        // Draw latched buttons sunken, so they can be told apart from a momentary press
+        if latched && !no_border {
+            ui.painter().rect_stroke(
+                face_rect,
+                0.0,
+                egui::Stroke::new(1.0, darken_color(border_color, 0.4)),
+                egui::StrokeKind::Inside,
+            );
+        }
+
         // Child objects are clipped to the face area
         ui.scope_builder(UiBuilder::new().max_rect(face_rect), |ui| {
             render_object_refs(ui, pool, &self.object_refs);
@@ -351,7 +491,7 @@ impl RenderableObject for InputBoolean {
             }
 
             // If disabled, overlay a semi-transparent layer
-            if !self.enabled {
+            if !object_state(ui.ctx(), self.id, self.enabled) {
                 ui.painter().rect_filled(
                     rect,
                     0.0,
@@ -370,9 +510,26 @@ impl RenderableObject for InputString {
             egui::Vec2::new(self.width() as f32, self.height() as f32),
         );
 
-        ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
-            ui.colored_label(Color32::RED, "InputString not implemented");
-        });
+        let text_value = match self
+            .variable_reference
+            .0
+            .and_then(|id| pool.object_by_id(id))
+        {
+            Some(Object::StringVariable(s)) => s.value.clone(),
+            _ => self.value.clone(),
+        };
+
+        render_vt_string(
+            ui,
+            pool,
+            rect,
+            self.font_attributes,
+            self.background_colour,
+            self.options.transparent,
+            self.options.auto_wrap,
+            (&self.justification.horizontal, &self.justification.vertical),
+            &text_value,
+        );
     }
 }
 
@@ -384,6 +541,20 @@ impl RenderableObject for InputNumber {
             egui::Vec2::new(self.width as f32, self.height as f32),
         );
 
+        // Determine the “raw” number value to use: a value entered while simulating, the
+        // referenced NumberVariable’s value if a variable_reference exists, or our own value.
+        let raw_value = simulated_input_value(ui.ctx(), self.id, self.variable_reference.0)
+            .unwrap_or_else(|| {
+                match self
+                    .variable_reference
+                    .0
+                    .and_then(|id| pool.object_by_id(id))
+                {
+                    Some(Object::NumberVariable(num_var)) => num_var.value,
+                    _ => self.value,
+                }
+            });
+
         ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
             // Look up the font attributes. If missing, show an error.
             let font_attributes = match pool.object_by_id(self.font_attributes) {
@@ -400,52 +571,28 @@ impl RenderableObject for InputNumber {
                 }
             };
 
-            // Get the background colour from the pool.
-            let background_colour = pool.color_by_index(self.background_colour).convert();
+            // Get the background and font colour from the pool.
+            let (font_colour, background_colour, text_visible) = apply_font_style(
+                ui.ctx(),
+                &font_attributes.font_style,
+                pool.color_by_index(font_attributes.font_colour).convert(),
+                pool.color_by_index(self.background_colour).convert(),
+            );
             // Fill the background if the NumberOptions do not specify transparency.
             if !self.options.transparent {
                 ui.painter().rect_filled(rect, 0.0, background_colour);
             }
 
-            // Determine the “raw” number value to use: if a variable_reference exists, use the referenced
-            // NumberVariable’s value; otherwise use our own value.
-            let raw_value: u32 = if let Some(var_id) = self.variable_reference.0 {
-                match pool.object_by_id(var_id) {
-                    Some(Object::NumberVariable(num_var)) => num_var.value,
-                    _ => self.value,
-                }
-            } else {
-                self.value
-            };
-
-            // Compute the displayed value using double precision:
-            //   displayed_value = (raw_value + offset) * scale
-            let mut displayed_value = {
-                let float_raw = raw_value as f64;
-                let float_offset = self.offset as f64;
-                let float_scale = self.scale as f64;
-                (float_raw + float_offset) * float_scale
-            };
-
-            // Use the number of decimals (up to 7) and the "truncate" flag from NumberOptions
-            let decimals = self.nr_of_decimals.min(7);
-            let power_of_ten = 10f64.powi(decimals as i32);
-            if self.options.truncate {
-                displayed_value = (displayed_value * power_of_ten).trunc() / power_of_ten;
-            } else {
-                displayed_value = (displayed_value * power_of_ten).round() / power_of_ten;
-            }
-
-            // If the "display_zero_as_blank" option is set and the computed value is exactly zero, show nothing.
-            if self.options.display_zero_as_blank && displayed_value == 0.0 {
+            // Format the value, a blank field when zero is displayed as blank
+            let Some(mut number_string) = format_vt_number(
+                raw_value,
+                self.offset,
+                self.scale,
+                self.nr_of_decimals,
+                &self.format,
+                &self.options,
+            ) else {
                 return;
-            }
-
-            // Format the number to a string. Use exponential formatting if requested.
-            let mut number_string = if self.format == FormatType::Exponential {
-                format!("{:.*e}", decimals as usize, displayed_value)
-            } else {
-                format!("{:.*}", decimals as usize, displayed_value)
             };
 
             // If the "display_leading_zeros" option is set, try to pad the text on the left with zeros
@@ -456,15 +603,13 @@ impl RenderableObject for InputNumber {
                     FontSize::NonProportional(size) => size.height() as f32,
                     FontSize::Proportional(h) => h as f32,
                 };
-                let font_id = egui::FontId::new(font_height, egui::FontFamily::Proportional);
+                let font_family = vt_font_family(ui.ctx(), &font_attributes.font_size);
+                let font_id = egui::FontId::new(font_height, font_family);
                 let mut zero_padded = number_string.clone();
                 let max_loop = 1000; // safety to avoid an infinite loop
                 for _ in 0..max_loop {
-                    let galley = fonts.layout_no_wrap(
-                        zero_padded.clone(),
-                        font_id.clone(),
-                        pool.color_by_index(font_attributes.font_colour).convert(),
-                    );
+                    let galley =
+                        fonts.layout_no_wrap(zero_padded.clone(), font_id.clone(), font_colour);
                     if galley.size().x >= rect.width() {
                         number_string = zero_padded;
                         break;
@@ -474,15 +619,11 @@ impl RenderableObject for InputNumber {
                 }
             }
 
-            // Get the font colour.
-            let font_colour = pool.color_by_index(font_attributes.font_colour).convert();
-
             // Choose the font family and height according to the font size:
-            let (font_family, font_height) = match font_attributes.font_size {
-                FontSize::NonProportional(npsize) => {
-                    (egui::FontFamily::Monospace, npsize.height() as f32)
-                }
-                FontSize::Proportional(h) => (egui::FontFamily::Proportional, h as f32),
+            let font_family = vt_font_family(ui.ctx(), &font_attributes.font_size);
+            let font_height = match font_attributes.font_size {
+                FontSize::NonProportional(npsize) => npsize.height() as f32,
+                FontSize::Proportional(h) => h as f32,
             };
             let font_id = egui::FontId::new(font_height, font_family);
 
@@ -536,11 +677,13 @@ impl RenderableObject for InputNumber {
             }
 
             // Draw the number string.
-            ui.painter().galley(paint_pos, galley, font_colour);
+            if text_visible {
+                ui.painter().galley(paint_pos, galley, font_colour);
+            }
 
             // If the InputNumber object is not enabled (according to its InputNumberOptions),
             // overlay a semi‐transparent gray rectangle.
-            if !self.options2.enabled {
+            if !object_state(ui.ctx(), self.id, self.options2.enabled) {
                 ui.painter().rect_filled(
                     rect,
                     0.0,
@@ -548,6 +691,8 @@ impl RenderableObject for InputNumber {
                 );
             }
         });
+
+        simulate_input_number(ui, rect, self, raw_value);
     }
 }
 
@@ -559,19 +704,46 @@ impl RenderableObject for InputList {
             egui::Vec2::new(self.width() as f32, self.height() as f32),
         );
 
-        ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
-            ui.colored_label(Color32::RED, "InputList not implemented");
-        });
+        let value = simulated_input_value(ui.ctx(), self.id, self.variable_reference.0)
+            .map(|value| value.min(u8::MAX as u32) as u8)
+            .unwrap_or_else(|| {
+                match self
+                    .variable_reference
+                    .0
+                    .and_then(|id| pool.object_by_id(id))
+                {
+                    Some(Object::NumberVariable(variable)) => {
+                        variable.value.min(u8::MAX as u32) as u8
+                    }
+                    _ => self.value,
+                }
+            });
+
+        // The selected item is shown in the area of the list, a missing item shows nothing
+        if let Some(item) = self
+            .list_items
+            .get(value as usize)
+            .and_then(|item| item.0)
+            .and_then(|id| pool.object_by_id(id))
+        {
+            let mut item_ui = ui.new_child(UiBuilder::new().max_rect(rect));
+            item_ui.set_clip_rect(rect.intersect(ui.clip_rect()));
+            item.render(&mut item_ui, pool, Point::default());
+        }
+
+        simulate_input_list(ui, rect, pool, self, value);
     }
 }
 
 impl RenderableObject for Key {
     fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>) {
-        let rect = create_relative_rect(ui, position, egui::Vec2::new(100.0, 100.0));
-
-        ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
-            render_object_refs(ui, pool, &self.object_refs);
-        });
+        render_designator(
+            ui,
+            pool,
+            position,
+            self.background_colour,
+            &self.object_refs,
+        );
     }
 }
 
@@ -593,171 +765,225 @@ impl RenderableObject for ObjectPointer {
     }
 }
 
-impl RenderableObject for OutputString {
-    fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>) {
-        let rect = create_relative_rect(
-            ui,
-            position,
-            egui::Vec2::new(self.width() as f32, self.height() as f32),
-        );
-
-        let font_attributes = match pool.object_by_id(self.font_attributes) {
-            Some(Object::FontAttributes(f)) => f,
-            _ => {
-                ui.colored_label(
-                    Color32::RED,
-                    format!("Missing font attributes: {:?}", self.font_attributes),
-                );
-                return;
-            }
-        };
-        let background_colour = pool.color_by_index(self.background_colour).convert();
+/// Truncate or round a scaled value to the number of decimals, the standard says 0–7 decimals
+pub fn round_vt_number(value: f64, nr_of_decimals: u8, truncate: bool) -> f64 {
+    let power_of_ten = 10f64.powi(nr_of_decimals.min(7) as i32);
+    if truncate {
+        (value * power_of_ten).trunc() / power_of_ten
+    } else {
+        (value * power_of_ten).round() / power_of_ten
+    }
+}
 
-        let transparent = self.options.transparent;
-        let auto_wrap = self.options.auto_wrap;
+/// Value of a number object as a VT shows it, before padding with leading zeros:
+/// (raw value + offset) * scale, rounded or truncated to the number of decimals (at most 7).
+/// None when the field is blank because zero is displayed as blank.
+pub fn format_vt_number(
+    raw_value: u32,
+    offset: i32,
+    scale: f32,
+    nr_of_decimals: u8,
+    format: &FormatType,
+    options: &NumberOptions,
+) -> Option<String> {
+    // Compute the displayed value using double precision to reduce rounding errors
+    let displayed_value = (raw_value as f64 + offset as f64) * scale as f64;
+    let decimals = nr_of_decimals.min(7);
+    let displayed_value = round_vt_number(displayed_value, decimals, options.truncate);
+
+    // "Exactly zero" is checked after the rounding/truncation step
+    if options.display_zero_as_blank && displayed_value == 0.0 {
+        return None;
+    }
 
-        // TODO: check if VT version is 4 or later, if so implement wrap_on_hyphen
-        // let wrap_on_hyphen = self.options.wrap_on_hyphen;
-        // Note: wrap_on_hyphen behavior is complex. For simplicity here, we rely on normal word-wrapping
-        // from egui and do not implement special hyphenation logic. A more thorough implementation
-        // would detect hyphens and possibly treat them as break opportunities.
+    if *format == FormatType::Exponential {
+        Some(format!("{:.*e}", decimals as usize, displayed_value))
+    } else {
+        Some(format!("{:.*}", decimals as usize, displayed_value))
+    }
+}
 
-        // According to the specification, we need to handle control characters (CR, LF) as line breaks.
-        // We'll normalize all line endings to '\n'.
-        let mut text_value = if let Some(variable_reference_id) = self.variable_reference.into() {
-            match pool.object_by_id(variable_reference_id) {
-                Some(Object::StringVariable(s)) => s.value.clone(),
-                _ => self.value.clone(),
-            }
-        } else {
-            self.value.clone()
-        };
-        text_value = text_value
-            .replace("\r\n", "\n")
-            .replace("\n\r", "\n")
-            .replace('\r', "\n")
-            .replace('\x0a', "\n");
-
-        // Apply space trimming rules based on horizontal justification:
-        // - Left justification: no trimming of leading spaces (for the first line), trailing spaces remain as is.
-        // - Middle justification: remove leading and trailing spaces on each line.
-        // - Right justification: remove trailing spaces on each line.
-        let mut lines: Vec<&str> = text_value.split('\n').collect();
-        for (line_number, line) in lines.iter_mut().enumerate() {
-            match self.justification.horizontal {
-                HorizontalAlignment::Left => {
-                    // Per ISO rules, if auto-wrapping is enabled, leading spaces on wrapped lines might be removed.
-                    if auto_wrap && line_number > 0 {
-                        // Remove leading spaces
-                        *line = line.trim_start();
-                    }
-                }
-                HorizontalAlignment::Middle => {
-                    // Remove both leading and trailing spaces
-                    *line = line.trim();
-                }
-                HorizontalAlignment::Right => {
-                    // Remove trailing spaces only
-                    *line = line.trim_end();
-                }
-                HorizontalAlignment::Reserved => {
-                    ui.colored_label(
-                        Color32::RED,
-                        "Configuration incorrect: horizontal alignment is set to Reserved",
-                    );
-                    return;
-                }
+/// Text of a string object as a VT shows it. CR, LF, CR LF and LF CR each start a new line and
+/// other control characters are not shown. Spaces are trimmed depending on the justification:
+/// middle justified lines lose leading and trailing spaces, right justified lines lose the
+/// padding at their end, and lines after the first of an auto wrapped left justified string lose
+/// their leading spaces.
+pub fn vt_display_text(value: &str, horizontal: &HorizontalAlignment, auto_wrap: bool) -> String {
+    let mut text = String::with_capacity(value.len());
+    let mut chars = value.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\r' | '\n' => {
+                let pair = if c == '\r' { '\n' } else { '\r' };
+                chars.next_if_eq(&pair);
+                text.push('\n');
             }
+            c if c.is_control() => {}
+            c => text.push(c),
         }
+    }
 
-        let processed_text = lines.join("\n");
+    let lines: Vec<&str> = text
+        .split('\n')
+        .enumerate()
+        .map(|(line_number, line)| match horizontal {
+            HorizontalAlignment::Left if auto_wrap && line_number > 0 => line.trim_start(),
+            HorizontalAlignment::Middle => line.trim(),
+            HorizontalAlignment::Right => line.trim_end(),
+            _ => line,
+        })
+        .collect();
+    lines.join("\n")
+}
 
-        let font_colour = pool.color_by_index(font_attributes.font_colour).convert();
-        let fonts = ui.fonts(|fonts| fonts.clone());
-        let font_height;
-        let font_family;
-        match font_attributes.font_size {
-            FontSize::NonProportional(size) => {
-                font_family = egui::FontFamily::Monospace;
+/// Paint the value of a string object like a VT does: the text is justified in the area of the
+/// object, only complete characters and lines are shown.
+#[allow(clippy::too_many_arguments)]
+fn render_vt_string(
+    ui: &mut egui::Ui,
+    pool: &ObjectPool,
+    rect: egui::Rect,
+    font_attributes: ObjectId,
+    background_colour: u8,
+    transparent: bool,
+    auto_wrap: bool,
+    justification: (&HorizontalAlignment, &VerticalAlignment),
+    value: &str,
+) {
+    let font_attributes = match pool.object_by_id(font_attributes) {
+        Some(Object::FontAttributes(f)) => f,
+        _ => {
+            ui.colored_label(
+                Color32::RED,
+                format!("Missing font attributes: {:?}", font_attributes),
+            );
+            return;
+        }
+    };
+    let (horizontal, vertical) = justification;
+    if *horizontal == HorizontalAlignment::Reserved {
+        ui.colored_label(
+            Color32::RED,
+            "Configuration incorrect: horizontal alignment is set to Reserved",
+        );
+        return;
+    }
+    if *vertical == VerticalAlignment::Reserved {
+        ui.colored_label(
+            Color32::RED,
+            "Configuration incorrect: vertical alignment is set to Reserved",
+        );
+        return;
+    }
 
-                // We need to calculate the font height based on the width of a letter in the monospace font.
-                let font_size = fonts
-                    .layout_no_wrap(
-                        "a".into(),
-                        FontId::new(size.height() as f32, egui::FontFamily::Monospace),
-                        font_colour,
-                    )
-                    .size();
+    // TODO: check if VT version is 4 or later, if so implement wrap_on_hyphen
+    // Note: wrap_on_hyphen behavior is complex. For simplicity here, we rely on normal word-wrapping
+    // from egui and do not implement special hyphenation logic.
+    let (font_colour, background_colour, text_visible) = apply_font_style(
+        ui.ctx(),
+        &font_attributes.font_style,
+        pool.color_by_index(font_attributes.font_colour).convert(),
+        pool.color_by_index(background_colour).convert(),
+    );
+    let fonts = ui.fonts(|fonts| fonts.clone());
+    let font_id = vt_font_id(ui, &font_attributes.font_size);
+
+    let mut text = vt_display_text(value, horizontal, auto_wrap);
+    if !auto_wrap {
+        // Characters that do not fit completely are truncated from the end of the line
+        let lines: Vec<String> = text
+            .split('\n')
+            .map(|line| {
+                let mut width = 0.0;
+                line.chars()
+                    .take_while(|c| {
+                        width += fonts.glyph_width(&font_id, *c);
+                        width <= rect.width() + 0.5
+                    })
+                    .collect()
+            })
+            .collect();
+        text = lines.join("\n");
+    }
 
-                font_height = size.height() as f32 * (font_size.x / size.width() as f32);
-            }
-            FontSize::Proportional(height) => {
-                font_height = height as f32;
-                font_family = egui::FontFamily::Proportional;
-            }
-        }
+    let wrap_width = if auto_wrap {
+        rect.width()
+    } else {
+        f32::INFINITY
+    };
+    let galley = fonts.layout(text, font_id, font_colour, wrap_width);
+    let text_size = galley.size();
+
+    // Lines that do not fit completely are not shown, the text then starts at the top
+    let overflows = text_size.y > rect.height() + 0.5;
+    let visible_height = galley
+        .rows
+        .iter()
+        .map(|row| row.rect().max.y)
+        .filter(|bottom| *bottom <= rect.height() + 0.5)
+        .fold(0.0, f32::max);
+
+    let mut paint_pos = rect.min;
+    paint_pos.x = match horizontal {
+        HorizontalAlignment::Middle => rect.center().x - (text_size.x * 0.5),
+        HorizontalAlignment::Right => rect.max.x - text_size.x,
+        _ => rect.min.x,
+    };
+    paint_pos.y = match vertical {
+        _ if overflows => rect.min.y,
+        VerticalAlignment::Middle => rect.center().y - (text_size.y * 0.5),
+        VerticalAlignment::Bottom => rect.max.y - text_size.y,
+        _ => rect.min.y,
+    };
+
+    // Paint the background first, so the text ends up on top of it. A transparent string
+    // paints nothing here, leaving the objects rendered before it visible underneath.
+    // Both are clipped to the object's area, so they never cover neighbouring objects.
+    let painter = ui.painter_at(rect);
+    if !transparent {
+        painter.rect_filled(rect, 0.0, background_colour);
+    }
 
-        let wrap_width = if auto_wrap {
-            self.width() as f32
+    if text_visible {
+        let text_clip = if overflows {
+            egui::Rect::from_min_size(rect.min, egui::vec2(rect.width(), visible_height))
         } else {
-            f32::INFINITY
+            rect
         };
+        painter
+            .with_clip_rect(text_clip)
+            .galley(paint_pos, galley, font_colour);
+    }
+}
 
-        let galley = fonts.layout(
-            processed_text,
-            FontId::new(font_height, font_family.clone()),
-            font_colour,
-            wrap_width,
+impl RenderableObject for OutputString {
+    fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>) {
+        let rect = create_relative_rect(
+            ui,
+            position,
+            egui::Vec2::new(self.width() as f32, self.height() as f32),
         );
 
-        let text_size = galley.size();
-
-        let mut paint_pos = rect.min;
-
-        match self.justification.horizontal {
-            HorizontalAlignment::Left => {
-                paint_pos.x = rect.min.x;
-            }
-            HorizontalAlignment::Middle => {
-                paint_pos.x = rect.center().x - (text_size.x * 0.5);
-            }
-            HorizontalAlignment::Right => {
-                paint_pos.x = rect.max.x - text_size.x;
-            }
-            HorizontalAlignment::Reserved => {
-                ui.colored_label(
-                    Color32::RED,
-                    "Configuration incorrect: horizontal alignment is set to Reserved",
-                );
-                return;
-            }
-        };
-
-        match self.justification.vertical {
-            VerticalAlignment::Top => {
-                paint_pos.y = rect.min.y;
-            }
-            VerticalAlignment::Middle => {
-                paint_pos.y = rect.center().y - (text_size.y * 0.5);
-            }
-            VerticalAlignment::Bottom => {
-                paint_pos.y = rect.max.y - text_size.y;
-            }
-            VerticalAlignment::Reserved => {
-                ui.colored_label(
-                    Color32::RED,
-                    "Configuration incorrect: vertical alignment is set to Reserved",
-                );
-                return;
+        let text_value = if let Some(variable_reference_id) = self.variable_reference.into() {
+            match pool.object_by_id(variable_reference_id) {
+                Some(Object::StringVariable(s)) => s.value.clone(),
+                _ => self.value.clone(),
             }
+        } else {
+            self.value.clone()
         };
 
-        if !transparent {
-            let painter = ui.painter();
-            painter.rect_filled(rect, 0.0, background_colour);
-        }
-
-        ui.painter().galley(paint_pos, galley, font_colour);
+        render_vt_string(
+            ui,
+            pool,
+            rect,
+            self.font_attributes,
+            self.background_colour,
+            self.options.transparent,
+            self.options.auto_wrap,
+            (&self.justification.horizontal, &self.justification.vertical),
+            &text_value,
+        );
     }
 }
 
@@ -785,9 +1011,13 @@ impl RenderableObject for OutputNumber {
                 }
             };
 
-            // 2. Convert the pool color indices to `egui::Color32`
-            let background_colour = pool.color_by_index(self.background_colour).convert();
-            let font_colour = pool.color_by_index(font_attributes.font_colour).convert();
+            // 2. Convert the pool color indices to `egui::Color32`, taking the font style into account
+            let (font_colour, background_colour, text_visible) = apply_font_style(
+                ui.ctx(),
+                &font_attributes.font_style,
+                pool.color_by_index(font_attributes.font_colour).convert(),
+                pool.color_by_index(self.background_colour).convert(),
+            );
 
             // 3. Determine if we need to fill the background or remain transparent
             if !self.options.transparent {
@@ -805,51 +1035,29 @@ impl RenderableObject for OutputNumber {
                 self.value
             };
 
-            // 5. Compute the displayed value using double precision to reduce rounding errors
-            let mut displayed_value = {
-                let float_raw = raw_value as f64;
-                let float_offset = self.offset as f64;
-                let float_scale = self.scale as f64;
-                (float_raw + float_offset) * float_scale
-            };
-
-            // 6. Apply truncation or rounding to the number of decimals
-            let decimals = self.nr_of_decimals.min(7); // standard says 0–7 decimals
-            let power_of_ten = 10f64.powi(decimals as i32);
-
-            if self.options.truncate {
-                // Truncate
-                displayed_value = (displayed_value * power_of_ten).trunc() / power_of_ten;
-            } else {
-                // Round
-                displayed_value = (displayed_value * power_of_ten).round() / power_of_ten;
-            }
-
-            // 7. If "display_zero_as_blank" and the final number is exactly zero, display blank
-            //    We interpret "exactly zero" after the rounding/truncation step
-            if self.options.display_zero_as_blank && displayed_value == 0.0 {
+            // 5. Apply offset, scale and decimals, a blank field when zero is displayed as blank
+            let Some(mut number_string) = format_vt_number(
+                raw_value,
+                self.offset,
+                self.scale,
+                self.nr_of_decimals,
+                &self.format,
+                &self.options,
+            ) else {
                 return;
-            }
-
-            // 8. Convert the (possibly truncated/rounded) displayed_value to string
-            //    Depending on the "format" attribute, use decimal or exponential
-            let mut number_string = if self.format == FormatType::Exponential {
-                format!("{:.*e}", decimals as usize, displayed_value)
-            } else {
-                format!("{:.*}", decimals as usize, displayed_value)
             };
 
-            // 9. The standard states that we must always display at least one digit
+            // 6. The standard states that we must always display at least one digit
             //    before the decimal point (i.e., "0.xxxx" if the absolute value < 1)
             //    Normal Rust formatting already ensures e.g. "0.12" for 0.12,
             //    so we usually don't need a special patch here. But we keep the note.
             //
-            // 10. If display_leading_zeros is set, we *attempt* to fill the entire width
-            //     with zeros to the left before applying alignment. (ISO 11783 says
-            //     "fill left to width of field with zeros, then apply justification.")
-            //     Below is a best-effort approach: we measure the text in a loop,
-            //     and keep prepending '0' until it meets or exceeds the available width.
-            //     We also place a reasonable safety limit to avoid infinite loops.
+            // 7. If display_leading_zeros is set, we *attempt* to fill the entire width
+            //    with zeros to the left before applying alignment. (ISO 11783 says
+            //    "fill left to width of field with zeros, then apply justification.")
+            //    Below is a best-effort approach: we measure the text in a loop,
+            //    and keep prepending '0' until it meets or exceeds the available width.
+            //    We also place a reasonable safety limit to avoid infinite loops.
             //
             if self.options.display_leading_zeros {
                 let fonts = ui.fonts(|f| f.clone());
@@ -857,7 +1065,8 @@ impl RenderableObject for OutputNumber {
                     FontSize::NonProportional(s) => s.height() as f32,
                     FontSize::Proportional(h) => h as f32,
                 };
-                let font_id = egui::FontId::new(font_height, egui::FontFamily::Proportional);
+                let font_family = vt_font_family(ui.ctx(), &font_attributes.font_size);
+                let font_id = egui::FontId::new(font_height, font_family);
                 let mut zero_padded = number_string.clone();
                 let max_loop = 1000; // safety net to avoid infinite loops
                 for _ in 0..max_loop {
@@ -882,12 +1091,10 @@ impl RenderableObject for OutputNumber {
             //     Next, figure out the font size and alignment. This is similar
             //     to the `OutputString` example.
             let fonts = ui.fonts(|fonts| fonts.clone());
-            let (font_family, font_height) = match font_attributes.font_size {
-                FontSize::NonProportional(npsize) => {
-                    // For simplicity, treat it as monospace
-                    (egui::FontFamily::Monospace, npsize.height() as f32)
-                }
-                FontSize::Proportional(h) => (egui::FontFamily::Proportional, h as f32),
+            let font_family = vt_font_family(ui.ctx(), &font_attributes.font_size);
+            let font_height = match font_attributes.font_size {
+                FontSize::NonProportional(npsize) => npsize.height() as f32,
+                FontSize::Proportional(h) => h as f32,
             };
             let font_id = egui::FontId::new(font_height, font_family);
             let galley = fonts.layout(
@@ -937,8 +1144,10 @@ impl RenderableObject for OutputNumber {
                 }
             }
 
-            // 13. Finally, paint the text
-            ui.painter().galley(paint_pos, galley, font_colour);
+            // 13. Finally, paint the text (unless it is hidden by flashing)
+            if text_visible {
+                ui.painter().galley(paint_pos, galley, font_colour);
+            }
         });
     }
 }
@@ -1154,105 +1363,34 @@ impl RenderableObject for PictureGraphic {
             egui::Vec2::new(self.width() as f32, self.height() as f32),
         );
 
+        // The image is made on the texture worker, the pool colours are part of its version
+        let palette = picture_palette(pool);
         let mut hasher = DefaultHasher::new();
         Object::PictureGraphic(self.clone())
             .write()
             .hash(&mut hasher);
+        palette.hash(&mut hasher);
         let hash = hasher.finish();
 
-        let changed: bool = ui.data_mut(|data| {
-            let old_hash: Option<u64> =
-                data.get_temp(format!("picturegraphic_{}_image", self.id.value()).into());
-            if old_hash.is_none() || old_hash.unwrap() != hash {
-                data.insert_temp(
-                    format!("picturegraphic_{}_image", self.id.value()).into(),
-                    hash,
-                );
-                true
-            } else {
-                false
-            }
-        });
-
-        let texture_id: Option<TextureId>;
-        if changed {
-            let mut x = 0;
-            let mut y = 0;
-
-            let mut image = ColorImage::filled(
-                [self.actual_width.into(), self.actual_height.into()],
-                Color32::TRANSPARENT,
-            );
-
-            for raw in self.data_as_raw_encoded() {
-                let mut colors: Vec<Color32> = vec![];
-                match self.format {
-                    PictureGraphicFormat::Monochrome => {
-                        for bit in 0..8 {
-                            colors.push(pool.color_by_index((raw >> (7 - bit)) & 0x01).convert());
-                        }
-                    }
-                    PictureGraphicFormat::FourBit => {
-                        for segment in 0..2 {
-                            let shift = 4 - (segment * 4);
-                            colors.push(pool.color_by_index((raw >> shift) & 0x0F).convert());
-                        }
-                    }
-                    PictureGraphicFormat::EightBit => {
-                        colors.push(pool.color_by_index(raw).convert());
-                    }
-                }
-
-                for color in colors {
-                    let idx = y as usize * self.actual_width as usize + x as usize;
-                    if idx >= image.pixels.len() {
-                        break;
-                    }
-                    if !(self.options.transparent
-                        && color == pool.color_by_index(self.transparency_colour).convert())
-                    {
-                        image.pixels[idx] = color;
-                    }
-
-                    x += 1;
-                    if x >= self.actual_width {
-                        x = 0;
-                        y += 1;
-                        // If we go onto the next row, then we discard the rest of the bits
-                        break;
-                    }
-                }
-            }
-
-            let new_texture = ui.ctx().load_texture(
-                format!("picturegraphic_{}_texture", self.id.value()).as_str(),
-                image,
-                Default::default(),
-            );
-            texture_id = Some(new_texture.id());
-            ui.data_mut(|data| {
-                println!("Saving texture - {:?}", self.id.value());
-                data.insert_temp(
-                    format!("picturegraphic_{}_texture", self.id.value()).into(),
-                    new_texture,
-                );
-            });
-        } else {
-            texture_id = ui.data(|data| {
-                data.get_temp::<TextureHandle>(
-                    format!("picturegraphic_{}_texture", self.id.value()).into(),
-                )
-                .map(|t| t.id())
-            });
-        }
+        let picture = self.clone();
+        let texture = lazy_texture(
+            ui.ctx(),
+            &format!("picturegraphic_{}_texture", self.id.value()),
+            hash,
+            move || palette_image(&picture, &palette),
+        );
 
         // Use image dimensions, but clip to the available rect
         let image_size = egui::Vec2::new(self.width as f32, self.height() as f32);
         ui.scope_builder(UiBuilder::new().max_rect(rect), |ui| {
-            if let Some(texture_id) = texture_id {
-                ui.image((texture_id, image_size));
+            if let Some(texture) = texture {
+                if !self.options.flashing || flash_phase_on(ui.ctx()) {
+                    ui.image((texture.id(), image_size));
+                }
             } else {
-                ui.colored_label(Color32::RED, "Failed to load image");
+                // Placeholder until the image is ready
+                ui.painter()
+                    .rect_filled(rect, 0.0, ui.visuals().faint_bg_color);
             }
         });
     }
@@ -1260,21 +1398,90 @@ impl RenderableObject for PictureGraphic {
 
 impl RenderableObject for AuxiliaryFunctionType2 {
     fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>) {
-        ui.colored_label(Color32::RED, "AuxiliaryFunctionType2 not implemented");
+        // Auxiliary designators have the same size as a soft key designator
+        render_designator(
+            ui,
+            pool,
+            position,
+            self.background_colour,
+            &self.object_refs,
+        );
     }
 }
 
 impl RenderableObject for AuxiliaryInputType2 {
     fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>) {
-        ui.colored_label(Color32::RED, "AuxiliaryInputType2 not implemented");
+        render_designator(
+            ui,
+            pool,
+            position,
+            self.background_colour,
+            &self.object_refs,
+        );
     }
 }
 
+/// Placeholder for a designator that is only known on the terminal, e.g. of an assigned input
+fn render_designator_placeholder(ui: &mut egui::Ui, position: Point<i16>, text: &str) {
+    let (width, height) = designator_size(ui);
+    let rect = create_relative_rect(ui, position, egui::Vec2::new(width as f32, height as f32));
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 0.0, Color32::from_gray(200));
+    painter.rect_stroke(
+        rect,
+        0.0,
+        egui::Stroke::new(1.0, Color32::DARK_GRAY),
+        egui::StrokeKind::Inside,
+    );
+    painter.text(
+        rect.center(),
+        egui::Align2::CENTER_CENTER,
+        text,
+        egui::FontId::proportional((height as f32 / 4.0).max(8.0)),
+        Color32::BLACK,
+    );
+}
+
 impl RenderableObject for AuxiliaryControlDesignatorType2 {
     fn render(&self, ui: &mut egui::Ui, pool: &ObjectPool, position: Point<i16>) {
-        ui.colored_label(
-            Color32::RED,
-            "AuxiliaryControlDesignatorType2 not implemented",
-        );
+        let referenced = self
+            .auxiliary_object_id
+            .0
+            .and_then(|id| pool.object_by_id(id));
+        match (self.pointer_type, referenced) {
+            // The designator of the referenced auxiliary object
+            (0, Some(Object::AuxiliaryFunctionType2(o))) => o.render(ui, pool, position),
+            (0, Some(Object::AuxiliaryInputType2(o))) => o.render(ui, pool, position),
+            // The designator of the working set of this pool
+            (2, _) => match pool.working_set_object() {
+                Some(working_set) => render_designator(
+                    ui,
+                    pool,
+                    position,
+                    working_set.background_colour,
+                    &working_set.object_refs,
+                ),
+                None => render_designator_placeholder(ui, position, "No working set"),
+            },
+            // Assignments are made on the terminal, so their designators can not be previewed
+            (1, Some(Object::AuxiliaryFunctionType2(_))) => {
+                render_designator_placeholder(ui, position, "Assigned input")
+            }
+            (1, Some(Object::AuxiliaryInputType2(_))) => {
+                render_designator_placeholder(ui, position, "Assigned function")
+            }
+            (3, Some(_)) => render_designator_placeholder(ui, position, "Assigned WS"),
+            (_, None) => render_designator_placeholder(ui, position, "Unassigned"),
+            (_, Some(other)) => {
+                ui.colored_label(
+                    Color32::RED,
+                    format!(
+                        "Not an auxiliary object: {:?} {}",
+                        other.object_type(),
+                        u16::from(other.id())
+                    ),
+                );
+            }
+        }
     }
 }
@@ -0,0 +1,72 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::HashSet;
+
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+
+use crate::macro_references::macro_targets;
+use crate::{ChangeCategory, EditorProject};
+
+/// Objects that only do something when another object references them
+const ORPHAN_TYPES: &[ObjectType] = &[
+    ObjectType::FontAttributes,
+    ObjectType::LineAttributes,
+    ObjectType::FillAttributes,
+    ObjectType::InputAttributes,
+    ObjectType::ExtendedInputAttributes,
+    ObjectType::NumberVariable,
+    ObjectType::StringVariable,
+];
+
+/// An attribute or variable object that no object references
+#[derive(Debug, Clone, PartialEq)]
+pub struct OrphanObject {
+    pub id: ObjectId,
+    pub object_type: ObjectType,
+
+    /// Bytes the object takes in the pool
+    pub size: usize,
+}
+
+/// Attribute and variable objects that are not referenced by any object or macro command.
+/// Unlike the unused objects of the minified export, objects referenced by unused objects are
+/// not listed, so deleting them never breaks a reference.
+pub fn orphan_attributes(pool: &ObjectPool) -> Vec<OrphanObject> {
+    let referenced: HashSet<ObjectId> = pool
+        .objects()
+        .iter()
+        .flat_map(|object| object.referenced_objects())
+        .chain(macro_targets(pool).into_iter().map(|target| target.object))
+        .collect();
+    pool.objects_by_types(ORPHAN_TYPES)
+        .into_iter()
+        .filter(|object| !referenced.contains(&object.id()))
+        .map(|object| OrphanObject {
+            id: object.id(),
+            object_type: object.object_type(),
+            size: object.write().len(),
+        })
+        .collect()
+}
+
+/// Delete the objects, locked objects are kept. All deletions are recorded as a single bulk
+/// change. Returns the number of deleted objects
+pub fn delete_orphans(project: &EditorProject, ids: &[ObjectId]) -> usize {
+    let mut pool = project.get_mut_pool().borrow_mut();
+    let mut deleted = 0;
+    for id in ids.iter().filter(|id| !project.is_locked(**id)) {
+        if pool.object_by_id(*id).is_some() {
+            pool.remove(*id);
+            deleted += 1;
+        }
+    }
+    if deleted > 0 {
+        project.describe_change(
+            format!("Delete {} orphan objects", deleted),
+            ChangeCategory::BulkChange,
+        );
+    }
+    deleted
+}
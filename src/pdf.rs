@@ -0,0 +1,301 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Minimal PDF writer for documents of text, lines and images, using the standard Helvetica fonts
+//! that every PDF reader has, so no fonts need to be embedded.
+
+use std::fmt::Write;
+
+/// Width and height of an A4 page in points
+pub const A4: (f32, f32) = (595.0, 842.0);
+
+/// An image on one or more pages, stored as JPEG which PDF readers decode natively
+struct PdfImage {
+    width: u32,
+    height: u32,
+    jpeg: Vec<u8>,
+}
+
+#[derive(Default)]
+struct PdfPage {
+    content: String,
+    images: Vec<usize>,
+}
+
+/// A document of A4 pages. Positions are in points from the top left corner of the page.
+#[derive(Default)]
+pub struct PdfDocument {
+    pages: Vec<PdfPage>,
+    images: Vec<PdfImage>,
+    current: usize,
+}
+
+/// Text as a PDF string in the WinAnsi encoding, characters it does not have become `?`
+fn pdf_string(text: &str) -> String {
+    let mut string = String::from("(");
+    for c in text.chars() {
+        match c {
+            '(' | ')' | '\\' => {
+                string.push('\\');
+                string.push(c);
+            }
+            ' '..='~' => string.push(c),
+            '\u{A0}'..='\u{FF}' => {
+                let _ = write!(string, "\\{:03o}", c as u32);
+            }
+            _ => string.push('?'),
+        }
+    }
+    string.push(')');
+    string
+}
+
+/// Approximate width of text in Helvetica, for wrapping and alignment
+pub fn text_width(text: &str, size: f32) -> f32 {
+    text.chars().count() as f32 * size * 0.5
+}
+
+/// Split text into lines that fit in `width` at the font size
+pub fn wrap_text(text: &str, size: f32, width: f32) -> Vec<String> {
+    let max_chars = ((width / (size * 0.5)) as usize).max(1);
+    let mut lines = Vec::new();
+    for paragraph in text.lines() {
+        let mut line = String::new();
+        for word in paragraph.split_whitespace() {
+            if !line.is_empty() && line.chars().count() + 1 + word.chars().count() > max_chars {
+                lines.push(std::mem::take(&mut line));
+            }
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(word);
+        }
+        lines.push(line);
+    }
+    lines
+}
+
+impl PdfDocument {
+    /// Start a new page, following drawing goes to it
+    pub fn add_page(&mut self) {
+        self.pages.push(PdfPage::default());
+        self.current = self.pages.len() - 1;
+    }
+
+    pub fn page_count(&self) -> usize {
+        self.pages.len()
+    }
+
+    /// Draw on an earlier page, e.g. to add page numbers when the page count is known
+    pub fn select_page(&mut self, index: usize) {
+        self.current = index.min(self.pages.len().saturating_sub(1));
+    }
+
+    fn page(&mut self) -> &mut PdfPage {
+        if self.pages.is_empty() {
+            self.add_page();
+        }
+        &mut self.pages[self.current]
+    }
+
+    /// Draw text with its baseline at `y`
+    pub fn text(&mut self, x: f32, y: f32, size: f32, bold: bool, text: &str) {
+        let font = if bold { "F2" } else { "F1" };
+        let y = A4.1 - y;
+        let text = pdf_string(text);
+        let _ = writeln!(
+            self.page().content,
+            "BT /{} {} Tf {} {} Td {} Tj ET",
+            font,
+            size,
+            x,
+            y,
+            text
+        );
+    }
+
+    /// Colour of the following text and lines
+    pub fn colour(&mut self, rgb: [u8; 3]) {
+        let [r, g, b] = rgb.map(|channel| channel as f32 / 255.0);
+        let _ = writeln!(self.page().content, "{r} {g} {b} RG {r} {g} {b} rg");
+    }
+
+    pub fn line(&mut self, from: (f32, f32), to: (f32, f32), width: f32) {
+        let _ = writeln!(
+            self.page().content,
+            "{} w {} {} m {} {} l S",
+            width,
+            from.0,
+            A4.1 - from.1,
+            to.0,
+            A4.1 - to.1
+        );
+    }
+
+    /// Draw an image of RGB pixels scaled to `size`
+    pub fn image(
+        &mut self,
+        rgb: &[u8],
+        pixels: (u32, u32),
+        position: (f32, f32),
+        size: (f32, f32),
+    ) -> Result<(), String> {
+        let mut jpeg = Vec::new();
+        image::codecs::jpeg::JpegEncoder::new_with_quality(&mut jpeg, 90)
+            .encode(rgb, pixels.0, pixels.1, image::ExtendedColorType::Rgb8)
+            .map_err(|e| format!("Failed to encode the image: {}", e))?;
+        self.images.push(PdfImage {
+            width: pixels.0,
+            height: pixels.1,
+            jpeg,
+        });
+        let index = self.images.len() - 1;
+        let page = self.page();
+        page.images.push(index);
+        let _ = writeln!(
+            page.content,
+            "q {} 0 0 {} {} {} cm /Im{} Do Q",
+            size.0,
+            size.1,
+            position.0,
+            A4.1 - position.1 - size.1,
+            index
+        );
+        Ok(())
+    }
+
+    /// The document as the bytes of a PDF file
+    pub fn to_bytes(&self) -> Vec<u8> {
+        // Objects: catalog, page tree, two fonts, the images, then a page and content per page
+        let first_image = 5;
+        let first_page = first_image + self.images.len();
+        let mut objects: Vec<Vec<u8>> = Vec::new();
+        let kids: Vec<String> = (0..self.pages.len())
+            .map(|index| format!("{} 0 R", first_page + 2 * index))
+            .collect();
+        objects.push(b"<< /Type /Catalog /Pages 2 0 R >>".to_vec());
+        objects.push(
+            format!(
+                "<< /Type /Pages /Kids [{}] /Count {} >>",
+                kids.join(" "),
+                self.pages.len()
+            )
+            .into_bytes(),
+        );
+        for font in ["Helvetica", "Helvetica-Bold"] {
+            objects.push(
+                format!(
+                    "<< /Type /Font /Subtype /Type1 /BaseFont /{} /Encoding /WinAnsiEncoding >>",
+                    font
+                )
+                .into_bytes(),
+            );
+        }
+        for image in &self.images {
+            let mut object = format!(
+                "<< /Type /XObject /Subtype /Image /Width {} /Height {} /ColorSpace /DeviceRGB \
+                 /BitsPerComponent 8 /Filter /DCTDecode /Length {} >>\nstream\n",
+                image.width,
+                image.height,
+                image.jpeg.len()
+            )
+            .into_bytes();
+            object.extend_from_slice(&image.jpeg);
+            object.extend_from_slice(b"\nendstream");
+            objects.push(object);
+        }
+        for (index, page) in self.pages.iter().enumerate() {
+            let images: String = page
+                .images
+                .iter()
+                .map(|image| format!("/Im{} {} 0 R ", image, first_image + image))
+                .collect();
+            objects.push(
+                format!(
+                    "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {} {}] /Resources << /Font << \
+                     /F1 3 0 R /F2 4 0 R >> /XObject << {}>> >> /Contents {} 0 R >>",
+                    A4.0,
+                    A4.1,
+                    images,
+                    first_page + 2 * index + 1
+                )
+                .into_bytes(),
+            );
+            objects.push(
+                format!(
+                    "<< /Length {} >>\nstream\n{}endstream",
+                    page.content.len(),
+                    page.content
+                )
+                .into_bytes(),
+            );
+        }
+
+        let mut bytes = b"%PDF-1.4\n".to_vec();
+        let mut offsets = Vec::new();
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(bytes.len());
+            bytes.extend_from_slice(format!("{} 0 obj\n", index + 1).as_bytes());
+            bytes.extend_from_slice(object);
+            bytes.extend_from_slice(b"\nendobj\n");
+        }
+        let xref = bytes.len();
+        let mut trailer = format!("xref\n0 {}\n0000000000 65535 f \n", objects.len() + 1);
+        for offset in offsets {
+            let _ = writeln!(trailer, "{:010} 00000 n ", offset);
+        }
+        let _ = write!(
+            trailer,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{}\n%%EOF\n",
+            objects.len() + 1,
+            xref
+        );
+        bytes.extend_from_slice(trailer.as_bytes());
+        bytes
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strings_are_escaped() {
+        assert_eq!(pdf_string("a (b) \\"), "(a \\(b\\) \\\\)");
+        assert_eq!(pdf_string("5 °C → 6"), "(5 \\260C ? 6)");
+    }
+
+    #[test]
+    fn text_is_wrapped_at_words() {
+        assert_eq!(
+            wrap_text("one two three\n\nfour", 10.0, 50.0),
+            vec!["one two", "three", "", "four"]
+        );
+    }
+
+    #[test]
+    fn cross_references_point_at_the_objects() {
+        let mut document = PdfDocument::default();
+        document.text(10.0, 20.0, 12.0, false, "Page 1");
+        document.add_page();
+        document.line((0.0, 0.0), (10.0, 10.0), 1.0);
+        let bytes = document.to_bytes();
+        let text = String::from_utf8(bytes).unwrap();
+        assert!(text.starts_with("%PDF-1.4\n"));
+        assert!(text.contains("/Count 2"));
+
+        let xref = text.rfind("\nxref\n").unwrap();
+        let offsets: Vec<usize> = text[xref + 1..]
+            .lines()
+            .skip(3)
+            .take_while(|line| line.ends_with(" n "))
+            .map(|line| line[..10].parse().unwrap())
+            .collect();
+        // Catalog, page tree, two fonts and a page with its content per page
+        assert_eq!(offsets.len(), 8);
+        for (index, offset) in offsets.into_iter().enumerate() {
+            assert!(text[offset..].starts_with(&format!("{} 0 obj\n", index + 1)));
+        }
+    }
+}
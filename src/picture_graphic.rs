@@ -0,0 +1,650 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::HashMap;
+
+use ag_iso_stack::object_pool::object::{Object, PictureGraphic};
+use ag_iso_stack::object_pool::object_attributes::{DataCodeType, PictureGraphicFormat};
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+use eframe::egui::{Color32, ColorImage};
+use image::DynamicImage;
+
+use crate::object_rendering::Colorable;
+use crate::{default_object, ChangeCategory, EditorProject, ObjectInfo};
+
+/// Palette index used for transparent pixels of imported images, not used by the colour cube
+pub const DEFAULT_TRANSPARENCY_COLOUR: u8 = 1;
+
+/// The 16 standard VT colours, the only ones a 4 bit picture can use
+const STANDARD_COLOURS: [(u8, u8, u8); 16] = [
+    (0, 0, 0),
+    (255, 255, 255),
+    (0, 153, 0),
+    (0, 153, 153),
+    (153, 0, 0),
+    (153, 0, 153),
+    (153, 153, 0),
+    (204, 204, 204),
+    (153, 153, 153),
+    (0, 0, 255),
+    (0, 255, 0),
+    (0, 255, 255),
+    (255, 0, 0),
+    (255, 0, 255),
+    (255, 255, 0),
+    (0, 0, 153),
+];
+
+/// Find the closest colour of the 6x6x6 colour cube (indices 16 to 231) for an RGB value
+pub fn find_closest_color_index(r: u8, g: u8, b: u8) -> u8 {
+    fn quantize_channel(c: u8) -> u8 {
+        // ((c + 25) / 51) in integer math, capped to 0..5
+        let v = (c as u16 + 25) / 51;
+        v.min(5) as u8
+    }
+    let rq = quantize_channel(r);
+    let gq = quantize_channel(g);
+    let bq = quantize_channel(b);
+
+    16 + 36 * rq + 6 * gq + bq
+}
+
+/// Number of palette indices a picture of the format can use
+fn colour_count(format: PictureGraphicFormat) -> usize {
+    match format {
+        PictureGraphicFormat::Monochrome => 2,
+        PictureGraphicFormat::FourBit => 16,
+        PictureGraphicFormat::EightBit => 256,
+    }
+}
+
+/// Squared distance between two RGB colours
+fn colour_distance((r1, g1, b1): (u8, u8, u8), (r2, g2, b2): (u8, u8, u8)) -> u32 {
+    let channel = |x: u8, y: u8| (x as i32 - y as i32).pow(2) as u32;
+    channel(r1, r2) + channel(g1, g2) + channel(b1, b2)
+}
+
+/// Index of the colour closest to an RGB value
+fn nearest_index(colours: &[(u8, u8, u8)], rgb: (u8, u8, u8)) -> u8 {
+    (0..colours.len())
+        .min_by_key(|index| colour_distance(colours[*index], rgb))
+        .unwrap_or(0) as u8
+}
+
+/// Closest palette index of an RGB value that a picture of the format can show
+fn quantize(format: PictureGraphicFormat, r: u8, g: u8, b: u8) -> u8 {
+    match format {
+        PictureGraphicFormat::EightBit => find_closest_color_index(r, g, b),
+        _ => nearest_index(&STANDARD_COLOURS[..colour_count(format)], (r, g, b)),
+    }
+}
+
+/// Number of bytes of a row of pixels, every row starts on a new byte
+fn row_size(format: PictureGraphicFormat, width: usize) -> usize {
+    match format {
+        PictureGraphicFormat::Monochrome => width.div_ceil(8),
+        PictureGraphicFormat::FourBit => width.div_ceil(2),
+        PictureGraphicFormat::EightBit => width,
+    }
+}
+
+/// Pack palette indices of a row into bytes, every row starts on a new byte
+fn pack_row(format: PictureGraphicFormat, row: &[u8], packed: &mut Vec<u8>) {
+    match format {
+        PictureGraphicFormat::EightBit => packed.extend_from_slice(row),
+        PictureGraphicFormat::FourBit => {
+            for pair in row.chunks(2) {
+                let low = pair.get(1).copied().unwrap_or(0);
+                packed.push((pair[0] & 0x0F) << 4 | (low & 0x0F));
+            }
+        }
+        PictureGraphicFormat::Monochrome => {
+            for bits in row.chunks(8) {
+                let byte = bits
+                    .iter()
+                    .enumerate()
+                    .fold(0u8, |byte, (bit, index)| byte | (index & 0x01) << (7 - bit));
+                packed.push(byte);
+            }
+        }
+    }
+}
+
+/// Run-length encode data as pairs of count and value
+pub fn run_length_encode(raw: &[u8]) -> Vec<u8> {
+    let mut rle = Vec::new();
+    let mut iter = raw.iter().copied().peekable();
+    while let Some(value) = iter.next() {
+        let mut count: u8 = 1;
+        while count < u8::MAX && iter.peek() == Some(&value) {
+            iter.next();
+            count += 1;
+        }
+        rle.push(count);
+        rle.push(value);
+    }
+    rle
+}
+
+/// Store packed data in a picture graphic with the given encoding, or the smaller one if None
+fn store_data(picture: &mut PictureGraphic, raw: Vec<u8>, encoding: Option<DataCodeType>) {
+    let rle = run_length_encode(&raw);
+    let encoding = encoding.unwrap_or(if rle.len() < raw.len() {
+        DataCodeType::RunLength
+    } else {
+        DataCodeType::Raw
+    });
+    log::info!(
+        "Selected {:?} encoding ({} bytes raw, {} bytes run-length)",
+        encoding,
+        raw.len(),
+        rle.len()
+    );
+    picture.data = match encoding {
+        DataCodeType::RunLength => rle,
+        DataCodeType::Raw => raw,
+    };
+    picture.options.data_code_type = encoding;
+}
+
+/// Replace the pixels of a picture graphic without scaling, keeping its format
+pub fn set_picture_pixels(picture: &mut PictureGraphic, width: u16, height: u16, pixels: &[u8]) {
+    let mut raw = Vec::new();
+    for row in pixels.chunks(width.max(1) as usize).take(height as usize) {
+        pack_row(picture.format, row, &mut raw);
+    }
+    picture.width = width;
+    picture.actual_width = width;
+    picture.actual_height = height;
+    store_data(picture, raw, None);
+}
+
+/// Store the data of a picture graphic with the smaller encoding.
+/// Returns the number of bytes saved.
+pub fn compress_picture(picture: &mut PictureGraphic) -> usize {
+    let original = picture.clone();
+    store_data(picture, picture.data_as_raw_encoded(), None);
+    if picture.data.len() >= original.data.len() {
+        *picture = original;
+        return 0;
+    }
+    original.data.len() - picture.data.len()
+}
+
+/// Palette index of every pixel of a picture graphic, row by row
+pub fn picture_pixels(picture: &PictureGraphic) -> Vec<u8> {
+    let width = picture.actual_width as usize;
+    let height = picture.actual_height as usize;
+    let raw = picture.data_as_raw_encoded();
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in raw
+        .chunks(row_size(picture.format, width).max(1))
+        .take(height)
+    {
+        for x in 0..width {
+            let index = match picture.format {
+                PictureGraphicFormat::Monochrome => {
+                    row.get(x / 8).map(|byte| (byte >> (7 - x % 8)) & 0x01)
+                }
+                PictureGraphicFormat::FourBit => row
+                    .get(x / 2)
+                    .map(|byte| (byte >> (4 - 4 * (x % 2))) & 0x0F),
+                PictureGraphicFormat::EightBit => row.get(x).copied(),
+            };
+            pixels.push(index.unwrap_or(0));
+        }
+    }
+    pixels.resize(width * height, 0);
+    pixels
+}
+
+/// Convert a picture graphic to another format and encoding. Colours the new format can not
+/// show are replaced by the closest colour of the palette of the pool.
+pub fn convert_picture(
+    picture: &PictureGraphic,
+    pool: &ObjectPool,
+    format: PictureGraphicFormat,
+    encoding: DataCodeType,
+) -> PictureGraphic {
+    let count = colour_count(format);
+    let colours: Vec<(u8, u8, u8)> = (0..count)
+        .map(|index| {
+            let colour = pool.color_by_index(index as u8);
+            (colour.r, colour.g, colour.b)
+        })
+        .collect();
+    let mut mapping = [0u8; 256];
+    for (index, mapped) in mapping.iter_mut().enumerate() {
+        *mapped = if index < count {
+            index as u8
+        } else {
+            let colour = pool.color_by_index(index as u8);
+            nearest_index(&colours, (colour.r, colour.g, colour.b))
+        };
+    }
+
+    let mut converted = picture.clone();
+    converted.format = format;
+    converted.transparency_colour = mapping[picture.transparency_colour as usize];
+    let width = picture.actual_width as usize;
+    let mut raw = Vec::new();
+    for row in picture_pixels(picture).chunks(width.max(1)) {
+        let indices: Vec<u8> = row.iter().map(|index| mapping[*index as usize]).collect();
+        pack_row(format, &indices, &mut raw);
+    }
+    store_data(&mut converted, raw, Some(encoding));
+    converted
+}
+
+/// Mean distance per pixel between the colours of two pictures of the same size
+pub fn conversion_error(pool: &ObjectPool, before: &PictureGraphic, after: &PictureGraphic) -> f32 {
+    let rgb = |index: u8| {
+        let colour = pool.color_by_index(index);
+        (colour.r, colour.g, colour.b)
+    };
+    let before = picture_pixels(before);
+    let after = picture_pixels(after);
+    if before.is_empty() {
+        return 0.0;
+    }
+    let total: f32 = before
+        .iter()
+        .zip(&after)
+        .map(|(a, b)| (colour_distance(rgb(*a), rgb(*b)) as f32).sqrt())
+        .sum();
+    total / before.len() as f32
+}
+
+/// The 256 colours of the pool by index
+pub fn picture_palette(pool: &ObjectPool) -> Vec<Color32> {
+    (0..=u8::MAX)
+        .map(|index| pool.color_by_index(index).convert())
+        .collect()
+}
+
+/// Image of a picture graphic in the colours of the pool, for previews
+pub fn picture_image(picture: &PictureGraphic, pool: &ObjectPool) -> ColorImage {
+    palette_image(picture, &picture_palette(pool))
+}
+
+/// Image of a picture graphic in the colours of a palette from [`picture_palette`], which does
+/// not need the pool so it can be made on another thread
+pub fn palette_image(picture: &PictureGraphic, palette: &[Color32]) -> ColorImage {
+    let pixels = picture_pixels(picture)
+        .into_iter()
+        .map(|index| {
+            if picture.options.transparent && index == picture.transparency_colour {
+                Color32::TRANSPARENT
+            } else {
+                palette[index as usize]
+            }
+        })
+        .collect();
+    let size = [
+        picture.actual_width as usize,
+        picture.actual_height as usize,
+    ];
+    ColorImage::new(size, pixels)
+}
+
+/// Set the image of a picture graphic in the given format. Pixels that are fully transparent get
+/// the transparency colour when transparency is enabled. The smaller of the raw and the
+/// run-length encoded data is stored.
+pub fn set_picture_image(
+    picture: &mut PictureGraphic,
+    image: &DynamicImage,
+    format: PictureGraphicFormat,
+) -> Result<(), String> {
+    let (width, height) = (image.width(), image.height());
+    if width > u16::MAX as u32 || height > u16::MAX as u32 {
+        return Err(format!(
+            "Image dimensions exceed maximum size of {}x{}",
+            u16::MAX,
+            u16::MAX
+        ));
+    }
+
+    let rgba = image.to_rgba8();
+    let mut raw = Vec::new();
+    for row in rgba.rows() {
+        let indices: Vec<u8> = row
+            .map(|p| {
+                if p[3] == 0 && picture.options.transparent {
+                    picture.transparency_colour
+                } else {
+                    quantize(format, p[0], p[1], p[2])
+                }
+            })
+            .collect();
+        pack_row(format, &indices, &mut raw);
+    }
+
+    picture.actual_width = width as u16;
+    picture.actual_height = height as u16;
+    if picture.width == 0 {
+        picture.width = picture.actual_width;
+    }
+    picture.format = format;
+    store_data(picture, raw, None);
+    Ok(())
+}
+
+/// Create an 8 bit picture graphic from an image, e.g. one pasted from the clipboard, with the
+/// same conversion as imported images. Returns the ID of the new object.
+pub fn create_picture_graphic(
+    project: &EditorProject,
+    image: &DynamicImage,
+    name: &str,
+) -> Result<ObjectId, String> {
+    let id = project.allocate_object_id();
+    let mut object = default_object(ObjectType::PictureGraphic);
+    object.mut_id().set_value(id.value()).ok();
+    let Object::PictureGraphic(picture) = &mut object else {
+        return Err("Failed to create a picture graphic".to_string());
+    };
+    picture.width = 0;
+    picture.transparency_colour = DEFAULT_TRANSPARENCY_COLOUR;
+    picture.options.transparent = false;
+    set_picture_image(picture, image, PictureGraphicFormat::EightBit)?;
+
+    project
+        .object_info
+        .borrow_mut()
+        .entry(id)
+        .or_insert_with(|| ObjectInfo::new(&object))
+        .set_name(name.to_string());
+    project.get_mut_pool().borrow_mut().add(object);
+    project.describe_change(format!("Paste image as {}", name), ChangeCategory::Edit);
+    Ok(id)
+}
+
+/// Settings of an image in the manifest of a batch import
+#[derive(Debug, Default, Clone)]
+struct ManifestEntry {
+    id: Option<u16>,
+    name: Option<String>,
+    format: Option<PictureGraphicFormat>,
+    /// None to keep the default, Some(None) to disable transparency
+    transparency: Option<Option<u8>>,
+}
+
+/// Split CSV text into rows of cells. Quoted cells can contain commas, line breaks and quotes
+/// written as two quotes. Returns the line number every row starts on.
+fn parse_csv(text: &str) -> Vec<(usize, Vec<String>)> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut cell = String::new();
+    let (mut quoted, mut was_quoted) = (false, false);
+    let (mut line, mut row_line) = (1, 1);
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                chars.next();
+                cell.push('"');
+            }
+            '"' if quoted => quoted = false,
+            '"' if cell.trim().is_empty() && !was_quoted => {
+                cell.clear();
+                quoted = true;
+                was_quoted = true;
+            }
+            ',' if !quoted => {
+                row.push(finish_cell(&mut cell, was_quoted));
+                was_quoted = false;
+            }
+            '\n' if !quoted => {
+                row.push(finish_cell(&mut cell, was_quoted));
+                was_quoted = false;
+                if row.iter().any(|cell| !cell.is_empty()) {
+                    rows.push((row_line, std::mem::take(&mut row)));
+                }
+                row.clear();
+                line += 1;
+                row_line = line;
+            }
+            '\r' if !quoted => {}
+            c => {
+                if c == '\n' {
+                    line += 1;
+                }
+                cell.push(c);
+            }
+        }
+    }
+    row.push(finish_cell(&mut cell, was_quoted));
+    if row.iter().any(|cell| !cell.is_empty()) {
+        rows.push((row_line, row));
+    }
+    rows
+}
+
+/// Unquoted cells are trimmed, the content of quoted cells is kept as it is
+fn finish_cell(cell: &mut String, quoted: bool) -> String {
+    let value = std::mem::take(cell);
+    if quoted {
+        value
+    } else {
+        value.trim().to_string()
+    }
+}
+
+/// Parse a manifest with the columns file, id, name, format (mono, 4bit, 8bit) and transparency
+/// (a colour index, or "none"). The first row is a header, empty cells keep the defaults.
+fn parse_manifest(text: &str) -> Result<HashMap<String, ManifestEntry>, String> {
+    let mut rows = parse_csv(text).into_iter();
+    let header: Vec<String> = rows
+        .next()
+        .ok_or("The manifest is empty")?
+        .1
+        .iter()
+        .map(|column| column.trim().to_lowercase())
+        .collect();
+    let column = |name: &str| header.iter().position(|c| c == name);
+    let file_column = column("file").ok_or("The manifest has no 'file' column")?;
+
+    let mut entries = HashMap::new();
+    for (line_number, cells) in rows {
+        let cell = |name: &str| {
+            column(name)
+                .and_then(|index| cells.get(index))
+                .map(String::as_str)
+                .filter(|cell| !cell.is_empty())
+        };
+        let error = |message: String| format!("Manifest line {}: {}", line_number, message);
+
+        let Some(file) = cells.get(file_column).filter(|f| !f.is_empty()) else {
+            continue;
+        };
+        let id = match cell("id") {
+            Some(id) => Some(
+                id.parse::<u16>()
+                    .map_err(|_| error(format!("invalid object ID '{}'", id)))?,
+            ),
+            None => None,
+        };
+        let format = match cell("format").map(|f| f.to_lowercase()) {
+            None => None,
+            Some(f) if f == "mono" || f == "monochrome" || f == "1bit" => {
+                Some(PictureGraphicFormat::Monochrome)
+            }
+            Some(f) if f == "4bit" => Some(PictureGraphicFormat::FourBit),
+            Some(f) if f == "8bit" => Some(PictureGraphicFormat::EightBit),
+            Some(f) => return Err(error(format!("unknown format '{}'", f))),
+        };
+        let transparency = match cell("transparency") {
+            None => None,
+            Some(t) if t.eq_ignore_ascii_case("none") => Some(None),
+            Some(t) => {
+                Some(Some(t.parse::<u8>().map_err(|_| {
+                    error(format!("invalid transparency colour '{}'", t))
+                })?))
+            }
+        };
+        entries.insert(
+            file.to_string(),
+            ManifestEntry {
+                id,
+                name: cell("name").map(str::to_string),
+                format,
+                transparency,
+            },
+        );
+    }
+    Ok(entries)
+}
+
+/// Create or update a picture graphic for every image, as a single change of the project.
+/// A CSV file among the files is used as manifest. Without an entry in the manifest an image
+/// updates the picture graphic named like the file, or creates a new one.
+/// Returns a line for every file describing what happened.
+pub fn import_images(
+    project: &EditorProject,
+    files: &[(String, Vec<u8>)],
+) -> Result<Vec<String>, String> {
+    let manifest = match files
+        .iter()
+        .find(|(name, _)| name.to_lowercase().ends_with(".csv"))
+    {
+        Some((_, content)) => parse_manifest(&String::from_utf8_lossy(content))?,
+        None => HashMap::new(),
+    };
+
+    let mut log = Vec::new();
+    let mut imported = 0;
+    for (file_name, content) in files {
+        if file_name.to_lowercase().ends_with(".csv") {
+            continue;
+        }
+        let entry = manifest.get(file_name).cloned().unwrap_or_default();
+        let stem = file_name
+            .rsplit_once('.')
+            .map_or(file_name.as_str(), |(stem, _)| stem);
+        let name = entry.name.clone().unwrap_or_else(|| stem.to_string());
+
+        let image = match image::load_from_memory(content) {
+            Ok(image) => image,
+            Err(e) => {
+                log.push(format!("{}: failed to decode image: {}", file_name, e));
+                continue;
+            }
+        };
+
+        // The object to update: by ID from the manifest, otherwise by name
+        let existing = match entry.id {
+            Some(id) => ObjectId::new(id)
+                .ok()
+                .filter(|id| project.get_mut_pool().borrow().object_by_id(*id).is_some()),
+            None => project
+                .get_mut_pool()
+                .borrow()
+                .objects_by_type(ObjectType::PictureGraphic)
+                .into_iter()
+                .find(|o| project.get_object_info(o).get_name(o) == name)
+                .map(|o| o.id()),
+        };
+        let (id, created) = match existing {
+            Some(id) => (id, false),
+            None => {
+                let id = match entry.id.and_then(|id| ObjectId::new(id).ok()) {
+                    Some(id) => id,
+                    None => project.allocate_object_id(),
+                };
+                let mut picture = default_object(ObjectType::PictureGraphic);
+                picture.mut_id().set_value(id.value()).ok();
+                project.get_mut_pool().borrow_mut().add(picture);
+                (id, true)
+            }
+        };
+
+        let mut pool = project.get_mut_pool().borrow_mut();
+        let Some(Object::PictureGraphic(picture)) = pool.object_mut_by_id(id) else {
+            log.push(format!(
+                "{}: object {} is not a picture graphic",
+                file_name,
+                id.value()
+            ));
+            continue;
+        };
+        // Index 1 is white in 4 bit and monochrome pictures, so transparency is opt-in
+        if created {
+            picture.width = 0;
+            picture.transparency_colour = DEFAULT_TRANSPARENCY_COLOUR;
+            picture.options.transparent = false;
+        }
+        match entry.transparency {
+            Some(Some(colour)) => {
+                picture.transparency_colour = colour;
+                picture.options.transparent = true;
+            }
+            Some(None) => picture.options.transparent = false,
+            None => {}
+        }
+        let format = entry.format.unwrap_or(if created {
+            PictureGraphicFormat::EightBit
+        } else {
+            picture.format
+        });
+        if let Err(e) = set_picture_image(picture, &image, format) {
+            log.push(format!("{}: {}", file_name, e));
+            continue;
+        }
+        let picture = Object::PictureGraphic(picture.clone());
+        drop(pool);
+
+        if created {
+            let mut object_info = project.object_info.borrow_mut();
+            object_info
+                .entry(id)
+                .or_insert_with(|| ObjectInfo::new(&picture))
+                .set_name(name.clone());
+        }
+        log.push(format!(
+            "{}: {} picture graphic {} '{}'",
+            file_name,
+            if created { "created" } else { "updated" },
+            id.value(),
+            name
+        ));
+        imported += 1;
+    }
+
+    if imported > 0 {
+        project.describe_change(
+            format!("Batch import of {} images", imported),
+            ChangeCategory::BulkChange,
+        );
+    }
+    Ok(log)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn manifest_cells_can_be_quoted() {
+        let manifest = parse_manifest(
+            "file,id,name,transparency\n\
+             \"arrow, left.png\",5000,\"Arrow \"\"left\"\"\",none\n\
+             \n\
+             logo.png, 5001 ,\"Logo\nnew\",3\n",
+        )
+        .unwrap();
+        let arrow = &manifest["arrow, left.png"];
+        assert_eq!(arrow.id, Some(5000));
+        assert_eq!(arrow.name.as_deref(), Some("Arrow \"left\""));
+        assert_eq!(arrow.transparency, Some(None));
+        let logo = &manifest["logo.png"];
+        assert_eq!(logo.id, Some(5001));
+        assert_eq!(logo.name.as_deref(), Some("Logo\nnew"));
+        assert_eq!(logo.transparency, Some(Some(3)));
+    }
+
+    #[test]
+    fn manifest_errors_name_the_line() {
+        let error = parse_manifest("file,id\n\"a\nb.png\",1\nc.png,x\n").unwrap_err();
+        assert_eq!(error, "Manifest line 4: invalid object ID 'x'");
+    }
+}
@@ -0,0 +1,402 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! A built-in pixel font for the preview. Many terminals draw text with a 5x7 dot matrix font in
+//! a 6x8 cell, so the glyphs below are turned into a TrueType font with one square per dot, which
+//! egui renders like any other font.
+
+/// Name of the pixel font in the font mapping of terminal profiles
+pub const PIXEL_FONT_NAME: &str = "Built-in pixel font";
+
+/// Size of a dot in font units, a cell of 8 dots high is one em
+const DOT: i16 = 128;
+
+/// Width of a cell: 5 dots of the glyph and 1 dot of spacing
+const ADVANCE: u16 = 6 * DOT as u16;
+
+/// Rows of the glyphs from top to bottom, with the leftmost dot in bit 4. The baseline is below
+/// row 6, row 7 holds the descenders.
+const GLYPHS: &[(char, [u8; 8])] = &[
+    (' ', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('!', [0x04, 0x04, 0x04, 0x04, 0x04, 0x00, 0x04, 0x00]),
+    ('"', [0x0A, 0x0A, 0x0A, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('#', [0x0A, 0x0A, 0x1F, 0x0A, 0x1F, 0x0A, 0x0A, 0x00]),
+    ('$', [0x04, 0x0F, 0x14, 0x0E, 0x05, 0x1E, 0x04, 0x00]),
+    ('%', [0x18, 0x19, 0x02, 0x04, 0x08, 0x13, 0x03, 0x00]),
+    ('&', [0x0C, 0x12, 0x14, 0x08, 0x15, 0x12, 0x0D, 0x00]),
+    ('\'', [0x0C, 0x04, 0x08, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('(', [0x02, 0x04, 0x08, 0x08, 0x08, 0x04, 0x02, 0x00]),
+    (')', [0x08, 0x04, 0x02, 0x02, 0x02, 0x04, 0x08, 0x00]),
+    ('*', [0x00, 0x04, 0x15, 0x0E, 0x15, 0x04, 0x00, 0x00]),
+    ('+', [0x00, 0x04, 0x04, 0x1F, 0x04, 0x04, 0x00, 0x00]),
+    (',', [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x04, 0x08]),
+    ('-', [0x00, 0x00, 0x00, 0x1F, 0x00, 0x00, 0x00, 0x00]),
+    ('.', [0x00, 0x00, 0x00, 0x00, 0x00, 0x0C, 0x0C, 0x00]),
+    ('/', [0x00, 0x01, 0x02, 0x04, 0x08, 0x10, 0x00, 0x00]),
+    ('0', [0x0E, 0x11, 0x13, 0x15, 0x19, 0x11, 0x0E, 0x00]),
+    ('1', [0x04, 0x0C, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00]),
+    ('2', [0x0E, 0x11, 0x01, 0x02, 0x04, 0x08, 0x1F, 0x00]),
+    ('3', [0x1F, 0x02, 0x04, 0x02, 0x01, 0x11, 0x0E, 0x00]),
+    ('4', [0x02, 0x06, 0x0A, 0x12, 0x1F, 0x02, 0x02, 0x00]),
+    ('5', [0x1F, 0x10, 0x1E, 0x01, 0x01, 0x11, 0x0E, 0x00]),
+    ('6', [0x06, 0x08, 0x10, 0x1E, 0x11, 0x11, 0x0E, 0x00]),
+    ('7', [0x1F, 0x01, 0x02, 0x04, 0x08, 0x08, 0x08, 0x00]),
+    ('8', [0x0E, 0x11, 0x11, 0x0E, 0x11, 0x11, 0x0E, 0x00]),
+    ('9', [0x0E, 0x11, 0x11, 0x0F, 0x01, 0x02, 0x0C, 0x00]),
+    (':', [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x0C, 0x00, 0x00]),
+    (';', [0x00, 0x0C, 0x0C, 0x00, 0x0C, 0x04, 0x08, 0x00]),
+    ('<', [0x02, 0x04, 0x08, 0x10, 0x08, 0x04, 0x02, 0x00]),
+    ('=', [0x00, 0x00, 0x1F, 0x00, 0x1F, 0x00, 0x00, 0x00]),
+    ('>', [0x08, 0x04, 0x02, 0x01, 0x02, 0x04, 0x08, 0x00]),
+    ('?', [0x0E, 0x11, 0x01, 0x02, 0x04, 0x00, 0x04, 0x00]),
+    ('@', [0x0E, 0x11, 0x01, 0x0D, 0x15, 0x15, 0x0E, 0x00]),
+    ('A', [0x0E, 0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x00]),
+    ('B', [0x1E, 0x11, 0x11, 0x1E, 0x11, 0x11, 0x1E, 0x00]),
+    ('C', [0x0E, 0x11, 0x10, 0x10, 0x10, 0x11, 0x0E, 0x00]),
+    ('D', [0x1C, 0x12, 0x11, 0x11, 0x11, 0x12, 0x1C, 0x00]),
+    ('E', [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x1F, 0x00]),
+    ('F', [0x1F, 0x10, 0x10, 0x1E, 0x10, 0x10, 0x10, 0x00]),
+    ('G', [0x0E, 0x11, 0x10, 0x17, 0x11, 0x11, 0x0F, 0x00]),
+    ('H', [0x11, 0x11, 0x11, 0x1F, 0x11, 0x11, 0x11, 0x00]),
+    ('I', [0x0E, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00]),
+    ('J', [0x07, 0x02, 0x02, 0x02, 0x02, 0x12, 0x0C, 0x00]),
+    ('K', [0x11, 0x12, 0x14, 0x18, 0x14, 0x12, 0x11, 0x00]),
+    ('L', [0x10, 0x10, 0x10, 0x10, 0x10, 0x10, 0x1F, 0x00]),
+    ('M', [0x11, 0x1B, 0x15, 0x15, 0x11, 0x11, 0x11, 0x00]),
+    ('N', [0x11, 0x11, 0x19, 0x15, 0x13, 0x11, 0x11, 0x00]),
+    ('O', [0x0E, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00]),
+    ('P', [0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10, 0x10, 0x00]),
+    ('Q', [0x0E, 0x11, 0x11, 0x11, 0x15, 0x12, 0x0D, 0x00]),
+    ('R', [0x1E, 0x11, 0x11, 0x1E, 0x14, 0x12, 0x11, 0x00]),
+    ('S', [0x0F, 0x10, 0x10, 0x0E, 0x01, 0x01, 0x1E, 0x00]),
+    ('T', [0x1F, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00]),
+    ('U', [0x11, 0x11, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00]),
+    ('V', [0x11, 0x11, 0x11, 0x11, 0x11, 0x0A, 0x04, 0x00]),
+    ('W', [0x11, 0x11, 0x11, 0x15, 0x15, 0x15, 0x0A, 0x00]),
+    ('X', [0x11, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x11, 0x00]),
+    ('Y', [0x11, 0x11, 0x11, 0x0A, 0x04, 0x04, 0x04, 0x00]),
+    ('Z', [0x1F, 0x01, 0x02, 0x04, 0x08, 0x10, 0x1F, 0x00]),
+    ('[', [0x0E, 0x08, 0x08, 0x08, 0x08, 0x08, 0x0E, 0x00]),
+    ('\\', [0x00, 0x10, 0x08, 0x04, 0x02, 0x01, 0x00, 0x00]),
+    (']', [0x0E, 0x02, 0x02, 0x02, 0x02, 0x02, 0x0E, 0x00]),
+    ('^', [0x04, 0x0A, 0x11, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('_', [0x00, 0x00, 0x00, 0x00, 0x00, 0x00, 0x1F, 0x00]),
+    ('`', [0x08, 0x04, 0x02, 0x00, 0x00, 0x00, 0x00, 0x00]),
+    ('a', [0x00, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00]),
+    ('b', [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x1E, 0x00]),
+    ('c', [0x00, 0x00, 0x0E, 0x10, 0x10, 0x11, 0x0E, 0x00]),
+    ('d', [0x01, 0x01, 0x0D, 0x13, 0x11, 0x11, 0x0F, 0x00]),
+    ('e', [0x00, 0x00, 0x0E, 0x11, 0x1F, 0x10, 0x0E, 0x00]),
+    ('f', [0x06, 0x09, 0x08, 0x1C, 0x08, 0x08, 0x08, 0x00]),
+    ('g', [0x00, 0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x0E]),
+    ('h', [0x10, 0x10, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00]),
+    ('i', [0x04, 0x00, 0x0C, 0x04, 0x04, 0x04, 0x0E, 0x00]),
+    ('j', [0x02, 0x00, 0x06, 0x02, 0x02, 0x02, 0x12, 0x0C]),
+    ('k', [0x10, 0x10, 0x12, 0x14, 0x18, 0x14, 0x12, 0x00]),
+    ('l', [0x0C, 0x04, 0x04, 0x04, 0x04, 0x04, 0x0E, 0x00]),
+    ('m', [0x00, 0x00, 0x1A, 0x15, 0x15, 0x11, 0x11, 0x00]),
+    ('n', [0x00, 0x00, 0x16, 0x19, 0x11, 0x11, 0x11, 0x00]),
+    ('o', [0x00, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00]),
+    ('p', [0x00, 0x00, 0x1E, 0x11, 0x11, 0x1E, 0x10, 0x10]),
+    ('q', [0x00, 0x00, 0x0F, 0x11, 0x11, 0x0F, 0x01, 0x01]),
+    ('r', [0x00, 0x00, 0x16, 0x19, 0x10, 0x10, 0x10, 0x00]),
+    ('s', [0x00, 0x00, 0x0E, 0x10, 0x0E, 0x01, 0x1E, 0x00]),
+    ('t', [0x08, 0x08, 0x1C, 0x08, 0x08, 0x09, 0x06, 0x00]),
+    ('u', [0x00, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00]),
+    ('v', [0x00, 0x00, 0x11, 0x11, 0x11, 0x0A, 0x04, 0x00]),
+    ('w', [0x00, 0x00, 0x11, 0x11, 0x15, 0x15, 0x0A, 0x00]),
+    ('x', [0x00, 0x00, 0x11, 0x0A, 0x04, 0x0A, 0x11, 0x00]),
+    ('y', [0x00, 0x00, 0x11, 0x11, 0x11, 0x0F, 0x01, 0x0E]),
+    ('z', [0x00, 0x00, 0x1F, 0x02, 0x04, 0x08, 0x1F, 0x00]),
+    ('{', [0x02, 0x04, 0x04, 0x08, 0x04, 0x04, 0x02, 0x00]),
+    ('|', [0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x04, 0x00]),
+    ('}', [0x08, 0x04, 0x04, 0x02, 0x04, 0x04, 0x08, 0x00]),
+    ('~', [0x00, 0x00, 0x08, 0x15, 0x02, 0x00, 0x00, 0x00]),
+    ('\u{B0}', [0x0C, 0x12, 0x12, 0x0C, 0x00, 0x00, 0x00, 0x00]),
+    ('\u{C4}', [0x0A, 0x00, 0x0E, 0x11, 0x1F, 0x11, 0x11, 0x00]),
+    ('\u{D6}', [0x0A, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00]),
+    ('\u{DC}', [0x0A, 0x00, 0x11, 0x11, 0x11, 0x11, 0x0E, 0x00]),
+    ('\u{DF}', [0x0C, 0x12, 0x12, 0x1C, 0x12, 0x12, 0x1C, 0x10]),
+    ('\u{E4}', [0x0A, 0x00, 0x0E, 0x01, 0x0F, 0x11, 0x0F, 0x00]),
+    ('\u{F6}', [0x0A, 0x00, 0x0E, 0x11, 0x11, 0x11, 0x0E, 0x00]),
+    ('\u{FC}', [0x0A, 0x00, 0x11, 0x11, 0x11, 0x13, 0x0D, 0x00]),
+];
+
+/// Glyph for characters the font does not have: a hollow box
+const MISSING_GLYPH: [u8; 8] = [0x1F, 0x11, 0x11, 0x11, 0x11, 0x11, 0x1F, 0x00];
+
+/// A rectangle of dots as a clockwise contour
+type Contour = [(i16, i16); 4];
+
+/// One rectangle per horizontal run of dots
+fn glyph_contours(rows: &[u8; 8]) -> Vec<Contour> {
+    let mut contours = Vec::new();
+    for (row, bits) in rows.iter().enumerate() {
+        let bottom = (6 - row as i16) * DOT;
+        let top = bottom + DOT;
+        let dot = |column: i16| bits & (0x10 >> column) != 0;
+        let mut column = 0;
+        while column < 5 {
+            if !dot(column) {
+                column += 1;
+                continue;
+            }
+            let start = column;
+            while column < 5 && dot(column) {
+                column += 1;
+            }
+            let (left, right) = (start * DOT, column * DOT);
+            contours.push([(left, bottom), (left, top), (right, top), (right, bottom)]);
+        }
+    }
+    contours
+}
+
+/// A simple glyph of the glyf table, empty for glyphs without dots
+fn glyph_data(contours: &[Contour]) -> Vec<u8> {
+    let points = || contours.iter().flatten();
+    let Some(x_min) = points().map(|p| p.0).min() else {
+        return Vec::new();
+    };
+    let x_max = points().map(|p| p.0).max().unwrap_or(x_min);
+    let y_min = points().map(|p| p.1).min().unwrap_or(0);
+    let y_max = points().map(|p| p.1).max().unwrap_or(0);
+
+    let mut data = Vec::new();
+    for value in [contours.len() as i16, x_min, y_min, x_max, y_max] {
+        data.extend(value.to_be_bytes());
+    }
+    for index in 0..contours.len() {
+        data.extend((index as u16 * 4 + 3).to_be_bytes());
+    }
+    // No instructions, and every point is on the curve
+    data.extend(0u16.to_be_bytes());
+    data.extend(std::iter::repeat_n(0x01u8, contours.len() * 4));
+    for coordinate in [|p: &(i16, i16)| p.0, |p: &(i16, i16)| p.1] {
+        let mut previous = 0;
+        for point in points() {
+            data.extend((coordinate(point) - previous).to_be_bytes());
+            previous = coordinate(point);
+        }
+    }
+    while data.len() % 4 != 0 {
+        data.push(0);
+    }
+    data
+}
+
+/// Segments of a format 4 character map, of characters whose glyphs follow each other
+fn cmap_table() -> Vec<u8> {
+    let mut segments: Vec<(u16, u16, u16)> = Vec::new();
+    for (index, (character, _)) in GLYPHS.iter().enumerate() {
+        let (code, glyph) = (*character as u16, index as u16 + 1);
+        match segments.last_mut() {
+            Some((_, end, delta)) if *end + 1 == code && code.wrapping_add(*delta) == glyph => {
+                *end = code;
+            }
+            _ => segments.push((code, code, glyph.wrapping_sub(code))),
+        }
+    }
+    segments.push((0xFFFF, 0xFFFF, 1));
+
+    let count = segments.len() as u16;
+    let search_range = 2 * (1u16 << (15 - count.leading_zeros()));
+    let mut subtable = Vec::new();
+    for value in [
+        4,
+        16 + 8 * count,
+        0,
+        2 * count,
+        search_range,
+        search_range.trailing_zeros() as u16 - 1,
+        2 * count - search_range,
+    ] {
+        subtable.extend(value.to_be_bytes());
+    }
+    segments
+        .iter()
+        .for_each(|s| subtable.extend(s.1.to_be_bytes()));
+    subtable.extend(0u16.to_be_bytes());
+    segments
+        .iter()
+        .for_each(|s| subtable.extend(s.0.to_be_bytes()));
+    segments
+        .iter()
+        .for_each(|s| subtable.extend(s.2.to_be_bytes()));
+    segments
+        .iter()
+        .for_each(|_| subtable.extend(0u16.to_be_bytes()));
+
+    // One Windows Unicode BMP subtable
+    let mut table = Vec::new();
+    for value in [0u16, 1, 3, 1] {
+        table.extend(value.to_be_bytes());
+    }
+    table.extend(12u32.to_be_bytes());
+    table.extend(subtable);
+    table
+}
+
+fn checksum(data: &[u8]) -> u32 {
+    data.chunks(4).fold(0u32, |sum, chunk| {
+        let mut word = [0; 4];
+        word[..chunk.len()].copy_from_slice(chunk);
+        sum.wrapping_add(u32::from_be_bytes(word))
+    })
+}
+
+/// The TrueType font file of the pixel font
+pub fn pixel_font_data() -> Vec<u8> {
+    let glyphs: Vec<Vec<Contour>> = std::iter::once(&MISSING_GLYPH)
+        .chain(GLYPHS.iter().map(|(_, rows)| rows))
+        .map(glyph_contours)
+        .collect();
+    let glyph_count = glyphs.len() as u16;
+
+    let mut glyf = Vec::new();
+    let mut loca = Vec::new();
+    for contours in &glyphs {
+        loca.extend((glyf.len() as u32).to_be_bytes());
+        glyf.extend(glyph_data(contours));
+    }
+    loca.extend((glyf.len() as u32).to_be_bytes());
+
+    let mut hmtx = Vec::new();
+    for contours in &glyphs {
+        let left = contours.iter().flatten().map(|p| p.0).min().unwrap_or(0);
+        hmtx.extend(ADVANCE.to_be_bytes());
+        hmtx.extend(left.to_be_bytes());
+    }
+
+    let max_points = glyphs.iter().map(|c| c.len() * 4).max().unwrap_or(0) as u16;
+    let max_contours = glyphs.iter().map(|c| c.len()).max().unwrap_or(0) as u16;
+    let mut maxp = 0x0001_0000u32.to_be_bytes().to_vec();
+    for value in [glyph_count, max_points, max_contours, 0, 0, 2] {
+        maxp.extend(value.to_be_bytes());
+    }
+    maxp.extend([0; 18]);
+
+    let mut head = Vec::new();
+    head.extend(0x0001_0000u32.to_be_bytes());
+    head.extend(0x0001_0000u32.to_be_bytes());
+    head.extend(0u32.to_be_bytes()); // Checksum adjustment, set below
+    head.extend(0x5F0F_3CF5u32.to_be_bytes());
+    head.extend(0x000Bu16.to_be_bytes());
+    head.extend((8 * DOT as u16).to_be_bytes());
+    head.extend([0; 16]); // Created and modified
+    for value in [0, -DOT, 5 * DOT, 7 * DOT, 0, 8, 2, 1, 0] {
+        head.extend(value.to_be_bytes());
+    }
+
+    let mut hhea = 0x0001_0000u32.to_be_bytes().to_vec();
+    for value in [7 * DOT, -DOT, 0, ADVANCE as i16, 0, DOT, 5 * DOT, 1, 0, 0] {
+        hhea.extend(value.to_be_bytes());
+    }
+    hhea.extend([0; 10]);
+    hhea.extend(glyph_count.to_be_bytes());
+
+    let mut post = 0x0003_0000u32.to_be_bytes().to_vec();
+    post.extend([0; 4]); // Italic angle
+    post.extend((-DOT).to_be_bytes());
+    post.extend(DOT.to_be_bytes());
+    post.extend(1u32.to_be_bytes()); // Fixed pitch
+    post.extend([0; 16]);
+
+    // Tables sorted by tag
+    let tables: [(&[u8; 4], Vec<u8>); 8] = [
+        (b"cmap", cmap_table()),
+        (b"glyf", glyf),
+        (b"head", head),
+        (b"hhea", hhea),
+        (b"hmtx", hmtx),
+        (b"loca", loca),
+        (b"maxp", maxp),
+        (b"post", post),
+    ];
+    let count = tables.len() as u16;
+    let search_range = 16 * (1u16 << (15 - count.leading_zeros()));
+    let mut font = 0x0001_0000u32.to_be_bytes().to_vec();
+    for value in [
+        count,
+        search_range,
+        search_range.trailing_zeros() as u16 - 4,
+        16 * count - search_range,
+    ] {
+        font.extend(value.to_be_bytes());
+    }
+    let mut offset = 12 + 16 * tables.len();
+    let mut head_offset = 0;
+    for (tag, data) in &tables {
+        if *tag == b"head" {
+            head_offset = offset;
+        }
+        font.extend(*tag);
+        font.extend(checksum(data).to_be_bytes());
+        font.extend((offset as u32).to_be_bytes());
+        font.extend((data.len() as u32).to_be_bytes());
+        offset += data.len().next_multiple_of(4);
+    }
+    for (_, data) in &tables {
+        font.extend(data);
+        font.resize(font.len().next_multiple_of(4), 0);
+    }
+    let adjustment = 0xB1B0_AFBAu32.wrapping_sub(checksum(&font));
+    font[head_offset + 8..head_offset + 12].copy_from_slice(&adjustment.to_be_bytes());
+    font
+}
+
+#[cfg(test)]
+mod tests {
+    use ab_glyph::{Font, FontRef, GlyphId};
+
+    use super::*;
+
+    #[test]
+    fn pixel_font_parses() {
+        let data = pixel_font_data();
+        let font = FontRef::try_from_slice(&data).expect("valid font");
+        assert_eq!(font.glyph_count(), GLYPHS.len() + 1);
+        assert_eq!(font.units_per_em(), Some(8.0 * DOT as f32));
+        assert_eq!(font.ascent_unscaled(), 7.0 * DOT as f32);
+        assert_eq!(font.descent_unscaled(), -DOT as f32);
+    }
+
+    #[test]
+    fn characters_map_to_their_glyphs() {
+        let data = pixel_font_data();
+        let font = FontRef::try_from_slice(&data).unwrap();
+        for (index, (character, _)) in GLYPHS.iter().enumerate() {
+            assert_eq!(font.glyph_id(*character), GlyphId(index as u16 + 1));
+            assert_eq!(font.h_advance_unscaled(GlyphId(index as u16 + 1)), 768.0);
+        }
+        assert_eq!(font.glyph_id('\u{263A}'), GlyphId(0));
+    }
+
+    #[test]
+    fn glyphs_cover_their_dots() {
+        let data = pixel_font_data();
+        let font = FontRef::try_from_slice(&data).unwrap();
+        assert!(font.outline(font.glyph_id(' ')).is_none());
+        let outline = font.outline(font.glyph_id('A')).unwrap();
+        assert_eq!(outline.bounds.min.x, 0.0);
+        assert_eq!(outline.bounds.max.x, 5.0 * DOT as f32);
+        // ab_glyph keeps the top of the glyph in min.y
+        assert_eq!(outline.bounds.min.y, 7.0 * DOT as f32);
+        assert_eq!(outline.bounds.max.y, 0.0);
+        let descender = font.outline(font.glyph_id('g')).unwrap();
+        assert_eq!(descender.bounds.max.y, -DOT as f32);
+    }
+
+    #[test]
+    fn runs_of_dots_become_one_rectangle() {
+        let contours = glyph_contours(&[0x1B, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            contours,
+            vec![
+                [(0, 768), (0, 896), (256, 896), (256, 768)],
+                [(384, 768), (384, 896), (640, 896), (640, 768)],
+            ]
+        );
+    }
+}
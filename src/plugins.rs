@@ -0,0 +1,306 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Plugins are external programs described by a JSON manifest in the plugin directory.
+//! For every hook the designer runs the program with the hook name as last argument and the
+//! object pool (IOP) on stdin:
+//! - `export`: stdout is written to the exported file
+//! - `validate`: stdout is a JSON array of `{"severity": "error" | "warning", "object": <id>, "message": "..."}`
+//! - `transform`: stdout is the transformed object pool (IOP), which replaces the current pool
+//!
+//! A non zero exit code is reported as an error, with stderr as message.
+//!
+//! Plugins run as separate processes with the rights of the user, on a worker thread so the
+//! designer stays responsive. A plugin that does not finish within its timeout is killed.
+//!
+//! Processes are used instead of dynamic libraries or WASM modules. Rust has no stable ABI, so a
+//! dynamic library would have to be built with the same compiler and ag-iso-stack version as the
+//! designer, and a crash in it would take down the designer with the unsaved project. A WASM
+//! runtime would have to be embedded in the designer, which itself runs as WASM in the browser,
+//! and would limit plugins to languages that compile to WASM. With processes the API is the
+//! standard IOP format and JSON, so existing in-house tools in any language can be plugins, and
+//! a plugin that crashes, hangs or returns a damaged pool only fails its own run: the exit code
+//! and timeout are reported, and a returned pool is recovered and replaces the pool as a change
+//! that can be undone.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{Receiver, TryRecvError};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+use serde::{Deserialize, Serialize};
+
+use crate::{recover_pool, ChangeCategory, EditorProject, RecoveryReport, ValidationIssue};
+
+/// Environment variable that overrides the plugin directory
+pub const PLUGIN_DIR_ENV: &str = "AG_ISO_DESIGNER_PLUGINS";
+
+/// Seconds a plugin may run when its manifest does not set a timeout
+pub const DEFAULT_PLUGIN_TIMEOUT: u64 = 60;
+
+fn default_timeout() -> u64 {
+    DEFAULT_PLUGIN_TIMEOUT
+}
+
+/// Extension point a plugin can implement
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum PluginHook {
+    Export,
+    Validate,
+    Transform,
+}
+
+impl PluginHook {
+    /// Argument passed to the plugin program
+    pub fn argument(&self) -> &'static str {
+        match self {
+            PluginHook::Export => "export",
+            PluginHook::Validate => "validate",
+            PluginHook::Transform => "transform",
+        }
+    }
+}
+
+/// Description of a plugin, read from `<name>.json` in the plugin directory
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct Plugin {
+    pub name: String,
+
+    #[serde(default)]
+    pub description: String,
+
+    /// Program to run. Relative paths like `./export.py` are resolved against the plugin
+    /// directory, bare program names are looked up in the PATH
+    pub command: PathBuf,
+
+    /// Arguments passed before the hook name
+    #[serde(default)]
+    pub args: Vec<String>,
+    pub hooks: Vec<PluginHook>,
+
+    /// File extension of the exported file, without dot
+    #[serde(default)]
+    pub export_extension: Option<String>,
+
+    /// Seconds after which the plugin program is killed
+    #[serde(default = "default_timeout")]
+    pub timeout_seconds: u64,
+}
+
+/// Validation issue as reported by a plugin
+#[derive(Deserialize)]
+struct PluginIssue {
+    severity: String,
+    #[serde(default)]
+    object: Option<u16>,
+    message: String,
+}
+
+impl Plugin {
+    pub fn supports(&self, hook: PluginHook) -> bool {
+        self.hooks.contains(&hook)
+    }
+
+    /// Start the plugin program for a hook with the pool on stdin, on a worker thread
+    pub fn start(&self, hook: PluginHook, pool: &ObjectPool) -> PluginRun {
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let cancelled = Arc::new(AtomicBool::new(false));
+        let plugin = self.clone();
+        let input = pool.as_iop();
+        let cancel = cancelled.clone();
+        std::thread::spawn(move || {
+            let _ = sender.send(plugin.run(hook, input, &cancel));
+        });
+        PluginRun {
+            plugin: self.clone(),
+            hook,
+            receiver,
+            cancelled,
+        }
+    }
+
+    /// Run the plugin program and return its stdout, killing it on timeout or when cancelled
+    fn run(
+        &self,
+        hook: PluginHook,
+        input: Vec<u8>,
+        cancelled: &AtomicBool,
+    ) -> Result<Vec<u8>, String> {
+        let mut child = Command::new(&self.command)
+            .args(&self.args)
+            .arg(hook.argument())
+            .stdin(Stdio::piped())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(|e| format!("Failed to start {}: {}", self.command.display(), e))?;
+
+        // Write and read from separate threads, the plugin may fill its stdout before reading
+        // all input
+        let mut stdin = child.stdin.take().ok_or("Failed to open stdin")?;
+        let mut stdout = child.stdout.take().ok_or("Failed to open stdout")?;
+        let mut stderr = child.stderr.take().ok_or("Failed to open stderr")?;
+        std::thread::spawn(move || stdin.write_all(&input));
+        let stdout_reader = std::thread::spawn(move || {
+            let mut output = Vec::new();
+            stdout.read_to_end(&mut output).map(|_| output)
+        });
+        let stderr_reader = std::thread::spawn(move || {
+            let mut output = Vec::new();
+            let _ = stderr.read_to_end(&mut output);
+            output
+        });
+
+        let started = Instant::now();
+        let timeout = Duration::from_secs(self.timeout_seconds);
+        let status = loop {
+            match child.try_wait() {
+                Ok(Some(status)) => break status,
+                Ok(None) => {}
+                Err(e) => return Err(format!("Failed to run {}: {}", self.name, e)),
+            }
+            let stopped = if cancelled.load(Ordering::Relaxed) {
+                Some("was cancelled".to_string())
+            } else if started.elapsed() > timeout {
+                Some(format!("timed out after {} s", self.timeout_seconds))
+            } else {
+                None
+            };
+            if let Some(reason) = stopped {
+                let _ = child.kill();
+                let _ = child.wait();
+                return Err(format!("{} {}", self.name, reason));
+            }
+            std::thread::sleep(Duration::from_millis(20));
+        };
+
+        let output = stdout_reader
+            .join()
+            .map_err(|_| format!("Failed to read the output of {}", self.name))?
+            .map_err(|e| format!("Failed to read the output of {}: {}", self.name, e))?;
+        let errors = stderr_reader.join().unwrap_or_default();
+        if !status.success() {
+            return Err(format!(
+                "{} failed ({}): {}",
+                self.name,
+                status,
+                String::from_utf8_lossy(&errors).trim()
+            ));
+        }
+        Ok(output)
+    }
+
+    /// Parse the output of the validate hook into issues
+    pub fn issues(&self, output: &[u8]) -> Result<Vec<ValidationIssue>, String> {
+        let issues: Vec<PluginIssue> = serde_json::from_slice(output)
+            .map_err(|e| format!("{} returned invalid issues: {}", self.name, e))?;
+        Ok(issues
+            .into_iter()
+            .map(|issue| {
+                let object = issue.object.and_then(|id| ObjectId::new(id).ok());
+                let message = format!("[{}] {}", self.name, issue.message);
+                if issue.severity.eq_ignore_ascii_case("error") {
+                    ValidationIssue::error(object, message)
+                } else {
+                    ValidationIssue::warning(object, message)
+                }
+            })
+            .collect())
+    }
+
+    /// Replace the mutating pool by the pool the transform hook returned. Damaged parts of the
+    /// returned pool are skipped, the report tells what was lost.
+    pub fn transform(
+        &self,
+        project: &EditorProject,
+        output: &[u8],
+    ) -> Result<RecoveryReport, String> {
+        if output.is_empty() {
+            return Err(format!("{} returned an empty pool", self.name));
+        }
+        let (pool, report) = recover_pool(output);
+        if report.recovered_objects == 0 {
+            return Err(format!("{} returned an invalid pool", self.name));
+        }
+        project.get_mut_pool().replace(pool);
+        project.describe_change(
+            format!("Transform pool with {}", self.name),
+            ChangeCategory::BulkChange,
+        );
+        Ok(report)
+    }
+}
+
+/// A plugin program running on a worker thread
+pub struct PluginRun {
+    pub plugin: Plugin,
+    pub hook: PluginHook,
+    receiver: Receiver<Result<Vec<u8>, String>>,
+    cancelled: Arc<AtomicBool>,
+}
+
+impl PluginRun {
+    /// The stdout of the plugin once it finished, None while it is still running
+    pub fn try_finish(&self) -> Option<Result<Vec<u8>, String>> {
+        match self.receiver.try_recv() {
+            Ok(result) => Some(result),
+            Err(TryRecvError::Empty) => None,
+            Err(TryRecvError::Disconnected) => {
+                Some(Err(format!("{} stopped unexpectedly", self.plugin.name)))
+            }
+        }
+    }
+
+    /// Kill the plugin program
+    pub fn cancel(&self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Directory plugins are loaded from: the environment variable if set,
+/// otherwise `plugins` next to the executable
+pub fn default_plugin_dir() -> Option<PathBuf> {
+    if let Ok(dir) = std::env::var(PLUGIN_DIR_ENV) {
+        return Some(PathBuf::from(dir));
+    }
+    std::env::current_exe()
+        .ok()
+        .and_then(|exe| exe.parent().map(|dir| dir.join("plugins")))
+}
+
+/// Load all plugin manifests of a directory.
+/// Manifests that can not be parsed are returned as errors, so they can be shown to the user.
+pub fn load_plugins(dir: &Path) -> (Vec<Plugin>, Vec<String>) {
+    let mut plugins = Vec::new();
+    let mut errors = Vec::new();
+    let Ok(entries) = std::fs::read_dir(dir) else {
+        return (plugins, errors);
+    };
+
+    let mut paths: Vec<PathBuf> = entries
+        .filter_map(|entry| entry.ok().map(|e| e.path()))
+        .filter(|path| path.extension().is_some_and(|ext| ext == "json"))
+        .collect();
+    paths.sort();
+    for path in paths {
+        let manifest = std::fs::read(&path)
+            .map_err(|e| e.to_string())
+            .and_then(|data| serde_json::from_slice::<Plugin>(&data).map_err(|e| e.to_string()));
+        match manifest {
+            Ok(mut plugin) => {
+                if plugin.command.is_relative() && plugin.command.components().count() > 1 {
+                    plugin.command = dir.join(&plugin.command);
+                }
+                plugins.push(plugin);
+            }
+            Err(e) => errors.push(format!("{}: {}", path.display(), e)),
+        }
+    }
+    (plugins, errors)
+}
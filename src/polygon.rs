@@ -0,0 +1,157 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use ag_iso_stack::object_pool::object::OutputPolygon;
+use ag_iso_stack::object_pool::object_attributes::Point;
+
+/// Polygon type of a convex polygon, terminals may fill it with a simpler algorithm
+pub const POLYGON_TYPE_CONVEX: u8 = 0;
+
+/// Polygon type of an open polygon, the last point is not connected to the first one
+pub const POLYGON_TYPE_OPEN: u8 = 3;
+
+/// Cross product of the edges a->b and b->c, positive for a turn to the right in screen
+/// coordinates
+fn turn(a: Point<u16>, b: Point<u16>, c: Point<u16>) -> i64 {
+    let (ab_x, ab_y) = (b.x as i64 - a.x as i64, b.y as i64 - a.y as i64);
+    let (bc_x, bc_y) = (c.x as i64 - b.x as i64, c.y as i64 - b.y as i64);
+    ab_x * bc_y - ab_y * bc_x
+}
+
+/// Whether the closed polygon through the points is convex. Straight angles and repeated
+/// points are allowed, a polygon that turns around more than once (like a star) is not convex.
+pub fn is_convex(points: &[Point<u16>]) -> bool {
+    let mut points: Vec<Point<u16>> = points.to_vec();
+    points.dedup();
+    while points.len() > 1 && points.first() == points.last() {
+        points.pop();
+    }
+    if points.len() < 3 {
+        return true;
+    }
+
+    let mut sign = 0;
+    let mut total_angle = 0.0;
+    for i in 0..points.len() {
+        let a = points[i];
+        let b = points[(i + 1) % points.len()];
+        let c = points[(i + 2) % points.len()];
+        let cross = turn(a, b, c);
+        if cross != 0 {
+            if sign != 0 && sign != cross.signum() {
+                return false;
+            }
+            sign = cross.signum();
+        }
+        let before = (b.y as f64 - a.y as f64).atan2(b.x as f64 - a.x as f64);
+        let after = (c.y as f64 - b.y as f64).atan2(c.x as f64 - b.x as f64);
+        let mut angle = after - before;
+        if angle > std::f64::consts::PI {
+            angle -= std::f64::consts::TAU;
+        } else if angle < -std::f64::consts::PI {
+            angle += std::f64::consts::TAU;
+        }
+        total_angle += angle;
+    }
+    (total_angle.abs() - std::f64::consts::TAU).abs() < 0.01
+}
+
+/// Problems with the points of a polygon
+pub fn polygon_problems(polygon: &OutputPolygon) -> Vec<String> {
+    let mut problems = Vec::new();
+    if polygon.points.len() < 3 {
+        problems.push(format!(
+            "A polygon needs at least 3 points, it has {}",
+            polygon.points.len()
+        ));
+    }
+    let outside: Vec<String> = polygon
+        .points
+        .iter()
+        .enumerate()
+        .filter(|(_, p)| p.x > polygon.width || p.y > polygon.height)
+        .map(|(i, _)| i.to_string())
+        .collect();
+    if !outside.is_empty() {
+        problems.push(format!(
+            "Points {} are outside of the {}\u{d7}{} polygon area",
+            outside.join(", "),
+            polygon.width,
+            polygon.height
+        ));
+    }
+    if polygon.polygon_type == POLYGON_TYPE_CONVEX && !is_convex(&polygon.points) {
+        problems.push(
+            "The polygon is marked convex but it is not, terminals may fill it wrong".to_string(),
+        );
+    }
+    problems
+}
+
+/// The edge nearest to a position, as the index of its first point
+pub fn nearest_edge(points: &[Point<u16>], closed: bool, x: f32, y: f32) -> Option<usize> {
+    let edges = if closed {
+        points.len()
+    } else {
+        points.len().saturating_sub(1)
+    };
+    (0..edges)
+        .map(|i| {
+            let a = points[i];
+            let b = points[(i + 1) % points.len()];
+            let (ax, ay) = (a.x as f32, a.y as f32);
+            let (dx, dy) = (b.x as f32 - ax, b.y as f32 - ay);
+            let length = dx * dx + dy * dy;
+            let t = if length > 0.0 {
+                (((x - ax) * dx + (y - ay) * dy) / length).clamp(0.0, 1.0)
+            } else {
+                0.0
+            };
+            let (px, py) = (ax + t * dx - x, ay + t * dy - y);
+            (i, px * px + py * py)
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(i, _)| i)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn points(points: &[(u16, u16)]) -> Vec<Point<u16>> {
+        points.iter().map(|&(x, y)| Point { x, y }).collect()
+    }
+
+    #[test]
+    fn convex_polygons_are_detected() {
+        assert!(is_convex(&points(&[(0, 0), (10, 0), (10, 10), (0, 10)])));
+        // Straight angles, repeated points and a closing point are allowed
+        assert!(is_convex(&points(&[
+            (0, 0),
+            (5, 0),
+            (10, 0),
+            (10, 0),
+            (10, 10),
+            (0, 10),
+            (0, 0)
+        ])));
+        assert!(is_convex(&points(&[(0, 0), (10, 0)])));
+
+        // A notch and a star that turns around twice
+        assert!(!is_convex(&points(&[
+            (0, 0),
+            (10, 0),
+            (5, 5),
+            (10, 10),
+            (0, 10)
+        ])));
+        assert!(!is_convex(&points(&[
+            (10, 0),
+            (16, 20),
+            (0, 7),
+            (20, 7),
+            (4, 20)
+        ])));
+    }
+}
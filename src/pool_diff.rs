@@ -0,0 +1,216 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use ag_iso_stack::object_pool::{object::Object, ObjectId, ObjectPool, ObjectType};
+
+use crate::hex_inspector::{annotate_object, format_hex};
+
+/// A single attribute that differs between two versions of an object
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeChange {
+    pub attribute: String,
+    pub old_value: String,
+    pub new_value: String,
+}
+
+/// How an object differs between two pools
+#[derive(Debug, Clone, PartialEq)]
+pub enum ObjectChange {
+    Added,
+    Removed,
+    Modified(Vec<AttributeChange>),
+}
+
+/// Difference of a single object between two pools
+#[derive(Debug, Clone, PartialEq)]
+pub struct ObjectDiff {
+    pub id: ObjectId,
+    pub object_type: ObjectType,
+    pub change: ObjectChange,
+}
+
+/// Compare two pools object by object.
+/// Objects are matched by their ID, the result is sorted by ID
+pub fn diff_pools(old: &ObjectPool, new: &ObjectPool) -> Vec<ObjectDiff> {
+    let mut diffs = Vec::new();
+
+    for object in new.objects() {
+        match old.object_by_id(object.id()) {
+            None => diffs.push(ObjectDiff {
+                id: object.id(),
+                object_type: object.object_type(),
+                change: ObjectChange::Added,
+            }),
+            Some(old_object) if old_object != object => diffs.push(ObjectDiff {
+                id: object.id(),
+                object_type: object.object_type(),
+                change: ObjectChange::Modified(diff_objects(old_object, object)),
+            }),
+            Some(_) => {}
+        }
+    }
+    for object in old.objects() {
+        if new.object_by_id(object.id()).is_none() {
+            diffs.push(ObjectDiff {
+                id: object.id(),
+                object_type: object.object_type(),
+                change: ObjectChange::Removed,
+            });
+        }
+    }
+
+    diffs.sort_by_key(|diff| diff.id.value());
+    diffs
+}
+
+/// Attributes that are lists, by the name of their entries in the ISO 11783-6 layout
+const LIST_ATTRIBUTES: &[(&str, &str)] = &[
+    ("Object reference", "object_refs"),
+    ("Macro reference", "macro_refs"),
+    ("Key reference", "objects"),
+    ("List item", "list_items"),
+    ("Language code", "language_codes"),
+    ("Point", "points"),
+];
+
+/// Values of long attributes, e.g. picture data, are summarised above this length
+const MAX_HEX_VALUE_LENGTH: usize = 16;
+
+/// Compare two versions of an object attribute by attribute
+pub fn diff_objects(old: &Object, new: &Object) -> Vec<AttributeChange> {
+    if old.object_type() != new.object_type() {
+        return vec![AttributeChange {
+            attribute: "type".to_string(),
+            old_value: format!("{:?}", old.object_type()),
+            new_value: format!("{:?}", new.object_type()),
+        }];
+    }
+
+    let old_attributes = object_attributes(old);
+    let new_attributes = object_attributes(new);
+    let value_of = |attributes: &[(String, String)], name: &str| {
+        attributes
+            .iter()
+            .find(|(attribute, _)| attribute == name)
+            .map(|(_, value)| value.clone())
+            .unwrap_or_else(|| missing_value(name))
+    };
+    let mut names: Vec<&String> = new_attributes.iter().map(|(name, _)| name).collect();
+    for (name, _) in &old_attributes {
+        if !names.contains(&name) {
+            names.push(name);
+        }
+    }
+    names
+        .into_iter()
+        .filter_map(|name| {
+            let old_value = value_of(&old_attributes, name);
+            let new_value = value_of(&new_attributes, name);
+            (old_value != new_value).then(|| AttributeChange {
+                attribute: name.clone(),
+                old_value,
+                new_value,
+            })
+        })
+        .collect()
+}
+
+/// Value of an attribute an object does not encode, i.e. an empty list
+fn missing_value(name: &str) -> String {
+    if LIST_ATTRIBUTES.iter().any(|(_, list)| *list == name) {
+        "[]".to_string()
+    } else {
+        "-".to_string()
+    }
+}
+
+fn little_endian(bytes: &[u8]) -> u32 {
+    bytes
+        .iter()
+        .rev()
+        .fold(0, |value, byte| (value << 8) | *byte as u32)
+}
+
+/// Text of a string attribute, which is ISO 8859-1 encoded
+fn latin1(bytes: &[u8]) -> String {
+    bytes.iter().map(|byte| *byte as char).collect()
+}
+
+/// Readable value of the bytes of an attribute
+fn format_value(name: &str, bytes: &[u8]) -> String {
+    match (name, bytes.len()) {
+        ("Scale", 4) => f32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).to_string(),
+        ("Offset", 4) => i32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]).to_string(),
+        ("Object reference", 6) => format!(
+            "{} ({}, {})",
+            little_endian(&bytes[0..2]),
+            i16::from_le_bytes([bytes[2], bytes[3]]),
+            i16::from_le_bytes([bytes[4], bytes[5]])
+        ),
+        ("Macro reference", 2) => format!("event {}: macro {}", bytes[0], bytes[1]),
+        ("Point", 4) => format!(
+            "({}, {})",
+            little_endian(&bytes[0..2]),
+            little_endian(&bytes[2..4])
+        ),
+        ("Language code", _) => latin1(bytes),
+        (_, 1 | 2 | 4) => little_endian(bytes).to_string(),
+        (_, len) if len <= MAX_HEX_VALUE_LENGTH => format_hex(bytes),
+        (_, len) => {
+            let mut hasher = DefaultHasher::new();
+            bytes.hash(&mut hasher);
+            format!("{} bytes, checksum {:016X}", len, hasher.finish())
+        }
+    }
+}
+
+/// Get the attributes of an object as name and value pairs.
+///
+/// The attributes are read from the serialized object as laid out in ISO 11783-6, so every
+/// object type is compared the same way a terminal would see it. Lists like the child references
+/// are one attribute, and the ID and type of the object itself are left out.
+pub fn object_attributes(object: &Object) -> Vec<(String, String)> {
+    let bytes = object.write();
+    let mut attributes: Vec<(String, String)> = Vec::new();
+    let mut lists: Vec<(&str, Vec<String>)> = Vec::new();
+    let mut text_follows = false;
+    for field in annotate_object(&bytes) {
+        let value = &bytes[field.offset..field.offset + field.len];
+        if field.count {
+            // Strings are the only values preceded by their length
+            text_follows = field.name == "Length" && value.iter().any(|byte| *byte != 0);
+            continue;
+        }
+        if field.name == "Object ID" {
+            continue;
+        }
+
+        let entry = field.name.rsplit_once(' ').and_then(|(base, index)| {
+            index.parse::<usize>().ok()?;
+            LIST_ATTRIBUTES.iter().find(|(entry, _)| *entry == base)
+        });
+        if let Some((entry, list)) = entry {
+            let value = format_value(entry, value);
+            match lists.iter_mut().find(|(name, _)| name == list) {
+                Some((_, values)) => values.push(value),
+                None => lists.push((list, vec![value])),
+            }
+            continue;
+        }
+
+        let value = if std::mem::take(&mut text_follows) {
+            format!("\"{}\"", latin1(value))
+        } else {
+            format_value(&field.name, value)
+        };
+        attributes.push((field.name.to_lowercase().replace(' ', "_"), value));
+    }
+    for (name, values) in lists {
+        attributes.push((name.to_string(), format!("[{}]", values.join(", "))));
+    }
+    attributes
+}
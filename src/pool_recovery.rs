@@ -0,0 +1,139 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use ag_iso_stack::object_pool::{object::Object, ObjectPool};
+
+/// A region of the input data that could not be parsed into an object
+#[derive(Debug, Clone)]
+pub struct SkippedRegion {
+    /// Byte offset of the first skipped byte
+    pub start: usize,
+
+    /// Byte offset one past the last skipped byte
+    pub end: usize,
+
+    /// Why parsing failed at the start of this region
+    pub reason: String,
+}
+
+impl SkippedRegion {
+    /// Number of bytes in this region
+    pub fn size(&self) -> usize {
+        self.end - self.start
+    }
+}
+
+/// Summary of what the recovery loader could and could not salvage
+#[derive(Debug, Clone, Default)]
+pub struct RecoveryReport {
+    /// Total size of the input data in bytes
+    pub total_bytes: usize,
+
+    /// Number of objects that were parsed successfully
+    pub recovered_objects: usize,
+
+    /// Regions that were skipped while searching for the next valid object
+    pub skipped_regions: Vec<SkippedRegion>,
+}
+
+impl RecoveryReport {
+    /// Returns true if the whole input was parsed without skipping anything
+    pub fn is_clean(&self) -> bool {
+        self.skipped_regions.is_empty()
+    }
+
+    /// Total number of bytes that were skipped
+    pub fn skipped_bytes(&self) -> usize {
+        self.skipped_regions.iter().map(|r| r.size()).sum()
+    }
+}
+
+/// Iterator adapter that keeps track of how many bytes were consumed
+struct CountingIter<'a> {
+    data: &'a [u8],
+    position: usize,
+}
+
+impl Iterator for CountingIter<'_> {
+    type Item = u8;
+
+    fn next(&mut self) -> Option<u8> {
+        let byte = self.data.get(self.position).copied();
+        if byte.is_some() {
+            self.position += 1;
+        }
+        byte
+    }
+}
+
+/// Try to parse a single object at the given offset.
+/// Returns the object and the offset directly after it.
+pub(crate) fn read_object_at(data: &[u8], offset: usize) -> Result<(Object, usize), String> {
+    let mut iter = CountingIter {
+        data,
+        position: offset,
+    };
+    match Object::read(&mut iter) {
+        Ok(object) => Ok((object, iter.position)),
+        Err(e) => Err(format!("{:?}", e)),
+    }
+}
+
+/// Parse as many objects as possible from (possibly damaged) IOP data.
+///
+/// Whenever an object fails to parse, the loader moves forward one byte at a time until it finds
+/// the start of the next valid object. Every region that had to be skipped is reported with its
+/// byte offsets, so the user can see which part of the file was lost.
+pub fn recover_pool(data: &[u8]) -> (ObjectPool, RecoveryReport) {
+    let mut pool = ObjectPool::default();
+    let mut report = RecoveryReport {
+        total_bytes: data.len(),
+        ..Default::default()
+    };
+
+    let mut offset = 0;
+    let mut current_skip: Option<SkippedRegion> = None;
+
+    while offset < data.len() {
+        // While searching for the next valid object, an object with a known ID is most likely
+        // damaged data that happens to parse. Elsewhere duplicates are kept, so the user can give
+        // them new IDs instead of losing them.
+        let result = read_object_at(data, offset).and_then(|(object, next_offset)| {
+            if current_skip.is_some() && pool.object_by_id(object.id()).is_some() {
+                Err(format!("Duplicate object ID {}", object.id().value()))
+            } else {
+                Ok((object, next_offset))
+            }
+        });
+
+        match result {
+            Ok((object, next_offset)) => {
+                if let Some(mut region) = current_skip.take() {
+                    region.end = offset;
+                    report.skipped_regions.push(region);
+                }
+                pool.add(object);
+                report.recovered_objects += 1;
+                offset = next_offset;
+            }
+            Err(reason) => {
+                if current_skip.is_none() {
+                    current_skip = Some(SkippedRegion {
+                        start: offset,
+                        end: offset,
+                        reason,
+                    });
+                }
+                offset += 1;
+            }
+        }
+    }
+
+    if let Some(mut region) = current_skip.take() {
+        region.end = data.len();
+        report.skipped_regions.push(region);
+    }
+
+    (pool, report)
+}
@@ -0,0 +1,197 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use serde::{Deserialize, Serialize};
+
+/// Length of a version label in the (non extended) version commands
+pub const VERSION_LABEL_LENGTH: usize = 7;
+
+/// Length of a version label in the extended version commands of VT version 5 and later
+pub const EXTENDED_VERSION_LABEL_LENGTH: usize = 32;
+
+const WORKING_SET_MAINTENANCE: u8 = 0xFF;
+const STORE_VERSION: u8 = 0xD0;
+const LOAD_VERSION: u8 = 0xD1;
+const EXTENDED_GET_VERSIONS: u8 = 0xD3;
+const EXTENDED_STORE_VERSION: u8 = 0xD4;
+const EXTENDED_LOAD_VERSION: u8 = 0xD5;
+const GET_VERSIONS: u8 = 0xDF;
+
+/// A message the VT client sends to the terminal, with its data bytes
+#[derive(Debug, Clone, PartialEq)]
+pub struct VersionMessage {
+    pub name: &'static str,
+    pub data: Vec<u8>,
+}
+
+/// Version label under which terminals store the pool, so they can tell a changed pool from the
+/// one in their cache
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct PoolVersion {
+    pub label: String,
+
+    /// VT version reported in the working set maintenance message
+    pub vt_version: u8,
+
+    /// Bump the label when a changed pool is exported
+    pub bump_on_export: bool,
+
+    /// Hash of the last exported pool
+    #[serde(default)]
+    last_exported: Option<u64>,
+}
+
+impl Default for PoolVersion {
+    fn default() -> Self {
+        PoolVersion {
+            label: "1".to_string(),
+            vt_version: 4,
+            bump_on_export: true,
+            last_exported: None,
+        }
+    }
+}
+
+/// FNV-1a hash of pool data, stable between builds unlike the standard library hasher
+pub(crate) fn pool_hash(data: &[u8]) -> u64 {
+    data.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ *byte as u64).wrapping_mul(0x100000001b3)
+    })
+}
+
+impl PoolVersion {
+    /// Labels longer than 7 characters need the extended version commands
+    pub fn is_extended(&self) -> bool {
+        self.label.chars().count() > VERSION_LABEL_LENGTH
+    }
+
+    /// The label as sent to the terminal: ISO 8859-1, padded with spaces
+    pub fn label_bytes(&self) -> Vec<u8> {
+        let length = if self.is_extended() {
+            EXTENDED_VERSION_LABEL_LENGTH
+        } else {
+            VERSION_LABEL_LENGTH
+        };
+        let mut bytes: Vec<u8> = self
+            .label
+            .chars()
+            .take(length)
+            .map(|c| u8::try_from(c as u32).unwrap_or(b'?'))
+            .collect();
+        bytes.resize(length, b' ');
+        bytes
+    }
+
+    /// Problems that keep terminals from storing the pool under this label
+    pub fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.label.trim().is_empty() {
+            problems.push("The version label is empty".to_string());
+        }
+        let length = self.label.chars().count();
+        if length > EXTENDED_VERSION_LABEL_LENGTH {
+            problems.push(format!(
+                "The version label is {} characters, only the first {} are used",
+                length, EXTENDED_VERSION_LABEL_LENGTH
+            ));
+        }
+        if self.label.chars().any(|c| c as u32 > 0xFF) {
+            problems.push("The version label has characters outside of ISO 8859-1".to_string());
+        }
+        if self.is_extended() && self.vt_version < 5 {
+            problems.push(format!(
+                "Labels longer than {} characters need VT version 5 or later",
+                VERSION_LABEL_LENGTH
+            ));
+        }
+        problems
+    }
+
+    /// Increase the last number in the label, keeping leading zeros, or append one
+    pub fn bump(&mut self) {
+        let chars: Vec<char> = self.label.chars().collect();
+        let Some(end) = chars.iter().rposition(char::is_ascii_digit) else {
+            self.label.push('1');
+            return;
+        };
+        let start = chars[..end]
+            .iter()
+            .rposition(|c| !c.is_ascii_digit())
+            .map_or(0, |i| i + 1);
+        let digits: String = chars[start..=end].iter().collect();
+        let number = digits.parse::<u128>().unwrap_or(0) + 1;
+        let bumped = format!("{:0width$}", number, width = digits.len());
+        let mut label: String = chars[..start].iter().collect();
+        label.push_str(&bumped);
+        label.extend(&chars[end + 1..]);
+        self.label = label;
+    }
+
+    /// Record an export of the pool, bumping the label if the pool changed since the last
+    /// export. Returns whether the label was bumped.
+    pub fn register_export(&mut self, iop: &[u8]) -> bool {
+        let hash = pool_hash(iop);
+        let bumped = self.bump_on_export && self.last_exported.is_some_and(|last| last != hash);
+        if bumped {
+            self.bump();
+        }
+        self.last_exported = Some(hash);
+        bumped
+    }
+
+    /// Messages the VT client sends to check for and store the pool under this label
+    pub fn messages(&self) -> Vec<VersionMessage> {
+        let label = self.label_bytes();
+        let command = |command: u8, data: &[u8]| {
+            let mut message = vec![command];
+            message.extend_from_slice(data);
+            message
+        };
+        let mut messages = vec![VersionMessage {
+            name: "Working set maintenance (initiating)",
+            data: vec![
+                WORKING_SET_MAINTENANCE,
+                0x01,
+                self.vt_version,
+                0xFF,
+                0xFF,
+                0xFF,
+                0xFF,
+                0xFF,
+            ],
+        }];
+        if self.is_extended() {
+            messages.extend([
+                VersionMessage {
+                    name: "Extended get versions",
+                    data: command(EXTENDED_GET_VERSIONS, &[0xFF; 7]),
+                },
+                VersionMessage {
+                    name: "Extended load version",
+                    data: command(EXTENDED_LOAD_VERSION, &label),
+                },
+                VersionMessage {
+                    name: "Extended store version (after uploading the pool)",
+                    data: command(EXTENDED_STORE_VERSION, &label),
+                },
+            ]);
+        } else {
+            messages.extend([
+                VersionMessage {
+                    name: "Get versions",
+                    data: command(GET_VERSIONS, &[0xFF; 7]),
+                },
+                VersionMessage {
+                    name: "Load version",
+                    data: command(LOAD_VERSION, &label),
+                },
+                VersionMessage {
+                    name: "Store version (after uploading the pool)",
+                    data: command(STORE_VERSION, &label),
+                },
+            ]);
+        }
+        messages
+    }
+}
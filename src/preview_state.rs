@@ -0,0 +1,273 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{HashMap, HashSet};
+
+use ag_iso_stack::object_pool::object_attributes::FontSize;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+use eframe::egui;
+
+use crate::reference_overlay::ReferenceOverlay;
+use crate::terminal_frame::TerminalFrame;
+use crate::terminal_profile::font_size_key;
+
+const PREVIEW_STATE_ID: &str = "preview_state";
+
+/// Duration of one on/off cycle of flashing objects in seconds
+const FLASH_PERIOD: f64 = 1.0;
+
+/// Number of pointing events kept in the log of the coordinate inspector
+const MAX_POINTING_EVENTS: usize = 200;
+
+/// Number of input messages kept in the log of the simulation
+const MAX_INPUT_MESSAGES: usize = 200;
+
+/// How the preview behaves
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum PreviewMode {
+    /// Objects are shown as configured and clicking selects them
+    #[default]
+    Edit,
+    /// Objects react to input like they would on a real terminal
+    Simulate,
+}
+
+/// Visual state of a button that is shown in edit mode
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonPreviewState {
+    /// The state the terminal would show first (latched if the initial state is latched)
+    #[default]
+    Initial,
+    Pressed,
+    Disabled,
+}
+
+/// Touch state of a pointing event, as sent in the VT pointing event message
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TouchState {
+    Released,
+    Pressed,
+}
+
+/// A press or release in the simulated preview, resolved like a touch VT would
+#[derive(Debug, Clone, PartialEq)]
+pub struct PointingEvent {
+    /// The mask that was shown
+    pub mask: ObjectId,
+    /// Position relative to the top left corner of the mask
+    pub x: i16,
+    pub y: i16,
+    pub state: TouchState,
+    /// Objects from the mask down to the topmost object under the pointer
+    pub chain: Vec<ObjectId>,
+    /// Other objects under the pointer that are covered by the chain
+    pub covered: Vec<ObjectId>,
+}
+
+/// Message a VT sends to the working set when the operator uses an input object
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum InputMessage {
+    /// VT Select Input Object message, when an input object gets focus or is opened for input
+    SelectInputObject {
+        object: ObjectId,
+        open_for_input: bool,
+    },
+    /// VT ESC message, when the operator aborts the input
+    Esc { object: ObjectId },
+    /// VT Change Numeric Value message, when the operator accepts a new value
+    ChangeNumericValue { object: ObjectId, value: u32 },
+}
+
+impl InputMessage {
+    /// Function code of the message in the VT to ECU direction
+    pub fn function_code(&self) -> u8 {
+        match self {
+            InputMessage::SelectInputObject { .. } => 0x03,
+            InputMessage::Esc { .. } => 0x04,
+            InputMessage::ChangeNumericValue { .. } => 0x05,
+        }
+    }
+
+    /// The input object the message is about
+    pub fn object(&self) -> ObjectId {
+        match self {
+            InputMessage::SelectInputObject { object, .. }
+            | InputMessage::Esc { object }
+            | InputMessage::ChangeNumericValue { object, .. } => *object,
+        }
+    }
+}
+
+/// An input object that is opened for input in the simulation
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct OpenInput {
+    pub object: ObjectId,
+    /// Value the operator is entering, in displayed units for numbers and as index for lists
+    pub entry: f64,
+}
+
+/// State of the preview that is shared between frames, but not part of the object pool
+#[derive(Debug, Default, Clone)]
+pub struct PreviewState {
+    pub mode: PreviewMode,
+
+    /// Per button state shown in edit mode
+    pub button_preview: HashMap<ObjectId, ButtonPreviewState>,
+
+    /// Latched state of latchable buttons while simulating
+    pub button_latched: HashMap<ObjectId, bool>,
+
+    /// Keep flashing objects in their normal state, e.g. for taking screenshots
+    pub flashing_frozen: bool,
+
+    /// Width and height of a soft key (and working set) designator of the simulated terminal
+    pub soft_key_size: (u16, u16),
+
+    /// Objects that are hidden from the preview in edit mode
+    pub hidden_objects: HashSet<ObjectId>,
+
+    /// Registered font family of the simulated terminal for each font size key
+    pub font_families: HashMap<String, egui::FontFamily>,
+
+    /// Decorative terminal bezel shown around the preview
+    pub frame: TerminalFrame,
+
+    /// Log of pointing events while simulating, oldest first
+    pub pointing_events: Vec<PointingEvent>,
+
+    /// Reveal spaces and control characters in the string value editors
+    pub show_control_characters: bool,
+
+    /// Values entered in input objects while simulating, by the input object or its variable
+    pub input_values: HashMap<ObjectId, u32>,
+
+    /// The input object that is currently opened for input while simulating
+    pub open_input: Option<OpenInput>,
+
+    /// Log of messages sent by the simulated input objects, oldest first
+    pub input_messages: Vec<InputMessage>,
+
+    /// Screenshot of a real terminal shown over the preview
+    pub reference: Option<ReferenceOverlay>,
+
+    /// Number the objects that can get the input focus in the preview
+    pub show_focus_order: bool,
+
+    /// Shown state of containers and enabled state of input objects and buttons set in the state
+    /// sandbox, overriding the state in the pool
+    pub object_states: HashMap<ObjectId, bool>,
+
+    /// Mask shown instead of the active mask of the working set, e.g. picked in the mask
+    /// thumbnails, so browsing masks does not edit the pool
+    pub mask_override: Option<ObjectId>,
+}
+
+impl PreviewState {
+    /// Switch the preview mode, resetting all simulated state when the mode changes
+    pub fn set_mode(&mut self, mode: PreviewMode) {
+        if self.mode != mode {
+            self.mode = mode;
+            self.button_latched.clear();
+            self.input_values.clear();
+            self.open_input = None;
+        }
+    }
+
+    /// Add an input message to the log, dropping the oldest one when it is full
+    pub fn log_input_message(&mut self, message: InputMessage) {
+        if self.input_messages.len() >= MAX_INPUT_MESSAGES {
+            self.input_messages.remove(0);
+        }
+        self.input_messages.push(message);
+    }
+
+    /// Add a pointing event to the log, dropping the oldest one when it is full
+    pub fn log_pointing_event(&mut self, event: PointingEvent) {
+        if self.pointing_events.len() >= MAX_POINTING_EVENTS {
+            self.pointing_events.remove(0);
+        }
+        self.pointing_events.push(event);
+    }
+}
+
+/// Access the preview state stored in the egui context
+pub fn with_preview_state<R>(ctx: &egui::Context, f: impl FnOnce(&mut PreviewState) -> R) -> R {
+    ctx.data_mut(|data| {
+        f(data.get_temp_mut_or_default::<PreviewState>(egui::Id::new(PREVIEW_STATE_ID)))
+    })
+}
+
+/// The mask the preview shows: the mask override while it exists, the active mask of the
+/// working set otherwise
+pub fn previewed_mask(ctx: &egui::Context, pool: &ObjectPool) -> Option<ObjectId> {
+    with_preview_state(ctx, |state| state.mask_override)
+        .filter(|mask| pool.object_by_id(*mask).is_some())
+        .or_else(|| pool.working_set_object().map(|ws| ws.active_mask))
+}
+
+/// Tag of a ui that renders for another terminal than the preview, with its soft key size
+const DESIGNATOR_SIZE_TAG: &str = "designator_size";
+
+/// Width and height of a soft key designator for the objects rendered in the ui. A parent ui can
+/// set another size than the preview with [`designator_size_info`].
+pub fn designator_size(ui: &egui::Ui) -> (u16, u16) {
+    ui.stack()
+        .iter()
+        .find_map(|frame| {
+            frame
+                .tags()
+                .get_downcast::<(u16, u16)>(DESIGNATOR_SIZE_TAG)
+                .copied()
+        })
+        .unwrap_or_else(|| with_preview_state(ui.ctx(), |state| state.soft_key_size))
+}
+
+/// Info for a child ui that renders its objects with another soft key designator size
+pub fn designator_size_info(size: (u16, u16)) -> egui::UiStackInfo {
+    egui::UiStackInfo::default().with_tag_value(DESIGNATOR_SIZE_TAG, size)
+}
+
+/// Get the current preview mode
+pub fn preview_mode(ctx: &egui::Context) -> PreviewMode {
+    with_preview_state(ctx, |state| state.mode)
+}
+
+/// Check if an object is hidden from the preview.
+/// Objects are only hidden in edit mode, the simulation always shows everything.
+pub fn is_hidden_in_preview(ctx: &egui::Context, id: ObjectId) -> bool {
+    with_preview_state(ctx, |state| {
+        state.mode == PreviewMode::Edit && state.hidden_objects.contains(&id)
+    })
+}
+
+/// Whether a container is shown or an input object or button is enabled in the preview, the state
+/// set in the state sandbox or else `pool_state`
+pub fn object_state(ctx: &egui::Context, id: ObjectId, pool_state: bool) -> bool {
+    with_preview_state(ctx, |state| state.object_states.get(&id).copied()).unwrap_or(pool_state)
+}
+
+/// Font family to render a VT font size with, custom fonts of the terminal profile take precedence
+pub fn vt_font_family(ctx: &egui::Context, font_size: &FontSize) -> egui::FontFamily {
+    let custom = with_preview_state(ctx, |state| {
+        state.font_families.get(&font_size_key(font_size)).cloned()
+    });
+    custom.unwrap_or(match font_size {
+        FontSize::NonProportional(_) => egui::FontFamily::Monospace,
+        FontSize::Proportional(_) => egui::FontFamily::Proportional,
+    })
+}
+
+/// Check if flashing objects are in their normal ("on") phase of the blink clock.
+/// Schedules a repaint for the next phase change, so only call this for objects that flash.
+pub fn flash_phase_on(ctx: &egui::Context) -> bool {
+    if with_preview_state(ctx, |state| state.flashing_frozen) {
+        return true;
+    }
+
+    let half_period = FLASH_PERIOD / 2.0;
+    let phase = ctx.input(|i| i.time) % FLASH_PERIOD;
+    let until_next_phase = half_period - (phase % half_period);
+    ctx.request_repaint_after(std::time::Duration::from_secs_f64(until_next_phase));
+    phase < half_period
+}
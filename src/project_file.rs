@@ -2,13 +2,22 @@
 //! SPDX-License-Identifier: GPL-3.0-or-later
 //! Authors: Daan Steenbergen
 
+use crate::colour_roles::ColourRoles;
+use crate::golden_pool::GoldenPool;
+use crate::history::{HistoryLimits, StoredHistory};
+use crate::pool_version::PoolVersion;
+use crate::project_migration::{
+    downgrade_project, upgrade_project, ProjectFileFormat, KNOWN_FEATURES, PROJECT_FILE_VERSION,
+};
+use crate::review::ObjectReview;
+use crate::state_scenarios::StateScenario;
+use crate::string_inventory::TranslationStatus;
+use crate::style_presets::StylePreset;
+use crate::terminal_profile::TerminalProfile;
 use crate::ObjectInfo;
 use ag_iso_stack::object_pool::{object::Object, ObjectId, ObjectPool};
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
-
-/// Project file format version
-const PROJECT_FILE_VERSION: u32 = 1;
+use std::collections::{BTreeMap, HashMap};
 
 /// AgIsoTerminalProject file format (.aitp)
 /// This format stores both the object pool and custom metadata
@@ -17,6 +26,14 @@ pub struct ProjectFile {
     /// Version of the project file format
     version: u32,
 
+    /// Version of the designer that wrote the file
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    designer_version: Option<String>,
+
+    /// Annotations the project uses, so older designers can tell what they would drop
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    features: Vec<String>,
+
     /// The object pool data as IOP bytes
     object_pool_data: Vec<u8>,
 
@@ -25,6 +42,14 @@ pub struct ProjectFile {
 
     /// Project-level settings
     settings: ProjectSettings,
+
+    /// Undo and redo history, only stored if enabled in the settings
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    history: Option<StoredHistory>,
+
+    /// Format of the loaded file before it was migrated
+    #[serde(skip)]
+    format: ProjectFileFormat,
 }
 
 /// Metadata for a single object
@@ -35,6 +60,54 @@ pub struct ObjectMetadata {
 
     /// Notes or comments about the object
     pub notes: Option<String>,
+
+    /// Semantic colour role assigned to each colour attribute
+    #[serde(default)]
+    pub colour_roles: BTreeMap<String, String>,
+
+    /// Review status and comments
+    #[serde(default)]
+    pub review: ObjectReview,
+
+    /// Protects the object against modification
+    #[serde(default)]
+    pub locked: bool,
+
+    /// Translation status of the text of string objects
+    #[serde(default)]
+    pub translation: TranslationStatus,
+
+    /// Name of the style preset that is applied to the object
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub style_preset: Option<String>,
+}
+
+impl ObjectMetadata {
+    /// The metadata to store for an object
+    pub fn from_info(info: &ObjectInfo) -> Self {
+        ObjectMetadata {
+            name: info.name.clone(),
+            notes: Some(info.notes.clone()).filter(|notes| !notes.is_empty()),
+            colour_roles: info.colour_roles.clone(),
+            review: info.review.clone(),
+            locked: info.locked,
+            translation: info.translation,
+            style_preset: info.style_preset.clone(),
+        }
+    }
+
+    /// Restore the stored metadata into the info of an object
+    pub fn restore(&self, info: &mut ObjectInfo) {
+        if let Some(name) = &self.name {
+            info.set_name(name.clone());
+        }
+        info.colour_roles = self.colour_roles.clone();
+        info.review = self.review.clone();
+        info.locked = self.locked;
+        info.translation = self.translation;
+        info.notes = self.notes.clone().unwrap_or_default();
+        info.style_preset = self.style_preset.clone();
+    }
 }
 
 /// Project-level settings
@@ -45,34 +118,84 @@ pub struct ProjectSettings {
 
     /// Last selected object ID
     pub last_selected: Option<u16>,
+
+    /// Soft key designator size of the simulated terminal
+    #[serde(default)]
+    pub soft_key_size: Option<(u16, u16)>,
+
+    /// Semantic colour roles and themes
+    #[serde(default)]
+    pub colour_roles: ColourRoles,
+
+    /// Named sets of attributes that are applied together
+    #[serde(default)]
+    pub style_presets: Vec<StylePreset>,
+
+    /// Expressions over number variables shown while simulating
+    #[serde(default)]
+    pub watch_expressions: Vec<String>,
+
+    /// Named runtime states of containers and input objects to review layouts in
+    #[serde(default)]
+    pub state_scenarios: Vec<StateScenario>,
+
+    /// Everyone who made changes to the project
+    #[serde(default)]
+    pub authors: Vec<String>,
+
+    /// Released object IDs and names that must not change
+    #[serde(default)]
+    pub golden_pool: Option<GoldenPool>,
+
+    /// Bookmarked object IDs
+    #[serde(default)]
+    pub bookmarks: Vec<u16>,
+
+    /// Terminals the preview can simulate
+    #[serde(default)]
+    pub terminal_profiles: Vec<TerminalProfile>,
+
+    /// Index of the terminal profile used by the preview
+    #[serde(default)]
+    pub active_profile: usize,
+
+    /// Store the undo history in the project file
+    #[serde(default)]
+    pub save_history: bool,
+
+    /// When old history entries are coalesced and dropped
+    #[serde(default)]
+    pub history_limits: HistoryLimits,
+
+    /// Version label terminals store the pool under
+    #[serde(default)]
+    pub pool_version: PoolVersion,
 }
 
 impl ProjectFile {
-    /// Create a new project file from an ObjectPool and metadata
+    /// Create a new project file from an ObjectPool, metadata and settings
     pub fn new(
         pool: &ObjectPool,
         object_info: &HashMap<ObjectId, ObjectInfo>,
-        mask_size: u16,
-        selected: Option<ObjectId>,
+        settings: ProjectSettings,
+        history: Option<StoredHistory>,
     ) -> Self {
         // Convert ObjectInfo map to ObjectMetadata map
         let mut object_metadata = HashMap::new();
         for (id, info) in object_info {
-            let metadata = ObjectMetadata {
-                name: info.name.clone(),
-                notes: None, // Future feature
-            };
-            object_metadata.insert(id.value(), metadata);
+            object_metadata.insert(id.value(), ObjectMetadata::from_info(info));
         }
 
+        let features = used_features(&object_metadata, &settings, history.is_some());
         ProjectFile {
             version: PROJECT_FILE_VERSION,
+            designer_version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            features,
             object_pool_data: pool.as_iop(),
             object_metadata,
-            settings: ProjectSettings {
-                mask_size,
-                last_selected: selected.map(|id| id.value()),
-            },
+            settings,
+            history,
+            format: ProjectFileFormat::default(),
         }
     }
 
@@ -106,22 +229,79 @@ impl ProjectFile {
         &self.settings
     }
 
-    /// Serialize project to JSON bytes
-    pub fn to_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
-        serde_json::to_vec_pretty(self)
+    /// Get the stored undo and redo history, if any
+    pub fn get_history(&self) -> Option<&StoredHistory> {
+        self.history.as_ref()
     }
 
-    /// Deserialize project from JSON bytes
+    /// Format of the file before it was migrated to the current version
+    pub fn get_format(&self) -> &ProjectFileFormat {
+        &self.format
+    }
+
+    /// Serialize project to JSON bytes in the given format version
+    pub fn to_bytes(&self, version: u32) -> Result<Vec<u8>, serde_json::Error> {
+        if version == PROJECT_FILE_VERSION {
+            return serde_json::to_vec_pretty(self);
+        }
+        let mut file = serde_json::to_value(self)?;
+        downgrade_project(&mut file, version);
+        serde_json::to_vec_pretty(&file)
+    }
+
+    /// Deserialize project from JSON bytes, older versions are migrated to the current one
     pub fn from_bytes(bytes: &[u8]) -> Result<Self, serde_json::Error> {
-        serde_json::from_slice(bytes)
+        let mut file: serde_json::Value = serde_json::from_slice(bytes)?;
+        let format = upgrade_project(&mut file);
+        let mut project: ProjectFile = serde_json::from_value(file)?;
+        project.format = format;
+        Ok(project)
     }
 }
 
+/// The annotations of [`KNOWN_FEATURES`] the project uses
+fn used_features(
+    object_metadata: &HashMap<u16, ObjectMetadata>,
+    settings: &ProjectSettings,
+    has_history: bool,
+) -> Vec<String> {
+    let any = |used: fn(&ObjectMetadata) -> bool| object_metadata.values().any(used);
+    let used = [
+        any(|meta| meta.name.is_some()),
+        any(|meta| meta.notes.is_some()),
+        any(|meta| !meta.colour_roles.is_empty()),
+        any(|meta| meta.review != ObjectReview::default()),
+        any(|meta| meta.locked),
+        any(|meta| meta.translation != TranslationStatus::default()),
+        !settings.style_presets.is_empty() || any(|meta| meta.style_preset.is_some()),
+        has_history,
+    ];
+    KNOWN_FEATURES
+        .iter()
+        .zip(used)
+        .filter(|(_, used)| *used)
+        .map(|(feature, _)| feature.to_string())
+        .collect()
+}
+
 impl Default for ProjectSettings {
     fn default() -> Self {
         ProjectSettings {
             mask_size: 500,
             last_selected: None,
+            soft_key_size: None,
+            colour_roles: ColourRoles::default(),
+            style_presets: Vec::new(),
+            watch_expressions: Vec::new(),
+            state_scenarios: Vec::new(),
+            authors: Vec::new(),
+            golden_pool: None,
+            bookmarks: Vec::new(),
+            terminal_profiles: Vec::new(),
+            active_profile: 0,
+            save_history: false,
+            history_limits: HistoryLimits::default(),
+            pool_version: PoolVersion::default(),
         }
     }
 }
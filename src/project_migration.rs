@@ -0,0 +1,191 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Versions of the project file format. Older files are migrated step by step to the current
+//! version when they are loaded, and a project can be written in an older version for designers
+//! that can not open the current one yet. Every file lists the annotation features it uses, so a
+//! designer can warn before saving drops annotations it does not know.
+
+use serde_json::{Map, Value};
+
+/// Current project file format version
+pub const PROJECT_FILE_VERSION: u32 = 2;
+
+/// Oldest version a project can be written in
+pub const OLDEST_PROJECT_FILE_VERSION: u32 = 1;
+
+/// Annotations this designer stores in project files
+pub const KNOWN_FEATURES: [&str; 8] = [
+    "names",
+    "notes",
+    "colour_roles",
+    "reviews",
+    "locks",
+    "translations",
+    "style_presets",
+    "history",
+];
+
+/// A change of the format from the previous version to `version`
+struct Migration {
+    version: u32,
+    description: &'static str,
+    upgrade: fn(&mut Map<String, Value>),
+    downgrade: fn(&mut Map<String, Value>),
+}
+
+const MIGRATIONS: [Migration; 1] = [Migration {
+    version: 2,
+    description: "Record the designer version and the annotation features the project uses",
+    upgrade: upgrade_to_2,
+    downgrade: downgrade_from_2,
+}];
+
+fn upgrade_to_2(file: &mut Map<String, Value>) {
+    // The features of older files are not known, they are listed again when the file is saved
+    file.entry("features")
+        .or_insert_with(|| Value::Array(Vec::new()));
+}
+
+fn downgrade_from_2(file: &mut Map<String, Value>) {
+    file.remove("designer_version");
+    file.remove("features");
+}
+
+/// Format of a loaded project file and the version the project is saved in
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectFileFormat {
+    /// Version of the loaded file, the current version for new projects
+    pub loaded_version: u32,
+    pub save_version: u32,
+
+    /// Version of the designer that wrote the loaded file, if it is known
+    pub designer_version: Option<String>,
+
+    /// Annotations of the loaded file this designer does not know, they are lost when saving
+    pub unknown_features: Vec<String>,
+
+    /// Descriptions of the migrations done while loading
+    pub migrations: Vec<&'static str>,
+}
+
+impl Default for ProjectFileFormat {
+    fn default() -> Self {
+        ProjectFileFormat {
+            loaded_version: PROJECT_FILE_VERSION,
+            save_version: PROJECT_FILE_VERSION,
+            designer_version: None,
+            unknown_features: Vec::new(),
+            migrations: Vec::new(),
+        }
+    }
+}
+
+impl ProjectFileFormat {
+    /// Whether the file was written by a newer designer, saving drops what this one does not know
+    pub fn is_newer(&self) -> bool {
+        self.loaded_version > PROJECT_FILE_VERSION || !self.unknown_features.is_empty()
+    }
+
+    /// Whether the user should decide how the project is saved
+    pub fn needs_prompt(&self) -> bool {
+        self.loaded_version != PROJECT_FILE_VERSION || !self.unknown_features.is_empty()
+    }
+
+    /// What is dropped when the project is saved in an older version
+    pub fn downgrade_losses(version: u32) -> Vec<&'static str> {
+        MIGRATIONS
+            .iter()
+            .filter(|migration| migration.version > version)
+            .map(|migration| migration.description)
+            .collect()
+    }
+}
+
+/// Migrate a project file to the current version, returns the format the file had
+pub fn upgrade_project(file: &mut Value) -> ProjectFileFormat {
+    let loaded_version = file
+        .get("version")
+        .and_then(Value::as_u64)
+        .map_or(OLDEST_PROJECT_FILE_VERSION, |version| version as u32);
+    let mut format = ProjectFileFormat {
+        loaded_version,
+        designer_version: file
+            .get("designer_version")
+            .and_then(Value::as_str)
+            .map(str::to_string),
+        unknown_features: file
+            .get("features")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+            .filter_map(Value::as_str)
+            .filter(|feature| !KNOWN_FEATURES.contains(feature))
+            .map(str::to_string)
+            .collect(),
+        ..Default::default()
+    };
+    if let Some(map) = file.as_object_mut() {
+        for migration in MIGRATIONS.iter().filter(|m| m.version > loaded_version) {
+            (migration.upgrade)(map);
+            format.migrations.push(migration.description);
+        }
+        map.insert("version".to_string(), PROJECT_FILE_VERSION.into());
+    }
+    format
+}
+
+/// Write a project file of the current version as an older version
+pub fn downgrade_project(file: &mut Value, version: u32) {
+    let version = version.clamp(OLDEST_PROJECT_FILE_VERSION, PROJECT_FILE_VERSION);
+    if let Some(map) = file.as_object_mut() {
+        for migration in MIGRATIONS.iter().rev().filter(|m| m.version > version) {
+            (migration.downgrade)(map);
+        }
+        map.insert("version".to_string(), version.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn version_1_files_survive_a_round_trip() {
+        let mut file = json!({ "object_pool_data": [1, 2, 3] });
+        let format = upgrade_project(&mut file);
+        assert_eq!(format.loaded_version, 1);
+        assert_eq!(format.migrations.len(), 1);
+        assert!(format.needs_prompt());
+        assert!(!format.is_newer());
+        assert_eq!(file["version"], json!(2));
+        assert_eq!(file["features"], json!([]));
+
+        downgrade_project(&mut file, 1);
+        assert_eq!(file, json!({ "object_pool_data": [1, 2, 3], "version": 1 }));
+    }
+
+    #[test]
+    fn newer_files_are_detected() {
+        let mut file = json!({ "version": PROJECT_FILE_VERSION + 1, "designer_version": "9.9" });
+        let format = upgrade_project(&mut file);
+        assert!(format.is_newer());
+        assert!(format.migrations.is_empty());
+        assert_eq!(format.designer_version.as_deref(), Some("9.9"));
+    }
+
+    #[test]
+    fn unknown_features_are_listed() {
+        let mut file = json!({ "version": 2, "features": ["names", "layers"] });
+        let format = upgrade_project(&mut file);
+        assert_eq!(format.unknown_features, vec!["layers".to_string()]);
+        assert!(format.is_newer());
+        assert!(format.needs_prompt());
+        assert_eq!(
+            ProjectFileFormat::downgrade_losses(1),
+            vec![MIGRATIONS[0].description]
+        );
+    }
+}
@@ -0,0 +1,215 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::sync::Arc;
+
+use eframe::egui;
+
+use crate::preview_state::with_preview_state;
+use crate::regression::{compare_images, ComparisonResult, DEFAULT_TOLERANCE};
+
+const TEXTURE_ID: &str = "reference_overlay_texture";
+const DIFFERENCES_TEXTURE_ID: &str = "reference_overlay_differences_texture";
+
+/// A screenshot of a real terminal or of AgIsoVirtualTerminal, shown over the preview to see
+/// where the rendering of the designer differs from real hardware
+#[derive(Debug, Clone)]
+pub struct ReferenceOverlay {
+    pub name: String,
+    pub image: Arc<egui::ColorImage>,
+    pub visible: bool,
+
+    /// Opacity of the screenshot over the preview, from 0 to 1
+    pub opacity: f32,
+
+    /// Position of the screenshot relative to the top left corner of the mask, to align it
+    pub offset: (i16, i16),
+
+    /// Allowed difference per colour channel when comparing
+    pub tolerance: u8,
+
+    /// Show the differing pixels of the last comparison instead of the screenshot
+    pub show_differences: bool,
+
+    /// Differing and total pixels of the last comparison
+    pub differences: Option<(usize, usize)>,
+
+    /// Where the mask was last drawn
+    mask_rect: Option<egui::Rect>,
+
+    /// A comparison is running, the overlay is hidden so the screenshot only has the preview
+    comparing: bool,
+    screenshot_requested: bool,
+}
+
+/// Load a screenshot as the reference overlay of the preview
+pub fn load_reference_overlay(
+    ctx: &egui::Context,
+    name: String,
+    data: &[u8],
+) -> Result<(), String> {
+    let image = image::load_from_memory(data)
+        .map_err(|e| format!("Failed to decode {}: {}", name, e))?
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    let image = egui::ColorImage::from_rgba_unmultiplied(size, image.as_raw());
+    let texture = ctx.load_texture(TEXTURE_ID, image.clone(), egui::TextureOptions::NEAREST);
+    ctx.data_mut(|data| data.insert_temp(egui::Id::new(TEXTURE_ID), texture));
+    with_preview_state(ctx, |state| {
+        state.reference = Some(ReferenceOverlay {
+            name,
+            image: Arc::new(image),
+            visible: true,
+            opacity: 0.5,
+            offset: (0, 0),
+            tolerance: DEFAULT_TOLERANCE,
+            show_differences: false,
+            differences: None,
+            mask_rect: None,
+            comparing: false,
+            screenshot_requested: false,
+        });
+    });
+    Ok(())
+}
+
+/// Draw the reference overlay over the mask that was drawn in `mask_rect`
+pub fn render_reference_overlay(ui: &egui::Ui, mask_rect: egui::Rect) {
+    let Some(overlay) = with_preview_state(ui.ctx(), |state| {
+        let overlay = state.reference.as_mut()?;
+        overlay.mask_rect = Some(mask_rect);
+        Some(overlay.clone())
+    }) else {
+        return;
+    };
+    if !overlay.visible || overlay.comparing {
+        return;
+    }
+
+    let tint = egui::Color32::WHITE.gamma_multiply(overlay.opacity);
+    let uv = egui::Rect::from_min_max(egui::pos2(0.0, 0.0), egui::pos2(1.0, 1.0));
+    let painter = ui.painter_at(mask_rect);
+    if overlay.show_differences {
+        let texture = ui.data(|data| {
+            data.get_temp::<egui::TextureHandle>(egui::Id::new(DIFFERENCES_TEXTURE_ID))
+        });
+        if let Some(texture) = texture {
+            painter.image(texture.id(), mask_rect, uv, tint);
+        }
+        return;
+    }
+    let texture = ui.data(|data| data.get_temp::<egui::TextureHandle>(egui::Id::new(TEXTURE_ID)));
+    if let Some(texture) = texture {
+        let min = mask_rect.min + egui::vec2(overlay.offset.0 as f32, overlay.offset.1 as f32);
+        let size = egui::vec2(overlay.image.size[0] as f32, overlay.image.size[1] as f32);
+        painter.image(texture.id(), egui::Rect::from_min_size(min, size), uv, tint);
+    }
+}
+
+/// Apply the settings the user can change, e.g. the opacity and offset, of an edited copy of the
+/// reference overlay
+pub fn set_reference_settings(ctx: &egui::Context, edited: &ReferenceOverlay) {
+    with_preview_state(ctx, |state| {
+        if let Some(overlay) = &mut state.reference {
+            overlay.visible = edited.visible;
+            overlay.opacity = edited.opacity;
+            overlay.offset = edited.offset;
+            overlay.tolerance = edited.tolerance;
+            overlay.show_differences = edited.show_differences;
+        }
+    });
+}
+
+/// Compare the preview with the reference overlay, the result is available after a few frames
+pub fn request_reference_comparison(ctx: &egui::Context) {
+    with_preview_state(ctx, |state| {
+        if let Some(overlay) = &mut state.reference {
+            overlay.comparing = true;
+            overlay.screenshot_requested = false;
+        }
+    });
+    ctx.request_repaint();
+}
+
+/// The screenshot aligned to the rendered mask, in the physical pixels of the rendering
+fn aligned_reference(overlay: &ReferenceOverlay, size: [usize; 2], scale: f32) -> egui::ColorImage {
+    let mut aligned =
+        egui::ColorImage::new(size, vec![egui::Color32::TRANSPARENT; size[0] * size[1]]);
+    for y in 0..size[1] {
+        for x in 0..size[0] {
+            let reference_x = (x as f32 / scale).floor() as i64 - overlay.offset.0 as i64;
+            let reference_y = (y as f32 / scale).floor() as i64 - overlay.offset.1 as i64;
+            if (0..overlay.image.size[0] as i64).contains(&reference_x)
+                && (0..overlay.image.size[1] as i64).contains(&reference_y)
+            {
+                aligned.pixels[y * size[0] + x] = overlay.image.pixels
+                    [reference_y as usize * overlay.image.size[0] + reference_x as usize];
+            }
+        }
+    }
+    aligned
+}
+
+/// Drive a requested comparison, call this once every frame
+pub fn update_reference_comparison(ctx: &egui::Context) {
+    let Some(overlay) = with_preview_state(ctx, |state| state.reference.clone()) else {
+        return;
+    };
+    if !overlay.comparing {
+        return;
+    }
+    if !overlay.screenshot_requested {
+        // The overlay is hidden from this frame on, so the screenshot only shows the preview
+        with_preview_state(ctx, |state| {
+            if let Some(overlay) = &mut state.reference {
+                overlay.screenshot_requested = true;
+            }
+        });
+        ctx.send_viewport_cmd(egui::ViewportCommand::Screenshot(Default::default()));
+        ctx.request_repaint();
+        return;
+    }
+    let screenshot = ctx.input(|i| {
+        i.raw.events.iter().find_map(|e| match e {
+            egui::Event::Screenshot { image, .. } => Some(image.clone()),
+            _ => None,
+        })
+    });
+    let Some(screenshot) = screenshot else {
+        ctx.request_repaint();
+        return;
+    };
+
+    let differences = overlay.mask_rect.map(|rect| {
+        let actual = screenshot.region(&rect, Some(ctx.pixels_per_point()));
+        let reference = aligned_reference(&overlay, actual.size, ctx.pixels_per_point());
+        let total = actual.pixels.len();
+        match compare_images(&actual, &reference, overlay.tolerance) {
+            ComparisonResult::Different {
+                differing_pixels,
+                diff,
+            } => {
+                let texture =
+                    ctx.load_texture(DIFFERENCES_TEXTURE_ID, diff, egui::TextureOptions::NEAREST);
+                ctx.data_mut(|data| {
+                    data.insert_temp(egui::Id::new(DIFFERENCES_TEXTURE_ID), texture)
+                });
+                (differing_pixels, total)
+            }
+            _ => {
+                ctx.data_mut(|data| {
+                    data.remove::<egui::TextureHandle>(egui::Id::new(DIFFERENCES_TEXTURE_ID))
+                });
+                (0, total)
+            }
+        }
+    });
+    with_preview_state(ctx, |state| {
+        if let Some(overlay) = &mut state.reference {
+            overlay.comparing = false;
+            overlay.differences = differences;
+            overlay.show_differences = differences.is_some_and(|(differing, _)| differing > 0);
+        }
+    });
+}
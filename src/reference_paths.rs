@@ -0,0 +1,280 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{HashMap, HashSet};
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::{NullableObjectId, ObjectId, ObjectPool, ObjectRef};
+
+/// Stop searching after this many paths, heavily reused objects can have a lot of them
+const MAX_REFERENCE_PATHS: usize = 32;
+
+/// Map every object to the objects that reference it
+pub fn parent_map(pool: &ObjectPool) -> HashMap<ObjectId, Vec<ObjectId>> {
+    let mut parents: HashMap<ObjectId, Vec<ObjectId>> = HashMap::new();
+    for object in pool.objects() {
+        for child in object.referenced_objects() {
+            let entry = parents.entry(child).or_default();
+            if !entry.contains(&object.id()) {
+                entry.push(object.id());
+            }
+        }
+    }
+    parents
+}
+
+/// The object and everything it references, directly or indirectly
+pub fn subtree(pool: &ObjectPool, root: ObjectId) -> Vec<ObjectId> {
+    let mut objects = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![root];
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        let Some(object) = pool.object_by_id(id) else {
+            continue;
+        };
+        objects.push(id);
+        stack.extend(object.referenced_objects().into_iter().rev());
+    }
+    objects
+}
+
+/// All reference paths from an unreferenced object (e.g. the working set) down to the given object.
+/// Each path starts at the root and ends with the object itself.
+pub fn reference_paths(pool: &ObjectPool, id: ObjectId) -> Vec<Vec<ObjectId>> {
+    let parents = parent_map(pool);
+    let mut paths = Vec::new();
+    let mut current = vec![id];
+    collect_paths(&parents, &mut current, &mut paths);
+    paths
+}
+
+/// Walk up the parents depth first, `current` holds the path from the object upwards
+fn collect_paths(
+    parents: &HashMap<ObjectId, Vec<ObjectId>>,
+    current: &mut Vec<ObjectId>,
+    paths: &mut Vec<Vec<ObjectId>>,
+) {
+    if paths.len() >= MAX_REFERENCE_PATHS {
+        return;
+    }
+    let last = *current.last().unwrap();
+    let candidates: Vec<ObjectId> = parents
+        .get(&last)
+        .map(|p| p.iter().filter(|p| !current.contains(p)).copied().collect())
+        .unwrap_or_default();
+
+    if candidates.is_empty() {
+        paths.push(current.iter().rev().copied().collect());
+        return;
+    }
+    for parent in candidates {
+        current.push(parent);
+        collect_paths(parents, current, paths);
+        current.pop();
+    }
+}
+
+/// Call the visitor of the kind of reference for every reference of an object: required IDs,
+/// nullable IDs, positioned children and lists of IDs
+fn visit_references_mut(
+    object: &mut Object,
+    id: &mut dyn FnMut(&mut ObjectId),
+    nullable: &mut dyn FnMut(&mut NullableObjectId),
+    object_refs: &mut dyn FnMut(&mut Vec<ObjectRef>),
+    ids: &mut dyn FnMut(&mut Vec<ObjectId>),
+) {
+    match object {
+        Object::WorkingSet(o) => {
+            id(&mut o.active_mask);
+            object_refs(&mut o.object_refs);
+        }
+        Object::DataMask(o) => {
+            nullable(&mut o.soft_key_mask);
+            object_refs(&mut o.object_refs);
+        }
+        Object::AlarmMask(o) => {
+            nullable(&mut o.soft_key_mask);
+            object_refs(&mut o.object_refs);
+        }
+        Object::Container(o) => object_refs(&mut o.object_refs),
+        Object::SoftKeyMask(o) => ids(&mut o.objects),
+        Object::Key(o) => object_refs(&mut o.object_refs),
+        Object::Button(o) => object_refs(&mut o.object_refs),
+        Object::InputBoolean(o) => {
+            id(&mut o.foreground_colour);
+            nullable(&mut o.variable_reference);
+        }
+        Object::InputString(o) => {
+            id(&mut o.font_attributes);
+            nullable(&mut o.input_attributes);
+            nullable(&mut o.variable_reference);
+        }
+        Object::InputNumber(o) => {
+            id(&mut o.font_attributes);
+            nullable(&mut o.variable_reference);
+        }
+        Object::InputList(o) => {
+            nullable(&mut o.variable_reference);
+            o.list_items.iter_mut().for_each(&mut *nullable);
+        }
+        Object::OutputString(o) => {
+            id(&mut o.font_attributes);
+            nullable(&mut o.variable_reference);
+        }
+        Object::OutputNumber(o) => {
+            id(&mut o.font_attributes);
+            nullable(&mut o.variable_reference);
+        }
+        Object::OutputList(o) => {
+            nullable(&mut o.variable_reference);
+            o.list_items.iter_mut().for_each(&mut *nullable);
+        }
+        Object::OutputLine(o) => id(&mut o.line_attributes),
+        Object::OutputRectangle(o) => {
+            id(&mut o.line_attributes);
+            nullable(&mut o.fill_attributes);
+        }
+        Object::OutputEllipse(o) => {
+            id(&mut o.line_attributes);
+            nullable(&mut o.fill_attributes);
+        }
+        Object::OutputPolygon(o) => {
+            id(&mut o.line_attributes);
+            nullable(&mut o.fill_attributes);
+        }
+        Object::OutputMeter(o) => nullable(&mut o.variable_reference),
+        Object::OutputLinearBarGraph(o) => {
+            nullable(&mut o.variable_reference);
+            nullable(&mut o.target_value_variable_reference);
+        }
+        Object::OutputArchedBarGraph(o) => {
+            nullable(&mut o.variable_reference);
+            nullable(&mut o.target_value_variable_reference);
+        }
+        Object::FillAttributes(o) => nullable(&mut o.fill_pattern),
+        Object::ObjectPointer(o) => nullable(&mut o.value),
+        Object::AuxiliaryFunctionType1(o) => object_refs(&mut o.object_refs),
+        Object::AuxiliaryInputType1(o) => object_refs(&mut o.object_refs),
+        Object::AuxiliaryFunctionType2(o) => object_refs(&mut o.object_refs),
+        Object::AuxiliaryInputType2(o) => object_refs(&mut o.object_refs),
+        Object::AuxiliaryControlDesignatorType2(o) => nullable(&mut o.auxiliary_object_id),
+        Object::WindowMask(o) => {
+            id(&mut o.name);
+            nullable(&mut o.window_title);
+            nullable(&mut o.window_icon);
+            o.objects.iter_mut().for_each(&mut *nullable);
+            object_refs(&mut o.object_refs);
+        }
+        Object::KeyGroup(o) => {
+            id(&mut o.name);
+            nullable(&mut o.key_group_icon);
+            ids(&mut o.objects);
+        }
+        Object::GraphicsContext(o) => {
+            nullable(&mut o.font_attributes_object);
+            nullable(&mut o.line_attributes_object);
+            nullable(&mut o.fill_attributes_object);
+        }
+        Object::ObjectLabelReferenceList(o) => {
+            for label in o.object_labels.iter_mut() {
+                id(&mut label.id);
+                nullable(&mut label.string_variable_reference);
+                nullable(&mut label.graphic_representation);
+            }
+        }
+        Object::ExternalObjectDefinition(o) => o.objects.iter_mut().for_each(&mut *nullable),
+        Object::ExternalObjectPointer(o) => {
+            nullable(&mut o.default_object_id);
+            nullable(&mut o.external_reference_name_id);
+        }
+        Object::Animation(o) => object_refs(&mut o.object_refs),
+        Object::WorkingSetSpecialControls(o) => {
+            nullable(&mut o.id_of_colour_map);
+            nullable(&mut o.id_of_colour_palette);
+        }
+        Object::ScaledGraphic(o) => nullable(&mut o.value),
+        Object::PictureGraphic(_)
+        | Object::NumberVariable(_)
+        | Object::StringVariable(_)
+        | Object::FontAttributes(_)
+        | Object::LineAttributes(_)
+        | Object::InputAttributes(_)
+        | Object::Macro(_)
+        | Object::ExtendedInputAttributes(_)
+        | Object::ColourMap(_)
+        | Object::ExternalReferenceName(_)
+        | Object::ColourPalette(_)
+        | Object::GraphicData(_) => {}
+    }
+}
+
+/// Replace every reference to an object in `map` by the object it maps to.
+/// The ID of the object itself is not changed.
+pub fn remap_references(object: &mut Object, map: &HashMap<ObjectId, ObjectId>) {
+    let mut id = |id: &mut ObjectId| {
+        if let Some(new_id) = map.get(id) {
+            *id = *new_id;
+        }
+    };
+    let mut nullable = |nullable: &mut NullableObjectId| {
+        if let Some(old_id) = nullable.0 {
+            if let Some(new_id) = map.get(&old_id) {
+                nullable.0 = Some(*new_id);
+            }
+        }
+    };
+    let mut object_refs = |refs: &mut Vec<ObjectRef>| {
+        for r in refs.iter_mut() {
+            if let Some(new_id) = map.get(&r.id) {
+                r.id = *new_id;
+            }
+        }
+    };
+    let mut ids = |ids: &mut Vec<ObjectId>| {
+        for id in ids.iter_mut() {
+            if let Some(new_id) = map.get(id) {
+                *id = *new_id;
+            }
+        }
+    };
+    visit_references_mut(object, &mut id, &mut nullable, &mut object_refs, &mut ids);
+}
+
+/// Point every reference of an object to `placeholder`, so objects can be compared without the
+/// IDs they reference. Empty references stay empty.
+pub fn mask_references(object: &mut Object, placeholder: ObjectId) {
+    visit_references_mut(
+        object,
+        &mut |id| *id = placeholder,
+        &mut |nullable| {
+            if nullable.0.is_some() {
+                nullable.0 = Some(placeholder);
+            }
+        },
+        &mut |refs| refs.iter_mut().for_each(|r| r.id = placeholder),
+        &mut |ids| ids.iter_mut().for_each(|id| *id = placeholder),
+    );
+}
+
+/// Remove every reference to `target` from an object: positioned children and list entries are
+/// removed and nullable references are cleared. Returns false if a required reference to the
+/// target remains, it has to point to another object instead.
+pub fn remove_references(object: &mut Object, target: ObjectId) -> bool {
+    let mut required = false;
+    visit_references_mut(
+        object,
+        &mut |id| required |= *id == target,
+        &mut |nullable| {
+            if nullable.0 == Some(target) {
+                nullable.0 = None;
+            }
+        },
+        &mut |refs| refs.retain(|r| r.id != target),
+        &mut |ids| ids.retain(|id| *id != target),
+    );
+    !required
+}
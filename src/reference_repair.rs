@@ -0,0 +1,223 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Guided fixes for references to objects that are not in the pool: remove the reference, point
+//! it to another object, or recreate a stub object with the missing ID.
+
+use std::collections::{BTreeSet, HashMap};
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::vt_version::VtVersion;
+use ag_iso_stack::object_pool::{NullableObjectId, ObjectId, ObjectPool, ObjectType};
+
+use crate::allowed_object_relationships::get_allowed_child_refs;
+use crate::layout::object_refs;
+use crate::object_defaults::default_object;
+use crate::reference_paths::{remap_references, remove_references};
+use crate::{ChangeCategory, EditorProject, ObjectInfo};
+
+/// A reference of an object to an object that is not in the pool
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct DanglingReference {
+    /// The object with the reference
+    pub object: ObjectId,
+
+    /// The referenced object that does not exist
+    pub missing: ObjectId,
+}
+
+/// Every dangling reference in the pool, once per referencing and missing object
+pub fn dangling_references(pool: &ObjectPool) -> Vec<DanglingReference> {
+    let mut dangling = BTreeSet::new();
+    for object in pool.objects() {
+        for missing in object.referenced_objects() {
+            if pool.object_by_id(missing).is_none() {
+                dangling.insert(DanglingReference {
+                    object: object.id(),
+                    missing,
+                });
+            }
+        }
+    }
+    dangling.into_iter().collect()
+}
+
+/// The types of the attribute objects an object references, as far as they are known
+fn attribute_types(object: &Object, target: ObjectId) -> Vec<ObjectType> {
+    let is = |id: ObjectId| id == target;
+    let is_nullable = |id: &NullableObjectId| id.0 == Some(target);
+    let number_variable = vec![ObjectType::NumberVariable];
+    match object {
+        Object::WorkingSet(o) if is(o.active_mask) => {
+            vec![ObjectType::DataMask, ObjectType::AlarmMask]
+        }
+        Object::DataMask(o) if is_nullable(&o.soft_key_mask) => vec![ObjectType::SoftKeyMask],
+        Object::AlarmMask(o) if is_nullable(&o.soft_key_mask) => vec![ObjectType::SoftKeyMask],
+        Object::InputBoolean(o) if is(o.foreground_colour) => vec![ObjectType::FontAttributes],
+        Object::InputBoolean(o) if is_nullable(&o.variable_reference) => number_variable,
+        Object::InputString(o) if is(o.font_attributes) => vec![ObjectType::FontAttributes],
+        Object::InputString(o) if is_nullable(&o.input_attributes) => vec![
+            ObjectType::InputAttributes,
+            ObjectType::ExtendedInputAttributes,
+        ],
+        Object::InputString(o) if is_nullable(&o.variable_reference) => {
+            vec![ObjectType::StringVariable]
+        }
+        Object::OutputString(o) if is(o.font_attributes) => vec![ObjectType::FontAttributes],
+        Object::OutputString(o) if is_nullable(&o.variable_reference) => {
+            vec![ObjectType::StringVariable]
+        }
+        Object::InputNumber(o) if is(o.font_attributes) => vec![ObjectType::FontAttributes],
+        Object::InputNumber(o) if is_nullable(&o.variable_reference) => number_variable,
+        Object::OutputNumber(o) if is(o.font_attributes) => vec![ObjectType::FontAttributes],
+        Object::OutputNumber(o) if is_nullable(&o.variable_reference) => number_variable,
+        Object::InputList(o) if is_nullable(&o.variable_reference) => number_variable,
+        Object::OutputList(o) if is_nullable(&o.variable_reference) => number_variable,
+        Object::OutputMeter(o) if is_nullable(&o.variable_reference) => number_variable,
+        Object::OutputLinearBarGraph(_) | Object::OutputArchedBarGraph(_) => number_variable,
+        Object::OutputLine(_) => vec![ObjectType::LineAttributes],
+        Object::OutputRectangle(o) if is(o.line_attributes) => vec![ObjectType::LineAttributes],
+        Object::OutputEllipse(o) if is(o.line_attributes) => vec![ObjectType::LineAttributes],
+        Object::OutputPolygon(o) if is(o.line_attributes) => vec![ObjectType::LineAttributes],
+        Object::OutputRectangle(_) | Object::OutputEllipse(_) | Object::OutputPolygon(_) => {
+            vec![ObjectType::FillAttributes]
+        }
+        Object::FillAttributes(_) => vec![ObjectType::PictureGraphic],
+        Object::GraphicsContext(o) if is_nullable(&o.font_attributes_object) => {
+            vec![ObjectType::FontAttributes]
+        }
+        Object::GraphicsContext(o) if is_nullable(&o.line_attributes_object) => {
+            vec![ObjectType::LineAttributes]
+        }
+        Object::GraphicsContext(o) if is_nullable(&o.fill_attributes_object) => {
+            vec![ObjectType::FillAttributes]
+        }
+        Object::WorkingSetSpecialControls(o) if is_nullable(&o.id_of_colour_map) => {
+            vec![ObjectType::ColourMap]
+        }
+        Object::WorkingSetSpecialControls(o) if is_nullable(&o.id_of_colour_palette) => {
+            vec![ObjectType::ColourPalette]
+        }
+        _ => vec![],
+    }
+}
+
+/// The object types the missing object can have, judged by how it is referenced. The most likely
+/// type comes first.
+pub fn expected_types(object: &Object, missing: ObjectId) -> Vec<ObjectType> {
+    let types = attribute_types(object, missing);
+    if !types.is_empty() {
+        return types;
+    }
+    // Children and list entries, any version is allowed as the pool already references them
+    let is_child = object_refs(object).is_some_and(|refs| refs.iter().any(|r| r.id == missing));
+    if is_child || matches!(object, Object::SoftKeyMask(_) | Object::KeyGroup(_)) {
+        return get_allowed_child_refs(object.object_type(), VtVersion::Version6);
+    }
+    match object {
+        Object::InputList(_) | Object::OutputList(_) => {
+            get_allowed_child_refs(object.object_type(), VtVersion::Version6)
+        }
+        _ => vec![],
+    }
+}
+
+fn object_label(project: &EditorProject, id: ObjectId) -> String {
+    project
+        .get_pool()
+        .object_by_id(id)
+        .map_or(id.value().to_string(), |object| {
+            project.get_object_info(object).get_name(object)
+        })
+}
+
+/// Remove the reference from the object, see [`remove_references`]
+pub fn remove_dangling_reference(
+    project: &EditorProject,
+    dangling: DanglingReference,
+) -> Result<(), String> {
+    let mut pool = project.get_mut_pool().borrow_mut();
+    let object = pool
+        .object_mut_by_id(dangling.object)
+        .ok_or_else(|| format!("Object {} does not exist", dangling.object.value()))?;
+    let mut edited = object.clone();
+    if !remove_references(&mut edited, dangling.missing) {
+        return Err(format!(
+            "{} requires the reference to {}, point it to another object or recreate the object",
+            object_label(project, dangling.object),
+            dangling.missing.value()
+        ));
+    }
+    *object = edited;
+    drop(pool);
+    project.describe_change(
+        format!(
+            "Remove the reference to missing object {} from {}",
+            dangling.missing.value(),
+            object_label(project, dangling.object)
+        ),
+        ChangeCategory::Edit,
+    );
+    Ok(())
+}
+
+/// Point the reference to another object
+pub fn redirect_dangling_reference(
+    project: &EditorProject,
+    dangling: DanglingReference,
+    replacement: ObjectId,
+) -> Result<(), String> {
+    if project.get_pool().object_by_id(replacement).is_none() {
+        return Err(format!("Object {} does not exist", replacement.value()));
+    }
+    let mut pool = project.get_mut_pool().borrow_mut();
+    let object = pool
+        .object_mut_by_id(dangling.object)
+        .ok_or_else(|| format!("Object {} does not exist", dangling.object.value()))?;
+    remap_references(object, &HashMap::from([(dangling.missing, replacement)]));
+    drop(pool);
+    project.describe_change(
+        format!(
+            "Point the reference of {} to missing object {} to {}",
+            object_label(project, dangling.object),
+            dangling.missing.value(),
+            object_label(project, replacement)
+        ),
+        ChangeCategory::Edit,
+    );
+    Ok(())
+}
+
+/// Create an object with default attributes and the missing ID, so the reference is valid again
+pub fn recreate_missing_object(
+    project: &EditorProject,
+    dangling: DanglingReference,
+    object_type: ObjectType,
+) -> Result<(), String> {
+    let mut pool = project.get_mut_pool().borrow_mut();
+    if pool.object_by_id(dangling.missing).is_some() {
+        return Err(format!(
+            "Object {} already exists",
+            dangling.missing.value()
+        ));
+    }
+    let mut object = default_object(object_type);
+    *object.mut_id() = dangling.missing;
+    project
+        .object_info
+        .borrow_mut()
+        .insert(dangling.missing, ObjectInfo::new(&object));
+    project.apply_smart_naming_to_object(&object);
+    pool.add(object);
+    drop(pool);
+    project.describe_change(
+        format!(
+            "Recreate missing object {} as {:?}",
+            dangling.missing.value(),
+            object_type
+        ),
+        ChangeCategory::Edit,
+    );
+    Ok(())
+}
@@ -0,0 +1,236 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::path::{Path, PathBuf};
+
+use ag_iso_stack::object_pool::ObjectId;
+use eframe::egui;
+
+use crate::mask_capture::MaskCapture;
+use crate::EditorProject;
+
+/// Default allowed difference per colour channel
+pub const DEFAULT_TOLERANCE: u8 = 8;
+
+/// Outcome of comparing a single rendered mask against its baseline
+#[derive(Clone)]
+pub enum ComparisonResult {
+    Match,
+    Different {
+        differing_pixels: usize,
+        /// The rendered mask with every differing pixel marked red
+        diff: egui::ColorImage,
+    },
+    SizeMismatch {
+        baseline: [usize; 2],
+        actual: [usize; 2],
+    },
+    MissingBaseline,
+    BaselineUpdated,
+    Error(String),
+}
+
+impl ComparisonResult {
+    /// Returns true if the result should fail the regression run
+    pub fn is_failure(&self) -> bool {
+        !matches!(
+            self,
+            ComparisonResult::Match | ComparisonResult::BaselineUpdated
+        )
+    }
+
+    /// Short human readable description
+    pub fn summary(&self) -> String {
+        match self {
+            ComparisonResult::Match => "Matches baseline".to_string(),
+            ComparisonResult::Different {
+                differing_pixels, ..
+            } => format!("{} pixels differ", differing_pixels),
+            ComparisonResult::SizeMismatch { baseline, actual } => format!(
+                "Size changed from {}x{} to {}x{}",
+                baseline[0], baseline[1], actual[0], actual[1]
+            ),
+            ComparisonResult::MissingBaseline => "No baseline image".to_string(),
+            ComparisonResult::BaselineUpdated => "Baseline updated".to_string(),
+            ComparisonResult::Error(e) => e.clone(),
+        }
+    }
+}
+
+/// Result of a single mask in a regression run
+#[derive(Clone)]
+pub struct MaskResult {
+    pub mask: ObjectId,
+    pub name: String,
+    pub result: ComparisonResult,
+}
+
+/// Compare two images, allowing each colour channel to differ by at most `tolerance`
+pub fn compare_images(
+    actual: &egui::ColorImage,
+    baseline: &egui::ColorImage,
+    tolerance: u8,
+) -> ComparisonResult {
+    if actual.size != baseline.size {
+        return ComparisonResult::SizeMismatch {
+            baseline: baseline.size,
+            actual: actual.size,
+        };
+    }
+
+    let mut differing_pixels = 0;
+    let pixels = actual
+        .pixels
+        .iter()
+        .zip(&baseline.pixels)
+        .map(|(a, b)| {
+            let differs = a
+                .to_array()
+                .iter()
+                .zip(b.to_array())
+                .any(|(x, y)| x.abs_diff(y) > tolerance);
+            if differs {
+                differing_pixels += 1;
+                egui::Color32::RED
+            } else {
+                a.gamma_multiply(0.3)
+            }
+        })
+        .collect();
+
+    if differing_pixels == 0 {
+        ComparisonResult::Match
+    } else {
+        ComparisonResult::Different {
+            differing_pixels,
+            diff: egui::ColorImage::new(actual.size, pixels),
+        }
+    }
+}
+
+/// Path of the baseline image of a mask
+pub fn baseline_path(baseline_dir: &Path, mask: ObjectId) -> PathBuf {
+    baseline_dir.join(format!("mask_{}.png", mask.value()))
+}
+
+/// Load a PNG image from disk
+pub fn load_png(path: &Path) -> Result<egui::ColorImage, String> {
+    let image = image::open(path)
+        .map_err(|e| format!("Failed to load {}: {}", path.display(), e))?
+        .to_rgba8();
+    let size = [image.width() as usize, image.height() as usize];
+    Ok(egui::ColorImage::from_rgba_unmultiplied(
+        size,
+        image.as_flat_samples().as_slice(),
+    ))
+}
+
+/// Save an image to disk as PNG, creating the directory if needed
+pub fn save_png(path: &Path, image: &egui::ColorImage) -> Result<(), String> {
+    if let Some(dir) = path.parent() {
+        std::fs::create_dir_all(dir)
+            .map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+    }
+    let bytes: Vec<u8> = image
+        .pixels
+        .iter()
+        .flat_map(|p| p.to_srgba_unmultiplied())
+        .collect();
+    image::save_buffer(
+        path,
+        &bytes,
+        image.size[0] as u32,
+        image.size[1] as u32,
+        image::ColorType::Rgba8,
+    )
+    .map_err(|e| format!("Failed to save {}: {}", path.display(), e))
+}
+
+/// Renders every mask of a pool in turn, takes a screenshot of it and compares it against (or
+/// stores it as) the baseline image.
+pub struct RegressionRun {
+    pub baseline_dir: PathBuf,
+    pub tolerance: u8,
+
+    /// Store the rendered masks as the new baseline instead of comparing
+    pub update_baseline: bool,
+    pub results: Vec<MaskResult>,
+
+    capture: MaskCapture,
+}
+
+impl RegressionRun {
+    pub fn new(
+        project: &EditorProject,
+        baseline_dir: PathBuf,
+        tolerance: u8,
+        update_baseline: bool,
+    ) -> Self {
+        RegressionRun {
+            baseline_dir,
+            tolerance,
+            update_baseline,
+            results: Vec::new(),
+            capture: MaskCapture::new(project),
+        }
+    }
+
+    /// Only render the given masks
+    pub fn with_masks(mut self, masks: Vec<ObjectId>) -> Self {
+        self.capture = self.capture.with_masks(masks);
+        self
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.capture.is_finished()
+    }
+
+    /// Number of masks done and the total number of masks
+    pub fn progress(&self) -> (usize, usize) {
+        self.capture.progress()
+    }
+
+    /// Number of masks that failed the comparison
+    pub fn failures(&self) -> usize {
+        self.results
+            .iter()
+            .filter(|r| r.result.is_failure())
+            .count()
+    }
+
+    /// Drive the run, call this once every frame until the run is finished.
+    /// The current mask is rendered on top of the user interface while the run is active.
+    pub fn update(&mut self, ctx: &egui::Context, project: &EditorProject) {
+        let Some((mask, image)) = self.capture.update(ctx, project) else {
+            return;
+        };
+        let result = match image {
+            Ok(actual) => self.compare_mask(mask, actual),
+            Err(e) => ComparisonResult::Error(e),
+        };
+        let name = match project.get_pool().object_by_id(mask) {
+            Some(object) => project.get_object_info(object).get_name(object),
+            None => format!("{}", mask.value()),
+        };
+        self.results.push(MaskResult { mask, name, result });
+    }
+
+    /// Compare the rendered mask against the baseline, or store it as the new baseline
+    fn compare_mask(&self, mask: ObjectId, actual: egui::ColorImage) -> ComparisonResult {
+        let path = baseline_path(&self.baseline_dir, mask);
+        if self.update_baseline {
+            match save_png(&path, &actual) {
+                Ok(()) => ComparisonResult::BaselineUpdated,
+                Err(e) => ComparisonResult::Error(e),
+            }
+        } else if !path.exists() {
+            ComparisonResult::MissingBaseline
+        } else {
+            match load_png(&path) {
+                Ok(baseline) => compare_images(&actual, &baseline, self.tolerance),
+                Err(e) => ComparisonResult::Error(e),
+            }
+        }
+    }
+}
@@ -0,0 +1,312 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Local WebSocket server that accepts JSON-RPC 2.0 requests, e.g.
+//! `{"jsonrpc": "2.0", "id": 1, "method": "switch_mask", "params": {"id": 1000}}`.
+//! Requests are handed to the user interface thread, which executes them between frames.
+//!
+//! Clients authenticate with the token of the session, shown in the remote control window, as
+//! `Authorization: Bearer <token>` header or `?token=<token>` in the URL. Connections from web
+//! pages (with an `Origin` header) are refused, so a website can not control the designer.
+
+use std::collections::HashMap;
+use std::io::ErrorKind;
+use std::net::{Shutdown, SocketAddr, TcpListener, TcpStream};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+use std::time::Duration;
+
+use ag_iso_stack::object_pool::ObjectId;
+use eframe::egui;
+use serde_json::{json, Value};
+use socket2::{Domain, Protocol, Socket, Type};
+use tungstenite::handshake::server::{ErrorResponse, Request, Response};
+use tungstenite::http::StatusCode;
+use tungstenite::Message;
+
+/// Port the server listens on if none is given
+pub const DEFAULT_REMOTE_PORT: u16 = 9740;
+
+/// Environment variable with the token to use instead of a random one, for test benches that
+/// start the designer themselves
+pub const REMOTE_TOKEN_ENV: &str = "AG_ISO_DESIGNER_REMOTE_TOKEN";
+
+/// How often the server checks for new connections and whether it should stop
+const ACCEPT_POLL_INTERVAL: Duration = Duration::from_millis(100);
+
+/// JSON-RPC error
+#[derive(Debug, Clone, PartialEq)]
+pub struct RemoteError {
+    pub code: i64,
+    pub message: String,
+}
+
+impl RemoteError {
+    /// Generic failure of a command
+    pub fn new(message: impl Into<String>) -> Self {
+        RemoteError {
+            code: -32000,
+            message: message.into(),
+        }
+    }
+
+    pub fn method_not_found(method: &str) -> Self {
+        RemoteError {
+            code: -32601,
+            message: format!("Unknown method '{}'", method),
+        }
+    }
+
+    pub fn invalid_params(message: impl Into<String>) -> Self {
+        RemoteError {
+            code: -32602,
+            message: message.into(),
+        }
+    }
+}
+
+pub type RemoteResult = Result<Value, RemoteError>;
+
+/// A command received from a client, answer it with `respond`
+pub struct RemoteRequest {
+    pub method: String,
+    pub params: Value,
+    reply: Sender<RemoteResult>,
+}
+
+impl RemoteRequest {
+    /// Send the result back to the client
+    pub fn respond(self, result: RemoteResult) {
+        let _ = self.reply.send(result);
+    }
+
+    /// Get a required parameter
+    pub fn param(&self, name: &str) -> Result<&Value, RemoteError> {
+        self.params
+            .get(name)
+            .ok_or_else(|| RemoteError::invalid_params(format!("Missing parameter '{}'", name)))
+    }
+
+    pub fn param_str(&self, name: &str) -> Result<&str, RemoteError> {
+        self.param(name)?.as_str().ok_or_else(|| {
+            RemoteError::invalid_params(format!("Parameter '{}' must be a string", name))
+        })
+    }
+
+    pub fn param_object_id(&self, name: &str) -> Result<ObjectId, RemoteError> {
+        self.param(name)?
+            .as_u64()
+            .and_then(|id| u16::try_from(id).ok())
+            .and_then(|id| ObjectId::new(id).ok())
+            .ok_or_else(|| {
+                RemoteError::invalid_params(format!("Parameter '{}' must be an object ID", name))
+            })
+    }
+}
+
+/// Open client connections by connection number, shut down when the server stops
+type Connections = Arc<Mutex<HashMap<u64, TcpStream>>>;
+
+/// Background server, stops when dropped
+pub struct RemoteServer {
+    pub port: u16,
+
+    /// Secret clients have to present, new for every server start
+    pub token: String,
+    requests: Receiver<RemoteRequest>,
+    stop: Arc<AtomicBool>,
+    connections: Connections,
+    accept_thread: Option<JoinHandle<()>>,
+}
+
+/// Listen on localhost. The address can be reused right away, so the server can be restarted on
+/// the same port while connections of the previous one are still closing.
+fn bind_listener(port: u16) -> std::io::Result<TcpListener> {
+    let socket = Socket::new(Domain::IPV4, Type::STREAM, Some(Protocol::TCP))?;
+    socket.set_reuse_address(true)?;
+    socket.bind(&SocketAddr::from(([127, 0, 0, 1], port)).into())?;
+    socket.listen(128)?;
+    Ok(socket.into())
+}
+
+impl RemoteServer {
+    /// Listen on localhost only, the API gives full control over the designer
+    pub fn start(port: u16, ctx: egui::Context) -> std::io::Result<Self> {
+        let listener = bind_listener(port)?;
+        listener.set_nonblocking(true)?;
+        let (sender, requests) = channel();
+        let stop = Arc::new(AtomicBool::new(false));
+        let connections = Connections::default();
+        let token = std::env::var(REMOTE_TOKEN_ENV)
+            .ok()
+            .filter(|token| !token.is_empty())
+            .unwrap_or_else(|| uuid::Uuid::new_v4().simple().to_string());
+
+        let server_stop = stop.clone();
+        let server_connections = connections.clone();
+        let server_token = token.clone();
+        let accept_thread = std::thread::spawn(move || {
+            let mut next_connection = 0;
+            while !server_stop.load(Ordering::Relaxed) {
+                match listener.accept() {
+                    Ok((stream, address)) => {
+                        log::info!("Remote control client connected from {}", address);
+                        let connection = next_connection;
+                        next_connection += 1;
+                        if let Ok(clone) = stream.try_clone() {
+                            if let Ok(mut connections) = server_connections.lock() {
+                                connections.insert(connection, clone);
+                            }
+                        }
+                        let sender = sender.clone();
+                        let ctx = ctx.clone();
+                        let stop = server_stop.clone();
+                        let connections = server_connections.clone();
+                        let token = server_token.clone();
+                        std::thread::spawn(move || {
+                            if let Err(e) = serve_client(stream, &token, sender, ctx, stop) {
+                                log::warn!("Remote control connection closed: {}", e);
+                            }
+                            if let Ok(mut connections) = connections.lock() {
+                                connections.remove(&connection);
+                            }
+                        });
+                    }
+                    Err(e) if e.kind() == ErrorKind::WouldBlock => {
+                        std::thread::sleep(ACCEPT_POLL_INTERVAL)
+                    }
+                    Err(e) => {
+                        log::error!("Remote control server stopped: {}", e);
+                        break;
+                    }
+                }
+            }
+        });
+
+        Ok(RemoteServer {
+            port,
+            token,
+            requests,
+            stop,
+            connections,
+            accept_thread: Some(accept_thread),
+        })
+    }
+
+    /// Get the next request that is waiting to be executed
+    pub fn try_recv(&self) -> Option<RemoteRequest> {
+        self.requests.try_recv().ok()
+    }
+}
+
+impl Drop for RemoteServer {
+    /// Close the listener and every client connection, so their threads end
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        if let Some(thread) = self.accept_thread.take() {
+            let _ = thread.join();
+        }
+        if let Ok(mut connections) = self.connections.lock() {
+            for (_, stream) in connections.drain() {
+                let _ = stream.shutdown(Shutdown::Both);
+            }
+        }
+    }
+}
+
+fn reject(status: StatusCode, message: &str) -> ErrorResponse {
+    let mut response = ErrorResponse::new(Some(message.to_string()));
+    *response.status_mut() = status;
+    response
+}
+
+/// Accept the WebSocket handshake of local tools that present the token, not of web pages
+fn check_handshake(request: &Request, token: &str) -> Result<(), ErrorResponse> {
+    if request.headers().get("Origin").is_some() {
+        return Err(reject(
+            StatusCode::FORBIDDEN,
+            "Connections from web pages are not allowed",
+        ));
+    }
+    let header_token = request
+        .headers()
+        .get("Authorization")
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "));
+    let query_token = request
+        .uri()
+        .query()
+        .into_iter()
+        .flat_map(|query| query.split('&'))
+        .find_map(|pair| pair.strip_prefix("token="));
+    if header_token.or(query_token) != Some(token) {
+        return Err(reject(StatusCode::UNAUTHORIZED, "Missing or wrong token"));
+    }
+    Ok(())
+}
+
+/// Handle the messages of a single client until it disconnects
+fn serve_client(
+    stream: TcpStream,
+    token: &str,
+    requests: Sender<RemoteRequest>,
+    ctx: egui::Context,
+    stop: Arc<AtomicBool>,
+) -> Result<(), String> {
+    stream.set_nonblocking(false).map_err(|e| e.to_string())?;
+    let mut socket = tungstenite::accept_hdr(stream, |request: &Request, response: Response| {
+        check_handshake(request, token).map(|()| response)
+    })
+    .map_err(|e| e.to_string())?;
+
+    while !stop.load(Ordering::Relaxed) {
+        let message = socket.read().map_err(|e| e.to_string())?;
+        if message.is_close() {
+            return Ok(());
+        }
+        if !message.is_text() {
+            continue;
+        }
+        let text = message.to_text().map_err(|e| e.to_string())?;
+        let response = match serde_json::from_str::<Value>(text) {
+            Ok(request) => handle_message(request, &requests, &ctx),
+            Err(e) => error_response(Value::Null, -32700, &format!("Parse error: {}", e)),
+        };
+        socket
+            .send(Message::text(response.to_string()))
+            .map_err(|e| e.to_string())?;
+    }
+    Ok(())
+}
+
+/// Forward a JSON-RPC request to the user interface and wait for the result
+fn handle_message(request: Value, requests: &Sender<RemoteRequest>, ctx: &egui::Context) -> Value {
+    let id = request.get("id").cloned().unwrap_or(Value::Null);
+    let Some(method) = request.get("method").and_then(|m| m.as_str()) else {
+        return error_response(id, -32600, "Invalid request: missing method");
+    };
+
+    let (reply, result) = channel();
+    let remote_request = RemoteRequest {
+        method: method.to_string(),
+        params: request.get("params").cloned().unwrap_or(json!({})),
+        reply,
+    };
+    if requests.send(remote_request).is_err() {
+        return error_response(id, -32000, "The designer is shutting down");
+    }
+    ctx.request_repaint();
+
+    match result.recv() {
+        Ok(Ok(value)) => json!({"jsonrpc": "2.0", "id": id, "result": value}),
+        Ok(Err(e)) => error_response(id, e.code, &e.message),
+        Err(_) => error_response(id, -32000, "The request was dropped"),
+    }
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({"jsonrpc": "2.0", "id": id, "error": {"code": code, "message": message}})
+}
@@ -0,0 +1,167 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Where the preview knowingly deviates from the standard, so preview quirks are not mistaken for
+//! bugs in the pool, or the other way around.
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::FontStyle;
+use ag_iso_stack::object_pool::{NullableObjectId, ObjectId, ObjectPool};
+
+use crate::terminal_profile::{font_size_key, TerminalProfile};
+
+/// How far the preview of an object is from what a terminal shows
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Fidelity {
+    /// Drawn, but parts of it look different on a terminal
+    Approximate,
+
+    /// Not drawn at all, a placeholder is shown instead
+    NotRendered,
+}
+
+/// A known difference between the preview and a terminal
+#[derive(Debug, Clone, PartialEq)]
+pub struct FidelityIssue {
+    pub fidelity: Fidelity,
+    pub message: String,
+}
+
+impl FidelityIssue {
+    fn approximate(message: String) -> Self {
+        FidelityIssue {
+            fidelity: Fidelity::Approximate,
+            message,
+        }
+    }
+}
+
+fn line_art_issues(pool: &ObjectPool, line_attributes: ObjectId, issues: &mut Vec<FidelityIssue>) {
+    if let Some(Object::LineAttributes(line)) = pool.object_by_id(line_attributes) {
+        if line.line_art != 0xFFFF {
+            issues.push(FidelityIssue::approximate(format!(
+                "Line art {:#06X} is drawn as a solid line",
+                line.line_art
+            )));
+        }
+    }
+}
+
+fn font_style_issues(style: &FontStyle, issues: &mut Vec<FidelityIssue>) {
+    let ignored: Vec<&str> = [
+        (style.bold, "bold"),
+        (style.italic, "italic"),
+        (style.underlined, "underlined"),
+        (style.crossed_out, "crossed out"),
+    ]
+    .into_iter()
+    .filter(|(set, _)| *set)
+    .map(|(_, name)| name)
+    .collect();
+    if !ignored.is_empty() {
+        issues.push(FidelityIssue::approximate(format!(
+            "The {} font style is drawn as regular text",
+            ignored.join(", ")
+        )));
+    }
+}
+
+fn font_issues(
+    pool: &ObjectPool,
+    font_attributes: ObjectId,
+    profile: Option<&TerminalProfile>,
+    issues: &mut Vec<FidelityIssue>,
+) {
+    let Some(Object::FontAttributes(font)) = pool.object_by_id(font_attributes) else {
+        return;
+    };
+    if profile
+        .and_then(|p| p.mapped_font(&font.font_size))
+        .is_none()
+    {
+        issues.push(FidelityIssue::approximate(format!(
+            "Font size {} is drawn with a built-in font that approximates the terminal font, \
+             map a terminal font in the terminal profile to get the exact glyphs",
+            font_size_key(&font.font_size)
+        )));
+    }
+    font_style_issues(&font.font_style, issues);
+}
+
+fn wrap_issues(auto_wrap: bool, wrap_on_hyphen: bool, issues: &mut Vec<FidelityIssue>) {
+    if auto_wrap && wrap_on_hyphen {
+        issues.push(FidelityIssue::approximate(
+            "Wrapping on hyphens is not implemented, lines only wrap at spaces".to_string(),
+        ));
+    }
+}
+
+fn rectangle_issues(
+    pool: &ObjectPool,
+    line_suppression: u8,
+    fill_attributes: NullableObjectId,
+    issues: &mut Vec<FidelityIssue>,
+) {
+    if line_suppression != 0 {
+        issues.push(FidelityIssue::approximate(
+            "Line suppression is ignored, all border lines are drawn".to_string(),
+        ));
+    }
+    let Some(fill) = fill_attributes.into() else {
+        return;
+    };
+    if let Some(Object::FillAttributes(fill)) = pool.object_by_id(fill) {
+        match fill.fill_type {
+            2 => {}
+            3 => issues.push(FidelityIssue::approximate(
+                "The fill pattern is drawn as a fill with the fill colour".to_string(),
+            )),
+            fill_type => issues.push(FidelityIssue::approximate(format!(
+                "Fill type {} is drawn as a fill with the fill colour",
+                fill_type
+            ))),
+        }
+    }
+}
+
+/// The known differences between the preview of an object and how a terminal draws it. Fonts
+/// are compared with the fonts mapped in the terminal `profile`.
+pub fn render_fidelity(
+    pool: &ObjectPool,
+    object: &Object,
+    profile: Option<&TerminalProfile>,
+) -> Vec<FidelityIssue> {
+    let mut issues = Vec::new();
+    match object {
+        Object::OutputList(_)
+        | Object::OutputEllipse(_)
+        | Object::OutputPolygon(_)
+        | Object::OutputMeter(_)
+        | Object::OutputLinearBarGraph(_)
+        | Object::OutputArchedBarGraph(_) => issues.push(FidelityIssue {
+            fidelity: Fidelity::NotRendered,
+            message: format!(
+                "The preview does not draw {:?} objects yet",
+                object.object_type()
+            ),
+        }),
+        Object::OutputLine(o) => line_art_issues(pool, o.line_attributes, &mut issues),
+        Object::OutputRectangle(o) => {
+            line_art_issues(pool, o.line_attributes, &mut issues);
+            rectangle_issues(pool, o.line_suppression, o.fill_attributes, &mut issues);
+        }
+        Object::OutputString(o) => {
+            font_issues(pool, o.font_attributes, profile, &mut issues);
+            wrap_issues(o.options.auto_wrap, o.options.wrap_on_hyphen, &mut issues);
+        }
+        Object::InputString(o) => {
+            font_issues(pool, o.font_attributes, profile, &mut issues);
+            wrap_issues(o.options.auto_wrap, o.options.wrap_on_hyphen, &mut issues);
+        }
+        Object::OutputNumber(o) => font_issues(pool, o.font_attributes, profile, &mut issues),
+        Object::InputNumber(o) => font_issues(pool, o.font_attributes, profile, &mut issues),
+        _ => {}
+    }
+    issues
+}
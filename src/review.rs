@@ -0,0 +1,92 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use serde::{Deserialize, Serialize};
+
+/// Review status of a single object
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum ReviewStatus {
+    #[default]
+    Unreviewed,
+    Approved,
+    NeedsChanges,
+}
+
+impl ReviewStatus {
+    pub const ALL: [ReviewStatus; 3] = [
+        ReviewStatus::Unreviewed,
+        ReviewStatus::Approved,
+        ReviewStatus::NeedsChanges,
+    ];
+
+    /// Human readable name of the status
+    pub fn label(&self) -> &'static str {
+        match self {
+            ReviewStatus::Unreviewed => "Unreviewed",
+            ReviewStatus::Approved => "Approved",
+            ReviewStatus::NeedsChanges => "Needs changes",
+        }
+    }
+
+    /// Short marker shown next to the object name, if any
+    pub fn icon(&self) -> Option<&'static str> {
+        match self {
+            ReviewStatus::Unreviewed => None,
+            ReviewStatus::Approved => Some("\u{2714}"),
+            ReviewStatus::NeedsChanges => Some("\u{26A0}"),
+        }
+    }
+}
+
+/// A review comment, with the replies to it
+#[derive(Serialize, Deserialize, Debug, Clone, PartialEq)]
+pub struct ReviewComment {
+    pub author: String,
+    pub text: String,
+
+    #[serde(default)]
+    pub replies: Vec<ReviewComment>,
+}
+
+impl ReviewComment {
+    pub fn new(author: String, text: String) -> Self {
+        ReviewComment {
+            author,
+            text,
+            replies: Vec::new(),
+        }
+    }
+}
+
+/// Review state of a single object
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+pub struct ObjectReview {
+    #[serde(default)]
+    pub status: ReviewStatus,
+
+    /// Top level comment threads
+    #[serde(default)]
+    pub comments: Vec<ReviewComment>,
+}
+
+impl ObjectReview {
+    pub fn is_unreviewed(&self) -> bool {
+        self.status == ReviewStatus::Unreviewed
+    }
+
+    /// Add a comment to the thread at the given path of comment indices.
+    /// An empty path starts a new top level thread.
+    /// Returns false if the path does not point to an existing comment
+    pub fn add_comment(&mut self, path: &[usize], comment: ReviewComment) -> bool {
+        let mut thread = &mut self.comments;
+        for &index in path {
+            match thread.get_mut(index) {
+                Some(parent) => thread = &mut parent.replies,
+                None => return false,
+            }
+        }
+        thread.push(comment);
+        true
+    }
+}
@@ -0,0 +1,246 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! A PDF document to review the design of a pool without the designer: one page per mask with
+//! its rendered image, objects, notes and open validation issues.
+
+use std::collections::HashSet;
+
+use ag_iso_stack::object_pool::ObjectId;
+use eframe::egui;
+
+use crate::mask_capture::MaskCapture;
+use crate::pdf::{text_width, wrap_text, PdfDocument, A4};
+use crate::reference_paths::subtree;
+use crate::validation::{validate_project, Severity};
+use crate::EditorProject;
+
+const MARGIN: f32 = 40.0;
+const BOTTOM: f32 = A4.1 - MARGIN;
+const IMAGE_HEIGHT: f32 = 380.0;
+const LINE: f32 = 13.0;
+const TEXT_SIZE: f32 = 9.0;
+
+/// Renders every mask and collects the images for the review document
+pub struct ReviewDocumentRun {
+    capture: MaskCapture,
+    images: Vec<(ObjectId, Result<egui::ColorImage, String>)>,
+}
+
+impl ReviewDocumentRun {
+    pub fn new(project: &EditorProject) -> Self {
+        ReviewDocumentRun {
+            capture: MaskCapture::new(project),
+            images: Vec::new(),
+        }
+    }
+
+    pub fn is_finished(&self) -> bool {
+        self.capture.is_finished()
+    }
+
+    /// Number of masks rendered and the total number of masks
+    pub fn progress(&self) -> (usize, usize) {
+        self.capture.progress()
+    }
+
+    /// Drive the run, call this once every frame until the run is finished
+    pub fn update(&mut self, ctx: &egui::Context, project: &EditorProject) {
+        if let Some(image) = self.capture.update(ctx, project) {
+            self.images.push(image);
+        }
+    }
+
+    /// The review document as the bytes of a PDF file
+    pub fn document(&self, project: &EditorProject) -> Result<Vec<u8>, String> {
+        let mut writer = Writer {
+            pdf: PdfDocument::default(),
+            y: MARGIN,
+            heading: String::new(),
+        };
+        writer.cover(project, self.images.len());
+        for (mask, image) in &self.images {
+            writer.mask_page(project, *mask, image)?;
+        }
+
+        let pages = writer.pdf.page_count();
+        for page in 0..pages {
+            writer.pdf.select_page(page);
+            let footer = format!("{} / {}", page + 1, pages);
+            let x = A4.0 - MARGIN - text_width(&footer, 8.0);
+            writer.pdf.text(x, A4.1 - 20.0, 8.0, false, &footer);
+        }
+        Ok(writer.pdf.to_bytes())
+    }
+}
+
+/// Lays out text from top to bottom, continuing on a new page when the page is full
+struct Writer {
+    pdf: PdfDocument,
+    y: f32,
+
+    /// Title repeated on continued pages
+    heading: String,
+}
+
+impl Writer {
+    fn new_page(&mut self) {
+        self.pdf.add_page();
+        self.y = MARGIN;
+    }
+
+    /// Make room for `height`, continuing on a new page if needed
+    fn reserve(&mut self, height: f32) {
+        if self.y + height > BOTTOM {
+            self.new_page();
+            let heading = format!("{} (continued)", self.heading);
+            self.pdf.text(MARGIN, self.y + 10.0, 10.0, true, &heading);
+            self.y += 2.0 * LINE;
+        }
+    }
+
+    fn title(&mut self, text: &str) {
+        self.pdf.text(MARGIN, self.y + 16.0, 16.0, true, text);
+        self.y += 24.0;
+    }
+
+    fn section(&mut self, text: &str) {
+        self.reserve(3.0 * LINE);
+        self.y += LINE / 2.0;
+        self.pdf.text(MARGIN, self.y + 11.0, 11.0, true, text);
+        self.y += LINE + 2.0;
+    }
+
+    fn paragraph(&mut self, text: &str, indent: f32) {
+        for line in wrap_text(text, TEXT_SIZE, A4.0 - 2.0 * MARGIN - indent) {
+            self.reserve(LINE);
+            self.pdf
+                .text(MARGIN + indent, self.y + TEXT_SIZE, TEXT_SIZE, false, &line);
+            self.y += LINE;
+        }
+    }
+
+    /// A table row, the columns start at the given offsets from the margin
+    fn row(&mut self, columns: &[(f32, &str)], bold: bool) {
+        self.reserve(LINE);
+        for (x, text) in columns {
+            self.pdf
+                .text(MARGIN + x, self.y + TEXT_SIZE, TEXT_SIZE, bold, text);
+        }
+        self.y += LINE;
+    }
+
+    fn cover(&mut self, project: &EditorProject, masks: usize) {
+        self.new_page();
+        self.title("Design review");
+        let pool = project.get_pool();
+        let working_set = pool
+            .working_set_object()
+            .and_then(|working_set| pool.object_by_id(working_set.id))
+            .map(|object| project.get_object_info(object).get_name(object));
+        if let Some(name) = working_set {
+            self.paragraph(&format!("Working set: {}", name), 0.0);
+        }
+        self.paragraph(&format!("Version: {}", project.pool_version.label), 0.0);
+        self.paragraph(
+            &format!(
+                "{} masks, {} objects, {} bytes",
+                masks,
+                pool.objects().len(),
+                pool.as_iop().len()
+            ),
+            0.0,
+        );
+        if !project.authors().is_empty() {
+            self.paragraph(&format!("Authors: {}", project.authors().join(", ")), 0.0);
+        }
+    }
+
+    fn mask_page(
+        &mut self,
+        project: &EditorProject,
+        mask: ObjectId,
+        image: &Result<egui::ColorImage, String>,
+    ) -> Result<(), String> {
+        let pool = project.get_pool();
+        let Some(object) = pool.object_by_id(mask) else {
+            return Ok(());
+        };
+        let name = project.get_object_info(object).get_name(object);
+        self.new_page();
+        self.heading = name.clone();
+        self.title(&name);
+        self.paragraph(&format!("{:?} {}", object.object_type(), mask.value()), 0.0);
+        self.y += LINE / 2.0;
+
+        match image {
+            Ok(image) if image.size[0] > 0 && image.size[1] > 0 => {
+                let (width, height) = (image.size[0] as f32, image.size[1] as f32);
+                let scale = ((A4.0 - 2.0 * MARGIN) / width).min(IMAGE_HEIGHT / height);
+                let rgb: Vec<u8> = image
+                    .pixels
+                    .iter()
+                    .flat_map(|pixel| [pixel.r(), pixel.g(), pixel.b()])
+                    .collect();
+                self.pdf.image(
+                    &rgb,
+                    (image.size[0] as u32, image.size[1] as u32),
+                    (MARGIN, self.y),
+                    (width * scale, height * scale),
+                )?;
+                self.y += height * scale + LINE;
+            }
+            Ok(_) => self.paragraph("The mask is empty", 0.0),
+            Err(e) => self.paragraph(&format!("The mask could not be rendered: {}", e), 0.0),
+        }
+
+        let objects = subtree(pool, mask);
+        self.section("Objects");
+        self.row(&[(0.0, "ID"), (50.0, "Type"), (190.0, "Name")], true);
+        let rule = self.y - 3.0;
+        self.pdf.line((MARGIN, rule), (A4.0 - MARGIN, rule), 0.5);
+        for id in objects.iter().filter(|id| **id != mask) {
+            if let Some(object) = pool.object_by_id(*id) {
+                let name = project.get_object_info(object).get_name(object);
+                let object_type = format!("{:?}", object.object_type());
+                let id = id.value().to_string();
+                self.row(&[(0.0, &id), (50.0, &object_type), (190.0, &name)], false);
+            }
+        }
+
+        let notes: Vec<(String, String)> = objects
+            .iter()
+            .filter_map(|id| pool.object_by_id(*id))
+            .filter_map(|object| {
+                let info = project.get_object_info(object);
+                let notes = info.notes.trim();
+                (!notes.is_empty()).then(|| (info.get_name(object), notes.to_string()))
+            })
+            .collect();
+        if !notes.is_empty() {
+            self.section("Notes");
+            for (name, notes) in notes {
+                self.paragraph(&format!("{}:", name), 0.0);
+                self.paragraph(&notes, 12.0);
+            }
+        }
+
+        let in_mask: HashSet<ObjectId> = objects.into_iter().collect();
+        let issues: Vec<_> = validate_project(project)
+            .into_iter()
+            .filter(|issue| issue.object.is_some_and(|id| in_mask.contains(&id)))
+            .collect();
+        if !issues.is_empty() {
+            self.section("Open validation issues");
+            for issue in issues {
+                let severity = match issue.severity {
+                    Severity::Error => "Error",
+                    Severity::Warning => "Warning",
+                };
+                self.paragraph(&format!("{}: {}", severity, issue.message), 0.0);
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,150 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Undo limited to a part of the pool, e.g. a single mask. The last change that only affected
+//! that part is reverted on top of the current pool, so later changes elsewhere are kept.
+
+use std::collections::BTreeSet;
+
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+
+use crate::reference_paths::subtree;
+use crate::Change;
+
+/// IDs of the objects that differ between the pools
+fn changed_objects(before: &ObjectPool, after: &ObjectPool) -> BTreeSet<ObjectId> {
+    let modified = after
+        .objects()
+        .iter()
+        .filter(|object| before.object_by_id(object.id()) != Some(*object));
+    let removed = before
+        .objects()
+        .iter()
+        .filter(|object| after.object_by_id(object.id()).is_none());
+    modified.chain(removed).map(|object| object.id()).collect()
+}
+
+/// The pool after the undo entry at the index
+fn pool_after<'a>(current: &'a ObjectPool, undo: &'a [Change], index: usize) -> &'a ObjectPool {
+    undo.get(index + 1).map_or(current, |next| &next.pool)
+}
+
+/// Index of the most recent undo entry that only changed `scope` and the objects it references,
+/// before or after the change. Undos within a scope and the changes they already reverted are
+/// skipped, so repeating the undo goes further back instead of toggling between two states.
+pub fn last_change_within(current: &ObjectPool, undo: &[Change], scope: ObjectId) -> Option<usize> {
+    (0..undo.len()).rev().find(|&index| {
+        if undo[index].scoped_undo {
+            return false;
+        }
+        let (before, after) = (&undo[index].pool, pool_after(current, undo, index));
+        let changed = changed_objects(before, after);
+        let reverted = changed
+            .iter()
+            .all(|id| current.object_by_id(*id) == before.object_by_id(*id));
+        if reverted {
+            return false;
+        }
+        let in_scope: BTreeSet<ObjectId> = subtree(before, scope)
+            .into_iter()
+            .chain(subtree(after, scope))
+            .collect();
+        !changed.is_empty() && changed.is_subset(&in_scope)
+    })
+}
+
+/// The current pool with the change of the undo entry at the index reverted. Fails if a later
+/// change edited the same objects, as reverting would also discard that change.
+pub fn revert_change(
+    current: &ObjectPool,
+    undo: &[Change],
+    index: usize,
+) -> Result<ObjectPool, String> {
+    let before = &undo[index].pool;
+    let changed = changed_objects(before, pool_after(current, undo, index));
+    for later in index + 1..undo.len() {
+        let overlap = changed_objects(&undo[later].pool, pool_after(current, undo, later))
+            .intersection(&changed)
+            .next()
+            .copied();
+        if let Some(id) = overlap {
+            return Err(format!(
+                "The later change '{}' also changed object {}",
+                undo[later].description,
+                id.value()
+            ));
+        }
+    }
+
+    let mut reverted = current.clone();
+    for id in changed {
+        match before.object_by_id(id) {
+            Some(object) => match reverted.object_mut_by_id(id) {
+                Some(target) => *target = object.clone(),
+                None => reverted.add(object.clone()),
+            },
+            None => reverted.remove(id),
+        }
+    }
+    Ok(reverted)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ChangeCategory;
+    use ag_iso_stack::object_pool::object::{Macro, Object};
+
+    /// A pool of macros 1 and 2 with the given commands
+    fn pool(first: &[u8], second: &[u8]) -> ObjectPool {
+        let mut pool = ObjectPool::new();
+        for (id, commands) in [(1, first), (2, second)] {
+            pool.add(Object::Macro(Macro {
+                id: ObjectId::new(id).unwrap(),
+                commands: commands.to_vec(),
+            }));
+        }
+        pool
+    }
+
+    fn change(pool: ObjectPool, description: &str) -> Change {
+        Change::new(
+            pool,
+            description.to_string(),
+            ChangeCategory::Edit,
+            String::new(),
+        )
+    }
+
+    #[test]
+    fn later_changes_elsewhere_are_kept() {
+        let scope = ObjectId::new(1).unwrap();
+        let mut undo = vec![
+            change(pool(&[], &[]), "Edit macro 1"),
+            change(pool(&[1], &[]), "Edit macro 2"),
+        ];
+        let current = pool(&[1], &[2]);
+        assert_eq!(last_change_within(&current, &undo, scope), Some(0));
+        let reverted = revert_change(&current, &undo, 0).unwrap();
+        assert!(reverted == pool(&[], &[2]));
+
+        // Undoing within the scope again goes further back instead of redoing the change
+        let mut scoped = change(current, "Undo within macro 1");
+        scoped.scoped_undo = true;
+        undo.push(scoped);
+        assert_eq!(last_change_within(&reverted, &undo, scope), None);
+    }
+
+    #[test]
+    fn overlapping_later_changes_are_not_discarded() {
+        let undo = vec![
+            change(pool(&[], &[]), "Edit macro 1"),
+            change(pool(&[1], &[]), "Edit macro 1 again"),
+        ];
+        assert_eq!(
+            revert_change(&pool(&[1, 1], &[]), &undo, 0).err(),
+            Some("The later change 'Edit macro 1 again' also changed object 1".to_string())
+        );
+    }
+}
@@ -0,0 +1,606 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+use std::rc::Rc;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::Point;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectRef, ObjectType};
+use rhai::{Array, Engine, EvalAltResult, Map, INT};
+
+use crate::colour_roles::colour_attributes_mut;
+use crate::layout::object_refs_mut;
+use crate::object_defaults::default_object;
+use crate::pool_diff::object_attributes;
+use crate::reference_paths::{parent_map, remove_references};
+use crate::spell_check::visible_text;
+use crate::{ChangeCategory, EditorProject, ObjectInfo};
+
+/// Stop scripts after this many operations, so an endless loop does not hang the designer
+const MAX_SCRIPT_OPERATIONS: u64 = 10_000_000;
+
+/// Functions available to scripts, shown in the console
+pub const SCRIPT_API: &[&str] = &[
+    "objects() -> [id]",
+    "objects_of_type(type) -> [id]",
+    "object_type(id) -> string",
+    "name(id) -> string",
+    "attributes(id) -> #{name: value}",
+    "children(id) / descendants(id) / parents(id) -> [id]",
+    "position(parent, child) -> [x, y]",
+    "set_position(parent, child, x, y)",
+    "move_child(parent, child, dx, dy)",
+    "add_child(parent, child, x, y)",
+    "text(id) / set_text(id, text)",
+    "colour(id, attribute) / set_colour(id, attribute, index)",
+    "create(type) -> id",
+    "delete(id), references to it are removed",
+    "tags(id) -> [tag] / tagged(tag) -> [id]",
+    "add_tag(id, tag) / remove_tag(id, tag)",
+    "undo() / redo(), step the pool through the history",
+    "describe(text)",
+    "print(value)",
+];
+
+/// Output of a script run
+#[derive(Debug, Clone, Default)]
+pub struct ScriptResult {
+    /// Everything the script printed
+    pub output: Vec<String>,
+
+    /// Error message if the script failed, the pool is not changed in that case
+    pub error: Option<String>,
+
+    /// Whether the script changed the pool
+    pub changed: bool,
+}
+
+type ScriptError = Box<EvalAltResult>;
+
+fn to_object_id(id: INT) -> Result<ObjectId, ScriptError> {
+    u16::try_from(id)
+        .ok()
+        .and_then(|id| ObjectId::new(id).ok())
+        .ok_or_else(|| format!("Invalid object ID {}", id).into())
+}
+
+fn to_object_type(name: &str) -> Result<ObjectType, ScriptError> {
+    (0..=u8::MAX)
+        .filter_map(|v| ObjectType::try_from(v).ok())
+        .find(|t| format!("{:?}", t).eq_ignore_ascii_case(name))
+        .ok_or_else(|| format!("Unknown object type '{}'", name).into())
+}
+
+fn id_array(ids: impl IntoIterator<Item = ObjectId>) -> Array {
+    ids.into_iter()
+        .map(|id| (id.value() as INT).into())
+        .collect()
+}
+
+fn to_offset(value: INT) -> i16 {
+    value.clamp(i16::MIN as INT, i16::MAX as INT) as i16
+}
+
+/// Tags in the notes of an object, words like `#metric`
+fn note_tags(notes: &str) -> impl Iterator<Item = &str> {
+    notes.split_whitespace().filter_map(|word| {
+        word.strip_prefix('#')
+            .map(|tag| tag.trim_end_matches(|c: char| c.is_ascii_punctuation()))
+            .filter(|tag| !tag.is_empty())
+    })
+}
+
+fn has_tag(notes: &str, tag: &str) -> bool {
+    note_tags(notes).any(|t| t.eq_ignore_ascii_case(tag.trim_start_matches('#')))
+}
+
+/// Notes without the tag, lines that only held the tag are removed
+fn without_tag(notes: &str, tag: &str) -> String {
+    notes
+        .lines()
+        .filter_map(|line| {
+            let words: Vec<&str> = line.split(' ').filter(|word| !has_tag(word, tag)).collect();
+            let kept = words.join(" ");
+            (kept.trim().len() == line.trim().len() || !kept.trim().is_empty()).then_some(kept)
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Pools of the project history the script steps through with undo() and redo()
+struct ScriptHistory {
+    pools: Vec<ObjectPool>,
+    position: usize,
+}
+
+/// Run a closure on an existing object of the script pool
+fn with_object<R>(
+    pool: &RefCell<ObjectPool>,
+    id: INT,
+    f: impl FnOnce(&mut Object) -> Result<R, ScriptError>,
+) -> Result<R, ScriptError> {
+    let id = to_object_id(id)?;
+    let mut pool = pool.borrow_mut();
+    let object = pool
+        .object_mut_by_id(id)
+        .ok_or_else(|| ScriptError::from(format!("Object {} does not exist", id.value())))?;
+    f(object)
+}
+
+/// Run a closure on every reference from a parent to a child
+fn with_child_refs(
+    pool: &RefCell<ObjectPool>,
+    parent: INT,
+    child: INT,
+    mut f: impl FnMut(&mut ObjectRef),
+) -> Result<(), ScriptError> {
+    let child = to_object_id(child)?;
+    with_object(pool, parent, |object| {
+        let refs = object_refs_mut(object)
+            .ok_or_else(|| ScriptError::from("Object has no positioned children"))?;
+        let mut found = false;
+        for obj_ref in refs.iter_mut().filter(|r| r.id == child) {
+            f(obj_ref);
+            found = true;
+        }
+        if found {
+            Ok(())
+        } else {
+            Err(format!("Object {} is not a child", child.value()).into())
+        }
+    })
+}
+
+/// Register the pool API on a script engine.
+/// All functions work on a copy of the pool, which is only applied when the script succeeds.
+fn register_api(
+    engine: &mut Engine,
+    pool: &Rc<RefCell<ObjectPool>>,
+    names: Rc<HashMap<ObjectId, String>>,
+    notes: &Rc<RefCell<HashMap<ObjectId, String>>>,
+    history: Rc<RefCell<ScriptHistory>>,
+    description: &Rc<RefCell<Option<String>>>,
+) {
+    let p = pool.clone();
+    engine.register_fn("objects", move || {
+        id_array(p.borrow().objects().iter().map(|o| o.id()))
+    });
+
+    let p = pool.clone();
+    engine.register_fn(
+        "objects_of_type",
+        move |name: &str| -> Result<Array, ScriptError> {
+            let object_type = to_object_type(name)?;
+            Ok(id_array(
+                p.borrow()
+                    .objects()
+                    .iter()
+                    .filter(|o| o.object_type() == object_type)
+                    .map(|o| o.id()),
+            ))
+        },
+    );
+
+    let p = pool.clone();
+    engine.register_fn("object_type", move |id: INT| {
+        with_object(&p, id, |o| Ok(format!("{:?}", o.object_type())))
+    });
+
+    engine.register_fn("name", move |id: INT| -> Result<String, ScriptError> {
+        let id = to_object_id(id)?;
+        Ok(names.get(&id).cloned().unwrap_or_default())
+    });
+
+    let p = pool.clone();
+    engine.register_fn("attributes", move |id: INT| {
+        with_object(&p, id, |o| {
+            let mut map = Map::new();
+            for (name, value) in object_attributes(o) {
+                map.insert(name.into(), value.into());
+            }
+            Ok(map)
+        })
+    });
+
+    let p = pool.clone();
+    engine.register_fn("children", move |id: INT| {
+        with_object(&p, id, |o| Ok(id_array(o.referenced_objects())))
+    });
+
+    let p = pool.clone();
+    engine.register_fn(
+        "descendants",
+        move |id: INT| -> Result<Array, ScriptError> {
+            let root = to_object_id(id)?;
+            let pool = p.borrow();
+            let mut found = Vec::new();
+            let mut visited = HashSet::from([root]);
+            let mut stack = vec![root];
+            while let Some(id) = stack.pop() {
+                let Some(object) = pool.object_by_id(id) else {
+                    continue;
+                };
+                for child in object.referenced_objects() {
+                    if visited.insert(child) {
+                        found.push(child);
+                        stack.push(child);
+                    }
+                }
+            }
+            Ok(id_array(found))
+        },
+    );
+
+    let p = pool.clone();
+    engine.register_fn("parents", move |id: INT| -> Result<Array, ScriptError> {
+        let id = to_object_id(id)?;
+        Ok(id_array(
+            parent_map(&p.borrow()).remove(&id).unwrap_or_default(),
+        ))
+    });
+
+    let p = pool.clone();
+    engine.register_fn(
+        "position",
+        move |parent: INT, child: INT| -> Result<Array, ScriptError> {
+            let mut position = None;
+            with_child_refs(&p, parent, child, |r| {
+                position.get_or_insert((r.offset.x, r.offset.y));
+            })?;
+            let (x, y) = position.unwrap_or_default();
+            Ok(vec![(x as INT).into(), (y as INT).into()])
+        },
+    );
+
+    let p = pool.clone();
+    engine.register_fn(
+        "set_position",
+        move |parent: INT, child: INT, x: INT, y: INT| {
+            with_child_refs(&p, parent, child, |r| {
+                r.offset.x = to_offset(x);
+                r.offset.y = to_offset(y);
+            })
+        },
+    );
+
+    let p = pool.clone();
+    engine.register_fn(
+        "move_child",
+        move |parent: INT, child: INT, dx: INT, dy: INT| {
+            with_child_refs(&p, parent, child, |r| {
+                r.offset.x = to_offset(r.offset.x as INT + dx);
+                r.offset.y = to_offset(r.offset.y as INT + dy);
+            })
+        },
+    );
+
+    let p = pool.clone();
+    engine.register_fn(
+        "add_child",
+        move |parent: INT, child: INT, x: INT, y: INT| -> Result<(), ScriptError> {
+            let child = to_object_id(child)?;
+            if p.borrow().object_by_id(child).is_none() {
+                return Err(format!("Object {} does not exist", child.value()).into());
+            }
+            with_object(&p, parent, |object| {
+                let refs = object_refs_mut(object)
+                    .ok_or_else(|| ScriptError::from("Object has no positioned children"))?;
+                refs.push(ObjectRef {
+                    id: child,
+                    offset: Point {
+                        x: to_offset(x),
+                        y: to_offset(y),
+                    },
+                });
+                Ok(())
+            })
+        },
+    );
+
+    let p = pool.clone();
+    engine.register_fn("text", move |id: INT| {
+        with_object(&p, id, |o| {
+            visible_text(o)
+                .map(|text| text.to_string())
+                .ok_or_else(|| "Object has no text".into())
+        })
+    });
+
+    let p = pool.clone();
+    engine.register_fn("set_text", move |id: INT, text: &str| {
+        with_object(&p, id, |o| {
+            match o {
+                Object::OutputString(o) => o.value = text.to_string(),
+                Object::InputString(o) => o.value = text.to_string(),
+                Object::StringVariable(o) => o.value = text.to_string(),
+                _ => return Err("Object has no text".into()),
+            }
+            Ok(())
+        })
+    });
+
+    let p = pool.clone();
+    engine.register_fn("colour", move |id: INT, attribute: &str| {
+        with_object(&p, id, |o| {
+            colour_attributes_mut(o)
+                .into_iter()
+                .find(|(name, _)| name.eq_ignore_ascii_case(attribute))
+                .map(|(_, colour)| *colour as INT)
+                .ok_or_else(|| format!("Unknown colour attribute '{}'", attribute).into())
+        })
+    });
+
+    let p = pool.clone();
+    engine.register_fn(
+        "set_colour",
+        move |id: INT, attribute: &str, colour: INT| {
+            let colour = u8::try_from(colour)
+                .map_err(|_| ScriptError::from(format!("Invalid colour {}", colour)))?;
+            with_object(&p, id, |o| {
+                let (_, value) = colour_attributes_mut(o)
+                    .into_iter()
+                    .find(|(name, _)| name.eq_ignore_ascii_case(attribute))
+                    .ok_or_else(|| {
+                        ScriptError::from(format!("Unknown colour attribute '{}'", attribute))
+                    })?;
+                *value = colour;
+                Ok(())
+            })
+        },
+    );
+
+    let p = pool.clone();
+    engine.register_fn("create", move |name: &str| -> Result<INT, ScriptError> {
+        let object_type = to_object_type(name)?;
+        let mut pool = p.borrow_mut();
+        let id = (1..u16::MAX)
+            .filter_map(|id| ObjectId::new(id).ok())
+            .find(|id| pool.object_by_id(*id).is_none())
+            .ok_or_else(|| ScriptError::from("No free object ID left"))?;
+        let mut object = default_object(object_type);
+        *object.mut_id() = id;
+        pool.add(object);
+        Ok(id.value() as INT)
+    });
+
+    // References to the object are removed as well, the object is kept if another object
+    // requires it
+    let p = pool.clone();
+    engine.register_fn("delete", move |id: INT| -> Result<(), ScriptError> {
+        let id = to_object_id(id)?;
+        let mut pool = p.borrow_mut();
+        if pool.object_by_id(id).is_none() {
+            return Err(format!("Object {} does not exist", id.value()).into());
+        }
+        let mut edited = Vec::new();
+        for object in pool.objects().iter().filter(|o| o.id() != id) {
+            let mut copy = object.clone();
+            if !remove_references(&mut copy, id) {
+                return Err(format!(
+                    "Object {} is required by object {}",
+                    id.value(),
+                    object.id().value()
+                )
+                .into());
+            }
+            if copy != *object {
+                edited.push(copy);
+            }
+        }
+        for object in edited {
+            if let Some(stored) = pool.object_mut_by_id(object.id()) {
+                *stored = object;
+            }
+        }
+        pool.remove(id);
+        Ok(())
+    });
+
+    let n = notes.clone();
+    engine.register_fn("tags", move |id: INT| -> Result<Array, ScriptError> {
+        let id = to_object_id(id)?;
+        let notes = n.borrow();
+        let notes = notes.get(&id).map_or("", |notes| notes.as_str());
+        Ok(note_tags(notes).map(|tag| tag.to_string().into()).collect())
+    });
+
+    let (p, n) = (pool.clone(), notes.clone());
+    engine.register_fn("tagged", move |tag: &str| {
+        let notes = n.borrow();
+        id_array(
+            p.borrow()
+                .objects()
+                .iter()
+                .map(|o| o.id())
+                .filter(|id| notes.get(id).is_some_and(|notes| has_tag(notes, tag))),
+        )
+    });
+
+    let (p, n) = (pool.clone(), notes.clone());
+    engine.register_fn(
+        "add_tag",
+        move |id: INT, tag: &str| -> Result<(), ScriptError> {
+            let id = to_object_id(id)?;
+            let tag = tag.trim_start_matches('#');
+            if p.borrow().object_by_id(id).is_none() {
+                return Err(format!("Object {} does not exist", id.value()).into());
+            }
+            if tag.is_empty() || tag.contains(char::is_whitespace) {
+                return Err(format!("Invalid tag '{}'", tag).into());
+            }
+            let mut notes = n.borrow_mut();
+            let notes = notes.entry(id).or_default();
+            if !has_tag(notes, tag) {
+                if !notes.is_empty() {
+                    notes.push('\n');
+                }
+                notes.push('#');
+                notes.push_str(tag);
+            }
+            Ok(())
+        },
+    );
+
+    let n = notes.clone();
+    engine.register_fn(
+        "remove_tag",
+        move |id: INT, tag: &str| -> Result<(), ScriptError> {
+            let id = to_object_id(id)?;
+            if let Some(notes) = n.borrow_mut().get_mut(&id) {
+                *notes = without_tag(notes, tag);
+            }
+            Ok(())
+        },
+    );
+
+    let (p, h) = (pool.clone(), history.clone());
+    engine.register_fn("undo", move || -> Result<(), ScriptError> {
+        let mut history = h.borrow_mut();
+        if history.position == 0 {
+            return Err("Nothing to undo".into());
+        }
+        history.position -= 1;
+        p.replace(history.pools[history.position].clone());
+        Ok(())
+    });
+
+    let p = pool.clone();
+    engine.register_fn("redo", move || -> Result<(), ScriptError> {
+        let mut history = history.borrow_mut();
+        if history.position + 1 >= history.pools.len() {
+            return Err("Nothing to redo".into());
+        }
+        history.position += 1;
+        p.replace(history.pools[history.position].clone());
+        Ok(())
+    });
+
+    let d = description.clone();
+    engine.register_fn("describe", move |text: &str| {
+        d.replace(Some(text.to_string()));
+    });
+}
+
+/// Run a script on the project. All edits are applied as a single change that can be undone,
+/// or not at all if the script fails. Undo and redo in a script move its copy of the pool through
+/// the history, the result is still recorded as a new change.
+pub fn run_script(project: &EditorProject, script: &str) -> ScriptResult {
+    let pool = Rc::new(RefCell::new(project.get_mut_pool().borrow().clone()));
+    let mut pools: Vec<ObjectPool> = project
+        .undo_history()
+        .iter()
+        .map(|change| change.pool.clone())
+        .collect();
+    let position = pools.len();
+    pools.push(pool.borrow().clone());
+    pools.extend(
+        project
+            .redo_history()
+            .iter()
+            .rev()
+            .map(|change| change.pool.clone()),
+    );
+    let history = Rc::new(RefCell::new(ScriptHistory { pools, position }));
+    let original_notes: HashMap<ObjectId, String> = project
+        .object_info
+        .borrow()
+        .iter()
+        .filter(|(_, info)| !info.notes.is_empty())
+        .map(|(id, info)| (*id, info.notes.clone()))
+        .collect();
+    let notes = Rc::new(RefCell::new(original_notes.clone()));
+    let names: HashMap<ObjectId, String> = project
+        .get_pool()
+        .objects()
+        .iter()
+        .map(|o| (o.id(), project.get_object_info(o).get_name(o)))
+        .collect();
+    let description = Rc::new(RefCell::new(None));
+    let output = Rc::new(RefCell::new(Vec::new()));
+
+    let mut engine = Engine::new();
+    engine.set_max_operations(MAX_SCRIPT_OPERATIONS);
+    let o = output.clone();
+    engine.on_print(move |text| o.borrow_mut().push(text.to_string()));
+    let o = output.clone();
+    engine.on_debug(move |text, _, _| o.borrow_mut().push(text.to_string()));
+    register_api(
+        &mut engine,
+        &pool,
+        Rc::new(names),
+        &notes,
+        history,
+        &description,
+    );
+
+    let result = engine.run(script);
+    let mut script_result = ScriptResult {
+        output: output.take(),
+        ..Default::default()
+    };
+    match result {
+        Ok(()) => {
+            let new_pool = pool.take();
+            if new_pool != *project.get_mut_pool().borrow() {
+                project.get_mut_pool().replace(new_pool);
+                project.describe_change(
+                    description
+                        .take()
+                        .unwrap_or_else(|| "Run script".to_string()),
+                    ChangeCategory::BulkChange,
+                );
+                script_result.changed = true;
+            }
+            let notes = notes.take();
+            if notes != original_notes {
+                let pool = project.get_mut_pool().borrow();
+                let mut object_info = project.object_info.borrow_mut();
+                for (id, notes) in notes {
+                    if original_notes.get(&id) == Some(&notes) {
+                        continue;
+                    }
+                    if let Some(object) = pool.object_by_id(id) {
+                        object_info
+                            .entry(id)
+                            .or_insert_with(|| ObjectInfo::new(object))
+                            .notes = notes;
+                    }
+                }
+                script_result.changed = true;
+            }
+        }
+        Err(e) => script_result.error = Some(e.to_string()),
+    }
+    script_result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tags_are_hash_words_in_the_notes() {
+        let notes = "Speed display #metric, see #units.\nTODO check #Metric";
+        assert_eq!(
+            note_tags(notes).collect::<Vec<_>>(),
+            ["metric", "units", "Metric"]
+        );
+        assert!(has_tag(notes, "METRIC"));
+        assert!(has_tag(notes, "#units"));
+        assert!(!has_tag(notes, "imperial"));
+        assert!(!has_tag("# not a tag", ""));
+    }
+
+    #[test]
+    fn removing_a_tag_keeps_the_other_notes() {
+        assert_eq!(without_tag("#metric", "metric"), "");
+        assert_eq!(
+            without_tag("Speed #metric display\n#metric\nTODO", "metric"),
+            "Speed display\nTODO"
+        );
+        assert_eq!(without_tag("First\n\nSecond", "metric"), "First\n\nSecond");
+    }
+}
@@ -0,0 +1,73 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::HashMap;
+
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+
+use crate::reference_paths::{parent_map, remap_references};
+use crate::{ChangeCategory, EditorProject, ObjectInfo};
+
+/// The objects that reference the object, each once. Edits of an object with more than one
+/// parent show up everywhere it is used.
+pub fn object_parents(pool: &ObjectPool, id: ObjectId) -> Vec<ObjectId> {
+    parent_map(pool).remove(&id).unwrap_or_default()
+}
+
+/// Copy the object under a new ID and point the references of `parent` to the copy, so it can
+/// be edited without changing the other parents. Returns the ID of the copy
+pub fn make_unique_copy(
+    project: &EditorProject,
+    id: ObjectId,
+    parent: ObjectId,
+) -> Result<ObjectId, String> {
+    let pool = project.get_pool();
+    let object = pool
+        .object_by_id(id)
+        .ok_or_else(|| format!("Object {} does not exist", id.value()))?;
+    let parent_object = pool
+        .object_by_id(parent)
+        .ok_or_else(|| format!("Object {} does not exist", parent.value()))?;
+    if !parent_object.referenced_objects().contains(&id) {
+        return Err(format!(
+            "Object {} does not reference object {}",
+            parent.value(),
+            id.value()
+        ));
+    }
+    if project.is_locked(parent) {
+        return Err(format!("Object {} is locked", parent.value()));
+    }
+    let name = project.get_object_info(object).get_name(object);
+    let parent_name = project
+        .get_object_info(parent_object)
+        .get_name(parent_object);
+
+    let new_id = project.allocate_object_id();
+    let mut copy = object.clone();
+    *copy.mut_id() = new_id;
+    let mut info = ObjectInfo::new(&copy);
+    if project
+        .object_info
+        .borrow()
+        .get(&id)
+        .is_some_and(|info| info.name.is_some())
+    {
+        info.set_name(format!("{} ({})", name, parent_name));
+    }
+    project.object_info.borrow_mut().insert(new_id, info);
+    project.apply_smart_naming_to_object(&copy);
+
+    let mut pool = project.get_mut_pool().borrow_mut();
+    if let Some(parent_object) = pool.object_mut_by_id(parent) {
+        remap_references(parent_object, &HashMap::from([(id, new_id)]));
+    }
+    pool.add(copy);
+    drop(pool);
+    project.describe_change(
+        format!("Make a unique copy of {} for {}", name, parent_name),
+        ChangeCategory::Create,
+    );
+    Ok(new_id)
+}
@@ -0,0 +1,228 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{HashMap, HashSet};
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+
+use crate::macro_references::{macro_targets, macro_value_objects, remap_macro_references};
+use crate::pool_diff::object_attributes;
+use crate::reference_paths::{mask_references, remap_references};
+use crate::{ChangeCategory, EditorProject};
+
+/// Attributes that hold the content of an object rather than its structure
+const CONTENT_ATTRIBUTES: &[&str] = &["value", "minimum_value", "maximum_value"];
+
+/// Objects that only differ in their object IDs, or also in the content of their subtrees
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimilarGroup {
+    /// The roots of the similar subtrees, in pool order
+    pub objects: Vec<ObjectId>,
+    /// Number of objects in each subtree
+    pub subtree_size: usize,
+    /// Differences between the first subtree and the others, empty for exact duplicates
+    pub differences: Vec<SubtreeDifference>,
+    /// Share of the attributes in the subtrees that are equal, from 0 to 1
+    pub similarity: f32,
+}
+
+impl SimilarGroup {
+    pub fn is_exact(&self) -> bool {
+        self.differences.is_empty()
+    }
+}
+
+/// An attribute that differs between the first subtree of a group and another one
+#[derive(Debug, Clone, PartialEq)]
+pub struct SubtreeDifference {
+    /// The object in the first subtree and the matching object in the other subtree
+    pub objects: (ObjectId, ObjectId),
+    pub attribute: String,
+    pub values: (String, String),
+}
+
+/// Attributes of an object with the IDs of its references left out
+fn comparable_attributes(object: &Object) -> Vec<(String, String)> {
+    let mut object = object.clone();
+    mask_references(&mut object, ObjectId::new(0).unwrap());
+    object_attributes(&object)
+}
+
+/// Objects of a subtree in depth first order, stopping at objects that were visited already
+fn subtree(
+    pool: &ObjectPool,
+    id: ObjectId,
+    visited: &mut HashSet<ObjectId>,
+    out: &mut Vec<ObjectId>,
+) {
+    if !visited.insert(id) {
+        return;
+    }
+    let Some(object) = pool.object_by_id(id) else {
+        return;
+    };
+    out.push(id);
+    for child in object.referenced_objects() {
+        subtree(pool, child, visited, out);
+    }
+}
+
+/// Key of a subtree: the types and attributes of all its objects in order.
+/// With `structure_only` the content attributes are left out.
+fn subtree_key(pool: &ObjectPool, objects: &[ObjectId], structure_only: bool) -> String {
+    let mut key = String::new();
+    for id in objects {
+        let Some(object) = pool.object_by_id(*id) else {
+            continue;
+        };
+        key.push_str(&format!("{:?}(", object.object_type()));
+        for (name, value) in comparable_attributes(object) {
+            if structure_only && CONTENT_ATTRIBUTES.contains(&name.as_str()) {
+                continue;
+            }
+            key.push_str(&format!("{}={};", name, value));
+        }
+        key.push(')');
+    }
+    key
+}
+
+/// Find groups of objects whose subtrees are exact duplicates or only differ in values and
+/// strings. Groups inside the subtrees of a larger group are not reported separately.
+pub fn find_similar_objects(pool: &ObjectPool) -> Vec<SimilarGroup> {
+    let subtrees: Vec<(ObjectId, Vec<ObjectId>)> = pool
+        .objects()
+        .iter()
+        .filter(|o| !matches!(o.object_type(), ObjectType::WorkingSet))
+        .map(|o| {
+            let mut objects = Vec::new();
+            subtree(pool, o.id(), &mut HashSet::new(), &mut objects);
+            (o.id(), objects)
+        })
+        .collect();
+
+    let mut by_structure: HashMap<String, Vec<usize>> = HashMap::new();
+    for (index, (_, objects)) in subtrees.iter().enumerate() {
+        by_structure
+            .entry(subtree_key(pool, objects, true))
+            .or_default()
+            .push(index);
+    }
+
+    let mut candidates: Vec<Vec<usize>> = by_structure
+        .into_values()
+        .filter(|members| members.len() > 1)
+        .collect();
+    candidates.sort_by_key(|members| {
+        (
+            std::cmp::Reverse(subtrees[members[0]].1.len()),
+            subtrees[members[0]].0.value(),
+        )
+    });
+
+    let mut covered = HashSet::new();
+    let mut groups = Vec::new();
+    for mut members in candidates {
+        if members
+            .iter()
+            .all(|index| covered.contains(&subtrees[*index].0))
+        {
+            continue;
+        }
+        members.sort();
+        for index in &members {
+            covered.extend(subtrees[*index].1.iter().skip(1).copied());
+        }
+
+        let (_, first) = &subtrees[members[0]];
+        let mut differences = Vec::new();
+        let mut total = 0;
+        let mut equal = 0;
+        for index in &members[1..] {
+            let (_, other) = &subtrees[*index];
+            for (a, b) in first.iter().zip(other) {
+                let (Some(object_a), Some(object_b)) =
+                    (pool.object_by_id(*a), pool.object_by_id(*b))
+                else {
+                    continue;
+                };
+                for ((name, value_a), (_, value_b)) in comparable_attributes(object_a)
+                    .into_iter()
+                    .zip(comparable_attributes(object_b))
+                {
+                    total += 1;
+                    if value_a == value_b {
+                        equal += 1;
+                    } else {
+                        differences.push(SubtreeDifference {
+                            objects: (*a, *b),
+                            attribute: name,
+                            values: (value_a, value_b),
+                        });
+                    }
+                }
+            }
+        }
+        groups.push(SimilarGroup {
+            objects: members.iter().map(|index| subtrees[*index].0).collect(),
+            subtree_size: first.len(),
+            differences,
+            similarity: if total == 0 {
+                1.0
+            } else {
+                equal as f32 / total as f32
+            },
+        });
+    }
+    groups
+}
+
+/// Consolidate exact duplicates: references to the other objects are changed to the kept one,
+/// then the others are removed with the objects of their subtrees that are no longer used
+pub fn merge_duplicates(project: &EditorProject, keep: ObjectId, others: &[ObjectId]) {
+    let map: HashMap<ObjectId, ObjectId> = others.iter().map(|id| (*id, keep)).collect();
+    let mut pool = project.get_mut_pool().borrow_mut();
+    for object in pool.objects_mut() {
+        remap_references(object, &map);
+        remap_macro_references(object, &map);
+    }
+    // Objects changed or set by macros stay, even if nothing references them anymore
+    let macro_objects: HashSet<ObjectId> = macro_targets(&pool)
+        .into_iter()
+        .map(|target| target.object)
+        .chain(macro_value_objects(&pool))
+        .collect();
+
+    let mut removed: HashSet<ObjectId> = others.iter().copied().collect();
+    let mut candidates: Vec<ObjectId> = others.to_vec();
+    while let Some(id) = candidates.pop() {
+        let Some(object) = pool.object_by_id(id) else {
+            continue;
+        };
+        for child in object.referenced_objects() {
+            let still_used = pool
+                .objects()
+                .iter()
+                .filter(|o| !removed.contains(&o.id()))
+                .any(|o| o.referenced_objects().contains(&child));
+            if !still_used && !macro_objects.contains(&child) && removed.insert(child) {
+                candidates.push(child);
+            }
+        }
+    }
+    for id in &removed {
+        pool.remove(*id);
+    }
+    drop(pool);
+
+    project.describe_change(
+        format!(
+            "Merge {} duplicates into object {}",
+            others.len(),
+            keep.value()
+        ),
+        ChangeCategory::BulkChange,
+    );
+}
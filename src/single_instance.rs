@@ -0,0 +1,222 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Opening files in an already running designer instead of starting another window, and
+//! registering the file types with the operating system so double-clicking a pool opens it.
+
+use std::io::{BufRead, BufReader, Write};
+use std::net::{Ipv4Addr, SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver};
+use std::time::Duration;
+
+/// Local port the running instance listens on for files to open
+pub const SINGLE_INSTANCE_PORT: u16 = 9741;
+
+/// File types the designer opens: pools, projects and workspaces
+pub const OPENABLE_EXTENSIONS: [&str; 3] = ["iop", "aitp", "aitw"];
+
+/// First line of a request, so other programs on the port are not mistaken for the designer
+const HANDSHAKE: &str = "AgIsoTerminalDesigner open";
+
+/// How long to wait for a running instance to answer
+const FORWARD_TIMEOUT: Duration = Duration::from_millis(500);
+
+/// MIME types of the openable file types on Linux, by extension
+const MIME_TYPES: [(&str, &str, &str); 3] = [
+    (
+        "iop",
+        "application/x-isobus-object-pool",
+        "ISOBUS object pool",
+    ),
+    (
+        "aitp",
+        "application/x-agisoterminal-project",
+        "AgIsoTerminal project",
+    ),
+    (
+        "aitw",
+        "application/x-agisoterminal-workspace",
+        "AgIsoTerminal workspace",
+    ),
+];
+
+fn is_openable(path: &Path) -> bool {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .is_some_and(|extension| {
+            OPENABLE_EXTENSIONS
+                .iter()
+                .any(|openable| openable.eq_ignore_ascii_case(extension))
+        })
+}
+
+/// The files to open from the command line arguments, e.g. the file that was double-clicked
+pub fn file_arguments(args: &[String]) -> Vec<PathBuf> {
+    args.iter()
+        .skip(1)
+        .filter(|arg| !arg.starts_with('-'))
+        .map(PathBuf::from)
+        .filter(|path| is_openable(path))
+        .map(|path| std::fs::canonicalize(&path).unwrap_or(path))
+        .collect()
+}
+
+/// Hand the files to an instance that is already running. Returns false if there is none, then
+/// this instance should open them itself.
+pub fn forward_to_running_instance(paths: &[PathBuf]) -> bool {
+    let address = SocketAddr::from((Ipv4Addr::LOCALHOST, SINGLE_INSTANCE_PORT));
+    let Ok(mut stream) = TcpStream::connect_timeout(&address, FORWARD_TIMEOUT) else {
+        return false;
+    };
+    stream.set_read_timeout(Some(FORWARD_TIMEOUT)).ok();
+    let mut request = format!("{}\n", HANDSHAKE);
+    for path in paths {
+        request.push_str(&format!("{}\n", path.display()));
+    }
+    request.push('\n');
+    if stream.write_all(request.as_bytes()).is_err() {
+        return false;
+    }
+    let mut reply = String::new();
+    BufReader::new(stream).read_line(&mut reply).is_ok() && reply.trim() == "ok"
+}
+
+/// Read the paths of one request, answering it if it came from another instance
+fn read_request(stream: TcpStream) -> Vec<PathBuf> {
+    stream.set_read_timeout(Some(FORWARD_TIMEOUT)).ok();
+    let mut reader = BufReader::new(&stream);
+    let mut handshake = String::new();
+    if reader.read_line(&mut handshake).is_err() || handshake.trim_end() != HANDSHAKE {
+        return Vec::new();
+    }
+    let paths = reader
+        .lines()
+        .map_while(Result::ok)
+        .take_while(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .collect();
+    (&stream).write_all(b"ok\n").ok();
+    paths
+}
+
+/// Listen for files that other instances hand over, calling `notify` after each request so the
+/// user interface can wake up. Returns None if another instance is already listening.
+pub fn listen_for_open_requests(notify: impl Fn() + Send + 'static) -> Option<Receiver<PathBuf>> {
+    let listener = TcpListener::bind((Ipv4Addr::LOCALHOST, SINGLE_INSTANCE_PORT)).ok()?;
+    let (sender, receiver) = channel();
+    std::thread::spawn(move || {
+        for stream in listener.incoming().map_while(Result::ok) {
+            for path in read_request(stream) {
+                if sender.send(path).is_err() {
+                    return;
+                }
+            }
+            notify();
+        }
+    });
+    Some(receiver)
+}
+
+/// Register the designer as the application for pools, projects and workspaces of the current
+/// user. Returns what was registered.
+pub fn register_file_associations(executable: &Path) -> Result<Vec<String>, String> {
+    if cfg!(target_os = "windows") {
+        register_windows(executable)
+    } else if cfg!(target_os = "macos") {
+        Err("On macOS the file types are registered by the application bundle".to_string())
+    } else {
+        register_freedesktop(executable)
+    }
+}
+
+/// Add the file types to the registry of the current user
+fn register_windows(executable: &Path) -> Result<Vec<String>, String> {
+    let reg = |key: String, value: String| {
+        let status = std::process::Command::new("reg")
+            .args(["add", &key, "/ve", "/d", &value, "/f"])
+            .status()
+            .map_err(|e| format!("Failed to run reg: {}", e))?;
+        if status.success() {
+            Ok(())
+        } else {
+            Err(format!("Failed to write registry key {}", key))
+        }
+    };
+    let mut log = Vec::new();
+    for (extension, _, description) in MIME_TYPES {
+        let class = format!("AgIsoTerminalDesigner.{}", extension);
+        let classes = r"HKCU\Software\Classes";
+        reg(format!(r"{}\.{}", classes, extension), class.clone())?;
+        reg(format!(r"{}\{}", classes, class), description.to_string())?;
+        reg(
+            format!(r"{}\{}\shell\open\command", classes, class),
+            format!("\"{}\" \"%1\"", executable.display()),
+        )?;
+        log.push(format!("Registered .{} files", extension));
+    }
+    Ok(log)
+}
+
+/// Write a MIME package and desktop entry to the data directory of the user
+fn register_freedesktop(executable: &Path) -> Result<Vec<String>, String> {
+    let data_dir = std::env::var_os("XDG_DATA_HOME")
+        .map(PathBuf::from)
+        .or_else(|| std::env::var_os("HOME").map(|home| PathBuf::from(home).join(".local/share")))
+        .ok_or("Could not find the data directory of the user")?;
+    let write = |path: PathBuf, contents: String| {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create {}: {}", parent.display(), e))?;
+        }
+        std::fs::write(&path, contents)
+            .map_err(|e| format!("Failed to write {}: {}", path.display(), e))?;
+        Ok::<_, String>(format!("Wrote {}", path.display()))
+    };
+    let mut log = Vec::new();
+
+    let mut mime = String::from(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+         <mime-info xmlns=\"http://www.freedesktop.org/standards/shared-mime-info\">\n",
+    );
+    for (extension, mime_type, description) in MIME_TYPES {
+        mime.push_str(&format!(
+            "  <mime-type type=\"{}\">\n    <comment>{}</comment>\n    \
+             <glob pattern=\"*.{}\"/>\n  </mime-type>\n",
+            mime_type, description, extension
+        ));
+    }
+    mime.push_str("</mime-info>\n");
+    let mime_dir = data_dir.join("mime");
+    log.push(write(
+        mime_dir.join("packages/ag-iso-terminal-designer.xml"),
+        mime,
+    )?);
+
+    let mime_types: Vec<&str> = MIME_TYPES.iter().map(|(_, mime, _)| *mime).collect();
+    let desktop_entry = format!(
+        "[Desktop Entry]\nType=Application\nName=AgIsoTerminalDesigner\n\
+         Comment=Design ISOBUS virtual terminal object pools\nExec=\"{}\" %F\n\
+         Categories=Development;\nMimeType={};\n",
+        executable.display(),
+        mime_types.join(";")
+    );
+    let applications_dir = data_dir.join("applications");
+    log.push(write(
+        applications_dir.join("ag-iso-terminal-designer.desktop"),
+        desktop_entry,
+    )?);
+
+    // Refresh the caches, desktops pick the files up at the next login if the tools are missing
+    for (tool, dir) in [
+        ("update-mime-database", &mime_dir),
+        ("update-desktop-database", &applications_dir),
+    ] {
+        match std::process::Command::new(tool).arg(dir).status() {
+            Ok(status) if status.success() => log.push(format!("Ran {}", tool)),
+            _ => log.push(format!("Could not run {}, log in again to apply", tool)),
+        }
+    }
+    Ok(log)
+}
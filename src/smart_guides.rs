@@ -0,0 +1,243 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use eframe::egui::{pos2, vec2, Pos2, Rect, Vec2};
+
+/// Distance in pixels within which a dragged object snaps to a guide
+pub const SNAP_DISTANCE: f32 = 4.0;
+
+/// A guide shown while dragging, in the coordinates of the rectangles
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Guide {
+    /// Edges or centres that line up
+    Alignment { from: Pos2, to: Pos2 },
+    /// A gap that is as large as another gap
+    Spacing { from: Pos2, to: Pos2 },
+}
+
+/// Where a dragged rectangle snaps to and the guides to show for it
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Snap {
+    /// Offset to add to the dragged rectangle
+    pub offset: Vec2,
+    pub guides: Vec<Guide>,
+}
+
+/// One axis of the rectangles, so both axes share the same logic
+#[derive(Debug, Clone, Copy)]
+struct Span {
+    min: f32,
+    max: f32,
+}
+
+impl Span {
+    fn centre(&self) -> f32 {
+        (self.min + self.max) / 2.0
+    }
+
+    fn lines(&self) -> [f32; 3] {
+        [self.min, self.centre(), self.max]
+    }
+
+    fn overlaps(&self, other: &Span) -> bool {
+        self.min < other.max && other.min < self.max
+    }
+}
+
+fn spans(rect: &Rect, vertical: bool) -> (Span, Span) {
+    let x = Span {
+        min: rect.min.x,
+        max: rect.max.x,
+    };
+    let y = Span {
+        min: rect.min.y,
+        max: rect.max.y,
+    };
+    if vertical {
+        (y, x)
+    } else {
+        (x, y)
+    }
+}
+
+/// Point from a position along the axis and across it
+fn point(along: f32, across: f32, vertical: bool) -> Pos2 {
+    if vertical {
+        pos2(across, along)
+    } else {
+        pos2(along, across)
+    }
+}
+
+/// Gaps between rectangles that are next to each other along the axis
+fn gaps(rects: &[(Span, Span)]) -> Vec<f32> {
+    let mut gaps = Vec::new();
+    for (a, a_across) in rects {
+        let next = rects
+            .iter()
+            .filter(|(b, b_across)| b.min >= a.max && a_across.overlaps(b_across))
+            .map(|(b, _)| b.min - a.max)
+            .min_by(f32::total_cmp);
+        gaps.extend(next.filter(|gap| *gap > 0.0));
+    }
+    gaps
+}
+
+/// The smallest move along one axis that aligns the span with a sibling or gives it the same
+/// gap as other siblings
+fn snap_axis(moving: Rect, siblings: &[Rect], parent: Option<Rect>, vertical: bool) -> f32 {
+    let (span, across) = spans(&moving, vertical);
+    let others: Vec<(Span, Span)> = siblings.iter().map(|r| spans(r, vertical)).collect();
+    let mut targets: Vec<(f32, f32)> = Vec::new();
+
+    // Edges and centres of the siblings and the parent
+    let parent_span = parent.map(|p| spans(&p, vertical).0);
+    for other in others
+        .iter()
+        .map(|(other, _)| other)
+        .chain(parent_span.iter())
+    {
+        for line in span.lines() {
+            for target in other.lines() {
+                targets.push((line, target));
+            }
+        }
+    }
+
+    // Gaps to the neighbours before and after that match other gaps, or are equal
+    let neighbours: Vec<&(Span, Span)> =
+        others.iter().filter(|(_, o)| o.overlaps(&across)).collect();
+    let before = neighbours
+        .iter()
+        .filter(|(o, _)| o.max <= span.min + SNAP_DISTANCE)
+        .max_by(|a, b| a.0.max.total_cmp(&b.0.max));
+    let after = neighbours
+        .iter()
+        .filter(|(o, _)| o.min >= span.max - SNAP_DISTANCE)
+        .min_by(|a, b| a.0.min.total_cmp(&b.0.min));
+    for gap in gaps(&others) {
+        if let Some((before, _)) = before {
+            targets.push((span.min, before.max + gap));
+        }
+        if let Some((after, _)) = after {
+            targets.push((span.max, after.min - gap));
+        }
+    }
+    if let (Some((before, _)), Some((after, _))) = (before, after) {
+        let centre = (before.max + after.min) / 2.0;
+        targets.push((span.centre(), centre));
+    }
+
+    targets
+        .into_iter()
+        .map(|(line, target)| target - line)
+        .filter(|delta| delta.abs() <= SNAP_DISTANCE)
+        .min_by(|a, b| a.abs().total_cmp(&b.abs()))
+        .unwrap_or(0.0)
+}
+
+/// The guides for a rectangle that is already snapped
+fn guides_axis(
+    moving: Rect,
+    siblings: &[Rect],
+    parent: Option<Rect>,
+    vertical: bool,
+) -> Vec<Guide> {
+    let (span, across) = spans(&moving, vertical);
+    let others: Vec<(Span, Span)> = siblings.iter().map(|r| spans(r, vertical)).collect();
+    let mut guides = Vec::new();
+    let aligned = |a: f32, b: f32| (a - b).abs() < 0.5;
+
+    for (other, other_across) in others
+        .iter()
+        .chain(parent.map(|p| spans(&p, vertical)).iter())
+    {
+        for line in span.lines() {
+            if other.lines().iter().any(|target| aligned(line, *target)) {
+                let from = across.min.min(other_across.min);
+                let to = across.max.max(other_across.max);
+                guides.push(Guide::Alignment {
+                    from: point(line, from, vertical),
+                    to: point(line, to, vertical),
+                });
+            }
+        }
+    }
+
+    // Gaps to the direct neighbours that are as large as another gap
+    let mut rects = others.clone();
+    rects.push((span, across));
+    let all_gaps = gaps(&rects);
+    for (other, other_across) in others.iter().filter(|(_, o)| o.overlaps(&across)) {
+        let gap = if other.max <= span.min {
+            Some((other.max, span.min))
+        } else if other.min >= span.max {
+            Some((span.max, other.min))
+        } else {
+            None
+        };
+        let Some((start, end)) = gap.filter(|(start, end)| end > start) else {
+            continue;
+        };
+        let size = end - start;
+        if all_gaps.iter().filter(|g| aligned(**g, size)).count() > 1 {
+            let middle =
+                (across.min.max(other_across.min) + across.max.min(other_across.max)) / 2.0;
+            guides.push(Guide::Spacing {
+                from: point(start, middle, vertical),
+                to: point(end, middle, vertical),
+            });
+        }
+    }
+    guides
+}
+
+/// Snap a dragged rectangle to the edges and centres of its siblings and parent, and to the
+/// gaps between the siblings, within [`SNAP_DISTANCE`]
+pub fn snap_rect(moving: Rect, siblings: &[Rect], parent: Option<Rect>) -> Snap {
+    let offset = vec2(
+        snap_axis(moving, siblings, parent, false),
+        snap_axis(moving, siblings, parent, true),
+    );
+    let snapped = moving.translate(offset);
+    let mut guides = guides_axis(snapped, siblings, parent, false);
+    guides.extend(guides_axis(snapped, siblings, parent, true));
+    Snap { offset, guides }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect {
+        Rect::from_min_size(pos2(x, y), vec2(width, height))
+    }
+
+    #[test]
+    fn rectangles_snap_to_nearby_edges() {
+        let sibling = rect(0.0, 0.0, 20.0, 20.0);
+        let snap = snap_rect(rect(3.0, 30.0, 20.0, 10.0), &[sibling], None);
+        assert_eq!(snap.offset, vec2(-3.0, 0.0));
+        assert!(snap.guides.contains(&Guide::Alignment {
+            from: pos2(0.0, 0.0),
+            to: pos2(0.0, 40.0)
+        }));
+
+        // Too far away to snap
+        let snap = snap_rect(rect(50.0, 50.0, 10.0, 10.0), &[sibling], None);
+        assert_eq!(snap.offset, Vec2::ZERO);
+        assert!(snap.guides.is_empty());
+    }
+
+    #[test]
+    fn rectangles_snap_to_equal_gaps() {
+        let siblings = [rect(0.0, 0.0, 10.0, 10.0), rect(20.0, 0.0, 10.0, 10.0)];
+        let snap = snap_rect(rect(42.0, 0.0, 10.0, 10.0), &siblings, None);
+        assert_eq!(snap.offset.x, -2.0);
+        assert!(snap
+            .guides
+            .iter()
+            .any(|guide| matches!(guide, Guide::Spacing { .. })));
+    }
+}
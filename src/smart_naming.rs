@@ -2,12 +2,35 @@
 //! SPDX-License-Identifier: GPL-3.0-or-later
 //! Authors: Daan Steenbergen
 
-use ag_iso_stack::object_pool::{object::Object, ObjectPool, ObjectType};
+use ag_iso_stack::object_pool::{object::Object, ObjectId, ObjectPool, ObjectType};
 use std::collections::HashMap;
 
+use crate::layout::object_refs;
+use crate::reference_paths::subtree;
+
 /// ISOBUS maximum object count (16-bit IDs)
 const MAX_OBJECTS: u32 = 65535;
 
+/// Longest text of a string object that is used in a name
+const MAX_TEXT_LENGTH: usize = 24;
+
+/// How deep the children of an object are searched for a text that names it
+const TEXT_SEARCH_DEPTH: usize = 3;
+
+/// Objects that are not named after the mask they are used on, they are often shared
+const SHARED_TYPES: &[ObjectType] = &[
+    ObjectType::FontAttributes,
+    ObjectType::LineAttributes,
+    ObjectType::FillAttributes,
+    ObjectType::InputAttributes,
+    ObjectType::ExtendedInputAttributes,
+    ObjectType::NumberVariable,
+    ObjectType::StringVariable,
+    ObjectType::Macro,
+    ObjectType::ColourMap,
+    ObjectType::ColourPalette,
+];
+
 /// Get a user-friendly name for an object type
 pub fn get_object_type_name(object_type: ObjectType) -> &'static str {
     match object_type {
@@ -87,3 +110,114 @@ pub fn generate_smart_default_name(
         counter += 1;
     }
 }
+
+/// The name, or the name with the lowest number after it that is not used yet
+pub fn unique_name(name: &str, existing_names: &HashMap<String, ObjectType>) -> String {
+    let mut candidate = name.to_string();
+    let mut counter = 2;
+    while existing_names.contains_key(&candidate) {
+        candidate = format!("{} {}", name, counter);
+        counter += 1;
+    }
+    candidate
+}
+
+/// The text a string object shows, from its string variable if it has one
+fn shown_text(pool: &ObjectPool, object: &Object) -> Option<String> {
+    let (value, variable) = match object {
+        Object::OutputString(o) => (&o.value, o.variable_reference),
+        Object::InputString(o) => (&o.value, o.variable_reference),
+        _ => return None,
+    };
+    let value = match variable.0.and_then(|id| pool.object_by_id(id)) {
+        Some(Object::StringVariable(variable)) => &variable.value,
+        _ => value,
+    };
+    let text: String = value
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .chars()
+        .filter(|c| !c.is_control())
+        .take(MAX_TEXT_LENGTH)
+        .collect();
+    let text = text.trim();
+    (!text.is_empty()).then(|| text.to_string())
+}
+
+/// The first text an object shows, children are searched from top to bottom and left to right
+fn first_text(pool: &ObjectPool, object: &Object, depth: usize) -> Option<String> {
+    if let Some(text) = shown_text(pool, object) {
+        return Some(text);
+    }
+    if depth == 0 {
+        return None;
+    }
+    let mut children: Vec<_> = object_refs(object)?.iter().collect();
+    children.sort_by_key(|child| (child.offset.y, child.offset.x));
+    children
+        .into_iter()
+        .filter_map(|child| pool.object_by_id(child.id))
+        .find_map(|child| first_text(pool, child, depth - 1))
+}
+
+/// Names for the objects of an imported pool from the texts they show: a button is named after
+/// its label, an object on a mask after the mask and a variable after the object showing it.
+/// Objects without a text to go by are not in the map.
+pub fn content_based_names(pool: &ObjectPool) -> HashMap<ObjectId, String> {
+    let mut names = HashMap::new();
+    for object in pool.objects() {
+        let text = match object {
+            Object::OutputString(_) | Object::InputString(_) => shown_text(pool, object),
+            Object::Button(_)
+            | Object::Key(_)
+            | Object::Container(_)
+            | Object::DataMask(_)
+            | Object::AlarmMask(_)
+            | Object::WindowMask(_) => first_text(pool, object, TEXT_SEARCH_DEPTH),
+            _ => None,
+        };
+        if let Some(text) = text {
+            let type_name = get_object_type_name(object.object_type());
+            names.insert(object.id(), format!("{} {}", type_name, text));
+        }
+    }
+
+    for mask in pool.objects_by_types(&[ObjectType::DataMask, ObjectType::AlarmMask]) {
+        let Some(mask_text) = first_text(pool, mask, TEXT_SEARCH_DEPTH) else {
+            continue;
+        };
+        for id in subtree(pool, mask.id()).into_iter().skip(1) {
+            let Some(object) = pool.object_by_id(id) else {
+                continue;
+            };
+            if !SHARED_TYPES.contains(&object.object_type()) {
+                let type_name = get_object_type_name(object.object_type());
+                names
+                    .entry(id)
+                    .or_insert_with(|| format!("{} on {}", type_name, mask_text));
+            }
+        }
+    }
+
+    for object in pool.objects() {
+        let Some(name) = names.get(&object.id()).cloned() else {
+            continue;
+        };
+        for id in object.referenced_objects() {
+            let Some(variable) = pool.object_by_id(id) else {
+                continue;
+            };
+            if matches!(
+                variable.object_type(),
+                ObjectType::NumberVariable | ObjectType::StringVariable
+            ) {
+                let type_name = get_object_type_name(variable.object_type());
+                names
+                    .entry(id)
+                    .or_insert_with(|| format!("{} of {}", type_name, name));
+            }
+        }
+    }
+    names
+}
@@ -0,0 +1,270 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{BTreeSet, HashSet};
+use std::ops::Range;
+use std::sync::Arc;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::ObjectId;
+use eframe::egui;
+
+use crate::preview_state::with_preview_state;
+use crate::{tr, EditorProject};
+
+const SPELL_CHECKER_ID: &str = "spell_checker";
+
+/// Words of a single language
+#[derive(Debug, Clone, Default)]
+pub struct Dictionary {
+    pub language: String,
+    words: HashSet<String>,
+}
+
+impl Dictionary {
+    /// Parse a plain word list (one word per line) or a Hunspell .dic file.
+    /// Hunspell affix flags are ignored, so only the listed word forms are known.
+    pub fn from_word_list(language: String, text: &str) -> Self {
+        let mut lines = text.lines().peekable();
+        // Hunspell dictionaries start with the number of words
+        if lines
+            .peek()
+            .is_some_and(|l| l.trim().chars().all(|c| c.is_ascii_digit()))
+        {
+            lines.next();
+        }
+        let words = lines
+            .filter_map(|line| line.split('/').next())
+            .map(|word| word.trim().to_lowercase())
+            .filter(|word| !word.is_empty())
+            .collect();
+        Dictionary { language, words }
+    }
+
+    pub fn len(&self) -> usize {
+        self.words.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn contains(&self, word: &str) -> bool {
+        self.words.contains(&word.to_lowercase())
+    }
+}
+
+/// Checks words against the loaded dictionaries, a word is correct if any dictionary knows it
+#[derive(Debug, Clone, Default)]
+pub struct SpellChecker {
+    pub enabled: bool,
+    pub dictionaries: Vec<Dictionary>,
+
+    /// Words that are accepted in addition to the dictionaries, e.g. product names
+    pub ignored: BTreeSet<String>,
+}
+
+impl SpellChecker {
+    /// Check if the spell checker is enabled and has something to check against
+    pub fn is_active(&self) -> bool {
+        self.enabled && self.dictionaries.iter().any(|d| !d.is_empty())
+    }
+
+    pub fn is_correct(&self, word: &str) -> bool {
+        self.ignored.contains(&word.to_lowercase())
+            || self.dictionaries.iter().any(|d| d.contains(word))
+    }
+
+    /// Byte ranges of the misspelled words in a text.
+    /// Words with digits and short all uppercase words (units, abbreviations) are skipped.
+    pub fn misspelled(&self, text: &str) -> Vec<Range<usize>> {
+        if !self.is_active() {
+            return Vec::new();
+        }
+        words(text)
+            .filter(|range| {
+                let word = &text[range.clone()];
+                let abbreviation =
+                    word.chars().count() <= 4 && word.chars().all(|c| !c.is_lowercase());
+                word.chars().count() > 1
+                    && !word.chars().any(|c| c.is_ascii_digit())
+                    && !abbreviation
+                    && !self.is_correct(word)
+            })
+            .collect()
+    }
+}
+
+/// Byte ranges of the words in a text, apostrophes inside a word belong to the word
+fn words(text: &str) -> impl Iterator<Item = Range<usize>> + '_ {
+    let mut start = None;
+    let mut chars = text.char_indices().peekable();
+    std::iter::from_fn(move || {
+        while let Some((idx, c)) = chars.next() {
+            let next_is_letter = chars.peek().is_some_and(|(_, n)| n.is_alphanumeric());
+            let in_word = c.is_alphanumeric() || (c == '\'' && start.is_some() && next_is_letter);
+            match (in_word, start) {
+                (true, None) => start = Some(idx),
+                (false, Some(s)) => {
+                    start = None;
+                    return Some(s..idx);
+                }
+                _ => {}
+            }
+        }
+        start.take().map(|s| s..text.len())
+    })
+}
+
+/// A misspelled word in a user visible string
+#[derive(Debug, Clone, PartialEq)]
+pub struct SpellingIssue {
+    pub object: ObjectId,
+    pub word: String,
+    pub text: String,
+}
+
+/// User visible text of an object, if it has any
+pub fn visible_text(object: &Object) -> Option<&str> {
+    match object {
+        Object::OutputString(o) => Some(&o.value),
+        Object::InputString(o) => Some(&o.value),
+        Object::StringVariable(o) => Some(&o.value),
+        _ => None,
+    }
+}
+
+/// Find all misspelled words in the strings of the project
+pub fn check_spelling(project: &EditorProject, checker: &SpellChecker) -> Vec<SpellingIssue> {
+    let mut issues = Vec::new();
+    for object in project.get_pool().objects() {
+        let Some(text) = visible_text(object) else {
+            continue;
+        };
+        for range in checker.misspelled(text) {
+            issues.push(SpellingIssue {
+                object: object.id(),
+                word: text[range].to_string(),
+                text: text.to_string(),
+            });
+        }
+    }
+    issues
+}
+
+/// Change the spell checker stored in the egui context
+pub fn with_spell_checker<R>(ctx: &egui::Context, f: impl FnOnce(&mut SpellChecker) -> R) -> R {
+    ctx.data_mut(|data| {
+        let checker =
+            data.get_temp_mut_or_default::<Arc<SpellChecker>>(egui::Id::new(SPELL_CHECKER_ID));
+        f(Arc::make_mut(checker))
+    })
+}
+
+/// The spell checker stored in the egui context. It is shared rather than copied, so reading it
+/// every frame does not clone the dictionaries.
+pub fn spell_checker(ctx: &egui::Context) -> Arc<SpellChecker> {
+    ctx.data_mut(|data| {
+        data.get_temp_mut_or_default::<Arc<SpellChecker>>(egui::Id::new(SPELL_CHECKER_ID))
+            .clone()
+    })
+}
+
+/// Symbol shown for a space or control character when revealing non-printing characters.
+/// Line feeds are left as they are, they already start a new line in the editor.
+fn control_symbol(c: char) -> Option<char> {
+    match c {
+        ' ' => Some('·'),
+        '\r' => Some('¶'),
+        '\t' => Some('»'),
+        '\n' => None,
+        c if c.is_control() => Some('¤'),
+        _ => None,
+    }
+}
+
+/// Append text to a layout job, optionally replacing non-printing characters by visible symbols
+fn append_revealed(
+    job: &mut egui::text::LayoutJob,
+    text: &str,
+    format: &egui::TextFormat,
+    reveal: bool,
+    symbol_colour: egui::Color32,
+) {
+    if !reveal {
+        job.append(text, 0.0, format.clone());
+        return;
+    }
+    let symbol_format = egui::TextFormat {
+        color: symbol_colour,
+        ..format.clone()
+    };
+    let mut start = 0;
+    for (index, c) in text.char_indices() {
+        if let Some(symbol) = control_symbol(c) {
+            job.append(&text[start..index], 0.0, format.clone());
+            job.append(&symbol.to_string(), 0.0, symbol_format.clone());
+            start = index + c.len_utf8();
+        }
+    }
+    job.append(&text[start..], 0.0, format.clone());
+}
+
+/// Text edit for string values that underlines misspelled words. Line breaks can be entered,
+/// and spaces and control characters can be revealed with the toggle next to it.
+pub fn spell_checked_text_edit(ui: &mut egui::Ui, text: &mut String) -> egui::Response {
+    let checker = spell_checker(ui.ctx());
+    let mut reveal = with_preview_state(ui.ctx(), |state| state.show_control_characters);
+
+    let mut layouter = |ui: &egui::Ui, buffer: &dyn egui::TextBuffer, wrap_width: f32| {
+        let text = buffer.as_str();
+        let style = ui.style();
+        let font_id = egui::TextStyle::Body.resolve(style);
+        let colour = style.visuals.text_color();
+        let symbol_colour = style.visuals.weak_text_color();
+        let normal = egui::TextFormat::simple(font_id, colour);
+        let misspelled = egui::TextFormat {
+            underline: egui::Stroke::new(1.5, egui::Color32::RED),
+            ..normal.clone()
+        };
+
+        let mut job = egui::text::LayoutJob::default();
+        let mut position = 0;
+        if checker.is_active() {
+            for range in checker.misspelled(text) {
+                append_revealed(
+                    &mut job,
+                    &text[position..range.start],
+                    &normal,
+                    reveal,
+                    symbol_colour,
+                );
+                append_revealed(
+                    &mut job,
+                    &text[range.clone()],
+                    &misspelled,
+                    reveal,
+                    symbol_colour,
+                );
+                position = range.end;
+            }
+        }
+        append_revealed(&mut job, &text[position..], &normal, reveal, symbol_colour);
+        job.wrap.max_width = wrap_width;
+        ui.fonts(|fonts| fonts.layout_job(job))
+    };
+    let response = ui.add(
+        egui::TextEdit::multiline(text)
+            .desired_rows(1)
+            .layouter(&mut layouter),
+    );
+    if ui
+        .toggle_value(&mut reveal, "¶")
+        .on_hover_text(tr("Show spaces and control characters"))
+        .changed()
+    {
+        with_preview_state(ui.ctx(), |state| state.show_control_characters = reveal);
+    }
+    response
+}
@@ -0,0 +1,45 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Scenarios of the runtime state of a pool: containers shown or hidden and input objects enabled
+//! or disabled, like the working set changes them with Hide/Show and Enable/Disable commands.
+
+use std::collections::BTreeMap;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::ObjectId;
+use serde::{Deserialize, Serialize};
+
+/// Named runtime state of objects, to review a layout in the states the working set uses
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct StateScenario {
+    pub name: String,
+
+    /// Shown state of containers and enabled state of input objects and buttons by object ID.
+    /// Objects that are not listed keep the state of the pool.
+    pub states: BTreeMap<u16, bool>,
+}
+
+impl StateScenario {
+    /// The state of the objects as IDs, invalid IDs are skipped
+    pub fn object_states(&self) -> impl Iterator<Item = (ObjectId, bool)> + '_ {
+        self.states
+            .iter()
+            .filter_map(|(id, state)| ObjectId::new(*id).ok().map(|id| (id, *state)))
+    }
+}
+
+/// Whether a container is shown or an input object or button is enabled in the pool. None for
+/// objects without such a state.
+pub fn pool_state(object: &Object) -> Option<bool> {
+    match object {
+        Object::Container(o) => Some(!o.hidden),
+        Object::Button(o) => Some(!o.options.disabled),
+        Object::InputBoolean(o) => Some(o.enabled),
+        Object::InputString(o) => Some(o.enabled),
+        Object::InputNumber(o) => Some(o.options2.enabled),
+        Object::InputList(o) => Some(o.options.enabled),
+        _ => None,
+    }
+}
@@ -0,0 +1,173 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::{HashMap, HashSet};
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::FontSize;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+use serde::{Deserialize, Serialize};
+
+use crate::reference_paths::parent_map;
+use crate::EditorProject;
+
+/// Translation status of a user visible string
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TranslationStatus {
+    #[default]
+    Untranslated,
+    NeedsUpdate,
+    Translated,
+}
+
+impl TranslationStatus {
+    pub const ALL: [TranslationStatus; 3] = [
+        TranslationStatus::Untranslated,
+        TranslationStatus::NeedsUpdate,
+        TranslationStatus::Translated,
+    ];
+
+    /// Human readable name of the status
+    pub fn label(&self) -> &'static str {
+        match self {
+            TranslationStatus::Untranslated => "Untranslated",
+            TranslationStatus::NeedsUpdate => "Needs update",
+            TranslationStatus::Translated => "Translated",
+        }
+    }
+}
+
+/// A user visible string of the pool
+#[derive(Debug, Clone, PartialEq)]
+pub struct StringEntry {
+    pub object: ObjectId,
+    pub object_type: ObjectType,
+    pub value: String,
+
+    /// Length of the value in bytes, as it is stored in the pool. The pool uses ISO 8859 with one
+    /// byte per character, not UTF-8.
+    pub length: usize,
+
+    /// Number of characters that fit in the displaying object(s).
+    /// None if the string is not displayed or uses a proportional font.
+    pub capacity: Option<usize>,
+
+    /// Masks that (indirectly) display the string
+    pub masks: Vec<ObjectId>,
+    pub translation: TranslationStatus,
+}
+
+impl StringEntry {
+    /// Check if the string is longer than the objects that display it can fit
+    pub fn exceeds_capacity(&self) -> bool {
+        self.capacity
+            .is_some_and(|capacity| self.value.chars().count() > capacity)
+    }
+}
+
+/// Number of characters a string object can show with its non proportional font
+fn display_capacity(pool: &ObjectPool, object: &Object) -> Option<usize> {
+    let (width, height, font_attributes) = match object {
+        Object::OutputString(o) => (o.width, o.height, o.font_attributes),
+        Object::InputString(o) => (o.width, o.height, o.font_attributes),
+        _ => return None,
+    };
+    let Some(Object::FontAttributes(font)) = pool.object_by_id(font_attributes) else {
+        return None;
+    };
+    match font.font_size {
+        FontSize::NonProportional(size) => {
+            let columns = width / size.width().max(1) as u16;
+            let rows = height / size.height().max(1) as u16;
+            Some(columns as usize * rows as usize)
+        }
+        FontSize::Proportional(_) => None,
+    }
+}
+
+/// Masks that display an object, found by walking up the references
+fn displaying_masks(
+    pool: &ObjectPool,
+    parents: &HashMap<ObjectId, Vec<ObjectId>>,
+    id: ObjectId,
+) -> Vec<ObjectId> {
+    let mut masks = Vec::new();
+    let mut visited = HashSet::new();
+    let mut stack = vec![id];
+    while let Some(id) = stack.pop() {
+        if !visited.insert(id) {
+            continue;
+        }
+        let is_mask = pool.object_by_id(id).is_some_and(|o| {
+            matches!(
+                o.object_type(),
+                ObjectType::DataMask
+                    | ObjectType::AlarmMask
+                    | ObjectType::SoftKeyMask
+                    | ObjectType::WindowMask
+            )
+        });
+        if is_mask {
+            masks.push(id);
+        } else if let Some(p) = parents.get(&id) {
+            stack.extend(p.iter().copied());
+        }
+    }
+    masks.sort_by_key(|id| id.value());
+    masks
+}
+
+/// List every user visible string of the project.
+/// String variables take the smallest capacity of the string objects that reference them.
+pub fn string_inventory(project: &EditorProject) -> Vec<StringEntry> {
+    let pool = project.get_pool();
+    let parents = parent_map(pool);
+    let object_info = project.object_info.borrow();
+
+    let mut entries = Vec::new();
+    for object in pool.objects() {
+        let value = match object {
+            Object::OutputString(o) if o.variable_reference.0.is_none() => &o.value,
+            Object::InputString(o) if o.variable_reference.0.is_none() => &o.value,
+            Object::StringVariable(o) => &o.value,
+            _ => continue,
+        };
+
+        let capacity = match object {
+            Object::StringVariable(_) => parents
+                .get(&object.id())
+                .into_iter()
+                .flatten()
+                .filter_map(|p| pool.object_by_id(*p))
+                .filter_map(|p| display_capacity(pool, p))
+                .min(),
+            _ => display_capacity(pool, object),
+        };
+
+        entries.push(StringEntry {
+            object: object.id(),
+            object_type: object.object_type(),
+            value: value.clone(),
+            length: value.chars().count(),
+            capacity,
+            masks: displaying_masks(pool, &parents, object.id()),
+            translation: object_info
+                .get(&object.id())
+                .map(|info| info.translation)
+                .unwrap_or_default(),
+        });
+    }
+    entries
+}
+
+/// Change the value of a string object in the mutating pool
+pub fn set_string_value(project: &EditorProject, id: ObjectId, value: String) {
+    let mut pool = project.get_mut_pool().borrow_mut();
+    match pool.object_mut_by_id(id) {
+        Some(Object::OutputString(o)) => o.value = value,
+        Some(Object::InputString(o)) => o.value = value,
+        Some(Object::StringVariable(o)) => o.value = value,
+        _ => {}
+    }
+}
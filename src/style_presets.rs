@@ -0,0 +1,267 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::BTreeMap;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::{
+    Alignment, HorizontalAlignment, VerticalAlignment,
+};
+use ag_iso_stack::object_pool::{NullableObjectId, ObjectId};
+use serde::{Deserialize, Serialize};
+
+use crate::colour_roles::colour_attributes_mut;
+
+/// Value of an attribute in a style preset
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, PartialEq)]
+pub enum PresetValue {
+    /// Palette index
+    Colour(u8),
+
+    /// Referenced attribute object, None for no object
+    Object(Option<u16>),
+    Flag(bool),
+
+    /// Justification with the horizontal alignment in bits 0-1 and the vertical in bits 2-3
+    Justification(u8),
+}
+
+/// Named set of attributes that are applied together, e.g. "Heading" with a font and background
+/// colour. Objects keep the preset they were given, so changes to the preset can be re-applied.
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct StylePreset {
+    pub name: String,
+
+    /// Values of the attributes the preset sets, by attribute name
+    pub values: BTreeMap<String, PresetValue>,
+}
+
+/// Mutable reference to an attribute a preset can set
+enum PresetAttribute<'a> {
+    Colour(&'a mut u8),
+    Object(&'a mut ObjectId),
+    NullableObject(&'a mut NullableObjectId),
+    Flag(&'a mut bool),
+    Justification(&'a mut Alignment),
+}
+
+fn justification_value(alignment: &Alignment) -> u8 {
+    let horizontal = match alignment.horizontal {
+        HorizontalAlignment::Left => 0,
+        HorizontalAlignment::Middle => 1,
+        HorizontalAlignment::Right => 2,
+        HorizontalAlignment::Reserved => 3,
+    };
+    let vertical = match alignment.vertical {
+        VerticalAlignment::Top => 0,
+        VerticalAlignment::Middle => 1,
+        VerticalAlignment::Bottom => 2,
+        VerticalAlignment::Reserved => 3,
+    };
+    horizontal | (vertical << 2)
+}
+
+fn justification_from_value(value: u8) -> Alignment {
+    Alignment {
+        horizontal: match value & 0x03 {
+            0 => HorizontalAlignment::Left,
+            1 => HorizontalAlignment::Middle,
+            2 => HorizontalAlignment::Right,
+            _ => HorizontalAlignment::Reserved,
+        },
+        vertical: match (value >> 2) & 0x03 {
+            0 => VerticalAlignment::Top,
+            1 => VerticalAlignment::Middle,
+            2 => VerticalAlignment::Bottom,
+            _ => VerticalAlignment::Reserved,
+        },
+    }
+}
+
+impl PresetAttribute<'_> {
+    fn value(&self) -> PresetValue {
+        match self {
+            PresetAttribute::Colour(colour) => PresetValue::Colour(**colour),
+            PresetAttribute::Object(id) => PresetValue::Object(Some(id.value())),
+            PresetAttribute::NullableObject(id) => PresetValue::Object(id.0.map(|id| id.value())),
+            PresetAttribute::Flag(flag) => PresetValue::Flag(**flag),
+            PresetAttribute::Justification(alignment) => {
+                PresetValue::Justification(justification_value(alignment))
+            }
+        }
+    }
+
+    /// Set the attribute to the value, returns whether it changed
+    fn set(&mut self, value: PresetValue) -> bool {
+        if self.value() == value {
+            return false;
+        }
+        match (self, value) {
+            (PresetAttribute::Colour(colour), PresetValue::Colour(value)) => **colour = value,
+            (PresetAttribute::Object(id), PresetValue::Object(Some(value))) => {
+                let Ok(value) = ObjectId::new(value) else {
+                    return false;
+                };
+                **id = value;
+            }
+            (PresetAttribute::NullableObject(id), PresetValue::Object(value)) => {
+                **id = NullableObjectId(value.and_then(|value| ObjectId::new(value).ok()));
+            }
+            (PresetAttribute::Flag(flag), PresetValue::Flag(value)) => **flag = value,
+            (PresetAttribute::Justification(alignment), PresetValue::Justification(value)) => {
+                **alignment = justification_from_value(value)
+            }
+            _ => return false,
+        }
+        true
+    }
+}
+
+/// The colour attributes of an object, named like the colour roles
+fn colour_preset_attributes(object: &mut Object) -> Vec<(String, PresetAttribute<'_>)> {
+    colour_attributes_mut(object)
+        .into_iter()
+        .map(|(name, colour)| (format!("{} colour", name), PresetAttribute::Colour(colour)))
+        .collect()
+}
+
+/// The attributes other than colours of an object a preset can set, by attribute name
+fn other_preset_attributes(object: &mut Object) -> Vec<(String, PresetAttribute<'_>)> {
+    let attributes = match object {
+        Object::InputString(o) => vec![
+            (
+                "Font attributes",
+                PresetAttribute::Object(&mut o.font_attributes),
+            ),
+            (
+                "Transparent",
+                PresetAttribute::Flag(&mut o.options.transparent),
+            ),
+            (
+                "Justification",
+                PresetAttribute::Justification(&mut o.justification),
+            ),
+        ],
+        Object::OutputString(o) => vec![
+            (
+                "Font attributes",
+                PresetAttribute::Object(&mut o.font_attributes),
+            ),
+            (
+                "Transparent",
+                PresetAttribute::Flag(&mut o.options.transparent),
+            ),
+            (
+                "Justification",
+                PresetAttribute::Justification(&mut o.justification),
+            ),
+        ],
+        Object::InputNumber(o) => vec![
+            (
+                "Font attributes",
+                PresetAttribute::Object(&mut o.font_attributes),
+            ),
+            (
+                "Transparent",
+                PresetAttribute::Flag(&mut o.options.transparent),
+            ),
+            (
+                "Justification",
+                PresetAttribute::Justification(&mut o.justification),
+            ),
+        ],
+        Object::OutputNumber(o) => vec![
+            (
+                "Font attributes",
+                PresetAttribute::Object(&mut o.font_attributes),
+            ),
+            (
+                "Transparent",
+                PresetAttribute::Flag(&mut o.options.transparent),
+            ),
+            (
+                "Justification",
+                PresetAttribute::Justification(&mut o.justification),
+            ),
+        ],
+        Object::OutputLine(o) => vec![(
+            "Line attributes",
+            PresetAttribute::Object(&mut o.line_attributes),
+        )],
+        Object::OutputRectangle(o) => vec![
+            (
+                "Line attributes",
+                PresetAttribute::Object(&mut o.line_attributes),
+            ),
+            (
+                "Fill attributes",
+                PresetAttribute::NullableObject(&mut o.fill_attributes),
+            ),
+        ],
+        Object::OutputEllipse(o) => vec![
+            (
+                "Line attributes",
+                PresetAttribute::Object(&mut o.line_attributes),
+            ),
+            (
+                "Fill attributes",
+                PresetAttribute::NullableObject(&mut o.fill_attributes),
+            ),
+        ],
+        Object::OutputPolygon(o) => vec![
+            (
+                "Line attributes",
+                PresetAttribute::Object(&mut o.line_attributes),
+            ),
+            (
+                "Fill attributes",
+                PresetAttribute::NullableObject(&mut o.fill_attributes),
+            ),
+        ],
+        Object::Button(o) => vec![(
+            "Transparent",
+            PresetAttribute::Flag(&mut o.options.transparent_background),
+        )],
+        _ => vec![],
+    };
+    attributes
+        .into_iter()
+        .map(|(name, attribute)| (name.to_string(), attribute))
+        .collect()
+}
+
+/// The current values of the attributes of an object a preset can set, by attribute name
+pub fn preset_values(object: &Object) -> BTreeMap<String, PresetValue> {
+    let mut object = object.clone();
+    let mut values: BTreeMap<String, PresetValue> = colour_preset_attributes(&mut object)
+        .into_iter()
+        .map(|(name, attribute)| (name, attribute.value()))
+        .collect();
+    values.extend(
+        other_preset_attributes(&mut object)
+            .into_iter()
+            .map(|(name, attribute)| (name, attribute.value())),
+    );
+    values
+}
+
+fn apply_values(attributes: Vec<(String, PresetAttribute)>, preset: &StylePreset) -> usize {
+    let mut changed = 0;
+    for (name, mut attribute) in attributes {
+        if preset
+            .values
+            .get(&name)
+            .is_some_and(|value| attribute.set(*value))
+        {
+            changed += 1;
+        }
+    }
+    changed
+}
+
+/// Set the attributes of the preset that the object has. Returns the number of changed attributes.
+pub fn apply_style_preset_to(object: &mut Object, preset: &StylePreset) -> usize {
+    apply_values(colour_preset_attributes(object), preset)
+        + apply_values(other_preset_attributes(object), preset)
+}
@@ -0,0 +1,108 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use ag_iso_stack::object_pool::ObjectId;
+
+use crate::review::ReviewComment;
+use crate::EditorProject;
+
+/// Tag that marks a line of a note or review comment as outstanding work
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum TaskTag {
+    Todo,
+    Fixme,
+}
+
+impl TaskTag {
+    pub const ALL: [TaskTag; 2] = [TaskTag::Todo, TaskTag::Fixme];
+
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskTag::Todo => "TODO",
+            TaskTag::Fixme => "FIXME",
+        }
+    }
+}
+
+/// Where a task was written down
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TaskSource {
+    Notes,
+    ReviewComment,
+}
+
+impl TaskSource {
+    pub fn label(&self) -> &'static str {
+        match self {
+            TaskSource::Notes => "Notes",
+            TaskSource::ReviewComment => "Review comment",
+        }
+    }
+}
+
+/// A TODO or FIXME found in the annotations of an object
+#[derive(Debug, Clone, PartialEq)]
+pub struct ProjectTask {
+    pub object: ObjectId,
+    pub tag: TaskTag,
+    pub source: TaskSource,
+    /// The text after the tag
+    pub text: String,
+}
+
+/// The tag of a line and the text after it, if the line contains a tag as a whole word
+fn parse_task(line: &str) -> Option<(TaskTag, String)> {
+    TaskTag::ALL.into_iter().find_map(|tag| {
+        line.match_indices(tag.label()).find_map(|(start, label)| {
+            let end = start + label.len();
+            let before = line[..start].chars().next_back();
+            let after = line[end..].chars().next();
+            let is_word = |c: Option<char>| c.is_some_and(|c| c.is_alphanumeric() || c == '_');
+            if is_word(before) || is_word(after) {
+                return None;
+            }
+            let text = line[end..].trim_start_matches([':', ' ', '-', '\t']);
+            Some((tag, text.trim_end().to_string()))
+        })
+    })
+}
+
+fn collect_tasks(object: ObjectId, source: TaskSource, text: &str, tasks: &mut Vec<ProjectTask>) {
+    for line in text.lines() {
+        if let Some((tag, text)) = parse_task(line) {
+            tasks.push(ProjectTask {
+                object,
+                tag,
+                source,
+                text,
+            });
+        }
+    }
+}
+
+fn collect_comment_tasks(object: ObjectId, comment: &ReviewComment, tasks: &mut Vec<ProjectTask>) {
+    collect_tasks(object, TaskSource::ReviewComment, &comment.text, tasks);
+    for reply in &comment.replies {
+        collect_comment_tasks(object, reply, tasks);
+    }
+}
+
+/// All TODO and FIXME lines in the notes and review comments of the objects, by object ID
+pub fn project_tasks(project: &EditorProject) -> Vec<ProjectTask> {
+    let object_info = project.object_info.borrow();
+    let mut objects: Vec<_> = object_info
+        .iter()
+        .filter(|(id, _)| project.get_pool().object_by_id(**id).is_some())
+        .collect();
+    objects.sort_by_key(|(id, _)| id.value());
+
+    let mut tasks = Vec::new();
+    for (id, info) in objects {
+        collect_tasks(*id, TaskSource::Notes, &info.notes, &mut tasks);
+        for comment in &info.review.comments {
+            collect_comment_tasks(*id, comment, &mut tasks);
+        }
+    }
+    tasks
+}
@@ -0,0 +1,337 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Capabilities of an installed terminal, imported from a capability file. The file has one
+//! `key = value` setting per line, or the data bytes of the technical data responses of the
+//! terminal in hex, as captured from the VT messages of e.g. AgIsoStack.
+//!
+//! ```text
+//! # Get Hardware response: 256 colours, 480 x 480 pixels
+//! C7 FF 02 00 E0 01 E0 01
+//! vt_version = 4
+//! memory = 262144
+//! fonts = 6x8, 8x8, 12x16, 16x16
+//! ```
+
+use std::collections::BTreeSet;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::FontSize;
+use ag_iso_stack::object_pool::ObjectType;
+use serde::{Deserialize, Serialize};
+
+use crate::terminal_profile::{font_size_key, NON_PROPORTIONAL_FONT_SIZES};
+use crate::validation::ValidationIssue;
+use crate::{ColourDepth, EditorProject, TerminalProfile};
+
+pub(crate) const GET_MEMORY: u8 = 0xC0;
+pub(crate) const GET_NUMBER_OF_SOFT_KEYS: u8 = 0xC2;
+pub(crate) const GET_TEXT_FONT_DATA: u8 = 0xC3;
+pub(crate) const GET_HARDWARE: u8 = 0xC7;
+
+/// What a terminal reported about itself, None for what is not known
+#[derive(Serialize, Deserialize, Clone, Debug, Default, PartialEq)]
+pub struct TerminalCapabilities {
+    #[serde(default)]
+    pub vt_version: Option<u8>,
+
+    /// Object pool memory in bytes
+    #[serde(default)]
+    pub memory: Option<usize>,
+
+    /// Width and height of the data mask area in pixels
+    #[serde(default)]
+    pub mask_size: Option<(u16, u16)>,
+
+    /// Width and height of a soft key designator in pixels
+    #[serde(default)]
+    pub soft_key_size: Option<(u16, u16)>,
+
+    /// Number of soft keys the terminal shows at once
+    #[serde(default)]
+    pub soft_key_count: Option<u8>,
+
+    #[serde(default)]
+    pub colour_depth: Option<ColourDepth>,
+
+    /// Keys of the non proportional font sizes the terminal has, see [`font_size_key`]
+    #[serde(default)]
+    pub font_sizes: Option<BTreeSet<String>>,
+}
+
+/// The VT version that introduced an object type
+fn introduced_in(object_type: ObjectType) -> u8 {
+    match object_type {
+        ObjectType::AuxiliaryFunctionType2
+        | ObjectType::AuxiliaryInputType2
+        | ObjectType::AuxiliaryControlDesignatorType2 => 3,
+        ObjectType::WindowMask
+        | ObjectType::KeyGroup
+        | ObjectType::GraphicsContext
+        | ObjectType::OutputList
+        | ObjectType::ExtendedInputAttributes
+        | ObjectType::ColourMap
+        | ObjectType::ObjectLabelReferenceList => 4,
+        ObjectType::ExternalObjectDefinition
+        | ObjectType::ExternalReferenceName
+        | ObjectType::ExternalObjectPointer
+        | ObjectType::Animation => 5,
+        ObjectType::ColourPalette
+        | ObjectType::GraphicData
+        | ObjectType::WorkingSetSpecialControls
+        | ObjectType::ScaledGraphic => 6,
+        _ => 2,
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| format!("Invalid value for {}: {}", key, value.trim()))
+}
+
+fn parse_size(key: &str, value: &str) -> Result<(u16, u16), String> {
+    match value.split_once(['x', 'X']) {
+        Some((width, height)) => Ok((parse_number(key, width)?, parse_number(key, height)?)),
+        None => {
+            let size = parse_number(key, value)?;
+            Ok((size, size))
+        }
+    }
+}
+
+/// VT versions before version 3 report 0xFF
+fn vt_version_from_byte(byte: u8) -> u8 {
+    if byte == 0xFF {
+        2
+    } else {
+        byte
+    }
+}
+
+impl TerminalCapabilities {
+    /// Parse a capability file, later lines override earlier ones
+    pub fn parse(text: &str) -> Result<TerminalCapabilities, String> {
+        let mut capabilities = TerminalCapabilities::default();
+        for (number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let result = match line.split_once('=') {
+                Some((key, value)) => capabilities.set(key.trim(), value),
+                None => capabilities.read_response(line),
+            };
+            result.map_err(|e| format!("Line {}: {}", number + 1, e))?;
+        }
+        if capabilities == TerminalCapabilities::default() {
+            return Err("The file does not contain any terminal capabilities".to_string());
+        }
+        Ok(capabilities)
+    }
+
+    fn set(&mut self, key: &str, value: &str) -> Result<(), String> {
+        match key {
+            "vt_version" => self.vt_version = Some(parse_number(key, value)?),
+            "memory" => self.memory = Some(parse_number(key, value)?),
+            "mask_size" => self.mask_size = Some(parse_size(key, value)?),
+            "soft_key_size" => self.soft_key_size = Some(parse_size(key, value)?),
+            "soft_keys" => self.soft_key_count = Some(parse_number(key, value)?),
+            "colours" => {
+                self.colour_depth = Some(match value.trim() {
+                    "256" => ColourDepth::Colours256,
+                    "16" => ColourDepth::Colours16,
+                    "2" => ColourDepth::Monochrome,
+                    other => return Err(format!("Unsupported number of colours: {}", other)),
+                })
+            }
+            "fonts" => {
+                let known: Vec<String> = NON_PROPORTIONAL_FONT_SIZES
+                    .iter()
+                    .map(|size| font_size_key(&FontSize::NonProportional(*size)))
+                    .collect();
+                let mut fonts = BTreeSet::new();
+                for font in value.split(',').map(str::trim).filter(|f| !f.is_empty()) {
+                    if !known.iter().any(|key| key == font) {
+                        return Err(format!("Unknown font size: {}", font));
+                    }
+                    fonts.insert(font.to_string());
+                }
+                self.font_sizes = Some(fonts);
+            }
+            _ => return Err(format!("Unknown setting: {}", key)),
+        }
+        Ok(())
+    }
+
+    /// Read the data bytes of a technical data response of the terminal
+    fn read_response(&mut self, line: &str) -> Result<(), String> {
+        let bytes = line
+            .split_whitespace()
+            .map(|byte| u8::from_str_radix(byte.trim_start_matches("0x"), 16))
+            .collect::<Result<Vec<u8>, _>>()
+            .map_err(|_| format!("Not a setting or a message in hex: {}", line))?;
+        let bytes: [u8; 8] = bytes
+            .try_into()
+            .map_err(|bytes: Vec<u8>| format!("Expected 8 data bytes, found {}", bytes.len()))?;
+        self.read_message(&bytes)
+    }
+
+    /// Take the capabilities from a technical data response message of the terminal
+    pub(crate) fn read_message(&mut self, bytes: &[u8; 8]) -> Result<(), String> {
+        match bytes[0] {
+            GET_MEMORY => self.vt_version = Some(vt_version_from_byte(bytes[1])),
+            GET_NUMBER_OF_SOFT_KEYS => {
+                self.soft_key_size = Some((bytes[4] as u16, bytes[5] as u16));
+                self.soft_key_count = Some(bytes[7]);
+            }
+            GET_TEXT_FONT_DATA => {
+                let mask = u16::from_le_bytes([bytes[5], bytes[6]]);
+                let fonts = NON_PROPORTIONAL_FONT_SIZES
+                    .iter()
+                    .enumerate()
+                    .filter(|(bit, _)| mask & (1 << bit) != 0)
+                    .map(|(_, size)| font_size_key(&FontSize::NonProportional(*size)))
+                    .collect();
+                self.font_sizes = Some(fonts);
+            }
+            GET_HARDWARE => {
+                self.colour_depth = match bytes[2] {
+                    0 => Some(ColourDepth::Monochrome),
+                    1 => Some(ColourDepth::Colours16),
+                    2 => Some(ColourDepth::Colours256),
+                    _ => None,
+                };
+                self.mask_size = Some((
+                    u16::from_le_bytes([bytes[4], bytes[5]]),
+                    u16::from_le_bytes([bytes[6], bytes[7]]),
+                ));
+            }
+            function => return Err(format!("Unsupported message: {:02X}", function)),
+        }
+        Ok(())
+    }
+
+    /// Check the project against the capabilities
+    pub fn validate(
+        &self,
+        project: &EditorProject,
+        profile: &TerminalProfile,
+        issues: &mut Vec<ValidationIssue>,
+    ) {
+        let pool = project.get_pool();
+        if let Some(version) = self.vt_version {
+            if project.pool_version.vt_version > version {
+                issues.push(ValidationIssue::error(
+                    None,
+                    format!(
+                        "The pool is made for VT version {}, {} supports version {}",
+                        project.pool_version.vt_version, profile.name, version
+                    ),
+                ));
+            }
+            for object in pool.objects() {
+                let required = introduced_in(object.object_type());
+                if required > version {
+                    issues.push(ValidationIssue::error(
+                        Some(object.id()),
+                        format!(
+                            "{:?} {} needs VT version {}, {} supports version {}",
+                            object.object_type(),
+                            object.id().value(),
+                            required,
+                            profile.name,
+                            version
+                        ),
+                    ));
+                }
+            }
+        }
+        if let Some(fonts) = &self.font_sizes {
+            for object in pool.objects_by_type(ObjectType::FontAttributes) {
+                let Object::FontAttributes(font) = object else {
+                    continue;
+                };
+                let key = font_size_key(&font.font_size);
+                if matches!(font.font_size, FontSize::NonProportional(_)) && !fonts.contains(&key) {
+                    issues.push(ValidationIssue::warning(
+                        Some(font.id),
+                        format!(
+                            "{} does not have font size {}, font attributes {} are shown with a \
+                             smaller font",
+                            profile.name,
+                            key,
+                            font.id.value()
+                        ),
+                    ));
+                }
+            }
+        }
+        if let Some((width, height)) = self.mask_size {
+            if project.mask_size > width.min(height) {
+                issues.push(ValidationIssue::warning(
+                    None,
+                    format!(
+                        "The masks are designed for {} px, {} shows {} x {} px",
+                        project.mask_size, profile.name, width, height
+                    ),
+                ));
+            }
+        }
+        if let Some((width, height)) = self.soft_key_size {
+            let (design_width, design_height) = project.soft_key_size;
+            if design_width > width || design_height > height {
+                issues.push(ValidationIssue::warning(
+                    None,
+                    format!(
+                        "The soft keys are designed for {} x {} px, {} shows {} x {} px",
+                        design_width, design_height, profile.name, width, height
+                    ),
+                ));
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn settings_and_responses_are_parsed() {
+        let fonts = crate::terminal_profile::font_size_keys();
+        let capabilities = TerminalCapabilities::parse(&format!(
+            "# Get Hardware response: 256 colours, 480 x 480 pixels\n\
+             C7 FF 02 00 E0 01 E0 01\n\
+             C0 FF 00 00 00 00 00 00\n\
+             vt_version = 4 # overrides the Get Memory response\n\
+             memory = 262144\n\
+             soft_key_size = 60x32\n\
+             fonts = {}, {}\n",
+            fonts[0], fonts[1]
+        ))
+        .unwrap();
+        assert_eq!(capabilities.colour_depth, Some(ColourDepth::Colours256));
+        assert_eq!(capabilities.mask_size, Some((480, 480)));
+        assert_eq!(capabilities.vt_version, Some(4));
+        assert_eq!(capabilities.memory, Some(262144));
+        assert_eq!(capabilities.soft_key_size, Some((60, 32)));
+        assert_eq!(
+            capabilities.font_sizes,
+            Some(BTreeSet::from([fonts[0].clone(), fonts[1].clone()]))
+        );
+    }
+
+    #[test]
+    fn invalid_lines_are_reported() {
+        assert_eq!(
+            TerminalCapabilities::parse("memory = 1\ncolours = 3"),
+            Err("Line 2: Unsupported number of colours: 3".to_string())
+        );
+        assert!(TerminalCapabilities::parse("C7 FF 02").is_err());
+        assert!(TerminalCapabilities::parse("brightness = 5").is_err());
+        assert!(TerminalCapabilities::parse("# only a comment").is_err());
+    }
+}
@@ -0,0 +1,252 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Capture of the capabilities of a terminal on a live ISOBUS. The designer claims an address,
+//! waits for the VT status message and asks the terminal for its technical data. The object pool
+//! memory is not reported directly, it is found with Get Memory requests of decreasing size.
+
+use std::time::{Duration, Instant};
+
+use crate::terminal_capabilities::{
+    GET_HARDWARE, GET_MEMORY, GET_NUMBER_OF_SOFT_KEYS, GET_TEXT_FONT_DATA,
+};
+use crate::TerminalCapabilities;
+
+/// Address the designer claims if none is given, from the self-configurable range
+pub const DEFAULT_CAPTURE_ADDRESS: u8 = 0x80;
+
+/// NAME the designer claims its address with: self-configurable, agricultural industry group
+const DESIGNER_NAME: u64 = 0xA000_8200_0000_0001;
+
+const PGN_ADDRESS_CLAIM: u32 = 0xEE00;
+const PGN_VT_TO_ECU: u32 = 0xE600;
+const PGN_ECU_TO_VT: u32 = 0xE700;
+const GLOBAL_ADDRESS: u8 = 0xFF;
+const VT_STATUS: u8 = 0xFE;
+
+/// Time other ECUs have to contest the address claim
+const ADDRESS_CLAIM_WAIT: Duration = Duration::from_millis(250);
+
+/// Terminals send their status every second
+const VT_STATUS_TIMEOUT: Duration = Duration::from_secs(3);
+const RESPONSE_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// Largest memory that is probed and the resolution of the probe
+const MAX_PROBED_MEMORY: u32 = 4 * 1024 * 1024;
+const MEMORY_RESOLUTION: u32 = 1024;
+
+/// A connection to the bus, frames are 29 bit identifiers with their data
+pub trait CanInterface {
+    fn send(&mut self, id: u32, data: [u8; 8]) -> Result<(), String>;
+
+    /// The next frame on the bus, None if no frame arrived within the timeout
+    fn receive(&mut self, timeout: Duration) -> Result<Option<(u32, Vec<u8>)>, String>;
+}
+
+fn can_id(pgn: u32, destination: u8, source: u8) -> u32 {
+    const PRIORITY: u32 = 6;
+    (PRIORITY << 26) | (pgn << 8) | ((destination as u32) << 8) | source as u32
+}
+
+/// PGN, destination and source address of a frame, the destination is only set for PDU1 PGNs
+fn split_id(id: u32) -> (u32, u8, u8) {
+    let pdu_format = (id >> 16) & 0xFF;
+    let pgn = (id >> 8) & 0x3FF00;
+    if pdu_format < 0xF0 {
+        (pgn, (id >> 8) as u8, id as u8)
+    } else {
+        (pgn | ((id >> 8) & 0xFF), GLOBAL_ADDRESS, id as u8)
+    }
+}
+
+struct Capture<'a> {
+    bus: &'a mut dyn CanInterface,
+    address: u8,
+}
+
+impl Capture<'_> {
+    /// Receive frames until `accept` returns a value or the timeout expires
+    fn wait_for<T>(
+        &mut self,
+        timeout: Duration,
+        mut accept: impl FnMut(u32, u8, u8, &[u8]) -> Option<T>,
+    ) -> Result<Option<T>, String> {
+        let deadline = Instant::now() + timeout;
+        while let Some(remaining) = deadline.checked_duration_since(Instant::now()) {
+            if let Some((id, data)) = self.bus.receive(remaining)? {
+                let (pgn, destination, source) = split_id(id);
+                if let Some(value) = accept(pgn, destination, source, &data) {
+                    return Ok(Some(value));
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    fn claim_address(&mut self) -> Result<(), String> {
+        let name = DESIGNER_NAME.to_le_bytes();
+        self.bus.send(
+            can_id(PGN_ADDRESS_CLAIM, GLOBAL_ADDRESS, self.address),
+            name,
+        )?;
+        let address = self.address;
+        let contested = self.wait_for(ADDRESS_CLAIM_WAIT, |pgn, _, source, data| {
+            let other = u64::from_le_bytes(data.try_into().ok()?);
+            (pgn == PGN_ADDRESS_CLAIM && source == address && other < DESIGNER_NAME).then_some(())
+        })?;
+        match contested {
+            Some(()) => Err(format!(
+                "Address {:#04X} is used by another ECU, choose another address",
+                self.address
+            )),
+            None => Ok(()),
+        }
+    }
+
+    /// Address of the terminal, from its status message
+    fn find_terminal(&mut self) -> Result<u8, String> {
+        self.wait_for(VT_STATUS_TIMEOUT, |pgn, _, source, data| {
+            (pgn == PGN_VT_TO_ECU && data.first() == Some(&VT_STATUS)).then_some(source)
+        })?
+        .ok_or_else(|| "No VT status message received, is a terminal on the bus?".to_string())
+    }
+
+    /// Send a request to the terminal and wait for the response with the same function
+    fn request(&mut self, terminal: u8, data: [u8; 8]) -> Result<[u8; 8], String> {
+        self.bus
+            .send(can_id(PGN_ECU_TO_VT, terminal, self.address), data)?;
+        let address = self.address;
+        self.wait_for(RESPONSE_TIMEOUT, |pgn, destination, source, response| {
+            let response: [u8; 8] = response.try_into().ok()?;
+            (pgn == PGN_VT_TO_ECU
+                && destination == address
+                && source == terminal
+                && response[0] == data[0])
+                .then_some(response)
+        })?
+        .ok_or_else(|| format!("The terminal did not answer request {:02X}", data[0]))
+    }
+
+    /// Whether the terminal has `size` bytes of memory for a pool, with the Get Memory response
+    fn has_memory(&mut self, terminal: u8, size: u32) -> Result<([u8; 8], bool), String> {
+        let [a, b, c, d] = size.to_le_bytes();
+        let response = self.request(terminal, [GET_MEMORY, 0xFF, a, b, c, d, 0xFF, 0xFF])?;
+        Ok((response, response[2] == 0))
+    }
+
+    /// The largest pool the terminal has memory for, by bisection
+    fn probe_memory(&mut self, terminal: u8) -> Result<(Option<usize>, [u8; 8]), String> {
+        let (response, enough) = self.has_memory(terminal, MAX_PROBED_MEMORY)?;
+        if enough {
+            return Ok((Some(MAX_PROBED_MEMORY as usize), response));
+        }
+        let (mut low, mut high) = (0, MAX_PROBED_MEMORY);
+        while high - low > MEMORY_RESOLUTION {
+            let middle = low + (high - low) / 2;
+            if self.has_memory(terminal, middle)?.1 {
+                low = middle;
+            } else {
+                high = middle;
+            }
+        }
+        Ok(((low > 0).then_some(low as usize), response))
+    }
+}
+
+/// Claim `address` on the bus, find the terminal and ask it for its capabilities. Blocks for a few
+/// seconds, run it on a separate thread.
+pub fn capture_capabilities(
+    bus: &mut dyn CanInterface,
+    address: u8,
+) -> Result<TerminalCapabilities, String> {
+    let mut capture = Capture { bus, address };
+    capture.claim_address()?;
+    let terminal = capture.find_terminal()?;
+
+    let mut capabilities = TerminalCapabilities::default();
+    let (memory, response) = capture.probe_memory(terminal)?;
+    capabilities.read_message(&response)?;
+    capabilities.memory = memory;
+    for function in [GET_NUMBER_OF_SOFT_KEYS, GET_TEXT_FONT_DATA, GET_HARDWARE] {
+        let response = capture.request(
+            terminal,
+            [function, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF],
+        )?;
+        capabilities.read_message(&response)?;
+    }
+    Ok(capabilities)
+}
+
+/// A SocketCAN interface, e.g. `can0`
+#[cfg(all(feature = "can-capture", target_os = "linux"))]
+pub struct SocketCanInterface {
+    socket: socketcan::CanSocket,
+}
+
+#[cfg(all(feature = "can-capture", target_os = "linux"))]
+impl SocketCanInterface {
+    pub fn open(interface: &str) -> Result<Self, String> {
+        use socketcan::Socket;
+        socketcan::CanSocket::open(interface)
+            .map(|socket| SocketCanInterface { socket })
+            .map_err(|e| format!("Failed to open {}: {}", interface, e))
+    }
+}
+
+#[cfg(all(feature = "can-capture", target_os = "linux"))]
+impl CanInterface for SocketCanInterface {
+    fn send(&mut self, id: u32, data: [u8; 8]) -> Result<(), String> {
+        use socketcan::{EmbeddedFrame, Socket};
+        let frame = socketcan::ExtendedId::new(id)
+            .and_then(|id| socketcan::CanFrame::new(id, &data))
+            .ok_or_else(|| format!("Invalid CAN identifier {:08X}", id))?;
+        self.socket
+            .write_frame(&frame)
+            .map_err(|e| format!("Failed to send a frame: {}", e))
+    }
+
+    fn receive(&mut self, timeout: Duration) -> Result<Option<(u32, Vec<u8>)>, String> {
+        use socketcan::{EmbeddedFrame, Socket};
+        self.socket
+            .set_read_timeout(timeout.max(Duration::from_millis(1)))
+            .map_err(|e| e.to_string())?;
+        match self.socket.read_frame() {
+            Ok(frame) => match frame.id() {
+                socketcan::Id::Extended(id) => Ok(Some((id.as_raw(), frame.data().to_vec()))),
+                socketcan::Id::Standard(_) => Ok(None),
+            },
+            Err(e)
+                if matches!(
+                    e.kind(),
+                    std::io::ErrorKind::WouldBlock | std::io::ErrorKind::TimedOut
+                ) =>
+            {
+                Ok(None)
+            }
+            Err(e) => Err(format!("Failed to receive a frame: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pdu1_identifiers_keep_the_destination() {
+        let id = can_id(PGN_ECU_TO_VT, 0x26, 0x80);
+        assert_eq!(id, 0x18E7_2680);
+        assert_eq!(split_id(id), (PGN_ECU_TO_VT, 0x26, 0x80));
+        assert_eq!(
+            split_id(can_id(PGN_ADDRESS_CLAIM, GLOBAL_ADDRESS, 0x26)),
+            (PGN_ADDRESS_CLAIM, GLOBAL_ADDRESS, 0x26)
+        );
+    }
+
+    #[test]
+    fn pdu2_identifiers_are_broadcast() {
+        // Language command, PGN 0xFE0F from address 0x26
+        assert_eq!(split_id(0x18FE_0F26), (0xFE0F, GLOBAL_ADDRESS, 0x26));
+    }
+}
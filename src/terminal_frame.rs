@@ -0,0 +1,172 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::Point;
+use ag_iso_stack::object_pool::ObjectPool;
+use eframe::egui;
+use egui::{Color32, Rect, Vec2};
+
+use crate::object_rendering::Colorable;
+use crate::preview_state::designator_size;
+use crate::RenderableObject;
+
+/// Width of the bezel around the screen and the soft keys
+const BEZEL: f32 = 24.0;
+
+/// Gap between a soft key designator and its physical button
+const BUTTON_GAP: f32 = 8.0;
+
+/// Depth of the physical buttons next to the designators
+const BUTTON_DEPTH: f32 = 20.0;
+
+const BEZEL_COLOUR: Color32 = Color32::from_gray(40);
+const BUTTON_COLOUR: Color32 = Color32::from_gray(90);
+
+/// Decorative bezel around the preview, approximating common terminal layouts
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum TerminalFrame {
+    #[default]
+    None,
+    /// Six soft keys in a column right of the screen
+    SideColumn,
+    /// Ten soft keys in two columns right of the screen
+    DualColumn,
+    /// Six soft keys in a row below the screen
+    BottomRow,
+}
+
+impl TerminalFrame {
+    pub const ALL: [TerminalFrame; 4] = [
+        TerminalFrame::None,
+        TerminalFrame::SideColumn,
+        TerminalFrame::DualColumn,
+        TerminalFrame::BottomRow,
+    ];
+
+    /// Human readable name of the frame
+    pub fn label(&self) -> &'static str {
+        match self {
+            TerminalFrame::None => "No frame",
+            TerminalFrame::SideColumn => "6 keys, side column",
+            TerminalFrame::DualColumn => "10 keys, dual column",
+            TerminalFrame::BottomRow => "6 keys, bottom row",
+        }
+    }
+
+    /// Number of soft keys next to the screen of a typical terminal with this layout
+    pub fn key_count(&self) -> usize {
+        match self {
+            TerminalFrame::None => 0,
+            TerminalFrame::SideColumn | TerminalFrame::BottomRow => 6,
+            TerminalFrame::DualColumn => 10,
+        }
+    }
+
+    /// Size of the whole frame, the screen and the soft key designators relative to the frame.
+    /// Each designator comes with the rectangle of its physical button.
+    fn layout(
+        &self,
+        key_count: usize,
+        mask_size: f32,
+        key_size: Vec2,
+    ) -> (Vec2, Rect, Vec<(Rect, Rect)>) {
+        let screen = Rect::from_min_size(egui::pos2(BEZEL, BEZEL), Vec2::splat(mask_size));
+        let key_slot = |min: egui::Pos2| Rect::from_min_size(min, key_size);
+        let mut keys = Vec::new();
+        let size = match self {
+            TerminalFrame::None => return (screen.size(), screen, keys),
+            TerminalFrame::SideColumn | TerminalFrame::DualColumn => {
+                let columns = if *self == TerminalFrame::DualColumn {
+                    2
+                } else {
+                    1
+                };
+                let rows = key_count.div_ceil(columns);
+                let column_width = key_size.x + BUTTON_GAP + BUTTON_DEPTH + BUTTON_GAP;
+                for column in 0..columns {
+                    let x = screen.max.x + BUTTON_GAP + column as f32 * column_width;
+                    for row in 0..rows.min(key_count - column * rows) {
+                        let designator = key_slot(egui::pos2(x, BEZEL + row as f32 * key_size.y));
+                        let button = Rect::from_min_size(
+                            designator.right_top() + egui::vec2(BUTTON_GAP, 2.0),
+                            egui::vec2(BUTTON_DEPTH, key_size.y - 4.0),
+                        );
+                        keys.push((designator, button));
+                    }
+                }
+                let height = mask_size.max(rows as f32 * key_size.y);
+                egui::vec2(
+                    screen.max.x + BUTTON_GAP + columns as f32 * column_width + BEZEL,
+                    BEZEL + height + BEZEL,
+                )
+            }
+            TerminalFrame::BottomRow => {
+                let y = screen.max.y + BUTTON_GAP;
+                for index in 0..key_count {
+                    let designator = key_slot(egui::pos2(BEZEL + index as f32 * key_size.x, y));
+                    let button = Rect::from_min_size(
+                        designator.left_bottom() + egui::vec2(2.0, BUTTON_GAP),
+                        egui::vec2(key_size.x - 4.0, BUTTON_DEPTH),
+                    );
+                    keys.push((designator, button));
+                }
+                let width = mask_size.max(key_count as f32 * key_size.x);
+                egui::vec2(
+                    BEZEL + width + BEZEL,
+                    y + key_size.y + BUTTON_GAP + BUTTON_DEPTH + BEZEL,
+                )
+            }
+        };
+        (size, screen, keys)
+    }
+}
+
+/// Render the mask inside a terminal frame, with the first keys of its soft key mask next to it.
+/// Returns the response of the mask widget.
+pub fn render_terminal_frame(
+    ui: &mut egui::Ui,
+    pool: &ObjectPool,
+    mask: &Object,
+    frame: TerminalFrame,
+    key_count: usize,
+    mask_size: f32,
+    mask_widget: impl egui::Widget,
+) -> egui::Response {
+    let (key_width, key_height) = designator_size(ui);
+    let key_size = egui::vec2(key_width as f32, key_height as f32);
+    let (size, screen, key_slots) = frame.layout(key_count, mask_size, key_size);
+    let (rect, _) = ui.allocate_exact_size(size, egui::Sense::hover());
+    let offset = rect.min.to_vec2();
+
+    let painter = ui.painter_at(rect);
+    painter.rect_filled(rect, 12.0, BEZEL_COLOUR);
+
+    let soft_key_mask = match mask {
+        Object::DataMask(m) => m.soft_key_mask.0,
+        Object::AlarmMask(m) => m.soft_key_mask.0,
+        _ => None,
+    }
+    .and_then(|id| pool.object_by_id(id));
+    let (background, keys) = match soft_key_mask {
+        Some(Object::SoftKeyMask(skm)) => (
+            pool.color_by_index(skm.background_colour).convert(),
+            skm.objects.as_slice(),
+        ),
+        _ => (Color32::from_gray(200), [].as_slice()),
+    };
+
+    for (index, (designator, button)) in key_slots.iter().enumerate() {
+        let designator = designator.translate(offset);
+        painter.rect_filled(designator, 0.0, background);
+        painter.rect_filled(button.translate(offset), 4.0, BUTTON_COLOUR);
+        if let Some(key) = keys.get(index).and_then(|id| pool.object_by_id(*id)) {
+            let mut key_ui = ui.new_child(egui::UiBuilder::new().max_rect(designator));
+            key_ui.set_clip_rect(designator.intersect(ui.clip_rect()));
+            key.render(&mut key_ui, pool, Point::default());
+        }
+    }
+
+    ui.put(screen.translate(offset), mask_widget)
+}
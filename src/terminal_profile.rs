@@ -0,0 +1,294 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::hash_map::DefaultHasher;
+use std::collections::BTreeMap;
+use std::hash::{Hash, Hasher};
+
+use ag_iso_stack::object_pool::object_attributes::{FontSize, NonProportionalFontSize};
+use ag_iso_stack::object_pool::{Colour, ObjectPool};
+use serde::{Deserialize, Serialize};
+
+use crate::colour_roles::colour_attributes_mut;
+use crate::pixel_font::PIXEL_FONT_NAME;
+use crate::terminal_capabilities::TerminalCapabilities;
+
+/// Number of soft keys a profile starts with when the limit is enabled
+pub const DEFAULT_SOFT_KEY_COUNT: u8 = 6;
+
+/// Pool size budget a profile starts with when the limit is enabled
+pub const DEFAULT_POOL_SIZE_BUDGET: usize = 64 * 1024;
+
+/// Key of the font mapping that is used for all proportional font sizes
+pub const PROPORTIONAL_FONT_KEY: &str = "Proportional";
+
+/// All non proportional VT font sizes
+pub const NON_PROPORTIONAL_FONT_SIZES: [NonProportionalFontSize; 15] = [
+    NonProportionalFontSize::Px6x8,
+    NonProportionalFontSize::Px8x8,
+    NonProportionalFontSize::Px8x12,
+    NonProportionalFontSize::Px12x16,
+    NonProportionalFontSize::Px16x16,
+    NonProportionalFontSize::Px16x24,
+    NonProportionalFontSize::Px24x32,
+    NonProportionalFontSize::Px32x32,
+    NonProportionalFontSize::Px32x48,
+    NonProportionalFontSize::Px48x64,
+    NonProportionalFontSize::Px64x64,
+    NonProportionalFontSize::Px64x96,
+    NonProportionalFontSize::Px96x128,
+    NonProportionalFontSize::Px128x128,
+    NonProportionalFontSize::Px128x192,
+];
+
+/// Key of a VT font size in the font mapping, e.g. "6x8" or "Proportional"
+pub fn font_size_key(font_size: &FontSize) -> String {
+    match font_size {
+        FontSize::NonProportional(size) => format!("{}x{}", size.width(), size.height()),
+        FontSize::Proportional(_) => PROPORTIONAL_FONT_KEY.to_string(),
+    }
+}
+
+/// All keys of the font mapping, in order of increasing size
+pub fn font_size_keys() -> Vec<String> {
+    let mut keys: Vec<String> = NON_PROPORTIONAL_FONT_SIZES
+        .iter()
+        .map(|size| font_size_key(&FontSize::NonProportional(*size)))
+        .collect();
+    keys.push(PROPORTIONAL_FONT_KEY.to_string());
+    keys
+}
+
+/// A TTF or OTF font file supplied by the user
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct CustomFont {
+    pub name: String,
+    pub data: Vec<u8>,
+}
+
+impl CustomFont {
+    /// Hash of the font file, to notice when a font is replaced by another file of the same name
+    pub fn data_hash(&self) -> u64 {
+        let mut hasher = DefaultHasher::new();
+        self.data.hash(&mut hasher);
+        hasher.finish()
+    }
+}
+
+/// Check that a font file is a TTF or OTF font egui can load
+pub fn validate_font(data: &[u8]) -> Result<(), String> {
+    ab_glyph::FontRef::try_from_slice(data)
+        .map(|_| ())
+        .map_err(|_| "Not a valid TTF or OTF font".to_string())
+}
+
+/// Number of colours a terminal can show
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum ColourDepth {
+    #[default]
+    Colours256,
+    Colours16,
+    Monochrome,
+}
+
+impl ColourDepth {
+    pub const ALL: [ColourDepth; 3] = [
+        ColourDepth::Colours256,
+        ColourDepth::Colours16,
+        ColourDepth::Monochrome,
+    ];
+
+    /// Human readable name of the colour depth
+    pub fn label(&self) -> &'static str {
+        match self {
+            ColourDepth::Colours256 => "256 colours",
+            ColourDepth::Colours16 => "16 colours",
+            ColourDepth::Monochrome => "Monochrome",
+        }
+    }
+
+    /// Closest colour index the terminal can show for a colour index of the pool
+    pub fn closest_colour(&self, pool: &ObjectPool, index: u8) -> u8 {
+        let available: &[u8] = match self {
+            ColourDepth::Colours256 => return index,
+            ColourDepth::Colours16 => &[0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15],
+            ColourDepth::Monochrome => &[0, 1],
+        };
+        let distance = |a: &Colour, b: &Colour| {
+            let channel = |x: u8, y: u8| (x as i32 - y as i32).pow(2);
+            channel(a.r, b.r) + channel(a.g, b.g) + channel(a.b, b.b)
+        };
+        let colour = pool.color_by_index(index);
+        available
+            .iter()
+            .copied()
+            .min_by_key(|candidate| distance(&colour, &pool.color_by_index(*candidate)))
+            .unwrap_or(index)
+    }
+
+    /// Copy of the pool with every colour attribute replaced by the closest colour the terminal
+    /// can show. Picture graphics keep their pixel data.
+    pub fn reduce_colours(&self, pool: &ObjectPool) -> ObjectPool {
+        let mut reduced = pool.clone();
+        if *self == ColourDepth::Colours256 {
+            return reduced;
+        }
+        let ids: Vec<_> = pool.objects().iter().map(|o| o.id()).collect();
+        for id in ids {
+            let Some(object) = reduced.object_mut_by_id(id) else {
+                continue;
+            };
+            for (_, value) in colour_attributes_mut(object) {
+                *value = self.closest_colour(pool, *value);
+            }
+        }
+        reduced
+    }
+}
+
+/// Properties of a terminal the preview simulates
+#[derive(Serialize, Deserialize, Clone, Debug, PartialEq)]
+pub struct TerminalProfile {
+    pub name: String,
+
+    /// Fonts that can be mapped to VT font sizes
+    #[serde(default)]
+    pub fonts: Vec<CustomFont>,
+
+    /// Name of the custom font used for a font size key, sizes without an entry use the built-in fonts
+    #[serde(default)]
+    pub font_mapping: BTreeMap<String, String>,
+
+    /// Object pool memory of the terminal in bytes, None if unknown
+    #[serde(default)]
+    pub pool_size_budget: Option<usize>,
+
+    /// Number of soft keys the terminal shows at once, None if unknown
+    #[serde(default)]
+    pub soft_key_count: Option<u8>,
+
+    /// Width and height of the data mask area in pixels, None to use the virtual mask size
+    #[serde(default)]
+    pub mask_size: Option<u16>,
+
+    /// Width and height of a soft key designator, None to use the project setting
+    #[serde(default)]
+    pub soft_key_size: Option<(u16, u16)>,
+
+    #[serde(default)]
+    pub colour_depth: ColourDepth,
+
+    /// Capabilities of the installed terminal the profile was imported from, if any
+    #[serde(default)]
+    pub capabilities: Option<TerminalCapabilities>,
+}
+
+impl Default for TerminalProfile {
+    fn default() -> Self {
+        TerminalProfile {
+            name: "Default terminal".to_string(),
+            fonts: Vec::new(),
+            font_mapping: BTreeMap::new(),
+            pool_size_budget: None,
+            soft_key_count: None,
+            mask_size: None,
+            soft_key_size: None,
+            colour_depth: ColourDepth::default(),
+            capabilities: None,
+        }
+    }
+}
+
+impl TerminalProfile {
+    /// The profile without its custom fonts, e.g. for the settings file where the font data
+    /// does not belong
+    pub fn without_fonts(&self) -> TerminalProfile {
+        TerminalProfile {
+            fonts: Vec::new(),
+            font_mapping: BTreeMap::new(),
+            ..self.clone()
+        }
+    }
+
+    /// Add a font, replacing a font with the same name. Files that are not a valid font are
+    /// rejected, egui can not load them.
+    pub fn add_font(&mut self, name: String, data: Vec<u8>) -> Result<(), String> {
+        if name == PIXEL_FONT_NAME {
+            return Err(format!(
+                "The name '{name}' is used by the built-in pixel font"
+            ));
+        }
+        validate_font(&data)?;
+        self.fonts.retain(|f| f.name != name);
+        self.fonts.push(CustomFont { name, data });
+        Ok(())
+    }
+
+    /// Whether a font name of the mapping is available, a custom font or the built-in pixel font
+    pub fn has_font(&self, name: &str) -> bool {
+        name == PIXEL_FONT_NAME || self.fonts.iter().any(|f| f.name == name)
+    }
+
+    /// Remove a font and every mapping that uses it
+    pub fn remove_font(&mut self, name: &str) {
+        self.fonts.retain(|f| f.name != name);
+        self.font_mapping.retain(|_, font| font != name);
+    }
+
+    /// Name of the custom font used for a font size, if any
+    pub fn mapped_font(&self, font_size: &FontSize) -> Option<&str> {
+        self.font_mapping
+            .get(&font_size_key(font_size))
+            .filter(|name| self.has_font(name))
+            .map(|name| name.as_str())
+    }
+
+    /// Take the limits of the profile from the capabilities of an installed terminal
+    pub fn apply_capabilities(&mut self, capabilities: TerminalCapabilities) {
+        if let Some(memory) = capabilities.memory {
+            self.pool_size_budget = Some(memory);
+        }
+        if let Some((width, height)) = capabilities.mask_size {
+            self.mask_size = Some(width.min(height));
+        }
+        if let Some(size) = capabilities.soft_key_size {
+            self.soft_key_size = Some(size);
+        }
+        if let Some(count) = capabilities.soft_key_count {
+            self.soft_key_count = Some(count);
+        }
+        if let Some(depth) = capabilities.colour_depth {
+            self.colour_depth = depth;
+        }
+        self.capabilities = Some(capabilities);
+    }
+
+    /// Name of the egui font family a custom font is registered as
+    pub fn font_family_name(font_name: &str) -> String {
+        format!("vt_font_{}", font_name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::pixel_font::pixel_font_data;
+
+    #[test]
+    fn add_font_rejects_invalid_files() {
+        let mut profile = TerminalProfile::default();
+        assert!(profile.add_font("broken".to_string(), vec![0; 64]).is_err());
+        assert!(profile
+            .add_font(PIXEL_FONT_NAME.to_string(), pixel_font_data())
+            .is_err());
+        assert!(profile.fonts.is_empty());
+
+        profile
+            .add_font("pixel".to_string(), pixel_font_data())
+            .unwrap();
+        assert!(profile.has_font("pixel"));
+        assert!(profile.has_font(PIXEL_FONT_NAME));
+        assert!(!profile.has_font("other"));
+    }
+}
@@ -0,0 +1,185 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Images for textures are generated on a background thread and uploaded when they are ready, so
+//! pools with many or large images do not stall the user interface. On the web, where there are
+//! no threads, one image is generated per frame instead.
+
+use std::collections::HashMap;
+#[cfg(target_arch = "wasm32")]
+use std::collections::VecDeque;
+#[cfg(not(target_arch = "wasm32"))]
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::sync::{Arc, Mutex};
+
+use eframe::egui;
+
+const TEXTURE_WORKER_ID: &str = "texture_worker";
+
+type Job = Box<dyn FnOnce() -> egui::ColorImage + Send>;
+
+/// A generated image and the version it was generated for
+struct Finished {
+    key: String,
+    version: u64,
+    image: egui::ColorImage,
+}
+
+struct TextureWorker {
+    #[cfg(not(target_arch = "wasm32"))]
+    jobs: Sender<(String, u64, Job)>,
+    #[cfg(not(target_arch = "wasm32"))]
+    finished: Receiver<Finished>,
+    #[cfg(target_arch = "wasm32")]
+    queue: VecDeque<(String, u64, Job)>,
+
+    /// Uploaded textures by key, with the version of their image
+    textures: HashMap<String, (u64, egui::TextureHandle)>,
+
+    /// The versions that are being generated, by key
+    pending: HashMap<String, u64>,
+
+    /// Pass in which the finished images were last collected
+    last_poll: Option<u64>,
+}
+
+impl TextureWorker {
+    #[cfg(not(target_arch = "wasm32"))]
+    fn new(ctx: &egui::Context) -> Self {
+        let (jobs, job_receiver) = channel::<(String, u64, Job)>();
+        let (finished_sender, finished) = channel();
+        let ctx = ctx.clone();
+        std::thread::spawn(move || {
+            for (key, version, job) in job_receiver {
+                let image = job();
+                if finished_sender
+                    .send(Finished {
+                        key,
+                        version,
+                        image,
+                    })
+                    .is_err()
+                {
+                    break;
+                }
+                ctx.request_repaint();
+            }
+        });
+        TextureWorker {
+            jobs,
+            finished,
+            textures: HashMap::new(),
+            pending: HashMap::new(),
+            last_poll: None,
+        }
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn new(_ctx: &egui::Context) -> Self {
+        TextureWorker {
+            queue: VecDeque::new(),
+            textures: HashMap::new(),
+            pending: HashMap::new(),
+            last_poll: None,
+        }
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn submit(&mut self, key: String, version: u64, job: Job) {
+        // The thread only stops when the worker is dropped
+        let _ = self.jobs.send((key, version, job));
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn submit(&mut self, key: String, version: u64, job: Job) {
+        self.queue.push_back((key, version, job));
+    }
+
+    #[cfg(not(target_arch = "wasm32"))]
+    fn take_finished(&mut self, _ctx: &egui::Context) -> Vec<Finished> {
+        self.finished.try_iter().collect()
+    }
+
+    #[cfg(target_arch = "wasm32")]
+    fn take_finished(&mut self, ctx: &egui::Context) -> Vec<Finished> {
+        let Some((key, version, job)) = self.queue.pop_front() else {
+            return Vec::new();
+        };
+        if !self.queue.is_empty() {
+            ctx.request_repaint();
+        }
+        vec![Finished {
+            key,
+            version,
+            image: job(),
+        }]
+    }
+
+    /// Upload the images that are finished, once per pass
+    fn poll(&mut self, ctx: &egui::Context) {
+        let pass = ctx.cumulative_pass_nr();
+        if self.last_poll == Some(pass) {
+            return;
+        }
+        self.last_poll = Some(pass);
+        for finished in self.take_finished(ctx) {
+            // Images of outdated versions are dropped, a newer one is on its way
+            if self.pending.get(&finished.key) != Some(&finished.version) {
+                continue;
+            }
+            self.pending.remove(&finished.key);
+            let texture = ctx.load_texture(&finished.key, finished.image, Default::default());
+            self.textures
+                .insert(finished.key, (finished.version, texture));
+        }
+    }
+}
+
+#[derive(Clone)]
+struct SharedTextureWorker(Arc<Mutex<TextureWorker>>);
+
+fn with_texture_worker<R>(ctx: &egui::Context, f: impl FnOnce(&mut TextureWorker) -> R) -> R {
+    let worker = ctx.data_mut(|data| {
+        data.get_temp_mut_or_insert_with(egui::Id::new(TEXTURE_WORKER_ID), || {
+            SharedTextureWorker(Arc::new(Mutex::new(TextureWorker::new(ctx))))
+        })
+        .clone()
+    });
+    let mut worker = worker.0.lock().unwrap_or_else(|e| e.into_inner());
+    f(&mut worker)
+}
+
+/// The texture of `key`. If there is none for `version` yet, `generate` is run on the worker and
+/// the texture of the previous version is returned until it is done, or None if there is none.
+pub fn lazy_texture(
+    ctx: &egui::Context,
+    key: &str,
+    version: u64,
+    generate: impl FnOnce() -> egui::ColorImage + Send + 'static,
+) -> Option<egui::TextureHandle> {
+    with_texture_worker(ctx, |worker| {
+        worker.poll(ctx);
+        let (current, texture) = match worker.textures.get(key) {
+            Some((current, texture)) => (Some(*current), Some(texture.clone())),
+            None => (None, None),
+        };
+        if current != Some(version) && worker.pending.get(key) != Some(&version) {
+            worker.pending.insert(key.to_string(), version);
+            worker.submit(key.to_string(), version, Box::new(generate));
+        }
+        texture
+    })
+}
+
+/// The texture of `key` if it is ready for `version`
+pub fn ready_texture(ctx: &egui::Context, key: &str, version: u64) -> Option<egui::TextureHandle> {
+    with_texture_worker(ctx, |worker| {
+        worker.poll(ctx);
+        worker
+            .textures
+            .get(key)
+            .filter(|(current, _)| *current == version)
+            .map(|(_, texture)| texture.clone())
+    })
+}
@@ -0,0 +1,128 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use eframe::egui;
+use egui::{Color32, Spacing, Stroke, Theme, ThemePreference, Visuals};
+use serde::{Deserialize, Serialize};
+
+/// Smallest and largest scale of the designer user interface
+pub const UI_SCALE_RANGE: std::ops::RangeInclusive<f32> = 0.5..=3.0;
+
+/// Smallest size of interactive widgets in touch mode, about the size of a finger tip
+const TOUCH_TARGET_SIZE: f32 = 40.0;
+
+/// Colour theme of the designer user interface
+#[derive(Serialize, Deserialize, Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum UiTheme {
+    #[default]
+    System,
+    Light,
+    Dark,
+    HighContrast,
+}
+
+impl UiTheme {
+    pub const ALL: [UiTheme; 4] = [
+        UiTheme::System,
+        UiTheme::Light,
+        UiTheme::Dark,
+        UiTheme::HighContrast,
+    ];
+
+    /// Human readable name of the theme
+    pub fn label(&self) -> &'static str {
+        match self {
+            UiTheme::System => "Follow system",
+            UiTheme::Light => "Light",
+            UiTheme::Dark => "Dark",
+            UiTheme::HighContrast => "High contrast",
+        }
+    }
+
+    /// Apply the theme to all windows of the context
+    pub fn apply(&self, ctx: &egui::Context) {
+        ctx.set_visuals_of(Theme::Light, Visuals::light());
+        ctx.set_visuals_of(Theme::Dark, Visuals::dark());
+        match self {
+            UiTheme::System => ctx.set_theme(ThemePreference::System),
+            UiTheme::Light => ctx.set_theme(ThemePreference::Light),
+            UiTheme::Dark => ctx.set_theme(ThemePreference::Dark),
+            UiTheme::HighContrast => {
+                ctx.set_visuals_of(Theme::Dark, high_contrast_visuals());
+                ctx.set_theme(ThemePreference::Dark);
+            }
+        }
+    }
+}
+
+/// White on black with thick outlines and a yellow selection, readable on projectors
+fn high_contrast_visuals() -> Visuals {
+    let mut visuals = Visuals::dark();
+    let outline = Stroke::new(1.5, Color32::WHITE);
+
+    visuals.override_text_color = Some(Color32::WHITE);
+    visuals.panel_fill = Color32::BLACK;
+    visuals.window_fill = Color32::BLACK;
+    visuals.extreme_bg_color = Color32::BLACK;
+    visuals.faint_bg_color = Color32::from_gray(24);
+    visuals.window_stroke = outline;
+    visuals.hyperlink_color = Color32::from_rgb(0, 255, 255);
+    visuals.selection.bg_fill = Color32::from_rgb(255, 215, 0);
+    visuals.selection.stroke = Stroke::new(2.0, Color32::BLACK);
+
+    let widgets = &mut visuals.widgets;
+    widgets.noninteractive.bg_stroke = outline;
+    widgets.noninteractive.fg_stroke = outline;
+    for state in [
+        &mut widgets.inactive,
+        &mut widgets.hovered,
+        &mut widgets.active,
+    ] {
+        state.bg_fill = Color32::BLACK;
+        state.weak_bg_fill = Color32::BLACK;
+        state.bg_stroke = outline;
+        state.fg_stroke = Stroke::new(2.0, Color32::WHITE);
+    }
+    widgets.hovered.bg_stroke = Stroke::new(2.0, Color32::from_rgb(255, 215, 0));
+    widgets.active.bg_stroke = Stroke::new(2.5, Color32::from_rgb(255, 215, 0));
+    widgets.open.bg_stroke = outline;
+    visuals
+}
+
+/// Check if the user interface is in touch mode
+pub fn touch_mode(ctx: &egui::Context) -> bool {
+    ctx.data(|data| data.get_temp(egui::Id::new("touch_mode")))
+        .unwrap_or(false)
+}
+
+/// Switch touch mode, which enlarges all interactive widgets so they are easier to hit
+pub fn set_touch_mode(ctx: &egui::Context, enabled: bool) {
+    ctx.data_mut(|data| data.insert_temp(egui::Id::new("touch_mode"), enabled));
+    ctx.all_styles_mut(|style| {
+        style.spacing = if enabled {
+            touch_spacing()
+        } else {
+            Spacing::default()
+        };
+    });
+}
+
+fn touch_spacing() -> Spacing {
+    let defaults = Spacing::default();
+    Spacing {
+        interact_size: egui::vec2(TOUCH_TARGET_SIZE * 1.5, TOUCH_TARGET_SIZE),
+        button_padding: egui::vec2(12.0, 8.0),
+        item_spacing: egui::vec2(12.0, 8.0),
+        icon_width: 24.0,
+        icon_spacing: 8.0,
+        slider_width: 200.0,
+        combo_height: 400.0,
+        scroll: egui::style::ScrollStyle {
+            bar_width: 16.0,
+            floating: false,
+            ..defaults.scroll
+        },
+        ..defaults
+    }
+}
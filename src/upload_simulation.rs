@@ -0,0 +1,241 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Simulation of the pool upload to the terminal of the active profile: the Get Memory request,
+//! the transfer and the End of Object Pool response. When the pool does not fit, the objects that
+//! push it over the limit are listed with reductions that would make it fit.
+
+use std::collections::HashMap;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::object_attributes::{DataCodeType, PictureGraphicFormat};
+use ag_iso_stack::object_pool::{ObjectId, ObjectType};
+
+use crate::{minify_pool, EditorProject, MinifyOptions};
+
+const GET_MEMORY: u8 = 0xC0;
+const OBJECT_POOL_TRANSFER: u8 = 0x11;
+const END_OF_OBJECT_POOL: u8 = 0x12;
+
+/// Error bit of the End of Object Pool response for a terminal that ran out of memory
+const OUT_OF_MEMORY: u8 = 0x02;
+
+/// A message of the upload with its data bytes and how the terminal reacts to it
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadStep {
+    pub message: &'static str,
+    pub data: Vec<u8>,
+    pub outcome: String,
+}
+
+/// An object that makes the pool too large, with what can be done about it
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadOffender {
+    pub id: ObjectId,
+    pub object_type: ObjectType,
+
+    /// Bytes the object takes in the pool
+    pub size: usize,
+
+    /// Suggested reduction and the bytes it saves, if any is known
+    pub suggestion: Option<(String, usize)>,
+}
+
+/// Result of the simulated upload
+#[derive(Debug, Clone, PartialEq)]
+pub struct UploadSimulation {
+    pub profile: String,
+    pub pool_size: usize,
+    pub memory: usize,
+    pub steps: Vec<UploadStep>,
+
+    /// The object the terminal runs out of memory on during the transfer, with its offset
+    pub failing_object: Option<(ObjectId, usize)>,
+
+    /// The largest objects, until removing them would make the pool fit
+    pub offenders: Vec<UploadOffender>,
+
+    /// Reductions of the whole pool and the bytes they save
+    pub reductions: Vec<(String, usize)>,
+}
+
+impl UploadSimulation {
+    pub fn fits(&self) -> bool {
+        self.pool_size <= self.memory
+    }
+
+    /// Bytes the pool is larger than the memory of the terminal
+    pub fn overflow(&self) -> usize {
+        self.pool_size.saturating_sub(self.memory)
+    }
+}
+
+/// What could make a picture graphic smaller, with the estimated bytes saved
+fn picture_suggestion(object: &Object, size: usize) -> Option<(String, usize)> {
+    let Object::PictureGraphic(picture) = object else {
+        return None;
+    };
+    if picture.actual_width > picture.width && picture.actual_width > 0 {
+        let ratio = picture.width as f32 / picture.actual_width as f32;
+        let saved = (picture.data.len() as f32 * (1.0 - ratio * ratio)) as usize;
+        return Some((
+            format!(
+                "Stored {} px wide but shown {} px wide, scale the image down",
+                picture.actual_width, picture.width
+            ),
+            saved.min(size),
+        ));
+    }
+    if picture.format == PictureGraphicFormat::EightBit
+        && picture.options.data_code_type == DataCodeType::Raw
+    {
+        return Some(("Convert to 16 colours".to_string(), picture.data.len() / 2));
+    }
+    None
+}
+
+/// Simulate the upload to the terminal of the active profile, None if the profile has no memory
+/// limit
+pub fn simulate_upload(project: &EditorProject) -> Option<UploadSimulation> {
+    let profile = project.terminal_profile()?;
+    let memory = profile.pool_size_budget?;
+    let pool = project.get_pool();
+    let pool_size = pool.as_iop().len();
+    let fits = pool_size <= memory;
+
+    let mut steps = Vec::new();
+    let mut request = vec![GET_MEMORY, 0xFF];
+    request.extend_from_slice(&(pool_size as u32).to_le_bytes());
+    request.extend_from_slice(&[0xFF, 0xFF]);
+    steps.push(UploadStep {
+        message: "Get Memory",
+        data: request,
+        outcome: if fits {
+            "The terminal answers that there is enough memory".to_string()
+        } else {
+            format!(
+                "The terminal answers that there is not enough memory, {} bytes are missing",
+                pool_size - memory
+            )
+        },
+    });
+    steps.push(UploadStep {
+        message: "Object Pool Transfer",
+        data: vec![OBJECT_POOL_TRANSFER],
+        outcome: format!("{} bytes are transferred", pool_size),
+    });
+
+    // The transfer fails on the object that does not fit in the remaining memory anymore
+    let mut failing_object = None;
+    let mut offset = 0;
+    for object in pool.objects() {
+        let size = object.write().len();
+        if failing_object.is_none() && offset + size > memory {
+            failing_object = Some((object.id(), offset));
+        }
+        offset += size;
+    }
+    let error = if failing_object.is_some() {
+        OUT_OF_MEMORY
+    } else {
+        0
+    };
+    steps.push(UploadStep {
+        message: "End of Object Pool response",
+        data: vec![END_OF_OBJECT_POOL, error, 0xFF, 0xFF, 0xFF, 0xFF, 0, 0xFF],
+        outcome: match failing_object {
+            Some((id, offset)) => format!(
+                "The terminal ran out of memory on object {} at byte {}",
+                id.value(),
+                offset
+            ),
+            None => "The pool is accepted".to_string(),
+        },
+    });
+
+    let (_, report) = minify_pool(pool, &MinifyOptions::default());
+    let mut object_suggestions: HashMap<ObjectId, (String, usize)> = HashMap::new();
+    for id in &report.removed {
+        object_suggestions.insert(*id, ("Not used by any mask, remove it".to_string(), 0));
+    }
+    for (duplicate, kept) in &report.merged {
+        object_suggestions.insert(
+            *duplicate,
+            (format!("Same as object {}, merge them", kept.value()), 0),
+        );
+    }
+    for (id, saved) in &report.compressed {
+        object_suggestions.insert(*id, ("Run-length encode the picture".to_string(), *saved));
+    }
+
+    let mut offenders = Vec::new();
+    if !fits {
+        let mut objects: Vec<(&Object, usize)> = pool
+            .objects()
+            .iter()
+            .map(|object| (object, object.write().len()))
+            .collect();
+        objects.sort_by_key(|(_, size)| std::cmp::Reverse(*size));
+        let mut covered = 0;
+        for (object, size) in objects {
+            if covered >= pool_size - memory {
+                break;
+            }
+            covered += size;
+            let suggestion = object_suggestions
+                .get(&object.id())
+                .map(|(text, saved)| (text.clone(), if *saved == 0 { size } else { *saved }))
+                .or_else(|| picture_suggestion(object, size));
+            offenders.push(UploadOffender {
+                id: object.id(),
+                object_type: object.object_type(),
+                size,
+                suggestion,
+            });
+        }
+    }
+
+    let mut reductions = Vec::new();
+    let unused: usize = report
+        .removed
+        .iter()
+        .filter_map(|id| pool.object_by_id(*id))
+        .map(|object| object.write().len())
+        .sum();
+    if unused > 0 {
+        reductions.push((
+            format!("Remove {} unused objects", report.removed.len()),
+            unused,
+        ));
+    }
+    let merged: usize = report
+        .merged
+        .iter()
+        .filter_map(|(id, _)| pool.object_by_id(*id))
+        .map(|object| object.write().len())
+        .sum();
+    if merged > 0 {
+        reductions.push((
+            format!("Merge {} duplicate attribute objects", report.merged.len()),
+            merged,
+        ));
+    }
+    let compressed: usize = report.compressed.iter().map(|(_, saved)| saved).sum();
+    if compressed > 0 {
+        reductions.push((
+            format!("Run-length encode {} pictures", report.compressed.len()),
+            compressed,
+        ));
+    }
+
+    Some(UploadSimulation {
+        profile: profile.name.clone(),
+        pool_size,
+        memory,
+        steps,
+        failing_object,
+        offenders,
+        reductions,
+    })
+}
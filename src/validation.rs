@@ -0,0 +1,246 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::BTreeMap;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool, ObjectType};
+
+use crate::{
+    dangling_references, polygon_problems, project_tasks, validate_macros, EditorProject, TaskTag,
+};
+
+/// How serious a validation issue is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+/// A problem found in the project
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationIssue {
+    pub severity: Severity,
+
+    /// The object the issue is about, if any
+    pub object: Option<ObjectId>,
+    pub message: String,
+}
+
+impl ValidationIssue {
+    pub fn error(object: Option<ObjectId>, message: String) -> Self {
+        ValidationIssue {
+            severity: Severity::Error,
+            object,
+            message,
+        }
+    }
+
+    pub fn warning(object: Option<ObjectId>, message: String) -> Self {
+        ValidationIssue {
+            severity: Severity::Warning,
+            object,
+            message,
+        }
+    }
+}
+
+/// Run all validators on the project, errors first.
+/// The validators of the pool only run again after the pool or the terminal profile changed.
+pub fn validate_project(project: &EditorProject) -> Vec<ValidationIssue> {
+    let mut issues = project.pool_issues(|| validate_pool(project));
+
+    // Names and notes can change without changing the pool
+    if let Some(golden_pool) = &project.golden_pool {
+        golden_pool.validate(project, &mut issues);
+    }
+    let tasks = project_tasks(project);
+    if !tasks.is_empty() {
+        let fixmes = tasks.iter().filter(|t| t.tag == TaskTag::Fixme).count();
+        issues.push(ValidationIssue::warning(
+            None,
+            format!(
+                "{} outstanding TODO/FIXME items in object notes and comments ({} FIXME)",
+                tasks.len(),
+                fixmes
+            ),
+        ));
+    }
+
+    issues.sort_by_key(|issue| std::cmp::Reverse(issue.severity));
+    issues
+}
+
+/// Validators that only depend on the pool and the active terminal profile
+fn validate_pool(project: &EditorProject) -> Vec<ValidationIssue> {
+    let mut issues = Vec::new();
+    let pool = project.get_pool();
+    for dangling in dangling_references(pool) {
+        if let Some(object) = pool.object_by_id(dangling.object) {
+            issues.push(ValidationIssue::error(
+                Some(dangling.object),
+                format!(
+                    "{:?} {} references object {}, which does not exist",
+                    object.object_type(),
+                    dangling.object.value(),
+                    dangling.missing.value()
+                ),
+            ));
+        }
+    }
+    validate_soft_key_masks(project, &mut issues);
+    validate_macros(project.get_pool(), &mut issues);
+    for object in project
+        .get_pool()
+        .objects_by_type(ObjectType::OutputPolygon)
+    {
+        if let Object::OutputPolygon(polygon) = object {
+            for problem in polygon_problems(polygon) {
+                issues.push(ValidationIssue::warning(Some(polygon.id), problem));
+            }
+        }
+    }
+    if let Some(profile) = project.terminal_profile() {
+        let size = project.pool_size();
+        if let Some(budget) = profile.pool_size_budget.filter(|budget| size > *budget) {
+            issues.push(ValidationIssue::error(
+                None,
+                format!(
+                    "The object pool is {} bytes, {} allows {} bytes",
+                    size, profile.name, budget
+                ),
+            ));
+        }
+        if let Some(capabilities) = &profile.capabilities {
+            capabilities.validate(project, profile, &mut issues);
+        }
+    }
+    issues
+}
+
+/// Key object a soft key mask entry shows, following object pointers
+fn soft_key(pool: &ObjectPool, id: ObjectId) -> Option<&Object> {
+    match pool.object_by_id(id)? {
+        Object::ObjectPointer(pointer) => pointer.value.0.and_then(|id| soft_key(pool, id)),
+        Object::ExternalObjectPointer(pointer) => pointer
+            .default_object_id
+            .0
+            .and_then(|id| soft_key(pool, id)),
+        object => Some(object),
+    }
+}
+
+/// Check the soft key masks of the data and alarm masks and the keys on them
+fn validate_soft_key_masks(project: &EditorProject, issues: &mut Vec<ValidationIssue>) {
+    let pool = project.get_pool();
+    let key_limit = project
+        .terminal_profile()
+        .and_then(|profile| profile.soft_key_count.map(|count| (profile, count)));
+
+    // Masks using each soft key mask
+    let mut users: BTreeMap<u16, Vec<&Object>> = BTreeMap::new();
+    for mask in pool.objects_by_types(&[ObjectType::DataMask, ObjectType::AlarmMask]) {
+        let soft_key_mask = match mask {
+            Object::DataMask(o) => o.soft_key_mask,
+            Object::AlarmMask(o) => o.soft_key_mask,
+            _ => continue,
+        };
+        let Some(id) = soft_key_mask.0 else {
+            continue;
+        };
+        match pool.object_by_id(id) {
+            Some(Object::SoftKeyMask(_)) => users.entry(id.value()).or_default().push(mask),
+            Some(other) => issues.push(ValidationIssue::error(
+                Some(mask.id()),
+                format!(
+                    "Soft key mask reference {} of {:?} {} is a {:?}",
+                    id.value(),
+                    mask.object_type(),
+                    mask.id().value(),
+                    other.object_type()
+                ),
+            )),
+            None => issues.push(ValidationIssue::error(
+                Some(mask.id()),
+                format!(
+                    "Soft key mask {} of {:?} {} does not exist",
+                    id.value(),
+                    mask.object_type(),
+                    mask.id().value()
+                ),
+            )),
+        }
+    }
+
+    for soft_key_mask in pool.objects_by_type(ObjectType::SoftKeyMask) {
+        let Object::SoftKeyMask(o) = soft_key_mask else {
+            continue;
+        };
+        let id = soft_key_mask.id();
+
+        if let Some((profile, count)) = key_limit {
+            if o.objects.len() > count as usize {
+                issues.push(ValidationIssue::warning(
+                    Some(id),
+                    format!(
+                        "Soft key mask {} has {} keys, {} shows {} at once so the operator has to page",
+                        id.value(),
+                        o.objects.len(),
+                        profile.name,
+                        count
+                    ),
+                ));
+            }
+        }
+
+        // Key codes tell the ECU which key was pressed, so they must be unique per mask
+        let mut key_codes: BTreeMap<u8, ObjectId> = BTreeMap::new();
+        for key_id in &o.objects {
+            match soft_key(pool, *key_id) {
+                Some(Object::Key(key)) => {
+                    if let Some(other) = key_codes.insert(key.key_code, key.id) {
+                        if other != key.id {
+                            issues.push(ValidationIssue::error(
+                                Some(id),
+                                format!(
+                                    "Keys {} and {} on soft key mask {} share key code {}",
+                                    other.value(),
+                                    key.id.value(),
+                                    id.value(),
+                                    key.key_code
+                                ),
+                            ));
+                        }
+                    }
+                }
+                Some(other) => issues.push(ValidationIssue::error(
+                    Some(id),
+                    format!(
+                        "Soft key mask {} contains {:?} {}, only keys are allowed",
+                        id.value(),
+                        other.object_type(),
+                        other.id().value()
+                    ),
+                )),
+                None => {}
+            }
+        }
+
+        // Alarm soft keys typically acknowledge the alarm, data mask keys control the implement
+        let masks = users.get(&id.value()).map_or(&[][..], |m| m.as_slice());
+        let data_masks = masks
+            .iter()
+            .filter(|m| m.object_type() == ObjectType::DataMask)
+            .count();
+        if data_masks > 0 && data_masks < masks.len() {
+            issues.push(ValidationIssue::warning(
+                Some(id),
+                format!(
+                    "Soft key mask {} is shared by data masks and alarm masks, which usually expect different keys",
+                    id.value()
+                ),
+            ));
+        }
+    }
+}
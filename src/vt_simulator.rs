@@ -0,0 +1,77 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+//!
+//! Export for the AgIsoVirtualTerminal simulator, the reference VT server implementation, so the
+//! rendering of a pool can be cross-checked against it. The export is the pool and a settings
+//! file with the hardware the simulated terminal reports to the working set.
+//!
+//! The simulator has no command line or file interface to load a pool, it only shows pools a
+//! working set uploads over CAN. Starting it with the exported pool needs such an interface in
+//! AgIsoVirtualTerminal first.
+
+use crate::terminal_profile::DEFAULT_SOFT_KEY_COUNT;
+use crate::EditorProject;
+
+/// File name of the exported pool
+pub const SIMULATOR_POOL_FILE: &str = "object_pool.iop";
+
+/// File name of the exported settings
+pub const SIMULATOR_SETTINGS_FILE: &str = "vt_settings.xml";
+
+/// Hardware of the simulated terminal, taken from the active terminal profile
+#[derive(Debug, Clone, PartialEq)]
+pub struct SimulatorSettings {
+    pub data_mask_size: u16,
+    pub soft_key_size: (u16, u16),
+    pub physical_soft_keys: u8,
+    pub vt_version: u8,
+}
+
+impl SimulatorSettings {
+    pub fn from_project(project: &EditorProject) -> Self {
+        let profile = project.terminal_profile();
+        SimulatorSettings {
+            data_mask_size: profile
+                .and_then(|p| p.mask_size)
+                .unwrap_or(project.mask_size),
+            soft_key_size: profile
+                .and_then(|p| p.soft_key_size)
+                .unwrap_or(project.soft_key_size),
+            physical_soft_keys: profile
+                .and_then(|p| p.soft_key_count)
+                .unwrap_or(DEFAULT_SOFT_KEY_COUNT),
+            vt_version: project.pool_version.vt_version,
+        }
+    }
+
+    /// The settings file in the XML layout the simulator stores its settings in
+    pub fn to_xml(&self) -> String {
+        format!(
+            "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\n\
+             <Settings>\n  \
+             <Hardware DataMaskRenderAreaSize=\"{}\" SoftKeyDesignatorWidth=\"{}\" \
+             SoftKeyDesignatorHeight=\"{}\" NumberOfPhysicalSoftKeys=\"{}\"/>\n  \
+             <Compatibility VTVersion=\"{}\"/>\n\
+             </Settings>\n",
+            self.data_mask_size,
+            self.soft_key_size.0,
+            self.soft_key_size.1,
+            self.physical_soft_keys,
+            self.vt_version
+        )
+    }
+}
+
+/// The files of the export, by file name
+pub fn simulator_export(project: &EditorProject) -> Vec<(&'static str, Vec<u8>)> {
+    vec![
+        (SIMULATOR_POOL_FILE, project.get_pool().as_iop()),
+        (
+            SIMULATOR_SETTINGS_FILE,
+            SimulatorSettings::from_project(project)
+                .to_xml()
+                .into_bytes(),
+        ),
+    ]
+}
@@ -0,0 +1,114 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use crate::pool_version::{pool_hash, PoolVersion};
+
+/// A pool the simulated terminal stored in its non-volatile memory
+#[derive(Debug, Clone, PartialEq)]
+pub struct StoredVersion {
+    /// The label bytes the pool is stored under, the only thing the terminal compares
+    pub label: Vec<u8>,
+    pub size: usize,
+
+    /// Number of the upload that stored the pool
+    pub upload: usize,
+    hash: u64,
+}
+
+impl StoredVersion {
+    /// The label as text, without the padding
+    pub fn label_text(&self) -> String {
+        self.label
+            .iter()
+            .map(|byte| *byte as char)
+            .collect::<String>()
+            .trim_end()
+            .to_string()
+    }
+}
+
+/// What a terminal does when the working set connects with a pool
+#[derive(Debug, Clone, PartialEq)]
+pub enum ConnectOutcome {
+    /// The terminal has a pool stored under the label and loads it instead of the uploaded one.
+    /// It is stale when it differs from the pool that would have been uploaded.
+    LoadCached { upload: usize, stale: bool },
+    /// No pool is stored under the label, the pool is uploaded and stored
+    Upload,
+}
+
+impl ConnectOutcome {
+    pub fn describe(&self) -> String {
+        match self {
+            ConnectOutcome::LoadCached {
+                upload,
+                stale: false,
+            } => format!(
+                "The terminal loads the pool stored by upload {}, which is the same as this pool",
+                upload
+            ),
+            ConnectOutcome::LoadCached {
+                upload,
+                stale: true,
+            } => format!(
+                "The terminal loads the STALE pool stored by upload {}: it has the same version \
+                 label but a different content, so the changes are not shown",
+                upload
+            ),
+            ConnectOutcome::Upload => {
+                "No pool is stored under this label, the pool is uploaded and stored".to_string()
+            }
+        }
+    }
+}
+
+/// Non-volatile pool storage of a simulated terminal. Like a real terminal it only compares
+/// version labels, never the content of the pools.
+#[derive(Debug, Clone, Default)]
+pub struct SimulatedVtStorage {
+    pub versions: Vec<StoredVersion>,
+    uploads: usize,
+    pub log: Vec<String>,
+}
+
+impl SimulatedVtStorage {
+    /// What the terminal would do when a working set connects with this pool and version
+    pub fn preview(&self, version: &PoolVersion, iop: &[u8]) -> ConnectOutcome {
+        let label = version.label_bytes();
+        match self.versions.iter().find(|stored| stored.label == label) {
+            Some(stored) => ConnectOutcome::LoadCached {
+                upload: stored.upload,
+                stale: stored.hash != pool_hash(iop),
+            },
+            None => ConnectOutcome::Upload,
+        }
+    }
+
+    /// Connect a working set with this pool and version, storing the pool if it is uploaded
+    pub fn connect(&mut self, version: &PoolVersion, iop: &[u8]) -> ConnectOutcome {
+        let outcome = self.preview(version, iop);
+        if outcome == ConnectOutcome::Upload {
+            self.uploads += 1;
+            self.versions.push(StoredVersion {
+                label: version.label_bytes(),
+                size: iop.len(),
+                upload: self.uploads,
+                hash: pool_hash(iop),
+            });
+        }
+        self.log
+            .push(format!("\"{}\": {}", version.label, outcome.describe()));
+        outcome
+    }
+
+    /// Delete a stored version, like the Delete Version command
+    pub fn delete(&mut self, label: &[u8]) {
+        self.versions.retain(|stored| stored.label != label);
+    }
+
+    pub fn clear(&mut self) {
+        self.versions.clear();
+        self.log.push("Cleared the storage".to_string());
+    }
+}
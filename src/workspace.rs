@@ -0,0 +1,322 @@
+//! Copyright 2024 - The Open-Agriculture Developers
+//! SPDX-License-Identifier: GPL-3.0-or-later
+//! Authors: Daan Steenbergen
+
+use std::collections::HashSet;
+
+use ag_iso_stack::object_pool::object::Object;
+use ag_iso_stack::object_pool::{ObjectId, ObjectPool};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+
+use crate::colour_roles::ColourRoles;
+use crate::pool_diff::diff_objects;
+use crate::{ChangeCategory, EditorProject, ObjectInfo};
+
+/// Workspace file format version. Version 1 stored the project files as arrays of bytes.
+const WORKSPACE_FILE_VERSION: u32 = 2;
+
+/// A pool of a workspace, stored as a project file
+#[derive(Serialize, Deserialize, Clone)]
+pub struct WorkspaceMember {
+    pub name: String,
+
+    /// The project file of the pool, embedded as JSON
+    project: Value,
+}
+
+/// AgIsoTerminalWorkspace file format (.aitw)
+/// Groups the related pools of a product, e.g. the main implement pool, an auxiliary control only
+/// pool and a low resolution variant, into a single file
+#[derive(Serialize, Deserialize, Clone)]
+pub struct Workspace {
+    version: u32,
+
+    pub name: String,
+    pub members: Vec<WorkspaceMember>,
+
+    /// Index of the member that is open in the editor
+    pub active: usize,
+
+    /// Colour roles and themes shared by all pools
+    #[serde(default)]
+    pub colour_roles: ColourRoles,
+
+    /// IDs of template objects that must be defined the same in every pool
+    #[serde(default)]
+    pub shared_objects: Vec<u16>,
+}
+
+impl Workspace {
+    /// Create a workspace with the open project as its first pool
+    pub fn new(name: String, project: &EditorProject) -> Result<Self, String> {
+        let mut workspace = Workspace {
+            version: WORKSPACE_FILE_VERSION,
+            name,
+            members: Vec::new(),
+            active: 0,
+            colour_roles: project.colour_roles.clone(),
+            shared_objects: Vec::new(),
+        };
+        workspace.add_member("Main pool".to_string(), project)?;
+        Ok(workspace)
+    }
+
+    /// Add a pool to the workspace
+    pub fn add_member(&mut self, name: String, project: &EditorProject) -> Result<(), String> {
+        self.members.push(WorkspaceMember {
+            name,
+            project: save(project)?,
+        });
+        Ok(())
+    }
+
+    /// Remove a pool, the open pool can not be removed
+    pub fn remove_member(&mut self, index: usize) {
+        if index == self.active || index >= self.members.len() {
+            return;
+        }
+        self.members.remove(index);
+        if index < self.active {
+            self.active -= 1;
+        }
+    }
+
+    /// Store the open project in the workspace, its colour roles become the shared ones
+    pub fn store(&mut self, project: &EditorProject) -> Result<(), String> {
+        let member = self
+            .members
+            .get_mut(self.active)
+            .ok_or("The workspace has no open pool")?;
+        member.project = save(project)?;
+        self.colour_roles = project.colour_roles.clone();
+        Ok(())
+    }
+
+    /// Load a pool of the workspace with the shared colour roles
+    pub fn load(&self, index: usize) -> Result<EditorProject, String> {
+        let member = self
+            .members
+            .get(index)
+            .ok_or_else(|| format!("The workspace has no pool {}", index))?;
+        let data = serde_json::to_vec(&member.project)
+            .map_err(|e| format!("Failed to read the project of {}: {}", member.name, e))?;
+        let mut project = EditorProject::load_project(data)?;
+        project.colour_roles = self.colour_roles.clone();
+        Ok(project)
+    }
+
+    /// Store the open project and open another pool of the workspace
+    pub fn switch_to(
+        &mut self,
+        project: &EditorProject,
+        index: usize,
+    ) -> Result<EditorProject, String> {
+        self.store(project)?;
+        let project = self.load(index)?;
+        self.active = index;
+        Ok(project)
+    }
+
+    /// The pools that are not open, with their names
+    fn other_members(&self) -> Vec<(String, Result<EditorProject, String>)> {
+        self.members
+            .iter()
+            .enumerate()
+            .filter(|(index, _)| *index != self.active)
+            .map(|(index, member)| (member.name.clone(), self.load(index)))
+            .collect()
+    }
+
+    /// Check that the shared objects are defined the same in every pool and that no pool uses
+    /// the name of a shared object for another ID
+    pub fn check_consistency(&self, project: &EditorProject) -> Vec<String> {
+        let mut problems = Vec::new();
+        let active_name = self
+            .members
+            .get(self.active)
+            .map_or("Open pool", |m| m.name.as_str());
+
+        for (name, other) in self.other_members() {
+            let other = match other {
+                Ok(other) => other,
+                Err(e) => {
+                    problems.push(format!("{}: {}", name, e));
+                    continue;
+                }
+            };
+            for id in self.shared_ids() {
+                check_shared_object(project, active_name, &other, &name, id, &mut problems);
+            }
+        }
+        problems
+    }
+
+    /// Shared object IDs that are valid object IDs
+    pub fn shared_ids(&self) -> Vec<ObjectId> {
+        self.shared_objects
+            .iter()
+            .filter_map(|id| ObjectId::new(*id).ok())
+            .collect()
+    }
+
+    /// The shared objects of a pool with all objects they reference
+    fn shared_tree<'a>(&self, pool: &'a ObjectPool) -> Vec<&'a Object> {
+        let mut stack = self.shared_ids();
+        let mut visited = HashSet::new();
+        let mut objects = Vec::new();
+        while let Some(id) = stack.pop() {
+            if !visited.insert(id) {
+                continue;
+            }
+            if let Some(object) = pool.object_by_id(id) {
+                stack.extend(object.referenced_objects());
+                objects.push(object);
+            }
+        }
+        objects
+    }
+
+    /// Copy the shared objects of the open project, with the objects they reference and their
+    /// names, to all other pools. Returns the number of pools that were changed.
+    pub fn sync_shared_objects(&mut self, project: &EditorProject) -> Result<usize, String> {
+        let pool = project.get_pool();
+        let shared = self.shared_tree(pool);
+
+        let mut changed = 0;
+        for index in 0..self.members.len() {
+            if index == self.active {
+                continue;
+            }
+            let mut other = self.load(index)?;
+            for object in &shared {
+                let mut other_pool = other.get_mut_pool().borrow_mut();
+                other_pool.remove(object.id());
+                other_pool.add((*object).clone());
+                drop(other_pool);
+                let name = project.get_object_info(object).name;
+                let mut object_info = other.object_info.borrow_mut();
+                let info = object_info
+                    .entry(object.id())
+                    .or_insert_with(|| ObjectInfo::new(object));
+                info.name = name;
+            }
+            other.describe_change("Sync shared workspace objects", ChangeCategory::BulkChange);
+            if other.update_pool() {
+                self.members[index].project = save(&other)?;
+                changed += 1;
+            }
+        }
+        Ok(changed)
+    }
+
+    /// Serialize the workspace to JSON bytes
+    pub fn to_bytes(&self) -> Result<Vec<u8>, serde_json::Error> {
+        serde_json::to_vec_pretty(self)
+    }
+
+    /// Deserialize a workspace from JSON bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, String> {
+        let mut workspace: Workspace = serde_json::from_slice(bytes)
+            .map_err(|e| format!("Failed to parse workspace file: {}", e))?;
+        if workspace.members.is_empty() {
+            return Err("The workspace has no pools".to_string());
+        }
+        for member in &mut workspace.members {
+            if let Value::Array(bytes) = &member.project {
+                let bytes: Option<Vec<u8>> = bytes
+                    .iter()
+                    .map(|byte| byte.as_u64().and_then(|byte| u8::try_from(byte).ok()))
+                    .collect();
+                member.project = bytes
+                    .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+                    .ok_or_else(|| format!("The project of {} is damaged", member.name))?;
+            }
+        }
+        workspace.version = WORKSPACE_FILE_VERSION;
+        workspace.active = workspace.active.min(workspace.members.len() - 1);
+        Ok(workspace)
+    }
+}
+
+fn save(project: &EditorProject) -> Result<Value, String> {
+    project
+        .save_project()
+        .and_then(|data| serde_json::from_slice(&data))
+        .map_err(|e| format!("Failed to save project: {}", e))
+}
+
+/// Compare a shared object of the open project with the object in another pool
+fn check_shared_object(
+    project: &EditorProject,
+    project_name: &str,
+    other: &EditorProject,
+    other_name: &str,
+    id: ObjectId,
+    problems: &mut Vec<String>,
+) {
+    let pool = project.get_pool();
+    let Some(object) = pool.object_by_id(id) else {
+        problems.push(format!(
+            "Shared object {} is missing in {}",
+            id.value(),
+            project_name
+        ));
+        return;
+    };
+    let name = project.get_object_info(object).get_name(object);
+
+    let other_pool = other.get_pool();
+    let Some(other_object) = other_pool.object_by_id(id) else {
+        match find_by_name(other, other_pool, &name) {
+            Some(other_id) => problems.push(format!(
+                "{} uses ID {} for \"{}\", {} uses ID {}",
+                other_name,
+                other_id.value(),
+                name,
+                project_name,
+                id.value()
+            )),
+            None => problems.push(format!(
+                "Shared object \"{}\" ({}) is missing in {}",
+                name,
+                id.value(),
+                other_name
+            )),
+        }
+        return;
+    };
+
+    let other_object_name = other.get_object_info(other_object).get_name(other_object);
+    if other_object_name != name {
+        problems.push(format!(
+            "Shared object {} is named \"{}\" in {} and \"{}\" in {}",
+            id.value(),
+            name,
+            project_name,
+            other_object_name,
+            other_name
+        ));
+    }
+    let changes = diff_objects(object, other_object);
+    if !changes.is_empty() {
+        problems.push(format!(
+            "Shared object \"{}\" ({}) differs in {}: {}",
+            name,
+            id.value(),
+            other_name,
+            changes
+                .iter()
+                .map(|change| change.attribute.as_str())
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+    }
+}
+
+fn find_by_name(project: &EditorProject, pool: &ObjectPool, name: &str) -> Option<ObjectId> {
+    pool.objects()
+        .iter()
+        .find(|o| project.get_object_info(o).get_name(o) == name)
+        .map(|o| o.id())
+}